@@ -1,4 +1,4 @@
-//! Build script to generate our glyph name lookup table.
+//! Build script to generate our glyph name and unicode data lookup tables.
 
 use std::convert::TryFrom;
 use std::env;
@@ -8,13 +8,17 @@ use std::path::Path;
 use std::str::FromStr;
 
 const GLYPH_NAMES_FILE: &str = "glyph_names_codegen.rs";
+const UNICODE_DATA_FILE: &str = "unicode_data_codegen.rs";
 const THEME_PATH_FILE: &str = "theme_path.rs";
 const DEFAULT_THEME_PATH: &str = "resources/default.theme";
 
 fn main() {
     println!("cargo:rerun-if-env-changed=RB_THEME_PATH");
     println!("cargo:rerun-if-changed=../resources/aglfn.txt");
+    println!("cargo:rerun-if-changed=../resources/unicode_data.txt");
+    println!("cargo:rerun-if-changed=../resources/blocks.txt");
     generate_glyph_names();
+    generate_unicode_data();
     generate_theme_path();
 }
 
@@ -57,6 +61,76 @@ fn generate_glyph_names() {
     .unwrap();
 }
 
+fn generate_unicode_data() {
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join(UNICODE_DATA_FILE);
+    let mut file = BufWriter::new(File::create(&path).unwrap());
+    let data = include_str!("../resources/unicode_data.txt");
+    let blocks = include_str!("../resources/blocks.txt");
+
+    // fields: code point, name, general category, bidi class, decomposition
+    let mut entries = data
+        .lines()
+        .filter(|l| !l.starts_with('#'))
+        .map(|line| {
+            let fields = line.split(';').collect::<Vec<_>>();
+            assert!(fields.len() >= 6, "bad unicode data line '{}'", line);
+            let chr = u32::from_str_radix(fields[0], 16).unwrap();
+            (
+                char::try_from(chr).unwrap(),
+                fields[1],
+                fields[2],
+                fields[4],
+                fields[5],
+            )
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    let formatted = entries
+        .iter()
+        .map(|(chr, name, category, bidi, decomposition)| {
+            format!(
+                "('{}', {:?}, {:?}, {:?}, {:?})",
+                chr.escape_default(),
+                name,
+                category,
+                bidi,
+                decomposition
+            )
+        })
+        .collect::<Vec<_>>();
+    writeln!(
+        &mut file,
+        "static UNICODE_DATA: &[(char, &str, &str, &str, &str)] = &[\n{}];\n",
+        formatted.join(",\n")
+    )
+    .unwrap();
+
+    let mut blocks = blocks
+        .lines()
+        .filter(|l| !l.starts_with('#') && !l.trim().is_empty())
+        .map(|line| {
+            let mut split = line.split(';');
+            let range = split.next().unwrap();
+            let name = split.next().unwrap().trim();
+            let mut range = range.split("..");
+            let start = u32::from_str_radix(range.next().unwrap(), 16).unwrap();
+            let end = u32::from_str_radix(range.next().unwrap(), 16).unwrap();
+            (start, end, name)
+        })
+        .collect::<Vec<_>>();
+    blocks.sort_unstable();
+    let formatted = blocks
+        .iter()
+        .map(|(start, end, name)| format!("(0x{:X}, 0x{:X}, {:?})", start, end, name))
+        .collect::<Vec<_>>();
+    writeln!(
+        &mut file,
+        "static UNICODE_BLOCKS: &[(u32, u32, &str)] = &[\n{}];",
+        formatted.join(",\n")
+    )
+    .unwrap();
+}
+
 struct NameEntry {
     chr: char,
     postscript_name: String,
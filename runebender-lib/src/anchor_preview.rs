@@ -0,0 +1,100 @@
+//! Positioning one glyph against another by their anchors.
+//!
+//! This lets the editor show a mark on its base glyph (or a base under its
+//! mark) the way the compiled 'mark' feature would, without compiling.
+
+use druid::kurbo::Vec2;
+use norad::{Glyph, GlyphName, Ufo};
+
+/// How another glyph is placed relative to the glyph being edited.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Attachment {
+    /// The name of the anchor on the base, like `top`.
+    pub anchor: String,
+    /// The offset of the other glyph, in design space.
+    pub offset: Vec2,
+}
+
+/// Find how `other` attaches to `glyph`.
+///
+/// If `glyph` has an anchor like `top` and `other` has the matching `_top`,
+/// `other` is a mark placed on `glyph`; if `glyph` has `_top` and `other`
+/// has `top`, `other` is a base placed under `glyph`. The first matching
+/// anchor on `glyph` is used.
+pub(crate) fn attachment(glyph: &Glyph, other: &Glyph) -> Option<Attachment> {
+    named_anchors(glyph).find_map(|(name, pos)| {
+        let (target, base_name) = match name.strip_prefix('_') {
+            Some(class) => (class.to_string(), class),
+            None => (format!("_{}", name), name),
+        };
+        let other_pos = named_anchors(other)
+            .find(|(other_name, _)| *other_name == target)
+            .map(|(_, pos)| pos)?;
+        Some(Attachment {
+            anchor: base_name.to_string(),
+            offset: pos - other_pos,
+        })
+    })
+}
+
+/// The glyphs in the font that can be attached to `glyph`, in name order.
+pub(crate) fn attachable_glyphs(ufo: &Ufo, glyph: &Glyph) -> Vec<GlyphName> {
+    let mut names: Vec<_> = ufo
+        .iter_names()
+        .filter(|name| *name != glyph.name)
+        .filter(|name| {
+            ufo.get_glyph(name)
+                .map(|other| attachment(glyph, other).is_some())
+                .unwrap_or(false)
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn named_anchors(glyph: &Glyph) -> impl Iterator<Item = (&str, Vec2)> {
+    glyph.anchors.iter().flatten().filter_map(|anchor| {
+        let name = anchor.name.as_deref()?;
+        Some((name, Vec2::new(anchor.x as f64, anchor.y as f64)))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norad::glyph::Anchor;
+
+    fn glyph_with_anchors(name: &str, anchors: &[(&str, f32, f32)]) -> Glyph {
+        let mut glyph = Glyph::new_named(name);
+        glyph.anchors = Some(
+            anchors
+                .iter()
+                .map(|(name, x, y)| Anchor {
+                    name: Some(name.to_string()),
+                    x: *x,
+                    y: *y,
+                    color: None,
+                    identifier: None,
+                })
+                .collect(),
+        );
+        glyph
+    }
+
+    #[test]
+    fn mark_to_base() {
+        let base = glyph_with_anchors("a", &[("bottom", 250.0, 0.0), ("top", 250.0, 500.0)]);
+        let mark = glyph_with_anchors("acutecomb", &[("_top", 100.0, 450.0)]);
+
+        let on_base = attachment(&base, &mark).unwrap();
+        assert_eq!(on_base.anchor, "top");
+        assert_eq!(on_base.offset, Vec2::new(150.0, 50.0));
+
+        let under_mark = attachment(&mark, &base).unwrap();
+        assert_eq!(under_mark.anchor, "top");
+        assert_eq!(under_mark.offset, Vec2::new(-150.0, -50.0));
+
+        let other = glyph_with_anchors("cedillacomb", &[("_cedilla", 0.0, 0.0)]);
+        assert!(attachment(&base, &other).is_none());
+    }
+}
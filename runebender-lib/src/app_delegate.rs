@@ -1,64 +1,527 @@
 //! The `AppDelegate`.
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use druid::{
-    AppDelegate, Command, DelegateCtx, Handled, Selector, Target, Widget, WindowDesc, WindowId,
+    AppDelegate, Application, ClipboardFormat, Command, DelegateCtx, Handled, Selector, SingleUse,
+    Target, Widget, WindowDesc, WindowId,
 };
 
 use druid::kurbo::Line;
 use druid::lens::LensExt;
 use druid::text::format::ParseFormatter;
-use druid::widget::{prelude::*, Flex, Label, Painter, TextBox, WidgetExt};
+use druid::widget::{prelude::*, Checkbox, Flex, Label, Painter, TextBox, WidgetExt};
 use norad::{GlyphName, Ufo};
 
 use crate::consts;
-use crate::data::{AppState, PreviewSession, PreviewState, Workspace};
-use crate::edit_session::{EditSession, SessionId};
-use crate::widgets::{Editor, EditorController, Preview, ScrollZoom};
+use crate::data::{AppState, ExportOptions, PreviewSession, PreviewState, SaveStatus, Workspace};
+use crate::design_space::Precision;
+use crate::edit_session::SessionId;
+#[cfg(feature = "notify")]
+use crate::file_watcher::FontWatcher;
+use crate::reference_font::ReferenceFont;
+use crate::widgets::{EditorTabs, ModalHost, Preview};
 
 pub const EDIT_GLYPH: Selector<GlyphName> = Selector::new("runebender.open-editor-with-glyph");
 
 #[derive(Debug, Default)]
-pub struct Delegate;
+pub struct Delegate {
+    /// The window for editing feature code, if it is open.
+    features_window: Option<WindowId>,
+    /// The window with a tab for each glyph being edited, if it is open.
+    editor_window: Option<WindowId>,
+    /// The window showing the output of scripts, if it is open.
+    script_console: Option<WindowId>,
+    /// The window listing problems found in the font, if it is open.
+    audit_window: Option<WindowId>,
+    /// The window listing contours found by 'find shape', if it is open.
+    shape_window: Option<WindowId>,
+    /// The window reporting the font's coverage of languages, if it is open.
+    coverage_window: Option<WindowId>,
+    /// The first window, which shows the glyph grid.
+    main_window: Option<WindowId>,
+    /// Watches the font's directory for changes made by other programs.
+    #[cfg(feature = "notify")]
+    font_watcher: Option<FontWatcher>,
+    /// Incremented whenever the watcher is replaced, so that changes it
+    /// reported for a previous font are ignored.
+    watch_generation: u64,
+    /// A close, quit or replacement of the font waiting for the user to
+    /// decide what to do with unsaved changes.
+    pending_close: Option<CloseRequest>,
+    /// Set when the pending close should happen once the font is saved.
+    close_after_saving: bool,
+    /// Set when the user chose to discard unsaved changes, so that closing
+    /// is no longer interrupted, until another font is opened.
+    discard_changes: bool,
+}
+
+/// A request to close the font, which needs confirmation if there are
+/// unsaved changes.
+#[derive(Debug, Clone)]
+enum CloseRequest {
+    /// Close the main window.
+    Window(WindowId),
+    Quit,
+    /// Replace the font by running a command that opens or creates another.
+    Replace(Command),
+}
+
+impl Delegate {
+    /// Open the glyph `name` in the editor window, creating the window if
+    /// necessary.
+    fn edit_glyph(&mut self, ctx: &mut DelegateCtx, data: &mut AppState, name: &GlyphName) {
+        data.workspace.open_tab(name);
+        match self.editor_window {
+            Some(id) => {
+                ctx.submit_command(druid::commands::SHOW_WINDOW.to(id));
+            }
+            None => {
+                let new_win = WindowDesc::new(make_editor_window())
+                    .title(|d: &AppState, _: &_| {
+                        let workspace = &d.workspace;
+                        match workspace
+                            .active_tab
+                            .and_then(|id| workspace.sessions.get(&id))
+                        {
+                            Some(session) if session.is_dirty() => {
+                                format!("• {}", session.name)
+                            }
+                            Some(session) => session.name.to_string(),
+                            None => "Editor".to_string(),
+                        }
+                    })
+                    .window_size(Size::new(900.0, 800.0))
+                    .menu(crate::menus::make_menu);
+                self.editor_window = Some(new_win.id);
+                ctx.new_window(new_win);
+            }
+        }
+        let id = self.editor_window.unwrap();
+        Arc::make_mut(&mut data.workspace.open_glyphs).insert(name.clone(), id);
+    }
+
+    /// Open the font at `path`, replacing the current font.
+    fn open_font(&mut self, ctx: &mut DelegateCtx, data: &mut AppState, path: &Path) {
+        // the new font has its own unsaved changes to ask about
+        self.discard_changes = false;
+        match Ufo::load(path) {
+            Ok(ufo) => {
+                if let Some(id) = self.editor_window {
+                    ctx.submit_command(druid::commands::CLOSE_WINDOW.to(id));
+                }
+                data.workspace.set_file(ufo, path.to_owned());
+                data.workspace
+                    .build_paths_in_background(ctx.get_external_handle());
+                data.workspace.refresh_vcs_status(ctx.get_external_handle());
+                self.watch_font(ctx, &data.workspace);
+                data.settings.add_recent_file(path);
+                data.settings.save();
+            }
+            Err(e) => log::error!("failed to open file {:?}: '{:?}'", path, e),
+        }
+    }
+
+    /// If `cmd` would close the font while it has unsaved changes, the
+    /// request that should be confirmed first, and the window to ask in.
+    fn unconfirmed_close(
+        &self,
+        cmd: &Command,
+        target: Target,
+        workspace: &Workspace,
+    ) -> Option<(CloseRequest, WindowId)> {
+        let main_window = self.main_window?;
+        let request = if cmd.is(druid::commands::QUIT_APP) {
+            (
+                CloseRequest::Quit,
+                self.editor_window.unwrap_or(main_window),
+            )
+        } else if cmd.is(druid::commands::CLOSE_WINDOW) && target == Target::Window(main_window) {
+            (CloseRequest::Window(main_window), main_window)
+        } else if cmd.is(druid::commands::OPEN_FILE)
+            || cmd.is(consts::cmd::OPEN_RECENT)
+            || cmd.is(consts::cmd::NEW_FONT)
+        {
+            let window = match target {
+                Target::Window(id) => id,
+                _ => main_window,
+            };
+            (CloseRequest::Replace(cmd.clone()), window)
+        } else {
+            return None;
+        };
+        if self.discard_changes || !workspace.is_dirty() {
+            return None;
+        }
+        Some(request)
+    }
+
+    /// Carry out the close the user confirmed.
+    fn finish_close(&mut self, ctx: &mut DelegateCtx) {
+        self.close_after_saving = false;
+        match self.pending_close.take() {
+            Some(CloseRequest::Window(id)) => {
+                ctx.submit_command(druid::commands::CLOSE_WINDOW.to(id))
+            }
+            Some(CloseRequest::Quit) => ctx.submit_command(druid::commands::QUIT_APP),
+            Some(CloseRequest::Replace(cmd)) => ctx.submit_command(cmd),
+            None => (),
+        }
+    }
+
+    /// Start watching the font's directory, replacing any existing watcher.
+    fn watch_font(&mut self, ctx: &mut DelegateCtx, workspace: &Workspace) {
+        self.watch_generation += 1;
+        #[cfg(feature = "notify")]
+        {
+            self.font_watcher = workspace.font.path.as_ref().and_then(|path| {
+                FontWatcher::new(path, ctx.get_external_handle(), self.watch_generation)
+                    .map_err(|e| log::warn!("failed to watch {:?}: '{}'", path, e))
+                    .ok()
+            });
+        }
+        #[cfg(not(feature = "notify"))]
+        let _ = (ctx, workspace);
+    }
+
+    /// Load the font from disk on a background thread, to see what another
+    /// program changed; the result is sent back as `EXTERNAL_CHANGES_LOADED`.
+    fn check_external_changes(&mut self, ctx: &mut DelegateCtx, workspace: &Workspace) {
+        if matches!(workspace.save_status, SaveStatus::Saving) {
+            // these are probably our own writes
+            return;
+        }
+        let path = match workspace.font.path.clone() {
+            Some(path) => path,
+            None => return,
+        };
+        let sink = ctx.get_external_handle();
+        let generation = self.watch_generation;
+        std::thread::spawn(move || {
+            let ufo = match Ufo::load(&path) {
+                Ok(ufo) => ufo,
+                Err(e) => {
+                    log::warn!("failed to reload {:?}: '{:?}'", path, e);
+                    return;
+                }
+            };
+            if let Err(e) = sink.submit_command(
+                consts::cmd::EXTERNAL_CHANGES_LOADED,
+                Box::new((generation, SingleUse::new(ufo))),
+                Target::Auto,
+            ) {
+                log::error!("failed to report external changes: '{}'", e);
+            }
+        });
+    }
+
+    /// Ask the user whether to reload glyphs changed by another program,
+    /// given `ufo`, the font as it is on disk.
+    fn ask_about_external_changes(
+        &mut self,
+        ctx: &mut DelegateCtx,
+        workspace: &mut Workspace,
+        ufo: Ufo,
+    ) {
+        if matches!(workspace.save_status, SaveStatus::Saving) {
+            return;
+        }
+        let already_asked = workspace.external_changes.is_some();
+        if workspace.note_external_changes(ufo) && !already_asked {
+            // the modal must be sent to a single window
+            if let Some(window) = self.editor_window.or(self.main_window) {
+                ctx.submit_command(
+                    ModalHost::make_modal_command(crate::widgets::external_changes_panel)
+                        .to(window),
+                );
+            }
+        }
+    }
+}
 
 impl AppDelegate<AppState> for Delegate {
     fn command(
         &mut self,
         ctx: &mut DelegateCtx,
-        _target: Target,
+        target: Target,
         cmd: &Command,
         data: &mut AppState,
         _env: &Env,
     ) -> Handled {
-        if let Some(info) = cmd.get(druid::commands::OPEN_FILE) {
-            match Ufo::load(info.path()) {
-                Ok(ufo) => data.workspace.set_file(ufo, info.path().to_owned()),
-                Err(e) => log::error!("failed to open file {:?}: '{:?}'", info.path(), e),
-            };
+        if let Some((request, window)) = self.unconfirmed_close(cmd, target, &data.workspace) {
+            self.pending_close = Some(request);
+            self.close_after_saving = false;
+            ctx.submit_command(
+                ModalHost::make_modal_command(crate::widgets::unsaved_changes_panel).to(window),
+            );
             Handled::Yes
-        } else if cmd.is(druid::commands::SAVE_FILE) {
-            if let Err(e) = data.workspace.save() {
-                log::error!("saving failed: '{}'", e);
+        } else if cmd.is(consts::cmd::CLOSE_AFTER_SAVING) {
+            self.close_after_saving = true;
+            Handled::Yes
+        } else if cmd.is(consts::cmd::CLOSE_WITHOUT_SAVING) {
+            self.discard_changes = true;
+            self.finish_close(ctx);
+            Handled::Yes
+        } else if cmd.is(consts::cmd::CANCEL_CLOSE) {
+            self.pending_close = None;
+            self.close_after_saving = false;
+            Handled::Yes
+        } else if let Some(info) = cmd.get(druid::commands::OPEN_FILE) {
+            self.open_font(ctx, data, info.path());
+            Handled::Yes
+        } else if let Some(font) = cmd.get(consts::cmd::NEW_FONT) {
+            if let Some(id) = self.editor_window {
+                ctx.submit_command(druid::commands::CLOSE_WINDOW.to(id));
+            }
+            data.workspace.new_font(font.build());
+            self.watch_font(ctx, &data.workspace);
+            self.discard_changes = false;
+            Handled::Yes
+        } else if let Some(path) = cmd.get(consts::cmd::OPEN_RECENT) {
+            self.open_font(ctx, data, path);
+            Handled::Yes
+        } else if let Some(paths) = cmd.get(consts::cmd::FILES_DROPPED) {
+            match paths.iter().find_map(|path| font_path_for_file(path)) {
+                // opened like a recent file, so it is added to the recent files
+                Some(path) => ctx.submit_command(consts::cmd::OPEN_RECENT.with(path)),
+                None => log::warn!("no .ufo or .designspace among dropped files {:?}", paths),
             }
             Handled::Yes
+        } else if cmd.is(consts::cmd::CLEAR_RECENT_FILES) {
+            data.settings.recent_files.clear();
+            data.settings.save();
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_REOPEN_LAST_FONT) {
+            data.settings.reopen_last_font = !data.settings.reopen_last_font;
+            data.settings.save();
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_FLUSH_ON_CLOSE) {
+            data.settings.flush_on_close = !data.settings.flush_on_close;
+            data.workspace.flush_on_close = data.settings.flush_on_close;
+            data.settings.save();
+            Handled::Yes
+        } else if let Some(options) = cmd.get(consts::cmd::SET_TOOL_OPTIONS) {
+            data.workspace.tool_options = *options;
+            data.settings.tool_options = *options;
+            data.settings.save();
+            Handled::Yes
+        } else if cmd.is(druid::commands::SAVE_FILE) {
+            data.workspace.save_in_background(ctx.get_external_handle());
+            Handled::Yes
         } else if let Some(info) = cmd.get(druid::commands::SAVE_FILE_AS) {
-            Arc::make_mut(&mut data.workspace.font).path = Some(info.path().into());
-            if let Err(e) = data.workspace.save() {
-                log::error!("saving failed: '{}'", e);
+            data.workspace
+                .save_as_in_background(info.path(), ctx.get_external_handle());
+            if let Some(path) = data.workspace.font.path.as_ref() {
+                data.settings.add_recent_file(path);
+                data.settings.save();
+            }
+            Handled::Yes
+        } else if cmd.is(consts::cmd::REVERT_TO_SAVED) {
+            if let Err(e) = data.workspace.revert_to_saved() {
+                log::error!("revert failed: '{}'", e);
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::SAVE_COPY) {
+            data.workspace
+                .save_copy_in_background(info.path(), ctx.get_external_handle());
+            Handled::Yes
+        } else if let Some(result) = cmd.get(consts::cmd::SAVE_FINISHED) {
+            data.workspace
+                .save_finished(result, ctx.get_external_handle());
+            // the font may have been saved to a new location
+            self.watch_font(ctx, &data.workspace);
+            if self.close_after_saving {
+                match data.workspace.save_status {
+                    SaveStatus::Idle => self.finish_close(ctx),
+                    // another save was started; wait for it
+                    SaveStatus::Saving => (),
+                    SaveStatus::Failed(_) => {
+                        self.pending_close = None;
+                        self.close_after_saving = false;
+                    }
+                }
+            }
+            Handled::Yes
+        } else if let Some(generation) = cmd.get(consts::cmd::EXTERNAL_CHANGES) {
+            if *generation == self.watch_generation {
+                self.check_external_changes(ctx, &data.workspace);
+            }
+            Handled::Yes
+        } else if let Some((generation, ufo)) = cmd.get(consts::cmd::EXTERNAL_CHANGES_LOADED) {
+            // a font opened since the load started isn't compared with it
+            if *generation == self.watch_generation {
+                if let Some(ufo) = ufo.take() {
+                    self.ask_about_external_changes(ctx, &mut data.workspace, ufo);
+                }
+            }
+            Handled::Yes
+        } else if cmd.is(consts::cmd::RELOAD_EXTERNAL_CHANGES) {
+            let kept = data.workspace.reload_external_changes();
+            if !kept.is_empty() {
+                log::warn!("kept unsaved edits to {} glyphs", kept.len());
+            }
+            Handled::Yes
+        } else if let Some(message) = cmd.get(consts::cmd::COMMIT_CHANGES) {
+            if let Err(e) = data.workspace.commit(message, ctx.get_external_handle()) {
+                log::error!("commit failed: '{}'", e);
+            }
+            Handled::Yes
+        } else if let Some((path, status)) = cmd.get(consts::cmd::VCS_STATUS) {
+            data.workspace.set_vcs_status(path, status.clone());
+            Handled::Yes
+        } else if let Some(built) = cmd.get(consts::cmd::PATHS_BUILT) {
+            data.workspace.set_built_paths(built);
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_FRACTIONAL_COORDINATES) {
+            let fractional = !data.workspace.precision.is_fractional();
+            data.workspace.precision = Precision::from_fractional(fractional);
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_TUNNI_LINES) {
+            data.workspace.show_tunni_lines = !data.workspace.show_tunni_lines;
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_LOUPE) {
+            data.workspace.show_loupe = !data.workspace.show_loupe;
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_CONTOUR_LIST) {
+            data.workspace.show_contour_list = !data.workspace.show_contour_list;
+            Handled::Yes
+        } else if cmd.is(consts::cmd::TOGGLE_KEYBOARD_EDITING) {
+            data.workspace.keyboard_editing = !data.workspace.keyboard_editing;
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::EXPORT_REMOVE_OVERLAPS) {
+            let options = ExportOptions {
+                remove_overlaps: true,
+                ..Default::default()
+            };
+            if let Err(e) = data.workspace.export(info.path(), options) {
+                log::error!("export failed: '{}'", e);
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::EXPORT_NORMALIZED) {
+            let options = ExportOptions {
+                normalize: true,
+                ..Default::default()
+            };
+            if let Err(e) = data.workspace.export(info.path(), options) {
+                log::error!("export failed: '{}'", e);
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::EXPORT_GLYPH_IMAGE) {
+            if let Some(name) = data.workspace.image_export_glyph() {
+                if let Err(e) = data.workspace.export_glyph_image(name, info.path()) {
+                    log::error!("exporting '{}' failed: '{}'", name, e);
+                }
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::EXPORT_SESSION_JSON) {
+            if let Some(name) = data.workspace.image_export_glyph().cloned() {
+                if let Err(e) = data.workspace.export_session_json(&name, info.path()) {
+                    log::error!("exporting '{}' failed: '{}'", name, e);
+                }
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::IMPORT_SESSION_JSON) {
+            if let Some(name) = data.workspace.image_export_glyph().cloned() {
+                if let Err(e) = data.workspace.import_session_json(&name, info.path()) {
+                    log::error!("importing {:?} failed: '{}'", info.path(), e);
+                }
             }
             Handled::Yes
         } else if cmd.is(consts::cmd::NEW_GLYPH) {
             let new_glyph_name = data.workspace.add_new_glyph();
             data.workspace.selected = Some(new_glyph_name);
             Handled::Yes
+        } else if cmd.is(consts::cmd::NEXT_GLYPH) {
+            data.workspace.step_active_tab(1);
+            Handled::Yes
+        } else if cmd.is(consts::cmd::PREVIOUS_GLYPH) {
+            data.workspace.step_active_tab(-1);
+            Handled::Yes
         } else if cmd.is(consts::cmd::DELETE_SELECTED_GLYPH) {
-            data.workspace.delete_selected_glyph();
+            let users = match data.workspace.selected.as_ref() {
+                Some(name) => data.workspace.component_users(name),
+                None => Vec::new(),
+            };
+            let locked = data
+                .workspace
+                .selected
+                .as_ref()
+                .map(|name| data.workspace.is_glyph_locked(name))
+                .unwrap_or(false);
+            // a locked glyph isn't deleted, so there is nothing to ask
+            if users.is_empty() || locked {
+                data.workspace.delete_selected_glyph();
+            } else {
+                // ask what to do about the components; the modal must be
+                // sent to a single window
+                let window = match target {
+                    Target::Window(id) => Some(id),
+                    _ => self.main_window,
+                };
+                if let Some(window) = window {
+                    ctx.submit_command(
+                        ModalHost::make_modal_command(crate::widgets::delete_glyph_panel)
+                            .to(window),
+                    );
+                }
+            }
+            Handled::Yes
+        } else if let Some(name) = cmd.get(consts::cmd::UNLOCK_GLYPH) {
+            data.workspace.set_glyph_locked(name, false);
             Handled::Yes
         } else if let Some(consts::cmd::RenameGlyphArgs { old, new }) =
             cmd.get(consts::cmd::RENAME_GLYPH)
         {
-            data.workspace.rename_glyph(old.clone(), new.clone());
+            if data.workspace.font.ufo.get_glyph(new).is_some() {
+                // ask for another name, showing why this one can't be used;
+                // the modal must be sent to a single window
+                let window = match target {
+                    Target::Window(id) => Some(id),
+                    _ => self.main_window,
+                };
+                if let Some(window) = window {
+                    let (old, new) = (old.clone(), new.clone());
+                    ctx.submit_command(
+                        ModalHost::make_modal_command(move || {
+                            crate::widgets::rename_glyph_panel(old, new)
+                        })
+                        .to(window),
+                    );
+                }
+            } else {
+                data.workspace.rename_glyph(old.clone(), new.clone());
+            }
+            Handled::Yes
+        } else if let Some(consts::cmd::DuplicateGlyphArgs { name, new_name }) =
+            cmd.get(consts::cmd::DUPLICATE_GLYPH)
+        {
+            if data.workspace.duplicate_glyph(name, new_name.clone()) {
+                data.workspace.selected = Some(new_name.clone());
+            }
+            Handled::Yes
+        } else if let Some(name) = cmd.get(consts::cmd::NEW_COMPOSITE_GLYPH) {
+            if let Some(new_name) = data.workspace.new_composite_glyph(name) {
+                data.workspace.selected = Some(new_name.clone());
+                ctx.submit_command(EDIT_GLYPH.with(new_name));
+            }
+            Handled::Yes
+        } else if let Some(name) = cmd.get(consts::cmd::COPY_GLYPH) {
+            let json = data
+                .workspace
+                .glyph_paths(name)
+                .and_then(|paths| crate::clipboard::paths_to_json(&paths));
+            match json {
+                Some(json) => {
+                    Application::global()
+                        .clipboard()
+                        .put_formats(&[ClipboardFormat::new(
+                            consts::RUNEBENDER_PASTEBOARD_TYPE,
+                            json,
+                        )])
+                }
+                None => log::warn!("failed to copy glyph '{}'", name),
+            }
             Handled::Yes
         } else if cmd.is(consts::cmd::NEW_PREVIEW_WINDOW) {
             let session_id = data.workspace.new_preview_session();
@@ -68,37 +531,176 @@ impl AppDelegate<AppState> for Delegate {
                 .menu(crate::menus::make_menu);
             ctx.new_window(new_win);
             Handled::Yes
-        } else if let Some(payload) = cmd.get(EDIT_GLYPH) {
-            match data.workspace.open_glyphs.get(payload).to_owned() {
-                Some(id) => {
-                    ctx.submit_command(druid::commands::SHOW_WINDOW.to(*id));
+        } else if cmd.is(consts::cmd::SHOW_FEATURES_WINDOW) {
+            match self.features_window {
+                Some(id) => ctx.submit_command(druid::commands::SHOW_WINDOW.to(id)),
+                None => {
+                    let new_win = WindowDesc::new(make_features_editor())
+                        .title("Features")
+                        .window_size(Size::new(900.0, 600.0))
+                        .menu(crate::menus::make_menu);
+                    self.features_window = Some(new_win.id);
+                    ctx.new_window(new_win);
                 }
+            }
+            Handled::Yes
+        } else if cmd.is(consts::cmd::SHOW_SCRIPT_CONSOLE) {
+            match self.script_console {
+                Some(id) => ctx.submit_command(druid::commands::SHOW_WINDOW.to(id)),
                 None => {
-                    let session = data.workspace.get_or_create_session(payload);
-                    let session_id = session.id;
-                    let new_win = WindowDesc::new(make_editor(&session))
-                        .title(move |d: &AppState, _: &_| {
-                            d.workspace
-                                .sessions
-                                .get(&session_id)
-                                .map(|s| s.name.to_string())
-                                .unwrap_or_else(|| "Unknown".to_string())
-                        })
-                        .window_size(Size::new(900.0, 800.0))
+                    let new_win = WindowDesc::new(make_script_console())
+                        .title("Script Console")
+                        .window_size(Size::new(600.0, 400.0))
                         .menu(crate::menus::make_menu);
-
-                    let id = new_win.id;
+                    self.script_console = Some(new_win.id);
                     ctx.new_window(new_win);
-
-                    Arc::make_mut(&mut data.workspace.open_glyphs).insert(payload.clone(), id);
                 }
             }
             Handled::Yes
+        } else if cmd.is(consts::cmd::SHOW_AUDIT_WINDOW) {
+            data.workspace.run_audit();
+            match self.audit_window {
+                Some(id) => ctx.submit_command(druid::commands::SHOW_WINDOW.to(id)),
+                None => {
+                    let new_win = WindowDesc::new(make_audit_panel())
+                        .title("Font Audit")
+                        .window_size(Size::new(400.0, 500.0))
+                        .menu(crate::menus::make_menu);
+                    self.audit_window = Some(new_win.id);
+                    ctx.new_window(new_win);
+                }
+            }
+            Handled::Yes
+        } else if cmd.is(consts::cmd::FIND_SHAPE) {
+            if !data.workspace.find_selected_shape() {
+                log::warn!("find shape: no contour is selected");
+                return Handled::Yes;
+            }
+            match self.shape_window {
+                Some(id) => ctx.submit_command(druid::commands::SHOW_WINDOW.to(id)),
+                None => {
+                    let new_win = WindowDesc::new(make_shape_search_panel())
+                        .title("Find Shape")
+                        .window_size(Size::new(400.0, 500.0))
+                        .menu(crate::menus::make_menu);
+                    self.shape_window = Some(new_win.id);
+                    ctx.new_window(new_win);
+                }
+            }
+            Handled::Yes
+        } else if let Some(base) = cmd.get(consts::cmd::REPLACE_SHAPE_WITH_COMPONENT) {
+            let count = data.workspace.replace_shape_with_component(base);
+            log::info!("replaced {} contours with components of '{}'", count, base);
+            Handled::Yes
+        } else if cmd.is(consts::cmd::SHOW_COVERAGE_WINDOW) {
+            data.workspace.refresh_coverage();
+            match self.coverage_window {
+                Some(id) => ctx.submit_command(druid::commands::SHOW_WINDOW.to(id)),
+                None => {
+                    let new_win = WindowDesc::new(make_coverage_panel())
+                        .title("Coverage")
+                        .window_size(Size::new(460.0, 600.0))
+                        .menu(crate::menus::make_menu);
+                    self.coverage_window = Some(new_win.id);
+                    ctx.new_window(new_win);
+                }
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::CHECK_COVERAGE_FILE) {
+            let chars = std::fs::read_to_string(info.path())
+                .map_err(|e| e.to_string())
+                .and_then(|text| {
+                    crate::charset::parse_charset(&text)
+                        .map_err(|item| format!("'{}' is not a character", item))
+                });
+            match chars {
+                Ok(chars) => {
+                    let target = info
+                        .path()
+                        .file_name()
+                        .map(|name| name.to_string_lossy().into_owned())
+                        .unwrap_or_default();
+                    data.workspace.check_coverage(target, chars);
+                }
+                Err(e) => log::error!("failed to read character set {:?}: {}", info.path(), e),
+            }
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::RUN_SCRIPT) {
+            let output = match std::fs::read_to_string(info.path()) {
+                Ok(source) => crate::scripting::run_script(&mut data.workspace, &source),
+                Err(e) => format!("Error: failed to read script: '{}'", e),
+            };
+            let log = Arc::make_mut(&mut data.workspace.script_output);
+            if !log.is_empty() {
+                log.push('\n');
+            }
+            log.push_str(&format!("> {}\n{}\n", info.path().display(), output));
+            ctx.submit_command(consts::cmd::SHOW_SCRIPT_CONSOLE);
+            Handled::Yes
+        } else if let Some(info) = cmd.get(consts::cmd::LOAD_REFERENCE_FONT) {
+            match ReferenceFont::load(info.path()) {
+                Ok(font) => data.workspace.reference_font = Some(Arc::new(font)),
+                Err(e) => log::error!("failed to load reference font {:?}: '{}'", info.path(), e),
+            }
+            Handled::Yes
+        } else if let Some(payload) = cmd.get(EDIT_GLYPH) {
+            if data.workspace.needs_quadratic_conversion(payload) {
+                // ask first; the modal must be sent to a single window
+                let window = match target {
+                    Target::Window(id) => Some(id),
+                    _ => self.main_window,
+                };
+                if let Some(window) = window {
+                    let name = payload.clone();
+                    ctx.submit_command(
+                        ModalHost::make_modal_command(move || {
+                            crate::widgets::convert_quadratic_panel(name)
+                        })
+                        .to(window),
+                    );
+                }
+            } else {
+                self.edit_glyph(ctx, data, payload);
+            }
+            Handled::Yes
+        } else if let Some(payload) = cmd.get(consts::cmd::CONVERT_AND_EDIT_GLYPH) {
+            self.edit_glyph(ctx, data, payload);
+            Handled::Yes
         } else {
             Handled::No
         }
     }
 
+    fn event(
+        &mut self,
+        ctx: &mut DelegateCtx,
+        _id: WindowId,
+        event: Event,
+        data: &mut AppState,
+        _env: &Env,
+    ) -> Option<Event> {
+        // some edits, like scripts and renaming glyphs, reset the paths
+        data.workspace
+            .build_paths_in_background(ctx.get_external_handle());
+        Some(event)
+    }
+
+    fn window_added(
+        &mut self,
+        id: WindowId,
+        data: &mut AppState,
+        _env: &Env,
+        ctx: &mut DelegateCtx,
+    ) {
+        if self.main_window.is_none() {
+            self.main_window = Some(id);
+            data.workspace
+                .build_paths_in_background(ctx.get_external_handle());
+            data.workspace.refresh_vcs_status(ctx.get_external_handle());
+            self.watch_font(ctx, &data.workspace);
+        }
+    }
+
     /// The handler for window deletion events.
     /// This function is called after a window has been removed.
     fn window_removed(
@@ -108,29 +710,106 @@ impl AppDelegate<AppState> for Delegate {
         _env: &Env,
         _ctx: &mut DelegateCtx,
     ) {
-        let to_remove = data
-            .workspace
-            .open_glyphs
-            .iter()
-            .find(|(_k, v)| v == &&id)
-            .map(|(k, _v)| k.clone());
-        match to_remove {
-            Some(open_glyph) => {
-                log::info!("removing '{}' from open list", open_glyph);
-                Arc::make_mut(&mut data.workspace.open_glyphs).remove(&open_glyph);
-            }
-            None => log::info!("window {:?} is not an editor window", id),
+        if self.features_window == Some(id) {
+            self.features_window = None;
+            return;
+        }
+        if self.script_console == Some(id) {
+            self.script_console = None;
+            return;
+        }
+        if self.audit_window == Some(id) {
+            self.audit_window = None;
+            return;
+        }
+        if self.shape_window == Some(id) {
+            self.shape_window = None;
+            return;
+        }
+        if self.coverage_window == Some(id) {
+            self.coverage_window = None;
+            return;
+        }
+        if self.editor_window == Some(id) {
+            self.editor_window = None;
+            log::info!("closing editor window, clearing open glyphs");
+            let workspace = &mut data.workspace;
+            Arc::make_mut(&mut workspace.open_glyphs).retain(|_, window| *window != id);
+            workspace.close_all_tabs();
+        } else {
+            log::info!("window {:?} is not an editor window", id);
         }
     }
 }
 
-fn make_editor(session: &Arc<EditSession>) -> impl Widget<AppState> {
+/// The font to open for a file given to us by the system: a .ufo directory,
+/// or the default source of a .designspace document.
+pub fn font_path_for_file(path: &Path) -> Option<PathBuf> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("ufo") if path.is_dir() => Some(path.to_owned()),
+        Some("designspace") => {
+            let xml = std::fs::read_to_string(path)
+                .map_err(|e| log::warn!("failed to read {:?}: '{}'", path, e))
+                .ok()?;
+            let source = default_source(&xml)?;
+            // sources are relative to the document
+            Some(path.with_file_name(source))
+        }
+        _ => None,
+    }
+}
+
+/// The filename of the default source in a designspace document: the source
+/// whose info is copied to the instances, or else the first source.
+fn default_source(xml: &str) -> Option<String> {
+    let mut first = None;
+    for element in xml.split("<source").skip(1) {
+        // skip the <sources> element
+        if !element.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let element = element.split("</source>").next().unwrap_or(element);
+        let tag = &element[..element.find('>').unwrap_or(element.len())];
+        let filename = match tag.split("filename=\"").nth(1) {
+            Some(rest) => rest.split('"').next().unwrap_or(rest).to_owned(),
+            None => continue,
+        };
+        if element.contains("<info copy=\"1\"") {
+            return Some(filename);
+        }
+        first.get_or_insert(filename);
+    }
+    first
+}
+
+fn make_editor_window() -> impl Widget<AppState> {
     crate::theme::wrap_in_theme_loader(
-        EditorController::new(ScrollZoom::new(Editor::new(session.clone())))
-            .lens(AppState::workspace.then(Workspace::editor_state(session.id))),
+        ModalHost::new(EditorTabs::default()).lens(AppState::workspace),
     )
 }
 
+fn make_features_editor() -> impl Widget<AppState> {
+    crate::theme::wrap_in_theme_loader(crate::widgets::features_editor().lens(AppState::workspace))
+}
+
+fn make_script_console() -> impl Widget<AppState> {
+    crate::theme::wrap_in_theme_loader(crate::widgets::script_console().lens(AppState::workspace))
+}
+
+fn make_audit_panel() -> impl Widget<AppState> {
+    crate::theme::wrap_in_theme_loader(crate::widgets::audit_panel().lens(AppState::workspace))
+}
+
+fn make_shape_search_panel() -> impl Widget<AppState> {
+    crate::theme::wrap_in_theme_loader(
+        ModalHost::new(crate::widgets::shape_search_panel()).lens(AppState::workspace),
+    )
+}
+
+fn make_coverage_panel() -> impl Widget<AppState> {
+    crate::theme::wrap_in_theme_loader(crate::widgets::coverage_panel().lens(AppState::workspace))
+}
+
 fn make_preview(session: SessionId) -> impl Widget<AppState> {
     // this is duplicated in main.rs
     let hline_painter = Painter::new(|ctx, _: &PreviewState, env| {
@@ -161,6 +840,24 @@ fn make_preview(session: SessionId) -> impl Widget<AppState> {
                             .lens(PreviewState::session.then(PreviewSession::text)),
                         1.0,
                     )
+                    .with_default_spacer()
+                    .with_child(Label::new("Features:"))
+                    .with_default_spacer()
+                    .with_child(
+                        TextBox::new()
+                            .with_placeholder("smcp -liga")
+                            .lens(PreviewState::session.then(PreviewSession::features)),
+                    )
+                    .with_default_spacer()
+                    .with_child(
+                        Checkbox::new("Metrics")
+                            .lens(PreviewState::session.then(PreviewSession::show_metrics)),
+                    )
+                    .with_default_spacer()
+                    .with_child(
+                        Checkbox::new("Vertical")
+                            .lens(PreviewState::session.then(PreviewSession::vertical)),
+                    )
                     .padding(8.0)
                     .background(hline_painter),
             )
@@ -169,3 +866,30 @@ fn make_preview(session: SessionId) -> impl Widget<AppState> {
             .lens(AppState::workspace.then(Workspace::preview_state(session))),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn designspace_default_source() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<designspace format="4.1">
+  <sources>
+    <source filename="Light.ufo" name="light">
+      <location><dimension name="weight" xcoord="100"/></location>
+    </source>
+    <source filename="Regular.ufo" name="regular">
+      <info copy="1"/>
+      <location><dimension name="weight" xcoord="400"/></location>
+    </source>
+    <source filename="Bold.ufo" name="bold"/>
+  </sources>
+</designspace>"#;
+        assert_eq!(default_source(xml).as_deref(), Some("Regular.ufo"));
+
+        let no_info = xml.replace(r#"<info copy="1"/>"#, "");
+        assert_eq!(default_source(&no_info).as_deref(), Some("Light.ufo"));
+        assert_eq!(default_source("<designspace/>"), None);
+    }
+}
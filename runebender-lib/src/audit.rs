@@ -0,0 +1,256 @@
+//! Checking glyphs for common problems.
+
+use druid::kurbo::{BezPath, ParamCurve, ParamCurveExtrema, Rect, Shape};
+use druid::Data;
+use norad::glyph::{Contour, Glyph, GlyphName, PointType};
+
+use crate::data::append_contour;
+
+/// How far a curve can extend past its on-curve points before we consider
+/// it to be missing a point at its extremum, in design units.
+const EXTREMA_TOLERANCE: f64 = 0.5;
+
+/// A problem found in a glyph.
+#[derive(Debug, Clone, Data)]
+pub struct Issue {
+    pub glyph: GlyphName,
+    pub kind: IssueKind,
+}
+
+/// The kinds of problem we look for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum IssueKind {
+    OpenContour,
+    /// A contour goes clockwise when it should go counter-clockwise, or
+    /// vice versa.
+    WrongDirection,
+    /// Two adjacent on-curve points are in the same place.
+    DuplicatePoints,
+    /// A curve extends past its on-curve points.
+    MissingExtrema,
+    /// The glyph has both components and contours.
+    MixedComponents,
+    /// The glyph has an outline but no advance, and isn't a mark.
+    ZeroWidth,
+}
+
+impl IssueKind {
+    pub fn description(self) -> &'static str {
+        match self {
+            IssueKind::OpenContour => "open contour",
+            IssueKind::WrongDirection => "contour has the wrong direction",
+            IssueKind::DuplicatePoints => "duplicate points",
+            IssueKind::MissingExtrema => "missing points at extrema",
+            IssueKind::MixedComponents => "mixes components and contours",
+            IssueKind::ZeroWidth => "zero width",
+        }
+    }
+}
+
+/// Check a glyph, returning each kind of problem found at most once.
+pub fn audit_glyph(glyph: &Glyph) -> Vec<Issue> {
+    let outline = match glyph.outline.as_ref() {
+        Some(outline) => outline,
+        None => return Vec::new(),
+    };
+    let contours = &outline.contours;
+    let paths = contours
+        .iter()
+        .map(|contour| {
+            let mut path = BezPath::new();
+            append_contour(&mut path, contour);
+            path
+        })
+        .collect::<Vec<_>>();
+
+    let mut kinds = Vec::new();
+    if contours.iter().any(is_open) {
+        kinds.push(IssueKind::OpenContour);
+    }
+    if has_wrong_direction(contours, &paths) {
+        kinds.push(IssueKind::WrongDirection);
+    }
+    if contours.iter().any(has_duplicate_points) {
+        kinds.push(IssueKind::DuplicatePoints);
+    }
+    if paths.iter().any(is_missing_extrema) {
+        kinds.push(IssueKind::MissingExtrema);
+    }
+    if !contours.is_empty() && !outline.components.is_empty() {
+        kinds.push(IssueKind::MixedComponents);
+    }
+    let width = glyph.advance.as_ref().map(|a| a.width).unwrap_or(0.);
+    if width == 0. && !contours.is_empty() && !is_mark(glyph) {
+        kinds.push(IssueKind::ZeroWidth);
+    }
+
+    kinds
+        .into_iter()
+        .map(|kind| Issue {
+            glyph: glyph.name.clone(),
+            kind,
+        })
+        .collect()
+}
+
+fn is_open(contour: &Contour) -> bool {
+    contour
+        .points
+        .first()
+        .map(|pt| pt.typ == PointType::Move)
+        .unwrap_or(false)
+}
+
+/// Outer contours should go counter-clockwise, and contours inside them
+/// clockwise, alternating as they nest.
+fn has_wrong_direction(contours: &[Contour], paths: &[BezPath]) -> bool {
+    paths.iter().enumerate().any(|(i, path)| {
+        let start = match path.segments().next() {
+            Some(seg) if !is_open(&contours[i]) => seg.start(),
+            _ => return false,
+        };
+        let depth = paths
+            .iter()
+            .enumerate()
+            .filter(|(j, other)| *j != i && !is_open(&contours[*j]) && other.winding(start) != 0)
+            .count();
+        let counter_clockwise = path.area() > 0.0;
+        counter_clockwise != (depth % 2 == 0)
+    })
+}
+
+#[allow(clippy::float_cmp)]
+fn has_duplicate_points(contour: &Contour) -> bool {
+    let on_curve = contour
+        .points
+        .iter()
+        .filter(|pt| pt.typ != PointType::OffCurve)
+        .collect::<Vec<_>>();
+    if on_curve.len() < 2 {
+        return false;
+    }
+    let wraps = !is_open(contour);
+    on_curve
+        .windows(2)
+        .map(|pair| (pair[0], pair[1]))
+        .chain(wraps.then(|| (on_curve[on_curve.len() - 1], on_curve[0])))
+        .any(|(a, b)| a.x == b.x && a.y == b.y)
+}
+
+fn is_missing_extrema(path: &BezPath) -> bool {
+    path.segments().any(|seg| {
+        let ends = Rect::from_points(seg.eval(0.0), seg.eval(1.0));
+        let bounds = seg.bounding_box();
+        bounds.x0 < ends.x0 - EXTREMA_TOLERANCE
+            || bounds.y0 < ends.y0 - EXTREMA_TOLERANCE
+            || bounds.x1 > ends.x1 + EXTREMA_TOLERANCE
+            || bounds.y1 > ends.y1 + EXTREMA_TOLERANCE
+    })
+}
+
+/// Marks are attached with an anchor whose name starts with an underscore,
+/// and are expected to have no width.
+fn is_mark(glyph: &Glyph) -> bool {
+    glyph
+        .anchors
+        .iter()
+        .flatten()
+        .any(|anchor| anchor.name.as_deref().unwrap_or("").starts_with('_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norad::glyph::{ContourPoint, Outline};
+
+    fn contour(points: Vec<(f32, f32, PointType)>) -> Contour {
+        let points = points
+            .into_iter()
+            .map(|(x, y, typ)| ContourPoint::new(x, y, typ, false, None, None, None))
+            .collect();
+        Contour::new(points, None, None)
+    }
+
+    fn glyph(contours: Vec<Contour>) -> Glyph {
+        let mut glyph = Glyph::new_named("a");
+        glyph.advance = Some(norad::glyph::Advance {
+            width: 500.0,
+            height: 0.0,
+        });
+        glyph.outline = Some(Outline {
+            contours,
+            components: Vec::new(),
+        });
+        glyph
+    }
+
+    fn kinds(glyph: &Glyph) -> Vec<IssueKind> {
+        audit_glyph(glyph).into_iter().map(|i| i.kind).collect()
+    }
+
+    fn square(x: f32, y: f32, size: f32, clockwise: bool) -> Contour {
+        let mut pts = vec![
+            (x, y, PointType::Line),
+            (x + size, y, PointType::Line),
+            (x + size, y + size, PointType::Line),
+            (x, y + size, PointType::Line),
+        ];
+        if clockwise {
+            pts.reverse();
+        }
+        contour(pts)
+    }
+
+    #[test]
+    fn direction() {
+        assert!(kinds(&glyph(vec![square(0., 0., 100., false)])).is_empty());
+        assert_eq!(
+            kinds(&glyph(vec![square(0., 0., 100., true)])),
+            vec![IssueKind::WrongDirection]
+        );
+        // a counter is clockwise
+        let counter = vec![square(0., 0., 100., false), square(25., 25., 50., true)];
+        assert!(kinds(&glyph(counter)).is_empty());
+        let counter = vec![square(0., 0., 100., false), square(25., 25., 50., false)];
+        assert_eq!(kinds(&glyph(counter)), vec![IssueKind::WrongDirection]);
+    }
+
+    #[test]
+    fn points() {
+        let open = contour(vec![(0., 0., PointType::Move), (100., 0., PointType::Line)]);
+        assert_eq!(kinds(&glyph(vec![open])), vec![IssueKind::OpenContour]);
+
+        let duplicate = contour(vec![
+            (0., 0., PointType::Line),
+            (100., 0., PointType::Line),
+            (100., 0., PointType::Line),
+            (100., 100., PointType::Line),
+        ]);
+        assert_eq!(
+            kinds(&glyph(vec![duplicate])),
+            vec![IssueKind::DuplicatePoints]
+        );
+
+        // a curve from the bottom to the right, bulging below the baseline
+        let extrema = contour(vec![
+            (0., 0., PointType::Line),
+            (50., -50., PointType::OffCurve),
+            (100., -50., PointType::OffCurve),
+            (100., 0., PointType::Curve),
+            (50., 100., PointType::Line),
+        ]);
+        assert_eq!(
+            kinds(&glyph(vec![extrema])),
+            vec![IssueKind::MissingExtrema]
+        );
+    }
+
+    #[test]
+    fn width() {
+        let mut zero = glyph(vec![square(0., 0., 100., false)]);
+        zero.advance = None;
+        assert_eq!(kinds(&zero), vec![IssueKind::ZeroWidth]);
+        zero.outline = None;
+        assert!(kinds(&zero).is_empty());
+    }
+}
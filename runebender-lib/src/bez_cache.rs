@@ -3,12 +3,13 @@
 //! this is used to invalidate glyphs appropriately when components change
 
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
 
 use druid::kurbo::{Affine, BezPath};
 use druid::Data;
 use norad::{Glyph, GlyphName, Ufo};
+use rayon::prelude::*;
 
 const PRE_CACHE_SIZE: usize = 8;
 
@@ -21,6 +22,36 @@ pub struct BezCache {
     beziers: Arc<HashMap<GlyphName, Arc<BezPath>>>,
     pre_cache: PreCache,
     components: ComponentMap,
+    /// Glyphs whose components lead back to themselves, with the names
+    /// along the way, like `[a, b, a]`.
+    cycles: Arc<HashMap<GlyphName, Vec<GlyphName>>>,
+    /// Incremented by each `reset`, so that paths built for an older font
+    /// are ignored.
+    generation: u64,
+    /// The glyphs whose paths still need building, after a `reset`.
+    #[data(ignore)]
+    unbuilt: Option<UnbuiltPaths>,
+    /// While paths are being built in the background, the glyphs whose
+    /// paths have been rebuilt or invalidated since the `reset`; the built
+    /// paths are out of date for these.
+    #[data(ignore)]
+    changed: Option<Arc<HashSet<GlyphName>>>,
+}
+
+/// A snapshot of the glyphs in a font, whose paths can be built on
+/// another thread.
+#[derive(Debug, Clone)]
+pub struct UnbuiltPaths {
+    generation: u64,
+    glyphs: Arc<HashMap<GlyphName, Arc<Glyph>>>,
+    cycles: Arc<HashMap<GlyphName, Vec<GlyphName>>>,
+}
+
+/// The paths built from an [`UnbuiltPaths`].
+#[derive(Debug, Clone)]
+pub struct BuiltPaths {
+    generation: u64,
+    paths: Arc<HashMap<GlyphName, Arc<BezPath>>>,
 }
 
 /// Tracks what glyphs are used as components in what other glyphs
@@ -103,14 +134,73 @@ impl PreCache {
 }
 
 impl BezCache {
+    /// Forget the paths of all glyphs in the font.
+    ///
+    /// Building them again can take a while for large fonts, so it isn't
+    /// done here: [`BezCache::take_unbuilt`] returns the glyphs, to be built
+    /// on another thread and added back with [`BezCache::set_built`].
     pub fn reset<'a, F>(&mut self, ufo: &Ufo, getter: &'a F)
     where
         F: Fn(&GlyphName) -> Option<&'a Arc<Glyph>> + 'a,
     {
         self.components = ComponentMap::new(ufo);
         self.pre_cache = Default::default();
-        for name in ufo.iter_names() {
-            self.rebuild_without_inval(&name, getter);
+        self.beziers = Default::default();
+        self.changed = Some(Default::default());
+        self.generation += 1;
+        // the getter may borrow things that can't be shared between
+        // threads, so collect the glyphs first.
+        let glyphs: HashMap<GlyphName, Arc<Glyph>> = ufo
+            .iter_names()
+            .filter_map(|name| getter(&name).cloned().map(|glyph| (name, glyph)))
+            .collect();
+
+        let bases = |name: &GlyphName| glyphs.get(name).map(|g| component_bases(g));
+        let cycles: HashMap<_, _> = glyphs
+            .keys()
+            .filter_map(|name| Some((name.clone(), find_cycle(name, &bases)?)))
+            .collect();
+        for cycle in cycles.values() {
+            log::warn!("components form a cycle: {}", describe_cycle(cycle));
+        }
+        self.cycles = Arc::new(cycles);
+        self.unbuilt = Some(UnbuiltPaths {
+            generation: self.generation,
+            glyphs: Arc::new(glyphs),
+            cycles: self.cycles.clone(),
+        });
+    }
+
+    /// Whether there are glyphs to build since the last `reset`.
+    pub fn needs_building(&self) -> bool {
+        self.unbuilt.is_some()
+    }
+
+    /// The glyphs whose paths need building since the last `reset`, if
+    /// they haven't been taken already.
+    pub fn take_unbuilt(&mut self) -> Option<UnbuiltPaths> {
+        self.unbuilt.take()
+    }
+
+    /// Add paths built from [`BezCache::take_unbuilt`], unless the cache has
+    /// been reset since.
+    ///
+    /// Paths that were rebuilt or invalidated in the meantime are newer than
+    /// the built ones, and are kept.
+    pub fn set_built(&mut self, built: &BuiltPaths) {
+        if built.generation != self.generation {
+            return;
+        }
+        let changed = self.changed.take().unwrap_or_default();
+        if self.beziers.is_empty() && self.pre_cache.len == 0 && changed.is_empty() {
+            self.beziers = built.paths.clone();
+            return;
+        }
+        let cache = Arc::make_mut(&mut self.beziers);
+        for (name, path) in built.paths.iter() {
+            if !changed.contains(name) && self.pre_cache.get(name).is_none() {
+                cache.entry(name.clone()).or_insert_with(|| path.clone());
+            }
         }
     }
 
@@ -121,6 +211,9 @@ impl BezCache {
     }
 
     pub fn set(&mut self, name: GlyphName, path: Arc<BezPath>) {
+        if let Some(changed) = &mut self.changed {
+            Arc::make_mut(changed).insert(name.clone());
+        }
         let result = self.pre_cache.try_insert(name, path);
         // we need to actually hit the main cache
         if let Err((name, path)) = result {
@@ -135,6 +228,9 @@ impl BezCache {
         for glyph in self.components.glyphs_containing_component(name).iter() {
             self.pre_cache.remove(glyph);
             cache.remove(glyph);
+            if let Some(changed) = &mut self.changed {
+                Arc::make_mut(changed).insert(glyph.clone());
+            }
         }
     }
 
@@ -143,39 +239,94 @@ impl BezCache {
         F: Fn(&GlyphName) -> Option<&'a Arc<Glyph>> + 'a,
     {
         self.invalidate(name);
-        self.rebuild_without_inval(name, glyph_getter)
+        self.update_cycles(name, glyph_getter);
+        self.rebuild_without_inval(name, glyph_getter, &mut Vec::new())
     }
 
+    /// `building` is the glyphs whose paths are being built, outermost
+    /// first; it is how we notice cycles.
     fn rebuild_without_inval<'a, F>(
         &mut self,
         name: &GlyphName,
         glyph_getter: &'a F,
+        building: &mut Vec<GlyphName>,
     ) -> Option<Arc<BezPath>>
     where
         F: Fn(&GlyphName) -> Option<&'a Arc<Glyph>> + 'a,
     {
+        if building.contains(name) {
+            return None;
+        }
         let glyph = glyph_getter(name)?;
-        let mut path = crate::data::path_for_glyph(glyph)?;
+        building.push(name.clone());
+        // components that haven't been invalidated are still up to date
+        let path = glyph_path(glyph, |base| {
+            self.get(base)
+                .or_else(|| self.rebuild_without_inval(base, glyph_getter, building))
+        });
+        building.pop();
+        let path = Arc::new(path?);
+        self.set(name.clone(), path.clone());
+        Some(path)
+    }
 
-        for comp in glyph
-            .outline
-            .as_ref()
-            .iter()
-            .flat_map(|o| o.components.iter())
-        {
-            match self.rebuild_without_inval(&comp.base, glyph_getter) {
-                Some(component) => {
-                    let affine: Affine = comp.transform.into();
-                    for comp_elem in (affine * &*component).elements() {
-                        path.push(*comp_elem);
-                    }
-                }
-                None => log::warn!("missing component {} in glyph {}", comp.base, glyph.name),
+    /// The path for the glyph `name`, for when it isn't in the cache yet;
+    /// components use the cached paths where there are some.
+    ///
+    /// The path isn't stored, since this is called while painting.
+    pub fn build<'a, F>(&self, name: &GlyphName, glyph_getter: &'a F) -> Option<Arc<BezPath>>
+    where
+        F: Fn(&GlyphName) -> Option<&'a Arc<Glyph>> + 'a,
+    {
+        self.build_without_caching(name, glyph_getter, &mut Vec::new())
+    }
+
+    fn build_without_caching<'a, F>(
+        &self,
+        name: &GlyphName,
+        glyph_getter: &'a F,
+        building: &mut Vec<GlyphName>,
+    ) -> Option<Arc<BezPath>>
+    where
+        F: Fn(&GlyphName) -> Option<&'a Arc<Glyph>> + 'a,
+    {
+        if building.contains(name) {
+            return None;
+        }
+        let glyph = glyph_getter(name)?;
+        building.push(name.clone());
+        let path = glyph_path(glyph, |base| {
+            self.get(base)
+                .or_else(|| self.build_without_caching(base, glyph_getter, building))
+        });
+        building.pop();
+        path.map(Arc::new)
+    }
+
+    /// Check whether the glyph `name`, and the glyphs already known to be
+    /// in cycles, are (still) in cycles.
+    fn update_cycles<'a, F>(&mut self, name: &GlyphName, glyph_getter: &'a F)
+    where
+        F: Fn(&GlyphName) -> Option<&'a Arc<Glyph>> + 'a,
+    {
+        let bases = |name: &GlyphName| glyph_getter(name).map(|g| component_bases(g));
+        let mut cycles = HashMap::new();
+        for name in self.cycles.keys().chain(std::iter::once(name)) {
+            if let Some(cycle) = find_cycle(name, &bases) {
+                cycles.insert(name.clone(), cycle);
             }
         }
-        let path = Arc::new(path);
-        self.set(name.clone(), path.clone());
-        Some(path)
+        if cycles != *self.cycles {
+            if let Some(cycle) = cycles.get(name) {
+                log::warn!("components form a cycle: {}", describe_cycle(cycle));
+            }
+            self.cycles = Arc::new(cycles);
+        }
+    }
+
+    /// If the components of `name` lead back to it, the names along the way.
+    pub fn component_cycle(&self, name: &GlyphName) -> Option<&[GlyphName]> {
+        self.cycles.get(name).map(Vec::as_slice)
     }
 
     pub(crate) fn glyphs_containing_component<'a>(
@@ -186,6 +337,137 @@ impl BezCache {
     }
 }
 
+impl UnbuiltPaths {
+    /// Build the paths of all the glyphs, in parallel.
+    pub fn build(&self) -> BuiltPaths {
+        let shared = Mutex::new(HashMap::new());
+        let paths = self
+            .glyphs
+            .par_iter()
+            .filter_map(|(name, _)| {
+                let path = self.build_path(name, &shared, &mut Vec::new())?;
+                Some((name.clone(), path))
+            })
+            .collect();
+        BuiltPaths {
+            generation: self.generation,
+            paths: Arc::new(paths),
+        }
+    }
+
+    /// Build the path for the glyph `name`, building its components as
+    /// needed.
+    ///
+    /// Paths are shared between glyphs through `shared`, except those of
+    /// glyphs in cycles, which depend on where in the cycle we started.
+    /// `building` is the glyphs whose paths are being built, outermost
+    /// first; a component that refers back to one of them is left out.
+    fn build_path(
+        &self,
+        name: &GlyphName,
+        shared: &Mutex<HashMap<GlyphName, Arc<BezPath>>>,
+        building: &mut Vec<GlyphName>,
+    ) -> Option<Arc<BezPath>> {
+        if let Some(path) = shared.lock().unwrap().get(name) {
+            return Some(path.clone());
+        }
+        if building.contains(name) {
+            return None;
+        }
+        let glyph = self.glyphs.get(name)?;
+        building.push(name.clone());
+        let path = glyph_path(glyph, |base| self.build_path(base, shared, building));
+        building.pop();
+        let path = Arc::new(path?);
+        if !self.cycles.contains_key(name) {
+            shared.lock().unwrap().insert(name.clone(), path.clone());
+        }
+        Some(path)
+    }
+}
+
+/// The names of the glyphs used as components in `glyph`.
+fn component_bases(glyph: &Glyph) -> Vec<GlyphName> {
+    glyph
+        .outline
+        .iter()
+        .flat_map(|o| o.components.iter())
+        .map(|c| c.base.clone())
+        .collect()
+}
+
+/// If the components of `name` lead back to it, returns the names along the
+/// way, starting and ending with `name`.
+///
+/// `bases` returns the components of a glyph, or `None` if it is missing.
+fn find_cycle(
+    name: &GlyphName,
+    bases: &dyn Fn(&GlyphName) -> Option<Vec<GlyphName>>,
+) -> Option<Vec<GlyphName>> {
+    fn visit(
+        target: &GlyphName,
+        current: &GlyphName,
+        bases: &dyn Fn(&GlyphName) -> Option<Vec<GlyphName>>,
+        path: &mut Vec<GlyphName>,
+        visited: &mut HashSet<GlyphName>,
+    ) -> bool {
+        for base in bases(current).unwrap_or_default() {
+            path.push(base.clone());
+            if base == *target
+                || (visited.insert(base.clone()) && visit(target, &base, bases, path, visited))
+            {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+
+    let mut path = vec![name.clone()];
+    let mut visited = HashSet::new();
+    if visit(name, name, bases, &mut path, &mut visited) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// A cycle, like 'a → b → a'.
+pub(crate) fn describe_cycle(cycle: &[GlyphName]) -> String {
+    cycle
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>()
+        .join(" → ")
+}
+
+/// The outline of `glyph`, with the paths of its components, which are
+/// provided by `component_path`.
+fn glyph_path(
+    glyph: &Glyph,
+    mut component_path: impl FnMut(&GlyphName) -> Option<Arc<BezPath>>,
+) -> Option<BezPath> {
+    let mut path = crate::data::path_for_glyph(glyph)?;
+
+    for comp in glyph
+        .outline
+        .as_ref()
+        .iter()
+        .flat_map(|o| o.components.iter())
+    {
+        match component_path(&comp.base) {
+            Some(component) => {
+                let affine: Affine = comp.transform.into();
+                for comp_elem in (affine * &*component).elements() {
+                    path.push(*comp_elem);
+                }
+            }
+            None => log::warn!("missing component {} in glyph {}", comp.base, glyph.name),
+        }
+    }
+    Some(path)
+}
+
 impl ComponentMap {
     fn new(ufo: &Ufo) -> Self {
         let mut lookup: HashMap<GlyphName, Vec<GlyphName>> = HashMap::new();
@@ -210,21 +492,126 @@ impl ComponentMap {
         }
     }
 
+    /// All the glyphs that use `name` as a component, directly or through
+    /// other components.
+    ///
+    /// Each glyph comes after the components it uses, so that rebuilding
+    /// them in order leaves every path up to date.
     fn glyphs_containing_component<'a>(&'a self, name: &GlyphName) -> Cow<'a, [GlyphName]> {
-        if let Some(glyphs) = self.inner.get(name) {
-            let mut component_children = glyphs
-                .iter()
-                .flat_map(|g| self.inner.get(g))
-                .flat_map(|g| g.iter().cloned())
-                .collect::<Vec<_>>();
-            if component_children.is_empty() {
-                Cow::Borrowed(glyphs.as_slice())
-            } else {
-                component_children.extend_from_slice(glyphs);
-                Cow::Owned(component_children)
+        let glyphs = match self.inner.get(name) {
+            Some(glyphs) => glyphs,
+            None => return Cow::Owned(Vec::new()),
+        };
+        if glyphs.iter().all(|g| !self.inner.contains_key(g)) {
+            return Cow::Borrowed(glyphs.as_slice());
+        }
+
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        self.visit_users(name, &mut visited, &mut order);
+        order.reverse();
+        // only possible if components are cyclic
+        order.retain(|g| g != name);
+        Cow::Owned(order)
+    }
+
+    /// Depth-first search of the glyphs that use `name`, adding each glyph
+    /// to `order` after all the glyphs that use it.
+    fn visit_users<'a>(
+        &'a self,
+        name: &GlyphName,
+        visited: &mut HashSet<&'a GlyphName>,
+        order: &mut Vec<GlyphName>,
+    ) {
+        for user in self.inner.get(name).into_iter().flatten() {
+            if visited.insert(user) {
+                self.visit_users(user, visited, order);
+                order.push(user.clone());
             }
-        } else {
-            Cow::Owned(Vec::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use norad::glyph::{Component, Contour, ContourPoint, Outline, PointType};
+
+    /// A glyph with a square at `x`, and components of `bases`.
+    fn glyph(name: &str, x: f32, bases: &[&str]) -> Glyph {
+        let points = [(x, 0.), (x + 10., 0.), (x + 10., 10.), (x, 10.)]
+            .iter()
+            .map(|&(x, y)| ContourPoint::new(x, y, PointType::Line, false, None, None, None))
+            .collect();
+        let mut glyph = Glyph::new_named(name);
+        glyph.outline = Some(Outline {
+            contours: vec![Contour::new(points, None, None)],
+            components: bases
+                .iter()
+                .map(|base| Component::new((*base).into(), Affine::IDENTITY.into(), None, None))
+                .collect(),
+        });
+        glyph
+    }
+
+    #[test]
+    fn cycles_are_found() {
+        let mut graph: HashMap<GlyphName, Vec<GlyphName>> = HashMap::new();
+        graph.insert("a".into(), vec!["b".into()]);
+        graph.insert("b".into(), vec!["c".into(), "x".into()]);
+        graph.insert("c".into(), vec!["a".into()]);
+        graph.insert("d".into(), vec!["b".into()]);
+        let bases = |name: &GlyphName| graph.get(name).cloned();
+
+        let cycle = find_cycle(&"a".into(), &bases).unwrap();
+        assert_eq!(describe_cycle(&cycle), "a → b → c → a");
+        // 'd' uses glyphs in a cycle, but isn't in it
+        assert!(find_cycle(&"d".into(), &bases).is_none());
+        assert!(find_cycle(&"x".into(), &bases).is_none());
+    }
+
+    #[test]
+    fn nested_components_are_ordered() {
+        // 'acute' is used by 'aacute'; 'aacute' is used by 'aacute.sc',
+        // which also uses 'acute' directly.
+        let mut inner = HashMap::new();
+        inner.insert("acute".into(), vec!["aacute.sc".into(), "aacute".into()]);
+        inner.insert("aacute".into(), vec!["aacute.sc".into()]);
+        let map = ComponentMap {
+            inner: Arc::new(inner),
+        };
+        let order = map.glyphs_containing_component(&"acute".into());
+        assert_eq!(order.len(), 2);
+        let pos = |name: &str| order.iter().position(|g| &**g == name).unwrap();
+        assert!(pos("aacute") < pos("aacute.sc"));
+    }
+
+    #[test]
+    fn built_paths_match_paths_built_alone() {
+        // 'acute' is used by 'aacute' directly and by 'aacute.sc' through
+        // 'aacute'; 'x' and 'y' use each other.
+        let mut ufo = Ufo::new();
+        let layer = ufo.get_default_layer_mut().unwrap();
+        layer.insert_glyph(glyph("a", 0., &[]));
+        layer.insert_glyph(glyph("acute", 10., &[]));
+        layer.insert_glyph(glyph("aacute", 20., &["a", "acute"]));
+        layer.insert_glyph(glyph("aacute.sc", 30., &["aacute", "acute"]));
+        layer.insert_glyph(glyph("x", 40., &["y"]));
+        layer.insert_glyph(glyph("y", 50., &["x"]));
+        let getter = |name: &GlyphName| ufo.get_glyph(name);
+
+        let mut cache = BezCache::default();
+        cache.reset(&ufo, &getter);
+        assert!(cache.get(&"aacute".into()).is_none());
+        let built = cache.take_unbuilt().unwrap().build();
+        // a path rebuilt while the others were building is newer
+        cache.set("a".into(), Arc::new(BezPath::new()));
+        cache.set_built(&built);
+
+        assert!(cache.get(&"a".into()).unwrap().elements().is_empty());
+        for name in ufo.iter_names().filter(|name| &**name != "a") {
+            let alone = BezCache::default().build(&name, &getter).unwrap();
+            assert_eq!(cache.get(&name).unwrap().elements(), alone.elements());
         }
     }
 }
@@ -0,0 +1,467 @@
+//! Character set templates, parsing lists of glyphs to add, and grouping
+//! glyphs by script and category.
+
+use std::ops::RangeInclusive;
+
+use crate::glyph_names;
+use crate::unicode_info;
+
+/// A named set of characters that can be added to a font in one go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharsetTemplate {
+    pub name: &'static str,
+    ranges: &'static [RangeInclusive<u32>],
+}
+
+pub const TEMPLATES: &[CharsetTemplate] = &[
+    CharsetTemplate {
+        name: "Latin Basic",
+        ranges: &[0x20..=0x7E],
+    },
+    CharsetTemplate {
+        name: "Latin-1 Supplement",
+        ranges: &[0xA0..=0xFF],
+    },
+    CharsetTemplate {
+        name: "Latin Extended-A",
+        ranges: &[0x100..=0x17F],
+    },
+    CharsetTemplate {
+        name: "Latin Extended-B",
+        ranges: &[0x180..=0x24F],
+    },
+    CharsetTemplate {
+        name: "Greek",
+        ranges: &[0x370..=0x3FF],
+    },
+    CharsetTemplate {
+        name: "Cyrillic",
+        ranges: &[0x400..=0x4FF],
+    },
+    CharsetTemplate {
+        name: "Punctuation",
+        ranges: &[0x2010..=0x2027, 0x2030..=0x205E],
+    },
+];
+
+impl CharsetTemplate {
+    /// The template, in the format accepted by [`parse_glyph_list`].
+    pub fn to_spec(&self) -> String {
+        self.ranges
+            .iter()
+            .map(|r| format!("U+{:04X}-{:04X}", r.start(), r.end()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Parse a list of glyphs to add.
+///
+/// Items are separated by whitespace or commas, and each item is one of:
+///
+/// - a codepoint, as `U+XXXX`;
+/// - a range of codepoints, as `U+XXXX-YYYY` or `U+XXXX-U+YYYY`;
+/// - a glyph name.
+///
+/// Codepoints are mapped to their AGLFN name, if they have one, and
+/// otherwise to a `uniXXXX` name. Unassigned codepoints in ranges are
+/// skipped. Duplicates are removed, and the order of the input is preserved.
+///
+/// Returns the first item that could not be understood, on failure.
+pub fn parse_glyph_list(text: &str) -> Result<Vec<String>, String> {
+    let mut names: Vec<String> = Vec::new();
+    for item in text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+    {
+        let mut add = |name: String| {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        };
+
+        if let Some(range) = parse_codepoint_range(item) {
+            let range = range.ok_or_else(|| item.to_string())?;
+            let is_range = range.start() != range.end();
+            for chr in range.filter_map(std::char::from_u32) {
+                if is_range && !is_assigned(chr) {
+                    continue;
+                }
+                add(name_for_char(chr));
+            }
+        } else {
+            let name =
+                glyph_names::validate_and_standardize_name(item).map_err(|_| item.to_string())?;
+            add(name);
+        }
+    }
+    Ok(names)
+}
+
+/// Parse a glyph's list of codepoints.
+///
+/// Items are separated by whitespace or commas, and each item is either a
+/// codepoint, as `U+XXXX`, or a single character. Duplicates are removed,
+/// and the order of the input is preserved.
+///
+/// Returns the first item that could not be understood, on failure.
+pub fn parse_codepoints(text: &str) -> Result<Vec<char>, String> {
+    let mut chars: Vec<char> = Vec::new();
+    for item in text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+    {
+        let mut item_chars = item.chars();
+        let chr = match (item_chars.next(), item_chars.next()) {
+            (Some(chr), None) => Some(chr),
+            _ => match parse_codepoint_range(item) {
+                Some(Some(range)) if range.start() == range.end() => {
+                    std::char::from_u32(*range.start())
+                }
+                _ => None,
+            },
+        };
+        let chr = chr.ok_or_else(|| item.to_string())?;
+        if !chars.contains(&chr) {
+            chars.push(chr);
+        }
+    }
+    Ok(chars)
+}
+
+/// Parse a character set file, such as a list of the characters a font
+/// should support.
+///
+/// Anything after a `#` on a line is a comment. Items are separated by
+/// whitespace or commas, and each item is a codepoint or a range of
+/// codepoints, as in [`parse_glyph_list`], or characters written out; so
+/// `#` and `,` themselves must be given as codepoints. Duplicates are
+/// removed, and the order of the file is preserved.
+///
+/// Returns the first item that could not be understood, on failure.
+pub fn parse_charset(text: &str) -> Result<Vec<char>, String> {
+    let mut chars: Vec<char> = Vec::new();
+    let mut add = |chr: char| {
+        if !chars.contains(&chr) {
+            chars.push(chr);
+        }
+    };
+    for item in text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()))
+        .filter(|s| !s.is_empty())
+    {
+        match parse_codepoint_range(item) {
+            Some(range) => {
+                let range = range.ok_or_else(|| item.to_string())?;
+                let is_range = range.start() != range.end();
+                for chr in range.filter_map(std::char::from_u32) {
+                    if !is_range || is_assigned(chr) {
+                        add(chr);
+                    }
+                }
+            }
+            None => item.chars().for_each(&mut add),
+        }
+    }
+    Ok(chars)
+}
+
+/// Format a list of codepoints in the form accepted by [`parse_codepoints`].
+pub fn format_codepoints(chars: &[char]) -> String {
+    chars
+        .iter()
+        .map(|chr| format!("U+{:04X}", *chr as u32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A description of a list of codepoints being edited: the characters
+/// themselves, or the reason the list is invalid.
+pub fn describe_codepoints(text: &str) -> String {
+    match parse_codepoints(text) {
+        Ok(chars) if chars.is_empty() => "no codepoints".into(),
+        Ok(chars) => chars
+            .iter()
+            .map(|chr| display_char(*chr))
+            .collect::<Vec<_>>()
+            .join(" "),
+        Err(item) => format!("'{}' is not a codepoint", item),
+    }
+}
+
+/// The character, or a placeholder if it would not be visible.
+pub fn display_char(chr: char) -> String {
+    if chr.is_control() || chr.is_whitespace() {
+        "\u{25CC}".into()
+    } else {
+        chr.to_string()
+    }
+}
+
+/// The name we use for a new glyph for this `char`.
+pub fn name_for_char(chr: char) -> String {
+    match glyph_names::glyph_name_for_char(chr) {
+        Some(name) => name.to_string(),
+        None if (chr as u32) <= 0xFFFF => format!("uni{:04X}", chr as u32),
+        None => format!("u{:05X}", chr as u32),
+    }
+}
+
+/// Returns `None` if `item` is not a codepoint, and `Some(None)` if it looks
+/// like one but is malformed.
+fn parse_codepoint_range(item: &str) -> Option<Option<RangeInclusive<u32>>> {
+    let rest = item
+        .strip_prefix("U+")
+        .or_else(|| item.strip_prefix("u+"))?;
+    let mut parts = rest.splitn(2, '-');
+    let start = parts.next().and_then(parse_hex);
+    let end = match parts.next() {
+        Some(end) => {
+            let end = end
+                .strip_prefix("U+")
+                .or_else(|| end.strip_prefix("u+"))
+                .unwrap_or(end);
+            parse_hex(end)
+        }
+        None => start,
+    };
+    Some(match (start, end) {
+        (Some(start), Some(end)) if start <= end && end <= 0x10FFFF => Some(start..=end),
+        _ => None,
+    })
+}
+
+fn parse_hex(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 6 {
+        return None;
+    }
+    u32::from_str_radix(s, 16).ok()
+}
+
+/// `false` for controls, and for codepoints that our unicode data says are
+/// unassigned; outside the blocks the data covers, only controls are
+/// filtered out.
+fn is_assigned(chr: char) -> bool {
+    !chr.is_control() && unicode_info::is_assigned(chr).unwrap_or(true)
+}
+
+/// A coarse writing system classification, used to group glyphs.
+///
+/// This is not a full implementation of the unicode script property; it
+/// only knows about the blocks most commonly found in fonts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Kana,
+    Han,
+    Hangul,
+    /// Digits, punctuation and symbols shared between scripts.
+    Common,
+    Other,
+}
+
+/// A coarse general category, used to group glyphs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    Uppercase,
+    Lowercase,
+    Letter,
+    Digit,
+    Punctuation,
+    Symbol,
+    Mark,
+    Space,
+    Other,
+}
+
+/// The group a glyph belongs to in the glyph grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GlyphGroup {
+    Encoded(Script, Category),
+    Unencoded,
+}
+
+impl GlyphGroup {
+    pub fn for_char(chr: Option<char>) -> GlyphGroup {
+        match chr {
+            Some(chr) => {
+                let category = category_for_char(chr);
+                let script = match category {
+                    Category::Uppercase | Category::Lowercase | Category::Letter => {
+                        script_for_char(chr)
+                    }
+                    _ => Script::Common,
+                };
+                GlyphGroup::Encoded(script, category)
+            }
+            None => GlyphGroup::Unencoded,
+        }
+    }
+}
+
+impl std::fmt::Display for GlyphGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GlyphGroup::Encoded(Script::Common, category) => write!(f, "{:?}", category),
+            GlyphGroup::Encoded(script, category) => write!(f, "{:?} {:?}", script, category),
+            GlyphGroup::Unencoded => write!(f, "Unencoded"),
+        }
+    }
+}
+
+fn script_for_char(chr: char) -> Script {
+    match chr as u32 {
+        0x41..=0x5A
+        | 0x61..=0x7A
+        | 0xAA
+        | 0xBA
+        | 0xC0..=0x24F
+        | 0x1E00..=0x1EFF
+        | 0x2C60..=0x2C7F
+        | 0xA720..=0xA7FF
+        | 0xFB00..=0xFB06 => Script::Latin,
+        0x370..=0x3FF | 0x1F00..=0x1FFF => Script::Greek,
+        0x400..=0x52F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Script::Cyrillic,
+        0x590..=0x5FF | 0xFB1D..=0xFB4F => Script::Hebrew,
+        0x600..=0x6FF | 0x750..=0x77F | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Script::Arabic,
+        0x900..=0x97F => Script::Devanagari,
+        0x3040..=0x30FF => Script::Kana,
+        0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF => Script::Han,
+        0x1100..=0x11FF | 0xAC00..=0xD7AF => Script::Hangul,
+        _ => Script::Other,
+    }
+}
+
+fn category_for_char(chr: char) -> Category {
+    let is_mark = matches!(
+        chr as u32,
+        0x300..=0x36F | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF | 0x20D0..=0x20FF | 0xFE20..=0xFE2F
+    );
+    let is_punctuation = matches!(
+        chr,
+        '!'..='#' | '%'..='*' | ',' ..='/' | ':' | ';' | '?' | '@' | '['..=']' | '_' | '{' | '}'
+    ) || matches!(
+        chr as u32,
+        0xA1 | 0xA7 | 0xAB | 0xB6 | 0xB7 | 0xBB | 0xBF | 0x2010..=0x2027 | 0x2030..=0x205E
+    );
+
+    if is_mark {
+        Category::Mark
+    } else if chr.is_whitespace() {
+        Category::Space
+    } else if chr.is_control() {
+        Category::Other
+    } else if chr.is_uppercase() {
+        Category::Uppercase
+    } else if chr.is_lowercase() {
+        Category::Lowercase
+    } else if chr.is_numeric() {
+        Category::Digit
+    } else if chr.is_alphabetic() {
+        Category::Letter
+    } else if is_punctuation {
+        Category::Punctuation
+    } else {
+        Category::Symbol
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_list() {
+        assert_eq!(
+            parse_glyph_list("a, U+0042 c").unwrap(),
+            vec!["a", "B", "c"]
+        );
+        assert_eq!(
+            parse_glyph_list("U+0041-0043 U+0041").unwrap(),
+            vec!["A", "B", "C"]
+        );
+        assert_eq!(parse_glyph_list("U+E000").unwrap(), vec!["uniE000"]);
+        assert_eq!(parse_glyph_list("U+1F600").unwrap(), vec!["u1F600"]);
+        assert_eq!(parse_glyph_list("U+0043-0041"), Err("U+0043-0041".into()));
+        assert_eq!(parse_glyph_list("U+zz"), Err("U+zz".into()));
+        assert_eq!(parse_glyph_list("a-b"), Err("a-b".into()));
+        assert!(parse_glyph_list("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn codepoint_list() {
+        assert_eq!(
+            parse_codepoints("U+0041, u+00c1 Á").unwrap(),
+            vec!['A', 'Á']
+        );
+        assert_eq!(parse_codepoints("a 1").unwrap(), vec!['a', '1']);
+        assert!(parse_codepoints(" ").unwrap().is_empty());
+        assert_eq!(parse_codepoints("41"), Err("41".into()));
+        assert_eq!(parse_codepoints("U+0041-0043"), Err("U+0041-0043".into()));
+        assert_eq!(parse_codepoints("U+D800"), Err("U+D800".into()));
+        assert_eq!(format_codepoints(&['A', '\u{1F600}']), "U+0041 U+1F600");
+        assert_eq!(describe_codepoints("U+0041 U+00C1"), "A Á");
+        assert_eq!(describe_codepoints("U+0020"), "\u{25CC}");
+    }
+
+    #[test]
+    fn charset_file() {
+        let text = "# Latin\nabc U+0041-0043, ä\n\nU+0023 # a hash\n";
+        assert_eq!(
+            parse_charset(text).unwrap(),
+            vec!['a', 'b', 'c', 'A', 'B', 'C', 'ä', '#']
+        );
+        assert_eq!(parse_charset("a U+zz"), Err("U+zz".into()));
+        assert!(parse_charset("# nothing\n").unwrap().is_empty());
+    }
+
+    #[test]
+    fn templates() {
+        let basic = parse_glyph_list(&TEMPLATES[0].to_spec()).unwrap();
+        assert_eq!(basic.len(), 95);
+        assert_eq!(basic[0], "space");
+        let ext_a = parse_glyph_list(&TEMPLATES[2].to_spec()).unwrap();
+        assert_eq!(ext_a.len(), 128);
+        assert!(ext_a.iter().any(|n| n == "Amacron"));
+    }
+
+    #[test]
+    fn groups() {
+        let group = |c| GlyphGroup::for_char(Some(c));
+        assert_eq!(
+            group('A'),
+            GlyphGroup::Encoded(Script::Latin, Category::Uppercase)
+        );
+        assert_eq!(
+            group('\u{3B1}'),
+            GlyphGroup::Encoded(Script::Greek, Category::Lowercase)
+        );
+        assert_eq!(
+            group('7'),
+            GlyphGroup::Encoded(Script::Common, Category::Digit)
+        );
+        assert_eq!(
+            group(','),
+            GlyphGroup::Encoded(Script::Common, Category::Punctuation)
+        );
+        assert_eq!(
+            group('+'),
+            GlyphGroup::Encoded(Script::Common, Category::Symbol)
+        );
+        assert_eq!(
+            group('\u{301}'),
+            GlyphGroup::Encoded(Script::Common, Category::Mark)
+        );
+        assert!(group('z') < group('0'));
+        assert!(group('0') < GlyphGroup::Unencoded);
+        assert_eq!(group('a').to_string(), "Latin Lowercase");
+        assert_eq!(group('.').to_string(), "Punctuation");
+    }
+}
@@ -9,7 +9,7 @@ use lopdf::content::{Content, Operation};
 use lopdf::{Document, Object, Stream};
 
 use crate::cubic_path::CubicPath;
-use crate::design_space::DPoint;
+use crate::design_space::{DPoint, Precision};
 use crate::edit_session::EditSession;
 use crate::path::Path;
 use crate::plist::Plist;
@@ -84,9 +84,87 @@ pub fn make_code_string(session: &EditSession) -> Option<String> {
     Some(out)
 }
 
+/// A plain text format for the coordinates of points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoordinateFormat {
+    /// One point per line, with the coordinates separated by a tab, as
+    /// spreadsheets expect.
+    Text,
+    /// A Python list of tuples.
+    Python,
+    /// A JSON array of two-element arrays.
+    Json,
+}
+
+/// The coordinates of the selected points, in path order.
+pub fn make_coordinate_text(session: &EditSession, format: CoordinateFormat) -> Option<String> {
+    let points = session.selected_path_points();
+    if points.is_empty() {
+        return None;
+    }
+    let coords = points.iter().map(|pt| (pt.point.x, pt.point.y));
+    let text = match format {
+        CoordinateFormat::Text => coords
+            .map(|(x, y)| format!("{}\t{}\n", x, y))
+            .collect::<String>(),
+        CoordinateFormat::Python => {
+            let items: Vec<_> = coords.map(|(x, y)| format!("({}, {})", x, y)).collect();
+            format!("[{}]", items.join(", "))
+        }
+        CoordinateFormat::Json => {
+            let items: Vec<_> = coords.map(|(x, y)| format!("[{}, {}]", x, y)).collect();
+            format!("[{}]", items.join(", "))
+        }
+    };
+    Some(text)
+}
+
+/// Make a path from text listing coordinates, in any of the formats of
+/// [`CoordinateFormat`], or most others with numbers in x, y order.
+///
+/// The points are joined by lines, and the path is closed if it has more
+/// than two points. The coordinates are rounded to `precision`.
+pub fn path_from_coordinate_text(text: &str, precision: Precision) -> Option<Path> {
+    let mut numbers = Vec::new();
+    let is_number_char = |c: char| c.is_ascii_digit() || matches!(c, '.' | '-' | '+');
+    for token in text.split(|c: char| !is_number_char(c)) {
+        if token.is_empty() {
+            continue;
+        }
+        match token.parse::<f64>() {
+            Ok(num) => numbers.push(num),
+            Err(_) => {
+                log::warn!("not a coordinate: '{}'", token);
+                return None;
+            }
+        }
+    }
+    if numbers.len() < 4 || numbers.len() % 2 != 0 {
+        return None;
+    }
+
+    let mut points = numbers
+        .chunks(2)
+        .map(|pair| DPoint::from_raw((pair[0], pair[1]), precision));
+    let mut path = Path::new(points.next()?);
+    let mut len = 1;
+    for point in points {
+        path.line_to(point, false);
+        len += 1;
+    }
+    if len > 2 {
+        path.close(false);
+    }
+    Some(path)
+}
+
 pub fn make_json(session: &EditSession) -> Option<String> {
-    let paths: Vec<_> = session.paths_for_selection();
-    serde_json::to_string(&paths).ok()
+    paths_to_json(&session.paths_for_selection())
+}
+
+/// The JSON for `paths`, in the format we put on the clipboard.
+pub fn paths_to_json(paths: &[Path]) -> Option<String> {
+    serde_json::to_string(paths).ok()
 }
 
 pub fn from_json(json: &str) -> Option<Vec<Path>> {
@@ -120,11 +198,11 @@ pub fn make_glyphs_plist(session: &EditSession) -> Option<Vec<u8>> {
     Some(data)
 }
 
-pub fn from_glyphs_plist(data: Vec<u8>) -> Option<Vec<Path>> {
+pub fn from_glyphs_plist(data: Vec<u8>, precision: Precision) -> Option<Vec<Path>> {
     let cursor = std::io::Cursor::new(data);
     match plist::from_reader(cursor) {
         Ok(GlyphsPastePlist { paths, .. }) => {
-            Some(paths.iter().map(|p| CubicPath::from(p).into()).collect())
+            Some(paths.iter().map(|p| p.to_cubic(precision).into()).collect())
         }
         Err(e) => {
             log::warn!("failed to parse glyphs plist: '{}'", e);
@@ -133,7 +211,7 @@ pub fn from_glyphs_plist(data: Vec<u8>) -> Option<Vec<Path>> {
     }
 }
 
-pub fn from_glyphs_plist_string(text: String) -> Option<Vec<Path>> {
+pub fn from_glyphs_plist_string(text: String, precision: Precision) -> Option<Vec<Path>> {
     let plist = match Plist::parse(&text) {
         Ok(Plist::Dictionary(d)) => d,
         Ok(other) => {
@@ -145,17 +223,17 @@ pub fn from_glyphs_plist_string(text: String) -> Option<Vec<Path>> {
             return None;
         }
     };
-    paths_from_plist_dict(plist)
+    paths_from_plist_dict(plist, precision)
 }
 
-fn paths_from_plist_dict(dict: HashMap<String, Plist>) -> Option<Vec<Path>> {
+fn paths_from_plist_dict(dict: HashMap<String, Plist>, precision: Precision) -> Option<Vec<Path>> {
     let paths = dict.get("paths").and_then(Plist::as_array)?;
     let mut result = Vec::new();
     for path in paths {
         if let Plist::Dictionary(dict) = path {
             if let Some(path) = GlyphPlistPath::from_dict(dict)
                 .as_ref()
-                .map(CubicPath::from)
+                .map(|path| path.to_cubic(precision))
             {
                 result.push(path.into());
             }
@@ -213,7 +291,7 @@ pub fn make_pdf_data(session: &EditSession) -> Option<Vec<u8>> {
     }
 }
 
-pub fn from_pdf_data(data: Vec<u8>) -> Option<Vec<Path>> {
+pub fn from_pdf_data(data: Vec<u8>, precision: Precision) -> Option<Vec<Path>> {
     match Document::load_mem(&data) {
         Ok(doc) => {
             if doc.get_pages().len() > 1 {
@@ -224,7 +302,7 @@ pub fn from_pdf_data(data: Vec<u8>) -> Option<Vec<Path>> {
                 .get_and_decode_page_content(page)
                 .map_err(|e| log::warn!("failed to decode pdf content: '{}'", e))
                 .ok()?;
-            Some(paths_for_pdf_contents(content))
+            Some(paths_for_pdf_contents(content, precision))
         }
         Err(e) => {
             log::warn!("failed to load pdf data: '{}'", e);
@@ -261,11 +339,11 @@ fn append_pdf_ops(ops: &mut Vec<Operation>, path: &BezPath) {
 }
 
 // pdf paths have some weird duplication thing going on?
-fn paths_for_pdf_contents(contents: Content) -> Vec<Path> {
+fn paths_for_pdf_contents(contents: Content, precision: Precision) -> Vec<Path> {
     //contents.operations.iter().for_each(|op| eprintln!("{}: [{:?}]", op.operator, op.operands));
     let bez = bez_path_for_pdf_contents(contents);
     let mut result = Vec::new();
-    for path in iter_paths_for_bez_path(&bez) {
+    for path in iter_paths_for_bez_path(&bez, precision) {
         if !result.last().map(|p| approx_eq(p, &path)).unwrap_or(false) {
             result.push(path)
         }
@@ -289,12 +367,16 @@ fn approx_eq(path1: &CubicPath, path2: &CubicPath) -> bool {
 }
 
 // going to unjustifiable lengths to avoid an unecessary allocation :|
-fn iter_paths_for_bez_path(src: &BezPath) -> impl Iterator<Item = CubicPath> + '_ {
+fn iter_paths_for_bez_path(
+    src: &BezPath,
+    precision: Precision,
+) -> impl Iterator<Item = CubicPath> + '_ {
     let mut cur_path_id = EntityId::next();
     let mut cur_points = Vec::new();
     let mut closed = false;
     let mut iter = src.elements().iter();
 
+    let point = move |pt: &Point| DPoint::from_raw(*pt, precision);
     std::iter::from_fn(move || loop {
         let path_el = match iter.next() {
             Some(el) => el,
@@ -317,19 +399,17 @@ fn iter_paths_for_bez_path(src: &BezPath) -> impl Iterator<Item = CubicPath> + '
                 };
                 cur_path_id = EntityId::next();
                 closed = false;
-                cur_points.push(PathPoint::on_curve(cur_path_id, DPoint::from_raw(*pt)));
+                cur_points.push(PathPoint::on_curve(cur_path_id, point(pt)));
                 if let Some(path) = path {
                     return Some(path);
                 }
             }
-            PathEl::LineTo(pt) => {
-                cur_points.push(PathPoint::on_curve(cur_path_id, DPoint::from_raw(*pt)))
-            }
+            PathEl::LineTo(pt) => cur_points.push(PathPoint::on_curve(cur_path_id, point(pt))),
             PathEl::QuadTo(..) => log::warn!("ignoring quad_to in paste"),
             PathEl::CurveTo(p1, p2, p3) => {
-                cur_points.push(PathPoint::off_curve(cur_path_id, DPoint::from_raw(*p1)));
-                cur_points.push(PathPoint::off_curve(cur_path_id, DPoint::from_raw(*p2)));
-                cur_points.push(PathPoint::on_curve(cur_path_id, DPoint::from_raw(*p3)));
+                cur_points.push(PathPoint::off_curve(cur_path_id, point(p1)));
+                cur_points.push(PathPoint::off_curve(cur_path_id, point(p2)));
+                cur_points.push(PathPoint::on_curve(cur_path_id, point(p3)));
             }
             PathEl::ClosePath => closed = true,
         }
@@ -507,19 +587,24 @@ impl From<&CubicPath> for GlyphPlistPath {
     }
 }
 
-impl From<&GlyphPlistPath> for CubicPath {
-    fn from(src: &GlyphPlistPath) -> CubicPath {
+impl GlyphPlistPath {
+    /// The path, with its points rounded to `precision`.
+    fn to_cubic(&self, precision: Precision) -> CubicPath {
         let path_id = EntityId::next();
-        let paths: Vec<PathPoint> = src
+        let paths: Vec<PathPoint> = self
             .nodes
             .iter()
-            .flat_map(|node| from_glyphs_plist_point(node, path_id))
+            .flat_map(|node| from_glyphs_plist_point(node, path_id, precision))
             .collect();
-        CubicPath::from_raw_parts(path_id, paths, None, src.closed > 0)
+        CubicPath::from_raw_parts(path_id, paths, None, self.closed > 0)
     }
 }
 
-fn from_glyphs_plist_point(s: &str, parent_id: EntityId) -> Option<PathPoint> {
+fn from_glyphs_plist_point(
+    s: &str,
+    parent_id: EntityId,
+    precision: Precision,
+) -> Option<PathPoint> {
     let mut iter = s.trim_matches('"').splitn(3, ' ');
     match (iter.next(), iter.next(), iter.next()) {
         (Some(x_), Some(y_), Some(typ_)) => {
@@ -542,7 +627,7 @@ fn from_glyphs_plist_point(s: &str, parent_id: EntityId) -> Option<PathPoint> {
                     return None;
                 }
             };
-            let point = DPoint::new(x.round(), y.round());
+            let point = DPoint::from_raw((x, y), precision);
             let id = EntityId::new_with_parent(parent_id);
             Some(PathPoint { id, point, typ })
         }
@@ -552,3 +637,33 @@ fn from_glyphs_plist_point(s: &str, parent_id: EntityId) -> Option<PathPoint> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coordinate_text_formats() {
+        let inputs = [
+            "10\t20\n30\t-40\n50\t60\n",
+            "[(10, 20), (30, -40), (50, 60)]",
+            "[[10.0, 20.0], [30.0, -40.0], [50.0, 60.0]]",
+        ];
+        for input in &inputs {
+            let path = path_from_coordinate_text(input, Precision::Integer).unwrap();
+            assert!(path.is_closed());
+            let mut coords: Vec<_> = path
+                .points()
+                .iter()
+                .map(|pt| (pt.point.x, pt.point.y))
+                .collect();
+            // the first point of a closed path is stored last
+            coords.rotate_right(1);
+            assert_eq!(coords, vec![(10., 20.), (30., -40.), (50., 60.)]);
+        }
+
+        assert!(path_from_coordinate_text("10 20", Precision::Integer).is_none());
+        assert!(path_from_coordinate_text("10 20 30", Precision::Integer).is_none());
+        assert!(path_from_coordinate_text("10 - 20 30", Precision::Integer).is_none());
+    }
+}
@@ -1,18 +1,53 @@
 //! A glyph embedded in another glyph.
+//!
+//! A component can also be 'smart': instead of a bare transform, each use
+//! of it has its own parameters (offset, scale, rotation, and skew) from
+//! which its transform is made. The parameters are saved in the
+//! component's lib, so they survive a round trip through the UFO, and
+//! are where interpolation values will go once a font can have masters.
 
-use druid::kurbo::Affine;
-use druid::Data;
-use norad::GlyphName;
+use druid::kurbo::{Affine, Point};
+use druid::{Data, Lens};
+use norad::{GlyphName, Plist};
 
 use crate::design_space::DVec2;
 use crate::point::EntityId;
 
+/// The key in a component's lib for its parameters.
+const PARAMS_LIB_KEY: &str = "org.linebender.runebender.component-parameters";
+
+const OFFSET_X_KEY: &str = "offsetX";
+const OFFSET_Y_KEY: &str = "offsetY";
+const SCALE_X_KEY: &str = "scaleX";
+const SCALE_Y_KEY: &str = "scaleY";
+const ROTATION_KEY: &str = "rotation";
+const SKEW_KEY: &str = "skew";
+
 #[derive(Debug, Data, Clone)]
 pub struct Component {
     pub base: GlyphName,
     #[data(same_fn = "affine_eq")]
     pub transform: Affine,
     pub id: EntityId,
+    /// The parameters this component's transform was made from, if it is a
+    /// smart component.
+    pub params: Option<ComponentParams>,
+}
+
+/// The parameters of a smart component.
+///
+/// The transform they describe scales the base glyph, then skews it
+/// horizontally, rotates it, and finally moves it by the offset.
+#[derive(Debug, Clone, Copy, PartialEq, Data, Lens, Serialize, Deserialize)]
+pub struct ComponentParams {
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub scale_x: f64,
+    pub scale_y: f64,
+    /// The rotation, in degrees counter-clockwise.
+    pub rotation: f64,
+    /// The horizontal skew, in degrees.
+    pub skew: f64,
 }
 
 fn affine_eq(left: &Affine, right: &Affine) -> bool {
@@ -24,21 +59,236 @@ impl Component {
         let base = src.base.clone();
         let transform = src.transform.into();
         let id = EntityId::next();
+        let params = src
+            .lib()
+            .and_then(|lib| lib.get(PARAMS_LIB_KEY))
+            .and_then(ComponentParams::from_plist);
         Component {
             base,
             transform,
             id,
+            params,
         }
     }
 
     pub fn to_norad(&self) -> norad::glyph::Component {
         let base = self.base.clone();
         let transform = self.transform.into();
-        norad::glyph::Component::new(base, transform, None, None)
+        let lib = self.params.map(|params| {
+            let mut lib = Plist::new();
+            lib.insert(PARAMS_LIB_KEY.into(), params.to_plist());
+            lib
+        });
+        norad::glyph::Component::new(base, transform, None, lib)
+    }
+
+    /// The component's parameters; for a component that isn't smart, these
+    /// are found from its transform.
+    pub(crate) fn params(&self) -> ComponentParams {
+        self.params
+            .unwrap_or_else(|| ComponentParams::from_affine(self.transform))
+    }
+
+    /// Make this a smart component with these parameters, and update the
+    /// transform to match.
+    pub(crate) fn set_params(&mut self, params: ComponentParams) {
+        self.transform = params.to_affine();
+        self.params = Some(params);
     }
 
     pub(crate) fn nudge(&mut self, delta: DVec2) {
         let [a, b, c, d, t_x, t_y] = self.transform.as_coeffs();
         self.transform = Affine::new([a, b, c, d, t_x + delta.x, t_y + delta.y]);
+        if let Some(params) = self.params.as_mut() {
+            params.offset_x += delta.x;
+            params.offset_y += delta.y;
+        }
+    }
+}
+
+impl ComponentParams {
+    /// The parameters that describe `affine`.
+    ///
+    /// Any affine transform can be described this way, as long as it
+    /// doesn't collapse the glyph onto a line; a reflection comes out as a
+    /// negative vertical scale.
+    pub(crate) fn from_affine(affine: Affine) -> Self {
+        let [a, b, c, d, e, f] = affine.as_coeffs();
+        let scale_x = a.hypot(b);
+        let angle = b.atan2(a);
+        let (sin, cos) = angle.sin_cos();
+        let scale_y = d * cos - c * sin;
+        let skew = if scale_y.abs() < f64::EPSILON {
+            0.0
+        } else {
+            ((c * cos + d * sin) / scale_y).atan()
+        };
+        ComponentParams {
+            offset_x: e,
+            offset_y: f,
+            scale_x,
+            scale_y,
+            rotation: angle.to_degrees(),
+            skew: skew.to_degrees(),
+        }
+    }
+
+    pub(crate) fn to_affine(self) -> Affine {
+        Affine::translate((self.offset_x, self.offset_y))
+            * Affine::rotate(self.rotation.to_radians())
+            * Affine::new([1.0, 0.0, self.skew.to_radians().tan(), 1.0, 0.0, 0.0])
+            * Affine::scale_non_uniform(self.scale_x, self.scale_y)
+    }
+
+    fn from_plist(value: &plist::Value) -> Option<Self> {
+        let dict = value.as_dictionary()?;
+        let get = |key: &str| dict.get(key).and_then(|v| v.as_real());
+        Some(ComponentParams {
+            offset_x: get(OFFSET_X_KEY)?,
+            offset_y: get(OFFSET_Y_KEY)?,
+            scale_x: get(SCALE_X_KEY)?,
+            scale_y: get(SCALE_Y_KEY)?,
+            rotation: get(ROTATION_KEY).unwrap_or(0.0),
+            skew: get(SKEW_KEY).unwrap_or(0.0),
+        })
+    }
+
+    fn to_plist(self) -> plist::Value {
+        let mut dict = plist::Dictionary::new();
+        dict.insert(OFFSET_X_KEY.into(), self.offset_x.into());
+        dict.insert(OFFSET_Y_KEY.into(), self.offset_y.into());
+        dict.insert(SCALE_X_KEY.into(), self.scale_x.into());
+        dict.insert(SCALE_Y_KEY.into(), self.scale_y.into());
+        dict.insert(ROTATION_KEY.into(), self.rotation.into());
+        dict.insert(SKEW_KEY.into(), self.skew.into());
+        dict.into()
+    }
+}
+
+/// The outline that replaces a component of `base` with this `transform`
+/// when the component is decomposed: the base glyph's contours, and its own
+/// components, moved by the transform.
+pub(crate) fn decompose(base: &norad::Glyph, transform: Affine) -> norad::glyph::Outline {
+    let outline = match base.outline.as_ref() {
+        Some(outline) => outline,
+        None => {
+            return norad::glyph::Outline {
+                contours: Vec::new(),
+                components: Vec::new(),
+            }
+        }
+    };
+    let contours = outline
+        .contours
+        .iter()
+        .map(|contour| {
+            let mut contour = contour.clone();
+            for point in contour.points.iter_mut() {
+                let moved = transform * Point::new(point.x as f64, point.y as f64);
+                point.x = moved.x as f32;
+                point.y = moved.y as f32;
+            }
+            contour
+        })
+        .collect();
+    let components = outline
+        .components
+        .iter()
+        .map(|component| {
+            let nested: Affine = component.transform.into();
+            norad::glyph::Component::new(
+                component.base.clone(),
+                (transform * nested).into(),
+                None,
+                None,
+            )
+        })
+        .collect();
+    norad::glyph::Outline {
+        contours,
+        components,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_affine_eq(left: Affine, right: Affine) {
+        let (left, right) = (left.as_coeffs(), right.as_coeffs());
+        assert!(
+            left.iter()
+                .zip(right.iter())
+                .all(|(l, r)| (l - r).abs() < 1e-9),
+            "{:?} != {:?}",
+            left,
+            right
+        );
+    }
+
+    #[test]
+    fn params_describe_transform() {
+        let params = ComponentParams {
+            offset_x: 120.0,
+            offset_y: -40.0,
+            scale_x: 0.8,
+            scale_y: 1.2,
+            rotation: 30.0,
+            skew: 12.0,
+        };
+        let found = ComponentParams::from_affine(params.to_affine());
+        assert_affine_eq(found.to_affine(), params.to_affine());
+        assert!((found.rotation - 30.0).abs() < 1e-9);
+        assert!((found.skew - 12.0).abs() < 1e-9);
+
+        // a reflection is a negative scale
+        let flipped = Affine::new([1.0, 0.0, 0.0, -1.0, 500.0, 0.0]);
+        let found = ComponentParams::from_affine(flipped);
+        assert!(found.scale_y < 0.0);
+        assert_affine_eq(found.to_affine(), flipped);
+    }
+
+    #[test]
+    fn params_round_trip() {
+        let mut component = Component {
+            base: "acutecomb".into(),
+            transform: Affine::default(),
+            id: EntityId::next(),
+            params: None,
+        };
+        let mut params = component.params();
+        params.rotation = 15.0;
+        component.set_params(params);
+        component.nudge(DVec2::from_raw(
+            (10.0, 0.0),
+            crate::design_space::Precision::Integer,
+        ));
+
+        let loaded = Component::from_norad(&component.to_norad());
+        let loaded_params = loaded.params.unwrap();
+        assert!((loaded_params.rotation - 15.0).abs() < 1e-9);
+        assert!((loaded_params.offset_x - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decompose_moves_outline() {
+        use norad::glyph::{Contour, ContourPoint, Outline, PointType};
+        let mut base = norad::Glyph::new_named("a");
+        let point = ContourPoint::new(10.0, 20.0, PointType::Line, false, None, None, None);
+        base.outline = Some(Outline {
+            contours: vec![Contour::new(vec![point], None, None)],
+            components: vec![norad::glyph::Component::new(
+                "acutecomb".into(),
+                Affine::translate((5.0, 0.0)).into(),
+                None,
+                None,
+            )],
+        });
+
+        let outline = decompose(&base, Affine::translate((100.0, 0.0)));
+        let point = &outline.contours[0].points[0];
+        assert!((point.x - 110.0).abs() < 1e-6 && (point.y - 20.0).abs() < 1e-6);
+        let nested: Affine = outline.components[0].transform.into();
+        assert_affine_eq(nested, Affine::translate((105.0, 0.0)));
     }
 }
@@ -9,13 +9,24 @@ pub const RUNEBENDER_PASTEBOARD_TYPE: FormatId = "org.linebender.runebender-json
 
 /// Commands and Selectors
 pub mod cmd {
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
     use druid::kurbo::{Point, Vec2};
-    use druid::Selector;
-    use norad::GlyphName;
+    use druid::{FileInfo, Selector, SingleUse};
+    use norad::{GlyphName, Ufo};
 
+    use crate::bez_cache::BuiltPaths;
+    use crate::clipboard::CoordinateFormat;
+    use crate::component::ComponentParams;
     use crate::design_space::{DPoint, DVec2};
+    use crate::draw::ViewMode;
+    use crate::font_builder::FontBuilder;
+    use crate::guides::GuideEdit;
     use crate::point::EntityId;
+    use crate::settings::ToolOptions;
     use crate::tools::ToolId;
+    use crate::vcs::GitStatus;
 
     /// sent by the 'delete' menu item
     pub const DELETE: Selector = Selector::new("runebender.delete");
@@ -26,15 +37,178 @@ pub mod cmd {
     /// sent by the 'deselect' menu item
     pub const DESELECT_ALL: Selector = Selector::new("runebender.deselect-all");
 
+    /// Add to the selection every on-curve point with the same x coordinate
+    /// as a selected point; if the payload is `false`, the same y coordinate.
+    pub const SELECT_ALIGNED_POINTS: Selector<bool> =
+        Selector::new("runebender.select-aligned-points");
+
+    /// sent by the 'export with overlaps removed' menu item, after the user
+    /// has chosen a location.
+    pub const EXPORT_REMOVE_OVERLAPS: Selector<FileInfo> =
+        Selector::new("runebender.export-remove-overlaps");
+
+    /// sent by the 'export normalized UFO' menu item, after the user has
+    /// chosen a location.
+    pub const EXPORT_NORMALIZED: Selector<FileInfo> = Selector::new("runebender.export-normalized");
+
+    /// sent by the 'export glyph image' panel, after the user has chosen
+    /// a location.
+    pub const EXPORT_GLYPH_IMAGE: Selector<FileInfo> =
+        Selector::new("runebender.export-glyph-image");
+
+    /// sent by the 'export glyph as JSON' menu item, after the user has
+    /// chosen a location.
+    pub const EXPORT_SESSION_JSON: Selector<FileInfo> =
+        Selector::new("runebender.export-session-json");
+
+    /// sent by the 'import glyph from JSON' menu item, after the user has
+    /// chosen a file.
+    pub const IMPORT_SESSION_JSON: Selector<FileInfo> =
+        Selector::new("runebender.import-session-json");
+
+    /// Replace the font with a new, unsaved font built from the payload.
+    pub const NEW_FONT: Selector<FontBuilder> = Selector::new("runebender.new-font");
+
     /// sent by the 'new glyph' menu item
     pub const NEW_GLYPH: Selector = Selector::new("runebender.new-glyph");
 
+    /// sent by the 'next glyph' menu item, to show the next glyph in the
+    /// active editor tab.
+    pub const NEXT_GLYPH: Selector = Selector::new("runebender.next-glyph");
+
+    /// sent by the 'previous glyph' menu item
+    pub const PREVIOUS_GLYPH: Selector = Selector::new("runebender.previous-glyph");
+
     /// sent by the 'window->new text preview' menu item
     pub const NEW_PREVIEW_WINDOW: Selector = Selector::new("runebender.new-preview-window");
 
+    /// sent by the 'run script' menu item, after the user has chosen a script.
+    pub const RUN_SCRIPT: Selector<FileInfo> = Selector::new("runebender.run-script");
+
+    /// sent by the 'save a copy' menu item, after the user has chosen a
+    /// location.
+    pub const SAVE_COPY: Selector<FileInfo> = Selector::new("runebender.save-copy");
+
+    /// sent by the 'revert' panel, to discard all unsaved changes.
+    pub const REVERT_TO_SAVED: Selector = Selector::new("runebender.revert-to-saved");
+
+    /// Sent from the background thread when saving the font finishes, with
+    /// an error message if it failed.
+    pub const SAVE_FINISHED: Selector<Result<(), String>> =
+        Selector::new("runebender.save-finished");
+
+    /// Sent from a background thread when git has been run for the font at
+    /// the path, with the repository's status, or `None` if the font isn't
+    /// in a repository.
+    pub const VCS_STATUS: Selector<(PathBuf, Option<Arc<GitStatus>>)> =
+        Selector::new("runebender.vcs-status");
+
+    /// Sent from the background thread when the paths of the glyphs have
+    /// been built, after a font is opened.
+    pub const PATHS_BUILT: Selector<BuiltPaths> = Selector::new("runebender.paths-built");
+
+    /// Sent from the file watcher when files in the font's directory have
+    /// been changed by another program, with the generation of the watcher.
+    pub const EXTERNAL_CHANGES: Selector<u64> = Selector::new("runebender.external-changes");
+
+    /// Sent from the background thread when the font has been loaded again
+    /// after an `EXTERNAL_CHANGES` notification, with the generation of the
+    /// watcher that noticed the changes.
+    pub const EXTERNAL_CHANGES_LOADED: Selector<(u64, SingleUse<Ufo>)> =
+        Selector::new("runebender.external-changes-loaded");
+
+    /// sent by the 'external changes' panel, to replace the changed glyphs
+    /// with the versions on disk.
+    pub const RELOAD_EXTERNAL_CHANGES: Selector =
+        Selector::new("runebender.reload-external-changes");
+
+    /// sent by the 'open recent' menu items, with the path of the font.
+    pub const OPEN_RECENT: Selector<PathBuf> = Selector::new("runebender.open-recent");
+
+    /// sent when files are dropped onto a window or the application's icon;
+    /// the first .ufo or .designspace among them is opened.
+    pub const FILES_DROPPED: Selector<Vec<PathBuf>> = Selector::new("runebender.files-dropped");
+
+    /// sent by the 'clear recent' menu item
+    pub const CLEAR_RECENT_FILES: Selector = Selector::new("runebender.clear-recent-files");
+
+    /// sent by the 'reopen last font on launch' menu item
+    pub const TOGGLE_REOPEN_LAST_FONT: Selector =
+        Selector::new("runebender.toggle-reopen-last-font");
+
+    /// sent by the 'update font when closing glyphs' menu item
+    pub const TOGGLE_FLUSH_ON_CLOSE: Selector = Selector::new("runebender.toggle-flush-on-close");
+
+    /// Sent by the tool options strip when an option changes; the options
+    /// are saved with the settings.
+    pub const SET_TOOL_OPTIONS: Selector<ToolOptions> =
+        Selector::new("runebender.set-tool-options");
+
+    /// sent by the 'unsaved changes' panel when the user chooses to save;
+    /// the pending close happens once saving succeeds.
+    pub const CLOSE_AFTER_SAVING: Selector = Selector::new("runebender.close-after-saving");
+
+    /// sent by the 'unsaved changes' panel when the user chooses to close
+    /// without saving.
+    pub const CLOSE_WITHOUT_SAVING: Selector = Selector::new("runebender.close-without-saving");
+
+    /// sent by the 'unsaved changes' panel when the user chooses not to
+    /// close after all.
+    pub const CANCEL_CLOSE: Selector = Selector::new("runebender.cancel-close");
+
+    /// sent by the 'commit' panel, with the commit message.
+    pub const COMMIT_CHANGES: Selector<String> = Selector::new("runebender.commit-changes");
+
+    /// Allow or disallow points with fractional coordinates.
+    pub const TOGGLE_FRACTIONAL_COORDINATES: Selector =
+        Selector::new("runebender.toggle-fractional-coordinates");
+
+    /// Turn the select tool's keyboard editing commands on or off.
+    pub const TOGGLE_KEYBOARD_EDITING: Selector =
+        Selector::new("runebender.toggle-keyboard-editing");
+
+    /// Load a font whose outlines are drawn behind the glyphs being edited.
+    pub const LOAD_REFERENCE_FONT: Selector<FileInfo> =
+        Selector::new("runebender.load-reference-font");
+
+    /// sent by the 'window->script console' menu item, and after a script
+    /// has been run.
+    pub const SHOW_SCRIPT_CONSOLE: Selector = Selector::new("runebender.show-script-console");
+
+    /// sent by the 'window->font audit' menu item
+    pub const SHOW_AUDIT_WINDOW: Selector = Selector::new("runebender.show-audit-window");
+
+    /// sent by the 'paths->find shape' menu item; searches for contours with
+    /// the shape of the selected contour, and shows the matches.
+    pub const FIND_SHAPE: Selector = Selector::new("runebender.find-shape");
+
+    /// Replace the contour searched for by 'find shape', and its matches,
+    /// with components of the glyph with this name.
+    pub const REPLACE_SHAPE_WITH_COMPONENT: Selector<GlyphName> =
+        Selector::new("runebender.replace-shape-with-component");
+
+    /// sent by the 'window->coverage' menu item
+    pub const SHOW_COVERAGE_WINDOW: Selector = Selector::new("runebender.show-coverage-window");
+
+    /// Check the font against the characters listed in a character set file.
+    pub const CHECK_COVERAGE_FILE: Selector<FileInfo> =
+        Selector::new("runebender.check-coverage-file");
+
+    /// sent by the 'window->features' menu item
+    pub const SHOW_FEATURES_WINDOW: Selector = Selector::new("runebender.show-features-window");
+
     /// sent by the 'delete glyph' menu item
     pub const DELETE_SELECTED_GLYPH: Selector = Selector::new("runebender.delete-selected-glyph");
 
+    /// Sent to the root to unlock a glyph, from the banner shown in the
+    /// editor of a locked glyph.
+    pub const UNLOCK_GLYPH: Selector<GlyphName> = Selector::new("runebender.unlock-glyph");
+
+    /// Sent to the root to open a glyph with quadratic contours in an
+    /// editor, once the user has agreed to their conversion to cubics.
+    pub const CONVERT_AND_EDIT_GLYPH: Selector<GlyphName> =
+        Selector::new("runebender.convert-and-edit-glyph");
+
     /// Sent to the root to rename a glyph.
     ///
     /// The arguments **must** be a `RenameGlyphArgs`
@@ -46,15 +220,121 @@ pub mod cmd {
         pub new: GlyphName,
     }
 
+    /// Sent to the root to copy a glyph to a new name. Sent from the
+    /// duplicate glyph panel.
+    pub const DUPLICATE_GLYPH: Selector<DuplicateGlyphArgs> =
+        Selector::new("runebender.duplicate-glyph");
+
+    /// Arguments passed with the DUPLICATE_GLYPH command.
+    pub struct DuplicateGlyphArgs {
+        pub name: GlyphName,
+        pub new_name: GlyphName,
+    }
+
+    /// Sent to the root to put a glyph's outline on the clipboard. Sent from
+    /// the glyph grid's context menu.
+    pub const COPY_GLYPH: Selector<GlyphName> = Selector::new("runebender.copy-glyph");
+
+    /// Sent to the root to add a new glyph that is a component of this
+    /// glyph. Sent from the glyph grid's context menu.
+    pub const NEW_COMPOSITE_GLYPH: Selector<GlyphName> =
+        Selector::new("runebender.new-composite-glyph");
+
     /// sent by the 'add component' menu item
     pub const ADD_COMPONENT: Selector = Selector::new("runebender.add-component");
 
+    /// sent by the 'flip horizontally' menu item
+    pub const FLIP_HORIZONTAL: Selector = Selector::new("runebender.flip-horizontal");
+
+    /// sent by the 'flip vertically' menu item
+    pub const FLIP_VERTICAL: Selector = Selector::new("runebender.flip-vertical");
+
+    /// sent by the 'transform again' menu item
+    pub const TRANSFORM_AGAIN: Selector = Selector::new("runebender.transform-again");
+
+    /// sent by the 'duplicate and transform again' menu item
+    pub const DUPLICATE_AND_TRANSFORM_AGAIN: Selector =
+        Selector::new("runebender.duplicate-and-transform-again");
+
+    /// sent by the alignment palette
+    pub const ALIGN_POINTS: Selector<Alignment> = Selector::new("runebender.align-points");
+
+    /// How the selected points are moved by `ALIGN_POINTS`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum Alignment {
+        Left,
+        CenterX,
+        Right,
+        Top,
+        CenterY,
+        Bottom,
+        /// Space the points evenly between the leftmost and rightmost.
+        DistributeX,
+        /// Space the points evenly between the lowest and highest.
+        DistributeY,
+    }
+
     /// sent by 'align selection' menu item in Paths menu
     pub const ALIGN_SELECTION: Selector = Selector::new("runebender.align-selection");
 
     // sent by 'reverse contours' menu item in Paths menu
     pub const REVERSE_CONTOURS: Selector = Selector::new("runebender.reverse-contours");
 
+    /// sent by 'join contours' menu item in Paths menu
+    pub const JOIN_CONTOURS: Selector = Selector::new("runebender.join-contours");
+
+    /// sent by 'break contour' menu item in Paths menu
+    pub const BREAK_CONTOURS: Selector = Selector::new("runebender.break-contours");
+
+    /// sent by 'lock points' menu item in Paths menu
+    pub const TOGGLE_LOCK_SELECTION: Selector = Selector::new("runebender.toggle-lock-selection");
+
+    /// sent by 'lock contours' menu item in Paths menu
+    pub const LOCK_CONTOURS: Selector = Selector::new("runebender.lock-contours");
+
+    /// sent by 'unlock all' menu item in Paths menu
+    pub const UNLOCK_ALL: Selector = Selector::new("runebender.unlock-all");
+
+    /// sent by 'harmonize handles' menu item in Paths menu
+    pub const HARMONIZE_HANDLES: Selector = Selector::new("runebender.harmonize-handles");
+
+    /// sent by 'balance handles' menu item in Paths menu
+    pub const BALANCE_HANDLES: Selector = Selector::new("runebender.balance-handles");
+
+    /// sent by the 'simplify' panel, with the tolerance in design units
+    pub const SIMPLIFY_SELECTION: Selector<f64> = Selector::new("runebender.simplify-selection");
+
+    /// sent by 'expand stroke' menu item in Paths menu
+    pub const EXPAND_STROKE: Selector = Selector::new("runebender.expand-stroke");
+
+    /// Store the current outline of the glyph as a snapshot with this name.
+    pub const SAVE_SNAPSHOT: Selector<String> = Selector::new("runebender.save-snapshot");
+
+    /// sent by the snapshots panel, with the index of the snapshot to restore.
+    pub const RESTORE_SNAPSHOT: Selector<usize> = Selector::new("runebender.restore-snapshot");
+
+    /// sent by the snapshots panel, with the index of the snapshot to delete.
+    pub const DELETE_SNAPSHOT: Selector<usize> = Selector::new("runebender.delete-snapshot");
+
+    /// sent by the 'paste in place' menu item; this pastes without offsetting
+    /// repeated pastes.
+    pub const PASTE_IN_PLACE: Selector = Selector::new("runebender.paste-in-place");
+
+    /// sent by the 'paste at mouse' menu item; this centers the pasted paths
+    /// on the last position of the mouse.
+    pub const PASTE_AT_MOUSE: Selector = Selector::new("runebender.paste-at-mouse");
+
+    /// Copy the coordinates of the selected points as text in this format.
+    pub const COPY_COORDINATES: Selector<CoordinateFormat> =
+        Selector::new("runebender.copy-coordinates");
+
+    /// Add a path through the coordinates in the text on the clipboard.
+    pub const PASTE_COORDINATES: Selector = Selector::new("runebender.paste-coordinates");
+
+    /// sent by the editor when the mouse moves, with its position in design
+    /// space, or `None` when it leaves the canvas.
+    pub const CURSOR_MOVED: Selector<Option<DPoint>> = Selector::new("runebender.cursor-moved");
+
     /// Sent when a new tool has been selected.
     ///
     /// The payload must be a `ToolId`.
@@ -77,6 +357,32 @@ pub mod cmd {
     /// Sent when the 'reset zoom' menu item is selected
     pub const ZOOM_DEFAULT: Selector = Selector::new("runebender.zoom-default");
 
+    /// Sent when the 'snap to alignment zones' menu item is selected
+    pub const TOGGLE_ZONE_SNAPPING: Selector = Selector::new("runebender.toggle-zone-snapping");
+
+    /// Show or hide the Tunni lines of curves.
+    pub const TOGGLE_TUNNI_LINES: Selector = Selector::new("runebender.toggle-tunni-lines");
+
+    /// Show or hide the magnified view shown while dragging with the pen and
+    /// knife tools.
+    pub const TOGGLE_LOUPE: Selector = Selector::new("runebender.toggle-loupe");
+
+    /// Show or hide the list of the glyph's contours in the editor.
+    pub const TOGGLE_CONTOUR_LIST: Selector = Selector::new("runebender.toggle-contour-list");
+
+    /// Change how the glyph in the editor is drawn.
+    pub const SET_VIEW_MODE: Selector<ViewMode> = Selector::new("runebender.set-view-mode");
+
+    /// Show the glyph rasterized at the given size, in pixels per em, or stop
+    /// showing it if the size is `None`.
+    pub const SET_PIXEL_PREVIEW: Selector<Option<f64>> =
+        Selector::new("runebender.set-pixel-preview");
+
+    /// Show the named glyph attached to the glyph being edited by their
+    /// anchors, or stop showing it if the name is `None`.
+    pub const SET_ANCHOR_PREVIEW: Selector<Option<GlyphName>> =
+        Selector::new("runebender.set-anchor-preview");
+
     /// Sent when the 'add guide' context menu item is selected
     ///
     /// The arguments **must** be a `Point`, where the guide will be added.
@@ -93,6 +399,108 @@ pub mod cmd {
         pub pos: Point,
     }
 
+    /// Change the name, color or lock of a guide. Sent from the guide's
+    /// context menu.
+    pub const EDIT_GUIDE: Selector<EditGuideArgs> = Selector::new("runebender.edit-guide");
+
+    /// Arguments passed along with the EDIT_GUIDE command
+    pub struct EditGuideArgs {
+        pub id: EntityId,
+        pub edit: GuideEdit,
+    }
+
+    /// Make an on-curve point smooth or a corner. Sent from the list of
+    /// selected points in the coordinate panel.
+    pub const SET_POINT_TYPE: Selector<SetPointTypeArgs> =
+        Selector::new("runebender.set-point-type");
+
+    /// Arguments passed along with the SET_POINT_TYPE command
+    pub struct SetPointTypeArgs {
+        pub id: EntityId,
+        pub smooth: bool,
+    }
+
+    /// Make an on-curve point the start of its contour. Sent from the
+    /// point's context menu.
+    pub const SET_START_POINT: Selector<EntityId> = Selector::new("runebender.set-start-point");
+
+    /// Delete a single point or guide. Sent from its context menu.
+    pub const DELETE_ITEM: Selector<EntityId> = Selector::new("runebender.delete-item");
+
+    /// Split a segment with a new point. Sent from the segment's context menu.
+    pub const ADD_POINT_ON_SEGMENT: Selector<AddPointOnSegmentArgs> =
+        Selector::new("runebender.add-point-on-segment");
+
+    /// Arguments passed along with the ADD_POINT_ON_SEGMENT command
+    pub struct AddPointOnSegmentArgs {
+        /// The on-curve point at the start of the segment.
+        pub start: EntityId,
+        pub t: f64,
+    }
+
+    /// Make a segment a line or a curve. Sent from the segment's context
+    /// menu.
+    pub const SET_SEGMENT_TYPE: Selector<SetSegmentTypeArgs> =
+        Selector::new("runebender.set-segment-type");
+
+    /// Arguments passed along with the SET_SEGMENT_TYPE command
+    pub struct SetSegmentTypeArgs {
+        /// The on-curve point at the start of the segment.
+        pub start: EntityId,
+        pub line: bool,
+    }
+
+    /// Reverse the contour containing this point. Sent from a segment's
+    /// context menu.
+    pub const REVERSE_CONTOUR: Selector<EntityId> = Selector::new("runebender.reverse-contour");
+
+    /// Set the parameters of a smart component. Sent from the component
+    /// inspector.
+    pub const SET_COMPONENT_PARAMS: Selector<SetComponentParamsArgs> =
+        Selector::new("runebender.set-component-params");
+
+    /// Arguments passed along with the SET_COMPONENT_PARAMS command
+    pub struct SetComponentParamsArgs {
+        pub id: EntityId,
+        pub params: ComponentParams,
+    }
+
+    /// Select the points of a path. Sent from the contour list.
+    pub const SELECT_CONTOUR: Selector<SelectContourArgs> =
+        Selector::new("runebender.select-contour");
+
+    /// Arguments passed along with the SELECT_CONTOUR command
+    pub struct SelectContourArgs {
+        /// The path's id.
+        pub id: EntityId,
+        /// If `true`, the path's points are toggled in the existing selection,
+        /// instead of replacing it.
+        pub toggle: bool,
+    }
+
+    /// Give a path a name, or remove its name if the name is empty.
+    pub const RENAME_CONTOUR: Selector<RenameContourArgs> =
+        Selector::new("runebender.rename-contour");
+
+    /// Arguments passed along with the RENAME_CONTOUR command
+    pub struct RenameContourArgs {
+        pub id: EntityId,
+        pub name: String,
+    }
+
+    /// Move a path one place later in the glyph's contours, or earlier if
+    /// the payload is `false`.
+    pub const MOVE_CONTOUR: Selector<(EntityId, bool)> = Selector::new("runebender.move-contour");
+
+    /// Hide the path with this id, or show it if it is hidden.
+    pub const TOGGLE_CONTOUR_HIDDEN: Selector<EntityId> =
+        Selector::new("runebender.toggle-contour-hidden");
+
+    /// Lock the points of the path with this id, or unlock them if they are
+    /// all locked.
+    pub const TOGGLE_CONTOUR_LOCKED: Selector<EntityId> =
+        Selector::new("runebender.toggle-contour-locked");
+
     /// A hack: asks the editor view to take focus, so that it can handle
     /// keyboard events.
     ///
@@ -0,0 +1,251 @@
+//! Checking which of the characters needed by a set of languages, or listed
+//! in a character set file, the font supports.
+
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use druid::{Data, Lens};
+use norad::glyph::{Glyph, GlyphName};
+
+/// Digits and punctuation needed by every language.
+const COMMON: &str = " 0123456789.,:;!?'\"()-&";
+
+/// The basic Latin alphabet, followed by `$extra` letters.
+macro_rules! latin {
+    ($extra:expr) => {
+        concat!("abcdefghijklmnopqrstuvwxyz", $extra)
+    };
+}
+
+/// A language, and the letters needed to write it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Language {
+    pub name: &'static str,
+    /// The lowercase letters of the language; the uppercase letters are
+    /// derived from these.
+    letters: &'static str,
+}
+
+pub const LANGUAGES: &[Language] = &[
+    Language {
+        name: "English",
+        letters: latin!(""),
+    },
+    Language {
+        name: "French",
+        letters: latin!("àâæçéèêëîïôœùûüÿ"),
+    },
+    Language {
+        name: "German",
+        letters: latin!("äöüß"),
+    },
+    Language {
+        name: "Spanish",
+        letters: latin!("áéíñóúü"),
+    },
+    Language {
+        name: "Portuguese",
+        letters: latin!("áâãàçéêíóôõú"),
+    },
+    Language {
+        name: "Italian",
+        letters: latin!("àèéìíîòóùú"),
+    },
+    Language {
+        name: "Dutch",
+        letters: latin!("áéíóúäëïöü"),
+    },
+    Language {
+        name: "Polish",
+        letters: latin!("ąćęłńóśźż"),
+    },
+    Language {
+        name: "Czech",
+        letters: latin!("áčďéěíňóřšťúůýž"),
+    },
+    Language {
+        name: "Romanian",
+        letters: latin!("ăâîșț"),
+    },
+    Language {
+        name: "Turkish",
+        letters: latin!("çğıİöşü"),
+    },
+    Language {
+        name: "Greek",
+        letters: "αβγδεζηθικλμνξοπρσςτυφχψωάέήίόύώϊϋ",
+    },
+    Language {
+        name: "Russian",
+        letters: "абвгдеёжзийклмнопрстуфхцчшщъыьэюя",
+    },
+    Language {
+        name: "Ukrainian",
+        letters: "абвгґдеєжзиіїйклмнопрстуфхцчшщьюя",
+    },
+];
+
+impl Language {
+    /// The characters needed to write the language: its letters in both
+    /// cases, followed by digits and common punctuation.
+    pub fn chars(&self) -> Vec<char> {
+        let mut chars: Vec<char> = self.letters.chars().collect();
+        chars.extend(self.letters.chars().filter_map(single_uppercase));
+        chars.extend(COMMON.chars());
+        dedup(chars)
+    }
+}
+
+/// The uppercase form of `chr`, if it is a different, single character.
+fn single_uppercase(chr: char) -> Option<char> {
+    let mut upper = chr.to_uppercase();
+    match (upper.next(), upper.next()) {
+        (Some(upper), None) if upper != chr => Some(upper),
+        _ => None,
+    }
+}
+
+/// `chars` without duplicates, in their original order.
+fn dedup(chars: Vec<char>) -> Vec<char> {
+    let mut seen = BTreeSet::new();
+    chars.into_iter().filter(|chr| seen.insert(*chr)).collect()
+}
+
+/// The characters needed by each of the languages `names`, in the order of
+/// [`LANGUAGES`].
+pub fn chars_for_languages(names: &BTreeSet<&'static str>) -> Vec<char> {
+    let chars = LANGUAGES
+        .iter()
+        .filter(|lang| names.contains(lang.name))
+        .flat_map(|lang| lang.chars())
+        .collect();
+    dedup(chars)
+}
+
+/// How well the font supports a character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Data)]
+pub enum CoverageStatus {
+    /// No glyph is mapped to the character.
+    Missing,
+    /// A glyph is mapped to the character, but it has no outline.
+    Empty,
+    Complete,
+}
+
+impl CoverageStatus {
+    pub fn description(self) -> &'static str {
+        match self {
+            CoverageStatus::Missing => "missing",
+            CoverageStatus::Empty => "empty",
+            CoverageStatus::Complete => "complete",
+        }
+    }
+
+    /// The status of a character, given the glyph mapped to it.
+    pub fn for_glyph(glyph: Option<&Glyph>) -> Self {
+        let outline = match glyph {
+            Some(glyph) => glyph.outline.as_ref(),
+            None => return CoverageStatus::Missing,
+        };
+        match outline {
+            Some(outline) if !outline.contours.is_empty() || !outline.components.is_empty() => {
+                CoverageStatus::Complete
+            }
+            _ => CoverageStatus::Empty,
+        }
+    }
+}
+
+/// A required character, and how well the font supports it.
+#[derive(Debug, Clone, Data)]
+pub struct CoverageEntry {
+    pub chr: char,
+    /// The glyph mapped to the character, if there is one.
+    pub glyph: Option<GlyphName>,
+    pub status: CoverageStatus,
+}
+
+/// The result of checking the font against a list of required characters.
+#[derive(Debug, Clone, Data, Lens)]
+pub struct CoverageReport {
+    /// What the font was checked against, such as the names of languages.
+    pub target: String,
+    pub chars: Arc<Vec<char>>,
+    /// An entry for each of `chars`, missing characters first, then empty
+    /// ones, then complete ones.
+    pub entries: Arc<Vec<CoverageEntry>>,
+}
+
+impl CoverageReport {
+    /// The number of characters with this status.
+    pub fn count(&self, status: CoverageStatus) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.status == status)
+            .count()
+    }
+
+    /// The status of the glyph `name`, if it is mapped to a character in
+    /// the report.
+    pub fn status_of(&self, name: &GlyphName) -> Option<CoverageStatus> {
+        self.entries
+            .iter()
+            .find(|entry| entry.glyph.as_ref() == Some(name))
+            .map(|entry| entry.status)
+    }
+}
+
+/// The state of the coverage window, and the last report.
+#[derive(Debug, Clone, Default, Data, Lens)]
+pub struct Coverage {
+    /// The languages chosen in the coverage window.
+    pub languages: Arc<BTreeSet<&'static str>>,
+    /// The result of the last check, if the font has been checked.
+    pub report: Option<CoverageReport>,
+    /// If set, the main glyph grid only shows glyphs with this status in
+    /// the report.
+    pub filter: Option<CoverageStatus>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn language_chars() {
+        let german = LANGUAGES.iter().find(|l| l.name == "German").unwrap();
+        let chars = german.chars();
+        assert!(chars.contains(&'ä') && chars.contains(&'Ä'));
+        // 'ß' has no single uppercase character
+        assert!(chars.contains(&'ß') && !chars.contains(&'ẞ'));
+        assert!(chars.contains(&'7'));
+
+        let names = ["English", "German"].iter().copied().collect();
+        let both = chars_for_languages(&names);
+        assert_eq!(both.len(), chars.len());
+        assert_eq!(both.iter().filter(|c| **c == 'a').count(), 1);
+    }
+
+    #[test]
+    fn status() {
+        let mut glyph = Glyph::new_named("a");
+        assert_eq!(CoverageStatus::for_glyph(None), CoverageStatus::Missing);
+        assert_eq!(
+            CoverageStatus::for_glyph(Some(&glyph)),
+            CoverageStatus::Empty
+        );
+        glyph.outline = Some(norad::glyph::Outline {
+            contours: Vec::new(),
+            components: vec![norad::glyph::Component::new(
+                "b".into(),
+                druid::kurbo::Affine::IDENTITY.into(),
+                None,
+                None,
+            )],
+        });
+        assert_eq!(
+            CoverageStatus::for_glyph(Some(&glyph)),
+            CoverageStatus::Complete
+        );
+    }
+}
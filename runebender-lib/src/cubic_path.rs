@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use super::design_space::DPoint;
+use super::design_space::{DPoint, Precision};
 use super::point::{EntityId, PathPoint, PointType};
 use super::point_list::{PathPoints, RawSegment};
 use druid::kurbo::{BezPath, PathEl};
@@ -57,6 +57,7 @@ impl CubicPath {
     /// - we don't currently support quadratics.
     pub(crate) fn from_bezpath(
         path: impl IntoIterator<Item = PathEl>,
+        precision: Precision,
     ) -> Result<Self, &'static str> {
         let path_id = EntityId::next();
         let mut els = path.into_iter();
@@ -68,19 +69,34 @@ impl CubicPath {
             _ => return Err("missing initial moveto"),
         };
 
-        points.push(PathPoint::on_curve(path_id, DPoint::from_raw(start_point)));
+        points.push(PathPoint::on_curve(
+            path_id,
+            DPoint::from_raw(start_point, precision),
+        ));
 
         for el in els {
             match el {
                 // we only take the first path segment
                 PathEl::MoveTo(_) => break,
                 PathEl::LineTo(pt) => {
-                    points.push(PathPoint::on_curve(path_id, DPoint::from_raw(pt)));
+                    points.push(PathPoint::on_curve(
+                        path_id,
+                        DPoint::from_raw(pt, precision),
+                    ));
                 }
                 PathEl::CurveTo(p0, p1, p2) => {
-                    points.push(PathPoint::off_curve(path_id, DPoint::from_raw(p0)));
-                    points.push(PathPoint::off_curve(path_id, DPoint::from_raw(p1)));
-                    points.push(PathPoint::on_curve(path_id, DPoint::from_raw(p2)));
+                    points.push(PathPoint::off_curve(
+                        path_id,
+                        DPoint::from_raw(p0, precision),
+                    ));
+                    points.push(PathPoint::off_curve(
+                        path_id,
+                        DPoint::from_raw(p1, precision),
+                    ));
+                    points.push(PathPoint::on_curve(
+                        path_id,
+                        DPoint::from_raw(p2, precision),
+                    ));
                 }
                 PathEl::QuadTo(..) => return Err("quads not currently supported"),
                 PathEl::ClosePath => {
@@ -108,7 +124,7 @@ impl CubicPath {
         Ok(Self::from_raw_parts(path_id, points, None, closed))
     }
 
-    pub(crate) fn from_norad(src: &norad::glyph::Contour) -> CubicPath {
+    pub(crate) fn from_norad(src: &norad::glyph::Contour, precision: Precision) -> CubicPath {
         use norad::glyph::PointType as NoradPType;
         assert!(
             !src.points.is_empty(),
@@ -125,7 +141,7 @@ impl CubicPath {
         }
 
         for n_pt in &src.points {
-            let point = DPoint::new(n_pt.x.round() as f64, n_pt.y.round() as f64);
+            let point = DPoint::from_file(n_pt.x as f64, n_pt.y as f64, precision);
             let typ = PointType::from_norad(n_pt);
             let id = EntityId::new_with_parent(path_id);
             if let Some(ident) = n_pt.identifier() {
@@ -206,12 +222,12 @@ impl CubicPath {
         self.points.closed()
     }
 
-    pub(crate) fn split_segment_at_point(&mut self, seg: RawSegment, t: f64) {
-        let mut pre_seg = seg.subsegment(0.0..t);
+    pub(crate) fn split_segment_at_point(&mut self, seg: RawSegment, t: f64, precision: Precision) {
+        let mut pre_seg = seg.subsegment(0.0..t, precision);
         if let RawSegment::Cubic(_, _, _, p3) = &mut pre_seg {
             p3.typ = PointType::OnCurve { smooth: true };
         }
-        let post_seg = seg.subsegment(t..1.0);
+        let post_seg = seg.subsegment(t..1.0, precision);
         self.points.split_segment(seg, pre_seg, post_seg);
     }
 }
@@ -230,7 +246,7 @@ mod tests {
     #[test]
     fn from_bezpath() {
         let rect = Rect::from_origin_size((0., 0.), (10., 10.));
-        let path = CubicPath::from_bezpath(rect.to_path(0.1)).unwrap();
+        let path = CubicPath::from_bezpath(rect.to_path(0.1), Precision::Integer).unwrap();
         assert!(path.is_closed());
         assert_eq!(path.points.len(), 4);
         assert_eq!(path.points.start_point().point.to_raw(), Point::ORIGIN);
@@ -239,7 +255,7 @@ mod tests {
     #[test]
     fn iter_rect_segs() {
         let rect = Rect::new(0., 0., 10., 10.);
-        let path = CubicPath::from_bezpath(rect.to_path(0.1)).unwrap();
+        let path = CubicPath::from_bezpath(rect.to_path(0.1), Precision::Integer).unwrap();
 
         let mut seg_iter = path.iter_segments();
         assert!(matches!(seg_iter.next().unwrap(), RawSegment::Line(..)));
@@ -274,7 +290,7 @@ mod tests {
         bez.line_to((20., 0.));
         bez.close_path();
 
-        let path = CubicPath::from_bezpath(bez).unwrap();
+        let path = CubicPath::from_bezpath(bez, Precision::Integer).unwrap();
 
         assert!(path.points.closed());
         assert_eq!(path.points.len(), 3);
@@ -1,24 +1,45 @@
 //! Application state.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::error::Error;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use druid::kurbo::{Affine, BezPath, Point, Rect, Shape, Size, Vec2};
-use druid::{Data, Lens, WindowId};
+use druid::{Data, ExtEventSink, Lens, Target, WindowId};
 use norad::glyph::{Contour, ContourPoint, Glyph, GlyphName, PointType};
 use norad::{FontInfo, Ufo};
 
-use crate::bez_cache::BezCache;
+use crate::audit::{audit_glyph, Issue};
+use crate::bez_cache::{BezCache, BuiltPaths};
+use crate::charset::GlyphGroup;
+use crate::coverage::{Coverage, CoverageEntry, CoverageReport, CoverageStatus};
+use crate::design_space::{self, Precision, ViewPort};
 use crate::edit_session::{EditSession, SessionId};
+use crate::font_info::{LegalInfo, NameInfo, Os2Info, PostscriptInfo};
+use crate::glyph_lock;
+use crate::guides::Guide;
+use crate::mark_color::MarkColor;
+use crate::nib::Nib;
+use crate::reference_font::ReferenceFont;
+use crate::selection::Selection;
+use crate::settings::{Settings, ToolOptions};
+use crate::shape_search::{self, ShapeMatch, ShapeQuery};
+use crate::tools::ToolId;
+use crate::vcs::GitStatus;
 
 /// This is by convention.
 const DEFAULT_UNITS_PER_EM: f64 = 1000.;
 
 const DEFAULT_PREVIEW_FONT_SIZE: f64 = 96.0;
 
+/// The key in the font's lib for the preferred order of glyphs.
+const GLYPH_ORDER_LIB_KEY: &str = "public.glyphOrder";
+
+/// The key in a glyph's lib for the y coordinate of its vertical origin.
+const VERTICAL_ORIGIN_LIB_KEY: &str = "public.verticalOrigin";
+
 /// The top level data structure.
 ///
 /// Currently this just wraps `Workspace`; in the future multiple workspaces
@@ -26,6 +47,8 @@ const DEFAULT_PREVIEW_FONT_SIZE: f64 = 96.0;
 #[derive(Clone, Data, Default, Lens)]
 pub struct AppState {
     pub workspace: Workspace,
+    /// Settings that persist across launches.
+    pub settings: Settings,
 }
 
 /// A workspace is a single font, corresponding to a UFO file on disk.
@@ -37,12 +60,145 @@ pub struct Workspace {
     pub selected: Option<GlyphName>,
     /// glyphs that are already open in an editor window
     pub open_glyphs: Arc<HashMap<GlyphName, WindowId>>,
+    /// The sessions shown as tabs in the editor window, in order.
+    pub editor_tabs: Arc<Vec<SessionId>>,
+    /// The tab currently shown in the editor window.
+    pub active_tab: Option<SessionId>,
     pub sessions: Arc<HashMap<SessionId, Arc<EditSession>>>,
     pub(crate) previews: Arc<HashMap<SessionId, PreviewSession>>,
     session_map: Arc<HashMap<GlyphName, SessionId>>,
+    /// The editor state of glyphs that have been edited, restored when
+    /// a glyph is reopened.
+    editor_memory: Arc<HashMap<GlyphName, EditorMemory>>,
+    /// What undo and redo would do in each open editor, for the Edit menu.
+    undo_labels: Arc<HashMap<SessionId, UndoLabels>>,
     // really just a store of the fully resolved Beziers of all glyphs.
     cache: Arc<BezCache>,
     pub info: SimpleFontInfo,
+    /// How glyphs are ordered in the main glyph grid.
+    pub glyph_sort: GlyphSort,
+    /// Whether the main glyph grid is split into sections by script and category.
+    pub group_glyphs: bool,
+    /// If set, the main glyph grid only shows glyphs with this mark color.
+    pub mark_filter: Option<MarkColor>,
+    /// The font's OpenType feature code.
+    ///
+    /// This is written back to the UFO when saving.
+    pub features: Arc<String>,
+    /// The options last used to export a glyph image.
+    pub image_export: ImageExportOptions,
+    /// Everything printed by scripts, shown in the script console.
+    pub script_output: Arc<String>,
+    /// The problems found the last time the font was audited.
+    pub audit_issues: Arc<Vec<Issue>>,
+    /// The contour searched for by 'find shape', if there has been a search.
+    pub shape_query: Option<ShapeQuery>,
+    /// The contours found by the last shape search.
+    pub shape_matches: Arc<Vec<ShapeMatch>>,
+    /// The languages and report of the coverage window.
+    pub coverage: Coverage,
+    /// How far each repeated paste is moved from the one before.
+    pub paste_offset: PasteOffset,
+    /// How far the selection moves each time an arrow key is pressed.
+    pub nudge_amounts: NudgeAmounts,
+    /// The nib used by the nib tool and the 'expand stroke' command.
+    pub nib: Nib,
+    /// How the editor responds to zooming.
+    pub zoom_options: ZoomOptions,
+    /// The options of the editor's tools.
+    ///
+    /// This is a copy of the options in the settings, which are saved
+    /// whenever it changes.
+    pub tool_options: ToolOptions,
+    /// Whether a glyph's edits are written into the UFO when its editor tab
+    /// or window is closed, so that the grid and previews show them before
+    /// the font is saved.
+    ///
+    /// This is a copy of the setting of the same name.
+    pub flush_on_close: bool,
+    /// How finely points are rounded: to whole units, or to fractions of
+    /// a unit.
+    ///
+    /// This is fractional when a font with fractional coordinates is
+    /// opened. Changing it affects glyphs opened and edits made afterwards;
+    /// points that have already been loaded are not changed.
+    pub precision: Precision,
+    /// Whether points can be traversed, added, and described with the
+    /// keyboard alone.
+    pub keyboard_editing: bool,
+    /// Whether curves are drawn with their Tunni lines, which can be dragged
+    /// to adjust the curves' tension.
+    pub show_tunni_lines: bool,
+    /// Whether the editor shows a panel listing the glyph's contours.
+    pub show_contour_list: bool,
+    /// Whether a magnified view of the canvas is shown while dragging with
+    /// the pen and knife tools.
+    pub show_loupe: bool,
+    /// A font whose outlines are drawn behind the glyphs being edited.
+    pub reference_font: Option<Arc<ReferenceFont>>,
+    /// The git repository containing the font, if there is one.
+    pub vcs: Option<Arc<GitStatus>>,
+    /// Whether the font is being saved, or the last save failed.
+    pub save_status: SaveStatus,
+    /// If `true`, the font is saved again once the current save finishes,
+    /// because it was saved while a save was in progress.
+    #[data(ignore)]
+    save_pending: bool,
+    /// Glyphs changed on disk by another program, waiting for the user to
+    /// decide whether to reload them.
+    pub external_changes: Option<Arc<ExternalChanges>>,
+    /// The font as it was when it was opened or last saved, to tell whether
+    /// there are unsaved changes.
+    saved: Arc<SavedFont>,
+}
+
+/// The parts of a workspace that are written to disk, as they were when
+/// last saved.
+///
+/// Changing the font replaces its `Arc`, so comparing pointers is enough.
+#[derive(Clone, Default)]
+struct SavedFont {
+    font: Arc<FontObject>,
+    info: SimpleFontInfo,
+    features: Arc<String>,
+}
+
+/// Glyphs that were changed on disk by another program.
+#[derive(Debug)]
+pub struct ExternalChanges {
+    /// The font as it is on disk.
+    ufo: Ufo,
+    /// The glyphs that differ from the font being edited.
+    pub glyphs: Vec<GlyphName>,
+}
+
+/// The state of saving the font in the background.
+#[derive(Debug, Clone, Data)]
+pub enum SaveStatus {
+    /// No save is in progress, and the last one succeeded.
+    Idle,
+    Saving,
+    /// The last save failed, with this message.
+    Failed(Arc<str>),
+}
+
+/// The possible orderings of the main glyph grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum GlyphSort {
+    /// The order in the font's `public.glyphOrder`; glyphs not in that list
+    /// come last.
+    GlyphOrder,
+    /// By the glyph's first codepoint; unencoded glyphs come last.
+    Codepoint,
+    /// Alphabetically by name.
+    Name,
+}
+
+/// A run of glyphs in the glyph grid, with an optional title.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct GridSection {
+    pub title: Option<String>,
+    pub glyphs: Vec<GlyphName>,
 }
 
 #[derive(Clone, Data)]
@@ -64,6 +220,11 @@ pub(crate) struct GridGlyph {
     pub is_placeholder: bool,
     pub is_selected: bool,
     pub upm: f64,
+    pub mark_color: Option<MarkColor>,
+    /// Whether the glyph is locked against editing.
+    pub is_locked: bool,
+    /// Whether the glyph has changed since the font was last committed.
+    pub is_modified: bool,
 }
 
 /// Detailed information about a specific glyph.
@@ -83,6 +244,10 @@ pub struct SimpleFontInfo {
     metrics: FontMetrics,
     pub family_name: Arc<str>,
     pub style_name: Arc<str>,
+    pub names: NameInfo,
+    pub os2: Os2Info,
+    pub postscript: PostscriptInfo,
+    pub legal: LegalInfo,
 }
 
 /// Things in `FontInfo` that are relevant while editing or drawing.
@@ -96,6 +261,69 @@ pub struct FontMetrics {
     pub italic_angle: Option<f64>,
 }
 
+/// One of the horizontal metric lines that can be dragged in the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricLine {
+    Ascender,
+    CapHeight,
+    XHeight,
+    Descender,
+}
+
+/// Options that apply to a single export of the font.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExportOptions {
+    /// If `true`, overlapping contours are merged in the exported glyphs.
+    pub remove_overlaps: bool,
+    /// If `true`, the files are written in the normalized form used by
+    /// ufonormalizer, to minimize diffs in version control.
+    pub normalize: bool,
+}
+
+/// The file formats a glyph image can be exported as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum ImageFormat {
+    Svg,
+    Png,
+}
+
+/// Options for exporting a glyph as an image.
+#[derive(Debug, Clone, Data, Lens)]
+pub struct ImageExportOptions {
+    pub format: ImageFormat,
+    /// The height of the image's metric bounds, in pixels.
+    pub size: f64,
+    /// If `true`, the baseline, vertical metrics, and advance are drawn.
+    pub show_metrics: bool,
+}
+
+/// The distance between repeated pastes of the same paths, in design units.
+#[derive(Debug, Clone, Copy, Data, Lens)]
+pub struct PasteOffset {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// How far the arrow keys move the selection, in design units.
+#[derive(Debug, Clone, Copy, Data, Lens)]
+pub struct NudgeAmounts {
+    /// The distance with no modifier held.
+    pub plain: f64,
+    /// The distance with shift held.
+    pub shift: f64,
+    /// The distance with cmd held.
+    pub cmd: f64,
+}
+
+/// How the editor responds to zooming.
+#[derive(Debug, Clone, Copy, Data, Lens)]
+pub struct ZoomOptions {
+    /// How far each scroll or pinch zooms, relative to the default.
+    pub sensitivity: f64,
+    /// Whether zooming in and out with the keyboard is animated.
+    pub animate: bool,
+}
+
 /// The state for an editor view.
 #[derive(Clone, Data, Lens)]
 pub struct EditorState {
@@ -116,6 +344,31 @@ pub struct PreviewState {
 pub(crate) struct PreviewSession {
     font_size: f64,
     text: Arc<String>,
+    /// Features to toggle, in addition to the defaults, like `smcp -liga`.
+    features: Arc<String>,
+    /// Whether to show the advance and sidebearings of each glyph.
+    show_metrics: bool,
+    /// Whether to lay out each line as a column, top to bottom.
+    vertical: bool,
+}
+
+/// How a glyph's editor was left: its zoom and scroll position, the
+/// selected tool, and the selection.
+#[derive(Debug, Clone, Data)]
+pub struct EditorMemory {
+    pub viewport: ViewPort,
+    /// The scroll position of the canvas, if it has been laid out.
+    pub scroll_offset: Option<Vec2>,
+    pub tool: ToolId,
+    pub selection: Selection,
+}
+
+/// The descriptions of the edits that an editor's undo and redo would
+/// reverse and reapply; `None` if there is nothing to undo or redo.
+#[derive(Debug, Clone, Default, PartialEq, Data)]
+pub struct UndoLabels {
+    pub undo: Option<String>,
+    pub redo: Option<String>,
 }
 
 /// A type constructed by a lens to represent our sidebearings.
@@ -125,6 +378,29 @@ pub struct Sidebearings {
     pub right: f64,
 }
 
+/// The extent of the glyph being edited, relative to its metrics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GlyphStats {
+    /// The bounding box of the glyph's outline, including components.
+    pub bounds: Rect,
+    pub left_sidebearing: f64,
+    pub right_sidebearing: f64,
+    /// How far the top of the glyph extends past the nearest metric line.
+    pub top: Overshoot,
+    /// How far the bottom of the glyph extends past the nearest metric line.
+    pub bottom: Overshoot,
+}
+
+/// The distance from a metric line to the edge of a glyph.
+///
+/// This is positive if the glyph extends beyond the line, and negative if it
+/// stops short of it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Overshoot {
+    pub line: &'static str,
+    pub distance: f64,
+}
+
 impl Workspace {
     /// a lens into a particular editor view.
     pub(crate) fn editor_state(id: SessionId) -> impl Lens<Workspace, EditorState> {
@@ -145,15 +421,122 @@ impl Workspace {
     #[allow(non_upper_case_globals)]
     pub(crate) const selected_glyph: lenses::SelectedGlyph = lenses::SelectedGlyph;
 
+    /// Load the UFO at `path` into a new workspace.
+    ///
+    /// This is the starting point for editing a font without a window; see
+    /// the crate documentation.
+    pub fn open(path: impl AsRef<Path>) -> Result<Workspace, Box<dyn Error>> {
+        let path = path.as_ref();
+        let ufo = Ufo::load(path)?;
+        let mut workspace = Workspace::default();
+        workspace.set_file(ufo, path.to_owned());
+        Ok(workspace)
+    }
+
+    /// Replace the font with a new one that has not been saved, closing any
+    /// open glyphs.
+    pub fn new_font(&mut self, ufo: Ufo) {
+        self.set_file(ufo, None);
+    }
+
+    /// Replace the font, closing any open glyphs; the edit sessions of the
+    /// previous font are discarded.
     pub fn set_file(&mut self, ufo: Ufo, path: impl Into<Option<PathBuf>>) {
+        self.close_all_tabs();
+        self.sessions = Default::default();
+        self.session_map = Default::default();
+        self.open_glyphs = Default::default();
+        self.editor_memory = Default::default();
+        self.selected = None;
+        let old_path = self.font.path.clone();
         let obj = FontObject {
             path: path.into().map(Into::into),
             ufo,
             placeholder: Arc::new(placeholder_outline()),
         };
         self.font = obj.into();
+        self.precision = Precision::from_fractional(has_fractional_coordinates(&self.font.ufo));
         self.info = SimpleFontInfo::from_font(&self.font);
+        self.features = Arc::new(self.font.ufo.features.clone().unwrap_or_default());
         self.build_path_cache();
+        // the status of a font somewhere else comes from `refresh_vcs_status`
+        if self.font.path != old_path {
+            self.vcs = None;
+        }
+        self.external_changes = None;
+        self.mark_saved();
+    }
+
+    /// Discard all unsaved changes, reloading the font from disk.
+    ///
+    /// Glyphs open in editor tabs are reloaded, and tabs for glyphs that
+    /// no longer exist are closed.
+    pub fn revert_to_saved(&mut self) -> Result<(), Box<dyn Error>> {
+        if matches!(self.save_status, SaveStatus::Saving) {
+            return Err("the font is being saved".into());
+        }
+        let path = self
+            .font
+            .path
+            .clone()
+            .ok_or("the font has not been saved")?;
+        let ufo = Ufo::load(&path)?;
+        let tabs: Vec<_> = self
+            .editor_tabs
+            .iter()
+            .filter_map(|id| self.sessions.get(id))
+            .map(|session| (session.id, session.name.clone(), session.viewport))
+            .collect();
+        let active_tab = self.active_tab;
+        let open_glyphs = self.open_glyphs.clone();
+        let editor_memory = self.editor_memory.clone();
+        self.set_file(ufo, path);
+        self.open_glyphs = open_glyphs;
+        self.editor_memory = editor_memory;
+
+        let mut editor_tabs = Vec::new();
+        let mut new_active_tab = None;
+        for (old_id, name, viewport) in tabs {
+            if self.font.ufo.get_glyph(&name).is_none() {
+                Arc::make_mut(&mut self.open_glyphs).remove(&name);
+                continue;
+            }
+            // a new id gives the tab a new editor, without the old undo history
+            let id = self.get_or_create_session(&name).id;
+            let session = Arc::make_mut(&mut self.sessions).get_mut(&id).unwrap();
+            Arc::make_mut(session).viewport = viewport;
+            if active_tab == Some(old_id) {
+                new_active_tab = Some(id);
+            }
+            editor_tabs.push(id);
+        }
+        self.active_tab = new_active_tab.or_else(|| editor_tabs.first().copied());
+        self.editor_tabs = Arc::new(editor_tabs);
+        Ok(())
+    }
+
+    /// Whether there are changes that have not been written to disk.
+    pub fn is_dirty(&self) -> bool {
+        !(self.font.same(&self.saved.font)
+            && self.info.same(&self.saved.info)
+            && self.features.same(&self.saved.features))
+            || matches!(self.save_status, SaveStatus::Failed(_))
+            || self.sessions.values().any(|session| session.is_dirty())
+    }
+
+    /// Record that the font and all open glyphs, as they are now, have been
+    /// saved.
+    fn mark_saved(&mut self) {
+        for session in Arc::make_mut(&mut self.sessions).values_mut() {
+            if session.is_dirty() {
+                Arc::make_mut(session).mark_saved();
+            }
+        }
+        self.saved = Arc::new(SavedFont {
+            font: self.font.clone(),
+            info: self.info.clone(),
+            features: self.features.clone(),
+        });
     }
 
     fn build_path_cache(&mut self) {
@@ -173,11 +556,682 @@ impl Workspace {
         });
     }
 
+    /// If the paths of the glyphs need building, as after opening a font,
+    /// build them on a background thread.
+    ///
+    /// When they're built, `PATHS_BUILT` is submitted to `sink`.
+    pub fn build_paths_in_background(&mut self, sink: ExtEventSink) {
+        if !self.cache.needs_building() {
+            return;
+        }
+        let unbuilt = Arc::make_mut(&mut self.cache).take_unbuilt().unwrap();
+        std::thread::spawn(move || {
+            let built = unbuilt.build();
+            if let Err(e) = sink.submit_command(
+                crate::consts::cmd::PATHS_BUILT,
+                Box::new(built),
+                Target::Auto,
+            ) {
+                log::error!("failed to report built paths: '{}'", e);
+            }
+        });
+    }
+
+    /// Add the paths built by `build_paths_in_background`.
+    pub fn set_built_paths(&mut self, built: &BuiltPaths) {
+        Arc::make_mut(&mut self.cache).set_built(built);
+    }
+
     pub fn save(&mut self) -> Result<(), Box<dyn Error>> {
+        self.flush_sessions();
+        if let Some(path) = self.font.path.as_ref() {
+            write_ufo_at_path(&self.font.ufo, path, false)?;
+            self.mark_saved();
+        } else {
+            log::error!("save called with no path set");
+        }
+        Ok(())
+    }
+
+    /// Save the font to a new location, which becomes the font's path.
+    pub fn save_as(&mut self, path: &Path) -> Result<(), Box<dyn Error>> {
+        Arc::make_mut(&mut self.font).path = Some(ufo_path(path));
+        self.save()
+    }
+
+    /// Write the font to its path on a background thread, so that large
+    /// fonts don't block the UI.
+    ///
+    /// When writing finishes, `SAVE_FINISHED` is submitted to `sink`.
+    pub fn save_in_background(&mut self, sink: ExtEventSink) {
+        if matches!(self.save_status, SaveStatus::Saving) {
+            self.save_pending = true;
+            return;
+        }
+        let path = match self.font.path.clone() {
+            Some(path) => path,
+            None => {
+                log::error!("save called with no path set");
+                return;
+            }
+        };
+        self.flush_sessions();
+        // glyphs are shared, so this copy is cheap
+        let ufo = self.font.ufo.clone();
+        // if writing fails, `is_dirty` still reports the changes as unsaved
+        self.mark_saved();
+        self.save_status = SaveStatus::Saving;
+        std::thread::spawn(move || {
+            let result = write_ufo_at_path(&ufo, &path, false).map_err(|e| e.to_string());
+            if let Err(e) = sink.submit_command(
+                crate::consts::cmd::SAVE_FINISHED,
+                Box::new(result),
+                Target::Auto,
+            ) {
+                log::error!("failed to report save result: '{}'", e);
+            }
+        });
+    }
+
+    /// Save the font to a new location, which becomes the font's path.
+    pub fn save_as_in_background(&mut self, path: &Path, sink: ExtEventSink) {
+        Arc::make_mut(&mut self.font).path = Some(ufo_path(path));
+        self.save_in_background(sink);
+    }
+
+    /// Write a copy of the font to `path` on a background thread, leaving
+    /// the font's own path, and whether it has unsaved changes, as they are.
+    ///
+    /// When writing finishes, `SAVE_FINISHED` is submitted to `sink`.
+    pub fn save_copy_in_background(&mut self, path: &Path, sink: ExtEventSink) {
+        let path = ufo_path(path);
+        if self.font.path.as_deref() == Some(path.as_path()) {
+            self.save_in_background(sink);
+            return;
+        }
+        if let Some(font_path) = self.font.path.as_ref() {
+            if path.starts_with(font_path) {
+                log::error!("cannot save a copy inside the font at {:?}", font_path);
+                return;
+            }
+        }
+        if matches!(self.save_status, SaveStatus::Saving) {
+            log::warn!("cannot save a copy while the font is being saved");
+            return;
+        }
+        let ufo = self.flushed_ufo();
+        self.save_status = SaveStatus::Saving;
+        std::thread::spawn(move || {
+            let result = write_ufo_at_path(&ufo, &path, false).map_err(|e| e.to_string());
+            if let Err(e) = sink.submit_command(
+                crate::consts::cmd::SAVE_FINISHED,
+                Box::new(result),
+                Target::Auto,
+            ) {
+                log::error!("failed to report save result: '{}'", e);
+            }
+        });
+    }
+
+    /// Record the result of a save started by `save_in_background`.
+    ///
+    /// If the font was saved again while the save was in progress, another
+    /// save is started.
+    pub fn save_finished(&mut self, result: &Result<(), String>, sink: ExtEventSink) {
+        self.save_status = match result {
+            Ok(()) => SaveStatus::Idle,
+            Err(e) => {
+                log::error!("saving failed: '{}'", e);
+                SaveStatus::Failed(e.as_str().into())
+            }
+        };
+        self.refresh_vcs_status(sink.clone());
+        if std::mem::take(&mut self.save_pending) {
+            self.save_in_background(sink);
+        }
+    }
+
+    /// Compare the font with `ufo`, a copy just loaded from disk, and record
+    /// the glyphs that differ, so that the user can choose to reload them.
+    ///
+    /// Returns `true` if any glyphs differ.
+    pub fn note_external_changes(&mut self, ufo: Ufo) -> bool {
+        let mut glyphs: Vec<GlyphName> = self
+            .font
+            .ufo
+            .iter_names()
+            .chain(ufo.iter_names())
+            .filter(|name| self.font.ufo.get_glyph(name) != ufo.get_glyph(name))
+            .collect();
+        glyphs.sort();
+        glyphs.dedup();
+        self.external_changes = if glyphs.is_empty() {
+            None
+        } else {
+            Some(Arc::new(ExternalChanges { ufo, glyphs }))
+        };
+        self.external_changes.is_some()
+    }
+
+    /// Replace glyphs with the versions found by `note_external_changes`.
+    ///
+    /// Glyphs open in an editor are reloaded only if they have no unsaved
+    /// edits; the names of those that are kept as they are are returned.
+    pub fn reload_external_changes(&mut self) -> Vec<GlyphName> {
+        let changes = match self.external_changes.take() {
+            Some(changes) => changes,
+            None => return Vec::new(),
+        };
+        let mut kept = Vec::new();
+        for name in &changes.glyphs {
+            let new_glyph = changes.ufo.get_glyph(name).cloned();
+            let session_id = self.session_map.get(name).copied();
+            if let Some(id) = session_id {
+                let unsaved = match self.font.ufo.get_glyph(name) {
+                    Some(glyph) => **glyph != self.sessions[&id].to_norad_glyph(),
+                    None => true,
+                };
+                if unsaved || new_glyph.is_none() {
+                    kept.push(name.clone());
+                    continue;
+                }
+            }
+            let layer = self.font_mut().ufo.get_default_layer_mut().unwrap();
+            match new_glyph {
+                Some(glyph) => layer.insert_glyph(glyph),
+                None => {
+                    layer.remove_glyph(name);
+                }
+            }
+            self.reload_session(name);
+        }
+        self.build_path_cache();
+        kept
+    }
+
+    /// If the glyph `name` is open, rebuild its edit session from the font,
+    /// keeping the session's id and viewport so that its editor stays open
+    /// where it was.
+    fn reload_session(&mut self, name: &GlyphName) {
+        if let Some(id) = self.session_map.get(name).copied() {
+            let mut session = EditSession::new(name, self);
+            session.id = id;
+            session.viewport = self.sessions[&id].viewport;
+            Arc::make_mut(&mut self.sessions).insert(id, Arc::new(session));
+        }
+    }
+
+    /// Find which glyphs have changed since the last commit, if the font is
+    /// in a git repository.
+    ///
+    /// Git is run on a background thread; when it finishes, `VCS_STATUS` is
+    /// submitted to `sink`.
+    pub fn refresh_vcs_status(&self, sink: ExtEventSink) {
+        let path = match self.font.path.as_ref() {
+            Some(path) => path.to_path_buf(),
+            None => return,
+        };
+        std::thread::spawn(move || {
+            let status = GitStatus::for_ufo(&path);
+            report_vcs_status(&sink, path, status);
+        });
+    }
+
+    /// Record the status found by `refresh_vcs_status` or `commit`, unless
+    /// another font has been opened since.
+    pub fn set_vcs_status(&mut self, path: &Path, status: Option<Arc<GitStatus>>) {
+        if self.font.path.as_deref() == Some(path) {
+            self.vcs = status;
+        }
+    }
+
+    /// Save the font, and commit it to its git repository.
+    ///
+    /// Git is run on a background thread; when it finishes, the new status
+    /// is submitted to `sink`.
+    pub fn commit(&mut self, message: &str, sink: ExtEventSink) -> Result<(), Box<dyn Error>> {
+        if matches!(self.save_status, SaveStatus::Saving) {
+            return Err("the font is still being saved".into());
+        }
+        self.save()?;
+        let (mut vcs, path) = match (self.vcs.as_ref(), self.font.path.as_ref()) {
+            (Some(vcs), Some(path)) => (GitStatus::clone(vcs), path.to_path_buf()),
+            _ => return Err("the font is not in a repository".into()),
+        };
+        let message = message.to_owned();
+        std::thread::spawn(move || {
+            if let Err(e) = vcs.commit(&message) {
+                log::error!("commit failed: '{}'", e);
+            }
+            report_vcs_status(&sink, path, Some(vcs));
+        });
+        Ok(())
+    }
+
+    /// `true` if the glyph has changed since the font was last committed.
+    pub fn is_modified_in_vcs(&self, name: &GlyphName) -> bool {
+        self.vcs
+            .as_ref()
+            .map(|vcs| vcs.modified_glyphs.contains(name))
+            .unwrap_or(false)
+    }
+
+    /// Write a copy of the font to `path`, applying the provided options.
+    ///
+    /// Unlike `save`, this does not change the font's path, and any changes
+    /// made by the options are applied only to the exported copy.
+    pub fn export(&self, path: &Path, options: ExportOptions) -> Result<(), Box<dyn Error>> {
+        let mut ufo = self.flushed_ufo();
+        if options.remove_overlaps {
+            let layer = ufo.get_default_layer_mut().unwrap();
+            let glyphs = layer
+                .iter_contents()
+                .map(|glyph| crate::overlap::remove_overlaps(&glyph, self.precision))
+                .collect::<Vec<_>>();
+            for glyph in glyphs {
+                layer.insert_glyph(glyph);
+            }
+        }
+        write_ufo_at_path(&ufo, path, options.normalize)
+    }
+
+    /// Replace the font with one that has been edited by a script.
+    ///
+    /// The edit sessions of `changed` glyphs are reloaded, keeping their
+    /// ids so that their editors stay open. Locked glyphs keep their
+    /// current contents; their names are returned.
+    pub(crate) fn apply_script_changes(
+        &mut self,
+        mut ufo: Ufo,
+        metrics: FontMetrics,
+        changed: &BTreeSet<GlyphName>,
+    ) -> Vec<GlyphName> {
+        let locked: Vec<GlyphName> = changed
+            .iter()
+            .filter(|name| self.is_glyph_locked(name))
+            .cloned()
+            .collect();
+        let layer = ufo.get_default_layer_mut().unwrap();
+        for name in &locked {
+            let glyph = self.font.ufo.get_glyph(name).unwrap();
+            layer.insert_glyph(Arc::clone(glyph));
+        }
+        self.font_mut().ufo = ufo;
+        self.info.metrics = metrics;
+        let info = self.info.clone();
+        self.font_mut().update_info(&info);
+        for name in changed.iter().filter(|name| !locked.contains(name)) {
+            self.reload_session(name);
+        }
+        self.build_path_cache();
+        locked
+    }
+
+    /// The font's vertical metrics.
+    pub(crate) fn metrics(&self) -> &FontMetrics {
+        &self.info.metrics
+    }
+
+    /// The glyph that 'Export Glyph Image' and the JSON import and export
+    /// commands apply to: the glyph in the active editor tab, or else the
+    /// glyph selected in the grid.
+    pub fn image_export_glyph(&self) -> Option<&GlyphName> {
+        self.active_glyph().or_else(|| self.selected.as_ref())
+    }
+
+    /// Write a glyph's edit session, including its guides, anchors and
+    /// selection, to `path` as JSON.
+    pub fn export_session_json(
+        &mut self,
+        name: &GlyphName,
+        path: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        if self.font.ufo.get_glyph(name).is_none() {
+            return Err(format!("no glyph named '{}'", name).into());
+        }
+        let session = self.get_or_create_session(name);
+        fs::write(path, crate::session_json::to_json(&session)?)?;
+        Ok(())
+    }
+
+    /// Replace a glyph's outline, guides, anchors and selection with those
+    /// in a file written by `export_session_json`.
+    pub fn import_session_json(
+        &mut self,
+        name: &GlyphName,
+        path: &Path,
+    ) -> Result<(), Box<dyn Error>> {
+        let json = fs::read_to_string(path)?;
+        self.edit_glyph(name, |session| {
+            crate::session_json::load_json(session, &json)
+        })
+        .ok_or_else(|| format!("no glyph named '{}'", name))??;
+        Ok(())
+    }
+
+    /// Render a glyph to an image file at `path`, using the current
+    /// `image_export` options.
+    pub fn export_glyph_image(&self, name: &GlyphName, path: &Path) -> Result<(), Box<dyn Error>> {
+        // an open session may have edits that aren't in the ufo yet
+        let glyph = match self
+            .session_map
+            .get(name)
+            .and_then(|id| self.sessions.get(id))
+        {
+            Some(session) => session.glyph.clone(),
+            None => self
+                .font
+                .ufo
+                .get_glyph(name)
+                .cloned()
+                .ok_or_else(|| format!("no glyph named '{}'", name))?,
+        };
+        let outline = self.get_bezier(name).unwrap_or_default();
+        let bounds = layout_bounds(&glyph, &self.info.metrics);
+        crate::glyph_image::write_glyph_image(
+            path,
+            &outline,
+            bounds,
+            &self.info.metrics,
+            &self.image_export,
+        )
+    }
+
+    /// Check every glyph for problems, updating `audit_issues`.
+    ///
+    /// Glyphs with an open session are checked as they are being edited.
+    pub fn run_audit(&mut self) {
+        let mut issues = Vec::new();
+        for name in self.sorted_glyph_names() {
+            let session = self
+                .session_map
+                .get(&name)
+                .and_then(|id| self.sessions.get(id));
+            match session {
+                Some(session) => issues.extend(audit_glyph(&session.to_norad_glyph())),
+                None => {
+                    if let Some(glyph) = self.font.ufo.get_glyph(&name) {
+                        issues.extend(audit_glyph(glyph));
+                    }
+                }
+            }
+        }
+        self.audit_issues = Arc::new(issues);
+    }
+
+    /// Search every glyph for contours with the same shape as the first
+    /// selected contour in the active editor tab, updating `shape_matches`.
+    ///
+    /// Returns `false` if no contour is selected.
+    pub(crate) fn find_selected_shape(&mut self) -> bool {
+        let session = match self.active_tab.and_then(|id| self.sessions.get(&id)) {
+            Some(session) => session,
+            None => return false,
+        };
+        let index = match session.selected_path_index() {
+            Some(index) => index,
+            None => return false,
+        };
+        let glyph = session.to_norad_glyph();
+        let contour = match glyph.outline {
+            Some(mut outline) if index < outline.contours.len() => {
+                outline.contours.swap_remove(index)
+            }
+            _ => return false,
+        };
+        let tolerance = self
+            .shape_query
+            .as_ref()
+            .map(|query| query.tolerance)
+            .unwrap_or(shape_search::DEFAULT_TOLERANCE);
+        self.shape_query = Some(ShapeQuery {
+            glyph: glyph.name,
+            index,
+            contour: Arc::new(contour),
+            tolerance,
+        });
+        self.run_shape_search();
+        true
+    }
+
+    /// Search every glyph for the contour in `shape_query`, updating
+    /// `shape_matches`.
+    ///
+    /// Glyphs with an open session are searched as they are being edited.
+    pub fn run_shape_search(&mut self) {
+        let query = match self.shape_query.as_ref() {
+            Some(query) => query,
+            None => return,
+        };
+        let mut matches = Vec::new();
+        for name in self.sorted_glyph_names() {
+            if let Some(glyph) = self.current_glyph(&name) {
+                matches.extend(query.find_in(&glyph));
+            }
+        }
+        self.shape_matches = Arc::new(matches);
+    }
+
+    /// The contours that `replace_shape_with_component` would replace with
+    /// the glyph `base`, each with the offset of the component replacing it.
+    ///
+    /// This is the contour searched for, if it still has the shape, and each
+    /// of its matches, except those in `base` itself or in locked glyphs.
+    /// Returns `None` if there has been no search, or if `base` exists but
+    /// has no contour of the shape.
+    pub(crate) fn shape_replacements(&self, base: &GlyphName) -> Option<Vec<ShapeMatch>> {
+        let query = self.shape_query.as_ref()?;
+        // where the shape is in `base`, relative to the contour searched for
+        let base_offset = match self.current_glyph(base) {
+            Some(glyph) => glyph
+                .outline
+                .iter()
+                .flat_map(|outline| outline.contours.iter())
+                .find_map(|contour| {
+                    shape_search::match_contour(&query.contour, contour, query.tolerance)
+                })?,
+            None => Vec2::ZERO,
+        };
+        let source = self
+            .current_glyph(&query.glyph)
+            .and_then(|glyph| glyph.outline)
+            .and_then(|outline| {
+                let contour = outline.contours.get(query.index)?;
+                shape_search::match_contour(&query.contour, contour, query.tolerance)
+            })
+            .map(|offset| ShapeMatch {
+                glyph: query.glyph.clone(),
+                index: query.index,
+                offset,
+            });
+        let replacements = source
+            .into_iter()
+            .chain(self.shape_matches.iter().cloned())
+            .filter(|found| found.glyph != *base && !self.is_glyph_locked(&found.glyph))
+            .map(|found| ShapeMatch {
+                offset: found.offset - base_offset,
+                ..found
+            })
+            .collect();
+        Some(replacements)
+    }
+
+    /// Replace the contour searched for by 'find shape', and each of its
+    /// matches, with a component of the glyph `base`.
+    ///
+    /// If `base` doesn't exist it is created, with the contour searched for
+    /// as its outline. The search is cleared, since the contours it found are
+    /// gone. Returns the number of contours replaced.
+    pub fn replace_shape_with_component(&mut self, base: &GlyphName) -> usize {
+        // glyphs may have been edited since the search, moving or changing
+        // the contours it found
+        self.run_shape_search();
+        let replacements = match self.shape_replacements(base) {
+            Some(replacements) => replacements,
+            None => {
+                log::warn!("'{}' has no contour with the shape searched for", base);
+                return 0;
+            }
+        };
+        if replacements.is_empty() {
+            return 0;
+        }
+        if self.font.ufo.get_glyph(base).is_none() {
+            let query = self.shape_query.as_ref().unwrap();
+            let after = query.glyph.clone();
+            let mut glyph = Glyph::new_named(base.clone());
+            glyph.outline = Some(norad::glyph::Outline {
+                contours: vec![(*query.contour).clone()],
+                components: Vec::new(),
+            });
+            self.insert_glyph_after(&after, glyph);
+        }
+
+        let mut by_glyph: BTreeMap<GlyphName, Vec<ShapeMatch>> = BTreeMap::new();
+        for found in replacements.iter() {
+            by_glyph
+                .entry(found.glyph.clone())
+                .or_default()
+                .push(found.clone());
+        }
+        for (name, found) in by_glyph {
+            let mut glyph = match self.current_glyph(&name) {
+                Some(glyph) => glyph,
+                None => continue,
+            };
+            let indices = found.iter().map(|found| found.index).collect::<Vec<_>>();
+            let components = found
+                .iter()
+                .map(|found| {
+                    let transform = Affine::translate(found.offset);
+                    norad::glyph::Component::new(base.clone(), transform.into(), None, None)
+                })
+                .collect::<Vec<_>>();
+            if let Some(outline) = glyph.outline.as_mut() {
+                let mut index = 0;
+                outline.contours.retain(|_| {
+                    index += 1;
+                    !indices.contains(&(index - 1))
+                });
+                outline.components.extend(components.iter().cloned());
+            }
+            self.font_mut()
+                .ufo
+                .get_default_layer_mut()
+                .unwrap()
+                .insert_glyph(glyph);
+            if let Some(id) = self.session_map.get(&name).copied() {
+                let sessions = Arc::make_mut(&mut self.sessions);
+                Arc::make_mut(sessions.get_mut(&id).unwrap())
+                    .replace_paths_with_components(&indices, &components);
+            }
+        }
+
+        self.shape_query = None;
+        self.shape_matches = Arc::new(Vec::new());
+        self.build_path_cache();
+        replacements.len()
+    }
+
+    /// Check which of `chars` the font supports, updating the coverage
+    /// report. `target` describes where the characters came from.
+    ///
+    /// Glyphs with an open session are checked as they are being edited.
+    pub fn check_coverage(&mut self, target: String, chars: Vec<char>) {
+        // the same glyph `glyph_for_char` would find, without sorting the
+        // glyphs for each character
+        let mut glyph_for_char = HashMap::new();
+        for name in self.sorted_glyph_names() {
+            let glyph = self.font.ufo.get_glyph(&name);
+            for chr in glyph
+                .and_then(|g| g.codepoints.as_ref())
+                .into_iter()
+                .flatten()
+            {
+                glyph_for_char.entry(*chr).or_insert_with(|| name.clone());
+            }
+        }
+        let mut entries = chars
+            .iter()
+            .map(|chr| {
+                let glyph = glyph_for_char.get(chr).cloned();
+                let status = CoverageStatus::for_glyph(
+                    glyph
+                        .as_ref()
+                        .and_then(|name| self.current_glyph(name))
+                        .as_ref(),
+                );
+                CoverageEntry {
+                    chr: *chr,
+                    glyph,
+                    status,
+                }
+            })
+            .collect::<Vec<_>>();
+        // stable, so each status keeps the order of `chars`
+        entries.sort_by_key(|entry| entry.status);
+        self.coverage.report = Some(CoverageReport {
+            target,
+            chars: Arc::new(chars),
+            entries: Arc::new(entries),
+        });
+    }
+
+    /// Check the font against the languages chosen in the coverage window.
+    pub fn check_language_coverage(&mut self) {
+        let languages = self.coverage.languages.clone();
+        let target = languages.iter().copied().collect::<Vec<_>>().join(", ");
+        let chars = crate::coverage::chars_for_languages(&languages);
+        self.check_coverage(target, chars);
+    }
+
+    /// Check the font again against the characters of the last coverage
+    /// report, if there is one.
+    pub fn refresh_coverage(&mut self) {
+        if let Some(report) = self.coverage.report.clone() {
+            self.check_coverage(report.target, report.chars.to_vec());
+        }
+    }
+
+    /// Add an empty glyph for each character the coverage report found
+    /// missing, returning the number of glyphs added.
+    pub fn add_missing_glyphs(&mut self) -> usize {
+        let names = match self.coverage.report.as_ref() {
+            Some(report) => report
+                .entries
+                .iter()
+                .filter(|entry| entry.status == CoverageStatus::Missing)
+                .map(|entry| crate::charset::name_for_char(entry.chr))
+                .collect::<Vec<_>>(),
+            None => return 0,
+        };
+        let added = self.add_glyphs(&names);
+        self.refresh_coverage();
+        added
+    }
+
+    /// Write the glyph of a single session into the UFO, unless it is
+    /// locked.
+    fn flush_session(&mut self, id: SessionId) {
+        let glyph = match self.sessions.get(&id) {
+            Some(session) if !session.is_read_only() => session.to_norad_glyph(),
+            _ => return,
+        };
+        self.font_mut()
+            .ufo
+            .get_default_layer_mut()
+            .unwrap()
+            .insert_glyph(glyph);
+    }
+
+    /// Write the current font info and the glyphs of all open sessions
+    /// into the UFO.
+    pub(crate) fn flush_sessions(&mut self) {
         let font_obj = Arc::make_mut(&mut self.font);
         font_obj.update_info(&self.info);
-        // flush all open sessions
-        for session in self.sessions.values() {
+        font_obj.update_features(&self.features);
+        // edits to locked glyphs are never written
+        for session in self.sessions.values().filter(|s| !s.is_read_only()) {
             let glyph = session.to_norad_glyph();
             font_obj
                 .ufo
@@ -185,22 +1239,14 @@ impl Workspace {
                 .unwrap()
                 .insert_glyph(glyph);
         }
-        if let Some(path) = font_obj.path.as_ref() {
-            // very careful save: we write to a temporary location, then
-            // backup the existing data, then move data from the temporary
-            // location to the actual path
-            let temp_path = temp_write_path(path);
-            log::info!("saving to {:?}", temp_path);
-            font_obj.ufo.save(&temp_path)?;
-            if let Some(backup_path) = backup_ufo_at_path(path)? {
-                log::info!("backing up existing data to {:?}", backup_path);
-            }
+    }
 
-            fs::rename(&temp_path, path)?;
-        } else {
-            log::error!("save called with no path set");
-        }
-        Ok(())
+    /// A copy of the UFO with the current font info and the glyphs of all
+    /// open sessions, leaving the workspace unchanged.
+    fn flushed_ufo(&self) -> Ufo {
+        let mut copy = self.clone();
+        copy.flush_sessions();
+        copy.font.ufo.clone()
     }
 
     pub fn get_or_create_session(&mut self, glyph_name: &GlyphName) -> Arc<EditSession> {
@@ -217,6 +1263,210 @@ impl Workspace {
             })
     }
 
+    /// The paths of a glyph's outline, from its edit session if it has one,
+    /// or else from the UFO.
+    pub fn glyph_paths(&self, glyph_name: &GlyphName) -> Option<Vec<crate::path::Path>> {
+        if let Some(session) = self
+            .session_map
+            .get(glyph_name)
+            .and_then(|id| self.sessions.get(id))
+        {
+            return Some(session.paths.as_ref().clone());
+        }
+        let glyph = self.font.ufo.get_glyph(glyph_name)?;
+        Some(
+            glyph
+                .outline
+                .as_ref()
+                .map(|outline| {
+                    outline
+                        .contours
+                        .iter()
+                        .map(|contour| crate::path::Path::from_norad(contour, self.precision))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Change a glyph with `f`, outside of any editor.
+    ///
+    /// `f` is given the glyph's edit session, which is created if needed, so
+    /// the change is seen by any editor showing the glyph and is written to
+    /// the UFO when the font is saved. Returns `None` if there is no glyph
+    /// with this name.
+    pub fn edit_glyph<R>(
+        &mut self,
+        glyph_name: &GlyphName,
+        f: impl FnOnce(&mut EditSession) -> R,
+    ) -> Option<R> {
+        self.font.ufo.get_glyph(glyph_name)?;
+        let id = self.get_or_create_session(glyph_name).id;
+        let session = Arc::make_mut(Arc::make_mut(&mut self.sessions).get_mut(&id)?);
+        let generation = session.generation();
+        let result = f(session);
+        if session.generation() != generation {
+            session.rebuild_glyph();
+            self.invalidate_path(glyph_name);
+        }
+        Some(result)
+    }
+
+    /// Add a tab for this glyph to the editor window, or switch to its
+    /// existing tab.
+    pub fn open_tab(&mut self, glyph_name: &GlyphName) -> SessionId {
+        let id = self.get_or_create_session(glyph_name).id;
+        if !self.editor_tabs.contains(&id) {
+            self.restore_session(id);
+            Arc::make_mut(&mut self.editor_tabs).push(id);
+        }
+        self.active_tab = Some(id);
+        id
+    }
+
+    /// Close every editor tab, remembering the state of each.
+    pub fn close_all_tabs(&mut self) {
+        for id in self.editor_tabs.clone().iter() {
+            self.remember_session(*id);
+            if self.flush_on_close {
+                self.flush_session(*id);
+            }
+        }
+        self.editor_tabs = Default::default();
+        self.active_tab = None;
+        self.undo_labels = Default::default();
+    }
+
+    /// Save the viewport and selection of a session, so that they can be
+    /// restored when its glyph is reopened.
+    fn remember_session(&mut self, id: SessionId) {
+        let session = match self.sessions.get(&id) {
+            Some(session) => session.clone(),
+            None => return,
+        };
+        let memory = Arc::make_mut(&mut self.editor_memory)
+            .entry(session.name.clone())
+            .or_insert_with(|| EditorMemory::new(&session));
+        memory.viewport = session.viewport;
+        memory.selection = session.selection.clone();
+    }
+
+    /// Restore the remembered viewport and selection of a session, if
+    /// there are any.
+    fn restore_session(&mut self, id: SessionId) {
+        let memory = match self
+            .sessions
+            .get(&id)
+            .and_then(|session| self.editor_memory.get(&session.name))
+        {
+            Some(memory) => memory.clone(),
+            None => return,
+        };
+        let session = Arc::make_mut(&mut self.sessions).get_mut(&id).unwrap();
+        let session = Arc::make_mut(session);
+        session.viewport = memory.viewport;
+        session.selection = memory.selection;
+    }
+
+    /// Close an editor tab; if it was active, its neighbour becomes active.
+    pub fn close_tab(&mut self, id: SessionId) {
+        let idx = match self.editor_tabs.iter().position(|tab| *tab == id) {
+            Some(idx) => idx,
+            None => return,
+        };
+        self.remember_session(id);
+        if self.flush_on_close {
+            self.flush_session(id);
+        }
+        Arc::make_mut(&mut self.undo_labels).remove(&id);
+        Arc::make_mut(&mut self.editor_tabs).remove(idx);
+        if self.active_tab == Some(id) {
+            self.active_tab = self
+                .editor_tabs
+                .get(idx)
+                .or_else(|| self.editor_tabs.last())
+                .copied();
+        }
+        if let Some(name) = self.sessions.get(&id).map(|s| s.name.clone()) {
+            Arc::make_mut(&mut self.open_glyphs).remove(&name);
+        }
+    }
+
+    /// Move the tab at index `from` to index `to`.
+    pub fn move_tab(&mut self, from: usize, to: usize) {
+        if from == to || from >= self.editor_tabs.len() || to >= self.editor_tabs.len() {
+            return;
+        }
+        let tabs = Arc::make_mut(&mut self.editor_tabs);
+        let id = tabs.remove(from);
+        tabs.insert(to, id);
+    }
+
+    /// Show the glyph `offset` places away in the font's glyph order in the
+    /// active editor tab, keeping the current zoom.
+    ///
+    /// Returns the new glyph, if there was one.
+    pub fn step_active_tab(&mut self, offset: isize) -> Option<GlyphName> {
+        let current = self.active_glyph()?.clone();
+        let order = self.glyph_names_sorted_by(GlyphSort::GlyphOrder);
+        let idx = order.iter().position(|name| *name == current)? as isize + offset;
+        if idx < 0 {
+            return None;
+        }
+        let next = order.get(idx as usize)?.clone();
+        self.replace_active_tab(&next);
+        Some(next)
+    }
+
+    /// Show `glyph_name` in the active editor tab, in place of its current glyph.
+    fn replace_active_tab(&mut self, glyph_name: &GlyphName) {
+        let old_id = match self.active_tab {
+            Some(id) => id,
+            None => {
+                self.open_tab(glyph_name);
+                return;
+            }
+        };
+        let old_session = self.sessions.get(&old_id).cloned();
+        let new_id = self.get_or_create_session(glyph_name).id;
+
+        // if the glyph already has a tab, we just switch to it
+        if !self.editor_tabs.contains(&new_id) {
+            self.remember_session(old_id);
+            self.restore_session(new_id);
+            let tabs = Arc::make_mut(&mut self.editor_tabs);
+            if let Some(idx) = tabs.iter().position(|id| *id == old_id) {
+                tabs[idx] = new_id;
+            }
+            if let Some(old_session) = old_session {
+                let session = Arc::make_mut(&mut self.sessions).get_mut(&new_id).unwrap();
+                Arc::make_mut(session).viewport.zoom = old_session.viewport.zoom;
+                // a remembered scroll position is wrong at the new zoom
+                if let Some(memory) = Arc::make_mut(&mut self.editor_memory).get_mut(glyph_name) {
+                    memory.scroll_offset = None;
+                }
+                let open = Arc::make_mut(&mut self.open_glyphs);
+                if let Some(window) = open.remove(&old_session.name) {
+                    open.insert(glyph_name.clone(), window);
+                }
+            }
+        }
+        self.active_tab = Some(new_id);
+        self.selected = Some(glyph_name.clone());
+    }
+
+    /// The name of the glyph in the active editor tab.
+    pub fn active_glyph(&self) -> Option<&GlyphName> {
+        self.active_tab
+            .and_then(|id| self.sessions.get(&id))
+            .map(|session| &session.name)
+    }
+
+    /// What undo and redo would do in the active editor tab.
+    pub fn active_undo_labels(&self) -> Option<&UndoLabels> {
+        self.active_tab.and_then(|id| self.undo_labels.get(&id))
+    }
+
     pub fn new_preview_session(&mut self) -> SessionId {
         let id = SessionId::next();
         Arc::make_mut(&mut self.previews).insert(
@@ -224,13 +1474,33 @@ impl Workspace {
             PreviewSession {
                 text: "Hamburgler".to_string().into(),
                 font_size: DEFAULT_PREVIEW_FONT_SIZE,
+                features: Default::default(),
+                show_metrics: false,
+                vertical: false,
             },
         );
         id
     }
 
-    pub(crate) fn get_bezier(&self, name: &GlyphName) -> Option<Arc<BezPath>> {
-        self.cache.get(name)
+    pub(crate) fn get_bezier(&self, name: &GlyphName) -> Option<Arc<BezPath>> {
+        // until the paths are built in the background, build them here
+        self.cache.get(name).or_else(|| {
+            self.cache.build(name, &|name| {
+                self.sessions
+                    .values()
+                    .find(|sesh| sesh.name == *name)
+                    .map(|sesh| &sesh.glyph)
+                    .or_else(|| self.font.ufo.get_glyph(name))
+            })
+        })
+    }
+
+    /// If the components of the glyph `name` lead back to it, a description
+    /// of the cycle, like 'a → b → a'.
+    pub(crate) fn component_cycle(&self, name: &GlyphName) -> Option<String> {
+        self.cache
+            .component_cycle(name)
+            .map(crate::bez_cache::describe_cycle)
     }
 
     /// After a glyph is edited this rebuilds the affected beziers.
@@ -265,6 +1535,22 @@ impl Workspace {
             .unwrap_or(DEFAULT_UNITS_PER_EM)
     }
 
+    /// The font's own guidelines, which apply to every glyph.
+    pub(crate) fn global_guides(&self) -> Vec<Guide> {
+        self.font
+            .ufo
+            .font_info
+            .as_ref()
+            .and_then(|info| info.guidelines.as_ref())
+            .map(|guides| {
+                guides
+                    .iter()
+                    .map(|guide| Guide::from_norad(guide, self.precision))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn add_new_glyph(&mut self) -> GlyphName {
         let mut name = String::from("newGlyph");
         let mut counter = 0;
@@ -284,7 +1570,105 @@ impl Workspace {
         name
     }
 
+    /// Add a new, empty glyph for each of `names` that is not already in
+    /// the font, returning the number of glyphs added.
+    pub fn add_glyphs(&mut self, names: &[String]) -> usize {
+        let missing = names
+            .iter()
+            .filter(|name| self.font.ufo.get_glyph(name.as_str()).is_none())
+            .cloned()
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return 0;
+        }
+
+        let layer = self.font_mut().ufo.get_default_layer_mut().unwrap();
+        for name in &missing {
+            let mut glyph = norad::Glyph::new_named(name.as_str());
+            glyph.codepoints = crate::glyph_names::codepoints_for_glyph(name);
+            layer.insert_glyph(glyph);
+        }
+        missing.len()
+    }
+
+    /// The first name made by adding a numbered suffix to `base`, like
+    /// `a.001`, that no glyph in the font has.
+    pub(crate) fn unused_glyph_name(&self, base: &str) -> GlyphName {
+        (1..)
+            .map(|i| format!("{}.{:03}", base, i))
+            .find(|name| self.font.ufo.get_glyph(name.as_str()).is_none())
+            .unwrap()
+            .into()
+    }
+
+    /// The glyph `name`, including any edits that have not been saved.
+    fn current_glyph(&self, name: &GlyphName) -> Option<Glyph> {
+        match self.session_map.get(name) {
+            Some(id) => Some(self.sessions[id].to_norad_glyph()),
+            None => self.font.ufo.get_glyph(name).map(|glyph| (**glyph).clone()),
+        }
+    }
+
+    /// Copy the glyph `name`, with its outline, components, anchors and
+    /// advance, to a new glyph named `new_name`.
+    ///
+    /// The copy has no codepoints, since those still belong to the original.
+    /// Returns `false` if `name` is missing or `new_name` is taken.
+    pub fn duplicate_glyph(&mut self, name: &GlyphName, new_name: GlyphName) -> bool {
+        if self.font.ufo.get_glyph(&new_name).is_some() {
+            log::warn!("cannot duplicate '{}': '{}' already exists", name, new_name);
+            return false;
+        }
+        let mut glyph = match self.current_glyph(name) {
+            Some(glyph) => glyph,
+            None => {
+                log::warn!("attempted to duplicate missing glyph '{}'", name);
+                return false;
+            }
+        };
+        glyph.name = new_name;
+        glyph.codepoints = None;
+        self.insert_glyph_after(name, glyph);
+        true
+    }
+
+    /// Add a new glyph made of a single component of the glyph `base`, and
+    /// with the same advance, returning the new glyph's name.
+    pub fn new_composite_glyph(&mut self, base: &GlyphName) -> Option<GlyphName> {
+        let advance = self.current_glyph(base)?.advance;
+        let new_name = self.unused_glyph_name(base);
+        let mut glyph = Glyph::new_named(new_name.clone());
+        glyph.advance = advance;
+        glyph.outline = Some(norad::glyph::Outline {
+            contours: Vec::new(),
+            components: vec![norad::glyph::Component::new(
+                base.clone(),
+                Affine::IDENTITY.into(),
+                None,
+                None,
+            )],
+        });
+        self.insert_glyph_after(base, glyph);
+        Some(new_name)
+    }
+
+    /// Add `glyph` to the font, following `after` in the glyph order.
+    fn insert_glyph_after(&mut self, after: &GlyphName, glyph: Glyph) {
+        let name = glyph.name.clone();
+        let font = self.font_mut();
+        font.ufo
+            .get_default_layer_mut()
+            .unwrap()
+            .insert_glyph(glyph);
+        font.insert_in_glyph_order(after, &name);
+    }
+
+    /// Delete the selected glyph, unless it is locked.
     pub fn delete_selected_glyph(&mut self) -> Option<Arc<Glyph>> {
+        if let Some(name) = self.selected.as_ref().filter(|n| self.is_glyph_locked(n)) {
+            log::warn!("cannot delete '{}': it is locked", name);
+            return None;
+        }
         self.selected.take().and_then(|name| {
             self.font_mut()
                 .ufo
@@ -294,8 +1678,121 @@ impl Workspace {
         })
     }
 
+    /// The glyphs that use `name` as a component, including in edits that
+    /// have not been saved.
+    pub fn component_users(&self, name: &GlyphName) -> Vec<GlyphName> {
+        let in_font = self
+            .font
+            .ufo
+            .get_default_layer()
+            .into_iter()
+            .flat_map(|layer| layer.iter_contents())
+            .filter(|glyph| {
+                glyph
+                    .outline
+                    .as_ref()
+                    .map(|o| o.components.iter().any(|c| c.base == *name))
+                    .unwrap_or(false)
+            })
+            .map(|glyph| glyph.name.clone());
+        let in_sessions = self
+            .sessions
+            .values()
+            .filter(|session| session.components.iter().any(|c| c.base == *name))
+            .map(|session| session.name.clone());
+        let users = in_font.chain(in_sessions).collect::<BTreeSet<_>>();
+        users.into_iter().filter(|user| user != name).collect()
+    }
+
+    /// Replace every use of the glyph `name` as a component with its outline,
+    /// so that the glyph can be deleted.
+    ///
+    /// If any of the glyphs using it are locked, nothing is decomposed, and
+    /// the locked glyphs are returned.
+    pub fn decompose_component_uses(&mut self, name: &GlyphName) -> Vec<GlyphName> {
+        let base = match self.current_glyph(name) {
+            Some(glyph) => glyph,
+            None => return Vec::new(),
+        };
+        let users = self.component_users(name);
+        let locked: Vec<GlyphName> = users
+            .iter()
+            .filter(|user| self.is_glyph_locked(user))
+            .cloned()
+            .collect();
+        if !locked.is_empty() {
+            return locked;
+        }
+        let layer = self.font_mut().ufo.get_default_layer_mut().unwrap();
+        for user in &users {
+            let glyph = match layer.get_glyph_mut(user) {
+                Some(glyph) => glyph,
+                None => continue,
+            };
+            let outline = match glyph.outline.as_mut() {
+                Some(outline) => outline,
+                None => continue,
+            };
+            let (uses, others): (Vec<_>, Vec<_>) = outline
+                .components
+                .drain(..)
+                .partition(|component| component.base == *name);
+            outline.components = others;
+            for component in uses {
+                let decomposed = crate::component::decompose(&base, component.transform.into());
+                outline.contours.extend(decomposed.contours);
+                outline.components.extend(decomposed.components);
+            }
+        }
+
+        for user in &users {
+            if let Some(id) = self.session_map.get(user).copied() {
+                let sessions = Arc::make_mut(&mut self.sessions);
+                Arc::make_mut(sessions.get_mut(&id).unwrap()).decompose_components(&base);
+            }
+        }
+        self.build_path_cache();
+        locked
+    }
+
     /// Rename a glyph everywhere it might be.
     pub fn rename_glyph(&mut self, old_name: GlyphName, new_name: GlyphName) {
+        self.rename_glyphs(&[(old_name, new_name)]);
+    }
+
+    /// Rename several glyphs, in order, updating the components, kerning
+    /// groups and kerning that refer to them.
+    ///
+    /// Locked glyphs are not renamed. Returns the number of glyphs renamed.
+    pub fn rename_glyphs(&mut self, renames: &[(GlyphName, GlyphName)]) -> usize {
+        let mut count = 0;
+        let mut components_changed = false;
+        for (old_name, new_name) in renames {
+            if self.rename_one_glyph(old_name.clone(), new_name.clone()) {
+                count += 1;
+                components_changed |= self.rename_references(old_name, new_name);
+            }
+        }
+        if components_changed {
+            self.build_path_cache();
+        }
+        count
+    }
+
+    /// Returns `false` if the glyph could not be renamed.
+    fn rename_one_glyph(&mut self, old_name: GlyphName, new_name: GlyphName) -> bool {
+        if self.font.ufo.get_glyph(&new_name).is_some() {
+            log::warn!(
+                "cannot rename '{}': '{}' already exists",
+                old_name,
+                new_name
+            );
+            return false;
+        }
+        if self.is_glyph_locked(&old_name) {
+            log::warn!("cannot rename '{}': it is locked", old_name);
+            return false;
+        }
         let font = self.font_mut();
         let mut glyph = match font
             .ufo
@@ -306,7 +1803,7 @@ impl Workspace {
             Some(g) => g,
             None => {
                 log::warn!("attempted to rename missing glyph '{}'", old_name);
-                return;
+                return false;
             }
         };
 
@@ -320,6 +1817,7 @@ impl Workspace {
             .get_default_layer_mut()
             .unwrap()
             .insert_glyph(glyph);
+        font.rename_in_glyph_order(&old_name, &new_name);
 
         // and if this is the selected glyph, change that too;
         if self.selected.as_ref() == Some(&old_name) {
@@ -337,14 +1835,86 @@ impl Workspace {
             Arc::make_mut(session).rename(new_name.clone());
         }
 
+        if let Some(memory) = Arc::make_mut(&mut self.editor_memory).remove(&old_name) {
+            Arc::make_mut(&mut self.editor_memory).insert(new_name.clone(), memory);
+        }
+
         if self.open_glyphs.contains_key(&old_name) {
             let open = Arc::make_mut(&mut self.open_glyphs);
             let window = open.remove(&old_name).unwrap();
             open.insert(new_name, window);
         }
+        true
+    }
+
+    /// Point components, kerning groups and kerning at the glyph's new name.
+    ///
+    /// Returns `true` if any components were changed.
+    fn rename_references(&mut self, old_name: &GlyphName, new_name: &GlyphName) -> bool {
+        let ufo = &mut self.font_mut().ufo;
+        let layer = ufo.get_default_layer_mut().unwrap();
+        let users = layer
+            .iter_contents()
+            .filter(|glyph| {
+                glyph
+                    .outline
+                    .as_ref()
+                    .map(|o| o.components.iter().any(|c| c.base == *old_name))
+                    .unwrap_or(false)
+            })
+            .map(|glyph| glyph.name.clone())
+            .collect::<Vec<_>>();
+        for name in &users {
+            let glyph = layer.get_glyph_mut(name).unwrap();
+            let components = glyph
+                .outline
+                .iter_mut()
+                .flat_map(|o| o.components.iter_mut());
+            for component in components.filter(|c| c.base == *old_name) {
+                component.base = new_name.clone();
+            }
+        }
+
+        if let Some(groups) = ufo.groups.as_mut() {
+            for member in groups.values_mut().flatten() {
+                if *member == *old_name {
+                    *member = new_name.clone();
+                }
+            }
+        }
+
+        if let Some(kerning) = ufo.kerning.as_mut() {
+            if let Some(seconds) = kerning.remove(&**old_name) {
+                kerning.insert(new_name.to_string(), seconds);
+            }
+            for seconds in kerning.values_mut() {
+                if let Some(value) = seconds.remove(&**old_name) {
+                    seconds.insert(new_name.to_string(), value);
+                }
+            }
+        }
+
+        let session_users = self
+            .sessions
+            .iter()
+            .filter(|(_, session)| session.components.iter().any(|c| c.base == *old_name))
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        for id in &session_users {
+            let sessions = Arc::make_mut(&mut self.sessions);
+            let session = Arc::make_mut(sessions.get_mut(id).unwrap());
+            session.rename_component_base(old_name, new_name);
+        }
+        !users.is_empty() || !session_users.is_empty()
     }
 
+    /// Set the advance, codepoints, note and lib of a glyph, unless it is
+    /// locked.
     pub fn update_glyph_metadata(&mut self, changed: &Arc<Glyph>) {
+        if self.is_glyph_locked(&changed.name) {
+            log::warn!("cannot change '{}': it is locked", changed.name);
+            return;
+        }
         // update the active session, if one exists
         if let Some(session_id) = self.session_map.get(&changed.name) {
             let sessions = Arc::make_mut(&mut self.sessions);
@@ -355,12 +1925,330 @@ impl Workspace {
         // update the UFO;
         if let Some(glyph) = self.font_mut().ufo.get_glyph_mut(&changed.name) {
             glyph.advance = changed.advance.clone();
+            glyph.codepoints = changed.codepoints.clone();
+            glyph.note = changed.note.clone();
+            glyph.lib = changed.lib.clone();
         }
     }
 
+    /// Replace the lib of the glyph `name`.
+    pub fn set_glyph_lib(&mut self, name: &GlyphName, lib: Option<plist::Dictionary>) {
+        let mut glyph = match self.font.ufo.get_glyph(name) {
+            Some(glyph) => glyph.clone(),
+            None => {
+                log::warn!("attempted to set lib of missing glyph '{}'", name);
+                return;
+            }
+        };
+        Arc::make_mut(&mut glyph).lib = lib;
+        self.update_glyph_metadata(&glyph);
+    }
+
     pub fn font_mut(&mut self) -> &mut FontObject {
         Arc::make_mut(&mut self.font)
     }
+
+    /// The names of all glyphs in the font, in the current sort order.
+    pub fn sorted_glyph_names(&self) -> Vec<GlyphName> {
+        self.glyph_names_sorted_by(self.glyph_sort)
+    }
+
+    /// The glyph mapped to `chr`; if there are several, the first in the
+    /// current sort order.
+    pub fn glyph_for_char(&self, chr: char) -> Option<GlyphName> {
+        self.sorted_glyph_names().into_iter().find(|name| {
+            self.font
+                .ufo
+                .get_glyph(name)
+                .and_then(|glyph| glyph.codepoints.as_ref())
+                .map(|cps| cps.contains(&chr))
+                .unwrap_or(false)
+        })
+    }
+
+    /// The glyphs matching a search query, best matches first.
+    ///
+    /// A single character matches the glyph it is mapped to; after that
+    /// come exact names, then names starting with the query, then names
+    /// containing it, ignoring case.
+    pub fn find_glyphs(&self, query: &str, limit: usize) -> Vec<GlyphName> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Vec::new();
+        }
+        let mut chars = query.chars();
+        let single_char = match (chars.next(), chars.next()) {
+            (Some(chr), None) => Some(chr),
+            _ => None,
+        };
+        let lowercase = query.to_lowercase();
+        let mut matches = self
+            .sorted_glyph_names()
+            .into_iter()
+            .filter_map(|name| {
+                let has_char = single_char
+                    .and_then(|chr| {
+                        let glyph = self.font.ufo.get_glyph(&name)?;
+                        glyph.codepoints.as_ref().map(|cps| cps.contains(&chr))
+                    })
+                    .unwrap_or(false);
+                let name_lower = name.to_lowercase();
+                let rank = if has_char {
+                    0
+                } else if &*name == query {
+                    1
+                } else if name_lower.starts_with(&lowercase) {
+                    2
+                } else if name_lower.contains(&lowercase) {
+                    3
+                } else {
+                    return None;
+                };
+                Some((rank, name))
+            })
+            .collect::<Vec<_>>();
+        // this is stable, so equal matches stay in the grid's order
+        matches.sort_by_key(|(rank, _)| *rank);
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    fn glyph_names_sorted_by(&self, sort: GlyphSort) -> Vec<GlyphName> {
+        let mut names: Vec<GlyphName> = self.font.ufo.iter_names().collect();
+        match sort {
+            GlyphSort::GlyphOrder => {
+                let order = self.font.glyph_order();
+                let positions: HashMap<&str, usize> = order
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| (&**name, i))
+                    .collect();
+                names.sort_by_key(|name| positions.get(&**name).copied().unwrap_or(usize::MAX));
+            }
+            GlyphSort::Codepoint => names.sort_by_cached_key(|name| {
+                let codepoint = self.font.codepoint(name).map(|c| c as u32);
+                (codepoint.unwrap_or(u32::MAX), name.clone())
+            }),
+            GlyphSort::Name => names.sort(),
+        }
+        names
+    }
+
+    pub fn mark_color(&self, name: &GlyphName) -> Option<MarkColor> {
+        self.font
+            .ufo
+            .get_glyph(name)
+            .and_then(|glyph| MarkColor::for_glyph(glyph))
+    }
+
+    /// Set or clear the mark color of a glyph, including in any open editor,
+    /// unless it is locked.
+    pub fn set_mark_color(&mut self, name: &GlyphName, color: Option<MarkColor>) {
+        if self.mark_color(name) == color {
+            return;
+        }
+        if self.is_glyph_locked(name) {
+            log::warn!("cannot mark '{}': it is locked", name);
+            return;
+        }
+        match self.font_mut().ufo.get_glyph_mut(name) {
+            Some(glyph) => MarkColor::apply(color, glyph),
+            None => {
+                log::warn!("attempted to mark missing glyph '{}'", name);
+                return;
+            }
+        }
+
+        // the session is written back when saving, so it needs the lib too
+        if let Some(session_id) = self.session_map.get(name) {
+            let sessions = Arc::make_mut(&mut self.sessions);
+            let session = Arc::make_mut(sessions.get_mut(session_id).unwrap());
+            MarkColor::apply(color, Arc::make_mut(&mut session.glyph));
+        }
+    }
+
+    /// `true` if opening the glyph in an editor would convert quadratic
+    /// contours to cubics; a glyph that is already open has been converted.
+    pub fn needs_quadratic_conversion(&self, name: &GlyphName) -> bool {
+        !self.session_map.contains_key(name)
+            && self
+                .font
+                .ufo
+                .get_glyph(name)
+                .and_then(|glyph| glyph.outline.as_ref())
+                .map(|outline| outline.contours.iter().any(crate::quadratic::is_quadratic))
+                .unwrap_or(false)
+    }
+
+    /// `true` if the glyph is locked against editing.
+    pub fn is_glyph_locked(&self, name: &GlyphName) -> bool {
+        self.font
+            .ufo
+            .get_glyph(name)
+            .map(|glyph| glyph_lock::is_locked(glyph))
+            .unwrap_or(false)
+    }
+
+    /// Lock or unlock a glyph, including in any open editor.
+    ///
+    /// Edits made in an editor before the glyph is locked are kept; once it
+    /// is locked, its editor no longer writes to the font.
+    pub fn set_glyph_locked(&mut self, name: &GlyphName, locked: bool) {
+        if self.is_glyph_locked(name) == locked {
+            return;
+        }
+        let session = self
+            .session_map
+            .get(name)
+            .and_then(|id| self.sessions.get(id))
+            .cloned();
+        let layer = self.font_mut().ufo.get_default_layer_mut().unwrap();
+        if let Some(session) = session.as_ref() {
+            layer.insert_glyph(session.to_norad_glyph());
+        }
+        match layer.get_glyph_mut(name) {
+            Some(glyph) => glyph_lock::set_locked(glyph, locked),
+            None => {
+                log::warn!("attempted to lock missing glyph '{}'", name);
+                return;
+            }
+        }
+
+        if let Some(session) = session {
+            let sessions = Arc::make_mut(&mut self.sessions);
+            let session = Arc::make_mut(sessions.get_mut(&session.id).unwrap());
+            glyph_lock::set_locked(Arc::make_mut(&mut session.glyph), locked);
+        }
+    }
+
+    /// Move the glyph `name` so that it directly precedes `before` in the
+    /// font's glyph order, or to the end of the order if `before` is `None`.
+    ///
+    /// This writes the complete order of all glyphs to `public.glyphOrder`.
+    pub fn move_glyph(&mut self, name: &GlyphName, before: Option<&GlyphName>) {
+        if Some(name) == before {
+            return;
+        }
+        let mut order = self.glyph_names_sorted_by(GlyphSort::GlyphOrder);
+        let from = match order.iter().position(|n| n == name) {
+            Some(idx) => idx,
+            None => {
+                log::warn!("attempted to move missing glyph '{}'", name);
+                return;
+            }
+        };
+        let name = order.remove(from);
+        let to = before
+            .and_then(|before| order.iter().position(|n| n == before))
+            .unwrap_or_else(|| order.len());
+        order.insert(to, name);
+        self.font_mut().set_glyph_order(&order);
+    }
+
+    /// The glyphs in the glyph grid, in order, split into sections if
+    /// grouping is enabled.
+    pub(crate) fn grid_sections(&self) -> Vec<GridSection> {
+        let mut names = self.sorted_glyph_names();
+        if let Some(filter) = self.mark_filter {
+            names.retain(|name| {
+                self.mark_color(name)
+                    .map(|color| color.matches(&filter))
+                    .unwrap_or(false)
+            });
+        }
+        if let (Some(filter), Some(report)) = (self.coverage.filter, self.coverage.report.as_ref())
+        {
+            names.retain(|name| report.status_of(name) == Some(filter));
+        }
+        if !self.group_glyphs {
+            return vec![GridSection {
+                title: None,
+                glyphs: names,
+            }];
+        }
+
+        let mut grouped = names
+            .into_iter()
+            .map(|name| (GlyphGroup::for_char(self.font.codepoint(&name)), name))
+            .collect::<Vec<_>>();
+        // stable, so we preserve the sort order within each group
+        grouped.sort_by_key(|(group, _)| *group);
+
+        let mut sections: Vec<GridSection> = Vec::new();
+        let mut current = None;
+        for (group, name) in grouped {
+            if current != Some(group) {
+                current = Some(group);
+                sections.push(GridSection {
+                    title: Some(group.to_string()),
+                    glyphs: Vec::new(),
+                });
+            }
+            sections.last_mut().unwrap().glyphs.push(name);
+        }
+        sections
+    }
+}
+
+impl ImageFormat {
+    /// The file extension for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Svg => "svg",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl Default for ImageExportOptions {
+    fn default() -> Self {
+        ImageExportOptions {
+            format: ImageFormat::Svg,
+            size: 512.0,
+            show_metrics: false,
+        }
+    }
+}
+
+impl Default for PasteOffset {
+    fn default() -> Self {
+        // down and to the right, in design space
+        PasteOffset { x: 20.0, y: -20.0 }
+    }
+}
+
+impl Default for SaveStatus {
+    fn default() -> Self {
+        SaveStatus::Idle
+    }
+}
+
+impl Default for NudgeAmounts {
+    fn default() -> Self {
+        NudgeAmounts {
+            plain: 1.0,
+            shift: 10.0,
+            cmd: 100.0,
+        }
+    }
+}
+
+impl Default for ZoomOptions {
+    fn default() -> Self {
+        ZoomOptions {
+            sensitivity: 1.0,
+            animate: true,
+        }
+    }
+}
+
+impl Default for GlyphSort {
+    fn default() -> Self {
+        GlyphSort::GlyphOrder
+    }
 }
 
 #[allow(non_upper_case_globals)]
@@ -368,12 +2256,25 @@ impl GlyphDetail {
     /// A lens for retrieving the glyph's codepoint
     pub const codepoint: lenses::Codepoint = lenses::Codepoint;
 
+    /// A lens for the glyph's full list of codepoints, as `U+XXXX` items
+    /// separated by spaces.
+    pub const codepoints: lenses::Codepoints = lenses::Codepoints;
+
     /// A lens for the glyph's advance.
     pub const advance: lenses::Advance = lenses::Advance;
 
+    /// A lens for the glyph's advance height, used in vertical layout.
+    pub const advance_height: lenses::AdvanceHeight = lenses::AdvanceHeight;
+
+    /// A lens for the y coordinate of the glyph's vertical origin.
+    pub const vertical_origin: lenses::VerticalOrigin = lenses::VerticalOrigin;
+
     /// A lens for the glyph's name.
     pub const glyph_name: lenses::GlyphName = lenses::GlyphName;
 
+    /// A lens for the glyph's note; an empty string means no note.
+    pub const note: lenses::Note = lenses::Note;
+
     /// Get the fully resolved (including components) bezier path for this glyph.
     ///
     /// Returns the placeholder glyph if this glyph has no outline.
@@ -395,6 +2296,23 @@ impl GlyphDetail {
             .cloned()
     }
 
+    /// This glyph's codepoints, as `U+XXXX` items separated by spaces.
+    fn codepoints_text(&self) -> String {
+        let chars = self.glyph.codepoints.as_deref().unwrap_or_default();
+        crate::charset::format_codepoints(chars)
+    }
+
+    /// The characters of this glyph's codepoints, for display.
+    pub fn codepoint_chars(&self) -> String {
+        self.glyph
+            .codepoints
+            .iter()
+            .flatten()
+            .map(|chr| crate::charset::display_char(*chr))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     /// The bounds of the metric square, in design space. (0, 0) is at the
     /// left edge of the baseline, and y is up.
     pub(crate) fn layout_bounds(&self) -> Rect {
@@ -432,6 +2350,64 @@ impl EditorState {
         Arc::make_mut(&mut self.session)
     }
 
+    /// How this glyph's editor was left, if it has been edited before.
+    pub(crate) fn remembered(&self) -> Option<&EditorMemory> {
+        self.font.editor_memory.get(&self.session.name)
+    }
+
+    /// Remember the tool selected in this glyph's editor.
+    pub(crate) fn remember_tool(&mut self, tool: ToolId) {
+        if self.remembered().map(|m| m.tool) != Some(tool) {
+            self.memory_mut().tool = tool;
+        }
+    }
+
+    /// Remember the scroll position of this glyph's editor.
+    pub(crate) fn remember_scroll_offset(&mut self, offset: Vec2) {
+        if self.remembered().and_then(|m| m.scroll_offset) != Some(offset) {
+            self.memory_mut().scroll_offset = Some(offset);
+        }
+    }
+
+    /// Record what undo and redo would do in this editor.
+    pub(crate) fn set_undo_labels(&mut self, labels: UndoLabels) {
+        if self.font.undo_labels.get(&self.session.id) != Some(&labels) {
+            Arc::make_mut(&mut self.font.undo_labels).insert(self.session.id, labels);
+        }
+    }
+
+    fn memory_mut(&mut self) -> &mut EditorMemory {
+        let session = &self.session;
+        Arc::make_mut(&mut self.font.editor_memory)
+            .entry(session.name.clone())
+            .or_insert_with(|| EditorMemory::new(session))
+    }
+
+    /// The bounding box, sidebearings, and overshoots of the glyph, or
+    /// `None` if it has no outline.
+    pub(crate) fn glyph_stats(&self) -> Option<GlyphStats> {
+        let outline = self.font.get_bezier(&self.session.name)?;
+        if outline.elements().is_empty() {
+            return None;
+        }
+        let bounds = outline.bounding_box();
+        let Sidebearings { left, right } = self.compute_sidebearings();
+        let lines = [
+            ("baseline", 0.0),
+            ("x-height", self.metrics.value(MetricLine::XHeight)),
+            ("cap height", self.metrics.value(MetricLine::CapHeight)),
+            ("ascender", self.metrics.value(MetricLine::Ascender)),
+            ("descender", self.metrics.value(MetricLine::Descender)),
+        ];
+        Some(GlyphStats {
+            bounds,
+            left_sidebearing: left,
+            right_sidebearing: right,
+            top: Overshoot::nearest(&lines, bounds.max_y(), true),
+            bottom: Overshoot::nearest(&lines, bounds.min_y(), false),
+        })
+    }
+
     fn compute_sidebearings(&self) -> Sidebearings {
         let content_region = self
             .font
@@ -448,6 +2424,33 @@ impl EditorState {
     }
 }
 
+impl Overshoot {
+    /// The overshoot of the glyph edge at `y` past the nearest of `lines`.
+    ///
+    /// `is_top` is `true` if this is the top edge, which overshoots when it
+    /// is above the line.
+    fn nearest(lines: &[(&'static str, f64)], y: f64, is_top: bool) -> Overshoot {
+        let (line, value) = lines
+            .iter()
+            .copied()
+            .min_by(|a, b| (a.1 - y).abs().partial_cmp(&(b.1 - y).abs()).unwrap())
+            .unwrap();
+        let distance = if is_top { y - value } else { value - y };
+        Overshoot { line, distance }
+    }
+}
+
+impl EditorMemory {
+    fn new(session: &EditSession) -> Self {
+        EditorMemory {
+            viewport: session.viewport,
+            scroll_offset: None,
+            tool: "Select",
+            selection: session.selection.clone(),
+        }
+    }
+}
+
 impl PreviewState {
     pub(crate) fn text(&self) -> &str {
         &self.session.text
@@ -456,6 +2459,18 @@ impl PreviewState {
     pub(crate) fn font_size(&self) -> f64 {
         self.session.font_size
     }
+
+    pub(crate) fn features(&self) -> &str {
+        &self.session.features
+    }
+
+    pub(crate) fn show_metrics(&self) -> bool {
+        self.session.show_metrics
+    }
+
+    pub(crate) fn vertical(&self) -> bool {
+        self.session.vertical
+    }
 }
 
 impl FontObject {
@@ -491,8 +2506,85 @@ impl FontObject {
             if existing_info.metrics.italic_angle != info.metrics.italic_angle {
                 font_info.italic_angle = info.metrics.italic_angle.map(Into::into);
             }
+            if existing_info.names != info.names {
+                info.names.apply(font_info);
+            }
+            if existing_info.os2 != info.os2 {
+                info.os2.apply(font_info);
+            }
+            if existing_info.postscript != info.postscript {
+                info.postscript.apply(font_info);
+            }
+            if existing_info.legal != info.legal {
+                info.legal.apply(font_info);
+            }
+        }
+    }
+
+    /// The glyph order stored in the font's lib, if any.
+    pub fn glyph_order(&self) -> Vec<GlyphName> {
+        self.ufo
+            .lib
+            .as_ref()
+            .and_then(|lib| lib.get(GLYPH_ORDER_LIB_KEY))
+            .and_then(|order| order.as_array())
+            .map(|order| {
+                order
+                    .iter()
+                    .filter_map(|name| name.as_string())
+                    .map(GlyphName::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn update_features(&mut self, features: &str) {
+        if self.ufo.features.as_deref().unwrap_or_default() != features {
+            self.ufo.features = if features.is_empty() {
+                None
+            } else {
+                Some(features.to_owned())
+            };
+        }
+    }
+
+    fn set_glyph_order(&mut self, order: &[GlyphName]) {
+        let order = order
+            .iter()
+            .map(|name| plist::Value::String(name.to_string()))
+            .collect();
+        self.ufo
+            .lib
+            .get_or_insert_with(Default::default)
+            .insert(GLYPH_ORDER_LIB_KEY.into(), plist::Value::Array(order));
+    }
+
+    /// Replace `old` with `new` in the glyph order, if it is present.
+    fn rename_in_glyph_order(&mut self, old: &GlyphName, new: &GlyphName) {
+        let mut order = self.glyph_order();
+        if let Some(name) = order.iter_mut().find(|name| *name == old) {
+            *name = new.clone();
+            self.set_glyph_order(&order);
+        }
+    }
+
+    /// Put `name` directly after `after` in the glyph order, if `after` is
+    /// in it.
+    fn insert_in_glyph_order(&mut self, after: &GlyphName, name: &GlyphName) {
+        let mut order = self.glyph_order();
+        if let Some(idx) = order.iter().position(|n| n == after) {
+            order.insert(idx + 1, name.clone());
+            self.set_glyph_order(&order);
         }
     }
+
+    /// The first codepoint of the glyph with this name, if it has one.
+    fn codepoint(&self, name: &str) -> Option<char> {
+        self.ufo
+            .get_glyph(name)
+            .and_then(|glyph| glyph.codepoints.as_ref())
+            .and_then(|codepoints| codepoints.first().copied())
+    }
 }
 
 use std::convert::TryInto;
@@ -517,6 +2609,7 @@ impl Default for FontObject {
 
 impl SimpleFontInfo {
     fn from_font(font: &FontObject) -> Self {
+        let font_info = font.ufo.font_info.as_ref();
         SimpleFontInfo {
             family_name: font
                 .ufo
@@ -536,12 +2629,36 @@ impl SimpleFontInfo {
                 .as_ref()
                 .map(FontMetrics::from)
                 .unwrap_or_default(),
+            names: font_info.map(NameInfo::from_info).unwrap_or_default(),
+            os2: font_info.map(Os2Info::from_info).unwrap_or_default(),
+            postscript: font_info.map(PostscriptInfo::from_info).unwrap_or_default(),
+            legal: font_info.map(LegalInfo::from_info).unwrap_or_default(),
         }
     }
 
     pub(crate) fn font_metrics(&self) -> &FontMetrics {
         &self.metrics
     }
+
+    /// Returns a description of each invalid or inconsistent value, if any.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.family_name.is_empty() {
+            problems.push("Family name is required.".into());
+        }
+        if self.metrics.units_per_em <= 0.0 {
+            problems.push("Units per em must be greater than zero.".into());
+        }
+        if let (Some(asc), Some(desc)) = (self.metrics.ascender, self.metrics.descender) {
+            if asc < desc {
+                problems.push("Ascender is below the descender.".into());
+            }
+        }
+        self.names.validate(&mut problems);
+        self.os2.validate(&mut problems);
+        self.postscript.validate(&mut problems);
+        problems
+    }
 }
 
 impl Default for SimpleFontInfo {
@@ -550,6 +2667,10 @@ impl Default for SimpleFontInfo {
             metrics: Default::default(),
             family_name: "".into(),
             style_name: "".into(),
+            names: Default::default(),
+            os2: Default::default(),
+            postscript: Default::default(),
+            legal: Default::default(),
         }
     }
 }
@@ -570,6 +2691,63 @@ impl<'a> From<&'a FontInfo> for FontMetrics {
     }
 }
 
+impl MetricLine {
+    pub(crate) const ALL: [MetricLine; 4] = [
+        MetricLine::Ascender,
+        MetricLine::CapHeight,
+        MetricLine::XHeight,
+        MetricLine::Descender,
+    ];
+}
+
+impl FontMetrics {
+    /// Returns the value for this line, or a default based on the upm if
+    /// it is not set.
+    pub(crate) fn value(&self, line: MetricLine) -> f64 {
+        let upm = self.units_per_em;
+        match line {
+            MetricLine::Ascender => self.ascender.unwrap_or_else(|| (upm * 0.8).round()),
+            MetricLine::CapHeight => self.cap_height.unwrap_or_else(|| (upm * 0.7).round()),
+            MetricLine::XHeight => self.x_height.unwrap_or_else(|| (upm * 0.5).round()),
+            MetricLine::Descender => self.descender.unwrap_or_else(|| -(upm * 0.2).round()),
+        }
+    }
+
+    /// The glyph's advance height, or the upm if it has none.
+    pub(crate) fn advance_height(&self, glyph: &Glyph) -> f64 {
+        glyph
+            .advance
+            .as_ref()
+            .map(|a| a.height as f64)
+            .filter(|h| *h != 0.0)
+            .unwrap_or(self.units_per_em)
+    }
+
+    /// The y coordinate of the glyph's vertical origin, or the ascender if
+    /// the glyph does not specify one.
+    pub(crate) fn vertical_origin(&self, glyph: &Glyph) -> f64 {
+        glyph
+            .lib
+            .as_ref()
+            .and_then(|lib| lib.get(VERTICAL_ORIGIN_LIB_KEY))
+            .and_then(|v| {
+                v.as_real()
+                    .or_else(|| v.as_signed_integer().map(|i| i as f64))
+            })
+            .unwrap_or_else(|| self.value(MetricLine::Ascender))
+    }
+
+    pub(crate) fn set_value(&mut self, line: MetricLine, value: f64) {
+        let field = match line {
+            MetricLine::Ascender => &mut self.ascender,
+            MetricLine::CapHeight => &mut self.cap_height,
+            MetricLine::XHeight => &mut self.x_height,
+            MetricLine::Descender => &mut self.descender,
+        };
+        *field = Some(value);
+    }
+}
+
 impl Default for FontMetrics {
     fn default() -> Self {
         FontMetrics {
@@ -592,6 +2770,7 @@ mod lenses {
     use super::{
         EditorState as EditorState_, GlyphDetail, GridGlyph as GridGlyph_,
         PreviewState as PreviewState_, SessionId, Sidebearings as Sidebearings_, Workspace,
+        VERTICAL_ORIGIN_LIB_KEY,
     };
 
     /// Workspace -> EditorState
@@ -615,8 +2794,20 @@ mod lenses {
     /// GlyphPlus -> char
     pub struct Codepoint;
 
+    /// GlyphDetail -> String
+    pub struct Codepoints;
+
     pub struct Advance;
 
+    /// GlyphDetail -> f64
+    pub struct AdvanceHeight;
+
+    /// GlyphDetail -> f64
+    pub struct VerticalOrigin;
+
+    /// GlyphDetail -> String
+    pub struct Note;
+
     pub struct Sidebearings;
 
     impl Lens<Workspace, EditorState_> for EditorState {
@@ -641,15 +2832,28 @@ mod lenses {
                 session,
             };
             let v = f(&mut glyph);
-            if !data
-                .sessions
-                .get(&self.0)
-                .map(|s| s.same(&glyph.session))
-                .unwrap_or(true)
-            {
+            if !data.editor_memory.same(&glyph.font.editor_memory) {
+                data.editor_memory = glyph.font.editor_memory;
+            }
+            if !data.undo_labels.same(&glyph.font.undo_labels) {
+                data.undo_labels = glyph.font.undo_labels;
+            }
+            if !data.info.metrics.same(&glyph.metrics) {
+                data.info.metrics = glyph.metrics;
+                let info = data.info.clone();
+                Arc::make_mut(&mut data.font).update_info(&info);
+            }
+            let old_session = data.sessions.get(&self.0);
+            if !old_session.map(|s| s.same(&glyph.session)).unwrap_or(true) {
+                // selection and viewport changes don't touch the outline
+                let outline_changed = old_session
+                    .map(|s| s.generation() != glyph.session.generation())
+                    .unwrap_or(true);
                 let name = glyph.session.name.clone();
                 Arc::make_mut(&mut data.sessions).insert(self.0, glyph.session);
-                data.invalidate_path(&name);
+                if outline_changed {
+                    data.invalidate_path(&name);
+                }
             }
             v
         }
@@ -732,7 +2936,7 @@ mod lenses {
         ) -> V {
             let mut g = EditorGlyph::make_data(data);
             let r = f(&mut g);
-            if !g.glyph.same(&data.session.glyph) {
+            if !g.glyph.same(&data.session.glyph) && !data.session.is_read_only() {
                 data.session_mut().update_glyph_metadata(&g.glyph);
             }
             r
@@ -809,6 +3013,9 @@ mod lenses {
                 outline: outline.unwrap_or_else(|| data.font.placeholder.clone()),
                 upm: data.units_per_em(),
                 is_selected,
+                mark_color: data.mark_color(&self.0),
+                is_locked: data.is_glyph_locked(&self.0),
+                is_modified: data.is_modified_in_vcs(&self.0),
             });
             f(&glyph)
         }
@@ -826,6 +3033,9 @@ mod lenses {
                 outline: outline.unwrap_or_else(|| data.font.placeholder.clone()),
                 upm: data.units_per_em(),
                 is_selected,
+                mark_color: data.mark_color(&self.0),
+                is_locked: data.is_glyph_locked(&self.0),
+                is_modified: data.is_modified_in_vcs(&self.0),
             });
             let r = f(&mut glyph);
             // we track selections by having the grid item set this flag,
@@ -862,6 +3072,29 @@ mod lenses {
         }
     }
 
+    impl Lens<GlyphDetail, String> for Codepoints {
+        fn with<V, F: FnOnce(&String) -> V>(&self, data: &GlyphDetail, f: F) -> V {
+            let text = data.codepoints_text();
+            f(&text)
+        }
+
+        fn with_mut<V, F: FnOnce(&mut String) -> V>(&self, data: &mut GlyphDetail, f: F) -> V {
+            let text = data.codepoints_text();
+            let mut text2 = text.clone();
+            let result = f(&mut text2);
+            if text2 != text {
+                match crate::charset::parse_codepoints(&text2) {
+                    Ok(chars) => {
+                        let glyph = Arc::make_mut(&mut data.glyph);
+                        glyph.codepoints = if chars.is_empty() { None } else { Some(chars) };
+                    }
+                    Err(item) => log::warn!("invalid codepoint '{}'", item),
+                }
+            }
+            result
+        }
+    }
+
     impl Lens<GlyphDetail, f32> for Advance {
         fn with<V, F: FnOnce(&f32) -> V>(&self, data: &GlyphDetail, f: F) -> V {
             let advance = data.glyph.advance.as_ref().map(|a| a.width).unwrap_or(0.);
@@ -875,7 +3108,8 @@ mod lenses {
             let result = f(&mut advance2);
             if advance2 != advance {
                 let glyph = Arc::make_mut(&mut data.glyph);
-                if advance2 == 0. {
+                let height = glyph.advance.as_ref().map(|a| a.height).unwrap_or(0.);
+                if advance2 == 0. && height == 0. {
                     glyph.advance = None;
                 } else {
                     let mut advance = glyph.advance.clone().unwrap_or_default();
@@ -887,6 +3121,65 @@ mod lenses {
         }
     }
 
+    impl Lens<GlyphDetail, f64> for AdvanceHeight {
+        fn with<V, F: FnOnce(&f64) -> V>(&self, data: &GlyphDetail, f: F) -> V {
+            f(&data.metrics.advance_height(&data.glyph))
+        }
+
+        #[allow(clippy::float_cmp)]
+        fn with_mut<V, F: FnOnce(&mut f64) -> V>(&self, data: &mut GlyphDetail, f: F) -> V {
+            let height = data.metrics.advance_height(&data.glyph);
+            let mut height2 = height;
+            let result = f(&mut height2);
+            if height2 != height {
+                let glyph = Arc::make_mut(&mut data.glyph);
+                let mut advance = glyph.advance.clone().unwrap_or_default();
+                advance.height = height2 as f32;
+                glyph.advance = Some(advance);
+            }
+            result
+        }
+    }
+
+    impl Lens<GlyphDetail, f64> for VerticalOrigin {
+        fn with<V, F: FnOnce(&f64) -> V>(&self, data: &GlyphDetail, f: F) -> V {
+            f(&data.metrics.vertical_origin(&data.glyph))
+        }
+
+        #[allow(clippy::float_cmp)]
+        fn with_mut<V, F: FnOnce(&mut f64) -> V>(&self, data: &mut GlyphDetail, f: F) -> V {
+            let origin = data.metrics.vertical_origin(&data.glyph);
+            let mut origin2 = origin;
+            let result = f(&mut origin2);
+            if origin2 != origin {
+                let glyph = Arc::make_mut(&mut data.glyph);
+                glyph
+                    .lib
+                    .get_or_insert_with(Default::default)
+                    .insert(VERTICAL_ORIGIN_LIB_KEY.into(), plist::Value::Real(origin2));
+            }
+            result
+        }
+    }
+
+    impl Lens<GlyphDetail, String> for Note {
+        fn with<V, F: FnOnce(&String) -> V>(&self, data: &GlyphDetail, f: F) -> V {
+            let note = data.glyph.note.clone().unwrap_or_default();
+            f(&note)
+        }
+
+        fn with_mut<V, F: FnOnce(&mut String) -> V>(&self, data: &mut GlyphDetail, f: F) -> V {
+            let note = data.glyph.note.clone().unwrap_or_default();
+            let mut note2 = note.clone();
+            let result = f(&mut note2);
+            if note2 != note {
+                let glyph = Arc::make_mut(&mut data.glyph);
+                glyph.note = if note2.is_empty() { None } else { Some(note2) };
+            }
+            result
+        }
+    }
+
     impl Lens<GlyphDetail, GlyphName_> for GlyphName {
         fn with<V, F: FnOnce(&GlyphName_) -> V>(&self, data: &GlyphDetail, f: F) -> V {
             f(&data.glyph.name)
@@ -905,71 +3198,72 @@ mod lenses {
 /// Convert this glyph's path from the UFO representation into a `kurbo::BezPath`
 /// (which we know how to draw.)
 pub(crate) fn path_for_glyph(glyph: &Glyph) -> Option<BezPath> {
-    /// An outline can have multiple contours, which correspond to subpaths
-    fn add_contour(path: &mut BezPath, contour: &Contour) {
-        let mut close: Option<&ContourPoint> = None;
-
-        let start_idx = match contour
-            .points
+    if let Some(outline) = glyph.outline.as_ref() {
+        let mut path = BezPath::new();
+        outline
+            .contours
             .iter()
-            .position(|pt| pt.typ != PointType::OffCurve)
-        {
-            Some(idx) => idx,
-            None => return,
-        };
+            .for_each(|c| append_contour(&mut path, c));
+        Some(path)
+    } else {
+        None
+    }
+}
 
-        let first = &contour.points[start_idx];
-        path.move_to((first.x as f64, first.y as f64));
-        if first.typ != PointType::Move {
-            close = Some(first);
-        }
+/// Append a UFO contour to a `BezPath`.
+///
+/// An outline can have multiple contours, which correspond to subpaths.
+pub(crate) fn append_contour(path: &mut BezPath, contour: &Contour) {
+    if crate::quadratic::is_quadratic(contour) {
+        return append_contour(path, &crate::quadratic::to_cubic(contour));
+    }
+    let mut close: Option<&ContourPoint> = None;
+
+    let start_idx = match contour
+        .points
+        .iter()
+        .position(|pt| pt.typ != PointType::OffCurve)
+    {
+        Some(idx) => idx,
+        None => return,
+    };
+
+    let first = &contour.points[start_idx];
+    path.move_to((first.x as f64, first.y as f64));
+    if first.typ != PointType::Move {
+        close = Some(first);
+    }
 
-        let mut controls = Vec::with_capacity(2);
+    let mut controls = Vec::with_capacity(2);
 
-        let mut add_curve = |to_point: Point, controls: &mut Vec<Point>| {
-            match controls.as_slice() {
-                &[] => path.line_to(to_point),
-                &[a] => path.quad_to(a, to_point),
-                &[a, b] => path.curve_to(a, b, to_point),
-                _illegal => panic!("existence of second point implies first"),
-            };
-            controls.clear();
+    let mut add_curve = |to_point: Point, controls: &mut Vec<Point>| {
+        match controls.as_slice() {
+            &[] => path.line_to(to_point),
+            &[a] => path.quad_to(a, to_point),
+            &[a, b] => path.curve_to(a, b, to_point),
+            _illegal => panic!("existence of second point implies first"),
         };
+        controls.clear();
+    };
 
-        let mut idx = (start_idx + 1) % contour.points.len();
-        while idx != start_idx {
-            let next = &contour.points[idx];
-            let point = Point::new(next.x as f64, next.y as f64);
-            match next.typ {
-                PointType::OffCurve => controls.push(point),
-                PointType::Line => {
-                    debug_assert!(controls.is_empty(), "line type cannot follow offcurve");
-                    add_curve(point, &mut controls);
-                }
-                PointType::Curve => add_curve(point, &mut controls),
-                PointType::QCurve => {
-                    log::warn!("quadratic curves are currently ignored");
-                    add_curve(point, &mut controls);
-                }
-                PointType::Move => debug_assert!(false, "illegal move point in path?"),
+    let mut idx = (start_idx + 1) % contour.points.len();
+    while idx != start_idx {
+        let next = &contour.points[idx];
+        let point = Point::new(next.x as f64, next.y as f64);
+        match next.typ {
+            PointType::OffCurve => controls.push(point),
+            PointType::Line => {
+                debug_assert!(controls.is_empty(), "line type cannot follow offcurve");
+                add_curve(point, &mut controls);
             }
-            idx = (idx + 1) % contour.points.len();
-        }
-
-        if let Some(to_close) = close.take() {
-            add_curve((to_close.x as f64, to_close.y as f64).into(), &mut controls);
+            PointType::Curve | PointType::QCurve => add_curve(point, &mut controls),
+            PointType::Move => debug_assert!(false, "illegal move point in path?"),
         }
+        idx = (idx + 1) % contour.points.len();
     }
 
-    if let Some(outline) = glyph.outline.as_ref() {
-        let mut path = BezPath::new();
-        outline
-            .contours
-            .iter()
-            .for_each(|c| add_contour(&mut path, c));
-        Some(path)
-    } else {
-        None
+    if let Some(to_close) = close.take() {
+        add_curve((to_close.x as f64, to_close.y as f64).into(), &mut controls);
     }
 }
 
@@ -1027,6 +3321,58 @@ fn placeholder_outline() -> BezPath {
     bez
 }
 
+/// Write `ufo` to `path`, backing up any existing data.
+///
+/// This is a very careful save: we write to a temporary location, then
+/// backup the existing data, then move data from the temporary
+/// location to the actual path.
+///
+/// If `normalize` is `true`, the written files are normalized before they
+/// are moved into place.
+fn write_ufo_at_path(ufo: &Ufo, path: &Path, normalize: bool) -> Result<(), Box<dyn Error>> {
+    let temp_path = temp_write_path(path);
+    log::info!("saving to {:?}", temp_path);
+    ufo.save(&temp_path)?;
+    if normalize {
+        crate::normalize::normalize_ufo(&temp_path)?;
+    }
+    if let Some(backup_path) = backup_ufo_at_path(path)? {
+        log::info!("backing up existing data to {:?}", backup_path);
+    }
+
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+/// Send the git status of the font at `path` from a background thread.
+fn report_vcs_status(sink: &ExtEventSink, path: PathBuf, status: Option<GitStatus>) {
+    let payload = (path, status.map(Arc::new));
+    if let Err(e) = sink.submit_command(
+        crate::consts::cmd::VCS_STATUS,
+        Box::new(payload),
+        Target::Auto,
+    ) {
+        log::error!("failed to report git status: '{}'", e);
+    }
+}
+
+/// `path` with a `.ufo` extension, if it doesn't already have one.
+///
+/// Save panels don't always add the extension.
+fn ufo_path(path: &Path) -> PathBuf {
+    let has_extension = path
+        .extension()
+        .map(|ext| ext.eq_ignore_ascii_case("ufo"))
+        .unwrap_or(false);
+    if has_extension {
+        path.to_owned()
+    } else {
+        let mut name = path.file_name().unwrap_or_default().to_owned();
+        name.push(".ufo");
+        path.with_file_name(name)
+    }
+}
+
 /// Move the contents of the file at `path` to another location.
 ///
 /// If `path` exists, returns the backup location on success.
@@ -1073,9 +3419,65 @@ fn temp_write_path(path: &Path) -> PathBuf {
     }
 }
 
+/// Whether any point in the font's default layer has a fractional coordinate.
+fn has_fractional_coordinates(ufo: &Ufo) -> bool {
+    ufo.get_default_layer()
+        .iter()
+        .flat_map(|layer| layer.iter_contents())
+        .any(|glyph| {
+            glyph
+                .outline
+                .iter()
+                .flat_map(|outline| outline.contours.iter())
+                .flat_map(|contour| contour.points.iter())
+                .any(|pt| pt.x.fract() != 0.0 || pt.y.fract() != 0.0)
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::font_builder::FontBuilder;
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn overshoot() {
+        let lines = [("baseline", 0.0), ("x-height", 500.0), ("ascender", 750.0)];
+        let top = Overshoot::nearest(&lines, 512.0, true);
+        assert_eq!(top.line, "x-height");
+        assert_eq!(top.distance, 12.0);
+        let bottom = Overshoot::nearest(&lines, -10.0, false);
+        assert_eq!(bottom.line, "baseline");
+        assert_eq!(bottom.distance, 10.0);
+        let short = Overshoot::nearest(&lines, 740.0, true);
+        assert_eq!(short.line, "ascender");
+        assert_eq!(short.distance, -10.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn vertical_metrics() {
+        let metrics = FontMetrics {
+            ascender: Some(880.0),
+            ..Default::default()
+        };
+        let mut glyph = Glyph::new_named("uni4E00");
+        assert_eq!(metrics.advance_height(&glyph), 1000.0);
+        assert_eq!(metrics.vertical_origin(&glyph), 880.0);
+
+        glyph.advance = Some(norad::glyph::Advance {
+            width: 1000.0,
+            height: 1100.0,
+        });
+        let mut lib = plist::Dictionary::new();
+        lib.insert(
+            VERTICAL_ORIGIN_LIB_KEY.into(),
+            plist::Value::Integer(900.into()),
+        );
+        glyph.lib = Some(lib);
+        assert_eq!(metrics.advance_height(&glyph), 1100.0);
+        assert_eq!(metrics.vertical_origin(&glyph), 900.0);
+    }
+
     #[test]
     fn font_info_changes() {
         let mut fontobj = FontObject::default();
@@ -1101,4 +3503,559 @@ mod tests {
         assert_eq!(font_info.style_name, Some("Extra Cheese".to_string()));
         assert_eq!(font_info.descender, Some(420.0.into()));
     }
+
+    #[test]
+    fn extended_font_info() {
+        let mut fontobj = FontObject::default();
+        let mut info = SimpleFontInfo::from_font(&fontobj);
+        assert!(info.problems().is_empty());
+
+        info.legal.copyright = "Copyright the Authors".into();
+        info.postscript.blue_values = Arc::new(vec![-10.0, 0.0, 500.0, 510.0]);
+        fontobj.update_info(&info);
+        let font_info = fontobj.ufo.font_info.clone().unwrap();
+        assert_eq!(
+            font_info.copyright,
+            Some("Copyright the Authors".to_string())
+        );
+        assert_eq!(font_info.postscript_blue_values.map(|v| v.len()), Some(4));
+        assert!(font_info.open_type_os2_vendor_id.is_none());
+
+        let round_trip = SimpleFontInfo::from_font(&fontobj);
+        assert_eq!(round_trip.postscript, info.postscript);
+
+        info.postscript.blue_values = Arc::new(vec![0.0, -10.0, 500.0]);
+        assert_eq!(info.problems().len(), 2);
+    }
+
+    #[test]
+    fn locked_points_are_kept() {
+        let point = |x, y, typ, smooth| ContourPoint::new(x, y, typ, smooth, None, None, None);
+        let contour = Contour::new(
+            vec![
+                point(0., 0., PointType::Line, false),
+                point(0., 40., PointType::OffCurve, false),
+                point(40., 100., PointType::OffCurve, false),
+                point(100., 100., PointType::Curve, true),
+                point(160., 100., PointType::OffCurve, false),
+                point(200., 70., PointType::OffCurve, false),
+                point(200., 0., PointType::Curve, false),
+            ],
+            None,
+            None,
+        );
+        let mut glyph = Glyph::new_named("a");
+        glyph.outline = Some(norad::glyph::Outline {
+            contours: vec![contour],
+            components: Vec::new(),
+        });
+        let edit = |lock: bool, f: &dyn Fn(&mut EditSession)| {
+            let mut workspace = Workspace::default();
+            let layer = workspace.font_mut().ufo.get_default_layer_mut().unwrap();
+            layer.insert_glyph(glyph.clone());
+            workspace
+                .edit_glyph(&"a".into(), |session| {
+                    session.select_all();
+                    if lock {
+                        session.lock_selected_contours();
+                    }
+                    f(session);
+                    session.to_norad_glyph()
+                })
+                .unwrap()
+        };
+
+        let ops: [&dyn Fn(&mut EditSession); 4] = [
+            &|session| session.harmonize_selection(),
+            &|session| session.balance_selection(),
+            &|session| session.simplify_selection(50.0),
+            &|session| {
+                let seg = session.paths[0].iter_segments().next().unwrap();
+                let tunni = crate::tunni::Tunni::for_segment(&seg).unwrap();
+                session.drag_tunni_point(&tunni, Point::new(-20., 120.), false);
+            },
+        ];
+        let unchanged = edit(false, &|_| ());
+        for op in ops.iter() {
+            assert_ne!(edit(false, *op), unchanged);
+            assert_eq!(edit(true, *op), unchanged);
+        }
+    }
+
+    #[test]
+    fn only_quadratic_contours_are_saved_as_quadratics() {
+        let point = |x, y, typ| ContourPoint::new(x, y, typ, false, None, None, None);
+        let quadratic = Contour::new(
+            vec![
+                point(0., 0., PointType::Line),
+                point(150., 300., PointType::OffCurve),
+                point(300., 0., PointType::QCurve),
+            ],
+            None,
+            None,
+        );
+        let cubic = Contour::new(
+            vec![
+                point(0., 400., PointType::Line),
+                point(0., 500., PointType::OffCurve),
+                point(300., 500., PointType::OffCurve),
+                point(300., 400., PointType::Curve),
+            ],
+            None,
+            None,
+        );
+        let mut workspace = Workspace::default();
+        let mut glyph = Glyph::new_named("o");
+        glyph.outline = Some(norad::glyph::Outline {
+            contours: vec![quadratic],
+            components: Vec::new(),
+        });
+        let layer = workspace.font_mut().ufo.get_default_layer_mut().unwrap();
+        layer.insert_glyph(glyph);
+
+        let glyph = workspace
+            .edit_glyph(&"o".into(), |session| {
+                let path = crate::path::Path::from_norad(&cubic, Precision::Integer);
+                session.add_path(path);
+                session.to_norad_glyph()
+            })
+            .unwrap();
+        let contours = &glyph.outline.as_ref().unwrap().contours;
+        assert!(crate::quadratic::is_quadratic(&contours[0]));
+        assert!(!crate::quadratic::is_quadratic(&contours[1]));
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn replace_shape_with_component() {
+        let square = |x: f32, size: f32| {
+            let points = vec![(x, 0.), (x + size, 0.), (x + size, size), (x, size)]
+                .into_iter()
+                .map(|(x, y)| ContourPoint::new(x, y, PointType::Line, false, None, None, None))
+                .collect();
+            Contour::new(points, None, None)
+        };
+        let mut workspace = Workspace::default();
+        let layer = workspace.font_mut().ufo.get_default_layer_mut().unwrap();
+        for (name, contours) in [
+            ("o", vec![square(0., 100.)]),
+            ("b", vec![square(0., 50.), square(200., 100.)]),
+        ]
+        .iter()
+        {
+            let mut glyph = Glyph::new_named(*name);
+            glyph.outline = Some(norad::glyph::Outline {
+                contours: contours.clone(),
+                components: Vec::new(),
+            });
+            layer.insert_glyph(glyph);
+        }
+        workspace.shape_query = Some(ShapeQuery {
+            glyph: "o".into(),
+            index: 0,
+            contour: Arc::new(square(0., 100.)),
+            tolerance: 0.0,
+        });
+        workspace.run_shape_search();
+        assert_eq!(workspace.shape_matches.len(), 1);
+
+        // the contours of 'b' are reordered after the search
+        let mut b = (**workspace.font.ufo.get_glyph("b").unwrap()).clone();
+        b.outline.as_mut().unwrap().contours.reverse();
+        let layer = workspace.font_mut().ufo.get_default_layer_mut().unwrap();
+        layer.insert_glyph(b);
+
+        // a base without the shape can't be used
+        assert!(workspace.shape_replacements(&"b".into()).is_none());
+        assert_eq!(workspace.replace_shape_with_component(&"dot".into()), 2);
+        assert!(workspace.shape_query.is_none());
+
+        let ufo = &workspace.font.ufo;
+        let dot = ufo.get_glyph("dot").unwrap();
+        assert_eq!(dot.outline.as_ref().unwrap().contours.len(), 1);
+        for (name, contours, x_offset) in [("o", 0, 0.0), ("b", 1, 200.0)].iter() {
+            let outline = ufo.get_glyph(*name).unwrap().outline.as_ref().unwrap();
+            assert_eq!(outline.contours.len(), *contours);
+            assert_eq!(outline.components.len(), 1);
+            assert_eq!(&*outline.components[0].base, "dot");
+            let transform: Affine = outline.components[0].transform.into();
+            assert_eq!(transform.as_coeffs()[4], *x_offset);
+        }
+        // the small square in 'b' is kept
+        let b = ufo.get_glyph("b").unwrap().outline.as_ref().unwrap();
+        assert_eq!(b.contours[0].points[1].x, 50.0);
+    }
+
+    #[test]
+    fn coverage() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a", "b"]).workspace();
+        let mut b = (**workspace.font.ufo.get_glyph("b").unwrap()).clone();
+        b.outline = Some(norad::glyph::Outline {
+            contours: Vec::new(),
+            components: vec![norad::glyph::Component::new(
+                "a".into(),
+                Affine::IDENTITY.into(),
+                None,
+                None,
+            )],
+        });
+        let layer = workspace.font_mut().ufo.get_default_layer_mut().unwrap();
+        layer.insert_glyph(b);
+
+        workspace.check_coverage("test".into(), vec!['a', 'b', 'c']);
+        let statuses = |workspace: &Workspace| {
+            let report = workspace.coverage.report.as_ref().unwrap();
+            report
+                .entries
+                .iter()
+                .map(|entry| (entry.chr, entry.status))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            statuses(&workspace),
+            vec![
+                ('c', CoverageStatus::Missing),
+                ('a', CoverageStatus::Empty),
+                ('b', CoverageStatus::Complete)
+            ]
+        );
+
+        workspace.coverage.filter = Some(CoverageStatus::Empty);
+        let sections = workspace.grid_sections();
+        assert_eq!(sections[0].glyphs, vec![GlyphName::from("a")]);
+
+        assert_eq!(workspace.add_missing_glyphs(), 1);
+        assert_eq!(statuses(&workspace)[1], ('c', CoverageStatus::Empty));
+    }
+
+    #[test]
+    fn external_changes() {
+        let mut workspace = FontBuilder::new()
+            .with_glyphs(vec!["a", "b", "c"])
+            .workspace();
+
+        let mut on_disk = workspace.font.ufo.clone();
+        let layer = on_disk.get_default_layer_mut().unwrap();
+        layer.remove_glyph("a");
+        let mut b = Glyph::new_named("b");
+        b.codepoints = Some(vec!['x']);
+        layer.insert_glyph(b);
+        layer.insert_glyph(Glyph::new_named("d"));
+
+        assert!(!workspace.note_external_changes(workspace.font.ufo.clone()));
+        assert!(workspace.note_external_changes(on_disk));
+        let changed = &workspace.external_changes.as_ref().unwrap().glyphs;
+        assert_eq!(
+            changed.iter().map(|s| &**s).collect::<Vec<_>>(),
+            vec!["a", "b", "d"]
+        );
+
+        assert!(workspace.reload_external_changes().is_empty());
+        assert!(workspace.external_changes.is_none());
+        assert!(workspace.font.ufo.get_glyph("a").is_none());
+        assert!(workspace.font.ufo.get_glyph("d").is_some());
+        let b = workspace.font.ufo.get_glyph("b").unwrap();
+        assert_eq!(b.codepoints, Some(vec!['x']));
+    }
+
+    #[test]
+    fn unsaved_changes() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a"]).workspace();
+        assert!(!workspace.is_dirty());
+        workspace.add_glyphs(&["b".to_string()]);
+        assert!(workspace.is_dirty());
+        workspace.mark_saved();
+        assert!(!workspace.is_dirty());
+
+        let id = workspace.open_tab(&"a".into());
+        assert!(!workspace.is_dirty());
+        let session = Arc::make_mut(&mut workspace.sessions).get_mut(&id).unwrap();
+        Arc::make_mut(&mut Arc::make_mut(session).guides).clear();
+        assert!(workspace.sessions[&id].is_dirty());
+        assert!(workspace.is_dirty());
+
+        workspace.mark_saved();
+        assert!(!workspace.sessions[&id].is_dirty());
+        assert!(!workspace.is_dirty());
+
+        // the sessions of the previous font are not carried over
+        workspace.set_file(Ufo::new(), None);
+        assert!(workspace.sessions.is_empty() && workspace.session_map.is_empty());
+        assert!(workspace.editor_tabs.is_empty());
+        assert!(workspace.font.ufo.get_glyph("a").is_none());
+    }
+
+    #[test]
+    fn ufo_extension() {
+        assert_eq!(ufo_path(Path::new("/a/Font.ufo")), Path::new("/a/Font.ufo"));
+        assert_eq!(ufo_path(Path::new("/a/Font.UFO")), Path::new("/a/Font.UFO"));
+        assert_eq!(ufo_path(Path::new("/a/Font")), Path::new("/a/Font.ufo"));
+        assert_eq!(
+            ufo_path(Path::new("/a/Font-Bold.v2")),
+            Path::new("/a/Font-Bold.v2.ufo")
+        );
+    }
+
+    #[test]
+    fn find_glyphs() {
+        let workspace = FontBuilder::new()
+            .with_glyphs(vec!["a", "A", "a.sc", "aacute", "b"])
+            .workspace();
+
+        let found = workspace.find_glyphs("a", 10);
+        assert_eq!(
+            found.iter().map(|s| &**s).collect::<Vec<_>>(),
+            vec!["a", "A", "a.sc", "aacute"]
+        );
+        assert_eq!(workspace.find_glyphs("A", 1), vec![GlyphName::from("A")]);
+        assert_eq!(
+            workspace.find_glyphs(".SC", 10),
+            vec![GlyphName::from("a.sc")]
+        );
+        assert!(workspace.find_glyphs("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn glyph_for_char() {
+        let workspace = FontBuilder::new()
+            .with_glyphs(vec!["a", "A", "b"])
+            .workspace();
+        assert_eq!(workspace.glyph_for_char('A'), Some("A".into()));
+        assert_eq!(workspace.glyph_for_char('a'), Some("a".into()));
+        assert_eq!(workspace.glyph_for_char('z'), None);
+    }
+
+    #[test]
+    fn step_active_tab() {
+        let mut workspace = FontBuilder::new()
+            .with_glyphs(vec!["a", "b", "c"])
+            .workspace();
+        assert!(workspace.step_active_tab(1).is_none());
+
+        let a = workspace.open_tab(&"a".into());
+        let session = Arc::make_mut(&mut workspace.sessions).get_mut(&a).unwrap();
+        Arc::make_mut(session).viewport.zoom = 2.0;
+
+        assert_eq!(workspace.step_active_tab(2), Some("c".into()));
+        assert_eq!(workspace.editor_tabs.len(), 1);
+        assert_eq!(workspace.active_glyph(), Some(&"c".into()));
+        let c = workspace.active_tab.unwrap();
+        assert!((workspace.sessions[&c].viewport.zoom - 2.0).abs() < f64::EPSILON);
+
+        assert_eq!(workspace.step_active_tab(-1), Some("b".into()));
+        assert!(workspace.step_active_tab(5).is_none());
+        assert_eq!(workspace.active_glyph(), Some(&"b".into()));
+    }
+
+    #[test]
+    fn flush_on_close() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a", "b"]).workspace();
+        let outline_len = |workspace: &Workspace, name: &str| {
+            let glyph = workspace.font.ufo.get_glyph(name).unwrap();
+            glyph
+                .outline
+                .as_ref()
+                .map(|o| o.contours.len())
+                .unwrap_or(0)
+        };
+        let add_path = |workspace: &mut Workspace, id: SessionId| {
+            let mut path = crate::path::Path::new(design_space::DPoint::new(0., 0.));
+            path.line_to(design_space::DPoint::new(10., 0.), false);
+            let session = Arc::make_mut(&mut workspace.sessions).get_mut(&id).unwrap();
+            Arc::make_mut(&mut Arc::make_mut(session).paths).push(path);
+        };
+
+        let a = workspace.open_tab(&"a".into());
+        add_path(&mut workspace, a);
+        workspace.close_tab(a);
+        assert_eq!(outline_len(&workspace, "a"), 0);
+
+        workspace.flush_on_close = true;
+        let b = workspace.open_tab(&"b".into());
+        add_path(&mut workspace, b);
+        workspace.close_tab(b);
+        assert_eq!(outline_len(&workspace, "b"), 1);
+    }
+
+    #[test]
+    fn reopen_restores_viewport() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a"]).workspace();
+        let a = workspace.open_tab(&"a".into());
+        let session = Arc::make_mut(&mut workspace.sessions).get_mut(&a).unwrap();
+        Arc::make_mut(session).viewport.zoom = 3.0;
+        workspace.close_all_tabs();
+        assert!(workspace.active_tab.is_none());
+
+        // the session outlives its tab, and may be changed in the meantime
+        let session = Arc::make_mut(&mut workspace.sessions).get_mut(&a).unwrap();
+        Arc::make_mut(session).viewport.zoom = 0.5;
+        assert_eq!(workspace.open_tab(&"a".into()), a);
+        assert!((workspace.sessions[&a].viewport.zoom - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn glyph_sorting() {
+        let mut workspace = Workspace::default();
+        let names = ["b", "uniE000", "one", "A", "a"]
+            .iter()
+            .map(|s| s.to_string());
+        assert_eq!(workspace.add_glyphs(&names.collect::<Vec<_>>()), 5);
+        assert_eq!(workspace.add_glyphs(&["a".to_string()]), 0);
+
+        workspace.glyph_sort = GlyphSort::Codepoint;
+        let sorted = workspace.sorted_glyph_names();
+        assert_eq!(
+            sorted.iter().map(|s| &**s).collect::<Vec<_>>(),
+            vec!["one", "A", "a", "b", "uniE000"]
+        );
+
+        workspace.glyph_sort = GlyphSort::GlyphOrder;
+        workspace.move_glyph(&"a".into(), Some(&"b".into()));
+        workspace.move_glyph(&"one".into(), None);
+        let order = workspace.font.glyph_order();
+        let a = order.iter().position(|n| &**n == "a").unwrap();
+        assert_eq!(&*order[a + 1], "b");
+        assert_eq!(order.last().map(|n| &**n), Some("one"));
+        assert_eq!(workspace.sorted_glyph_names(), order);
+
+        workspace.rename_glyph("one".into(), "uno".into());
+        assert_eq!(
+            workspace.font.glyph_order().last().map(|n| &**n),
+            Some("uno")
+        );
+
+        workspace.glyph_sort = GlyphSort::Codepoint;
+        workspace.group_glyphs = true;
+        let titles = workspace
+            .grid_sections()
+            .into_iter()
+            .map(|s| s.title.unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            titles,
+            vec!["Latin Uppercase", "Latin Lowercase", "Symbol", "Unencoded"]
+        );
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn rename_updates_references() {
+        let mut workspace = FontBuilder::new()
+            .with_glyphs(vec!["a", "acute"])
+            .workspace();
+        let mut aacute = Glyph::new_named("aacute");
+        let component = |base: &str| {
+            norad::glyph::Component::new(base.into(), Affine::IDENTITY.into(), None, None)
+        };
+        aacute.outline = Some(norad::glyph::Outline {
+            contours: Vec::new(),
+            components: vec![component("a"), component("acute")],
+        });
+        let ufo = &mut workspace.font_mut().ufo;
+        ufo.get_default_layer_mut().unwrap().insert_glyph(aacute);
+        let mut groups = std::collections::BTreeMap::new();
+        groups.insert(
+            "public.kern1.a".to_string(),
+            vec!["a".into(), "aacute".into()],
+        );
+        ufo.groups = Some(groups);
+        let mut seconds = std::collections::BTreeMap::new();
+        seconds.insert("a".to_string(), -10.0);
+        let mut kerning = std::collections::BTreeMap::new();
+        kerning.insert("a".to_string(), seconds);
+        ufo.kerning = Some(kerning);
+
+        let renames = [
+            ("a".into(), "a.sc".into()),
+            ("acute".into(), "acutecomb".into()),
+        ];
+        assert_eq!(workspace.rename_glyphs(&renames), 2);
+        let ufo = &workspace.font.ufo;
+        let aacute = ufo.get_glyph("aacute").unwrap();
+        let bases = aacute.outline.as_ref().unwrap().components.iter();
+        assert_eq!(
+            bases.map(|c| &*c.base).collect::<Vec<_>>(),
+            vec!["a.sc", "acutecomb"]
+        );
+        assert_eq!(
+            ufo.groups.as_ref().unwrap()["public.kern1.a"],
+            vec!["a.sc".into(), "aacute".into()]
+        );
+        assert_eq!(ufo.kerning.as_ref().unwrap()["a.sc"]["a.sc"], -10.0);
+    }
+
+    #[test]
+    fn locked_glyphs_are_left_alone() {
+        let mut workspace = FontBuilder::new()
+            .with_glyphs(vec!["a", "acute"])
+            .workspace();
+        let mut aacute = Glyph::new_named("aacute");
+        aacute.outline = Some(norad::glyph::Outline {
+            contours: Vec::new(),
+            components: vec![norad::glyph::Component::new(
+                "acute".into(),
+                Affine::IDENTITY.into(),
+                None,
+                None,
+            )],
+        });
+        let layer = workspace.font_mut().ufo.get_default_layer_mut().unwrap();
+        layer.insert_glyph(aacute);
+        workspace.set_glyph_locked(&"a".into(), true);
+        workspace.set_glyph_locked(&"aacute".into(), true);
+        let a = workspace.font.ufo.get_glyph("a").unwrap().clone();
+
+        assert_eq!(workspace.rename_glyphs(&[("a".into(), "a.sc".into())]), 0);
+        let mut changed = a.clone();
+        Arc::make_mut(&mut changed).note = Some("changed".into());
+        workspace.update_glyph_metadata(&changed);
+        let red = MarkColor {
+            r: 1.0,
+            g: 0.0,
+            b: 0.0,
+            a: 1.0,
+        };
+        workspace.set_mark_color(&"a".into(), Some(red));
+        workspace.selected = Some("a".into());
+        assert!(workspace.delete_selected_glyph().is_none());
+        assert_eq!(workspace.font.ufo.get_glyph("a"), Some(&a));
+
+        // 'acute' can't be decomposed in 'aacute', so it isn't decomposed
+        // anywhere
+        assert_eq!(
+            workspace.decompose_component_uses(&"acute".into()),
+            vec![GlyphName::from("aacute")]
+        );
+        let aacute = workspace.font.ufo.get_glyph("aacute").unwrap();
+        assert_eq!(aacute.outline.as_ref().unwrap().components.len(), 1);
+    }
+
+    #[test]
+    fn duplicate_glyph() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a", "b"]).workspace();
+        workspace
+            .font_mut()
+            .set_glyph_order(&["a".into(), "b".into()]);
+
+        let copy = workspace.unused_glyph_name("a");
+        assert_eq!(&*copy, "a.001");
+        assert!(workspace.duplicate_glyph(&"a".into(), copy.clone()));
+        assert!(!workspace.duplicate_glyph(&"a".into(), copy));
+        let copy = workspace.font.ufo.get_glyph("a.001").unwrap();
+        assert!(copy.codepoints.is_none());
+        assert_eq!(&*workspace.unused_glyph_name("a"), "a.002");
+        assert!(workspace.duplicate_glyph(&"a".into(), "a.alt".into()));
+
+        let composite = workspace.new_composite_glyph(&"b".into()).unwrap();
+        let glyph = workspace.font.ufo.get_glyph(&composite).unwrap();
+        let bases = glyph.outline.as_ref().unwrap().components.iter();
+        assert_eq!(bases.map(|c| &*c.base).collect::<Vec<_>>(), vec!["b"]);
+
+        let order = workspace.font.glyph_order();
+        assert_eq!(
+            order.iter().map(|n| &**n).collect::<Vec<_>>(),
+            vec!["a", "a.alt", "a.001", "b", "b.001"]
+        );
+        assert!(!workspace.duplicate_glyph(&"missing".into(), "x".into()));
+    }
 }
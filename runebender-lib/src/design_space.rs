@@ -11,6 +11,66 @@ use std::ops::{Add, AddAssign, Sub, SubAssign};
 use druid::kurbo::{Affine, Point, Rect, Vec2};
 use druid::{Data, Lens};
 
+/// How finely coordinates are rounded.
+///
+/// This is a property of the open font: it is `Hundredths` if the font has
+/// points with fractional coordinates when it is loaded, and can be changed
+/// from the Edit menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum Precision {
+    /// Whole units.
+    Integer,
+    /// Hundredths of a unit.
+    Hundredths,
+}
+
+impl Precision {
+    /// The finest precision; every `DPoint` is rounded to at least this.
+    pub const FINEST: Precision = Precision::Hundredths;
+
+    pub fn from_fractional(fractional: bool) -> Self {
+        if fractional {
+            Precision::Hundredths
+        } else {
+            Precision::Integer
+        }
+    }
+
+    pub fn is_fractional(self) -> bool {
+        self != Precision::Integer
+    }
+
+    /// The distance between neighbouring rounded coordinates.
+    pub fn step(self) -> f64 {
+        1.0 / self.steps_per_unit()
+    }
+
+    fn steps_per_unit(self) -> f64 {
+        match self {
+            Precision::Integer => 1.0,
+            Precision::Hundredths => 100.0,
+        }
+    }
+}
+
+impl Default for Precision {
+    fn default() -> Self {
+        Precision::Integer
+    }
+}
+
+/// Round a design space coordinate to `precision`.
+pub fn round_coord(value: f64, precision: Precision) -> f64 {
+    let steps = precision.steps_per_unit();
+    (value * steps).round() / steps
+}
+
+/// `true` if `value` is already rounded to `precision`.
+#[allow(clippy::float_cmp)]
+fn is_rounded(value: f64, precision: Precision) -> bool {
+    round_coord(value, precision) == value
+}
+
 /// The position of the view, relative to the design space.
 #[derive(Data, Debug, Clone, Copy, PartialEq)]
 //TODO: rename to DesignSpace
@@ -50,11 +110,14 @@ pub struct DVec2 {
 impl DPoint {
     pub const ZERO: DPoint = DPoint { x: 0.0, y: 0.0 };
 
-    /// Should only be used with inputs already in design space, such as when
-    /// loaded from file.
+    /// Should only be used with inputs already in design space, and already
+    /// rounded to at least the finest precision.
     pub(crate) fn new(x: f64, y: f64) -> DPoint {
         assert!(
-            x.is_finite() && y.is_finite() && x.fract() == 0. && y.fract() == 0.,
+            x.is_finite()
+                && y.is_finite()
+                && is_rounded(x, Precision::FINEST)
+                && is_rounded(y, Precision::FINEST),
             "({}, {})",
             x,
             y
@@ -62,8 +125,14 @@ impl DPoint {
         DPoint { x, y }
     }
 
-    pub fn from_screen(point: Point, vport: ViewPort) -> DPoint {
-        vport.from_screen(point)
+    /// Create a `DPoint` from coordinates loaded from a file, rounded to the
+    /// font's `precision`.
+    pub(crate) fn from_file(x: f64, y: f64, precision: Precision) -> DPoint {
+        DPoint::from_raw((x, y), precision)
+    }
+
+    pub fn from_screen(point: Point, vport: ViewPort, precision: Precision) -> DPoint {
+        vport.from_screen(point, precision)
     }
 
     pub fn to_screen(self, vport: ViewPort) -> Point {
@@ -73,9 +142,12 @@ impl DPoint {
     /// Create a new `DPoint` from a `Point` in design space. This should only
     /// be used to convert back to a `DPoint` after using `Point` to do vector
     /// math in design space.
-    pub fn from_raw(point: impl Into<Point>) -> DPoint {
+    pub fn from_raw(point: impl Into<Point>, precision: Precision) -> DPoint {
         let point = point.into();
-        DPoint::new(point.x.round(), point.y.round())
+        DPoint::new(
+            round_coord(point.x, precision),
+            round_coord(point.y, precision),
+        )
     }
 
     /// Convert a design point directly to a point, without taking screen geometry
@@ -106,8 +178,8 @@ impl DPoint {
         }
     }
 
-    pub fn lerp(self, other: DPoint, t: f64) -> DPoint {
-        DPoint::from_raw(self.to_raw().lerp(other.to_raw(), t))
+    pub fn lerp(self, other: DPoint, t: f64, precision: Precision) -> DPoint {
+        DPoint::from_raw(self.to_raw().lerp(other.to_raw(), t), precision)
     }
 }
 
@@ -115,13 +187,21 @@ impl DVec2 {
     pub const ZERO: DVec2 = DVec2 { x: 0.0, y: 0.0 };
 
     fn new(x: f64, y: f64) -> DVec2 {
-        assert!(x.is_finite() && y.is_finite() && x.fract() == 0. && y.fract() == 0.);
+        assert!(
+            x.is_finite()
+                && y.is_finite()
+                && is_rounded(x, Precision::FINEST)
+                && is_rounded(y, Precision::FINEST)
+        );
         DVec2 { x, y }
     }
 
-    pub fn from_raw(vec2: impl Into<Vec2>) -> DVec2 {
+    pub fn from_raw(vec2: impl Into<Vec2>, precision: Precision) -> DVec2 {
         let vec2 = vec2.into();
-        DVec2::new(vec2.x.round(), vec2.y.round())
+        DVec2::new(
+            round_coord(vec2.x, precision),
+            round_coord(vec2.y, precision),
+        )
     }
 
     /// should not be public, used internally so we can reuse math ops
@@ -180,9 +260,9 @@ impl ViewPort {
     }
 
     #[allow(clippy::wrong_self_convention)]
-    pub fn from_screen(&self, point: impl Into<Point>) -> DPoint {
+    pub fn from_screen(&self, point: impl Into<Point>, precision: Precision) -> DPoint {
         let point = self.inverse_affine() * point.into();
-        DPoint::new(point.x.round(), point.y.round())
+        DPoint::from_raw(point, precision)
     }
 
     #[allow(clippy::wrong_self_convention)]
@@ -192,8 +272,8 @@ impl ViewPort {
 
     // rects get special treatment because they can't be transformed with an affine
     pub fn rect_to_screen(&self, rect: Rect) -> Rect {
-        let p0 = self.to_screen(DPoint::from_raw(rect.origin()));
-        let p1 = self.to_screen(DPoint::from_raw((rect.x1, rect.y1)));
+        let p0 = self.affine() * rect.origin();
+        let p1 = self.affine() * Point::new(rect.x1, rect.y1);
         Rect::from_points(p0, p1)
     }
 }
@@ -203,7 +283,7 @@ impl Add<DVec2> for DPoint {
 
     #[inline]
     fn add(self, other: DVec2) -> Self {
-        DPoint::new(self.x + other.x, self.y + other.y)
+        DPoint::from_raw((self.x + other.x, self.y + other.y), Precision::FINEST)
     }
 }
 
@@ -212,7 +292,7 @@ impl Sub<DVec2> for DPoint {
 
     #[inline]
     fn sub(self, other: DVec2) -> Self {
-        DPoint::new(self.x - other.x, self.y - other.y)
+        DPoint::from_raw((self.x - other.x, self.y - other.y), Precision::FINEST)
     }
 }
 
@@ -221,7 +301,7 @@ impl Sub<DPoint> for DPoint {
 
     #[inline]
     fn sub(self, other: DPoint) -> DVec2 {
-        DVec2::new(self.x - other.x, self.y - other.y)
+        DVec2::from_raw((self.x - other.x, self.y - other.y), Precision::FINEST)
     }
 }
 
@@ -230,7 +310,7 @@ impl Add for DVec2 {
 
     #[inline]
     fn add(self, other: DVec2) -> DVec2 {
-        DVec2::new((self.x + other.x).round(), (self.y + other.y).round())
+        DVec2::from_raw((self.x + other.x, self.y + other.y), Precision::FINEST)
     }
 }
 
@@ -245,7 +325,7 @@ impl Sub for DVec2 {
 
     #[inline]
     fn sub(self, other: DVec2) -> DVec2 {
-        DVec2::new(self.x - other.x, self.y - other.y)
+        DVec2::from_raw((self.x - other.x, self.y - other.y), Precision::FINEST)
     }
 }
 
@@ -257,7 +337,7 @@ impl SubAssign for DVec2 {
 
 impl From<(f64, f64)> for DPoint {
     fn from(src: (f64, f64)) -> DPoint {
-        DPoint::new(src.0.round(), src.1.round())
+        DPoint::from_raw(src, Precision::FINEST)
     }
 }
 
@@ -296,3 +376,27 @@ impl std::default::Default for ViewPort {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rounding() {
+        let point = (1.234, -5.4);
+        assert_eq!(
+            DPoint::from_raw(point, Precision::Integer),
+            DPoint::new(1.0, -5.0)
+        );
+        assert_eq!(
+            DPoint::from_raw(point, Precision::Hundredths),
+            DPoint::new(1.23, -5.4)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn unrounded_point() {
+        DPoint::new(0.001, 0.0);
+    }
+}
@@ -1,24 +1,88 @@
 //! Drawing algorithms and helpers
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::component::Component;
-use crate::data::{FontMetrics, Workspace};
-use crate::design_space::ViewPort;
+use crate::data::{FontMetrics, MetricLine, Workspace};
+use crate::design_space::{DPoint, DVec2, ViewPort};
 use crate::edit_session::EditSession;
+use crate::font_info::BlueZone;
 use crate::guides::{Guide, GuideLine};
+use crate::mark_color::MarkColor;
 use crate::path::Path;
-use crate::point::PointType;
+use crate::point::{EntityId, PointType};
 use crate::point_list::RawSegment;
+use crate::raster::Bitmap;
 use crate::selection::Selection;
 use crate::theme;
+use crate::tunni::Tunni;
+use crate::util::format_num;
 
-use druid::kurbo::{self, Affine, BezPath, Circle, CubicBez, Line, Point, Rect, Vec2};
-use druid::piet::{Color, Piet, RenderContext};
-use druid::{Env, PaintCtx};
+use druid::kurbo::{self, Affine, BezPath, Circle, CubicBez, Line, Point, Rect, Size, Vec2};
+use druid::piet::{
+    Color, FontFamily, Piet, PietTextLayout, RenderContext, StrokeStyle, Text, TextLayout,
+    TextLayoutBuilder,
+};
+use druid::{Data, Env, PaintCtx};
 
 use norad::Glyph;
 
+const METRIC_LABEL_FONT_SIZE: f64 = 10.0;
+const METRIC_LABEL_PADDING: f64 = 4.0;
+/// How far a guide's name is drawn from the edges of the visible area.
+const GUIDE_LABEL_INSET: f64 = 24.0;
+/// The max distance, in screen space, at which a metric line is hit.
+const METRIC_LINE_HIT_DISTANCE: f64 = 4.0;
+
+const DRAG_INFO_FONT_SIZE: f64 = 10.0;
+/// The distance between the cursor and the info box, in screen space.
+const DRAG_INFO_OFFSET: f64 = 16.0;
+const DRAG_INFO_PADDING: f64 = 4.0;
+
+const REFERENCE_OUTLINE_FILL_COLOR: Color = Color::rgba8(0xe0, 0x80, 0x20, 0x30);
+const REFERENCE_OUTLINE_STROKE_COLOR: Color = Color::rgba8(0xe0, 0x80, 0x20, 0xa0);
+
+const ATTACHED_OUTLINE_FILL_COLOR: Color = Color::rgba8(0x20, 0x60, 0xe0, 0x40);
+
+const LOCK_INDICATOR_COLOR: Color = Color::rgb8(0x70, 0x70, 0x70);
+
+const TUNNI_LINE_COLOR: Color = Color::rgba8(0x40, 0x90, 0xd0, 0xa0);
+/// The radius of the Tunni point, in screen space.
+pub(crate) const TUNNI_POINT_RADIUS: f64 = 3.5;
+
+const PIXEL_PREVIEW_FILL_COLOR: Color = Color::rgba8(0x10, 0x10, 0x10, 0xc0);
+const PIXEL_PREVIEW_GRID_COLOR: Color = Color::rgba8(0x40, 0x80, 0xd0, 0x60);
+/// The grid is hidden if pixels are smaller than this, in screen space.
+const PIXEL_PREVIEW_MIN_GRID_SIZE: f64 = 4.0;
+
+/// How the glyph being edited is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMode {
+    /// Only the filled glyph, with no points or other non-glyph items.
+    Filled,
+    /// Outlines and points, with components filled.
+    Outline,
+    /// Hairline outlines of paths and components, with points and handles.
+    Wireframe,
+}
+
+impl Default for ViewMode {
+    fn default() -> Self {
+        ViewMode::Outline
+    }
+}
+
+/// The radius of the loupe, in screen space.
+const LOUPE_RADIUS: f64 = 64.0;
+/// How much more the loupe is magnified than the canvas.
+const LOUPE_MAGNIFICATION: f64 = 4.0;
+/// The distance, on each axis, from the cursor to the center of the loupe.
+const LOUPE_OFFSET: f64 = 96.0;
+const LOUPE_POINT_RADIUS: f64 = 2.5;
+const LOUPE_BORDER_COLOR: Color = Color::rgb8(0x60, 0x60, 0x60);
+const LOUPE_CROSSHAIR_COLOR: Color = Color::rgba8(0xe0, 0x40, 0x40, 0xa0);
+
 /// A context for drawing that maps between screen space and design space.
 struct DrawCtx<'a, 'b: 'a> {
     ctx: &'a mut Piet<'b>,
@@ -26,6 +90,79 @@ struct DrawCtx<'a, 'b: 'a> {
     space: ViewPort,
     /// the size of the drawing area
     visible_rect: Rect,
+    cache: &'a mut PaintCache,
+}
+
+/// Geometry built while drawing a session, in screen space, that is reused
+/// for as long as the things it was built from are unchanged.
+///
+/// While dragging, usually only one path changes from one frame to the
+/// next, so the outlines of the other paths, of the components, and the
+/// metric labels needn't be rebuilt. Everything is rebuilt if the viewport
+/// changes.
+#[derive(Default)]
+pub(crate) struct PaintCache {
+    space: Option<ViewPort>,
+    /// Each path's outline, by path id, with the generation of the path it
+    /// was built from.
+    paths: HashMap<EntityId, (u64, Arc<BezPath>)>,
+    /// Each component's outline, with the component and base outline it
+    /// was built from.
+    components: HashMap<EntityId, (Component, Arc<BezPath>, Arc<BezPath>)>,
+    /// The metric labels, and their positions, with the metrics and
+    /// advance they show.
+    metric_labels: Option<(FontMetrics, f64, Vec<(PietTextLayout, Point)>)>,
+}
+
+impl PaintCache {
+    /// Forget everything if the viewport has changed.
+    fn begin(&mut self, space: ViewPort) {
+        if self.space != Some(space) {
+            *self = PaintCache {
+                space: Some(space),
+                ..Default::default()
+            };
+        }
+    }
+
+    /// Forget the outlines of paths and components that are gone.
+    fn finish(&mut self, session: &EditSession) {
+        let paths: HashSet<_> = session.paths.iter().map(Path::id).collect();
+        self.paths.retain(|id, _| paths.contains(id));
+        let components: HashSet<_> = session.components.iter().map(|c| c.id).collect();
+        self.components.retain(|id, _| components.contains(id));
+    }
+
+    fn path_outline(&mut self, path: &Path) -> Arc<BezPath> {
+        if let Some((generation, bez)) = self.paths.get(&path.id()) {
+            if *generation == path.generation() {
+                return bez.clone();
+            }
+        }
+        let space = self.space.unwrap_or_default();
+        let bez = Arc::new(space.affine() * path.bezier());
+        self.paths
+            .insert(path.id(), (path.generation(), bez.clone()));
+        bez
+    }
+
+    fn component_outline(
+        &mut self,
+        component: &Component,
+        font: &Workspace,
+    ) -> Option<Arc<BezPath>> {
+        let base = font.get_bezier(&component.base)?;
+        if let Some((cached, cached_base, bez)) = self.components.get(&component.id) {
+            if cached.same(component) && Arc::ptr_eq(cached_base, &base) {
+                return Some(bez.clone());
+            }
+        }
+        let space = self.space.unwrap_or_default();
+        let bez = Arc::new(space.affine() * component.transform * &*base);
+        self.components
+            .insert(component.id, (component.clone(), base, bez.clone()));
+        Some(bez)
+    }
 }
 
 impl<'a, 'b> std::ops::Deref for DrawCtx<'a, 'b> {
@@ -43,26 +180,29 @@ impl<'a, 'b> std::ops::DerefMut for DrawCtx<'a, 'b> {
 }
 
 impl<'a, 'b: 'a> DrawCtx<'a, 'b> {
-    fn new(ctx: &'a mut Piet<'b>, env: &'a Env, space: ViewPort, visible_rect: Rect) -> Self {
+    fn new(
+        ctx: &'a mut Piet<'b>,
+        env: &'a Env,
+        space: ViewPort,
+        visible_rect: Rect,
+        cache: &'a mut PaintCache,
+    ) -> Self {
+        cache.begin(space);
         DrawCtx {
             ctx,
             env,
             space,
             visible_rect,
+            cache,
         }
     }
 
     fn draw_metrics(&mut self, glyph: &Glyph, metrics: &FontMetrics, env: &Env) {
-        let upm = metrics.units_per_em;
-        let x_height = metrics.x_height.unwrap_or_else(|| (upm * 0.5).round());
-        let cap_height = metrics.cap_height.unwrap_or_else(|| (upm * 0.7).round());
-        let ascender = metrics.ascender.unwrap_or_else(|| (upm * 0.8).round());
-        let descender = metrics.descender.unwrap_or_else(|| -(upm * 0.2).round());
-        let hadvance = glyph
-            .advance
-            .as_ref()
-            .map(|a| a.width as f64)
-            .unwrap_or_else(|| (upm * 0.5).round());
+        let x_height = metrics.value(MetricLine::XHeight);
+        let cap_height = metrics.value(MetricLine::CapHeight);
+        let ascender = metrics.value(MetricLine::Ascender);
+        let descender = metrics.value(MetricLine::Descender);
+        let hadvance = metric_line_width(glyph, metrics);
 
         let metrics_color = env.get(theme::METRICS_COLOR);
         let bounds = Rect::from_points((0., descender), (hadvance, ascender));
@@ -80,6 +220,51 @@ impl<'a, 'b: 'a> DrawCtx<'a, 'b> {
         let cap_height_guide = Line::new((0.0, cap_height), (hadvance, cap_height));
         let cap_height_guide = self.space.affine() * cap_height_guide;
         self.stroke(cap_height_guide, &metrics_color, 1.0);
+
+        let labels = match self.cache.metric_labels.take() {
+            Some((cached, advance, labels)) if cached.same(metrics) && advance == hadvance => {
+                labels
+            }
+            _ => self.metric_labels(metrics, hadvance, &metrics_color),
+        };
+        for (layout, origin) in &labels {
+            self.draw_text(layout, *origin);
+        }
+        self.cache.metric_labels = Some((metrics.clone(), hadvance, labels));
+    }
+
+    fn metric_labels(
+        &mut self,
+        metrics: &FontMetrics,
+        hadvance: f64,
+        color: &Color,
+    ) -> Vec<(PietTextLayout, Point)> {
+        MetricLine::ALL
+            .iter()
+            .map(|line| {
+                let value = metrics.value(*line);
+                let pos = self.space.to_screen((hadvance, value));
+                let layout = self
+                    .text()
+                    .new_text_layout(format_num(value, 1))
+                    .font(FontFamily::SYSTEM_UI, METRIC_LABEL_FONT_SIZE)
+                    .text_color(color.clone())
+                    .build()
+                    .unwrap();
+                let origin = pos + Vec2::new(METRIC_LABEL_PADDING, -layout.size().height);
+                (layout, origin)
+            })
+            .collect()
+    }
+
+    fn draw_blue_zones(&mut self, zones: &[BlueZone], env: &Env) {
+        let color = env.get(theme::BLUE_ZONE_COLOR);
+        for zone in zones {
+            let bottom = self.space.to_screen((0.0, zone.bottom)).y;
+            let top = self.space.to_screen((0.0, zone.top)).y;
+            let rect = Rect::new(self.visible_rect.x0, top, self.visible_rect.x1, bottom);
+            self.fill(rect, &color);
+        }
     }
 
     fn draw_grid(&mut self) {
@@ -126,10 +311,37 @@ impl<'a, 'b: 'a> DrawCtx<'a, 'b> {
             if sels.contains(&guide.id) {
                 self.stroke(line, &env.get(theme::SELECTED_GUIDE_COLOR), 8.0);
             }
-            self.stroke(line, &env.get(theme::GUIDE_COLOR), 0.5);
+            let color = guide
+                .color
+                .map(MarkColor::to_color)
+                .unwrap_or_else(|| env.get(theme::GUIDE_COLOR));
+            if guide.locked {
+                let style = StrokeStyle::new().dash(vec![4.0, 2.0], 0.0);
+                self.stroke_styled(line, &color, 1.0, &style);
+            } else {
+                self.stroke(line, &color, 0.5);
+            }
+            if let Some(name) = guide.name.as_ref() {
+                self.draw_guide_name(guide, name, &color);
+            }
         }
     }
 
+    /// Draw a guide's name along it, near the top left of the visible area.
+    fn draw_guide_name(&mut self, guide: &Guide, name: &str, color: &Color) {
+        let corner = self.visible_rect.origin() + Vec2::new(GUIDE_LABEL_INSET, GUIDE_LABEL_INSET);
+        let pos = guide.nearest_screen_point(self.space, corner);
+        let layout = self
+            .text()
+            .new_text_layout(name.to_owned())
+            .font(FontFamily::SYSTEM_UI, METRIC_LABEL_FONT_SIZE)
+            .text_color(color.clone())
+            .build()
+            .unwrap();
+        let origin = pos + Vec2::new(METRIC_LABEL_PADDING, -layout.size().height);
+        self.draw_text(&layout, origin);
+    }
+
     fn line_for_guide(&self, guide: &Guide) -> Line {
         let view_origin = self.space.inverse_affine() * self.visible_rect.origin();
         let Point { x, y } = view_origin.round();
@@ -177,7 +389,10 @@ impl<'a, 'b: 'a> DrawCtx<'a, 'b> {
     }
 
     fn draw_filled(&mut self, session: &EditSession, font: &Workspace) {
-        let bez = self.space.affine() * session.to_bezier();
+        let mut bez = BezPath::new();
+        for path in session.paths.iter() {
+            bez.extend(self.cache.path_outline(path).iter());
+        }
         let fill_color = self.env.get(theme::PATH_FILL_COLOR);
         self.fill(bez, &fill_color);
 
@@ -312,6 +527,33 @@ impl<'a, 'b: 'a> DrawCtx<'a, 'b> {
         }
     }
 
+    /// Draw the Tunni line and point of each curve in the path.
+    fn draw_tunni_lines(&mut self, path: &Path) {
+        let affine = self.space.affine();
+        for tunni in path
+            .iter_segments()
+            .filter_map(|seg| Tunni::for_segment(&seg))
+        {
+            let line = Line::new(affine * tunni.curve.p1, affine * tunni.curve.p2);
+            let style = StrokeStyle::new().dash(vec![3.0, 3.0], 0.0);
+            self.stroke_styled(line, &TUNNI_LINE_COLOR, 1.0, &style);
+            let point = Circle::new(affine * tunni.point(), TUNNI_POINT_RADIUS);
+            self.fill(point, &TUNNI_LINE_COLOR);
+        }
+    }
+
+    /// Draw a small padlock beside a locked point.
+    fn draw_lock(&mut self, p: Point) {
+        let body = Rect::from_origin_size(p + Vec2::new(6.0, -10.0), (8.0, 6.0));
+        let mut shackle = BezPath::new();
+        shackle.move_to((body.x0 + 2.0, body.y0));
+        shackle.line_to((body.x0 + 2.0, body.y0 - 3.0));
+        shackle.line_to((body.x1 - 2.0, body.y0 - 3.0));
+        shackle.line_to((body.x1 - 2.0, body.y0));
+        self.fill(body, &LOCK_INDICATOR_COLOR);
+        self.stroke(shackle, &LOCK_INDICATOR_COLOR, 1.5);
+    }
+
     fn draw_direction_indicator(&mut self, path: &BezPath, env: &Env) {
         let first_seg = match path.segments().next().as_ref().map(|seg| seg.to_cubic()) {
             None => return,
@@ -329,13 +571,17 @@ impl<'a, 'b: 'a> DrawCtx<'a, 'b> {
     }
 
     fn draw_component(&mut self, component: &Component, font: &Workspace, color: &Color) {
-        if let Some(mut bez) = font.get_bezier(&component.base) {
-            let bez = Arc::make_mut(&mut bez);
-            bez.apply_affine(component.transform);
-            bez.apply_affine(self.space.affine());
+        if let Some(bez) = self.cache.component_outline(component, font) {
             self.fill(&*bez, color);
         }
     }
+
+    fn draw_component_outline(&mut self, component: &Component, font: &Workspace) {
+        if let Some(bez) = self.cache.component_outline(component, font) {
+            let path_color = self.env.get(theme::PATH_STROKE_COLOR);
+            self.stroke(&*bez, &path_color, 1.0);
+        }
+    }
 }
 
 struct PointStyle {
@@ -422,33 +668,51 @@ pub(crate) fn draw_session(
     metrics: &FontMetrics,
     session: &EditSession,
     font: &Workspace,
-    is_preview: bool,
+    mode: ViewMode,
+    cache: &mut PaintCache,
 ) {
-    let mut draw_ctx = DrawCtx::new(ctx.render_ctx, env, space, visible_rect);
+    let mut draw_ctx = DrawCtx::new(ctx.render_ctx, env, space, visible_rect, cache);
 
-    if is_preview {
+    if mode == ViewMode::Filled {
         draw_ctx.draw_filled(session, font);
+        draw_ctx.cache.finish(session);
         return;
     }
 
     draw_ctx.draw_grid();
+    draw_ctx.draw_blue_zones(&font.info.postscript.zones(), env);
     draw_ctx.draw_metrics(&session.glyph, metrics, env);
+    draw_ctx.draw_guides(&session.global_guides, &session.selection, env);
     draw_ctx.draw_guides(&session.guides, &session.selection, env);
 
     for path in session.paths.iter() {
+        if session.hidden_paths.contains(&path.id()) {
+            continue;
+        }
         if session.selection.len() > 1 {
             // for a segment to be selected at least two points must be selected
             draw_ctx.draw_selected_segments(path, &session.selection);
         }
-        let bez = space.affine() * path.bezier();
+        let bez = draw_ctx.cache.path_outline(path);
         draw_ctx.draw_path(&bez);
         draw_ctx.draw_control_point_lines(path);
-        draw_ctx.draw_direction_indicator(&bez, env);
+        if font.show_tunni_lines {
+            draw_ctx.draw_tunni_lines(path);
+        }
+        if mode != ViewMode::Wireframe {
+            draw_ctx.draw_direction_indicator(&bez, env);
+        }
 
         for point in PointIter::new(path, space, &bez, &session.selection) {
             draw_ctx.draw_point(point, env)
         }
 
+        for point in path.points() {
+            if session.locked.contains(&point.id) {
+                draw_ctx.draw_lock(point.point.to_screen(space));
+            }
+        }
+
         if let Some(pt) = path.trailing() {
             if path.should_draw_trailing() {
                 draw_ctx.draw_auto_point(pt.to_screen(space), false, env);
@@ -457,8 +721,293 @@ pub(crate) fn draw_session(
     }
 
     for component in session.components.iter() {
-        draw_ctx.draw_component(component, font, &env.get(theme::COMPONENT_FILL_COLOR));
+        match mode {
+            ViewMode::Wireframe => draw_ctx.draw_component_outline(component, font),
+            _ => draw_ctx.draw_component(component, font, &env.get(theme::COMPONENT_FILL_COLOR)),
+        }
     }
+    draw_ctx.cache.finish(session);
+}
+
+/// The width of the metric lines for this glyph; this is its advance.
+/// Draw the outline of a glyph from the reference font, in design space.
+pub(crate) fn draw_reference_outline(ctx: &mut PaintCtx, space: ViewPort, outline: &BezPath) {
+    let outline = space.affine() * outline;
+    ctx.fill(&outline, &REFERENCE_OUTLINE_FILL_COLOR);
+    ctx.stroke(&outline, &REFERENCE_OUTLINE_STROKE_COLOR, 1.0);
+}
+
+/// Draw the outline of a glyph attached to this one by anchors, moved by
+/// `offset` in design space.
+pub(crate) fn draw_attached_outline(
+    ctx: &mut PaintCtx,
+    space: ViewPort,
+    outline: &BezPath,
+    offset: Vec2,
+) {
+    let outline = space.affine() * Affine::translate(offset) * outline;
+    ctx.fill(&outline, &ATTACHED_OUTLINE_FILL_COLOR);
+}
+
+/// Draw a rasterized glyph over the canvas, with a grid showing the pixel
+/// boundaries across `bounds`, which is in design space.
+pub(crate) fn draw_pixel_preview(
+    ctx: &mut PaintCtx,
+    space: ViewPort,
+    bitmap: Option<&Bitmap>,
+    pixel_size: f64,
+    bounds: Rect,
+) {
+    if let Some(bitmap) = bitmap {
+        for (rect, coverage) in bitmap.pixels() {
+            let color = PIXEL_PREVIEW_FILL_COLOR.with_alpha(0.75 * coverage);
+            ctx.fill(space.rect_to_screen(rect), &color);
+        }
+    }
+
+    if pixel_size * space.zoom < PIXEL_PREVIEW_MIN_GRID_SIZE {
+        return;
+    }
+    let bounds = bitmap.map(|b| bounds.union(b.bounds())).unwrap_or(bounds);
+    let x0 = (bounds.x0 / pixel_size).floor() as i64;
+    let x1 = (bounds.x1 / pixel_size).ceil() as i64;
+    let y0 = (bounds.y0 / pixel_size).floor() as i64;
+    let y1 = (bounds.y1 / pixel_size).ceil() as i64;
+    let (top, bottom) = (y1 as f64 * pixel_size, y0 as f64 * pixel_size);
+    let (left, right) = (x0 as f64 * pixel_size, x1 as f64 * pixel_size);
+    for x in x0..=x1 {
+        let x = x as f64 * pixel_size;
+        let line = Line::new(space.to_screen((x, bottom)), space.to_screen((x, top)));
+        ctx.stroke(line, &PIXEL_PREVIEW_GRID_COLOR, 1.0);
+    }
+    for y in y0..=y1 {
+        let y = y as f64 * pixel_size;
+        let line = Line::new(space.to_screen((left, y)), space.to_screen((right, y)));
+        ctx.stroke(line, &PIXEL_PREVIEW_GRID_COLOR, 1.0);
+    }
+}
+
+fn metric_line_width(glyph: &Glyph, metrics: &FontMetrics) -> f64 {
+    glyph
+        .advance
+        .as_ref()
+        .map(|a| a.width as f64)
+        .unwrap_or_else(|| (metrics.units_per_em * 0.5).round())
+}
+
+/// Returns the metric line at `pos`, which is in screen space, if any.
+pub(crate) fn hit_test_metric_lines(
+    space: ViewPort,
+    glyph: &Glyph,
+    metrics: &FontMetrics,
+    pos: Point,
+) -> Option<MetricLine> {
+    let width = metric_line_width(glyph, metrics);
+    let x_range = space.to_screen((0.0, 0.0)).x..=space.to_screen((width, 0.0)).x;
+    if !x_range.contains(&pos.x) {
+        return None;
+    }
+    MetricLine::ALL
+        .iter()
+        .map(|line| {
+            (
+                *line,
+                (space.to_screen((0.0, metrics.value(*line))).y - pos.y).abs(),
+            )
+        })
+        .filter(|(_, dist)| *dist <= METRIC_LINE_HIT_DISTANCE)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(line, _)| line)
+}
+
+/// Highlight a metric line that is being dragged.
+pub(crate) fn draw_active_metric(
+    ctx: &mut PaintCtx,
+    env: &Env,
+    space: ViewPort,
+    glyph: &Glyph,
+    metrics: &FontMetrics,
+    line: MetricLine,
+) {
+    let value = metrics.value(line);
+    let width = metric_line_width(glyph, metrics);
+    let line = space.affine() * Line::new((0.0, value), (width, value));
+    ctx.stroke(line, &env.get(theme::SELECTED_GUIDE_COLOR), 2.0);
+}
+
+/// Information about an in-progress drag, shown in a small box next to the
+/// cursor.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DragInfo {
+    /// The current position of the dragged point, if a single point is
+    /// being dragged.
+    pub pos: Option<DPoint>,
+    /// The total distance moved since the drag began.
+    pub delta: DVec2,
+    /// If the dragged point is a handle, its vector from its on-curve point.
+    pub handle: Option<DVec2>,
+}
+
+impl DragInfo {
+    /// Info for a drag where `point` has moved by `delta`.
+    ///
+    /// If `point` is an off-curve point, this includes the length and angle
+    /// of its handle.
+    pub(crate) fn for_point(session: &EditSession, point: EntityId, delta: DVec2) -> DragInfo {
+        let path_point = session.path_point_for_id(point);
+        let handle = path_point.filter(|pt| pt.is_off_curve()).and_then(|pt| {
+            let path = session.path_for_point(point)?;
+            let on_curve = path
+                .prev_point(point)
+                .filter(|prev| prev.is_on_curve())
+                .or_else(|| path.next_point(point).filter(|next| next.is_on_curve()))?;
+            Some(pt.point - on_curve.point)
+        });
+        DragInfo {
+            pos: path_point.map(|pt| pt.point),
+            delta,
+            handle,
+        }
+    }
+
+    fn lines(&self) -> Vec<String> {
+        let mut lines = Vec::with_capacity(3);
+        if let Some(pos) = self.pos {
+            lines.push(format!(
+                "{}, {}",
+                format_num(pos.x, 1),
+                format_num(pos.y, 1)
+            ));
+        }
+        lines.push(format!(
+            "\u{0394} {}, {}",
+            format_num(self.delta.x, 1),
+            format_num(self.delta.y, 1)
+        ));
+        if let Some(handle) = self.handle {
+            let angle = handle.y.atan2(handle.x).to_degrees();
+            lines.push(format!(
+                "{} @ {}\u{00B0}",
+                format_num(handle.hypot(), 1),
+                format_num(angle, 1)
+            ));
+        }
+        lines
+    }
+
+    /// Draw the info next to `cursor`, which is in screen space.
+    pub(crate) fn paint(&self, ctx: &mut PaintCtx, env: &Env, cursor: Point) {
+        let layout = ctx
+            .text()
+            .new_text_layout(self.lines().join("\n"))
+            .font(FontFamily::SYSTEM_UI, DRAG_INFO_FONT_SIZE)
+            .text_color(env.get(theme::DRAG_INFO_TEXT_COLOR))
+            .build()
+            .unwrap();
+        let text_size = layout.size();
+        let box_size = Size::new(
+            text_size.width + DRAG_INFO_PADDING * 2.0,
+            text_size.height + DRAG_INFO_PADDING * 2.0,
+        );
+
+        // keep the box inside the visible area if possible
+        let visible = ctx.region().bounding_box();
+        let mut origin = cursor + Vec2::new(DRAG_INFO_OFFSET, DRAG_INFO_OFFSET);
+        if origin.x + box_size.width > visible.x1 {
+            origin.x = cursor.x - DRAG_INFO_OFFSET - box_size.width;
+        }
+        if origin.y + box_size.height > visible.y1 {
+            origin.y = cursor.y - DRAG_INFO_OFFSET - box_size.height;
+        }
+
+        let rect = Rect::from_origin_size(origin, box_size).to_rounded_rect(3.0);
+        ctx.fill(rect, &env.get(theme::DRAG_INFO_BACKGROUND_COLOR));
+        ctx.draw_text(
+            &layout,
+            origin + Vec2::new(DRAG_INFO_PADDING, DRAG_INFO_PADDING),
+        );
+    }
+}
+
+/// Draw a magnified view of the paths around `focus`, in a circle near it,
+/// so that a point being dragged can be placed precisely.
+///
+/// `focus` is in design space, but need not be on the grid.
+pub(crate) fn draw_loupe(ctx: &mut PaintCtx, env: &Env, session: &EditSession, focus: Point) {
+    let cursor = session.viewport.affine() * focus;
+    // above and to the left of the cursor, unless that is out of view
+    let visible = ctx.region().bounding_box();
+    let mut center = cursor - Vec2::new(LOUPE_OFFSET, LOUPE_OFFSET);
+    if center.x - LOUPE_RADIUS < visible.x0 {
+        center.x = cursor.x + LOUPE_OFFSET;
+    }
+    if center.y - LOUPE_RADIUS < visible.y0 {
+        center.y = cursor.y + LOUPE_OFFSET;
+    }
+
+    // maps design space to the loupe, with `focus` at its center
+    let scale = session.viewport.zoom * LOUPE_MAGNIFICATION;
+    let affine = Affine::new([
+        scale,
+        0.0,
+        0.0,
+        -scale,
+        center.x - focus.x * scale,
+        center.y + focus.y * scale,
+    ]);
+
+    let circle = Circle::new(center, LOUPE_RADIUS);
+    let path_color = env.get(theme::PATH_STROKE_COLOR);
+    let handle_color = env.get(theme::OFF_CURVE_HANDLE_COLOR);
+    ctx.with_save(|ctx| {
+        ctx.clip(circle);
+        ctx.fill(circle, &env.get(theme::GLYPH_LIST_BACKGROUND));
+        let visible_paths = session
+            .paths
+            .iter()
+            .filter(|path| !session.hidden_paths.contains(&path.id()));
+        for path in visible_paths {
+            ctx.stroke(affine * path.bezier(), &path_color, 1.0);
+            let mut end = affine * path.start_point().point.to_raw();
+            for seg in path.iter_segments() {
+                match seg.raw_segment() {
+                    RawSegment::Line(_, p1) => end = affine * p1.point.to_raw(),
+                    RawSegment::Cubic(p0, p1, p2, p3) => {
+                        let p0 = affine * p0.point.to_raw();
+                        let p1 = affine * p1.point.to_raw();
+                        let p2 = affine * p2.point.to_raw();
+                        end = affine * p3.point.to_raw();
+                        ctx.stroke(Line::new(p0, p1), &handle_color, 1.0);
+                        ctx.stroke(Line::new(p2, end), &handle_color, 1.0);
+                    }
+                }
+            }
+            if let Some(trailing) = path.trailing().filter(|_| path.should_draw_trailing()) {
+                let trailing = affine * trailing.to_raw();
+                ctx.stroke(Line::new(end, trailing), &handle_color, 1.0);
+                ctx.fill(Circle::new(trailing, LOUPE_POINT_RADIUS), &handle_color);
+            }
+            for point in path.points() {
+                let color = match point.is_on_curve() {
+                    true => &path_color,
+                    false => &handle_color,
+                };
+                let pos = affine * point.point.to_raw();
+                ctx.fill(Circle::new(pos, LOUPE_POINT_RADIUS), color);
+            }
+        }
+        let h_line = Line::new(
+            (center.x - LOUPE_RADIUS, center.y),
+            (center.x + LOUPE_RADIUS, center.y),
+        );
+        let v_line = Line::new(
+            (center.x, center.y - LOUPE_RADIUS),
+            (center.x, center.y + LOUPE_RADIUS),
+        );
+        ctx.stroke(h_line, &LOUPE_CROSSHAIR_COLOR, 1.0);
+        ctx.stroke(v_line, &LOUPE_CROSSHAIR_COLOR, 1.0);
+    });
+    ctx.stroke(circle, &LOUPE_BORDER_COLOR, 2.0);
 }
 
 /// Return the tangent of the cubic bezier `cb`, at time `t`, as a vector
@@ -1,32 +1,60 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
-use druid::kurbo::{BezPath, Point, Rect, Shape, Size, Vec2};
+use druid::kurbo::{Affine, BezPath, Line, Point, Rect, Shape, Size, Vec2};
 use druid::{Data, Lens};
 use norad::glyph::Outline;
 use norad::{Glyph, GlyphName};
 
-use crate::component::Component;
-use crate::data::Workspace;
-use crate::design_space::{DPoint, DVec2, ViewPort};
-use crate::guides::Guide;
+use crate::component::{Component, ComponentParams};
+use crate::consts::cmd::Alignment;
+use crate::data::{NudgeAmounts, Workspace};
+use crate::design_space::{self, DPoint, DVec2, Precision, ViewPort};
+use crate::font_info::{self, BlueZone};
+use crate::glyph_lock;
+use crate::guides::{Guide, GuideEdit, GuideLine};
+use crate::hit_index::HitIndexCache;
+use crate::nib::Nib;
 use crate::path::{Path, Segment};
-use crate::point::{EntityId, PathPoint};
+use crate::point::{EntityId, PathPoint, PointType};
 use crate::quadrant::Quadrant;
+use crate::quadratic;
 use crate::selection::Selection;
+use crate::settings::ToolOptions;
+use crate::snapshot::Snapshot;
+use crate::tunni::Tunni;
 
 /// Minimum distance in screen units that a click must occur to be considered
 /// on a point?
+///
+/// This is only the default; the editor uses the theme's `CLICK_DISTANCE`.
 //TODO: this doesn't feel very robust; items themselves should have hitzones?
 pub const MIN_CLICK_DISTANCE: f64 = 10.0;
 pub const SEGMENT_CLICK_DISTANCE: f64 = 6.0;
-/// Amount of bias penalizing on-curve points; we want to break ties in favor
-/// of off-curve.
-pub const ON_CURVE_PENALTY: f64 = MIN_CLICK_DISTANCE / 2.0;
+
+/// The zoom levels between which the click distance shrinks to half of its
+/// size; past the larger, points that are close in design space are far
+/// enough apart on screen to be picked precisely.
+const CLICK_DISTANCE_ZOOM_RANGE: (f64, f64) = (4.0, 32.0);
+
+/// The max distance, in screen space, at which a dragged point snaps to the
+/// edge of an alignment zone.
+const ZONE_SNAP_DISTANCE: f64 = 6.0;
+
+/// The max distance, in screen space, at which a dragged point snaps into
+/// line with another point or a guide.
+const ALIGNMENT_SNAP_DISTANCE: f64 = 4.0;
+
+/// Points within this distance, in design units, of a selected point's
+/// coordinate are treated as aligned with it.
+const ALIGNED_POINT_TOLERANCE: f64 = 0.5;
+
+/// The key in a contour's lib under which we store the contour's name.
+const CONTOUR_NAME_LIB_KEY: &str = "org.linebender.runebender.contour-name";
 
 /// A unique identifier for a session. A session keeps the same identifier
 /// even if the name of the glyph changes.
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Data)]
 pub struct SessionId(usize);
 
 impl SessionId {
@@ -46,11 +74,111 @@ pub struct EditSession {
     pub glyph: Arc<Glyph>,
     pub paths: Arc<Vec<Path>>,
     pub selection: Selection,
+    /// Selections saved to numbered slots, so they can be restored later.
+    ///
+    /// These are kept when undoing, and aren't saved with the glyph.
+    pub named_selections: Arc<BTreeMap<u8, Selection>>,
+    /// Points that can't be moved or deleted until they are unlocked.
+    pub locked: Arc<BTreeSet<EntityId>>,
+    /// Names given to paths in the contour list, by path id.
+    ///
+    /// These are saved in each contour's lib.
+    pub contour_names: Arc<BTreeMap<EntityId, String>>,
+    /// Paths that are not drawn or hit tested, by path id.
+    ///
+    /// Like locked points, these aren't saved with the glyph.
+    pub hidden_paths: Arc<BTreeSet<EntityId>>,
     pub components: Arc<Vec<Component>>,
+    /// Changes whenever the paths or components change; see [`generation`].
+    ///
+    /// [`generation`]: EditSession::generation
+    generation: u64,
     pub guides: Arc<Vec<Guide>>,
     pub viewport: ViewPort,
     work_bounds: Rect,
     quadrant: Quadrant,
+    /// The alignment zones that dragged points snap to, if snapping is enabled.
+    ///
+    /// This is set by the editor before each mouse event.
+    #[data(ignore)]
+    pub(crate) snap_zones: Option<Arc<Vec<BlueZone>>>,
+    /// The font's units per em, for tools that report sizes relative to it.
+    ///
+    /// This is also refreshed by the editor before each mouse event.
+    #[data(ignore)]
+    pub(crate) units_per_em: f64,
+    /// How far the arrow keys move the selection.
+    ///
+    /// This is set by the editor before each key event.
+    #[data(ignore)]
+    pub(crate) nudge_amounts: NudgeAmounts,
+    /// How finely new and moved points are rounded.
+    ///
+    /// This is set by the editor before each event, from the workspace.
+    #[data(ignore)]
+    pub(crate) precision: Precision,
+    /// The distance, in display points, within which a click hits a point,
+    /// before it is adjusted for the zoom; see [`click_distance`].
+    ///
+    /// This is set by the editor before each mouse event, from the theme.
+    ///
+    /// [`click_distance`]: EditSession::click_distance
+    #[data(ignore)]
+    pub(crate) base_click_distance: f64,
+    /// The font's guidelines, which are shared by every glyph.
+    #[data(ignore)]
+    pub(crate) global_guides: Arc<Vec<Guide>>,
+    /// Whether Tunni lines are shown, and so can be dragged.
+    ///
+    /// This is set by the editor before each mouse event.
+    #[data(ignore)]
+    pub(crate) show_tunni_lines: bool,
+    /// Whether tools show a magnified view while dragging.
+    ///
+    /// This is set by the editor before each mouse event.
+    #[data(ignore)]
+    pub(crate) show_loupe: bool,
+    /// Whether the select tool's keyboard editing commands are enabled.
+    ///
+    /// This is set by the editor before each key event.
+    #[data(ignore)]
+    pub(crate) keyboard_editing: bool,
+    /// The nib that the nib tool draws with.
+    ///
+    /// This is set by the editor before each mouse event.
+    #[data(ignore)]
+    pub(crate) nib: Nib,
+    /// The options of the editor's tools.
+    ///
+    /// This is set by the editor before each mouse event.
+    #[data(ignore)]
+    pub(crate) tool_options: ToolOptions,
+    /// The last transform applied to the selection, in design space, which
+    /// 'transform again' repeats.
+    #[data(ignore)]
+    pub(crate) last_transform: Option<Affine>,
+    /// A spatial index of the paths, to speed up hit testing large glyphs.
+    #[data(ignore)]
+    hit_index: HitIndexCache,
+    /// The paths that were quadratic contours when the glyph was loaded,
+    /// which are converted back to quadratics when saved.
+    #[data(ignore)]
+    quadratic_paths: Arc<BTreeSet<EntityId>>,
+    /// The outline as it was when the glyph was opened or last saved.
+    saved: SavedOutline,
+}
+
+/// The parts of a session that are written to the UFO, as they were at some
+/// point, so that we can tell whether they have been edited since.
+///
+/// Edits always replace these `Arc`s, so comparing pointers is enough.
+#[derive(Debug, Clone, Data)]
+struct SavedOutline {
+    glyph: Arc<Glyph>,
+    paths: Arc<Vec<Path>>,
+    contour_names: Arc<BTreeMap<EntityId, String>>,
+    components: Arc<Vec<Component>>,
+    guides: Arc<Vec<Guide>>,
 }
 
 /// A type that is only created by a lens, for our coordinate editing panel
@@ -61,6 +189,33 @@ pub struct CoordinateSelection {
     /// the bounding box of the selection
     pub frame: Rect,
     pub quadrant: Quadrant,
+    /// how finely edited coordinates are rounded
+    pub precision: Precision,
+}
+
+/// A row in the contour list: a summary of one path.
+#[derive(Debug, Clone, Data, Lens)]
+pub struct ContourInfo {
+    pub id: EntityId,
+    /// The name given to the path, which may be empty.
+    pub name: String,
+    pub point_count: usize,
+    pub closed: bool,
+    /// Whether the path goes clockwise; `None` for open or empty paths.
+    pub clockwise: Option<bool>,
+    pub hidden: bool,
+    /// Whether all of the path's points are locked.
+    pub locked: bool,
+    /// Whether any of the path's points are selected.
+    pub selected: bool,
+}
+
+/// Session generations are unique, so that sessions with the same
+/// generation have the same paths and components.
+fn next_generation() -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
 }
 
 impl EditSession {
@@ -74,17 +229,43 @@ impl EditSession {
         let paths: Vec<Path> = glyph
             .outline
             .as_ref()
-            .map(|ol| ol.contours.iter().map(Path::from_norad).collect())
+            .map(|ol| {
+                ol.contours
+                    .iter()
+                    .map(|contour| Path::from_norad(contour, glyphs.precision))
+                    .collect()
+            })
             .unwrap_or_default();
         let components = glyph
             .outline
             .as_ref()
             .map(|ol| ol.components.iter().map(Component::from_norad).collect())
             .unwrap_or_default();
+        let quadratic_paths = glyph
+            .outline
+            .iter()
+            .flat_map(|ol| ol.contours.iter().zip(paths.iter()))
+            .filter(|(contour, _)| quadratic::is_quadratic(contour))
+            .map(|(_, path)| path.id())
+            .collect();
+        let contour_names = glyph
+            .outline
+            .iter()
+            .flat_map(|ol| ol.contours.iter().zip(paths.iter()))
+            .filter_map(|(contour, path)| {
+                let name = contour.lib()?.get(CONTOUR_NAME_LIB_KEY)?.as_string()?;
+                Some((path.id(), name.to_owned()))
+            })
+            .collect();
         let guides = glyph
             .guidelines
             .as_ref()
-            .map(|guides| guides.iter().map(Guide::from_norad).collect())
+            .map(|guides| {
+                guides
+                    .iter()
+                    .map(|guide| Guide::from_norad(guide, glyphs.precision))
+                    .collect()
+            })
             .unwrap_or_default();
 
         //FIXME: this is never updated, and shouldn't be relied on
@@ -93,20 +274,89 @@ impl EditSession {
             .map(|b| b.bounding_box())
             .unwrap_or_default();
 
-        EditSession {
-            id: SessionId::next(),
-            name,
+        let saved = SavedOutline {
             glyph,
             paths: Arc::new(paths),
-            selection: Selection::new(),
+            contour_names: Arc::new(contour_names),
             components: Arc::new(components),
             guides: Arc::new(guides),
+        };
+
+        EditSession {
+            id: SessionId::next(),
+            name,
+            glyph: saved.glyph.clone(),
+            paths: saved.paths.clone(),
+            selection: Selection::new(),
+            named_selections: Arc::new(BTreeMap::new()),
+            locked: Arc::new(BTreeSet::new()),
+            contour_names: saved.contour_names.clone(),
+            hidden_paths: Arc::new(BTreeSet::new()),
+            components: saved.components.clone(),
+            generation: next_generation(),
+            guides: saved.guides.clone(),
             viewport: ViewPort::default(),
             quadrant: Quadrant::Center,
             work_bounds,
+            snap_zones: None,
+            units_per_em: glyphs.units_per_em(),
+            nudge_amounts: glyphs.nudge_amounts,
+            precision: glyphs.precision,
+            base_click_distance: MIN_CLICK_DISTANCE,
+            global_guides: Arc::new(glyphs.global_guides()),
+            show_tunni_lines: glyphs.show_tunni_lines,
+            show_loupe: glyphs.show_loupe,
+            keyboard_editing: glyphs.keyboard_editing,
+            nib: glyphs.nib,
+            tool_options: glyphs.tool_options,
+            last_transform: None,
+            hit_index: HitIndexCache::default(),
+            quadratic_paths: Arc::new(quadratic_paths),
+            saved,
         }
     }
 
+    /// Whether the glyph is locked, in which case it can't be edited.
+    pub fn is_read_only(&self) -> bool {
+        glyph_lock::is_locked(&self.glyph)
+    }
+
+    /// A number that changes whenever the paths or components change.
+    ///
+    /// Widgets can compare this instead of the paths and components
+    /// themselves to find out whether the outline needs redrawing.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Record that the paths or components have been changed.
+    ///
+    /// This is done by `paths_mut` and `components_mut`; it only needs to be
+    /// called when the paths or components are replaced outright.
+    pub(crate) fn outline_changed(&mut self) {
+        self.generation = next_generation();
+    }
+
+    /// Whether the glyph has been edited since it was opened or last saved.
+    pub fn is_dirty(&self) -> bool {
+        !(self.glyph.same(&self.saved.glyph)
+            && self.paths.same(&self.saved.paths)
+            && self.contour_names.same(&self.saved.contour_names)
+            && self.components.same(&self.saved.components)
+            && self.guides.same(&self.saved.guides))
+    }
+
+    /// Record that the glyph, as it is now, has been saved.
+    pub(crate) fn mark_saved(&mut self) {
+        self.saved = SavedOutline {
+            glyph: self.glyph.clone(),
+            paths: self.paths.clone(),
+            contour_names: self.contour_names.clone(),
+            components: self.components.clone(),
+            guides: self.guides.clone(),
+        };
+    }
+
     /// Construct a bezier of the paths in this glyph, ignoring components.
     pub fn to_bezier(&self) -> BezPath {
         let mut bez = BezPath::new();
@@ -125,6 +375,9 @@ impl EditSession {
     pub fn update_glyph_metadata(&mut self, changed: &Arc<Glyph>) {
         let glyph = Arc::make_mut(&mut self.glyph);
         glyph.advance = changed.advance.clone();
+        glyph.codepoints = changed.codepoints.clone();
+        glyph.note = changed.note.clone();
+        glyph.lib = changed.lib.clone();
     }
 
     pub fn rename(&mut self, name: GlyphName) {
@@ -134,6 +387,54 @@ impl EditSession {
         glyph.name = name;
     }
 
+    /// Point components based on the glyph `old` at the glyph `new`.
+    pub fn rename_component_base(&mut self, old: &GlyphName, new: &GlyphName) {
+        for component in self.components_mut() {
+            if component.base == *old {
+                component.base = new.clone();
+            }
+        }
+    }
+
+    /// Replace the components based on `base` with the outline of `base`.
+    pub(crate) fn decompose_components(&mut self, base: &Glyph) {
+        let (uses, others): (Vec<_>, Vec<_>) = self
+            .components
+            .iter()
+            .cloned()
+            .partition(|component| component.base == base.name);
+        let mut components = others;
+        let precision = self.precision;
+        for component in uses {
+            let outline = crate::component::decompose(base, component.transform);
+            self.paths_mut().extend(
+                outline
+                    .contours
+                    .iter()
+                    .map(|contour| Path::from_norad(contour, precision)),
+            );
+            components.extend(outline.components.iter().map(Component::from_norad));
+        }
+        self.components = Arc::new(components);
+        self.outline_changed();
+    }
+
+    /// Replace the paths at `indices` with `components`.
+    pub(crate) fn replace_paths_with_components(
+        &mut self,
+        indices: &[usize],
+        components: &[norad::glyph::Component],
+    ) {
+        let mut index = 0;
+        self.paths_mut().retain(|_| {
+            index += 1;
+            !indices.contains(&(index - 1))
+        });
+        self.components_mut()
+            .extend(components.iter().map(Component::from_norad));
+        self.selection.clear();
+    }
+
     /// Returns the current layout bounds of the 'work', that is, all the things
     /// that are 'part of the glyph'.
     pub fn work_bounds(&self) -> Rect {
@@ -141,10 +442,12 @@ impl EditSession {
     }
 
     pub fn paths_mut(&mut self) -> &mut Vec<Path> {
+        self.outline_changed();
         Arc::make_mut(&mut self.paths)
     }
 
     pub fn components_mut(&mut self) -> &mut Vec<Component> {
+        self.outline_changed();
         Arc::make_mut(&mut self.components)
     }
 
@@ -156,6 +459,23 @@ impl EditSession {
         self.paths.iter().flat_map(|p| p.points().iter())
     }
 
+    /// The points of the paths that aren't hidden.
+    pub(crate) fn iter_visible_points(&self) -> impl Iterator<Item = &PathPoint> {
+        self.paths
+            .iter()
+            .filter(move |p| !self.hidden_paths.contains(&p.id()))
+            .flat_map(|p| p.points().iter())
+    }
+
+    /// The index of the first path with a selected point.
+    pub(crate) fn selected_path_index(&self) -> Option<usize> {
+        self.paths.iter().position(|path| {
+            path.points()
+                .iter()
+                .any(|pt| self.selection.contains(&pt.id))
+        })
+    }
+
     pub(crate) fn paths_for_selection(&self) -> Vec<Path> {
         let mut result = Vec::new();
         for paths in self
@@ -193,12 +513,29 @@ impl EditSession {
     }
     */
 
+    /// The distance, in screen space, within which a click hits a point.
+    ///
+    /// Points are drawn at the same size at every zoom, and so is this
+    /// distance when zoomed out; when zoomed far in it shrinks, so that
+    /// handles that are drawn overlapping can be picked apart.
+    pub(crate) fn click_distance(&self) -> f64 {
+        let (min_zoom, max_zoom) = CLICK_DISTANCE_ZOOM_RANGE;
+        let zoom = self.viewport.zoom.max(min_zoom).min(max_zoom);
+        let t = (zoom / min_zoom).log2() / (max_zoom / min_zoom).log2();
+        self.base_click_distance * (1.0 - t / 2.0)
+    }
+
     /// Find the best hit, considering all items.
     pub fn hit_test_all(&self, point: Point, max_dist: Option<f64>) -> Option<EntityId> {
         if let Some(hit) = self.hit_test_filtered(point, max_dist, |_| true) {
             return Some(hit);
         }
-        let max_dist = max_dist.unwrap_or(MIN_CLICK_DISTANCE);
+        self.hit_test_guides(point, max_dist)
+    }
+
+    /// Hit test a point against the glyph's guides.
+    pub(crate) fn hit_test_guides(&self, point: Point, max_dist: Option<f64>) -> Option<EntityId> {
+        let max_dist = max_dist.unwrap_or_else(|| self.click_distance());
         let mut best = None;
         for g in &*self.guides {
             let dist = g.screen_dist(self.viewport, point);
@@ -223,14 +560,26 @@ impl EditSession {
         max_dist: Option<f64>,
         mut f: impl FnMut(&PathPoint) -> bool,
     ) -> Option<EntityId> {
-        let max_dist = max_dist.unwrap_or(MIN_CLICK_DISTANCE);
+        let max_dist = max_dist.unwrap_or_else(|| self.click_distance());
+        // a bias penalizing on-curve points; we want to break ties in favor
+        // of off-curve.
+        let on_curve_penalty = self.click_distance() / 2.0;
+        let index = self.hit_index.get(&self.paths);
+        let nearby = index.as_ref().map(|index| {
+            let dpt = self.viewport.inverse_affine() * point;
+            index.points_near(dpt, max_dist / self.viewport.zoom)
+        });
+        let points: Box<dyn Iterator<Item = &PathPoint> + '_> = match nearby {
+            Some(nearby) => Box::new(nearby.into_iter()),
+            None => Box::new(self.iter_points()),
+        };
         let mut best = None;
-        for p in self.iter_points() {
-            if f(p) {
+        for p in points {
+            if f(p) && !self.hidden_paths.contains(&p.id.parent()) {
                 let dist = p.screen_dist(self.viewport, point);
                 let score = dist
                     + if p.is_on_curve() {
-                        ON_CURVE_PENALTY
+                        on_curve_penalty
                     } else {
                         0.0
                     };
@@ -243,17 +592,75 @@ impl EditSession {
     }
 
     /// Hit test a point against the path segments.
+    /// The curve whose Tunni point is at `point`, in screen space, if Tunni
+    /// lines are shown.
+    pub(crate) fn hit_test_tunni(&self, point: Point) -> Option<Tunni> {
+        if !self.show_tunni_lines {
+            return None;
+        }
+        let affine = self.viewport.affine();
+        let max_dist = crate::draw::TUNNI_POINT_RADIUS + 2.0;
+        self.paths
+            .iter()
+            .filter(|path| !self.hidden_paths.contains(&path.id()))
+            .flat_map(Path::iter_segments)
+            .filter_map(|seg| Tunni::for_segment(&seg))
+            .find(|tunni| (affine * tunni.point()).distance(point) <= max_dist)
+    }
+
+    /// Whether either of the handles of the curve `tunni` was made from is
+    /// locked, so that its Tunni point can't be dragged.
+    pub(crate) fn tunni_is_locked(&self, tunni: &Tunni) -> bool {
+        let (id1, id2) = tunni.handles;
+        self.locked.contains(&id1) || self.locked.contains(&id2)
+    }
+
+    /// Move the handles of the curve `tunni` was made from so that its Tunni
+    /// point is as near as possible to `target`, in design space.
+    ///
+    /// Nothing moves if either handle is locked.
+    pub(crate) fn drag_tunni_point(&mut self, tunni: &Tunni, target: Point, symmetric: bool) {
+        if self.tunni_is_locked(tunni) {
+            return;
+        }
+        let (p1, p2) = tunni.handles_for_point(target, symmetric);
+        let (id1, id2) = tunni.handles;
+        let (p1, p2) = (
+            DPoint::from_raw(p1, self.precision),
+            DPoint::from_raw(p2, self.precision),
+        );
+        if let Some(path) = self.path_for_point_mut(id1) {
+            path.move_points(&[(id1, p1), (id2, p2)]);
+        }
+    }
+
     pub fn hit_test_segments(&self, point: Point, max_dist: Option<f64>) -> Option<(Segment, f64)> {
-        let max_dist = max_dist.unwrap_or(MIN_CLICK_DISTANCE);
-        let dpt = self.viewport.from_screen(point);
+        let max_dist = max_dist.unwrap_or_else(|| self.click_distance());
+        let dpt = self.viewport.from_screen(point, self.precision);
         let mut best = None;
-        for path in &*self.paths {
-            for seg in path.iter_segments() {
-                let (t, d2) = seg.nearest(dpt);
-                if best.as_ref().map(|(_seg, _t, d)| d2 < *d).unwrap_or(true) {
-                    best = Some((seg, t, d2));
-                }
+        let mut check = |seg: &Segment| {
+            if self.hidden_paths.contains(&seg.start_id().parent()) {
+                return;
+            }
+            let (t, d2) = seg.nearest(dpt);
+            if best.as_ref().map(|(_seg, _t, d)| d2 < *d).unwrap_or(true) {
+                best = Some((seg.clone(), t, d2));
             }
+        };
+        match self.hit_index.get(&self.paths) {
+            Some(index) => {
+                // `dpt` is rounded to the grid, so we look a little further
+                let radius = max_dist / self.viewport.zoom + 1.0;
+                index
+                    .segments_near(dpt.to_raw(), radius)
+                    .into_iter()
+                    .for_each(check);
+            }
+            None => self
+                .paths
+                .iter()
+                .flat_map(Path::iter_segments)
+                .for_each(|seg| check(&seg)),
         }
         if let Some((seg, t, d2)) = best {
             if d2 * self.viewport.zoom.powi(2) < max_dist.powi(2) {
@@ -288,6 +695,77 @@ impl EditSession {
         }
     }
 
+    /// If `id` is the first or last point of an open path, the index of that
+    /// path, and whether `id` is its first point.
+    fn open_endpoint(&self, id: EntityId) -> Option<(usize, bool)> {
+        let idx = self.path_idx_for_point(id)?;
+        let path = &self.paths[idx];
+        if path.is_closed() {
+            return None;
+        }
+        if path.start_point().id == id {
+            Some((idx, true))
+        } else if path.points().last().map(|pt| pt.id) == Some(id) {
+            Some((idx, false))
+        } else {
+            None
+        }
+    }
+
+    /// Select the end of the open path ending at `id`, so that the pen
+    /// continues drawing from it.
+    ///
+    /// If `id` is the path's first point the path is reversed, so that it
+    /// becomes the last. Returns `false` if `id` isn't the first or last
+    /// point of an open path.
+    pub(crate) fn continue_path(&mut self, id: EntityId) -> bool {
+        let (idx, is_start) = match self.open_endpoint(id) {
+            Some(endpoint) => endpoint,
+            None => return false,
+        };
+        if is_start && self.paths[idx].points().len() > 1 {
+            self.paths_mut()[idx].reverse_contour();
+        }
+        self.selection.select_one(id);
+        true
+    }
+
+    /// Join the end of the path being drawn to `id`, the first or last point
+    /// of another open path, merging the two paths.
+    ///
+    /// Returns `false` if the paths can't be joined.
+    pub(crate) fn join_active_path(&mut self, id: EntityId) -> bool {
+        let active_idx = match self.active_path_idx() {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let active_id = *self.selection.iter().next().unwrap();
+        let (other_idx, is_start) = match self.open_endpoint(id) {
+            Some(endpoint) => endpoint,
+            None => return false,
+        };
+        let active = &self.paths[active_idx];
+        let other = &self.paths[other_idx];
+        if other_idx == active_idx
+            || active.is_closed()
+            || active.points().last().map(|pt| pt.id) != Some(active_id)
+            || active.is_hyper() != other.is_hyper()
+        {
+            return false;
+        }
+
+        let mut other = other.clone();
+        if !is_start {
+            other.reverse_contour();
+        }
+        let precision = self.precision;
+        let paths = self.paths_mut();
+        paths[active_idx].append(&other, false, precision);
+        paths.remove(other_idx);
+        self.selection.clear();
+        true
+    }
+
     pub fn path_point_for_id(&self, id: EntityId) -> Option<PathPoint> {
         self.path_for_point(id)
             .and_then(|path| path.path_point_for_id(id))
@@ -307,7 +785,8 @@ impl EditSession {
         self.paths.iter().position(|p| p.contains(&point))
     }
 
-    pub(crate) fn add_path(&mut self, path: Path) {
+    /// Add a new path to the glyph, selecting its first point.
+    pub fn add_path(&mut self, path: Path) {
         let point = path.points()[0].id;
         self.paths_mut().push(path);
         self.selection.select_one(point);
@@ -326,16 +805,53 @@ impl EditSession {
         }
     }
 
+    /// Make an on-curve point smooth or a corner.
+    ///
+    /// A point can only be smooth if it has an adjacent off-curve point.
+    /// Returns `true` if the point was changed.
+    pub fn set_point_smooth(&mut self, id: EntityId, smooth: bool) -> bool {
+        match self.path_point_for_id(id) {
+            Some(pt) if pt.is_on_curve() && pt.is_smooth() != smooth => (),
+            _ => return false,
+        }
+        if let Some(path) = self.path_for_point_mut(id) {
+            path.toggle_on_curve_point_type(id);
+        }
+        self.path_point_for_id(id).map(|pt| pt.is_smooth()) == Some(smooth)
+    }
+
+    /// The selected points, in the order they appear in the glyph's paths.
+    pub(crate) fn selected_path_points(&self) -> Arc<Vec<PathPoint>> {
+        let points = self
+            .iter_points()
+            .filter(|pt| self.selection.contains(&pt.id))
+            .copied()
+            .collect();
+        Arc::new(points)
+    }
+
     /// if a guide his horizontal or vertical, toggle between the two.
     pub fn toggle_guide(&mut self, id: EntityId, pos: Point) {
-        let pos = self.viewport.from_screen(pos);
-        if let Some(guide) = self.guides_mut().iter_mut().find(|g| g.id == id) {
+        let pos = self.viewport.from_screen(pos, self.precision);
+        if let Some(guide) = self
+            .guides_mut()
+            .iter_mut()
+            .find(|g| g.id == id && !g.locked)
+        {
             guide.toggle_vertical_horiz(pos);
         }
     }
 
+    /// Change a guide's name, color, or lock; returns `true` if it changed.
+    pub(crate) fn edit_guide(&mut self, id: EntityId, edit: &GuideEdit) -> bool {
+        match self.guides.iter().position(|g| g.id == id) {
+            Some(idx) => self.guides_mut()[idx].apply_edit(edit),
+            None => false,
+        }
+    }
+
     pub fn delete_selection(&mut self) {
-        let to_delete = self.selection.per_path_selection();
+        let to_delete = self.unlocked_selection().per_path_selection();
         self.selection.clear();
         // if only deleting points from a single path, we will select a point
         // in that path afterwards.
@@ -355,11 +871,115 @@ impl EditSession {
         self.paths_mut().retain(|p| !p.points().is_empty());
     }
 
+    /// The selection, without any locked points or guides.
+    fn unlocked_selection(&self) -> Selection {
+        self.selection
+            .iter()
+            .filter(|id| !self.locked.contains(id))
+            .filter(|id| !id.is_guide() || !self.guides.iter().any(|g| g.id == **id && g.locked))
+            .copied()
+            .collect()
+    }
+
+    /// Lock the selected points, or unlock them if they are all locked.
+    pub(crate) fn toggle_lock_selection(&mut self) {
+        let selected = self
+            .selection
+            .iter()
+            .filter(|id| !id.is_guide())
+            .copied()
+            .collect::<Vec<_>>();
+        let locked = Arc::make_mut(&mut self.locked);
+        if selected.iter().all(|id| locked.contains(id)) {
+            for id in selected {
+                locked.remove(&id);
+            }
+        } else {
+            locked.extend(selected);
+        }
+    }
+
+    /// Lock every point in the paths that contain a selected point.
+    pub(crate) fn lock_selected_contours(&mut self) {
+        let ids = self
+            .paths
+            .iter()
+            .filter(|path| self.selection.iter().any(|id| path.contains(id)))
+            .flat_map(|path| path.points().iter().map(|pt| pt.id))
+            .collect::<Vec<_>>();
+        Arc::make_mut(&mut self.locked).extend(ids);
+    }
+
+    pub(crate) fn unlock_all(&mut self) {
+        self.locked = Arc::new(BTreeSet::new());
+    }
+
     /// Select all points.
     //NOTE: should this select other things too? Which ones?
     pub fn select_all(&mut self) {
         self.selection.clear();
-        self.selection = self.iter_points().map(|p| p.id).collect();
+        self.selection = self.iter_visible_points().map(|p| p.id).collect();
+    }
+
+    /// Add every on-curve point that shares an x coordinate (or, if
+    /// `vertical` is false, a y coordinate) with a selected point to the
+    /// selection; this picks up both sides of a stem or crossbar at once.
+    pub(crate) fn select_aligned_points(&mut self, vertical: bool) {
+        let coord = |pt: &PathPoint| if vertical { pt.point.x } else { pt.point.y };
+        let selected: Vec<f64> = self
+            .iter_points()
+            .filter(|pt| self.selection.contains(&pt.id))
+            .map(coord)
+            .collect();
+        let aligned: Vec<EntityId> = self
+            .iter_points()
+            .filter(|pt| pt.is_on_curve())
+            .filter(|pt| {
+                selected
+                    .iter()
+                    .any(|c| (coord(pt) - c).abs() <= ALIGNED_POINT_TOLERANCE)
+            })
+            .map(|pt| pt.id)
+            .collect();
+        self.selection.extend(aligned);
+    }
+
+    /// Save the current selection in `slot`, replacing anything saved there.
+    ///
+    /// Saving an empty selection clears the slot.
+    pub(crate) fn save_named_selection(&mut self, slot: u8) {
+        let named = Arc::make_mut(&mut self.named_selections);
+        if self.selection.is_empty() {
+            named.remove(&slot);
+        } else {
+            named.insert(slot, self.selection.clone());
+        }
+    }
+
+    /// Select the items saved in `slot` that still exist.
+    ///
+    /// Returns `false` if nothing is saved there.
+    pub(crate) fn restore_named_selection(&mut self, slot: u8) -> bool {
+        let saved = match self.named_selections.get(&slot) {
+            Some(saved) => saved.clone(),
+            None => return false,
+        };
+        self.selection = saved
+            .iter()
+            .filter(|id| self.contains_entity(**id))
+            .copied()
+            .collect();
+        true
+    }
+
+    /// `true` if `id` is a point, guide, or component in this glyph.
+    fn contains_entity(&self, id: EntityId) -> bool {
+        if id.is_guide() {
+            self.guides.iter().any(|guide| guide.id == id)
+        } else {
+            self.path_idx_for_point(id).is_some()
+                || self.components.iter().any(|comp| comp.id == id)
+        }
     }
 
     /// returns a rect representing the containing rect of the current selection
@@ -377,31 +997,214 @@ impl EditSession {
     }
 
     /// If the current selection is a single point, select the next point
-    /// on that path.
+    /// in the glyph, continuing on to the next path after a path's last
+    /// point. If nothing is selected, select the first point.
     pub fn select_next(&mut self) {
-        if self.selection.len() != 1 {
-            return;
-        }
-        let id = self.selection.iter().next().copied().unwrap();
-        let id = self
-            .path_for_point(id)
-            .and_then(|path| path.next_point(id).map(|pp| pp.id))
-            .unwrap_or(id);
-        self.selection.select_one(id);
+        self.select_adjacent_point(true)
     }
 
     /// If the current selection is a single point, select the previous point
-    /// on that path.
+    /// in the glyph, continuing on to the previous path before a path's
+    /// first point. If nothing is selected, select the last point.
     pub fn select_prev(&mut self) {
+        self.select_adjacent_point(false)
+    }
+
+    fn select_adjacent_point(&mut self, forward: bool) {
+        let points: Vec<EntityId> = self
+            .paths
+            .iter()
+            .flat_map(|path| path.points().iter().map(|pt| pt.id))
+            .collect();
+        if let Some(idx) = self.adjacent_index(&points, forward) {
+            self.selection.select_one(points[idx]);
+        }
+    }
+
+    /// Select the first point of the path after the path of the selected
+    /// point, or before it if `forward` is false.
+    pub(crate) fn select_adjacent_path(&mut self, forward: bool) {
+        let paths: Vec<EntityId> = self.paths.iter().map(|path| path.id()).collect();
+        let current = self.selection.iter().next().map(|id| id.parent());
+        let idx = match current {
+            Some(id) if self.selection.iter().all(|pt| pt.is_child_of(id)) => {
+                match paths.iter().position(|path| *path == id) {
+                    Some(idx) if forward => (idx + 1) % paths.len(),
+                    Some(idx) => (idx + paths.len() - 1) % paths.len(),
+                    None => return,
+                }
+            }
+            None if !paths.is_empty() => 0,
+            _ => return,
+        };
+        if let Some(point) = self.paths[idx].points().first() {
+            self.selection.select_one(point.id);
+        }
+    }
+
+    /// The index in `items` that follows (or precedes) the single selected
+    /// item, wrapping around at the ends.
+    fn adjacent_index(&self, items: &[EntityId], forward: bool) -> Option<usize> {
+        let len = items.len();
+        if len == 0 {
+            return None;
+        }
+        match self.selection.len() {
+            0 if forward => Some(0),
+            0 => Some(len - 1),
+            1 => {
+                let id = self.selection.iter().next()?;
+                let idx = items.iter().position(|item| item == id)?;
+                Some(if forward {
+                    (idx + 1) % len
+                } else {
+                    (idx + len - 1) % len
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Add a point after the selected point, without using the mouse.
+    ///
+    /// If the point starts a segment, the segment is split at its middle;
+    /// if it ends an open path, a new point is added `distance` units to
+    /// its right. The new point is selected.
+    pub(crate) fn insert_point_after_selection(&mut self, distance: f64) -> bool {
         if self.selection.len() != 1 {
-            return;
+            return false;
         }
-        let id = self.selection.iter().next().copied().unwrap();
-        let id = self
-            .path_for_point(id)
-            .and_then(|path| path.prev_point(id).map(|pp| pp.id))
-            .unwrap_or(id);
+        let id = *self.selection.iter().next().unwrap();
+        let precision = self.precision;
+        let path = match self.path_for_point_mut(id) {
+            Some(path) => path,
+            None => return false,
+        };
+        match path.iter_segments().find(|seg| seg.start_id() == id) {
+            Some(seg) => self.insert_point_on_segment(seg, 0.5).is_some(),
+            None if !path.is_closed() && path.points().last().map(|pt| pt.id) == Some(id) => {
+                let last = path.points().last().unwrap().point;
+                let new_id =
+                    path.line_to(last + DVec2::from_raw((distance, 0.0), precision), false);
+                self.selection.select_one(new_id);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Split `seg` at `t`, and select the new on-curve point.
+    pub(crate) fn insert_point_on_segment(&mut self, seg: Segment, t: f64) -> Option<EntityId> {
+        let precision = self.precision;
+        let path = self.path_for_point_mut(seg.start_id())?;
+        let before: BTreeSet<EntityId> = path.points().iter().map(|pt| pt.id).collect();
+        path.split_segment_at_point(seg, t, precision);
+        let new_point = path
+            .points()
+            .iter()
+            .find(|pt| pt.is_on_curve() && !before.contains(&pt.id))?
+            .id;
+        self.selection.select_one(new_point);
+        Some(new_point)
+    }
+
+    /// The segment that starts at the on-curve point `start`.
+    pub(crate) fn segment_starting_at(&self, start: EntityId) -> Option<Segment> {
+        self.path_for_point(start)?
+            .iter_segments()
+            .find(|seg| seg.start_id() == start)
+    }
+
+    /// Make the segment starting at `start` a line, or a curve if `line`
+    /// is false.
+    ///
+    /// Returns `false` if the segment is already of that type.
+    pub(crate) fn set_segment_is_line(&mut self, start: EntityId, line: bool) -> bool {
+        let seg = match self.segment_starting_at(start) {
+            Some(seg) if seg.is_line() != line => seg,
+            _ => return false,
+        };
+        let precision = self.precision;
+        let path = self.path_for_point_mut(start).unwrap();
+        if line {
+            path.downgrade_curve_seg(&seg);
+        } else {
+            path.upgrade_line_seg(&seg, false, precision);
+        }
+        // the handles of a curve are gone
+        self.selection = self
+            .selection
+            .iter()
+            .filter(|id| self.path_point_for_id(**id).is_some() || id.is_guide())
+            .copied()
+            .collect();
+        true
+    }
+
+    /// Make the on-curve point `id` the start of its contour.
+    pub(crate) fn set_start_point(&mut self, id: EntityId) -> bool {
+        self.path_for_point_mut(id)
+            .map(|path| path.set_start_point(id))
+            .unwrap_or(false)
+    }
+
+    /// Reverse the direction of the contour containing the point `id`.
+    pub(crate) fn reverse_contour(&mut self, id: EntityId) {
+        if let Some(path) = self.path_for_point_mut(id) {
+            path.reverse_contour();
+        }
+    }
+
+    /// Delete a single point or guide, unless it is locked.
+    pub(crate) fn delete_item(&mut self, id: EntityId) {
         self.selection.select_one(id);
+        self.delete_selection();
+    }
+
+    /// A description of the selection in words, for people who can't see
+    /// the canvas.
+    pub(crate) fn describe_selection(&self) -> Option<String> {
+        let selected: Vec<EntityId> = self
+            .selection
+            .iter()
+            .filter(|id| !id.is_guide())
+            .copied()
+            .collect();
+        match selected.as_slice() {
+            [] => None,
+            [id] => {
+                let path_idx = self.path_idx_for_point(*id)?;
+                let path = &self.paths[path_idx];
+                let point_idx = path.points().iter().position(|pt| pt.id == *id)?;
+                let point = path.points()[point_idx];
+                let kind = match point.typ {
+                    PointType::OnCurve { smooth: true } => "smooth point",
+                    PointType::OnCurve { smooth: false } => "corner point",
+                    PointType::OffCurve { .. } => "handle",
+                };
+                Some(format!(
+                    "{} {} of {} on contour {} of {}, at x {}, y {}",
+                    kind,
+                    point_idx + 1,
+                    path.points().len(),
+                    path_idx + 1,
+                    self.paths.len(),
+                    point.point.x,
+                    point.point.y,
+                ))
+            }
+            ids => {
+                let mut contours: Vec<EntityId> = ids.iter().map(|id| id.parent()).collect();
+                contours.sort();
+                contours.dedup();
+                Some(format!(
+                    "{} points selected on {} contour{}",
+                    ids.len(),
+                    contours.len(),
+                    if contours.len() == 1 { "" } else { "s" },
+                ))
+            }
+        }
     }
 
     pub fn select_path(&mut self, id: EntityId, toggle: bool) -> bool {
@@ -417,12 +1220,134 @@ impl EditSession {
         true
     }
 
-    pub(crate) fn nudge_selection(&mut self, nudge: DVec2) {
+    /// A summary of each path, in order, for the contour list.
+    pub(crate) fn contour_list(&self) -> Arc<Vec<ContourInfo>> {
+        let contours = self
+            .paths
+            .iter()
+            .map(|path| {
+                let points = path.points();
+                let area = path.bezier().area();
+                ContourInfo {
+                    id: path.id(),
+                    name: self
+                        .contour_names
+                        .get(&path.id())
+                        .cloned()
+                        .unwrap_or_default(),
+                    point_count: points.len(),
+                    closed: path.is_closed(),
+                    clockwise: (path.is_closed() && area != 0.0).then(|| area < 0.0),
+                    hidden: self.hidden_paths.contains(&path.id()),
+                    locked: points.iter().all(|pt| self.locked.contains(&pt.id)),
+                    selected: points.iter().any(|pt| self.selection.contains(&pt.id)),
+                }
+            })
+            .collect();
+        Arc::new(contours)
+    }
+
+    /// Select every point in the path `id`, in place of the current
+    /// selection, or, if `toggle` is true, in addition to it.
+    pub fn select_contour(&mut self, id: EntityId, toggle: bool) {
+        if self.hidden_paths.contains(&id) {
+            return;
+        }
+        if !toggle {
+            self.selection.clear();
+        }
+        self.select_path(id, toggle);
+    }
+
+    /// Name the path `id`; an empty name removes the path's name.
+    pub(crate) fn rename_contour(&mut self, id: EntityId, name: &str) {
+        let names = Arc::make_mut(&mut self.contour_names);
+        let name = name.trim();
+        if name.is_empty() {
+            names.remove(&id);
+        } else {
+            names.insert(id, name.to_owned());
+        }
+    }
+
+    /// Swap the path `id` with the path after it, or before it if `forward`
+    /// is false. This is the order in which the contours are saved.
+    ///
+    /// Returns `false` if the path is already at that end of the list.
+    pub(crate) fn move_contour(&mut self, id: EntityId, forward: bool) -> bool {
+        let idx = match self.paths.iter().position(|path| path.id() == id) {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let other = match forward {
+            true if idx + 1 < self.paths.len() => idx + 1,
+            false if idx > 0 => idx - 1,
+            _ => return false,
+        };
+        self.paths_mut().swap(idx, other);
+        true
+    }
+
+    /// Hide the path `id`, or show it if it is hidden.
+    ///
+    /// Hidden paths are still part of the glyph, but can't be selected.
+    pub(crate) fn toggle_contour_hidden(&mut self, id: EntityId) {
+        let hidden = Arc::make_mut(&mut self.hidden_paths);
+        if !hidden.remove(&id) {
+            hidden.insert(id);
+            let selection = self
+                .selection
+                .iter()
+                .filter(|pt| !pt.is_child_of(id))
+                .copied()
+                .collect();
+            self.selection = selection;
+        }
+    }
+
+    /// Lock every point in the path `id`, or unlock them if they are all
+    /// locked.
+    pub(crate) fn toggle_contour_locked(&mut self, id: EntityId) {
+        let ids: Vec<EntityId> = match self.paths.iter().find(|path| path.id() == id) {
+            Some(path) => path.points().iter().map(|pt| pt.id).collect(),
+            None => return,
+        };
+        let locked = Arc::make_mut(&mut self.locked);
+        if ids.iter().all(|id| locked.contains(id)) {
+            for id in ids {
+                locked.remove(&id);
+            }
+        } else {
+            locked.extend(ids);
+        }
+    }
+
+    /// Set the parameters of the component `id`, making it a smart
+    /// component.
+    ///
+    /// Returns `false` if there is no such component, or it already has
+    /// these parameters.
+    pub(crate) fn set_component_params(&mut self, id: EntityId, params: ComponentParams) -> bool {
+        match self.components.iter().find(|comp| comp.id == id) {
+            Some(comp) if comp.params != Some(params) => (),
+            _ => return false,
+        }
+        if let Some(comp) = self.components_mut().iter_mut().find(|comp| comp.id == id) {
+            comp.set_params(params);
+        }
+        true
+    }
+
+    /// Move the selected points and guides by `nudge`.
+    ///
+    /// Locked points are not moved.
+    pub fn nudge_selection(&mut self, nudge: DVec2) {
         if self.selection.is_empty() {
             return;
         }
+        self.last_transform = Some(Affine::translate(nudge.to_raw()));
 
-        let to_nudge = self.selection.per_path_selection();
+        let to_nudge = self.unlocked_selection().per_path_selection();
         for path_points in to_nudge.iter() {
             if let Some(path) = self.path_for_point_mut(path_points[0]) {
                 path.nudge_points(path_points, nudge);
@@ -436,7 +1361,116 @@ impl EditSession {
         }
     }
 
-    pub(crate) fn nudge_everything(&mut self, nudge: DVec2) {
+    /// Like `nudge_selection`, except that the handles of curves adjacent
+    /// to the selected points are adjusted proportionally.
+    pub(crate) fn nudge_selection_interpolated(&mut self, nudge: DVec2) {
+        if self.selection.is_empty() {
+            return;
+        }
+        self.last_transform = Some(Affine::translate(nudge.to_raw()));
+
+        let to_nudge = self.unlocked_selection().per_path_selection();
+        let precision = self.precision;
+        for path_points in to_nudge.iter() {
+            if let Some(path) = self.path_for_point_mut(path_points[0]) {
+                path.nudge_points_interpolated(path_points, nudge, precision);
+            } else if path_points[0].is_guide() {
+                for id in path_points {
+                    if let Some(guide) = self.guides_mut().iter_mut().find(|g| g.id == *id) {
+                        guide.nudge(nudge);
+                    }
+                }
+            }
+        }
+    }
+
+    /// If zone snapping is enabled and `point` is near the edge of an
+    /// alignment zone, returns `point` moved vertically onto that edge.
+    pub(crate) fn snap_to_zones(&self, point: DPoint) -> DPoint {
+        let zones = match self.snap_zones.as_ref() {
+            Some(zones) => zones,
+            None => return point,
+        };
+        let tolerance = ZONE_SNAP_DISTANCE / self.viewport.zoom;
+        match font_info::nearest_zone_edge(zones, point.y, tolerance) {
+            Some(y) => DPoint::new(point.x, design_space::round_coord(y, self.precision)),
+            None => point,
+        }
+    }
+
+    /// Snap `point` into line with nearby on-curve points and guides, as it
+    /// is dragged along with the points in `moving`.
+    ///
+    /// Returns the snapped point, and lines in design space from it to each
+    /// point or guide it is now aligned with.
+    #[allow(clippy::float_cmp)]
+    pub(crate) fn snap_to_alignment(
+        &self,
+        point: DPoint,
+        moving: &Selection,
+    ) -> (DPoint, Vec<Line>) {
+        let tolerance = ALIGNMENT_SNAP_DISTANCE / self.viewport.zoom;
+        let points: Vec<DPoint> = self
+            .paths
+            .iter()
+            .flat_map(|path| path.points().iter())
+            .filter(|pt| pt.is_on_curve() && !moving.contains(&pt.id))
+            .map(|pt| pt.point)
+            .collect();
+        let guides = self.guides.iter().chain(self.global_guides.iter());
+        let (mut xs, mut ys) = (Vec::new(), Vec::new());
+        for guide in guides {
+            match guide.guide {
+                GuideLine::Vertical(p) => xs.push(p.x),
+                GuideLine::Horiz(p) => ys.push(p.y),
+                GuideLine::Angle { .. } => (),
+            }
+        }
+        let nearest = |value: f64, candidates: &mut dyn Iterator<Item = f64>| {
+            candidates
+                .map(|c| (c, (c - value).abs()))
+                .filter(|(_, dist)| *dist <= tolerance)
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+                .map(|(c, _)| c)
+        };
+        let x = nearest(
+            point.x,
+            &mut points.iter().map(|pt| pt.x).chain(xs.iter().copied()),
+        );
+        let y = nearest(
+            point.y,
+            &mut points.iter().map(|pt| pt.y).chain(ys.iter().copied()),
+        );
+        let snapped = DPoint::new(x.unwrap_or(point.x), y.unwrap_or(point.y));
+        let snapped = if y.is_none() {
+            self.snap_to_zones(snapped)
+        } else {
+            snapped
+        };
+
+        // guides are infinite, so we highlight them across the whole canvas
+        const GUIDE_HINT_LENGTH: f64 = 5000.0;
+        let from = snapped.to_raw();
+        let mut hints = Vec::new();
+        for pt in &points {
+            if (pt.x == snapped.x && pt.y != snapped.y) || (pt.y == snapped.y && pt.x != snapped.x)
+            {
+                hints.push(Line::new(from, pt.to_raw()));
+            }
+        }
+        if xs.contains(&snapped.x) {
+            let offset = Vec2::new(0.0, GUIDE_HINT_LENGTH);
+            hints.push(Line::new(from - offset, from + offset));
+        }
+        if ys.contains(&snapped.y) {
+            let offset = Vec2::new(GUIDE_HINT_LENGTH, 0.0);
+            hints.push(Line::new(from - offset, from + offset));
+        }
+        (snapped, hints)
+    }
+
+    /// Move every path and component by `nudge`.
+    pub fn nudge_everything(&mut self, nudge: DVec2) {
         for path in self.paths_mut() {
             path.nudge_all_points(nudge);
         }
@@ -445,7 +1479,10 @@ impl EditSession {
         }
     }
 
-    pub(crate) fn adjust_sidebearing(&mut self, delta: f64, is_left: bool) {
+    /// Widen the glyph by `delta`, on the left or on the right.
+    ///
+    /// The advance width can't become negative.
+    pub fn adjust_sidebearing(&mut self, delta: f64, is_left: bool) {
         let glyph = Arc::make_mut(&mut self.glyph);
         if let Some(advance) = glyph.advance.as_mut() {
             // clamp the delta; we can't have an advance width < 0.
@@ -457,21 +1494,192 @@ impl EditSession {
             advance.width += delta as f32;
 
             if is_left {
-                self.nudge_everything(DVec2::from_raw((delta, 0.0)));
+                self.nudge_everything(DVec2::from_raw((delta, 0.0), self.precision));
             }
         }
     }
 
-    pub(crate) fn scale_selection(&mut self, scale: Vec2, anchor: DPoint) {
+    /// Scale the selected points, treating `anchor` as the origin.
+    pub fn scale_selection(&mut self, scale: Vec2, anchor: DPoint) {
         assert!(scale.x.is_finite() && scale.y.is_finite());
+        let scale_xform = Affine::scale_non_uniform(scale.x, scale.y);
+        self.transform_selection(scale_xform, anchor);
+    }
+
+    /// Apply `affine` to the selected points, treating `anchor` as the origin.
+    pub fn transform_selection(&mut self, affine: Affine, anchor: DPoint) {
         if !self.selection.is_empty() {
-            let sel = self.selection.per_path_selection();
+            self.last_transform = Some(about_anchor(affine, anchor));
+            let sel = self.unlocked_selection().per_path_selection();
+            let precision = self.precision;
+            for path_points in sel.iter() {
+                if let Some(path) = self.path_for_point_mut(path_points[0]) {
+                    path.transform_points(path_points, affine, anchor, precision);
+                }
+            }
+        }
+    }
+
+    /// Mirror the selected points, horizontally or vertically.
+    ///
+    /// If a vertical guide (when flipping horizontally) or a horizontal guide
+    /// (when flipping vertically) is selected, the points are flipped around
+    /// it; otherwise they are flipped around the center of their bounding box.
+    ///
+    /// Flipping a path reverses its direction, so paths that are flipped
+    /// entirely are reversed to keep their original direction.
+    pub fn flip_selection(&mut self, horizontal: bool) {
+        if !self.selection.iter().any(|id| !id.is_guide()) {
+            return;
+        }
+        let center = self.selection_dpoint_bbox().center();
+        let guide_axis = self
+            .guides
+            .iter()
+            .filter(|guide| self.selection.contains(&guide.id))
+            .find_map(|guide| match guide.guide {
+                GuideLine::Vertical(p) if horizontal => Some(p.x),
+                GuideLine::Horiz(p) if !horizontal => Some(p.y),
+                _ => None,
+            });
+        let (scale, anchor) = if horizontal {
+            let x = guide_axis.unwrap_or(center.x);
+            (
+                Vec2::new(-1.0, 1.0),
+                DPoint::from_raw((x, center.y), self.precision),
+            )
+        } else {
+            let y = guide_axis.unwrap_or(center.y);
+            (
+                Vec2::new(1.0, -1.0),
+                DPoint::from_raw((center.x, y), self.precision),
+            )
+        };
+        let flip = Affine::scale_non_uniform(scale.x, scale.y);
+        self.last_transform = Some(about_anchor(flip, anchor));
+
+        let sel = self.unlocked_selection().per_path_selection();
+        let precision = self.precision;
+        for path_points in sel.iter() {
+            if let Some(path) = self.path_for_point_mut(path_points[0]) {
+                path.scale_points(path_points, scale, anchor, precision);
+                if path.points().iter().all(|pt| path_points.contains(&pt.id)) {
+                    path.reverse_contour();
+                }
+            }
+        }
+    }
+
+    /// Apply the last transform to the selection again.
+    ///
+    /// If `duplicate` is true, the selected paths are copied first, and the
+    /// copies are transformed and selected instead; repeating this builds up
+    /// radial or repeating designs. Returns `false` if there is nothing to
+    /// transform.
+    pub(crate) fn transform_again(&mut self, duplicate: bool) -> bool {
+        let affine = match self.last_transform {
+            Some(affine) if !self.selection.is_empty() => affine,
+            _ => return false,
+        };
+        if duplicate {
+            let copies: Vec<Path> = self
+                .paths_for_selection()
+                .iter()
+                .map(Path::duplicate)
+                .collect();
+            if copies.is_empty() {
+                return false;
+            }
+            self.paste_paths(copies);
+        }
+        self.transform_selection(affine, DPoint::ZERO);
+
+        // as when flipping, keep the direction of mirrored paths
+        if affine.determinant() < 0.0 {
+            let sel = self.unlocked_selection().per_path_selection();
             for path_points in sel.iter() {
                 if let Some(path) = self.path_for_point_mut(path_points[0]) {
-                    path.scale_points(path_points, scale, anchor);
+                    if path.points().iter().all(|pt| path_points.contains(&pt.id)) {
+                        path.reverse_contour();
+                    }
                 }
             }
         }
+        true
+    }
+
+    /// Align the selected points to the edges or center of their bounding
+    /// box, or distribute them evenly across it.
+    ///
+    /// Off-curve points whose on-curve point is also selected move with that
+    /// point, rather than being aligned themselves.
+    pub(crate) fn align_points(&mut self, alignment: Alignment) {
+        let points = self
+            .selection
+            .iter()
+            .filter_map(|id| self.path_point_for_id(*id))
+            .filter(|pt| pt.is_on_curve() || !self.on_curve_neighbour_selected(pt.id))
+            .collect::<Vec<_>>();
+        if points.len() < 2 {
+            return;
+        }
+        let bbox = points.iter().map(|pt| pt.point.to_raw()).fold(
+            Rect::from_points(points[0].point.to_raw(), points[0].point.to_raw()),
+            |bb, pt| bb.union_pt(pt),
+        );
+
+        let x_to = |x: f64| points.iter().map(move |pt| (pt.id, x - pt.point.x, 0.0));
+        let y_to = |y: f64| points.iter().map(move |pt| (pt.id, 0.0, y - pt.point.y));
+        let moves: Vec<(EntityId, f64, f64)> = match alignment {
+            Alignment::Left => x_to(bbox.x0).collect(),
+            Alignment::CenterX => x_to(bbox.center().x).collect(),
+            Alignment::Right => x_to(bbox.x1).collect(),
+            Alignment::Top => y_to(bbox.y1).collect(),
+            Alignment::CenterY => y_to(bbox.center().y).collect(),
+            Alignment::Bottom => y_to(bbox.y0).collect(),
+            Alignment::DistributeX => {
+                let mut sorted = points.clone();
+                sorted.sort_by(|a, b| a.point.x.partial_cmp(&b.point.x).unwrap());
+                let step = bbox.width() / (sorted.len() - 1) as f64;
+                sorted
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pt)| (pt.id, bbox.x0 + step * i as f64 - pt.point.x, 0.0))
+                    .collect()
+            }
+            Alignment::DistributeY => {
+                let mut sorted = points.clone();
+                sorted.sort_by(|a, b| a.point.y.partial_cmp(&b.point.y).unwrap());
+                let step = bbox.height() / (sorted.len() - 1) as f64;
+                sorted
+                    .iter()
+                    .enumerate()
+                    .map(|(i, pt)| (pt.id, 0.0, bbox.y0 + step * i as f64 - pt.point.y))
+                    .collect()
+            }
+        };
+
+        for (id, dx, dy) in moves {
+            let delta = DVec2::from_raw((dx, dy), self.precision);
+            if delta == DVec2::ZERO || self.locked.contains(&id) {
+                continue;
+            }
+            if let Some(path) = self.path_for_point_mut(id) {
+                path.nudge_points(&[id], delta);
+            }
+        }
+    }
+
+    /// Returns `true` if `id` is next to a selected on-curve point.
+    fn on_curve_neighbour_selected(&self, id: EntityId) -> bool {
+        let path = match self.path_for_point(id) {
+            Some(path) => path,
+            None => return false,
+        };
+        path.prev_point(id)
+            .into_iter()
+            .chain(path.next_point(id))
+            .any(|pt| pt.is_on_curve() && self.selection.contains(&pt.id))
     }
 
     pub(crate) fn align_selection(&mut self) {
@@ -485,9 +1693,9 @@ impl EditSession {
         } else {
             (0.5 * (bbox.y0 + bbox.y1), false)
         };
-        let val = val.round();
+        let val = design_space::round_coord(val, self.precision);
         // make borrow checker happy; we could state-split the paths instead, but meh
-        let ids: Vec<EntityId> = self.selection.iter().copied().collect();
+        let ids: Vec<EntityId> = self.unlocked_selection().iter().copied().collect();
         for id in ids {
             if let Some(path) = self.path_for_point_mut(id) {
                 path.align_point(id, val, set_x);
@@ -495,7 +1703,145 @@ impl EditSession {
         }
     }
 
-    pub(crate) fn reverse_contours(&mut self) {
+    /// Reverse the direction of each path with a selected point.
+    pub fn reverse_contours(&mut self) {
+        let path_ixs = self.selected_path_indices();
+        let paths = self.paths_mut();
+        for ix in path_ixs {
+            paths[ix].reverse_contour();
+        }
+    }
+
+    /// Join the two selected endpoints of open paths.
+    ///
+    /// Endpoints of two different paths merge those paths into one; the two
+    /// ends of a single path close it. Endpoints at the same position become
+    /// a single point, and otherwise they are connected with a line.
+    ///
+    /// Returns `false` if the selection isn't two such endpoints.
+    pub fn join_selected_endpoints(&mut self) -> bool {
+        if self.selection.len() != 2 {
+            return false;
+        }
+        let mut ids = self.selection.iter().copied();
+        let (a, b) = (ids.next().unwrap(), ids.next().unwrap());
+        let ((a_idx, a_is_start), (b_idx, b_is_start)) =
+            match (self.open_endpoint(a), self.open_endpoint(b)) {
+                (Some(a), Some(b)) => (a, b),
+                _ => return false,
+            };
+        let merge = self.path_point_for_id(a).map(|pt| pt.point)
+            == self.path_point_for_id(b).map(|pt| pt.point);
+
+        let joined = if a_idx == b_idx {
+            let path = &mut self.paths_mut()[a_idx];
+            if merge && path.points().len() > 2 {
+                path.close_merging_ends()
+            } else {
+                path.close(false)
+            }
+        } else {
+            if self.paths[a_idx].is_hyper() != self.paths[b_idx].is_hyper() {
+                return false;
+            }
+            let mut other = self.paths[b_idx].clone();
+            if !b_is_start {
+                other.reverse_contour();
+            }
+            let precision = self.precision;
+            let paths = self.paths_mut();
+            if a_is_start {
+                paths[a_idx].reverse_contour();
+            }
+            let joined = paths[a_idx].append(&other, merge, precision);
+            paths.remove(b_idx);
+            joined
+        };
+        self.selection.select_one(joined);
+        true
+    }
+
+    /// Open each closed path that has a selected on-curve point, at that
+    /// point.
+    ///
+    /// If a path has more than one selected point, it is opened at the first.
+    pub fn break_selected_contours(&mut self) -> bool {
+        let mut broken = BTreeSet::new();
+        let selected = self.selection.iter().copied().collect::<Vec<_>>();
+        for id in selected {
+            let idx = match self.path_idx_for_point(id) {
+                Some(idx) if !broken.contains(&idx) => idx,
+                _ => continue,
+            };
+            if self.paths_mut()[idx].break_at(id).is_some() {
+                broken.insert(idx);
+            }
+        }
+        !broken.is_empty()
+    }
+
+    /// Remove unneeded points from the selected paths, refitting their
+    /// curves within `tolerance` design units of the original outline.
+    ///
+    /// Locked points are kept. If nothing is selected, this applies to every
+    /// path in the glyph.
+    pub fn simplify_selection(&mut self, tolerance: f64) {
+        let path_ixs = self.selected_path_indices();
+        let before = self.iter_points().map(|p| p.id).collect::<BTreeSet<_>>();
+        let precision = self.precision;
+        let locked = self.locked.clone();
+        let paths = self.paths_mut();
+        for ix in path_ixs {
+            paths[ix].simplify(tolerance, &locked, precision);
+        }
+        // forget any selected points that were removed
+        let after = self.iter_points().map(|p| p.id).collect::<BTreeSet<_>>();
+        self.selection = self
+            .selection
+            .iter()
+            .filter(|id| !before.contains(*id) || after.contains(*id))
+            .copied()
+            .collect();
+    }
+
+    /// Replace the selected paths with the outline drawn by moving `nib`
+    /// along them, and select that outline.
+    ///
+    /// If nothing is selected, every open path is expanded. Returns `false`
+    /// if there was nothing to expand.
+    pub(crate) fn expand_stroke(&mut self, nib: &Nib) -> bool {
+        let path_ixs: Vec<usize> = if self.selection.is_empty() {
+            (0..self.paths.len())
+                .filter(|ix| !self.paths[*ix].is_closed())
+                .collect()
+        } else {
+            self.selected_path_indices().into_iter().collect()
+        };
+        if path_ixs.is_empty() {
+            return false;
+        }
+
+        let mut skeleton = BezPath::new();
+        for ix in &path_ixs {
+            self.paths[*ix].append_to_bezier(&mut skeleton);
+        }
+        let outline: Vec<Path> = nib
+            .expand(&skeleton, self.precision)
+            .iter()
+            .map(|contour| Path::from_norad(contour, self.precision))
+            .collect();
+
+        let paths = self.paths_mut();
+        for ix in path_ixs.iter().rev() {
+            paths.remove(*ix);
+        }
+        self.paste_paths(outline);
+        true
+    }
+
+    /// The indices of the paths containing a selected point, or of every
+    /// path if nothing is selected.
+    fn selected_path_indices(&self) -> BTreeSet<usize> {
         let mut path_ixs = BTreeSet::new();
         for entity in self.selection.iter() {
             if let Some(path_ix) = self.path_idx_for_point(*entity) {
@@ -505,9 +1851,48 @@ impl EditSession {
         if path_ixs.is_empty() {
             path_ixs.extend(0..self.paths.len());
         }
-        let paths = self.paths_mut();
-        for ix in path_ixs {
-            paths[ix].reverse_contour();
+        path_ixs
+    }
+
+    /// Harmonize the selected smooth points with their handles.
+    ///
+    /// The handles stay where they are; each smooth point is moved along the
+    /// line between its handles. Locked points don't move. If nothing is
+    /// selected, this applies to every point in the glyph.
+    pub fn harmonize_selection(&mut self) {
+        let selection = self.unlocked_selection_or_all_points();
+        let per_path = selection.per_path_selection();
+        let precision = self.precision;
+        for path_points in per_path.iter() {
+            if let Some(path) = self.path_for_point_mut(path_points[0]) {
+                path.harmonize_points(path_points, precision);
+            }
+        }
+    }
+
+    /// Balance the handles of the selected curve segments.
+    ///
+    /// Segments with a locked point are left alone. If nothing is selected,
+    /// this applies to every segment in the glyph.
+    pub fn balance_selection(&mut self) {
+        let selection = self.unlocked_selection_or_all_points();
+        let precision = self.precision;
+        let locked = self.locked.clone();
+        for path in self.paths_mut() {
+            path.balance_segments(&selection, &locked, precision);
+        }
+    }
+
+    /// The current selection, or a selection of every point if the
+    /// selection is empty, without any locked points.
+    fn unlocked_selection_or_all_points(&self) -> Selection {
+        if self.selection.is_empty() {
+            self.iter_points()
+                .map(|p| p.id)
+                .filter(|id| !self.locked.contains(id))
+                .collect()
+        } else {
+            self.unlocked_selection()
         }
     }
 
@@ -538,20 +1923,75 @@ impl EditSession {
             _ => None,
         };
 
-        let guide =
-            guide.unwrap_or_else(|| Guide::horiz(DPoint::from_screen(point, self.viewport)));
+        let guide = guide.unwrap_or_else(|| {
+            Guide::horiz(DPoint::from_screen(point, self.viewport, self.precision))
+        });
         self.selection.select_one(guide.id);
         self.guides_mut().push(guide);
     }
 
+    /// The snapshots of this glyph's outline, oldest first.
+    pub(crate) fn snapshots(&self) -> Vec<Snapshot> {
+        Snapshot::load_all(&self.glyph)
+    }
+
+    /// Store the current outline as a new snapshot.
+    pub(crate) fn save_snapshot(&mut self, name: String) {
+        let mut snapshots = self.snapshots();
+        snapshots.push(Snapshot::new(
+            name,
+            self.paths.clone(),
+            self.components.clone(),
+        ));
+        Snapshot::save_all(&snapshots, Arc::make_mut(&mut self.glyph));
+    }
+
+    /// Replace the outline with the snapshot at `index`.
+    pub(crate) fn restore_snapshot(&mut self, index: usize) {
+        let snapshot = match self.snapshots().into_iter().nth(index) {
+            Some(snapshot) => snapshot,
+            None => return,
+        };
+        self.selection.clear();
+        self.paths = snapshot.paths;
+        self.components = snapshot.components;
+        self.outline_changed();
+    }
+
+    pub(crate) fn delete_snapshot(&mut self, index: usize) {
+        let mut snapshots = self.snapshots();
+        if index < snapshots.len() {
+            snapshots.remove(index);
+            Snapshot::save_all(&snapshots, Arc::make_mut(&mut self.glyph));
+        }
+    }
+
     /// Convert the current session back into a norad `Glyph`, for saving.
     pub fn to_norad_glyph(&self) -> Glyph {
         let mut glyph = Glyph::new_named("");
         glyph.name = self.name.clone();
         glyph.advance = self.glyph.advance.clone();
         glyph.codepoints = self.glyph.codepoints.clone();
+        glyph.note = self.glyph.note.clone();
+        glyph.anchors = self.glyph.anchors.clone();
+        glyph.lib = self.glyph.lib.clone();
 
-        let contours: Vec<_> = self.paths.iter().map(Path::to_norad).collect();
+        let contours: Vec<_> = self
+            .paths
+            .iter()
+            .map(|path| {
+                let contour = path.to_norad();
+                let contour = if self.quadratic_paths.contains(&path.id()) {
+                    quadratic::to_quadratic(&contour, self.precision)
+                } else {
+                    contour
+                };
+                match self.contour_names.get(&path.id()) {
+                    Some(name) => with_contour_name(contour, name),
+                    None => contour,
+                }
+            })
+            .collect();
         let components: Vec<_> = self.components.iter().map(Component::to_norad).collect();
         if !contours.is_empty() || !components.is_empty() {
             glyph.outline = Some(Outline {
@@ -567,6 +2007,14 @@ impl EditSession {
     }
 }
 
+/// `contour`, with `name` added to its lib.
+fn with_contour_name(contour: norad::glyph::Contour, name: &str) -> norad::glyph::Contour {
+    let mut lib = contour.lib().cloned().unwrap_or_default();
+    lib.insert(CONTOUR_NAME_LIB_KEY.into(), name.to_owned().into());
+    let identifier = contour.identifier().cloned();
+    norad::glyph::Contour::new(contour.points, identifier, Some(lib))
+}
+
 impl CoordinateSelection {
     /// a lens to return the point representation of the current selected coord(s)
     #[allow(non_upper_case_globals)]
@@ -592,6 +2040,7 @@ pub mod lenses {
                 count,
                 frame,
                 quadrant,
+                precision: data.precision,
             })
         }
 
@@ -607,6 +2056,7 @@ pub mod lenses {
                 count,
                 frame,
                 quadrant,
+                precision: data.precision,
             };
             let r = f(&mut sel);
             data.quadrant = sel.quadrant;
@@ -662,3 +2112,9 @@ pub mod lenses {
         }
     }
 }
+
+/// `affine`, applied with `anchor` as its origin.
+fn about_anchor(affine: Affine, anchor: DPoint) -> Affine {
+    let anchor = anchor.to_raw().to_vec2();
+    Affine::translate(anchor) * affine * Affine::translate(-anchor)
+}
@@ -0,0 +1,283 @@
+//! Lightweight support for OpenType feature files.
+//!
+//! This is not a real parser; it knows enough about the syntax to highlight
+//! feature code and to catch the most common mistakes while editing.
+
+use std::ops::Range;
+
+/// Keywords that are highlighted in the editor.
+const KEYWORDS: &[&str] = &[
+    "anchor",
+    "anchorDef",
+    "by",
+    "contourpoint",
+    "cursive",
+    "device",
+    "enum",
+    "exclude_dflt",
+    "feature",
+    "from",
+    "ignore",
+    "IgnoreBaseGlyphs",
+    "IgnoreLigatures",
+    "IgnoreMarks",
+    "include",
+    "include_dflt",
+    "language",
+    "languagesystem",
+    "lookup",
+    "lookupflag",
+    "mark",
+    "markClass",
+    "MarkAttachmentType",
+    "nameid",
+    "NULL",
+    "parameters",
+    "pos",
+    "position",
+    "required",
+    "RightToLeft",
+    "rsub",
+    "reversesub",
+    "script",
+    "sub",
+    "substitute",
+    "subtable",
+    "table",
+    "useExtension",
+    "UseMarkFilteringSet",
+    "valueRecordDef",
+];
+
+/// The kinds of token we distinguish when highlighting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Keyword,
+    Comment,
+    String,
+    /// A named glyph class, like `@lowercase`.
+    GlyphClass,
+    Number,
+    /// Braces, brackets, and other punctuation.
+    Punctuation,
+    /// Glyph names, tags, and anything else.
+    Name,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// The byte range of the token in the source.
+    pub range: Range<usize>,
+}
+
+/// A problem found in a feature file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// The line of the problem, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Split feature code into tokens; whitespace is skipped.
+pub fn tokenize(text: &str) -> Vec<Token> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let kind = match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'#' => {
+                i = find_from(bytes, i, |b| b == b'\n');
+                TokenKind::Comment
+            }
+            b'"' => {
+                i = find_from(bytes, i + 1, |b| b == b'"');
+                i = (i + 1).min(bytes.len());
+                TokenKind::String
+            }
+            b'{' | b'}' | b'[' | b']' | b'(' | b')' | b'<' | b'>' | b';' | b',' | b'\'' | b'=' => {
+                i += 1;
+                TokenKind::Punctuation
+            }
+            b'@' => {
+                i = find_from(bytes, i + 1, |b| !is_name_byte(b));
+                TokenKind::GlyphClass
+            }
+            b'-' | b'0'..=b'9'
+                if bytes[i + 1..]
+                    .iter()
+                    .take_while(|b| is_name_byte(**b))
+                    .all(|b| b.is_ascii_digit() || *b == b'.') =>
+            {
+                i = find_from(bytes, i + 1, |b| !is_name_byte(b));
+                TokenKind::Number
+            }
+            _ => {
+                i = find_from(bytes, i + 1, |b| !is_name_byte(b)).max(i + 1);
+                // don't split a multi-byte char
+                while !text.is_char_boundary(i) {
+                    i += 1;
+                }
+                if KEYWORDS.contains(&&text[start..i]) {
+                    TokenKind::Keyword
+                } else {
+                    TokenKind::Name
+                }
+            }
+        };
+        tokens.push(Token {
+            kind,
+            range: start..i,
+        });
+    }
+    tokens
+}
+
+/// Look for unbalanced blocks, mismatched block tags, and unterminated
+/// strings.
+pub fn check(text: &str) -> Vec<Problem> {
+    let tokens = tokenize(text)
+        .into_iter()
+        .filter(|t| t.kind != TokenKind::Comment)
+        .collect::<Vec<_>>();
+    let line_of = |offset: usize| text[..offset].matches('\n').count() + 1;
+    let token_text = |idx: usize| tokens.get(idx).map(|t: &Token| &text[t.range.clone()]);
+
+    let mut problems = Vec::new();
+    // (block keyword, block tag, line) for each open block
+    let mut blocks: Vec<(&str, Option<&str>, usize)> = Vec::new();
+
+    for (idx, token) in tokens.iter().enumerate() {
+        let line = line_of(token.range.start);
+        match &text[token.range.clone()] {
+            s if token.kind == TokenKind::String && (s.len() < 2 || !s.ends_with('"')) => {
+                problems.push(Problem {
+                    line,
+                    message: "unterminated string".into(),
+                });
+            }
+            "{" => {
+                // find the keyword that opened this block: `feature liga {`,
+                // `lookup name {`, or `table GDEF {`.
+                let keyword = idx
+                    .checked_sub(2)
+                    .and_then(token_text)
+                    .filter(|kw| matches!(*kw, "feature" | "lookup" | "table"));
+                let (keyword, tag) = match keyword {
+                    Some(keyword) => (keyword, token_text(idx - 1)),
+                    None => ("", None),
+                };
+                blocks.push((keyword, tag, line));
+            }
+            "}" => match blocks.pop() {
+                Some((keyword, Some(tag), _)) => {
+                    let closing = token_text(idx + 1);
+                    if closing != Some(tag) {
+                        problems.push(Problem {
+                            line,
+                            message: format!(
+                                "{} '{}' is closed with '{}'",
+                                keyword,
+                                tag,
+                                closing.unwrap_or("")
+                            ),
+                        });
+                    }
+                }
+                Some(_) => (),
+                None => problems.push(Problem {
+                    line,
+                    message: "unexpected '}'".into(),
+                }),
+            },
+            _ => (),
+        }
+    }
+
+    for (keyword, tag, line) in blocks {
+        let message = match tag {
+            Some(tag) => format!("{} '{}' is never closed", keyword, tag),
+            None => "block is never closed".into(),
+        };
+        problems.push(Problem { line, message });
+    }
+    problems.sort_by_key(|p| p.line);
+    problems
+}
+
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'_' | b'.' | b'-' | b'\\') || b >= 0x80
+}
+
+fn find_from(bytes: &[u8], start: usize, pred: impl Fn(u8) -> bool) -> usize {
+    bytes[start.min(bytes.len())..]
+        .iter()
+        .position(|b| pred(*b))
+        .map(|pos| pos + start)
+        .unwrap_or_else(|| bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens() {
+        let text = "feature liga { # ligatures\n  sub f i by f_i;\n} liga;";
+        let kinds = tokenize(text)
+            .into_iter()
+            .map(|t| (t.kind, &text[t.range]))
+            .collect::<Vec<_>>();
+        assert_eq!(kinds[0], (TokenKind::Keyword, "feature"));
+        assert_eq!(kinds[1], (TokenKind::Name, "liga"));
+        assert_eq!(kinds[2], (TokenKind::Punctuation, "{"));
+        assert_eq!(kinds[3], (TokenKind::Comment, "# ligatures"));
+        assert_eq!(kinds[4], (TokenKind::Keyword, "sub"));
+        assert_eq!(kinds[8], (TokenKind::Name, "f_i"));
+
+        let kinds = tokenize("@caps pos A -20 \"x")
+            .into_iter()
+            .map(|t| t.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                TokenKind::GlyphClass,
+                TokenKind::Keyword,
+                TokenKind::Name,
+                TokenKind::Number,
+                TokenKind::String
+            ]
+        );
+    }
+
+    #[test]
+    fn problems() {
+        assert!(check("feature liga {\n sub f i by f_i;\n} liga;\n").is_empty());
+        assert!(check("lookup A { sub a by b; } A;\nfeature x { lookup A; } x;").is_empty());
+
+        let problems = check("feature liga {\n sub f i by f_i;\n} kern;\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, 3);
+
+        let problems = check("feature liga {\n sub f i by f_i;\n");
+        assert_eq!(
+            problems[0].to_string(),
+            "line 1: feature 'liga' is never closed"
+        );
+
+        assert_eq!(check("}").len(), 1);
+        assert_eq!(check("sub a by \"b;").len(), 1);
+    }
+}
@@ -0,0 +1,87 @@
+//! Watching the font's directory for changes made by other programs.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use druid::{ExtEventSink, Target};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::consts;
+
+/// How long to wait for a burst of changes (such as a git checkout) to
+/// finish before reporting them.
+const DEBOUNCE_DURATION: Duration = Duration::from_millis(500);
+
+/// Watches a UFO directory, and submits `EXTERNAL_CHANGES` when files in it
+/// change.
+///
+/// Watching stops when this is dropped.
+pub(crate) struct FontWatcher {
+    _watcher: RecommendedWatcher,
+    path: PathBuf,
+}
+
+impl FontWatcher {
+    /// Start watching the UFO at `path`.
+    ///
+    /// `generation` is sent with each `EXTERNAL_CHANGES`, so that changes
+    /// reported by a watcher that has since been replaced can be ignored.
+    pub(crate) fn new(
+        path: &Path,
+        sink: ExtEventSink,
+        generation: u64,
+    ) -> Result<Self, notify::Error> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::watcher(tx, DEBOUNCE_DURATION)?;
+        watcher.watch(path, RecursiveMode::Recursive)?;
+        let root = path.to_owned();
+        std::thread::spawn(move || {
+            // the channel closes when the watcher is dropped
+            while let Ok(event) = rx.recv() {
+                // coalesce everything that has arrived so far
+                let changed = std::iter::once(event)
+                    .chain(rx.try_iter())
+                    .any(|event| is_change(&event, &root));
+                if !changed {
+                    continue;
+                }
+                if sink
+                    .submit_command(consts::cmd::EXTERNAL_CHANGES, generation, Target::Auto)
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        });
+        Ok(FontWatcher {
+            _watcher: watcher,
+            path: path.to_owned(),
+        })
+    }
+}
+
+/// Whether an event means that a file in the font may have changed.
+fn is_change(event: &DebouncedEvent, root: &Path) -> bool {
+    match event {
+        DebouncedEvent::Create(path)
+        | DebouncedEvent::Write(path)
+        | DebouncedEvent::Remove(path)
+        | DebouncedEvent::Rename(_, path) => path != root,
+        DebouncedEvent::Rescan => true,
+        DebouncedEvent::Error(e, _) => {
+            log::warn!("error watching font: '{}'", e);
+            false
+        }
+        _ => false,
+    }
+}
+
+impl fmt::Debug for FontWatcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FontWatcher")
+            .field("path", &self.path)
+            .finish()
+    }
+}
@@ -0,0 +1,233 @@
+//! Building new fonts, both as test fixtures and when starting a new font.
+
+use std::convert::TryFrom;
+
+use norad::glyph::{Advance, Contour, ContourPoint, Outline, PointType};
+use norad::{FontInfo, Glyph, Ufo};
+
+use crate::charset;
+use crate::glyph_names;
+
+/// A description of a new font, which is turned into a UFO by [`build`].
+///
+/// The defaults match the blank font we open when there is nothing else to
+/// show: a 1000 unit em, with the basic latin letters and no outlines.
+///
+/// [`build`]: FontBuilder::build
+#[derive(Debug, Clone, PartialEq)]
+pub struct FontBuilder {
+    family_name: String,
+    style_name: String,
+    units_per_em: f64,
+    ascender: f64,
+    descender: f64,
+    cap_height: f64,
+    x_height: f64,
+    glyphs: Vec<String>,
+    placeholder_outlines: bool,
+}
+
+impl Default for FontBuilder {
+    fn default() -> Self {
+        FontBuilder {
+            family_name: "Untitled".into(),
+            style_name: "Regular".into(),
+            units_per_em: 1000.0,
+            ascender: 800.0,
+            descender: -200.0,
+            cap_height: 700.0,
+            x_height: 500.0,
+            glyphs: ('a'..='z').chain('A'..='Z').map(String::from).collect(),
+            placeholder_outlines: false,
+        }
+    }
+}
+
+impl FontBuilder {
+    pub fn new() -> Self {
+        FontBuilder::default()
+    }
+
+    pub fn with_family_name(mut self, name: impl Into<String>) -> Self {
+        self.family_name = name.into();
+        self
+    }
+
+    pub fn with_style_name(mut self, name: impl Into<String>) -> Self {
+        self.style_name = name.into();
+        self
+    }
+
+    /// Set the units per em, scaling the vertical metrics to match.
+    pub fn with_units_per_em(mut self, units_per_em: f64) -> Self {
+        let scale = units_per_em / self.units_per_em;
+        self.units_per_em = units_per_em;
+        self.ascender = (self.ascender * scale).round();
+        self.descender = (self.descender * scale).round();
+        self.cap_height = (self.cap_height * scale).round();
+        self.x_height = (self.x_height * scale).round();
+        self
+    }
+
+    pub fn with_ascender(mut self, ascender: f64) -> Self {
+        self.ascender = ascender;
+        self
+    }
+
+    pub fn with_descender(mut self, descender: f64) -> Self {
+        self.descender = descender;
+        self
+    }
+
+    pub fn with_cap_height(mut self, cap_height: f64) -> Self {
+        self.cap_height = cap_height;
+        self
+    }
+
+    pub fn with_x_height(mut self, x_height: f64) -> Self {
+        self.x_height = x_height;
+        self
+    }
+
+    /// Use these glyphs, in place of the default ones.
+    pub fn with_glyphs<S: Into<String>>(mut self, names: impl IntoIterator<Item = S>) -> Self {
+        self.glyphs = names.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Use the glyphs in a list in the format accepted by
+    /// [`charset::parse_glyph_list`], like `U+0020-007E ae oe`.
+    ///
+    /// Returns the first item that could not be understood, on failure.
+    pub fn with_glyph_list(self, list: &str) -> Result<Self, String> {
+        let names = charset::parse_glyph_list(list)?;
+        Ok(self.with_glyphs(names))
+    }
+
+    /// Whether glyphs are given a simple box outline, so that they can be
+    /// told apart from missing glyphs when testing a layout.
+    pub fn with_placeholder_outlines(mut self, placeholder_outlines: bool) -> Self {
+        self.placeholder_outlines = placeholder_outlines;
+        self
+    }
+
+    pub fn units_per_em(&self) -> f64 {
+        self.units_per_em
+    }
+
+    pub fn glyph_names(&self) -> &[String] {
+        &self.glyphs
+    }
+
+    pub fn build(&self) -> Ufo {
+        let mut ufo = Ufo::new();
+        ufo.font_info = FontInfo {
+            family_name: Some(self.family_name.clone()),
+            style_name: Some(self.style_name.clone()),
+            units_per_em: TryFrom::try_from(self.units_per_em).ok(),
+            descender: Some(self.descender.into()),
+            ascender: Some(self.ascender.into()),
+            cap_height: Some(self.cap_height.into()),
+            x_height: Some(self.x_height.into()),
+            ..Default::default()
+        }
+        .into();
+
+        let layer = ufo.get_default_layer_mut().unwrap();
+        for name in &self.glyphs {
+            layer.insert_glyph(self.new_glyph(name));
+        }
+        ufo
+    }
+
+    /// A workspace with the built font, as if it had just been opened.
+    #[cfg(test)]
+    pub(crate) fn workspace(&self) -> crate::data::Workspace {
+        let mut workspace = crate::data::Workspace::default();
+        workspace.set_file(self.build(), None);
+        workspace
+    }
+
+    fn new_glyph(&self, name: &str) -> Glyph {
+        let mut glyph = Glyph::new_named(name);
+        glyph.codepoints = glyph_names::codepoints_for_glyph(name);
+        if !self.placeholder_outlines {
+            return glyph;
+        }
+
+        let chr = glyph
+            .codepoints
+            .as_ref()
+            .and_then(|cps| cps.first().copied());
+        let upm = self.units_per_em;
+        if chr.map(char::is_whitespace).unwrap_or(false) {
+            glyph.advance = Some(Advance {
+                width: (upm * 0.25).round() as f32,
+                height: 0.0,
+            });
+            return glyph;
+        }
+        let (width, height) = if chr.map(char::is_lowercase).unwrap_or(false) {
+            ((upm * 0.5).round(), self.x_height)
+        } else {
+            ((upm * 0.6).round(), self.cap_height)
+        };
+        let sidebearing = (upm * 0.05).round();
+        glyph.advance = Some(Advance {
+            width: width as f32,
+            height: 0.0,
+        });
+        glyph.outline = Some(Outline {
+            contours: vec![box_contour(sidebearing, width - sidebearing, height)],
+            components: Vec::new(),
+        });
+        glyph
+    }
+}
+
+/// A counter-clockwise rectangle from the baseline to `top`.
+fn box_contour(left: f64, right: f64, top: f64) -> Contour {
+    let points = [(left, 0.0), (right, 0.0), (right, top), (left, top)]
+        .iter()
+        .map(|(x, y)| {
+            ContourPoint::new(
+                *x as f32,
+                *y as f32,
+                PointType::Line,
+                false,
+                None,
+                None,
+                None,
+            )
+        })
+        .collect();
+    Contour::new(points, None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_font() {
+        let ufo = FontBuilder::new()
+            .with_units_per_em(2000.0)
+            .with_glyph_list("U+0041-0042 space")
+            .unwrap()
+            .with_placeholder_outlines(true)
+            .build();
+        let info = ufo.font_info.as_ref().unwrap();
+        assert_eq!(info.cap_height.map(|v| v.get()), Some(1400.0));
+        assert_eq!(ufo.iter_names().count(), 3);
+
+        let a = ufo.get_glyph("A").unwrap();
+        assert_eq!(a.codepoints, Some(vec!['A']));
+        let contour = &a.outline.as_ref().unwrap().contours[0];
+        assert_eq!(contour.points.len(), 4);
+        assert_eq!(contour.points[2].y, 1400.0);
+
+        let space = ufo.get_glyph("space").unwrap();
+        assert!(space.outline.is_none());
+        assert_eq!(space.advance.as_ref().map(|a| a.width), Some(500.0));
+    }
+}
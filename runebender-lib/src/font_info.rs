@@ -0,0 +1,341 @@
+//! Editable groups of UFO fontinfo fields.
+//!
+//! `SimpleFontInfo` keeps the names and metrics that are needed while drawing;
+//! the types here cover the rest of the fields shown in the font info panel.
+//!
+//! Empty strings and empty lists are written out as missing values.
+
+use std::sync::Arc;
+
+use druid::{Data, Lens};
+use norad::{FontInfo, IntegerOrFloat};
+
+/// The maximum number of values in the `postscriptBlueValues` and
+/// `postscriptFamilyBlues` lists.
+const MAX_BLUE_VALUES: usize = 14;
+/// The maximum number of values in the `postscriptOtherBlues` and
+/// `postscriptFamilyOtherBlues` lists.
+const MAX_OTHER_BLUES: usize = 10;
+/// The maximum number of values in the stem snap lists.
+const MAX_STEM_SNAPS: usize = 12;
+
+/// A PostScript alignment zone, in design space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlueZone {
+    pub bottom: f64,
+    pub top: f64,
+}
+
+/// Alternate names and version info.
+#[derive(Debug, Clone, Data, Lens, PartialEq)]
+pub struct NameInfo {
+    pub style_map_family_name: Arc<str>,
+    pub preferred_family_name: Arc<str>,
+    pub preferred_subfamily_name: Arc<str>,
+    pub postscript_font_name: Arc<str>,
+    pub postscript_full_name: Arc<str>,
+    pub version_major: Option<i32>,
+    pub version_minor: Option<u32>,
+}
+
+/// Values for the OS/2 and hhea tables.
+#[derive(Debug, Clone, Data, Lens, PartialEq)]
+pub struct Os2Info {
+    pub weight_class: Option<u32>,
+    pub vendor_id: Arc<str>,
+    pub typo_ascender: Option<i32>,
+    pub typo_descender: Option<i32>,
+    pub typo_line_gap: Option<i32>,
+    pub win_ascent: Option<u32>,
+    pub win_descent: Option<u32>,
+    pub hhea_ascender: Option<i32>,
+    pub hhea_descender: Option<i32>,
+    pub hhea_line_gap: Option<i32>,
+}
+
+/// PostScript hinting data.
+#[derive(Debug, Clone, Data, Lens, PartialEq)]
+pub struct PostscriptInfo {
+    pub blue_values: Arc<Vec<f64>>,
+    pub other_blues: Arc<Vec<f64>>,
+    pub family_blues: Arc<Vec<f64>>,
+    pub family_other_blues: Arc<Vec<f64>>,
+    pub stem_snap_h: Arc<Vec<f64>>,
+    pub stem_snap_v: Arc<Vec<f64>>,
+    pub blue_fuzz: Option<f64>,
+    pub blue_shift: Option<f64>,
+    pub blue_scale: Option<f64>,
+    pub underline_position: Option<f64>,
+    pub underline_thickness: Option<f64>,
+}
+
+/// Copyright, license, and credits.
+#[derive(Debug, Clone, Data, Lens, PartialEq)]
+pub struct LegalInfo {
+    pub copyright: Arc<str>,
+    pub trademark: Arc<str>,
+    pub year: Option<i32>,
+    pub designer: Arc<str>,
+    pub designer_url: Arc<str>,
+    pub manufacturer: Arc<str>,
+    pub manufacturer_url: Arc<str>,
+    pub license: Arc<str>,
+    pub license_url: Arc<str>,
+}
+
+impl NameInfo {
+    pub(crate) fn from_info(info: &FontInfo) -> Self {
+        NameInfo {
+            style_map_family_name: string(&info.style_map_family_name),
+            preferred_family_name: string(&info.open_type_name_preferred_family_name),
+            preferred_subfamily_name: string(&info.open_type_name_preferred_subfamily_name),
+            postscript_font_name: string(&info.postscript_font_name),
+            postscript_full_name: string(&info.postscript_full_name),
+            version_major: info.version_major,
+            version_minor: info.version_minor,
+        }
+    }
+
+    pub(crate) fn apply(&self, info: &mut FontInfo) {
+        info.style_map_family_name = opt_string(&self.style_map_family_name);
+        info.open_type_name_preferred_family_name = opt_string(&self.preferred_family_name);
+        info.open_type_name_preferred_subfamily_name = opt_string(&self.preferred_subfamily_name);
+        info.postscript_font_name = opt_string(&self.postscript_font_name);
+        info.postscript_full_name = opt_string(&self.postscript_full_name);
+        info.version_major = self.version_major;
+        info.version_minor = self.version_minor;
+    }
+
+    pub(crate) fn validate(&self, problems: &mut Vec<String>) {
+        let ps_name = &self.postscript_font_name;
+        if ps_name.len() > 63 {
+            problems.push("PostScript name must be at most 63 characters.".into());
+        }
+        if ps_name
+            .chars()
+            .any(|c| !c.is_ascii_graphic() || "[](){}<>/%".contains(c))
+        {
+            problems.push("PostScript name contains invalid characters.".into());
+        }
+    }
+}
+
+impl Os2Info {
+    pub(crate) fn from_info(info: &FontInfo) -> Self {
+        Os2Info {
+            weight_class: info.open_type_os2_weight_class,
+            vendor_id: string(&info.open_type_os2_vendor_id),
+            typo_ascender: info.open_type_os2_typo_ascender,
+            typo_descender: info.open_type_os2_typo_descender,
+            typo_line_gap: info.open_type_os2_typo_line_gap,
+            win_ascent: info.open_type_os2_win_ascent,
+            win_descent: info.open_type_os2_win_descent,
+            hhea_ascender: info.open_type_hhea_ascender,
+            hhea_descender: info.open_type_hhea_descender,
+            hhea_line_gap: info.open_type_hhea_line_gap,
+        }
+    }
+
+    pub(crate) fn apply(&self, info: &mut FontInfo) {
+        info.open_type_os2_weight_class = self.weight_class;
+        info.open_type_os2_vendor_id = opt_string(&self.vendor_id);
+        info.open_type_os2_typo_ascender = self.typo_ascender;
+        info.open_type_os2_typo_descender = self.typo_descender;
+        info.open_type_os2_typo_line_gap = self.typo_line_gap;
+        info.open_type_os2_win_ascent = self.win_ascent;
+        info.open_type_os2_win_descent = self.win_descent;
+        info.open_type_hhea_ascender = self.hhea_ascender;
+        info.open_type_hhea_descender = self.hhea_descender;
+        info.open_type_hhea_line_gap = self.hhea_line_gap;
+    }
+
+    pub(crate) fn validate(&self, problems: &mut Vec<String>) {
+        if let Some(weight) = self.weight_class {
+            if !(1..=1000).contains(&weight) {
+                problems.push("Weight class must be between 1 and 1000.".into());
+            }
+        }
+        if self.vendor_id.len() > 4 || !self.vendor_id.chars().all(|c| c.is_ascii()) {
+            problems.push("Vendor ID must be at most four ASCII characters.".into());
+        }
+        if let (Some(asc), Some(desc)) = (self.typo_ascender, self.typo_descender) {
+            if asc < desc {
+                problems.push("Typo ascender is below the typo descender.".into());
+            }
+        }
+    }
+}
+
+impl PostscriptInfo {
+    pub(crate) fn from_info(info: &FontInfo) -> Self {
+        PostscriptInfo {
+            blue_values: list(&info.postscript_blue_values),
+            other_blues: list(&info.postscript_other_blues),
+            family_blues: list(&info.postscript_family_blues),
+            family_other_blues: list(&info.postscript_family_other_blues),
+            stem_snap_h: list(&info.postscript_stem_snap_h),
+            stem_snap_v: list(&info.postscript_stem_snap_v),
+            blue_fuzz: info.postscript_blue_fuzz.map(|v| v.get()),
+            blue_shift: info.postscript_blue_shift.map(|v| v.get()),
+            blue_scale: info.postscript_blue_scale,
+            underline_position: info.postscript_underline_position.map(|v| v.get()),
+            underline_thickness: info.postscript_underline_thickness.map(|v| v.get()),
+        }
+    }
+
+    pub(crate) fn apply(&self, info: &mut FontInfo) {
+        info.postscript_blue_values = opt_list(&self.blue_values);
+        info.postscript_other_blues = opt_list(&self.other_blues);
+        info.postscript_family_blues = opt_list(&self.family_blues);
+        info.postscript_family_other_blues = opt_list(&self.family_other_blues);
+        info.postscript_stem_snap_h = opt_list(&self.stem_snap_h);
+        info.postscript_stem_snap_v = opt_list(&self.stem_snap_v);
+        info.postscript_blue_fuzz = self.blue_fuzz.map(Into::into);
+        info.postscript_blue_shift = self.blue_shift.map(Into::into);
+        info.postscript_blue_scale = self.blue_scale;
+        info.postscript_underline_position = self.underline_position.map(Into::into);
+        info.postscript_underline_thickness = self.underline_thickness.map(Into::into);
+    }
+
+    pub(crate) fn validate(&self, problems: &mut Vec<String>) {
+        validate_zones("Blue values", &self.blue_values, MAX_BLUE_VALUES, problems);
+        validate_zones("Other blues", &self.other_blues, MAX_OTHER_BLUES, problems);
+        validate_zones(
+            "Family blues",
+            &self.family_blues,
+            MAX_BLUE_VALUES,
+            problems,
+        );
+        validate_zones(
+            "Family other blues",
+            &self.family_other_blues,
+            MAX_OTHER_BLUES,
+            problems,
+        );
+        if self.stem_snap_h.len() > MAX_STEM_SNAPS || self.stem_snap_v.len() > MAX_STEM_SNAPS {
+            problems.push(format!(
+                "Stem snap lists can have at most {} values.",
+                MAX_STEM_SNAPS
+            ));
+        }
+        if matches!(self.blue_scale, Some(scale) if scale <= 0.0) {
+            problems.push("Blue scale must be greater than zero.".into());
+        }
+    }
+}
+
+impl PostscriptInfo {
+    /// The zones described by the blue values and other blues.
+    ///
+    /// A trailing unpaired value is ignored.
+    pub(crate) fn zones(&self) -> Vec<BlueZone> {
+        self.blue_values
+            .chunks_exact(2)
+            .chain(self.other_blues.chunks_exact(2))
+            .map(|pair| BlueZone {
+                bottom: pair[0].min(pair[1]),
+                top: pair[0].max(pair[1]),
+            })
+            .collect()
+    }
+}
+
+/// Returns the zone edge closest to `y`, if one is within `tolerance`.
+pub(crate) fn nearest_zone_edge(zones: &[BlueZone], y: f64, tolerance: f64) -> Option<f64> {
+    zones
+        .iter()
+        .flat_map(|zone| std::iter::once(zone.bottom).chain(std::iter::once(zone.top)))
+        .map(|edge| (edge, (edge - y).abs()))
+        .filter(|(_, dist)| *dist <= tolerance)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(edge, _)| edge)
+}
+
+impl LegalInfo {
+    pub(crate) fn from_info(info: &FontInfo) -> Self {
+        LegalInfo {
+            copyright: string(&info.copyright),
+            trademark: string(&info.trademark),
+            year: info.year,
+            designer: string(&info.open_type_name_designer),
+            designer_url: string(&info.open_type_name_designer_url),
+            manufacturer: string(&info.open_type_name_manufacturer),
+            manufacturer_url: string(&info.open_type_name_manufacturer_url),
+            license: string(&info.open_type_name_license),
+            license_url: string(&info.open_type_name_license_url),
+        }
+    }
+
+    pub(crate) fn apply(&self, info: &mut FontInfo) {
+        info.copyright = opt_string(&self.copyright);
+        info.trademark = opt_string(&self.trademark);
+        info.year = self.year;
+        info.open_type_name_designer = opt_string(&self.designer);
+        info.open_type_name_designer_url = opt_string(&self.designer_url);
+        info.open_type_name_manufacturer = opt_string(&self.manufacturer);
+        info.open_type_name_manufacturer_url = opt_string(&self.manufacturer_url);
+        info.open_type_name_license = opt_string(&self.license);
+        info.open_type_name_license_url = opt_string(&self.license_url);
+    }
+}
+
+/// Check that a list of blue zones has pairs of ascending values.
+fn validate_zones(name: &str, values: &[f64], max: usize, problems: &mut Vec<String>) {
+    if values.len() % 2 != 0 {
+        problems.push(format!("{} must contain pairs of values.", name));
+    }
+    if values.len() > max {
+        problems.push(format!("{} can have at most {} values.", name, max));
+    }
+    if values.windows(2).any(|pair| pair[0] > pair[1]) {
+        problems.push(format!("{} must be in ascending order.", name));
+    }
+}
+
+fn list(src: &Option<Vec<IntegerOrFloat>>) -> Arc<Vec<f64>> {
+    Arc::new(src.iter().flatten().map(|v| v.get()).collect())
+}
+
+fn opt_list(src: &[f64]) -> Option<Vec<IntegerOrFloat>> {
+    if src.is_empty() {
+        None
+    } else {
+        Some(src.iter().copied().map(Into::into).collect())
+    }
+}
+
+fn string(src: &Option<String>) -> Arc<str> {
+    src.as_deref().unwrap_or_default().into()
+}
+
+fn opt_string(src: &Arc<str>) -> Option<String> {
+    if src.is_empty() {
+        None
+    } else {
+        Some(src.to_string())
+    }
+}
+
+impl Default for NameInfo {
+    fn default() -> Self {
+        NameInfo::from_info(&FontInfo::default())
+    }
+}
+
+impl Default for Os2Info {
+    fn default() -> Self {
+        Os2Info::from_info(&FontInfo::default())
+    }
+}
+
+impl Default for PostscriptInfo {
+    fn default() -> Self {
+        PostscriptInfo::from_info(&FontInfo::default())
+    }
+}
+
+impl Default for LegalInfo {
+    fn default() -> Self {
+        LegalInfo::from_info(&FontInfo::default())
+    }
+}
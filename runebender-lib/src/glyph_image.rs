@@ -0,0 +1,132 @@
+//! Rendering a glyph to an SVG or PNG file.
+
+use std::error::Error;
+use std::path::Path;
+
+use druid::kurbo::{Affine, BezPath, Line, Rect, Shape};
+use druid::piet::{Color, Device, RenderContext};
+
+use crate::data::{FontMetrics, ImageExportOptions, ImageFormat};
+
+/// Space around the glyph, in pixels.
+const IMAGE_PADDING: f64 = 8.0;
+const METRICS_COLOR: Color = Color::rgb8(0xa0, 0xa0, 0xa0);
+const METRICS_STROKE_WIDTH: f64 = 1.0;
+
+/// A glyph, transformed into the coordinate space of the image.
+struct GlyphImage {
+    outline: BezPath,
+    /// The metric lines, if they are shown.
+    lines: Vec<Line>,
+    width: usize,
+    height: usize,
+}
+
+impl GlyphImage {
+    /// `layout_bounds` are the glyph's advance and vertical metrics, in
+    /// design space; the image is scaled so they are `options.size` pixels tall.
+    fn new(
+        outline: &BezPath,
+        layout_bounds: Rect,
+        metrics: &FontMetrics,
+        options: &ImageExportOptions,
+    ) -> Self {
+        // don't clip parts of the outline that extend past the metrics
+        let bounds = layout_bounds.union(outline.bounding_box());
+        let scale = options.size.max(1.0) / layout_bounds.height().max(1.0);
+        let to_image = Affine::translate((IMAGE_PADDING, IMAGE_PADDING))
+            * Affine::new([
+                scale,
+                0.0,
+                0.0,
+                -scale,
+                -bounds.x0 * scale,
+                bounds.y1 * scale,
+            ]);
+
+        let mut lines = Vec::new();
+        if options.show_metrics {
+            let heights = [
+                Some(0.0),
+                metrics.descender,
+                metrics.x_height,
+                metrics.cap_height,
+                metrics.ascender,
+            ];
+            for y in heights.iter().flatten() {
+                lines.push(Line::new((bounds.x0, *y), (bounds.x1, *y)));
+            }
+            for x in [layout_bounds.x0, layout_bounds.x1].iter() {
+                lines.push(Line::new((*x, bounds.y0), (*x, bounds.y1)));
+            }
+        }
+
+        GlyphImage {
+            outline: to_image * outline.clone(),
+            lines: lines
+                .into_iter()
+                .map(|line| Line::new(to_image * line.p0, to_image * line.p1))
+                .collect(),
+            width: (bounds.width() * scale + IMAGE_PADDING * 2.0).ceil() as usize,
+            height: (bounds.height() * scale + IMAGE_PADDING * 2.0).ceil() as usize,
+        }
+    }
+
+    fn write_svg(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        use svg::node::element;
+        use svg::Document;
+
+        let mut document = Document::new()
+            .set("width", self.width)
+            .set("height", self.height)
+            .set("viewBox", (0, 0, self.width, self.height));
+        for line in &self.lines {
+            document = document.add(
+                element::Line::new()
+                    .set("x1", line.p0.x)
+                    .set("y1", line.p0.y)
+                    .set("x2", line.p1.x)
+                    .set("y2", line.p1.y)
+                    .set("stroke", "#a0a0a0")
+                    .set("stroke-width", METRICS_STROKE_WIDTH),
+            );
+        }
+        document = document.add(
+            element::Path::new()
+                .set("fill", "black")
+                .set("d", self.outline.to_svg()),
+        );
+        svg::save(path, &document)?;
+        Ok(())
+    }
+
+    fn write_png(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut device = Device::new()?;
+        let mut target = device.bitmap_target(self.width, self.height, 1.0)?;
+        {
+            let mut rc = target.render_context();
+            for line in &self.lines {
+                rc.stroke(*line, &METRICS_COLOR, METRICS_STROKE_WIDTH);
+            }
+            rc.fill(&self.outline, &Color::BLACK);
+            rc.finish()?;
+        }
+        target.save_to_file(path)?;
+        Ok(())
+    }
+}
+
+/// Render a glyph's outline to an image file at `path`.
+pub fn write_glyph_image(
+    path: &Path,
+    outline: &BezPath,
+    layout_bounds: Rect,
+    metrics: &FontMetrics,
+    options: &ImageExportOptions,
+) -> Result<(), Box<dyn Error>> {
+    let image = GlyphImage::new(outline, layout_bounds, metrics, options);
+    match options.format {
+        ImageFormat::Svg => image.write_svg(path),
+        ImageFormat::Png => image.write_png(path),
+    }
+}
@@ -0,0 +1,47 @@
+//! Locking finished glyphs, so they aren't edited by accident.
+//!
+//! The lock is stored in the glyph lib, so it is saved with the UFO and
+//! shared with everyone working on the font. A locked glyph opens in a
+//! read-only editor, and edits to it are not written when saving.
+
+use norad::Glyph;
+
+/// The key in the glyph lib that marks a glyph as locked.
+const LOCKED_LIB_KEY: &str = "org.linebender.runebender.locked";
+
+/// `true` if this glyph is locked.
+pub fn is_locked(glyph: &Glyph) -> bool {
+    glyph
+        .lib
+        .as_ref()
+        .and_then(|lib| lib.get(LOCKED_LIB_KEY))
+        .and_then(|value| value.as_boolean())
+        .unwrap_or(false)
+}
+
+/// Lock or unlock this glyph.
+pub fn set_locked(glyph: &mut Glyph, locked: bool) {
+    if locked {
+        glyph
+            .lib
+            .get_or_insert_with(Default::default)
+            .insert(LOCKED_LIB_KEY.into(), plist::Value::Boolean(true));
+    } else if let Some(lib) = glyph.lib.as_mut() {
+        lib.remove(LOCKED_LIB_KEY);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lock_and_unlock() {
+        let mut glyph = Glyph::new_named("a");
+        assert!(!is_locked(&glyph));
+        set_locked(&mut glyph, true);
+        assert!(is_locked(&glyph));
+        set_locked(&mut glyph, false);
+        assert!(!is_locked(&glyph));
+    }
+}
@@ -6,6 +6,9 @@
 
 include!(concat!(env!("OUT_DIR"), "/glyph_names_codegen.rs"));
 
+/// The longest name supported by common production tools.
+const MAX_GLYPH_NAME_LEN: usize = 63;
+
 /// Given a `char`, returns the postscript name for that `char`s glyph,
 /// if one exists in the aglfn.
 pub fn glyph_name_for_char(chr: char) -> Option<&'static str> {
@@ -44,12 +47,14 @@ pub fn validate_and_standardize_name(name: &str) -> Result<String, IllegalName>
 
 /// Given a glyph name, guess what the unicode value is?
 ///
-/// Works fine for known glyph names, otherwise just uses the first character :shrug:
+/// Works fine for known glyph names and `uniXXXX`/`uXXXXX` names, otherwise
+/// just uses the first character :shrug:
 pub fn codepoints_for_glyph(name: &str) -> Option<Vec<char>> {
     GLYPH_NAMES
         .iter()
         .find(|(_, n)| *n == name)
         .map(|(c, _)| vec![*c])
+        .or_else(|| char_for_uni_name(name).map(|c| vec![c]))
         .or_else(|| {
             let mut chars = name.chars();
             // if we're at most one char long, use that as our codepoint
@@ -60,6 +65,91 @@ pub fn codepoints_for_glyph(name: &str) -> Option<Vec<char>> {
         })
 }
 
+/// Parse a production name of the form `uniXXXX` or `uXXXX[XX]`.
+fn char_for_uni_name(name: &str) -> Option<char> {
+    let hex = if name.starts_with("uni") && name.len() == 7 {
+        &name[3..]
+    } else if name.starts_with('u') && (5..=7).contains(&name.len()) {
+        &name[1..]
+    } else {
+        return None;
+    };
+    if !hex.chars().all(|c| matches!(c, '0'..='9' | 'A'..='F')) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16)
+        .ok()
+        .and_then(std::char::from_u32)
+}
+
+/// Returns up to `max` names from the aglfn that start with `prefix`.
+///
+/// Exact-case matches are listed before case-insensitive ones.
+pub fn names_with_prefix(prefix: &str, max: usize) -> Vec<&'static str> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let lowercase = prefix.to_lowercase();
+    let exact = GLYPH_NAMES
+        .iter()
+        .map(|(_, name)| *name)
+        .filter(|name| name.starts_with(prefix));
+    let inexact = GLYPH_NAMES
+        .iter()
+        .map(|(_, name)| *name)
+        .filter(|name| !name.starts_with(prefix) && name.to_lowercase().starts_with(&lowercase));
+    exact.chain(inexact).take(max).collect()
+}
+
+/// Returns the completion for a partially typed glyph name, if there is one.
+///
+/// This is the shortest aglfn name that starts with `prefix`.
+pub fn complete_name(prefix: &str) -> Option<String> {
+    GLYPH_NAMES
+        .iter()
+        .map(|(_, name)| *name)
+        .filter(|name| name.starts_with(prefix) && name.len() > prefix.len())
+        .min_by_key(|name| name.len())
+        .map(String::from)
+}
+
+/// A short description of what a glyph name will mean, shown while it is
+/// being edited.
+///
+/// This includes the codepoint that will be assigned to the glyph, or a
+/// description of why the name is not valid.
+pub fn describe_name(name: &str) -> String {
+    let name = match validate_and_standardize_name(name) {
+        Ok(name) => name,
+        Err(e) => return e.to_string(),
+    };
+    if name.starts_with(|c: char| c.is_ascii_digit())
+        || (name.starts_with('.') && name != ".notdef")
+    {
+        return "Glyph names should not start with a digit or period.".into();
+    }
+    if name.len() > MAX_GLYPH_NAME_LEN {
+        return format!(
+            "Glyph names should be at most {} characters.",
+            MAX_GLYPH_NAME_LEN
+        );
+    }
+    let codepoint = match codepoints_for_glyph(&name) {
+        Some(chars) => chars
+            .iter()
+            .map(|c| format!("U+{:04X}", *c as u32))
+            .collect::<Vec<_>>()
+            .join(", "),
+        None => "no codepoint".into(),
+    };
+    let suggestions = names_with_prefix(&name, 3);
+    match suggestions.as_slice() {
+        [] => format!("{}: {}", name, codepoint),
+        [only] if *only == name => format!("{}: {}", name, codepoint),
+        _ => format!("{}: {}  (tab: {})", name, codepoint, suggestions.join(", ")),
+    }
+}
+
 /// An error indicating a name included illegal characters.
 #[derive(Clone)]
 pub struct IllegalName;
@@ -102,6 +192,19 @@ mod tests {
         assert_eq!(codepoints_for_glyph("A"), Some(vec!['A']));
         assert_eq!(codepoints_for_glyph("eacute"), Some(vec!['é']));
         assert_eq!(codepoints_for_glyph("some-string"), None);
+        assert_eq!(codepoints_for_glyph("uni00E9"), Some(vec!['é']));
+        assert_eq!(codepoints_for_glyph("u1F600"), Some(vec!['😀']));
+        assert_eq!(codepoints_for_glyph("uni00e9"), None);
+        assert_eq!(codepoints_for_glyph("uni12345"), None);
+    }
+
+    #[test]
+    fn completion() {
+        assert_eq!(complete_name("eacut"), Some("eacute".to_string()));
+        assert_eq!(complete_name("eacute"), None);
+        assert!(names_with_prefix("Eacute", 5).contains(&"Eacute"));
+        assert!(names_with_prefix("eAcute", 5).contains(&"eacute"));
+        assert!(names_with_prefix("", 5).is_empty());
     }
 
     #[test]
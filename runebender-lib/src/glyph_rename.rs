@@ -0,0 +1,188 @@
+//! Planning batch renames of glyphs.
+
+use std::collections::HashSet;
+
+use norad::GlyphName;
+
+use crate::glyph_names;
+
+/// How to change each glyph name in a batch rename.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenameOp<'a> {
+    /// Replace every occurrence of `find` with `replace`.
+    Replace { find: &'a str, replace: &'a str },
+    /// Add a suffix, like `.sc`, to names that don't already have it.
+    AddSuffix(&'a str),
+    /// Remove a suffix from names that have it.
+    RemoveSuffix(&'a str),
+}
+
+impl RenameOp<'_> {
+    /// The new name for `name`, or `None` if it is unchanged.
+    pub fn apply(&self, name: &str) -> Option<String> {
+        let new_name = match *self {
+            RenameOp::Replace { find, .. } if find.is_empty() => return None,
+            RenameOp::Replace { find, replace } => name.replace(find, replace),
+            RenameOp::AddSuffix(suffix) => {
+                let suffix = normalize_suffix(suffix)?;
+                if name.ends_with(&suffix) {
+                    return None;
+                }
+                format!("{}{}", name, suffix)
+            }
+            RenameOp::RemoveSuffix(suffix) => {
+                let suffix = normalize_suffix(suffix)?;
+                match name.strip_suffix(&suffix) {
+                    Some(base) if !base.is_empty() => base.to_string(),
+                    _ => return None,
+                }
+            }
+        };
+        Some(new_name).filter(|new_name| new_name != name)
+    }
+}
+
+/// Suffixes always start with a period, so `sc` and `.sc` are the same.
+fn normalize_suffix(suffix: &str) -> Option<String> {
+    let suffix = suffix.trim().trim_start_matches('.');
+    if suffix.is_empty() {
+        None
+    } else {
+        Some(format!(".{}", suffix))
+    }
+}
+
+/// Work out the renames needed to apply `op` to `names`.
+///
+/// `exists` reports whether a glyph is already in the font. The renames are
+/// ordered so that they can be applied one at a time: a glyph is renamed
+/// before another glyph takes its name.
+///
+/// Returns a description of the problem if a new name is invalid or would
+/// clash with another glyph.
+pub fn plan_renames(
+    names: &[GlyphName],
+    op: &RenameOp,
+    exists: impl Fn(&str) -> bool,
+) -> Result<Vec<(GlyphName, GlyphName)>, String> {
+    let mut pending = Vec::new();
+    let mut new_names = HashSet::new();
+    for name in names {
+        let new_name = match op.apply(name) {
+            Some(new_name) => new_name,
+            None => continue,
+        };
+        let new_name = glyph_names::validate_and_standardize_name(&new_name)
+            .map_err(|_| format!("'{}' is not a valid glyph name", new_name))?;
+        if !new_names.insert(new_name.clone()) {
+            return Err(format!("more than one glyph would be named '{}'", new_name));
+        }
+        pending.push((name.clone(), GlyphName::from(new_name)));
+    }
+
+    let renamed = pending
+        .iter()
+        .map(|(old, _)| old.clone())
+        .collect::<HashSet<_>>();
+    if let Some((_, new)) = pending
+        .iter()
+        .find(|(_, new)| exists(new) && !renamed.contains(new))
+    {
+        return Err(format!("'{}' already exists", new));
+    }
+
+    // a glyph can only take a name once the glyph that had it is renamed
+    let mut ordered = Vec::with_capacity(pending.len());
+    while !pending.is_empty() {
+        let ready = pending
+            .iter()
+            .position(|(_, new)| !pending.iter().any(|(old, _)| old == new))
+            .ok_or_else(|| "glyphs would swap names".to_string())?;
+        ordered.push(pending.remove(ready));
+    }
+    Ok(ordered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<GlyphName> {
+        names.iter().map(|name| GlyphName::from(*name)).collect()
+    }
+
+    fn plan(names_: &[&str], op: RenameOp, existing: &[&str]) -> Result<Vec<String>, String> {
+        plan_renames(&names(names_), &op, |name| existing.contains(&name)).map(|renames| {
+            renames
+                .iter()
+                .map(|(old, new)| format!("{}>{}", old, new))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn ops() {
+        let replace = RenameOp::Replace {
+            find: "alt",
+            replace: "ss01",
+        };
+        assert_eq!(replace.apply("a.alt").as_deref(), Some("a.ss01"));
+        assert_eq!(replace.apply("b"), None);
+        assert_eq!(
+            RenameOp::AddSuffix("sc").apply("a").as_deref(),
+            Some("a.sc")
+        );
+        assert_eq!(RenameOp::AddSuffix(".sc").apply("a.sc"), None);
+        assert_eq!(
+            RenameOp::RemoveSuffix("sc").apply("a.sc").as_deref(),
+            Some("a")
+        );
+        assert_eq!(RenameOp::RemoveSuffix(".sc").apply(".sc"), None);
+        assert_eq!(RenameOp::AddSuffix(" ").apply("a"), None);
+    }
+
+    #[test]
+    fn planning() {
+        let all = ["a", "a.sc", "b"];
+        assert_eq!(
+            plan(&["a", "b"], RenameOp::AddSuffix("sc"), &all),
+            Err("'a.sc' already exists".into())
+        );
+        assert_eq!(
+            plan(&["b"], RenameOp::AddSuffix("sc"), &all),
+            Ok(vec!["b>b.sc".into()])
+        );
+        // 'a.sc' must be renamed before 'a' can take its name
+        assert_eq!(
+            plan(
+                &["a", "a.sc"],
+                RenameOp::Replace {
+                    find: "a",
+                    replace: "a.sc"
+                },
+                &all
+            ),
+            Ok(vec!["a.sc>a.sc.sc".into(), "a>a.sc".into()])
+        );
+        assert_eq!(
+            plan(
+                &["a", "b"],
+                RenameOp::Replace {
+                    find: "b",
+                    replace: "a"
+                },
+                &all
+            ),
+            Err("'a' already exists".into())
+        );
+        assert!(plan(
+            &["a", "b"],
+            RenameOp::Replace {
+                find: "a",
+                replace: "a b"
+            },
+            &all
+        )
+        .is_err());
+    }
+}
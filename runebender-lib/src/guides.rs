@@ -1,17 +1,36 @@
 use druid::kurbo::{Line, ParamCurve, ParamCurveNearest, Point, Vec2};
 use druid::Data;
 
-use crate::design_space::{DPoint, DVec2, ViewPort};
+use crate::design_space::{DPoint, DVec2, Precision, ViewPort};
+use crate::mark_color::MarkColor;
 use crate::point::EntityId;
 
+/// The key in a guideline's lib under which we store whether it is locked.
+const LOCKED_LIB_KEY: &str = "org.linebender.runebender.locked";
+
 #[derive(Debug, Clone, Data)]
 pub struct Guide {
     pub id: EntityId,
     pub guide: GuideLine,
+    /// A name, drawn alongside the guide.
+    pub name: Option<String>,
+    /// The color the guide is drawn in, in place of the theme's.
+    pub color: Option<MarkColor>,
+    /// A locked guide can be selected, but not moved or deleted.
+    pub locked: bool,
+}
+
+/// A change to a guide, made from its context menu.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GuideEdit {
+    /// Set the name, or remove it if it is empty.
+    Rename(String),
+    SetColor(Option<MarkColor>),
+    ToggleLocked,
 }
 
 /// A guideline.
-#[derive(Debug, Clone, Data)]
+#[derive(Debug, Clone, Data, Serialize, Deserialize)]
 pub enum GuideLine {
     Horiz(DPoint),
     Vertical(DPoint),
@@ -19,9 +38,15 @@ pub enum GuideLine {
 }
 
 impl Guide {
-    fn new(guide: GuideLine) -> Self {
+    pub(crate) fn new(guide: GuideLine) -> Self {
         let id = EntityId::new_for_guide();
-        Guide { id, guide }
+        Guide {
+            id,
+            guide,
+            name: None,
+            color: None,
+            locked: false,
+        }
     }
 
     pub fn horiz(p1: DPoint) -> Self {
@@ -45,6 +70,27 @@ impl Guide {
         self.guide = new;
     }
 
+    /// Apply `edit`, returning `true` if the guide changed.
+    pub(crate) fn apply_edit(&mut self, edit: &GuideEdit) -> bool {
+        match edit {
+            GuideEdit::Rename(name) => {
+                let name = Some(name.trim().to_owned()).filter(|name| !name.is_empty());
+                if name == self.name {
+                    return false;
+                }
+                self.name = name;
+            }
+            GuideEdit::SetColor(color) => {
+                if *color == self.color {
+                    return false;
+                }
+                self.color = *color;
+            }
+            GuideEdit::ToggleLocked => self.locked = !self.locked,
+        }
+        true
+    }
+
     pub fn screen_dist(&self, vport: ViewPort, point: Point) -> f64 {
         self.nearest_screen_point(vport, point).distance(point)
     }
@@ -89,22 +135,35 @@ impl Guide {
         }
     }
 
-    pub fn from_norad(src: &norad::Guideline) -> Self {
+    pub fn from_norad(src: &norad::Guideline, precision: Precision) -> Self {
         use norad::Line;
 
         let guide = match src.line {
-            Line::Vertical(x) => GuideLine::Vertical(DPoint::new(x as f64, 0.)),
-            Line::Horizontal(y) => GuideLine::Horiz(DPoint::new(0., y as f64)),
+            Line::Vertical(x) => GuideLine::Vertical(DPoint::from_file(x as f64, 0., precision)),
+            Line::Horizontal(y) => GuideLine::Horiz(DPoint::from_file(0., y as f64, precision)),
             Line::Angle { x, y, degrees } => {
-                let p1 = DPoint::new(x as f64, y as f64);
+                let p1 = DPoint::from_file(x as f64, y as f64, precision);
+                // p2 only sets the direction, so it is kept as close as we can
                 let p2 = p1.to_raw() + Vec2::from_angle(degrees as f64);
-                let p2 = DPoint::new(p2.x, p2.y);
+                let p2 = DPoint::from_raw(p2, Precision::FINEST);
                 GuideLine::Angle { p1, p2 }
             }
         };
 
-        let id = EntityId::new_for_guide();
-        Guide { id, guide }
+        let mut result = Guide::new(guide);
+        result.name = src.name.clone();
+        result.color = src.color.as_ref().map(|color| MarkColor {
+            r: color.red as f64,
+            g: color.green as f64,
+            b: color.blue as f64,
+            a: color.alpha as f64,
+        });
+        result.locked = src
+            .lib()
+            .and_then(|lib| lib.get(LOCKED_LIB_KEY))
+            .and_then(|value| value.as_boolean())
+            .unwrap_or(false);
+        result
     }
 
     pub fn to_norad(&self) -> norad::Guideline {
@@ -120,6 +179,19 @@ impl Guide {
             }
         };
 
-        norad::Guideline::new(line, None, None, None, None)
+        let color = self.color.map(|color| norad::Color {
+            red: color.r as f32,
+            green: color.g as f32,
+            blue: color.b as f32,
+            alpha: color.a as f32,
+        });
+        let lib = if self.locked {
+            let mut lib = plist::Dictionary::new();
+            lib.insert(LOCKED_LIB_KEY.into(), plist::Value::Boolean(true));
+            Some(lib)
+        } else {
+            None
+        };
+        norad::Guideline::new(line, self.name.clone(), color, None, lib)
     }
 }
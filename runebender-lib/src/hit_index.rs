@@ -0,0 +1,189 @@
+//! A spatial index of the points and segments in a glyph, for hit testing.
+//!
+//! Hit testing happens on every mouse move, and scanning every point and
+//! segment of a large glyph (such as a complex CJK glyph) each time makes
+//! hovering laggy. Instead we bucket them into a grid in design space, so
+//! that a hit test only looks at the items near the mouse.
+//!
+//! The index is built lazily, the first time it is needed after the paths
+//! change, and small glyphs are not indexed at all.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use druid::kurbo::{ParamCurveExtrema, Point, Rect};
+
+use crate::path::{Path, Segment};
+use crate::point::PathPoint;
+
+/// The size of each grid cell, in design units.
+const CELL_SIZE: f64 = 64.0;
+
+/// Glyphs with fewer points than this are hit tested by checking every
+/// point, which is fast enough.
+const MIN_INDEXED_POINTS: usize = 256;
+
+/// Points and segments, bucketed by the grid cells they touch.
+#[derive(Debug, Default)]
+pub(crate) struct HitIndex {
+    points: Vec<PathPoint>,
+    segments: Vec<Segment>,
+    cells: HashMap<(i64, i64), Cell>,
+}
+
+#[derive(Debug, Default)]
+struct Cell {
+    points: Vec<usize>,
+    segments: Vec<usize>,
+}
+
+/// The index for the current paths of a session.
+///
+/// Clones share the same index; it is rebuilt when it is asked for with
+/// paths other than the ones it was built from.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HitIndexCache {
+    inner: Arc<Mutex<Option<(Arc<Vec<Path>>, Arc<HitIndex>)>>>,
+}
+
+impl HitIndex {
+    pub(crate) fn new(paths: &[Path]) -> Self {
+        let mut index = HitIndex::default();
+        for path in paths {
+            for point in path.points() {
+                let idx = index.points.len();
+                index.points.push(*point);
+                let cell = cell_for_point(point.point.to_raw());
+                index.cells.entry(cell).or_default().points.push(idx);
+            }
+            for seg in path.iter_segments() {
+                let idx = index.segments.len();
+                let bounds = seg
+                    .kurbo_segments()
+                    .map(|seg| seg.bounding_box())
+                    .fold(None, |acc: Option<Rect>, rect| {
+                        Some(acc.map(|acc| acc.union(rect)).unwrap_or(rect))
+                    });
+                if let Some(bounds) = bounds {
+                    for cell in cells_in_rect(bounds) {
+                        index.cells.entry(cell).or_default().segments.push(idx);
+                    }
+                }
+                index.segments.push(seg);
+            }
+        }
+        index
+    }
+
+    /// The points that may be within `radius` of `point`, in the order they
+    /// appear in the glyph's paths.
+    pub(crate) fn points_near(&self, point: Point, radius: f64) -> Vec<&PathPoint> {
+        let mut found: Vec<usize> = self
+            .cells_near(point, radius)
+            .flat_map(|cell| cell.points.iter().copied())
+            .collect();
+        found.sort_unstable();
+        found.into_iter().map(|idx| &self.points[idx]).collect()
+    }
+
+    /// The segments that may be within `radius` of `point`, in the order they
+    /// appear in the glyph's paths.
+    pub(crate) fn segments_near(&self, point: Point, radius: f64) -> Vec<&Segment> {
+        let mut found: Vec<usize> = self
+            .cells_near(point, radius)
+            .flat_map(|cell| cell.segments.iter().copied())
+            .collect();
+        found.sort_unstable();
+        found.dedup();
+        found.into_iter().map(|idx| &self.segments[idx]).collect()
+    }
+
+    fn cells_near(&self, point: Point, radius: f64) -> impl Iterator<Item = &Cell> {
+        let rect = Rect::from_center_size(point, (radius * 2.0, radius * 2.0));
+        cells_in_rect(rect).filter_map(move |cell| self.cells.get(&cell))
+    }
+}
+
+impl HitIndexCache {
+    /// The index for `paths`, or `None` if there are too few points for an
+    /// index to be worthwhile.
+    pub(crate) fn get(&self, paths: &Arc<Vec<Path>>) -> Option<Arc<HitIndex>> {
+        let point_count: usize = paths.iter().map(|path| path.points().len()).sum();
+        if point_count < MIN_INDEXED_POINTS {
+            return None;
+        }
+        let mut inner = self.inner.lock().unwrap();
+        match inner.as_ref() {
+            // we hold on to the paths, so they can't be modified in place
+            Some((indexed, index)) if Arc::ptr_eq(indexed, paths) => Some(index.clone()),
+            _ => {
+                let index = Arc::new(HitIndex::new(paths));
+                *inner = Some((paths.clone(), index.clone()));
+                Some(index)
+            }
+        }
+    }
+}
+
+fn cell_for_point(point: Point) -> (i64, i64) {
+    (
+        (point.x / CELL_SIZE).floor() as i64,
+        (point.y / CELL_SIZE).floor() as i64,
+    )
+}
+
+fn cells_in_rect(rect: Rect) -> impl Iterator<Item = (i64, i64)> {
+    let (x0, y0) = cell_for_point(Point::new(rect.min_x(), rect.min_y()));
+    let (x1, y1) = cell_for_point(Point::new(rect.max_x(), rect.max_y()));
+    (x0..=x1).flat_map(move |x| (y0..=y1).map(move |y| (x, y)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubic_path::CubicPath;
+    use crate::design_space::DPoint;
+    use crate::point::EntityId;
+
+    fn square(x: f64, y: f64) -> Path {
+        let path_id = EntityId::next();
+        let points = [(x, y), (x + 10.0, y), (x + 10.0, y + 10.0), (x, y + 10.0)]
+            .iter()
+            .map(|pt| PathPoint::on_curve(path_id, DPoint::new(pt.0, pt.1)))
+            .collect();
+        CubicPath::from_raw_parts(path_id, points, None, true).into()
+    }
+
+    #[test]
+    fn finds_nearby_items() {
+        let paths: Vec<Path> = (0..20)
+            .flat_map(|i| (0..20).map(move |j| square(i as f64 * 100.0, j as f64 * 100.0)))
+            .collect();
+        let index = HitIndex::new(&paths);
+
+        // only the points of the nearest square are candidates
+        let near = index.points_near(Point::new(512.0, 501.0), 5.0);
+        assert_eq!(near.len(), 4);
+        assert!(near.iter().any(|pt| pt.point == DPoint::new(510.0, 500.0)));
+        assert!(index.points_near(Point::new(550.0, 550.0), 5.0).is_empty());
+
+        // the middle of a square's bottom edge is far from its points
+        let segments = index.segments_near(Point::new(505.0, 498.0), 3.0);
+        assert!(segments
+            .iter()
+            .any(|seg| seg.start().point == DPoint::new(500.0, 500.0)));
+        assert!(index
+            .segments_near(Point::new(550.0, 550.0), 3.0)
+            .is_empty());
+    }
+
+    #[test]
+    fn small_glyphs_are_not_indexed() {
+        let cache = HitIndexCache::default();
+        assert!(cache.get(&Arc::new(vec![square(0.0, 0.0)])).is_none());
+
+        let paths = Arc::new((0..100).map(|i| square(i as f64 * 20.0, 0.0)).collect());
+        let index = cache.get(&paths).unwrap();
+        assert!(Arc::ptr_eq(&index, &cache.get(&paths).unwrap()));
+    }
+}
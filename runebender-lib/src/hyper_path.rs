@@ -8,7 +8,7 @@ use spline::{Element, Segment as SplineSegment, SplineSpec};
 use norad::glyph::{Contour, ContourPoint, PointType};
 use norad::{Identifier, Plist};
 
-use super::design_space::DPoint;
+use super::design_space::{DPoint, Precision};
 use super::point::{EntityId, PathPoint};
 use super::point_list::{PathPoints, RawSegment};
 
@@ -67,7 +67,7 @@ impl HyperPath {
         self.solver.segments()
     }
 
-    pub(crate) fn from_norad(src: &norad::glyph::Contour) -> Self {
+    pub(crate) fn from_norad(src: &norad::glyph::Contour, precision: Precision) -> Self {
         let mut points = Vec::new();
         let mut identifier_map = HashMap::new();
         let path_id = EntityId::next();
@@ -85,10 +85,8 @@ impl HyperPath {
         for point in src.points.iter() {
             if matches!(point.typ, norad::PointType::Move) {
                 closed = false;
-                let start = PathPoint::on_curve(
-                    path_id,
-                    DPoint::from_raw((point.x as f64, point.y as f64)),
-                );
+                let start = DPoint::from_file(point.x as f64, point.y as f64, precision);
+                let start = PathPoint::on_curve(path_id, start);
                 add_id(point, &start);
 
                 points.push(start);
@@ -116,13 +114,15 @@ impl HyperPath {
                 let y2 = offcurves.get("y2").unwrap().as_real().unwrap();
                 let auto1 = offcurves.get("auto1").unwrap().as_boolean().unwrap();
                 let auto2 = offcurves.get("auto2").unwrap().as_boolean().unwrap();
-                let p1 = PathPoint::hyper_off_curve(path_id, DPoint::from_raw((x1, y1)), auto1);
-                let p2 = PathPoint::hyper_off_curve(path_id, DPoint::from_raw((x2, y2)), auto2);
+                let p1 = DPoint::from_file(x1, y1, precision);
+                let p2 = DPoint::from_file(x2, y2, precision);
+                let p1 = PathPoint::hyper_off_curve(path_id, p1, auto1);
+                let p2 = PathPoint::hyper_off_curve(path_id, p2, auto2);
                 points.push(p1);
                 points.push(p2);
             }
-            let mut end =
-                PathPoint::on_curve(path_id, DPoint::from_raw((point.x as f64, point.y as f64)));
+            let end = DPoint::from_file(point.x as f64, point.y as f64, precision);
+            let mut end = PathPoint::on_curve(path_id, end);
             add_id(point, &end);
             if point.smooth {
                 end.toggle_type();
@@ -251,8 +251,9 @@ impl HyperPath {
     pub(crate) fn spline_to(&mut self, p3: DPoint, smooth: bool) {
         let prev = self.points.as_slice().last().cloned().unwrap().point;
         let path_id = self.points.id();
-        let p1 = prev.lerp(p3, 1.0 / 3.0);
-        let p2 = prev.lerp(p3, 2.0 / 3.0);
+        // these are placed by the solver
+        let p1 = prev.lerp(p3, 1.0 / 3.0, Precision::FINEST);
+        let p2 = prev.lerp(p3, 2.0 / 3.0, Precision::FINEST);
         self.points
             .points_mut()
             .push(PathPoint::hyper_off_curve(path_id, p1, true));
@@ -267,8 +268,13 @@ impl HyperPath {
         }
     }
 
-    pub(crate) fn split_segment_at_point(&mut self, seg: HyperSegment, t: f64) {
-        let pt = DPoint::from_raw(seg.eval(t));
+    pub(crate) fn split_segment_at_point(
+        &mut self,
+        seg: HyperSegment,
+        t: f64,
+        precision: Precision,
+    ) {
+        let pt = DPoint::from_raw(seg.eval(t), precision);
         let path_id = seg.path_seg.start_id().parent();
         let (pre, post) = match &seg.path_seg {
             RawSegment::Line(p1, p2) => {
@@ -319,6 +325,8 @@ impl HyperPath {
     /// Takes the current solver and updates the position of auto points based
     /// on their position in the resolved spline.
     fn rebuild_spline(&mut self) {
+        // the solver places the auto points, which aren't edited directly
+        let precision = Precision::FINEST;
         let HyperPath { solver, points, .. } = self;
         let spline = solver.solve();
 
@@ -344,18 +352,18 @@ impl HyperPath {
                     ix += 1;
                 } else {
                     assert!(points.get(ix + 1).unwrap().is_off_curve());
-                    points.get_mut(ix).unwrap().point = DPoint::from_raw(p1);
-                    points.get_mut(ix + 1).unwrap().point = DPoint::from_raw(p2);
+                    points.get_mut(ix).unwrap().point = DPoint::from_raw(p1, precision);
+                    points.get_mut(ix + 1).unwrap().point = DPoint::from_raw(p2, precision);
                     ix += 3;
                 }
             } else {
                 let p1 = points.get_mut(ix).unwrap();
                 if p1.is_auto() {
-                    p1.point = DPoint::from_raw(segment.p1);
+                    p1.point = DPoint::from_raw(segment.p1, precision);
                 }
                 let p2 = points.get_mut(ix + 1).unwrap();
                 if p2.is_auto() {
-                    p2.point = DPoint::from_raw(segment.p2);
+                    p2.point = DPoint::from_raw(segment.p2, precision);
                 }
                 ix += 3;
             }
@@ -1,4 +1,25 @@
 //! The core library of the runebender font editor.
+//!
+//! Besides the editor's widgets, this crate can be used by other programs to
+//! edit fonts without opening a window. A [`Workspace`] holds a font, and
+//! each glyph is edited through an [`EditSession`], which holds the glyph's
+//! outline as [`Path`]s:
+//!
+//! ```no_run
+//! use runebender_lib::data::Workspace;
+//! use runebender_lib::{DVec2, Precision};
+//!
+//! let mut workspace = Workspace::open("MyFont.ufo").unwrap();
+//! for name in workspace.sorted_glyph_names() {
+//!     workspace.edit_glyph(&name, |session| {
+//!         session.select_all();
+//!         session.nudge_selection(DVec2::from_raw((10.0, 0.0), Precision::Integer));
+//!     });
+//! }
+//! workspace.save().unwrap();
+//! ```
+//!
+//! [`Workspace`]: data::Workspace
 
 #![allow(clippy::rc_buffer)]
 
@@ -11,34 +32,72 @@ extern crate lopdf;
 #[macro_use]
 mod util;
 
+mod anchor_preview;
 mod app_delegate;
+mod audit;
 mod bez_cache;
+mod charset;
 mod clipboard;
 mod component;
 mod consts;
+mod coverage;
 mod cubic_path;
 mod design_space;
 mod draw;
 mod edit_session;
+mod fea;
+#[cfg(feature = "notify")]
+mod file_watcher;
+mod font_builder;
+mod font_info;
+mod glyph_image;
+mod glyph_lock;
 mod glyph_names;
+mod glyph_rename;
 mod guides;
+mod hit_index;
 mod hyper_path;
+mod mark_color;
+mod nib;
+mod normalize;
+mod opentype;
+mod overlap;
 mod path;
 mod plist;
 mod point;
 mod point_list;
 mod quadrant;
+mod quadratic;
+mod raster;
+mod reference_font;
+mod scripting;
 mod selection;
+mod session_json;
+mod shape_search;
+mod simplify;
+mod snapshot;
 mod tools;
+mod tunni;
 mod undo;
+mod unicode_info;
+mod vcs;
 mod virtual_font;
 
 pub mod data;
 pub mod menus;
 pub mod mouse;
+pub mod settings;
 pub mod theme;
 pub mod widgets;
 
-pub use app_delegate::Delegate;
+pub use app_delegate::{font_path_for_file, Delegate};
 pub use bez_cache::BezCache;
+pub use component::{Component, ComponentParams};
+pub use design_space::{DPoint, DVec2, Precision, ViewPort};
+pub use edit_session::{EditSession, SessionId};
+pub use font_builder::FontBuilder;
+pub use guides::Guide;
+pub use path::{Path, Segment};
+pub use point::{EntityId, PathPoint, PointType};
+pub use selection::Selection;
 pub use util::create_blank_font;
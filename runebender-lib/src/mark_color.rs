@@ -0,0 +1,128 @@
+//! Glyph mark colors, stored in the glyph lib as `public.markColor`.
+
+use druid::{Color, Data};
+use norad::Glyph;
+
+/// The key in the glyph lib for the glyph's mark color.
+const MARK_COLOR_LIB_KEY: &str = "public.markColor";
+
+/// The colors offered when marking glyphs.
+pub const MARK_COLORS: &[(&str, MarkColor)] = &[
+    ("Red", MarkColor::new(1.0, 0.0, 0.0)),
+    ("Orange", MarkColor::new(1.0, 0.5, 0.0)),
+    ("Yellow", MarkColor::new(1.0, 0.9, 0.0)),
+    ("Green", MarkColor::new(0.0, 0.8, 0.2)),
+    ("Blue", MarkColor::new(0.0, 0.5, 1.0)),
+    ("Purple", MarkColor::new(0.6, 0.2, 0.9)),
+    ("Gray", MarkColor::new(0.5, 0.5, 0.5)),
+];
+
+/// Two components closer than this are considered the same.
+const COMPONENT_TOLERANCE: f64 = 0.005;
+
+/// A color used to tag a glyph, usually to track its progress.
+///
+/// The UFO spec stores this as a string of four comma-separated numbers
+/// between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq, Data)]
+pub struct MarkColor {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub a: f64,
+}
+
+impl MarkColor {
+    pub const fn new(r: f64, g: f64, b: f64) -> Self {
+        MarkColor { r, g, b, a: 1.0 }
+    }
+
+    /// Parse a color in the UFO `r,g,b,a` format.
+    pub fn parse(s: &str) -> Option<MarkColor> {
+        let components = s
+            .split(',')
+            .map(|c| {
+                c.trim()
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|c| (0.0..=1.0).contains(c))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        match components.as_slice() {
+            [r, g, b, a] => Some(MarkColor {
+                r: *r,
+                g: *g,
+                b: *b,
+                a: *a,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The mark color of this glyph, if it has a valid one.
+    pub fn for_glyph(glyph: &Glyph) -> Option<MarkColor> {
+        glyph
+            .lib
+            .as_ref()
+            .and_then(|lib| lib.get(MARK_COLOR_LIB_KEY))
+            .and_then(|value| value.as_string())
+            .and_then(MarkColor::parse)
+    }
+
+    /// Set or clear the mark color of this glyph.
+    pub fn apply(color: Option<MarkColor>, glyph: &mut Glyph) {
+        match color {
+            Some(color) => {
+                glyph.lib.get_or_insert_with(Default::default).insert(
+                    MARK_COLOR_LIB_KEY.into(),
+                    plist::Value::String(color.to_string()),
+                );
+            }
+            None => {
+                if let Some(lib) = glyph.lib.as_mut() {
+                    lib.remove(MARK_COLOR_LIB_KEY);
+                }
+            }
+        }
+    }
+
+    /// `true` if the two colors are the same, ignoring rounding differences
+    /// from other editors.
+    pub fn matches(&self, other: &MarkColor) -> bool {
+        (self.r - other.r).abs() < COMPONENT_TOLERANCE
+            && (self.g - other.g).abs() < COMPONENT_TOLERANCE
+            && (self.b - other.b).abs() < COMPONENT_TOLERANCE
+            && (self.a - other.a).abs() < COMPONENT_TOLERANCE
+    }
+
+    pub fn to_color(self) -> Color {
+        Color::rgba(self.r, self.g, self.b, self.a)
+    }
+}
+
+impl std::fmt::Display for MarkColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{},{},{},{}", self.r, self.g, self.b, self.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_apply() {
+        let color = MarkColor::parse("1,0.5, 0,1").unwrap();
+        assert!(color.matches(&MARK_COLORS[1].1));
+        assert!(MarkColor::parse("1,0,0").is_none());
+        assert!(MarkColor::parse("1,0,0,2").is_none());
+        assert_eq!(MarkColor::parse(&color.to_string()), Some(color));
+
+        let mut glyph = Glyph::new_named("a");
+        assert!(MarkColor::for_glyph(&glyph).is_none());
+        MarkColor::apply(Some(color), &mut glyph);
+        assert_eq!(MarkColor::for_glyph(&glyph), Some(color));
+        MarkColor::apply(None, &mut glyph);
+        assert!(MarkColor::for_glyph(&glyph).is_none());
+    }
+}
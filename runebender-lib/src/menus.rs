@@ -3,26 +3,160 @@
 use druid::commands;
 use druid::platform_menus;
 use druid::{
-    Data, Env, FileDialogOptions, FileSpec, KbKey, LocalizedString, Menu, MenuItem, Point, SysMods,
-    WindowId,
+    Data, Env, FileDialogOptions, FileSpec, KbKey, LocalizedString, Menu, MenuItem, Point,
+    Selector, SysMods, WindowId,
 };
+use norad::GlyphName;
 
+use crate::app_delegate::EDIT_GLYPH;
+use crate::charset;
+use crate::clipboard::CoordinateFormat;
 use crate::consts;
-use crate::data::{AppState, EditorState};
+use crate::coverage::CoverageStatus;
+use crate::data::{AppState, EditorState, GlyphSort};
+use crate::draw::ViewMode;
+use crate::font_builder::FontBuilder;
+use crate::guides::{Guide, GuideEdit};
+use crate::mark_color::{MarkColor, MARK_COLORS};
+use crate::point::EntityId;
+use crate::snapshot::Snapshot;
+use crate::widgets::ModalHost;
 
 pub const UFO_FILE_TYPE: FileSpec = FileSpec::new("Font Object", &["ufo"]);
+const COMPILED_FONT_FILE_TYPE: FileSpec = FileSpec::new("Compiled Font", &["otf", "ttf"]);
+const JSON_FILE_TYPE: FileSpec = FileSpec::new("JSON", &["json"]);
 
 /// Context menu's inner menu must have type T == the root app state.
 pub fn make_context_menu(data: &EditorState, pos: Point) -> Menu<AppState> {
-    let mut menu = Menu::empty().entry(
-        MenuItem::new(LocalizedString::new("menu-item-add-guide").with_placeholder("Add Guide"))
+    let session = &data.session;
+    if let Some(id) = session.hit_test_filtered(pos, None, |_| true) {
+        return point_context_menu(data, id);
+    }
+    let guide = session
+        .hit_test_guides(pos, None)
+        .and_then(|id| session.guides.iter().find(|g| g.id == id));
+    if let Some(guide) = guide {
+        return guide_context_menu(guide, pos);
+    }
+    if let Some((seg, t)) = session.hit_test_segments(pos, None) {
+        return segment_context_menu(seg.start_id(), seg.is_line(), t);
+    }
+
+    Menu::empty()
+        .entry(
+            MenuItem::new(LocalizedString::new("common-menu-edit-paste").with_placeholder("Paste"))
+                .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::PASTE_AT_MOUSE)),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-add-guide").with_placeholder("Add Guide"),
+            )
             .on_activate(move |ctx, _, _| ctx.submit_command(consts::cmd::ADD_GUIDE.with(pos))),
-    );
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("common-menu-edit-select-all").with_placeholder("Select All"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::SELECT_ALL)),
+        )
+}
 
-    // only show 'toggle guide' if a guide is selected
-    if data.session.selection.len() == 1 && data.session.selection.iter().all(|s| s.is_guide()) {
-        let id = *data.session.selection.iter().next().unwrap();
-        menu = menu.entry(
+/// The context menu for the point `id`.
+fn point_context_menu(data: &EditorState, id: EntityId) -> Menu<AppState> {
+    let session = &data.session;
+    let point = session.path_point_for_id(id);
+    let on_curve = point.map(|pt| pt.is_on_curve()).unwrap_or(false);
+    let smooth = point.map(|pt| pt.is_smooth()).unwrap_or(false);
+    // a closed path can start at any on-curve point, an open one at either end
+    let can_start = on_curve
+        && session.path_for_point(id).map_or(false, |path| {
+            let points = path.points();
+            path.start_point().id != id
+                && (path.is_closed() || points.last().map(|pt| pt.id) == Some(id))
+        });
+    let locked = session.locked.contains(&id);
+
+    Menu::empty()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-toggle-smooth").with_placeholder("Smooth Point"),
+            )
+            .on_activate(move |ctx, _, _| {
+                let args = consts::cmd::SetPointTypeArgs {
+                    id,
+                    smooth: !smooth,
+                };
+                ctx.submit_command(consts::cmd::SET_POINT_TYPE.with(args))
+            })
+            .selected_if(move |_, _| smooth)
+            .enabled(on_curve && !locked),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-set-start-point")
+                    .with_placeholder("Set as Start Point"),
+            )
+            .on_activate(move |ctx, _, _| ctx.submit_command(consts::cmd::SET_START_POINT.with(id)))
+            .enabled(can_start),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-delete-point").with_placeholder("Delete Point"),
+            )
+            .on_activate(move |ctx, _, _| ctx.submit_command(consts::cmd::DELETE_ITEM.with(id)))
+            .enabled(!locked),
+        )
+}
+
+/// The context menu for the segment starting at the point `start`, which
+/// was clicked at `t`.
+fn segment_context_menu(start: EntityId, is_line: bool, t: f64) -> Menu<AppState> {
+    let (convert_key, convert_title) = if is_line {
+        ("menu-item-convert-to-curve", "Convert to Curve")
+    } else {
+        ("menu-item-convert-to-line", "Convert to Line")
+    };
+    Menu::empty()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-add-point").with_placeholder("Add Point"),
+            )
+            .on_activate(move |ctx, _, _| {
+                let args = consts::cmd::AddPointOnSegmentArgs { start, t };
+                ctx.submit_command(consts::cmd::ADD_POINT_ON_SEGMENT.with(args))
+            }),
+        )
+        .entry(
+            MenuItem::new(LocalizedString::new(convert_key).with_placeholder(convert_title))
+                .on_activate(move |ctx, _, _| {
+                    let args = consts::cmd::SetSegmentTypeArgs {
+                        start,
+                        line: !is_line,
+                    };
+                    ctx.submit_command(consts::cmd::SET_SEGMENT_TYPE.with(args))
+                }),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-reverse-contour")
+                    .with_placeholder("Reverse Contour"),
+            )
+            .on_activate(move |ctx, _, _| {
+                ctx.submit_command(consts::cmd::REVERSE_CONTOUR.with(start))
+            }),
+        )
+}
+
+/// The context menu for a guide, which was clicked at `pos`.
+fn guide_context_menu(guide: &Guide, pos: Point) -> Menu<AppState> {
+    let id = guide.id;
+    let name = guide.name.clone().unwrap_or_default();
+    let locked = guide.locked;
+    Menu::empty()
+        .entry(
             MenuItem::new(
                 LocalizedString::new("menu-item-toggle-guide")
                     .with_placeholder("Toggle Guide Orientation"),
@@ -30,10 +164,177 @@ pub fn make_context_menu(data: &EditorState, pos: Point) -> Menu<AppState> {
             .on_activate(move |ctx, _, _| {
                 let args = consts::cmd::ToggleGuideCmdArgs { id, pos };
                 ctx.submit_command(consts::cmd::TOGGLE_GUIDE.with(args))
+            })
+            .enabled(!locked),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-name-guide").with_placeholder("Name Guide…"),
+            )
+            .on_activate(move |ctx, _, _| {
+                let name = name.clone();
+                ctx.submit_command(ModalHost::make_modal_command(move || {
+                    crate::widgets::guide_name_panel(id, name)
+                }))
+            }),
+        )
+        .entry(guide_color_menu(id, guide.color))
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-lock-guide").with_placeholder("Lock Guide"),
+            )
+            .on_activate(move |ctx, _, _| {
+                ctx.submit_command(consts::cmd::EDIT_GUIDE.with(consts::cmd::EditGuideArgs {
+                    id,
+                    edit: GuideEdit::ToggleLocked,
+                }))
+            })
+            .selected_if(move |_, _| locked),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-delete-guide").with_placeholder("Delete Guide"),
+            )
+            .on_activate(move |ctx, _, _| ctx.submit_command(consts::cmd::DELETE_ITEM.with(id)))
+            .enabled(!locked),
+        )
+}
+
+/// The context menu for a cell in the glyph grid.
+///
+/// The glyph `name` should already be selected; deleting and marking apply
+/// to the selected glyph.
+pub fn make_grid_context_menu(name: &GlyphName) -> Menu<AppState> {
+    let item = |key: &'static str, title: &'static str, cmd: Selector<GlyphName>| {
+        let name = name.clone();
+        MenuItem::new(LocalizedString::new(key).with_placeholder(title))
+            .on_activate(move |ctx, _, _| ctx.submit_command(cmd.with(name.clone())))
+    };
+    let rename_name = name.clone();
+    let duplicate_name = name.clone();
+    Menu::empty()
+        .entry(item("menu-item-open-glyph", "Open", EDIT_GLYPH))
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-rename-glyph").with_placeholder("Rename…"),
+            )
+            .on_activate(move |ctx, _, _| {
+                let name = rename_name.clone();
+                ctx.submit_command(ModalHost::make_modal_command(move || {
+                    crate::widgets::rename_glyph_panel(name.clone(), name)
+                }))
+            }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-duplicate-glyph")
+                    .with_placeholder("Duplicate Glyph…"),
+            )
+            .on_activate(move |ctx, _, _| {
+                let name = duplicate_name.clone();
+                ctx.submit_command(ModalHost::make_modal_command(move || {
+                    crate::widgets::duplicate_glyph_panel(name)
+                }))
             }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-delete-glyph").with_placeholder("Delete Glyph"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::DELETE_SELECTED_GLYPH)),
+        )
+        .entry(item(
+            "menu-item-copy-glyph",
+            "Copy",
+            consts::cmd::COPY_GLYPH,
+        ))
+        .separator()
+        .entry(mark_color_menu())
+        .separator()
+        .entry(item(
+            "menu-item-new-composite-glyph",
+            "New Composite from This Base",
+            consts::cmd::NEW_COMPOSITE_GLYPH,
+        ))
+}
+
+/// The colors a guide can be drawn in.
+fn guide_color_menu(id: EntityId, current: Option<MarkColor>) -> Menu<AppState> {
+    let item = move |key: &'static str, name: &'static str, color: Option<MarkColor>| {
+        MenuItem::new(LocalizedString::new(key).with_placeholder(name))
+            .on_activate(move |ctx, _, _| {
+                ctx.submit_command(consts::cmd::EDIT_GUIDE.with(consts::cmd::EditGuideArgs {
+                    id,
+                    edit: GuideEdit::SetColor(color),
+                }))
+            })
+            .selected_if(move |_, _| current == color)
+    };
+    let mut menu =
+        Menu::new(LocalizedString::new("menu-guide-color-menu").with_placeholder("Guide Color"));
+    for (title, color) in MARK_COLORS {
+        menu = menu.entry(item(*title, *title, Some(*color)));
+    }
+    menu.separator()
+        .entry(item("menu-item-guide-default-color", "Default", None))
+}
+
+/// The dropdown for changing the type of an on-curve point.
+pub fn make_point_type_menu(id: EntityId, smooth: bool) -> Menu<AppState> {
+    let item = |key: &'static str, name: &'static str, new_smooth: bool| {
+        MenuItem::new(LocalizedString::new(key).with_placeholder(name))
+            .on_activate(move |ctx, _, _| {
+                let args = consts::cmd::SetPointTypeArgs {
+                    id,
+                    smooth: new_smooth,
+                };
+                ctx.submit_command(consts::cmd::SET_POINT_TYPE.with(args))
+            })
+            .selected_if(move |_, _| smooth == new_smooth)
+    };
+    Menu::empty()
+        .entry(item("menu-item-corner-point", "Corner", false))
+        .entry(item("menu-item-smooth-point", "Smooth", true))
+}
+
+/// The fonts that have been opened recently.
+fn open_recent_menu(data: &AppState) -> Menu<AppState> {
+    let mut menu =
+        Menu::new(LocalizedString::new("menu-open-recent-menu").with_placeholder("Open Recent"));
+    for path in &data.settings.recent_files {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        let path = path.clone();
+        menu = menu.entry(
+            MenuItem::new(LocalizedString::new("menu-item-recent-file").with_placeholder(name))
+                .on_activate(move |ctx, _, _| {
+                    ctx.submit_command(consts::cmd::OPEN_RECENT.with(path.clone()))
+                }),
         );
     }
-    menu
+    if !data.settings.recent_files.is_empty() {
+        menu = menu.separator();
+    }
+    menu.entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-clear-recent").with_placeholder("Clear Menu"),
+        )
+        .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::CLEAR_RECENT_FILES))
+        .enabled_if(|data: &AppState, _| !data.settings.recent_files.is_empty()),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-reopen-last-font")
+                .with_placeholder("Reopen Last Font on Launch"),
+        )
+        .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_REOPEN_LAST_FONT))
+        .selected_if(|data: &AppState, _| data.settings.reopen_last_font),
+    )
+    .refresh_on(|old, new, _| old.settings.recent_files != new.settings.recent_files)
 }
 
 /// The main window/app menu.
@@ -55,7 +356,18 @@ pub fn make_menu(_window: Option<WindowId>, data: &AppState, _: &Env) -> Menu<Ap
 fn file_menu(data: &AppState) -> Menu<AppState> {
     let has_path = data.workspace.font.path.is_some();
     let mut menu = Menu::new(LocalizedString::new("common-menu-file-menu"))
-        .entry(platform_menus::mac::file::new_file().enabled(false))
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("common-menu-file-new").with_placeholder("New Font…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::new_font_panel,
+                ))
+            })
+            .hotkey(SysMods::Cmd, "n"),
+        )
+        .entry(new_from_template_menu())
         .entry(
             MenuItem::new(LocalizedString::new("common-menu-file-open"))
                 .on_activate(|ctx, _, _| {
@@ -66,6 +378,7 @@ fn file_menu(data: &AppState) -> Menu<AppState> {
                 })
                 .hotkey(SysMods::Cmd, "o"),
         )
+        .entry(open_recent_menu(data))
         .separator()
         .entry(platform_menus::mac::file::close());
     if has_path {
@@ -91,19 +404,255 @@ fn file_menu(data: &AppState) -> Menu<AppState> {
                 .hotkey(SysMods::Cmd, "s"),
         );
     }
-    menu.separator()
-        .entry(platform_menus::mac::file::page_setup().enabled(false))
-        .entry(platform_menus::mac::file::print().enabled(false))
+    menu.entry(
+        MenuItem::new(LocalizedString::new("menu-item-save-copy").with_placeholder("Save a Copy…"))
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(
+                    commands::SHOW_SAVE_PANEL.with(
+                        FileDialogOptions::new()
+                            .allowed_types(vec![UFO_FILE_TYPE])
+                            .accept_command(consts::cmd::SAVE_COPY),
+                    ),
+                )
+            })
+            .hotkey(SysMods::AltCmdShift, "S"),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-revert").with_placeholder("Revert to Saved…"),
+        )
+        .on_activate(|ctx, _, _| {
+            ctx.submit_command(ModalHost::make_modal_command(crate::widgets::revert_panel))
+        })
+        .enabled_if(|data: &AppState, _| {
+            data.workspace.font.path.is_some() && data.workspace.is_dirty()
+        }),
+    )
+    .entry(
+        MenuItem::new(LocalizedString::new("menu-item-commit").with_placeholder("Commit…"))
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(crate::widgets::commit_panel))
+            })
+            .enabled_if(|data: &AppState, _| data.workspace.vcs.is_some()),
+    )
+    .separator()
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-export-remove-overlaps")
+                .with_placeholder("Export with Overlaps Removed..."),
+        )
+        .on_activate(|ctx, _, _| {
+            ctx.submit_command(
+                commands::SHOW_SAVE_PANEL.with(
+                    FileDialogOptions::new()
+                        .allowed_types(vec![UFO_FILE_TYPE])
+                        .accept_command(consts::cmd::EXPORT_REMOVE_OVERLAPS),
+                ),
+            )
+        }),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-export-normalized")
+                .with_placeholder("Export Normalized UFO…"),
+        )
+        .on_activate(|ctx, _, _| {
+            ctx.submit_command(
+                commands::SHOW_SAVE_PANEL.with(
+                    FileDialogOptions::new()
+                        .allowed_types(vec![UFO_FILE_TYPE])
+                        .accept_command(consts::cmd::EXPORT_NORMALIZED),
+                ),
+            )
+        }),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-export-glyph-image")
+                .with_placeholder("Export Glyph Image…"),
+        )
+        .on_activate(|ctx, _, _| {
+            ctx.submit_command(ModalHost::make_modal_command(
+                crate::widgets::export_glyph_image,
+            ))
+        })
+        .enabled_if(|data: &AppState, _| data.workspace.image_export_glyph().is_some()),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-export-session-json")
+                .with_placeholder("Export Glyph as JSON…"),
+        )
+        .on_activate(|ctx, data: &mut AppState, _| {
+            let name = match data.workspace.image_export_glyph() {
+                Some(name) => name,
+                None => return,
+            };
+            ctx.submit_command(
+                commands::SHOW_SAVE_PANEL.with(
+                    FileDialogOptions::new()
+                        .allowed_types(vec![JSON_FILE_TYPE])
+                        .default_name(format!("{}.json", name))
+                        .accept_command(consts::cmd::EXPORT_SESSION_JSON),
+                ),
+            )
+        })
+        .enabled_if(|data: &AppState, _| data.workspace.image_export_glyph().is_some()),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-import-session-json")
+                .with_placeholder("Import Glyph from JSON…"),
+        )
+        .on_activate(|ctx, _, _| {
+            ctx.submit_command(
+                commands::SHOW_OPEN_PANEL.with(
+                    FileDialogOptions::new()
+                        .allowed_types(vec![JSON_FILE_TYPE])
+                        .accept_command(consts::cmd::IMPORT_SESSION_JSON),
+                ),
+            )
+        })
+        .enabled_if(|data: &AppState, _| data.workspace.image_export_glyph().is_some()),
+    )
+    .separator()
+    .entry(
+        MenuItem::new(LocalizedString::new("menu-item-run-script").with_placeholder("Run Script…"))
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(
+                    commands::SHOW_OPEN_PANEL.with(crate::widgets::run_script_dialog()),
+                )
+            }),
+    )
+    .separator()
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-load-reference-font")
+                .with_placeholder("Load Reference Font…"),
+        )
+        .on_activate(|ctx, _, _| {
+            ctx.submit_command(
+                commands::SHOW_OPEN_PANEL.with(
+                    FileDialogOptions::new()
+                        .allowed_types(vec![UFO_FILE_TYPE, COMPILED_FONT_FILE_TYPE])
+                        .accept_command(consts::cmd::LOAD_REFERENCE_FONT),
+                ),
+            )
+        }),
+    )
+    .entry(
+        MenuItem::new(
+            LocalizedString::new("menu-item-clear-reference-font")
+                .with_placeholder("Clear Reference Font"),
+        )
+        .on_activate(|_, data: &mut AppState, _| data.workspace.reference_font = None)
+        .enabled_if(|data: &AppState, _| data.workspace.reference_font.is_some()),
+    )
+    .separator()
+    .entry(platform_menus::mac::file::page_setup().enabled(false))
+    .entry(platform_menus::mac::file::print().enabled(false))
+}
+
+/// Undo or Redo, named for the edit in the active editor that it would
+/// affect, like "Undo Nudge", and disabled when there is none.
+fn undo_item(redo: bool) -> MenuItem<AppState> {
+    let description = move |data: &AppState| {
+        let labels = data.workspace.active_undo_labels()?;
+        if redo {
+            labels.redo.clone()
+        } else {
+            labels.undo.clone()
+        }
+    };
+    let verb = if redo { "Redo" } else { "Undo" };
+    let item = MenuItem::new(move |data: &AppState, _: &Env| match description(data) {
+        Some(edit) => format!("{} {}", verb, edit),
+        None => verb.to_string(),
+    })
+    .enabled_if(move |data: &AppState, _| description(data).is_some());
+    if redo {
+        item.on_activate(|ctx, _, _| ctx.submit_command(commands::REDO))
+            .hotkey(SysMods::CmdShift, "Z")
+    } else {
+        item.on_activate(|ctx, _, _| ctx.submit_command(commands::UNDO))
+            .hotkey(SysMods::Cmd, "z")
+    }
 }
 
-fn edit_menu<T: Data>() -> Menu<T> {
+fn edit_menu() -> Menu<AppState> {
     Menu::new(LocalizedString::new("common-menu-edit-menu"))
-        .entry(platform_menus::common::undo())
-        .entry(platform_menus::common::redo())
+        .entry(undo_item(false))
+        .entry(undo_item(true))
         .separator()
         .entry(platform_menus::common::cut().enabled(false))
         .entry(platform_menus::common::copy())
         .entry(platform_menus::common::paste())
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-paste-in-place").with_placeholder("Paste in Place"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::PASTE_IN_PLACE))
+            .hotkey(SysMods::CmdShift, "V"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-paste-at-mouse").with_placeholder("Paste at Mouse"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::PASTE_AT_MOUSE))
+            .hotkey(SysMods::AltCmd, "v"),
+        )
+        .entry(copy_coordinates_menu())
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-paste-coordinates")
+                    .with_placeholder("Paste Coordinates as Path"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::PASTE_COORDINATES)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-paste-offset").with_placeholder("Paste Offset…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::paste_offset_panel,
+                ))
+            }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-nudge-amounts").with_placeholder("Nudge Amounts…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::nudge_amounts_panel,
+                ))
+            }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-fractional-coordinates")
+                    .with_placeholder("Fractional Coordinates"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_FRACTIONAL_COORDINATES))
+            .selected_if(|data: &AppState, _| data.workspace.precision.is_fractional()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-keyboard-editing")
+                    .with_placeholder("Keyboard Editing"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_KEYBOARD_EDITING))
+            .selected_if(|data: &AppState, _| data.workspace.keyboard_editing),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-flush-on-close")
+                    .with_placeholder("Update Font When Closing Glyphs"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_FLUSH_ON_CLOSE))
+            .selected_if(|data: &AppState, _| data.settings.flush_on_close),
+        )
         .entry(
             MenuItem::new(LocalizedString::new("menu-item-delete").with_placeholder("Delete"))
                 .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::DELETE)),
@@ -123,6 +672,85 @@ fn edit_menu<T: Data>() -> Menu<T> {
             .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::DESELECT_ALL))
             .hotkey(SysMods::AltCmd, "A"),
         )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-select-aligned-vertically")
+                    .with_placeholder("Select Aligned Points (Same X)"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(consts::cmd::SELECT_ALIGNED_POINTS.with(true))
+            }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-select-aligned-horizontally")
+                    .with_placeholder("Select Aligned Points (Same Y)"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(consts::cmd::SELECT_ALIGNED_POINTS.with(false))
+            }),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-flip-horizontal")
+                    .with_placeholder("Flip Horizontally"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::FLIP_HORIZONTAL)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-flip-vertical").with_placeholder("Flip Vertically"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::FLIP_VERTICAL)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-transform-again")
+                    .with_placeholder("Transform Again"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TRANSFORM_AGAIN))
+            .hotkey(SysMods::Cmd, "d"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-duplicate-and-transform-again")
+                    .with_placeholder("Duplicate and Transform Again"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::DUPLICATE_AND_TRANSFORM_AGAIN))
+            .hotkey(SysMods::CmdShift, "D"),
+        )
+}
+
+fn copy_coordinates_menu<T: Data>() -> Menu<T> {
+    let formats = [
+        (
+            "menu-item-copy-coordinates-text",
+            "As Text",
+            CoordinateFormat::Text,
+        ),
+        (
+            "menu-item-copy-coordinates-python",
+            "As Python List",
+            CoordinateFormat::Python,
+        ),
+        (
+            "menu-item-copy-coordinates-json",
+            "As JSON",
+            CoordinateFormat::Json,
+        ),
+    ];
+    let mut menu = Menu::new(
+        LocalizedString::new("menu-copy-coordinates-menu").with_placeholder("Copy Coordinates"),
+    );
+    for &(key, title, format) in formats.iter() {
+        menu = menu.entry(
+            MenuItem::new(LocalizedString::new(key).with_placeholder(title)).on_activate(
+                move |ctx, _, _| ctx.submit_command(consts::cmd::COPY_COORDINATES.with(format)),
+            ),
+        );
+    }
+    menu
 }
 
 fn view_menu<T: Data>() -> Menu<T> {
@@ -148,6 +776,103 @@ fn view_menu<T: Data>() -> Menu<T> {
             .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::ZOOM_DEFAULT))
             .hotkey(SysMods::Cmd, "0"),
         )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-zoom-options").with_placeholder("Zoom Options…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::zoom_options_panel,
+                ))
+            }),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-view-filled").with_placeholder("Filled Preview"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(consts::cmd::SET_VIEW_MODE.with(ViewMode::Filled))
+            })
+            .hotkey(SysMods::CmdShift, "F"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-view-outline").with_placeholder("Outline"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(consts::cmd::SET_VIEW_MODE.with(ViewMode::Outline))
+            })
+            .hotkey(SysMods::CmdShift, "O"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-view-wireframe").with_placeholder("Wireframe"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(consts::cmd::SET_VIEW_MODE.with(ViewMode::Wireframe))
+            })
+            .hotkey(SysMods::CmdShift, "W"),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-snap-to-zones")
+                    .with_placeholder("Snap to Alignment Zones"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_ZONE_SNAPPING)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-show-tunni-lines")
+                    .with_placeholder("Show Tunni Lines"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_TUNNI_LINES)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-show-loupe")
+                    .with_placeholder("Show Loupe While Dragging"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_LOUPE)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-show-contour-list")
+                    .with_placeholder("Show Contour List"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_CONTOUR_LIST)),
+        )
+        .entry(pixel_preview_menu())
+}
+
+/// Sizes, in pixels per em, that the pixel preview can be shown at.
+const PIXEL_PREVIEW_SIZES: &[f64] = &[
+    8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 16.0, 18.0, 20.0, 24.0, 32.0,
+];
+
+fn pixel_preview_menu<T: Data>() -> Menu<T> {
+    let mut menu = Menu::new(
+        LocalizedString::new("menu-pixel-preview-menu").with_placeholder("Pixel Preview"),
+    )
+    .entry(
+        MenuItem::new(LocalizedString::new("menu-item-pixel-preview-off").with_placeholder("Off"))
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::SET_PIXEL_PREVIEW.with(None))),
+    )
+    .separator();
+    for ppem in PIXEL_PREVIEW_SIZES {
+        let ppem = *ppem;
+        let title = format!("{} ppem", ppem);
+        menu = menu.entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-pixel-preview-size").with_placeholder(title),
+            )
+            .on_activate(move |ctx, _, _| {
+                ctx.submit_command(consts::cmd::SET_PIXEL_PREVIEW.with(Some(ppem)))
+            }),
+        );
+    }
+    menu
 }
 
 fn glyph_menu(_data: &AppState) -> Menu<AppState> {
@@ -159,6 +884,63 @@ fn glyph_menu(_data: &AppState) -> Menu<AppState> {
             .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::NEW_GLYPH))
             .hotkey(SysMods::CmdShift, "N"),
         )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-add-glyphs").with_placeholder("Add Glyphs…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(crate::widgets::add_glyphs))
+            }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-rename-glyphs").with_placeholder("Rename Glyphs…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(crate::widgets::batch_rename))
+            }),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-go-to-glyph").with_placeholder("Go to Glyph…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::glyph_switcher,
+                ))
+            })
+            .hotkey(SysMods::Cmd, "j"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-next-glyph").with_placeholder("Next Glyph"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::NEXT_GLYPH))
+            .hotkey(SysMods::Cmd, "]")
+            .enabled_if(|data: &AppState, _| data.workspace.active_tab.is_some()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-previous-glyph").with_placeholder("Previous Glyph"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::PREVIOUS_GLYPH))
+            .hotkey(SysMods::Cmd, "[")
+            .enabled_if(|data: &AppState, _| data.workspace.active_tab.is_some()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-duplicate-glyph")
+                    .with_placeholder("Duplicate Glyph…"),
+            )
+            .on_activate(|ctx, data: &mut AppState, _| {
+                if let Some(name) = data.workspace.selected.clone() {
+                    ctx.submit_command(ModalHost::make_modal_command(move || {
+                        crate::widgets::duplicate_glyph_panel(name)
+                    }))
+                }
+            })
+            .enabled_if(|data: &AppState, _| data.workspace.selected.is_some()),
+        )
         .entry(
             MenuItem::new(
                 LocalizedString::new("menu-item-delete-glyph").with_placeholder("Delete Glyph"),
@@ -175,9 +957,212 @@ fn glyph_menu(_data: &AppState) -> Menu<AppState> {
             .hotkey(SysMods::CmdShift, "C")
             .enabled(false),
         )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-save-snapshot").with_placeholder("Save Snapshot"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(consts::cmd::SAVE_SNAPSHOT.with(Snapshot::default_name()))
+            })
+            .hotkey(SysMods::AltCmd, "s")
+            .enabled_if(|data: &AppState, _| data.workspace.active_tab.is_some()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-snapshots").with_placeholder("Snapshots…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::snapshots_panel,
+                ))
+            })
+            .enabled_if(|data: &AppState, _| data.workspace.active_tab.is_some()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-component-inspector")
+                    .with_placeholder("Component Inspector…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::component_inspector,
+                ))
+            })
+            .enabled_if(|data: &AppState, _| data.workspace.active_tab.is_some()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-unicode-inspector")
+                    .with_placeholder("Unicode Info…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::unicode_inspector,
+                ))
+            })
+            .enabled_if(|data: &AppState, _| data.workspace.selected.is_some()),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-anchor-preview")
+                    .with_placeholder("Anchor Preview…"),
+            )
+            .on_activate(|ctx, _, _| {
+                ctx.submit_command(ModalHost::make_modal_command(
+                    crate::widgets::anchor_preview_panel,
+                ))
+            })
+            .enabled_if(|data: &AppState, _| data.workspace.active_tab.is_some()),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(LocalizedString::new("menu-item-lock-glyph").with_placeholder("Locked"))
+                .on_activate(|_, data: &mut AppState, _| {
+                    if let Some(name) = data.workspace.selected.clone() {
+                        let locked = data.workspace.is_glyph_locked(&name);
+                        data.workspace.set_glyph_locked(&name, !locked);
+                    }
+                })
+                .selected_if(|data: &AppState, _| {
+                    data.workspace
+                        .selected
+                        .as_ref()
+                        .map(|name| data.workspace.is_glyph_locked(name))
+                        .unwrap_or(false)
+                })
+                .enabled_if(|data: &AppState, _| data.workspace.selected.is_some()),
+        )
+        .entry(mark_color_menu())
+        .entry(mark_filter_menu())
+        .entry(coverage_filter_menu())
+        .entry(sort_glyphs_menu())
         .refresh_on(|old, new, _| old.workspace.selected != new.workspace.selected)
 }
 
+/// A new font for each of our character set templates, with placeholder
+/// outlines so that text can be tried out straight away.
+fn new_from_template_menu() -> Menu<AppState> {
+    let mut menu = Menu::new(
+        LocalizedString::new("menu-new-from-template").with_placeholder("New Font from Template"),
+    );
+    for template in charset::TEMPLATES {
+        let spec = template.to_spec();
+        menu = menu.entry(
+            MenuItem::new(LocalizedString::new(template.name).with_placeholder(template.name))
+                .on_activate(
+                    move |ctx, _, _| match FontBuilder::new().with_glyph_list(&spec) {
+                        Ok(font) => ctx.submit_command(
+                            consts::cmd::NEW_FONT.with(font.with_placeholder_outlines(true)),
+                        ),
+                        Err(item) => log::warn!("bad template item '{}'", item),
+                    },
+                ),
+        );
+    }
+    menu
+}
+
+fn mark_color_menu() -> Menu<AppState> {
+    let mut menu =
+        Menu::new(LocalizedString::new("menu-mark-color-menu").with_placeholder("Mark Color"));
+    for (title, color) in MARK_COLORS {
+        let color = *color;
+        menu = menu.entry(
+            MenuItem::new(LocalizedString::new(*title).with_placeholder(*title))
+                .on_activate(move |_, data: &mut AppState, _| {
+                    if let Some(name) = data.workspace.selected.clone() {
+                        data.workspace.set_mark_color(&name, Some(color));
+                    }
+                })
+                .enabled_if(selected_is_unlocked),
+        );
+    }
+    menu.separator().entry(
+        MenuItem::new(LocalizedString::new("menu-item-clear-mark-color").with_placeholder("None"))
+            .on_activate(|_, data: &mut AppState, _| {
+                if let Some(name) = data.workspace.selected.clone() {
+                    data.workspace.set_mark_color(&name, None);
+                }
+            })
+            .enabled_if(selected_is_unlocked),
+    )
+}
+
+/// `true` if a glyph is selected and it isn't locked.
+fn selected_is_unlocked(data: &AppState, _: &Env) -> bool {
+    data.workspace
+        .selected
+        .as_ref()
+        .map(|name| !data.workspace.is_glyph_locked(name))
+        .unwrap_or(false)
+}
+
+fn mark_filter_menu() -> Menu<AppState> {
+    let mut menu = Menu::new(
+        LocalizedString::new("menu-mark-filter-menu").with_placeholder("Show Glyphs Marked"),
+    )
+    .entry(
+        MenuItem::new(LocalizedString::new("menu-item-show-all-glyphs").with_placeholder("All"))
+            .on_activate(|_, data: &mut AppState, _| data.workspace.mark_filter = None)
+            .selected_if(|data: &AppState, _| data.workspace.mark_filter.is_none()),
+    )
+    .separator();
+    for (title, color) in MARK_COLORS {
+        let color = *color;
+        menu = menu.entry(
+            MenuItem::new(LocalizedString::new(*title).with_placeholder(*title))
+                .on_activate(move |_, data: &mut AppState, _| {
+                    data.workspace.mark_filter = Some(color)
+                })
+                .selected_if(move |data: &AppState, _| data.workspace.mark_filter == Some(color)),
+        );
+    }
+    menu
+}
+
+fn coverage_filter_menu() -> Menu<AppState> {
+    fn filter_item(title: &'static str, filter: Option<CoverageStatus>) -> MenuItem<AppState> {
+        MenuItem::new(LocalizedString::new(title).with_placeholder(title))
+            .on_activate(move |_, data: &mut AppState, _| data.workspace.coverage.filter = filter)
+            .selected_if(move |data: &AppState, _| data.workspace.coverage.filter == filter)
+            .enabled_if(|data: &AppState, _| data.workspace.coverage.report.is_some())
+    }
+
+    Menu::new(
+        LocalizedString::new("menu-coverage-filter-menu")
+            .with_placeholder("Show Glyphs by Coverage"),
+    )
+    .entry(filter_item("All Glyphs", None))
+    .separator()
+    .entry(filter_item("Empty", Some(CoverageStatus::Empty)))
+    .entry(filter_item("Complete", Some(CoverageStatus::Complete)))
+}
+
+fn sort_glyphs_menu() -> Menu<AppState> {
+    fn sort_item(title: &'static str, sort: GlyphSort) -> MenuItem<AppState> {
+        MenuItem::new(LocalizedString::new(title).with_placeholder(title))
+            .on_activate(move |_, data: &mut AppState, _| data.workspace.glyph_sort = sort)
+            .selected_if(move |data: &AppState, _| data.workspace.glyph_sort == sort)
+    }
+
+    Menu::new(LocalizedString::new("menu-sort-glyphs-menu").with_placeholder("Sort Glyphs"))
+        .entry(sort_item("Glyph Order", GlyphSort::GlyphOrder))
+        .entry(sort_item("Unicode", GlyphSort::Codepoint))
+        .entry(sort_item("Glyph Name", GlyphSort::Name))
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-group-glyphs")
+                    .with_placeholder("Group by Script and Category"),
+            )
+            .on_activate(|_, data: &mut AppState, _| {
+                data.workspace.group_glyphs = !data.workspace.group_glyphs
+            })
+            .selected_if(|data: &AppState, _| data.workspace.group_glyphs),
+        )
+}
+
 fn paths_menu<T: Data>() -> Menu<T> {
     Menu::new(LocalizedString::new("menu-paths-menu").with_placeholder("Paths"))
         .entry(
@@ -192,6 +1177,40 @@ fn paths_menu<T: Data>() -> Menu<T> {
                 )
             }),
         )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-join-contours").with_placeholder("Join Contours"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::JOIN_CONTOURS)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-break-contour").with_placeholder("Break Contour"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::BREAK_CONTOURS)),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-lock-points")
+                    .with_placeholder("Lock/Unlock Points"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::TOGGLE_LOCK_SELECTION))
+            .hotkey(SysMods::Cmd, "l"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-lock-contours").with_placeholder("Lock Contours"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::LOCK_CONTOURS)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-unlock-all").with_placeholder("Unlock All"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::UNLOCK_ALL)),
+        )
+        .separator()
         .entry(
             MenuItem::new(
                 LocalizedString::new("menu-item-align-selection")
@@ -200,14 +1219,78 @@ fn paths_menu<T: Data>() -> Menu<T> {
             .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::ALIGN_SELECTION))
             .hotkey(SysMods::CmdShift, "A"),
         )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-harmonize-handles")
+                    .with_placeholder("Harmonize Handles"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::HARMONIZE_HANDLES)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-balance-handles")
+                    .with_placeholder("Balance Handles"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::BALANCE_HANDLES)),
+        )
+        .entry(
+            MenuItem::new(LocalizedString::new("menu-item-simplify").with_placeholder("Simplify…"))
+                .on_activate(|ctx, _, _| {
+                    ctx.submit_command(ModalHost::make_modal_command(
+                        crate::widgets::simplify_panel,
+                    ))
+                }),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-expand-stroke").with_placeholder("Expand Stroke"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::EXPAND_STROKE)),
+        )
+        .entry(
+            MenuItem::new(LocalizedString::new("menu-item-nib").with_placeholder("Nib…"))
+                .on_activate(|ctx, _, _| {
+                    ctx.submit_command(ModalHost::make_modal_command(crate::widgets::nib_panel))
+                }),
+        )
+        .separator()
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-find-shape").with_placeholder("Find Shape"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::FIND_SHAPE)),
+        )
 }
 
 fn window_menu(_app_state: &AppState) -> Menu<AppState> {
-    Menu::new(LocalizedString::new("menu-window-menu").with_placeholder("Window")).entry(
-        MenuItem::new(
-            LocalizedString::new("menu-item-new-preview").with_placeholder("New Preview"),
+    Menu::new(LocalizedString::new("menu-window-menu").with_placeholder("Window"))
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-new-preview").with_placeholder("New Preview"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::NEW_PREVIEW_WINDOW))
+            .hotkey(SysMods::AltCmd, "p"),
+        )
+        .entry(
+            MenuItem::new(LocalizedString::new("menu-item-features").with_placeholder("Features"))
+                .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::SHOW_FEATURES_WINDOW))
+                .hotkey(SysMods::AltCmd, "f"),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-script-console").with_placeholder("Script Console"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::SHOW_SCRIPT_CONSOLE)),
+        )
+        .entry(
+            MenuItem::new(
+                LocalizedString::new("menu-item-font-audit").with_placeholder("Font Audit"),
+            )
+            .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::SHOW_AUDIT_WINDOW)),
+        )
+        .entry(
+            MenuItem::new(LocalizedString::new("menu-item-coverage").with_placeholder("Coverage"))
+                .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::SHOW_COVERAGE_WINDOW)),
         )
-        .on_activate(|ctx, _, _| ctx.submit_command(consts::cmd::NEW_PREVIEW_WINDOW))
-        .hotkey(SysMods::AltCmd, "p"),
-    )
 }
@@ -0,0 +1,375 @@
+//! Expanding a skeleton path into the outline drawn by a calligraphic nib.
+//!
+//! Each segment of the skeleton is swept by the nib separately, and the
+//! pieces are then merged. For a flat nib the edges of each piece are exact
+//! copies of the skeleton, moved to the ends of the nib; for an oval nib
+//! they are approximate offsets, found in the space where the nib is a unit
+//! circle.
+
+use druid::kurbo::{
+    Affine, BezPath, CubicBez, ParamCurve, ParamCurveDeriv, PathSeg, Point, Shape, Vec2,
+};
+use druid::{Data, Lens};
+use norad::glyph::Contour;
+
+use crate::design_space::Precision;
+
+/// The number of pieces each curve is split into when offsetting it for an
+/// oval nib.
+const OVAL_SUBDIVISIONS: usize = 8;
+
+/// The length of the handles of a cubic quarter circle with unit radius.
+const QUARTER_CIRCLE_HANDLE: f64 = 0.552_284_749_8;
+
+/// Pieces that enclose less area than this are dropped.
+const MIN_AREA: f64 = 1e-3;
+
+/// The shape of a nib.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Data)]
+pub enum NibShape {
+    /// A broad, chisel-edged nib with no thickness.
+    Flat,
+    /// An elliptical nib, like a brush held at an angle.
+    Oval,
+}
+
+/// The nib that skeleton paths are stroked with.
+#[derive(Debug, Clone, Copy, PartialEq, Data, Lens)]
+pub struct Nib {
+    pub shape: NibShape,
+    /// The width of the nib, in design units.
+    pub width: f64,
+    /// The thickness of an oval nib, in design units.
+    pub height: f64,
+    /// The angle of the nib's width, in degrees counter-clockwise from
+    /// horizontal.
+    pub angle: f64,
+}
+
+impl Default for Nib {
+    fn default() -> Self {
+        Nib {
+            shape: NibShape::Flat,
+            width: 80.0,
+            height: 20.0,
+            angle: 30.0,
+        }
+    }
+}
+
+impl Nib {
+    /// The outline drawn by moving the nib along `skeleton`, as closed
+    /// contours with their points rounded to `precision`.
+    pub(crate) fn expand(&self, skeleton: &BezPath, precision: Precision) -> Vec<Contour> {
+        crate::overlap::union_of_paths(&self.pieces(skeleton), precision)
+    }
+
+    /// The areas swept by the nib along each segment of `skeleton`, which
+    /// overlap where the segments meet.
+    pub(crate) fn pieces(&self, skeleton: &BezPath) -> Vec<BezPath> {
+        if self.width <= 0.0 {
+            return Vec::new();
+        }
+        skeleton
+            .segments()
+            .map(to_cubic)
+            .filter(|seg| !is_point(seg))
+            .flat_map(|seg| match self.shape {
+                NibShape::Flat => flat_pieces(seg, self.half_width()),
+                NibShape::Oval => self.oval_piece(seg).into_iter().collect(),
+            })
+            .collect()
+    }
+
+    /// The vector from the center of the nib to one end of its width.
+    fn half_width(&self) -> Vec2 {
+        Vec2::from_angle(self.angle.to_radians()) * (self.width / 2.0)
+    }
+
+    /// The area swept by an oval nib along `seg`, including round ends.
+    fn oval_piece(&self, seg: CubicBez) -> Option<BezPath> {
+        let height = self.height.max(1.0).min(self.width);
+        // maps the unit circle onto the nib
+        let to_nib = Affine::rotate(self.angle.to_radians())
+            * Affine::scale_non_uniform(self.width / 2.0, height / 2.0);
+        let seg = to_nib.inverse() * seg;
+
+        let left = offset_curve(seg, 1.0);
+        let right = offset_curve(seg, -1.0);
+        let start_tan = start_tangent(seg);
+        let end_tan = end_tangent(seg);
+
+        let mut bez = BezPath::new();
+        bez.move_to(left[0].p0);
+        for piece in &left {
+            bez.curve_to(piece.p1, piece.p2, piece.p3);
+        }
+        half_circle(&mut bez, seg.p3, end_tan);
+        for piece in right.iter().rev() {
+            bez.curve_to(piece.p2, piece.p1, piece.p0);
+        }
+        half_circle(&mut bez, seg.p0, -start_tan);
+        bez.close_path();
+        bez.apply_affine(to_nib);
+        counter_clockwise(bez)
+    }
+}
+
+/// The areas swept by a flat nib from `-half_width` to `half_width` along
+/// `seg`.
+///
+/// The segment is split wherever it is parallel to the nib; between those
+/// points, the swept area is bounded by two copies of the segment.
+fn flat_pieces(seg: CubicBez, half_width: Vec2) -> Vec<BezPath> {
+    let mut splits = vec![0.0];
+    splits.extend(parallel_tangents(seg, half_width));
+    splits.push(1.0);
+    splits
+        .windows(2)
+        .filter_map(|range| {
+            let piece = seg.subsegment(range[0]..range[1]);
+            let a = Affine::translate(half_width) * piece;
+            let b = Affine::translate(-half_width) * piece;
+            let mut bez = BezPath::new();
+            bez.move_to(a.p0);
+            bez.curve_to(a.p1, a.p2, a.p3);
+            bez.line_to(b.p3);
+            bez.curve_to(b.p2, b.p1, b.p0);
+            bez.close_path();
+            counter_clockwise(bez)
+        })
+        .collect()
+}
+
+/// The parameters, in increasing order, at which the tangent of `seg` is
+/// parallel to `dir`.
+fn parallel_tangents(seg: CubicBez, dir: Vec2) -> Vec<f64> {
+    // the cross product of the derivative and `dir` is a quadratic in t
+    let q0 = (seg.p1 - seg.p0).cross(dir);
+    let q1 = (seg.p2 - seg.p1).cross(dir);
+    let q2 = (seg.p3 - seg.p2).cross(dir);
+    let a = q0 - 2.0 * q1 + q2;
+    let b = 2.0 * (q1 - q0);
+    let c = q0;
+
+    let mut roots = Vec::new();
+    if a.abs() < 1e-9 {
+        if b.abs() > 1e-9 {
+            roots.push(-c / b);
+        }
+    } else {
+        let disc = b * b - 4.0 * a * c;
+        if disc >= 0.0 {
+            let sqrt = disc.sqrt();
+            roots.push((-b - sqrt) / (2.0 * a));
+            roots.push((-b + sqrt) / (2.0 * a));
+        }
+    }
+    roots.retain(|t| *t > 1e-6 && *t < 1.0 - 1e-6);
+    roots.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    roots.dedup_by(|a, b| (*a - *b).abs() < 1e-6);
+    roots
+}
+
+/// Approximate the curve at distance `dist` to the left of `seg`.
+fn offset_curve(seg: CubicBez, dist: f64) -> Vec<CubicBez> {
+    let count = if is_line(&seg) { 1 } else { OVAL_SUBDIVISIONS };
+    (0..count)
+        .map(|i| {
+            let t0 = i as f64 / count as f64;
+            let t1 = (i + 1) as f64 / count as f64;
+            offset_piece(seg.subsegment(t0..t1), dist)
+        })
+        .collect()
+}
+
+/// Offset a short curve by moving its ends along their normals, and scaling
+/// its handles by how much the curvature changes the length of the curve.
+fn offset_piece(seg: CubicBez, dist: f64) -> CubicBez {
+    let p0 = seg.p0 + left_normal(start_tangent(seg)) * dist;
+    let p3 = seg.p3 + left_normal(end_tangent(seg)) * dist;
+    let scale0 = (1.0 - dist * curvature(seg, 0.0)).max(0.0);
+    let scale1 = (1.0 - dist * curvature(seg, 1.0)).max(0.0);
+    CubicBez::new(
+        p0,
+        p0 + (seg.p1 - seg.p0) * scale0,
+        p3 + (seg.p2 - seg.p3) * scale1,
+        p3,
+    )
+}
+
+/// Add a half circle of unit radius around `center` to `bez`, which must
+/// end to the left of `direction`, going through `direction` to its right.
+fn half_circle(bez: &mut BezPath, center: Point, direction: Vec2) {
+    let ahead = direction;
+    let left = left_normal(direction);
+    let k = QUARTER_CIRCLE_HANDLE;
+    bez.curve_to(
+        center + left + ahead * k,
+        center + ahead + left * k,
+        center + ahead,
+    );
+    bez.curve_to(
+        center + ahead - left * k,
+        center - left + ahead * k,
+        center - left,
+    );
+}
+
+/// The signed curvature of `seg` at `t`; positive where it turns left.
+fn curvature(seg: CubicBez, t: f64) -> f64 {
+    let deriv = seg.deriv();
+    let d1 = deriv.eval(t).to_vec2();
+    let d2 = deriv.deriv().eval(t).to_vec2();
+    let speed = d1.hypot();
+    if speed < 1e-9 {
+        0.0
+    } else {
+        d1.cross(d2) / (speed * speed * speed)
+    }
+}
+
+/// The unit tangent at the start of `seg`, even if its first handle is
+/// retracted.
+fn start_tangent(seg: CubicBez) -> Vec2 {
+    [seg.p1, seg.p2, seg.p3]
+        .iter()
+        .map(|pt| *pt - seg.p0)
+        .find(|v| v.hypot() > 1e-9)
+        .map(|v| v.normalize())
+        .unwrap_or_else(|| Vec2::new(1.0, 0.0))
+}
+
+/// The unit tangent at the end of `seg`.
+fn end_tangent(seg: CubicBez) -> Vec2 {
+    [seg.p2, seg.p1, seg.p0]
+        .iter()
+        .map(|pt| seg.p3 - *pt)
+        .find(|v| v.hypot() > 1e-9)
+        .map(|v| v.normalize())
+        .unwrap_or_else(|| Vec2::new(1.0, 0.0))
+}
+
+fn left_normal(tangent: Vec2) -> Vec2 {
+    Vec2::new(-tangent.y, tangent.x)
+}
+
+fn to_cubic(seg: PathSeg) -> CubicBez {
+    match seg {
+        PathSeg::Line(line) => CubicBez::new(
+            line.p0,
+            line.p0.lerp(line.p1, 1.0 / 3.0),
+            line.p0.lerp(line.p1, 2.0 / 3.0),
+            line.p1,
+        ),
+        PathSeg::Quad(quad) => quad.raise(),
+        PathSeg::Cubic(cubic) => cubic,
+    }
+}
+
+/// `true` if the handles of `seg` lie on the line between its ends.
+fn is_line(seg: &CubicBez) -> bool {
+    let chord = seg.p3 - seg.p0;
+    let len = chord.hypot();
+    len > 1e-9
+        && ((seg.p1 - seg.p0).cross(chord) / len).abs() < 1e-6
+        && ((seg.p2 - seg.p0).cross(chord) / len).abs() < 1e-6
+}
+
+/// `true` if every point of `seg` is in the same place.
+fn is_point(seg: &CubicBez) -> bool {
+    [seg.p1, seg.p2, seg.p3]
+        .iter()
+        .all(|pt| pt.distance(seg.p0) < 1e-9)
+}
+
+/// `bez`, a single closed path, reversed if needed so that it goes
+/// counter-clockwise, or `None` if it encloses no area.
+fn counter_clockwise(bez: BezPath) -> Option<BezPath> {
+    let area = bez.area();
+    if area.abs() < MIN_AREA {
+        None
+    } else if area > 0.0 {
+        Some(bez)
+    } else {
+        Some(reversed(&bez))
+    }
+}
+
+fn reversed(bez: &BezPath) -> BezPath {
+    let segments: Vec<_> = bez.segments().collect();
+    let mut result = BezPath::new();
+    let start = match segments.last() {
+        Some(seg) => to_cubic(*seg).p3,
+        None => return result,
+    };
+    result.move_to(start);
+    for seg in segments.iter().rev() {
+        match seg {
+            PathSeg::Line(line) => result.line_to(line.p0),
+            PathSeg::Quad(quad) => result.quad_to(quad.p1, quad.p0),
+            PathSeg::Cubic(cubic) => result.curve_to(cubic.p2, cubic.p1, cubic.p0),
+        }
+    }
+    result.close_path();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use druid::kurbo::Line;
+
+    fn area(contours: &[Contour]) -> f64 {
+        let mut bez = BezPath::new();
+        for contour in contours {
+            crate::data::append_contour(&mut bez, contour);
+        }
+        bez.area().abs()
+    }
+
+    fn line(p0: (f64, f64), p1: (f64, f64)) -> BezPath {
+        Line::new(p0, p1).into_path(0.1)
+    }
+
+    #[test]
+    fn flat_nib() {
+        let nib = Nib {
+            shape: NibShape::Flat,
+            width: 20.0,
+            angle: 90.0,
+            ..Default::default()
+        };
+        let horizontal = nib.expand(&line((0.0, 0.0), (100.0, 0.0)), Precision::Integer);
+        assert_eq!(horizontal.len(), 1);
+        assert!((area(&horizontal) - 2000.0).abs() < 1.0);
+
+        // a stroke along the nib has no width
+        assert!(nib
+            .expand(&line((0.0, 0.0), (0.0, 100.0)), Precision::Integer)
+            .is_empty());
+    }
+
+    #[test]
+    fn oval_nib() {
+        let nib = Nib {
+            shape: NibShape::Oval,
+            width: 20.0,
+            height: 20.0,
+            angle: 0.0,
+        };
+        let stroke = nib.expand(&line((0.0, 0.0), (100.0, 0.0)), Precision::Integer);
+        assert_eq!(stroke.len(), 1);
+        let expected = 100.0 * 20.0 + std::f64::consts::PI * 100.0;
+        assert!((area(&stroke) - expected).abs() < 5.0);
+    }
+
+    #[test]
+    fn split_where_parallel() {
+        // a symmetric arch is horizontal at its top
+        let arch = CubicBez::new((0.0, 0.0), (0.0, 100.0), (100.0, 100.0), (100.0, 0.0));
+        let splits = parallel_tangents(arch, Vec2::new(1.0, 0.0));
+        assert_eq!(splits.len(), 1);
+        assert!((splits[0] - 0.5).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,155 @@
+//! Rewriting a saved UFO in a normalized form.
+//!
+//! This follows the conventions of ufonormalizer, so that a font written by
+//! runebender and then normalized by another tool (or the other way around)
+//! produces no diffs in version control: plist dictionaries have sorted keys,
+//! and numbers are written as integers when they are whole, and otherwise
+//! with at most ten decimal places and no trailing zeros.
+
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+/// The most decimal places written for a number; ufonormalizer's default.
+const FLOAT_PRECISION: i32 = 10;
+
+/// glif attributes whose values are numbers.
+const NUMERIC_ATTRIBUTES: &[&str] = &[
+    "x", "y", "width", "height", "angle", "xScale", "xyScale", "yxScale", "yScale", "xOffset",
+    "yOffset",
+];
+
+/// Normalize every plist and glif file in the UFO at `path`, in place.
+pub(crate) fn normalize_ufo(path: &Path) -> Result<(), Box<dyn Error>> {
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            normalize_ufo(&path)?;
+        } else if has_extension(&path, "plist") {
+            let value = plist::Value::from_file(&path)?;
+            normalize_plist(value).to_file_xml(&path)?;
+        } else if has_extension(&path, "glif") {
+            let text = fs::read_to_string(&path)?;
+            fs::write(&path, normalize_glif(&text))?;
+        }
+    }
+    Ok(())
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension().and_then(|ext| ext.to_str()) == Some(extension)
+}
+
+/// Sort dictionary keys, and write whole numbers as integers.
+fn normalize_plist(value: plist::Value) -> plist::Value {
+    match value {
+        plist::Value::Dictionary(dict) => {
+            let mut items: Vec<_> = dict.into_iter().collect();
+            items.sort_by(|(a, _), (b, _)| a.cmp(b));
+            let mut sorted = plist::Dictionary::new();
+            for (key, value) in items {
+                sorted.insert(key, normalize_plist(value));
+            }
+            plist::Value::Dictionary(sorted)
+        }
+        plist::Value::Array(array) => {
+            plist::Value::Array(array.into_iter().map(normalize_plist).collect())
+        }
+        plist::Value::Real(real) => {
+            let real = round(real);
+            if real.fract() == 0.0 && real.abs() < i64::MAX as f64 {
+                plist::Value::Integer((real as i64).into())
+            } else {
+                plist::Value::Real(real)
+            }
+        }
+        other => other,
+    }
+}
+
+/// Rewrite the numeric attributes in a glif file.
+///
+/// Everything else is left as norad wrote it.
+fn normalize_glif(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(quote) = rest.find("=\"") {
+        let (before, after) = rest.split_at(quote + 2);
+        result.push_str(before);
+        let end = match after.find('"') {
+            Some(end) => end,
+            None => {
+                rest = after;
+                break;
+            }
+        };
+        let value = &after[..end];
+        let name = before[..quote]
+            .rsplit(|c: char| c.is_whitespace())
+            .next()
+            .unwrap_or_default();
+        match value.parse::<f64>() {
+            Ok(number) if NUMERIC_ATTRIBUTES.contains(&name) => {
+                result.push_str(&format_number(number))
+            }
+            _ => result.push_str(value),
+        }
+        rest = &after[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn round(value: f64) -> f64 {
+    let scale = 10f64.powi(FLOAT_PRECISION);
+    (value * scale).round() / scale
+}
+
+/// Format a number the way ufonormalizer does.
+fn format_number(value: f64) -> String {
+    let value = round(value);
+    if value.fract() == 0.0 {
+        return format!("{}", value as i64);
+    }
+    let text = format!("{:.*}", FLOAT_PRECISION as usize, value);
+    text.trim_end_matches('0').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers() {
+        assert_eq!(format_number(10.0), "10");
+        assert_eq!(format_number(-0.5), "-0.5");
+        assert_eq!(format_number(1.0 / 3.0), "0.3333333333");
+        assert_eq!(format_number(2.00000000001), "2");
+    }
+
+    #[test]
+    fn glif_attributes() {
+        let glif = r#"<point x="10.0" y="-20.50" type="line" name="1.0"/>"#;
+        assert_eq!(
+            normalize_glif(glif),
+            r#"<point x="10" y="-20.5" type="line" name="1.0"/>"#
+        );
+    }
+
+    #[test]
+    fn plist_keys_and_numbers() {
+        let mut dict = plist::Dictionary::new();
+        dict.insert("unitsPerEm".into(), plist::Value::Real(1000.0));
+        dict.insert("ascender".into(), plist::Value::Real(750.25));
+        let normalized = match normalize_plist(plist::Value::Dictionary(dict)) {
+            plist::Value::Dictionary(dict) => dict,
+            _ => panic!("expected a dictionary"),
+        };
+        let keys: Vec<_> = normalized.keys().collect();
+        assert_eq!(keys, vec!["ascender", "unitsPerEm"]);
+        assert_eq!(
+            normalized.get("unitsPerEm"),
+            Some(&plist::Value::Integer(1000.into()))
+        );
+    }
+}
@@ -0,0 +1,1043 @@
+//! Compiling OpenType layout tables for the preview.
+//!
+//! Substitutions come from the feature code, which supports a small subset
+//! of the feature file syntax: glyph class definitions, named lookups, and
+//! single, multiple and ligature substitutions inside feature blocks.
+//! Anything else is skipped, with a warning where it might affect the
+//! preview.
+//!
+//! Positioning is generated from the font's kerning and anchors, rather
+//! than from feature code.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::convert::TryInto;
+
+use norad::Ufo;
+
+use crate::fea::{self, Token, TokenKind};
+use crate::virtual_font::GlyphId;
+
+/// The maximum number of ligatures generated from a single rule with classes.
+const MAX_EXPANDED_LIGATURES: usize = 10_000;
+
+/// The result of compiling feature code.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledFeatures {
+    /// The GSUB table, if there were any substitutions.
+    pub gsub: Option<Vec<u8>>,
+    pub warnings: Vec<String>,
+}
+
+/// Compile the substitutions in `features` into a GSUB table.
+///
+/// `glyph_id` maps glyph names to glyph ids in the font being built.
+pub fn compile_features(
+    features: &str,
+    glyph_id: &dyn Fn(&str) -> Option<GlyphId>,
+) -> CompiledFeatures {
+    let mut parser = Parser::new(features, glyph_id);
+    parser.parse();
+    let Parser {
+        lookups,
+        features,
+        mut warnings,
+        ..
+    } = parser;
+
+    let gsub = if lookups.is_empty() || features.is_empty() {
+        None
+    } else {
+        let table = lookups
+            .iter()
+            .map(build_lookup)
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(|lookups| build_layout_table(&features, &lookups));
+        match table {
+            Ok(table) => Some(table),
+            Err(e) => {
+                warnings.push(e);
+                None
+            }
+        }
+    };
+    CompiledFeatures { gsub, warnings }
+}
+
+#[derive(Debug, Clone)]
+enum Lookup {
+    Single(BTreeMap<GlyphId, GlyphId>),
+    Multiple(BTreeMap<GlyphId, Vec<GlyphId>>),
+    /// Ligatures, keyed by their first component.
+    Ligature(BTreeMap<GlyphId, Vec<(Vec<GlyphId>, GlyphId)>>),
+}
+
+impl Lookup {
+    fn lookup_type(&self) -> u16 {
+        match self {
+            Lookup::Single(_) => 1,
+            Lookup::Multiple(_) => 2,
+            Lookup::Ligature(_) => 4,
+        }
+    }
+}
+
+/// A substitution rule, before it is added to a lookup.
+enum Rule {
+    Single(Vec<(GlyphId, GlyphId)>),
+    Multiple(GlyphId, Vec<GlyphId>),
+    Ligature(Vec<(Vec<GlyphId>, GlyphId)>),
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+    glyph_id: &'a dyn Fn(&str) -> Option<GlyphId>,
+    classes: HashMap<String, Vec<GlyphId>>,
+    lookups: Vec<Lookup>,
+    /// The lookups created for each named lookup block.
+    named_lookups: HashMap<String, Vec<usize>>,
+    /// The lookups for each feature tag.
+    features: BTreeMap<[u8; 4], Vec<usize>>,
+    warnings: Vec<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, glyph_id: &'a dyn Fn(&str) -> Option<GlyphId>) -> Self {
+        let tokens = fea::tokenize(text)
+            .into_iter()
+            .filter(|t| t.kind != TokenKind::Comment)
+            .collect();
+        Parser {
+            text,
+            tokens,
+            pos: 0,
+            glyph_id,
+            classes: HashMap::new(),
+            lookups: Vec::new(),
+            named_lookups: HashMap::new(),
+            features: BTreeMap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.peek_nth(0)
+    }
+
+    fn peek_nth(&self, n: usize) -> Option<&'a str> {
+        let text = self.text;
+        self.tokens
+            .get(self.pos + n)
+            .map(|t| &text[t.range.clone()])
+    }
+
+    fn next(&mut self) -> Option<&'a str> {
+        let next = self.peek();
+        self.pos += 1;
+        next
+    }
+
+    fn line(&self) -> usize {
+        let offset = self
+            .tokens
+            .get(self.pos)
+            .map(|t| t.range.start)
+            .unwrap_or_else(|| self.text.len());
+        self.text[..offset].matches('\n').count() + 1
+    }
+
+    fn warn(&mut self, message: impl std::fmt::Display) {
+        let warning = format!("line {}: {}", self.line(), message);
+        self.warnings.push(warning);
+    }
+
+    fn parse(&mut self) {
+        while let Some(token) = self.peek() {
+            match token {
+                "feature" => self.parse_feature(),
+                "lookup" => {
+                    self.parse_lookup_block();
+                }
+                s if s.starts_with('@') && self.peek_nth(1) == Some("=") => {
+                    self.parse_class_definition()
+                }
+                _ => self.skip_statement(),
+            }
+        }
+    }
+
+    /// Skip to the end of the current statement, including any block.
+    fn skip_statement(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            match token {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                ";" if depth <= 0 => return,
+                _ => (),
+            }
+        }
+    }
+
+    fn parse_class_definition(&mut self) {
+        let name = self.next().unwrap_or_default().to_string();
+        self.next(); // '='
+        match self.parse_glyph_set() {
+            Some(glyphs) => {
+                self.classes.insert(name, glyphs);
+            }
+            None => self.warn(format!("invalid class definition '{}'", name)),
+        }
+        self.skip_statement();
+    }
+
+    /// `feature tag { ... } tag;`
+    fn parse_feature(&mut self) {
+        self.next(); // 'feature'
+        let tag = match self.next().and_then(parse_tag) {
+            Some(tag) => tag,
+            None => {
+                self.warn("invalid feature tag");
+                self.skip_statement();
+                return;
+            }
+        };
+        if self.peek() != Some("{") {
+            // e.g. `feature aalt;` inside another feature
+            self.skip_statement();
+            return;
+        }
+        self.next();
+        let lookups = self.parse_block_contents();
+        self.features.entry(tag).or_default().extend(lookups);
+    }
+
+    /// `lookup name { ... } name;` or a reference, `lookup name;`.
+    ///
+    /// Returns the lookups that were defined or referenced.
+    fn parse_lookup_block(&mut self) -> Vec<usize> {
+        self.next(); // 'lookup'
+        let name = self.next().unwrap_or_default().to_string();
+        if self.peek() == Some("useExtension") {
+            self.next();
+        }
+        if self.peek() == Some(";") {
+            self.next();
+            return match self.named_lookups.get(&name) {
+                Some(lookups) => lookups.clone(),
+                None => {
+                    self.warn(format!("unknown lookup '{}'", name));
+                    Vec::new()
+                }
+            };
+        }
+        if self.next() != Some("{") {
+            self.warn(format!("expected '{{' after lookup '{}'", name));
+            self.skip_statement();
+            return Vec::new();
+        }
+        let lookups = self.parse_block_contents();
+        self.named_lookups.insert(name, lookups.clone());
+        lookups
+    }
+
+    /// Parse statements up to and including the closing `} tag;`,
+    /// returning the lookups used by the block.
+    fn parse_block_contents(&mut self) -> Vec<usize> {
+        let mut used = Vec::new();
+        // the lookup that rules directly in this block are added to
+        let mut current: Option<usize> = None;
+        while let Some(token) = self.peek() {
+            match token {
+                "}" => {
+                    self.next();
+                    // the closing tag and semicolon
+                    self.skip_statement();
+                    break;
+                }
+                "sub" | "substitute" => {
+                    if let Some(rule) = self.parse_substitution() {
+                        let idx = self.add_rule(current, rule);
+                        current = Some(idx);
+                        if !used.contains(&idx) {
+                            used.push(idx);
+                        }
+                    }
+                }
+                "lookup" => {
+                    current = None;
+                    used.extend(self.parse_lookup_block());
+                }
+                s if s.starts_with('@') && self.peek_nth(1) == Some("=") => {
+                    self.parse_class_definition()
+                }
+                "rsub" | "reversesub" | "ignore" => {
+                    self.warn(format!(
+                        "'{}' rules are not supported in the preview",
+                        token
+                    ));
+                    self.skip_statement();
+                }
+                _ => self.skip_statement(),
+            }
+        }
+        used
+    }
+
+    /// Add `rule` to the lookup `current` if it is the same type, or to a
+    /// new lookup; returns the index of the lookup used.
+    fn add_rule(&mut self, current: Option<usize>, rule: Rule) -> usize {
+        let same_type = matches!(
+            (current.map(|idx| &self.lookups[idx]), &rule),
+            (Some(Lookup::Single(_)), Rule::Single(_))
+                | (Some(Lookup::Multiple(_)), Rule::Multiple(..))
+                | (Some(Lookup::Ligature(_)), Rule::Ligature(_))
+        );
+        let idx = match current {
+            Some(idx) if same_type => idx,
+            _ => {
+                self.lookups.push(match &rule {
+                    Rule::Single(_) => Lookup::Single(BTreeMap::new()),
+                    Rule::Multiple(..) => Lookup::Multiple(BTreeMap::new()),
+                    Rule::Ligature(_) => Lookup::Ligature(BTreeMap::new()),
+                });
+                self.lookups.len() - 1
+            }
+        };
+
+        match (&mut self.lookups[idx], rule) {
+            (Lookup::Single(map), Rule::Single(pairs)) => map.extend(pairs),
+            (Lookup::Multiple(map), Rule::Multiple(glyph, sequence)) => {
+                map.insert(glyph, sequence);
+            }
+            (Lookup::Ligature(map), Rule::Ligature(ligatures)) => {
+                for (components, ligature) in ligatures {
+                    map.entry(components[0])
+                        .or_default()
+                        .push((components, ligature));
+                }
+            }
+            _ => unreachable!("rule added to lookup of a different type"),
+        }
+        idx
+    }
+
+    /// `sub <glyphs> by <glyphs>;`
+    fn parse_substitution(&mut self) -> Option<Rule> {
+        self.next(); // 'sub'
+        let mut input = Vec::new();
+        let mut output = Vec::new();
+        let mut seen_by = false;
+        loop {
+            match self.peek() {
+                Some(";") => {
+                    self.next();
+                    break;
+                }
+                Some("by") => {
+                    self.next();
+                    seen_by = true;
+                }
+                Some("'") | Some("from") => {
+                    self.warn(
+                        "contextual and alternate substitutions are not supported in the preview",
+                    );
+                    self.skip_statement();
+                    return None;
+                }
+                Some(_) => match self.parse_glyph_set() {
+                    Some(glyphs) if seen_by => output.push(glyphs),
+                    Some(glyphs) => input.push(glyphs),
+                    None => {
+                        self.skip_statement();
+                        return None;
+                    }
+                },
+                None => return None,
+            }
+        }
+
+        // unknown glyphs have already been reported
+        if input.iter().chain(&output).any(Vec::is_empty) {
+            return None;
+        }
+
+        match (input.as_slice(), output.as_slice()) {
+            ([from], [to]) if to.len() == 1 => {
+                Some(Rule::Single(from.iter().map(|g| (*g, to[0])).collect()))
+            }
+            ([from], [to]) if from.len() == to.len() => Some(Rule::Single(
+                from.iter().copied().zip(to.iter().copied()).collect(),
+            )),
+            ([from], to) if from.len() == 1 && to.len() > 1 && to.iter().all(|g| g.len() == 1) => {
+                Some(Rule::Multiple(from[0], to.iter().map(|g| g[0]).collect()))
+            }
+            (from, [to]) if from.len() > 1 && to.len() == 1 => {
+                let count: usize = from.iter().map(Vec::len).product();
+                if count > MAX_EXPANDED_LIGATURES {
+                    self.warn("ligature rule expands to too many ligatures");
+                    return None;
+                }
+                let ligatures = cartesian_product(&from)
+                    .into_iter()
+                    .map(|components| (components, to[0]))
+                    .collect();
+                Some(Rule::Ligature(ligatures))
+            }
+            _ => {
+                self.warn("unsupported substitution");
+                None
+            }
+        }
+    }
+
+    /// Parse a single glyph, a class reference, or a bracketed class.
+    ///
+    /// Glyphs missing from the font are skipped with a warning.
+    fn parse_glyph_set(&mut self) -> Option<Vec<GlyphId>> {
+        let token = self.next()?;
+        if token == "[" {
+            let mut glyphs = Vec::new();
+            loop {
+                match self.peek() {
+                    Some("]") => {
+                        self.next();
+                        return Some(glyphs);
+                    }
+                    Some(";") | None => {
+                        self.warn("unclosed glyph class");
+                        return None;
+                    }
+                    Some(_) => glyphs.extend(self.parse_glyph_set()?),
+                }
+            }
+        } else if token.starts_with('@') {
+            match self.classes.get(token) {
+                Some(glyphs) => Some(glyphs.clone()),
+                None => {
+                    self.warn(format!("unknown glyph class '{}'", token));
+                    None
+                }
+            }
+        } else {
+            let name = token.trim_start_matches('\\');
+            if let Some(id) = (self.glyph_id)(name) {
+                return Some(vec![id]);
+            }
+            match expand_range(name) {
+                Some(names) => Some(names.iter().filter_map(|n| (self.glyph_id)(n)).collect()),
+                None => {
+                    self.warn(format!("unknown glyph '{}'", name));
+                    Some(Vec::new())
+                }
+            }
+        }
+    }
+}
+
+/// Expand a range like `a-z` or `A.sc-Z.sc`, where the two ends differ only
+/// in a single character.
+fn expand_range(name: &str) -> Option<Vec<String>> {
+    let mut parts = name.splitn(2, '-');
+    let (start, end) = (parts.next()?, parts.next()?);
+    if start.len() != end.len() {
+        return None;
+    }
+    let diff = start
+        .char_indices()
+        .zip(end.chars())
+        .filter(|((_, a), b)| a != b)
+        .collect::<Vec<_>>();
+    match diff.as_slice() {
+        [((idx, first), last)] if first < last => {
+            let (prefix, suffix) = (&start[..*idx], &start[idx + first.len_utf8()..]);
+            Some(
+                (*first..=*last)
+                    .map(|c| format!("{}{}{}", prefix, c, suffix))
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn cartesian_product(sets: &[Vec<GlyphId>]) -> Vec<Vec<GlyphId>> {
+    sets.iter().fold(vec![Vec::new()], |acc, set| {
+        acc.into_iter()
+            .flat_map(|prefix| {
+                set.iter().map(move |g| {
+                    let mut seq = prefix.clone();
+                    seq.push(*g);
+                    seq
+                })
+            })
+            .collect()
+    })
+}
+
+fn parse_tag(s: &str) -> Option<[u8; 4]> {
+    if s.is_empty() || s.len() > 4 || !s.is_ascii() {
+        return None;
+    }
+    let mut tag = [b' '; 4];
+    tag[..s.len()].copy_from_slice(s.as_bytes());
+    Some(tag)
+}
+
+/// The anchor class and position of each mark glyph.
+type MarkAnchors = BTreeMap<GlyphId, (String, (i16, i16))>;
+/// The named anchors of each base glyph.
+type BaseAnchors = BTreeMap<GlyphId, BTreeMap<String, (i16, i16)>>;
+
+/// The result of compiling the font's kerning and anchors.
+#[derive(Debug, Clone, Default)]
+pub struct CompiledPositioning {
+    pub gpos: Option<Vec<u8>>,
+    pub gdef: Option<Vec<u8>>,
+}
+
+/// Compile the font's kerning and anchors into GPOS and GDEF tables.
+///
+/// Kerning is added to the 'kern' feature, and anchors to the 'mark'
+/// feature: an anchor named `_top` on a mark attaches to the anchor named
+/// `top` on the preceding base glyph.
+pub fn compile_positioning(
+    ufo: &Ufo,
+    glyph_id: &dyn Fn(&str) -> Option<GlyphId>,
+) -> CompiledPositioning {
+    let pairs = kerning_pairs(ufo, glyph_id);
+    let (marks, bases) = anchors(ufo, glyph_id);
+    match build_gpos(&pairs, &marks, &bases) {
+        Ok(gpos) => CompiledPositioning {
+            gpos,
+            gdef: if marks.is_empty() {
+                None
+            } else {
+                Some(build_gdef(&marks, &bases))
+            },
+        },
+        Err(e) => {
+            log::warn!("failed to compile positioning: {}", e);
+            CompiledPositioning::default()
+        }
+    }
+}
+
+/// Expand the kerning groups into pairs of glyphs, keyed by the first glyph.
+fn kerning_pairs(
+    ufo: &Ufo,
+    glyph_id: &dyn Fn(&str) -> Option<GlyphId>,
+) -> BTreeMap<GlyphId, BTreeMap<GlyphId, i16>> {
+    let kerning = match ufo.kerning.as_ref() {
+        Some(kerning) => kerning,
+        None => return BTreeMap::new(),
+    };
+    let group = |key: &str| ufo.groups.as_ref().and_then(|groups| groups.get(key));
+    let members = |key: &str| -> Vec<GlyphId> {
+        match group(key) {
+            Some(glyphs) => glyphs.iter().filter_map(|g| glyph_id(&**g)).collect(),
+            None => glyph_id(key).into_iter().collect(),
+        }
+    };
+
+    let mut entries = kerning
+        .iter()
+        .flat_map(|(first, seconds)| {
+            seconds
+                .iter()
+                .map(move |(second, value)| (first, second, *value))
+        })
+        .collect::<Vec<_>>();
+    // pairs involving single glyphs override the pairs of their groups
+    entries.sort_by_key(|(first, second, _)| {
+        (
+            group(first.as_str()).is_none(),
+            group(second.as_str()).is_none(),
+        )
+    });
+
+    let mut pairs = BTreeMap::new();
+    for (first, second, value) in entries {
+        for left in members(first.as_str()) {
+            for right in members(second.as_str()) {
+                pairs
+                    .entry(left)
+                    .or_insert_with(BTreeMap::new)
+                    .insert(right, value.round() as i16);
+            }
+        }
+    }
+    pairs
+}
+
+fn anchors(ufo: &Ufo, glyph_id: &dyn Fn(&str) -> Option<GlyphId>) -> (MarkAnchors, BaseAnchors) {
+    let mut marks = MarkAnchors::new();
+    let mut bases = BaseAnchors::new();
+    for glyph in ufo
+        .get_default_layer()
+        .iter()
+        .flat_map(|layer| layer.iter_contents())
+    {
+        let id = match glyph_id(&*glyph.name) {
+            Some(id) => id,
+            None => continue,
+        };
+        for anchor in glyph.anchors.iter().flatten() {
+            let name = match anchor.name.as_ref() {
+                Some(name) => name,
+                None => continue,
+            };
+            let pos = (anchor.x.round() as i16, anchor.y.round() as i16);
+            match name.strip_prefix('_') {
+                Some(class) => {
+                    marks.entry(id).or_insert_with(|| (class.to_string(), pos));
+                }
+                None => {
+                    bases.entry(id).or_default().insert(name.clone(), pos);
+                }
+            }
+        }
+    }
+    // anchors on marks are for mark-to-mark attachment, which we don't do
+    bases.retain(|id, _| !marks.contains_key(id));
+    (marks, bases)
+}
+
+fn build_gpos(
+    pairs: &BTreeMap<GlyphId, BTreeMap<GlyphId, i16>>,
+    marks: &MarkAnchors,
+    bases: &BaseAnchors,
+) -> Result<Option<Vec<u8>>, String> {
+    let mut features = BTreeMap::new();
+    let mut lookups = Vec::new();
+    if !pairs.is_empty() {
+        features.insert(*b"kern", vec![lookups.len()]);
+        lookups.push(lookup_table(2, pair_pos(pairs)?));
+    }
+    if !marks.is_empty() && !bases.is_empty() {
+        features.insert(*b"mark", vec![lookups.len()]);
+        lookups.push(lookup_table(4, mark_base_pos(marks, bases)?));
+    }
+    if lookups.is_empty() {
+        return Ok(None);
+    }
+    build_layout_table(&features, &lookups).map(Some)
+}
+
+/// A format 1 pair adjustment subtable, adjusting the advance of the
+/// first glyph.
+fn pair_pos(pairs: &BTreeMap<GlyphId, BTreeMap<GlyphId, i16>>) -> Result<Vec<u8>, String> {
+    let pair_sets = pairs
+        .values()
+        .map(|seconds| {
+            let mut table = Vec::new();
+            push_u16(&mut table, seconds.len() as u16);
+            for (glyph, value) in seconds {
+                push_u16(&mut table, *glyph);
+                push_u16(&mut table, *value as u16);
+            }
+            table
+        })
+        .collect::<Vec<_>>();
+    let coverage = coverage(pairs.keys());
+    let header_len = 10 + pair_sets.len() * 2;
+
+    let mut table = Vec::new();
+    push_u16(&mut table, 1); // format
+    push_offset(&mut table, header_len)?;
+    push_u16(&mut table, 0x0004); // valueFormat1: xAdvance
+    push_u16(&mut table, 0); // valueFormat2
+    push_u16(&mut table, pair_sets.len() as u16);
+    let mut offset = header_len + coverage.len();
+    for set in &pair_sets {
+        push_offset(&mut table, offset)?;
+        offset += set.len();
+    }
+    table.extend(coverage);
+    table.extend(pair_sets.concat());
+    Ok(table)
+}
+
+/// A format 1 mark-to-base attachment subtable.
+fn mark_base_pos(marks: &MarkAnchors, bases: &BaseAnchors) -> Result<Vec<u8>, String> {
+    let classes = marks
+        .values()
+        .map(|(class, _)| class)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let class_index = |name: &String| classes.iter().position(|class| *class == name);
+    let bases = bases
+        .iter()
+        .filter(|(_, anchors)| anchors.keys().any(|name| class_index(name).is_some()))
+        .collect::<Vec<_>>();
+
+    let mut mark_array = Vec::new();
+    push_u16(&mut mark_array, marks.len() as u16);
+    let mut offset = 2 + marks.len() * 4;
+    for (class, _) in marks.values() {
+        push_u16(
+            &mut mark_array,
+            class_index(class).unwrap_or_default() as u16,
+        );
+        push_offset(&mut mark_array, offset)?;
+        offset += ANCHOR_LEN;
+    }
+    for (_, pos) in marks.values() {
+        mark_array.extend(anchor(*pos));
+    }
+
+    let mut base_array = Vec::new();
+    push_u16(&mut base_array, bases.len() as u16);
+    let mut offset = 2 + bases.len() * classes.len() * 2;
+    let mut base_anchors = Vec::new();
+    for (_, anchors) in &bases {
+        for class in &classes {
+            match anchors.get(*class) {
+                Some(pos) => {
+                    push_offset(&mut base_array, offset)?;
+                    offset += ANCHOR_LEN;
+                    base_anchors.extend(anchor(*pos));
+                }
+                None => push_u16(&mut base_array, 0),
+            }
+        }
+    }
+    base_array.extend(base_anchors);
+
+    let mark_coverage = coverage(marks.keys());
+    let base_coverage = coverage(bases.iter().map(|(id, _)| *id));
+    let mut table = Vec::new();
+    push_u16(&mut table, 1); // format
+    let mut offset = 12;
+    push_offset(&mut table, offset)?;
+    offset += mark_coverage.len();
+    push_offset(&mut table, offset)?;
+    offset += base_coverage.len();
+    push_u16(&mut table, classes.len() as u16);
+    push_offset(&mut table, offset)?;
+    offset += mark_array.len();
+    push_offset(&mut table, offset)?;
+    table.extend(mark_coverage);
+    table.extend(base_coverage);
+    table.extend(mark_array);
+    table.extend(base_array);
+    Ok(table)
+}
+
+/// The length of a format 1 anchor table.
+const ANCHOR_LEN: usize = 6;
+
+fn anchor((x, y): (i16, i16)) -> Vec<u8> {
+    let mut table = Vec::with_capacity(ANCHOR_LEN);
+    push_u16(&mut table, 1); // format
+    push_u16(&mut table, x as u16);
+    push_u16(&mut table, y as u16);
+    table
+}
+
+/// A GDEF table classifying base and mark glyphs, so that harfbuzz
+/// can attach and zero the advance of marks.
+fn build_gdef(marks: &MarkAnchors, bases: &BaseAnchors) -> Vec<u8> {
+    const BASE_CLASS: u16 = 1;
+    const MARK_CLASS: u16 = 3;
+    let classes = bases
+        .keys()
+        .map(|id| (*id, BASE_CLASS))
+        .chain(marks.keys().map(|id| (*id, MARK_CLASS)))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut table = Vec::new();
+    push_u16(&mut table, 1); // majorVersion
+    push_u16(&mut table, 0); // minorVersion
+    push_u16(&mut table, 12); // glyphClassDefOffset
+    push_u16(&mut table, 0); // attachListOffset
+    push_u16(&mut table, 0); // ligCaretListOffset
+    push_u16(&mut table, 0); // markAttachClassDefOffset
+    push_u16(&mut table, 2); // classFormat
+    push_u16(&mut table, classes.len() as u16);
+    for (id, class) in classes {
+        push_u16(&mut table, id);
+        push_u16(&mut table, id);
+        push_u16(&mut table, class);
+    }
+    table
+}
+
+/// Build a GSUB or GPOS table from the compiled lookups.
+fn build_layout_table(
+    features: &BTreeMap<[u8; 4], Vec<usize>>,
+    lookup_tables: &[Vec<u8>],
+) -> Result<Vec<u8>, String> {
+    // all features are registered for the default script and language;
+    // harfbuzz falls back to this for every script.
+    let mut lang_sys = Vec::new();
+    push_u16(&mut lang_sys, 0); // lookupOrderOffset
+    push_u16(&mut lang_sys, 0xFFFF); // requiredFeatureIndex
+    push_u16(&mut lang_sys, features.len() as u16);
+    for i in 0..features.len() {
+        push_u16(&mut lang_sys, i as u16);
+    }
+    let mut script = Vec::new();
+    push_u16(&mut script, 4); // defaultLangSysOffset
+    push_u16(&mut script, 0); // langSysCount
+    script.extend(lang_sys);
+
+    let mut script_list = Vec::new();
+    push_u16(&mut script_list, 1);
+    script_list.extend(b"DFLT");
+    push_u16(&mut script_list, 8);
+    script_list.extend(script);
+
+    let feature_tables = features
+        .values()
+        .map(|indices| {
+            let mut table = Vec::new();
+            push_u16(&mut table, 0); // featureParamsOffset
+            push_u16(&mut table, indices.len() as u16);
+            for idx in indices {
+                push_u16(&mut table, *idx as u16);
+            }
+            table
+        })
+        .collect::<Vec<_>>();
+    let mut feature_list = Vec::new();
+    push_u16(&mut feature_list, features.len() as u16);
+    let mut offset = 2 + features.len() * 6;
+    for (tag, table) in features.keys().zip(&feature_tables) {
+        feature_list.extend(tag);
+        push_offset(&mut feature_list, offset)?;
+        offset += table.len();
+    }
+    feature_list.extend(feature_tables.concat());
+
+    let lookup_list = with_offsets(Vec::new(), lookup_tables)?;
+
+    let mut table = Vec::new();
+    push_u16(&mut table, 1); // majorVersion
+    push_u16(&mut table, 0); // minorVersion
+    let script_offset = 10;
+    let feature_offset = script_offset + script_list.len();
+    let lookup_offset = feature_offset + feature_list.len();
+    push_offset(&mut table, script_offset)?;
+    push_offset(&mut table, feature_offset)?;
+    push_offset(&mut table, lookup_offset)?;
+    table.extend(script_list);
+    table.extend(feature_list);
+    table.extend(lookup_list);
+    Ok(table)
+}
+
+fn build_lookup(lookup: &Lookup) -> Result<Vec<u8>, String> {
+    let subtable = match lookup {
+        Lookup::Single(map) => {
+            let mut table = Vec::new();
+            push_u16(&mut table, 2); // format
+            push_offset(&mut table, 6 + map.len() * 2)?; // coverageOffset
+            push_u16(&mut table, map.len() as u16);
+            for to in map.values() {
+                push_u16(&mut table, *to);
+            }
+            table.extend(coverage(map.keys()));
+            table
+        }
+        Lookup::Multiple(map) => {
+            let sequences = map
+                .values()
+                .map(|seq| glyph_array(seq.iter()))
+                .collect::<Vec<_>>();
+            subtable_with_coverage(map.keys(), &sequences)?
+        }
+        Lookup::Ligature(map) => {
+            let sets = map
+                .values()
+                .map(|ligatures| {
+                    let mut ligatures = ligatures.clone();
+                    // longer ligatures take precedence
+                    ligatures.sort_by_key(|(components, _)| std::cmp::Reverse(components.len()));
+                    let tables = ligatures
+                        .iter()
+                        .map(|(components, ligature)| {
+                            let mut table = Vec::new();
+                            push_u16(&mut table, *ligature);
+                            push_u16(&mut table, components.len() as u16);
+                            for glyph in &components[1..] {
+                                push_u16(&mut table, *glyph);
+                            }
+                            table
+                        })
+                        .collect::<Vec<_>>();
+                    with_offsets(Vec::new(), &tables)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            subtable_with_coverage(map.keys(), &sets)?
+        }
+    };
+
+    Ok(lookup_table(lookup.lookup_type(), subtable))
+}
+
+/// A lookup with a single subtable.
+fn lookup_table(lookup_type: u16, subtable: Vec<u8>) -> Vec<u8> {
+    let mut table = Vec::new();
+    push_u16(&mut table, lookup_type);
+    push_u16(&mut table, 0); // lookupFlag
+    push_u16(&mut table, 1); // subTableCount
+    push_u16(&mut table, 8); // subtableOffset
+    table.extend(subtable);
+    table
+}
+
+/// A format 1 subtable with a coverage table, followed by an offset
+/// to each of `children`.
+fn subtable_with_coverage<'a>(
+    covered: impl Iterator<Item = &'a GlyphId>,
+    children: &[Vec<u8>],
+) -> Result<Vec<u8>, String> {
+    let coverage = coverage(covered);
+    let header_len = 6 + children.len() * 2;
+    let mut table = Vec::new();
+    push_u16(&mut table, 1); // format
+    push_offset(&mut table, header_len)?;
+    push_u16(&mut table, children.len() as u16);
+    let mut offset = header_len + coverage.len();
+    for child in children {
+        push_offset(&mut table, offset)?;
+        offset += child.len();
+    }
+    table.extend(coverage);
+    table.extend(children.concat());
+    Ok(table)
+}
+
+/// `header`, followed by a count and an offset to each of `children`.
+fn with_offsets(mut header: Vec<u8>, children: &[Vec<u8>]) -> Result<Vec<u8>, String> {
+    let mut offset = header.len() + 2 + children.len() * 2;
+    push_u16(&mut header, children.len() as u16);
+    for child in children {
+        push_offset(&mut header, offset)?;
+        offset += child.len();
+    }
+    header.extend(children.concat());
+    Ok(header)
+}
+
+/// A format 1 coverage table; the glyphs must be sorted.
+fn coverage<'a>(glyphs: impl Iterator<Item = &'a GlyphId>) -> Vec<u8> {
+    let mut table = Vec::new();
+    push_u16(&mut table, 1);
+    table.extend(glyph_array(glyphs));
+    table
+}
+
+/// A count followed by glyph ids.
+fn glyph_array<'a>(glyphs: impl Iterator<Item = &'a GlyphId>) -> Vec<u8> {
+    let glyphs = glyphs.collect::<Vec<_>>();
+    let mut table = Vec::with_capacity(2 + glyphs.len() * 2);
+    push_u16(&mut table, glyphs.len() as u16);
+    for glyph in glyphs {
+        push_u16(&mut table, *glyph);
+    }
+    table
+}
+
+fn push_u16(buf: &mut Vec<u8>, val: u16) {
+    buf.extend_from_slice(&val.to_be_bytes());
+}
+
+fn push_offset(buf: &mut Vec<u8>, offset: usize) -> Result<(), String> {
+    let offset: u16 = offset
+        .try_into()
+        .map_err(|_| "features are too large to preview".to_string())?;
+    push_u16(buf, offset);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph_id(name: &str) -> Option<GlyphId> {
+        ["f", "i", "l", "f_i", "f_l", "a", "b", "a.sc", "b.sc"]
+            .iter()
+            .position(|n| *n == name)
+            .map(|idx| idx as GlyphId + 1)
+    }
+
+    #[test]
+    fn parse_substitutions() {
+        let fea = "
+            @lower = [a b];
+            lookup ligs { sub f i by f_i; sub f l by f_l; } ligs;
+            feature liga { lookup ligs; } liga;
+            feature smcp { sub @lower by [a.sc b.sc]; sub f_i by f i; } smcp;
+        ";
+        let mut parser = Parser::new(fea, &glyph_id);
+        parser.parse();
+        assert!(parser.warnings.is_empty(), "{:?}", parser.warnings);
+        assert_eq!(parser.lookups.len(), 3);
+        assert_eq!(parser.features[b"liga"], vec![0]);
+        assert_eq!(parser.features[b"smcp"], vec![1, 2]);
+        match &parser.lookups[0] {
+            Lookup::Ligature(map) => assert_eq!(map[&1].len(), 2),
+            other => panic!("unexpected lookup {:?}", other),
+        }
+        match &parser.lookups[1] {
+            Lookup::Single(map) => assert_eq!(map[&6], 8),
+            other => panic!("unexpected lookup {:?}", other),
+        }
+        assert!(matches!(&parser.lookups[2], Lookup::Multiple(_)));
+
+        let compiled = compile_features(fea, &glyph_id);
+        let gsub = compiled.gsub.unwrap();
+        assert_eq!(&gsub[..4], &[0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn warnings() {
+        let compiled = compile_features("feature liga { sub f x by f_x; } liga;", &glyph_id);
+        assert!(compiled.gsub.is_none());
+        assert_eq!(compiled.warnings.len(), 2);
+
+        let compiled = compile_features("feature calt { sub f' i by f_i; } calt;", &glyph_id);
+        assert_eq!(compiled.warnings.len(), 1);
+    }
+
+    #[test]
+    fn kerning() {
+        let mut ufo = Ufo::new();
+        let mut groups = BTreeMap::new();
+        groups.insert("public.kern1.f".to_string(), vec!["f".into(), "f_i".into()]);
+        ufo.groups = Some(groups);
+        let mut kerning = BTreeMap::new();
+        let mut seconds = BTreeMap::new();
+        seconds.insert("a".to_string(), -20.0);
+        seconds.insert("b".to_string(), -10.4);
+        kerning.insert("public.kern1.f".to_string(), seconds);
+        let mut seconds = BTreeMap::new();
+        seconds.insert("a".to_string(), 5.0);
+        kerning.insert("f".to_string(), seconds);
+        ufo.kerning = Some(kerning);
+
+        let pairs = kerning_pairs(&ufo, &glyph_id);
+        // the glyph pair overrides the group pair
+        assert_eq!(pairs[&1][&6], 5);
+        assert_eq!(pairs[&1][&7], -10);
+        assert_eq!(pairs[&4][&6], -20);
+
+        let compiled = compile_positioning(&ufo, &glyph_id);
+        assert!(compiled.gpos.is_some());
+        assert!(compiled.gdef.is_none());
+    }
+
+    #[test]
+    fn ranges() {
+        assert_eq!(
+            expand_range("a-c"),
+            Some(vec!["a".into(), "b".into(), "c".into()])
+        );
+        assert_eq!(expand_range("A.sc-B.sc").map(|r| r.len()), Some(2));
+        assert_eq!(expand_range("a-bc"), None);
+    }
+}
@@ -0,0 +1,265 @@
+//! Removing overlapping contours from glyph outlines.
+//!
+//! This is used when exporting, where the outlines in the editor are left
+//! untouched, and to merge the pieces of expanded nib strokes.
+
+use std::sync::Arc;
+
+use druid::kurbo::{BezPath, PathEl, PathSeg, Point};
+use flo_curves::bezier::path::{path_remove_overlaps, SimpleBezierPath};
+use flo_curves::Coord2;
+use norad::glyph::{Contour, ContourPoint, Glyph, PointType};
+
+use crate::design_space::{self, Precision};
+
+/// The max distance between the input and output curves.
+const ACCURACY: f64 = 0.01;
+
+/// Returns a copy of `glyph` where all overlapping closed contours have
+/// been merged, with the new points rounded to `precision`.
+///
+/// Open contours, components, and contours that have no segments, such as
+/// single points, are left untouched. If the glyph has no closed contours,
+/// it is returned unchanged.
+pub(crate) fn remove_overlaps(glyph: &Arc<Glyph>, precision: Precision) -> Arc<Glyph> {
+    let outline = match glyph.outline.as_ref() {
+        Some(outline) => outline,
+        None => return glyph.clone(),
+    };
+
+    let (closed, open): (Vec<_>, Vec<_>) = outline
+        .contours
+        .iter()
+        .partition(|c| !matches!(c.points.first(), Some(pt) if pt.typ == PointType::Move));
+
+    if closed.is_empty() {
+        return glyph.clone();
+    }
+
+    let mut paths = Vec::new();
+    let mut kept = Vec::new();
+    for contour in closed {
+        match flo_path(contour) {
+            Some(path) => paths.push(path),
+            None => kept.push(contour.clone()),
+        }
+    }
+    let merged: Vec<SimpleBezierPath> = path_remove_overlaps(&paths, ACCURACY);
+
+    let mut contours: Vec<Contour> = merged
+        .iter()
+        .map(|path| norad_contour(path, precision))
+        .collect();
+    contours.extend(kept);
+    contours.extend(open.into_iter().cloned());
+
+    let mut glyph = glyph.clone();
+    Arc::make_mut(&mut glyph).outline.as_mut().unwrap().contours = contours;
+    glyph
+}
+
+/// Returns the union of `paths`, each of which should be a single closed
+/// subpath, as closed norad contours with their points rounded to
+/// `precision`.
+///
+/// A path that is only a single point is returned as a single point.
+pub(crate) fn union_of_paths(paths: &[BezPath], precision: Precision) -> Vec<Contour> {
+    let mut flo_paths = Vec::new();
+    let mut kept = Vec::new();
+    for path in paths {
+        match flo_path_from_bez(path) {
+            Some(flo) => flo_paths.push(flo),
+            None => kept.extend(lone_point(path, precision)),
+        }
+    }
+    if flo_paths.is_empty() {
+        return kept;
+    }
+    let mut contours: Vec<Contour> = path_remove_overlaps(&flo_paths, ACCURACY)
+        .iter()
+        .map(|path| norad_contour(path, precision))
+        .collect();
+    contours.extend(kept);
+    contours
+}
+
+/// The contour for a path with no segments, which is at most a single point.
+fn lone_point(bez: &BezPath, precision: Precision) -> Option<Contour> {
+    match bez.elements().first() {
+        Some(PathEl::MoveTo(pt)) => Some(Contour::new(
+            vec![norad_point(coord(*pt), PointType::Move, precision)],
+            None,
+            None,
+        )),
+        _ => None,
+    }
+}
+
+/// Convert a closed norad contour into a flo_curves path.
+fn flo_path(contour: &Contour) -> Option<SimpleBezierPath> {
+    let mut bez = BezPath::new();
+    crate::data::append_contour(&mut bez, contour);
+    flo_path_from_bez(&bez)
+}
+
+/// Convert a single closed subpath into a flo_curves path.
+///
+/// Lines are represented as cubics with their control points on the
+/// endpoints.
+fn flo_path_from_bez(bez: &BezPath) -> Option<SimpleBezierPath> {
+    let start = match bez.elements().first() {
+        Some(PathEl::MoveTo(pt)) => coord(*pt),
+        _ => return None,
+    };
+
+    let segments = druid::kurbo::segments(bez.elements().iter().cloned())
+        .map(|seg| match seg {
+            PathSeg::Line(line) => (coord(line.p0), coord(line.p1), coord(line.p1)),
+            PathSeg::Cubic(cubic) => (coord(cubic.p1), coord(cubic.p2), coord(cubic.p3)),
+            PathSeg::Quad(quad) => {
+                let cubic = quad.raise();
+                (coord(cubic.p1), coord(cubic.p2), coord(cubic.p3))
+            }
+        })
+        .collect::<Vec<_>>();
+    if segments.is_empty() {
+        None
+    } else {
+        Some((start, segments))
+    }
+}
+
+/// Convert a flo_curves path back into a (closed) norad contour.
+fn norad_contour(path: &SimpleBezierPath, precision: Precision) -> Contour {
+    let (start, segments) = path;
+    let mut points = Vec::with_capacity(segments.len() * 3);
+    let mut prev = *start;
+    for (p1, p2, p3) in segments {
+        if is_line(prev, *p1, *p2, *p3) {
+            points.push(norad_point(*p3, PointType::Line, precision));
+        } else {
+            points.push(norad_point(*p1, PointType::OffCurve, precision));
+            points.push(norad_point(*p2, PointType::OffCurve, precision));
+            points.push(norad_point(*p3, PointType::Curve, precision));
+        }
+        prev = *p3;
+    }
+    // the last point is the same as the start point; we want the contour to
+    // begin with it instead.
+    if points.len() > 1 {
+        points.rotate_right(1);
+    }
+    Contour::new(points, None, None)
+}
+
+/// `true` if both control points lie on the line between the end points.
+fn is_line(p0: Coord2, p1: Coord2, p2: Coord2, p3: Coord2) -> bool {
+    let on_chord = |pt: Coord2| {
+        let (dx, dy) = (p3.0 - p0.0, p3.1 - p0.1);
+        let cross = (pt.0 - p0.0) * dy - (pt.1 - p0.1) * dx;
+        let len = dx.hypot(dy);
+        if len < ACCURACY {
+            (pt.0 - p0.0).hypot(pt.1 - p0.1) < ACCURACY
+        } else {
+            (cross / len).abs() < ACCURACY
+        }
+    };
+    on_chord(p1) && on_chord(p2)
+}
+
+fn coord(pt: Point) -> Coord2 {
+    Coord2(pt.x, pt.y)
+}
+
+fn norad_point(pt: Coord2, typ: PointType, precision: Precision) -> ContourPoint {
+    let x = design_space::round_coord(pt.0, precision) as f32;
+    let y = design_space::round_coord(pt.1, precision) as f32;
+    ContourPoint::new(x, y, typ, false, None, None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use druid::kurbo::{Rect, Shape};
+
+    fn square(x: f64, y: f64, size: f64) -> BezPath {
+        Rect::new(x, y, x + size, y + size).to_path(0.1)
+    }
+
+    fn area(contours: &[Contour]) -> f64 {
+        contours
+            .iter()
+            .map(|contour| {
+                let mut bez = BezPath::new();
+                crate::data::append_contour(&mut bez, contour);
+                bez.area().abs()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn overlapping_contours() {
+        let squares = [square(0., 0., 100.), square(50., 50., 100.)];
+        let union = union_of_paths(&squares, Precision::Integer);
+        assert_eq!(union.len(), 1);
+        assert!((area(&union) - 17500.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn touching_contours() {
+        // squares that share an edge cover the same area once merged
+        let squares = [square(0., 0., 100.), square(100., 0., 100.)];
+        let union = union_of_paths(&squares, Precision::Integer);
+        assert!((area(&union) - 20000.0).abs() < 1.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn unconverted_contours_are_kept() {
+        let point = |x, y| ContourPoint::new(x, y, PointType::Line, false, None, None, None);
+        let square_contour = |x: f32, y: f32| {
+            let points = vec![
+                point(x, y),
+                point(x + 100., y),
+                point(x + 100., y + 100.),
+                point(x, y + 100.),
+            ];
+            Contour::new(points, None, None)
+        };
+        // flo_curves has no use for a contour that is a single point
+        let lone = Contour::new(vec![point(20., 20.)], None, None);
+        let mut glyph = Glyph::new_named("a");
+        glyph.outline = Some(norad::glyph::Outline {
+            contours: vec![lone, square_contour(0., 0.), square_contour(50., 50.)],
+            components: Vec::new(),
+        });
+
+        let merged = remove_overlaps(&Arc::new(glyph), Precision::Integer);
+        let contours = &merged.outline.as_ref().unwrap().contours;
+        assert_eq!(contours.len(), 2);
+        assert!(contours
+            .iter()
+            .any(|c| c.points.len() == 1 && c.points[0].x == 20. && c.points[0].y == 20.));
+        assert!((area(contours) - 17500.0).abs() < 1.0);
+
+        let mut dot = BezPath::new();
+        dot.move_to((20., 20.));
+        let union = union_of_paths(&[square(0., 0., 100.), dot], Precision::Integer);
+        assert_eq!(union.len(), 2);
+        assert_eq!(union[1].points.len(), 1);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn fractional_points() {
+        let squares = [square(0.25, 0.25, 100.), square(50.5, 50.5, 100.)];
+        let has_corner = |contours: &[Contour]| {
+            contours[0]
+                .points
+                .iter()
+                .any(|pt| pt.x == 150.5 && pt.y == 150.5)
+        };
+        assert!(has_corner(&union_of_paths(&squares, Precision::Hundredths)));
+        assert!(!has_corner(&union_of_paths(&squares, Precision::Integer)));
+    }
+}
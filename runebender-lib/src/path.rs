@@ -1,15 +1,24 @@
+use std::collections::BTreeSet;
+
 use super::cubic_path::CubicPath;
-use super::design_space::{DPoint, DVec2, ViewPort};
+use super::design_space::{DPoint, DVec2, Precision, ViewPort};
 use super::hyper_path::{HyperPath, HyperSegment, HYPERBEZ_LIB_VERSION_KEY};
 use super::point::{EntityId, PathPoint};
 use super::point_list::{PathPoints, RawSegment};
 use druid::kurbo::{
-    Affine, BezPath, Line, LineIntersection, ParamCurve, ParamCurveNearest, PathSeg, Point, Vec2,
+    Affine, BezPath, Line, LineIntersection, ParamCurve, ParamCurveExtrema, ParamCurveNearest,
+    PathSeg, Point, Rect, Vec2,
 };
 use druid::Data;
 
 use crate::selection::Selection;
 
+/// Extrema closer than this to either end of a segment, in `t`, are ignored.
+const EXTREMA_MIN_T: f64 = 0.01;
+/// How far, in design units, a segment must reach past its ends for a point
+/// to be added at its extremum.
+const EXTREMA_TOLERANCE: f64 = 0.5;
+
 #[derive(Debug, Clone, Data)]
 pub enum Path {
     Cubic(CubicPath),
@@ -31,15 +40,22 @@ impl Path {
         Path::Hyper(HyperPath::new(point))
     }
 
-    pub fn from_norad(src: &norad::glyph::Contour) -> Path {
+    /// Create a path from a norad contour, with its points rounded to
+    /// `precision`.
+    ///
+    /// Quadratic segments are converted to cubics.
+    pub fn from_norad(src: &norad::glyph::Contour, precision: Precision) -> Path {
+        if crate::quadratic::is_quadratic(src) {
+            return Path::from_norad(&crate::quadratic::to_cubic(src), precision);
+        }
         if src
             .lib()
             .map(|lib| lib.contains_key(HYPERBEZ_LIB_VERSION_KEY))
             .unwrap_or(false)
         {
-            HyperPath::from_norad(src).into()
+            HyperPath::from_norad(src, precision).into()
         } else {
-            CubicPath::from_norad(src).into()
+            CubicPath::from_norad(src, precision).into()
         }
     }
 
@@ -77,10 +93,30 @@ impl Path {
         }
     }
 
+    /// A copy of this path, with new ids.
+    pub(crate) fn duplicate(&self) -> Path {
+        match self {
+            Path::Cubic(path) => {
+                CubicPath::from_path_points_unchecked(path.path_points().duplicate()).into()
+            }
+            Path::Hyper(path) => {
+                HyperPath::from_path_points_unchecked(path.path_points().duplicate()).into()
+            }
+        }
+    }
+
     pub fn is_closed(&self) -> bool {
         self.path_points().closed()
     }
 
+    /// A number that changes whenever this path's points change.
+    ///
+    /// This is cheaper to compare than the path itself, and can be stored
+    /// instead of a copy of the path to check whether cached data is stale.
+    pub fn generation(&self) -> u64 {
+        self.path_points().generation()
+    }
+
     pub fn points(&self) -> &[PathPoint] {
         match self {
             Path::Cubic(path) => path.path_points().as_slice(),
@@ -131,18 +167,42 @@ impl Path {
     ///
     /// `scale` is the new scale, as a ratio.
     /// `anchor` is a point on the screen that should remain fixed.
-    pub(crate) fn scale_points(&mut self, points: &[EntityId], scale: Vec2, anchor: DPoint) {
+    pub(crate) fn scale_points(
+        &mut self,
+        points: &[EntityId],
+        scale: Vec2,
+        anchor: DPoint,
+        precision: Precision,
+    ) {
         let scale_xform = Affine::scale_non_uniform(scale.x, scale.y);
+        self.transform_points(points, scale_xform, anchor, precision);
+    }
+
+    /// Apply an arbitrary transform to the selection.
+    ///
+    /// `anchor` is treated as the origin when applying the transform.
+    pub(crate) fn transform_points(
+        &mut self,
+        points: &[EntityId],
+        affine: Affine,
+        anchor: DPoint,
+        precision: Precision,
+    ) {
         self.path_points_mut()
-            .transform_points(points, scale_xform, anchor);
+            .transform_points(points, affine, anchor, precision);
         self.after_change();
     }
 
     pub(crate) fn nudge_points(&mut self, points: &[EntityId], v: DVec2) {
         let affine = Affine::translate(v.to_raw());
-        let transformed = self
-            .path_points_mut()
-            .transform_points(points, affine, DPoint::ZERO);
+        // the points and `v` are already rounded, so only rounding errors
+        // need to be removed.
+        let transformed = self.path_points_mut().transform_points(
+            points,
+            affine,
+            DPoint::ZERO,
+            Precision::FINEST,
+        );
         if self.is_hyper() {
             for point in points {
                 // if this is an off-curve, and its neighbouring on-curve or
@@ -167,9 +227,102 @@ impl Path {
         self.after_change();
     }
 
+    /// Nudge the provided points, scaling the handles of adjacent curve
+    /// segments to preserve their shape.
+    ///
+    /// Hyperbezier paths resolve their own handles, so they are nudged normally.
+    pub(crate) fn nudge_points_interpolated(
+        &mut self,
+        points: &[EntityId],
+        v: DVec2,
+        precision: Precision,
+    ) {
+        if self.is_hyper() {
+            self.nudge_points(points, v);
+        } else {
+            self.path_points_mut()
+                .nudge_points_interpolated(points, v, precision);
+        }
+    }
+
+    /// Harmonize each of the provided smooth points with its handles.
+    ///
+    /// This only applies to cubic paths; hyperbeziers are already smooth.
+    pub(crate) fn harmonize_points(&mut self, points: &[EntityId], precision: Precision) {
+        if let Path::Cubic(path) = self {
+            for point in points {
+                path.path_points_mut().harmonize_point(*point, precision);
+            }
+        }
+    }
+
+    /// Balance the handles of every curve segment whose start and end points
+    /// are both in `selection`, unless one of its handles is in `locked`.
+    pub(crate) fn balance_segments(
+        &mut self,
+        selection: &Selection,
+        locked: &BTreeSet<EntityId>,
+        precision: Precision,
+    ) {
+        if let Path::Cubic(path) = self {
+            let segments = path
+                .iter_segments()
+                .filter(|seg| {
+                    selection.contains(&seg.start_id()) && selection.contains(&seg.end_id())
+                })
+                .filter(|seg| !seg.iter_ids().any(|id| locked.contains(&id)))
+                .collect::<Vec<_>>();
+            for seg in segments {
+                path.path_points_mut().balance_segment(&seg, precision);
+            }
+        }
+    }
+
+    /// Remove points that aren't needed to draw this path, within `tolerance`.
+    ///
+    /// This only applies to cubic paths; a hyperbezier's points are already
+    /// as few as its shape needs.
+    pub(crate) fn simplify(
+        &mut self,
+        tolerance: f64,
+        locked: &BTreeSet<EntityId>,
+        precision: Precision,
+    ) {
+        if let Path::Cubic(path) = self {
+            path.path_points_mut()
+                .simplify(tolerance, locked, precision);
+        }
+    }
+
+    /// Add on-curve points at the horizontal and vertical extrema of each
+    /// curve, where there aren't points already.
+    ///
+    /// This only applies to cubic paths.
+    pub(crate) fn add_extrema(&mut self, precision: Precision) {
+        let path = match self {
+            Path::Cubic(path) => path,
+            Path::Hyper(_) => return,
+        };
+        // a curve has at most four extrema; this guards against splits
+        // that never settle because of rounding.
+        let max_splits = path.iter_segments().count() * 4;
+        for _ in 0..max_splits {
+            let split = path
+                .iter_segments()
+                .find_map(|seg| interior_extremum(seg.to_kurbo()).map(|t| (seg, t)));
+            match split {
+                Some((seg, t)) => path.split_segment_at_point(seg, t, precision),
+                None => break,
+            }
+        }
+    }
+
     pub(crate) fn nudge_all_points(&mut self, v: DVec2) {
         let affine = Affine::translate(v.to_raw());
-        self.path_points_mut().transform_all(affine, DPoint::ZERO);
+        // as in `nudge_points`, nothing needs rounding to the session's
+        // precision.
+        self.path_points_mut()
+            .transform_all(affine, DPoint::ZERO, Precision::FINEST);
         self.after_change();
     }
 
@@ -212,6 +365,51 @@ impl Path {
         self.after_change();
     }
 
+    /// Make the on-curve point `point` the start of this path.
+    ///
+    /// Returns `false` if it can't be; see [`PathPoints::set_start_point`].
+    pub(crate) fn set_start_point(&mut self, point: EntityId) -> bool {
+        let changed = self.path_points_mut().set_start_point(point);
+        if changed {
+            self.after_change();
+        }
+        changed
+    }
+
+    /// Append another open path to the end of this one, returning the id of
+    /// the point where they meet.
+    ///
+    /// If `merge` is true, this path's last point replaces the other path's
+    /// first point. Otherwise the two paths are joined with a line, or with a
+    /// curve if this path has a trailing handle.
+    pub(crate) fn append(&mut self, other: &Path, merge: bool, precision: Precision) -> EntityId {
+        let first = self.path_points_mut().append(other.path_points(), merge);
+        if !merge && self.trailing().is_some() {
+            if let Some(seg) = self.iter_segments().find(|seg| seg.end_id() == first) {
+                self.upgrade_line_seg(&seg, true, precision);
+            }
+        }
+        self.clear_trailing();
+        self.after_change();
+        first
+    }
+
+    /// Close this open path, merging its last point into its first.
+    pub(crate) fn close_merging_ends(&mut self) -> EntityId {
+        let id = self.path_points_mut().close_merging_ends();
+        self.after_change();
+        id
+    }
+
+    /// Open this closed path at an on-curve point.
+    ///
+    /// Returns the id of the path's new last point, a copy of `point`.
+    pub(crate) fn break_at(&mut self, point: EntityId) -> Option<EntityId> {
+        let end = self.path_points_mut().break_at(point)?;
+        self.after_change();
+        Some(end)
+    }
+
     fn after_change(&mut self) {
         if let Path::Hyper(path) = self {
             path.after_change();
@@ -237,37 +435,48 @@ impl Path {
             .filter(move |seg| points.contains(&seg.start_id()) && points.contains(&seg.end_id()))
     }
 
-    pub(crate) fn split_segment_at_point(&mut self, seg: Segment, t: f64) {
+    pub(crate) fn split_segment_at_point(&mut self, seg: Segment, t: f64, precision: Precision) {
         match self {
             Path::Cubic(path) => {
                 if let Segment::Cubic(seg) = seg {
-                    path.split_segment_at_point(seg, t);
+                    path.split_segment_at_point(seg, t, precision);
                 }
             }
             Path::Hyper(path) => {
                 if let Segment::Hyper(seg) = seg {
-                    path.split_segment_at_point(seg, t);
+                    path.split_segment_at_point(seg, t, precision);
                 }
             }
         }
         self.after_change();
     }
 
+    /// Downgrade a curve segment to a line.
+    pub(crate) fn downgrade_curve_seg(&mut self, seg: &Segment) {
+        self.path_points_mut().downgrade_curve_seg(seg.start_id());
+        self.after_change();
+    }
+
     /// Upgrade a line segment to a cubic bezier.
     ///
     /// If 'use trailing' is true, this will use the trailing point to populate
     /// the first handle.
-    pub(crate) fn upgrade_line_seg(&mut self, seg: &Segment, use_trailing: bool) {
+    pub(crate) fn upgrade_line_seg(
+        &mut self,
+        seg: &Segment,
+        use_trailing: bool,
+        precision: Precision,
+    ) {
         let cursor = self.path_points_mut().cursor(Some(seg.start_id()));
         let p0 = *bail!(cursor.point());
         let p3 = *bail!(cursor.peek_next(), "segment has correct number of points");
-        let p1 = p0.point.lerp(p3.point, 1.0 / 3.0);
+        let p1 = p0.point.lerp(p3.point, 1.0 / 3.0, precision);
         let p1 = if use_trailing {
             self.path_points_mut().take_trailing().unwrap_or(p1)
         } else {
             p1
         };
-        let p2 = p0.point.lerp(p3.point, 2.0 / 3.0);
+        let p2 = p0.point.lerp(p3.point, 2.0 / 3.0, precision);
         let path = seg.start_id().parent();
         let (p1, p2) = if self.is_hyper() {
             (
@@ -357,6 +566,45 @@ impl Path {
         }
     }
 
+    /// Move the handles of a cubic segment so that its point at `t` moves
+    /// by `delta`, leaving its end points in place.
+    ///
+    /// `seg` is the segment as it was before the bend began; each handle
+    /// does more of the work the nearer `t` is to its end of the segment.
+    pub(crate) fn bend_segment(
+        &mut self,
+        seg: &Segment,
+        t: f64,
+        delta: DVec2,
+        precision: Precision,
+    ) {
+        let (p1, p2) = match seg {
+            Segment::Cubic(RawSegment::Cubic(_, p1, p2, _)) => (*p1, *p2),
+            _ => return,
+        };
+        let (w1, w2) = bend_weights(t);
+        let delta = delta.to_raw();
+        self.move_points(&[
+            (
+                p1.id,
+                DPoint::from_raw(p1.point.to_raw() + delta * w1, precision),
+            ),
+            (
+                p2.id,
+                DPoint::from_raw(p2.point.to_raw() + delta * w2, precision),
+            ),
+        ]);
+    }
+
+    /// Move each of the given points to a new position.
+    pub(crate) fn move_points(&mut self, positions: &[(EntityId, DPoint)]) {
+        for (id, pos) in positions {
+            self.path_points_mut()
+                .with_point_mut(*id, |pp| pp.point = *pos);
+        }
+        self.after_change();
+    }
+
     /// Set one of a given point's axes to a new value; used when aligning a set
     /// of points.
     pub(crate) fn align_point(&mut self, point: EntityId, val: f64, set_x: bool) {
@@ -412,6 +660,16 @@ impl Path {
     }
 }
 
+/// The `t` of an extremum of this segment that reaches past its ends, if any.
+fn interior_extremum(seg: PathSeg) -> Option<f64> {
+    let ends = Rect::from_points(seg.eval(0.0), seg.eval(1.0))
+        .inflate(EXTREMA_TOLERANCE, EXTREMA_TOLERANCE);
+    seg.extrema()
+        .into_iter()
+        .filter(|t| *t > EXTREMA_MIN_T && *t < 1.0 - EXTREMA_MIN_T)
+        .find(|t| !ends.contains(seg.eval(*t)))
+}
+
 /// Walk the points in a list and mark those that look like tangent points
 /// as being tangent points (OnCurveSmooth).
 pub(crate) fn mark_tangent_handles(points: &mut [PathPoint]) {
@@ -549,6 +807,29 @@ impl Segment {
     //}
 }
 
+/// How far each handle of a cubic bezier must move for every unit its
+/// point at `t` moves.
+///
+/// The split between the handles follows Inkscape's curve dragging: near an
+/// end, only the nearer handle moves.
+fn bend_weights(t: f64) -> (f64, f64) {
+    // near the ends the handles would have to move very far
+    let t = t.max(0.05).min(0.95);
+    let second_share = if t <= 1.0 / 6.0 {
+        0.0
+    } else if t <= 0.5 {
+        ((6.0 * t - 1.0) / 2.0).powi(3) / 2.0
+    } else if t <= 5.0 / 6.0 {
+        (1.0 - ((6.0 * (1.0 - t) - 1.0) / 2.0).powi(3)) / 2.0 + 0.5
+    } else {
+        1.0
+    };
+    (
+        (1.0 - second_share) / (3.0 * t * (1.0 - t).powi(2)),
+        second_share / (3.0 * t.powi(2) * (1.0 - t)),
+    )
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "lowercase")]
 enum SerializePath {
@@ -607,3 +888,49 @@ impl From<HyperSegment> for Segment {
         Segment::Hyper(src)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use druid::kurbo::CubicBez;
+
+    #[test]
+    fn bend_moves_the_dragged_point() {
+        let curve = CubicBez::new((0., 0.), (10., 40.), (60., 40.), (100., 0.));
+        let delta = Vec2::new(5.0, 12.0);
+        for &t in &[0.1, 0.3, 0.5, 0.7, 0.9] {
+            let (w1, w2) = bend_weights(t);
+            let bent = CubicBez::new(
+                curve.p0,
+                curve.p1 + delta * w1,
+                curve.p2 + delta * w2,
+                curve.p3,
+            );
+            let moved = bent.eval(t) - curve.eval(t);
+            assert!((moved - delta).hypot() < 1e-9, "t {}: {:?}", t, moved);
+        }
+    }
+
+    #[test]
+    fn add_extrema_to_arch() {
+        let mut bez = BezPath::new();
+        bez.move_to((0., 0.));
+        bez.curve_to((0., 100.), (100., 100.), (100., 0.));
+        bez.close_path();
+        let mut path: Path = CubicPath::from_bezpath(bez, Precision::Integer)
+            .unwrap()
+            .into();
+        path.add_extrema(Precision::Integer);
+        let on_curve = path.points().iter().filter(|p| p.is_on_curve()).count();
+        assert_eq!(on_curve, 3);
+        assert!(path
+            .points()
+            .iter()
+            .any(|p| p.is_on_curve() && p.point == DPoint::new(50., 75.)));
+
+        // adding them again changes nothing
+        let len = path.points().len();
+        path.add_extrema(Precision::Integer);
+        assert_eq!(path.points().len(), len);
+    }
+}
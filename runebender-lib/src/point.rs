@@ -3,7 +3,7 @@
 //! This is intended to be agnostic to whether the path is a bezier or a
 //! hyperbezier.
 
-use super::design_space::{DPoint, DVec2, ViewPort};
+use super::design_space::{DPoint, DVec2, Precision, ViewPort};
 
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -144,11 +144,14 @@ impl PointType {
             .unwrap_or(false);
         match &norad_point.typ {
             NoradPointType::OffCurve => PointType::OffCurve { auto },
-            NoradPointType::QCurve => panic!(
-                "quadratics unsupported, we should have \
-                         validated input before now"
-            ),
-            NoradPointType::Move | NoradPointType::Line | NoradPointType::Curve if smooth => {
+            // quadratic contours are converted to cubics when they're loaded,
+            // but we treat a stray quadratic point like any other on-curve point.
+            NoradPointType::Move
+            | NoradPointType::Line
+            | NoradPointType::Curve
+            | NoradPointType::QCurve
+                if smooth =>
+            {
                 PointType::OnCurve { smooth: true }
             }
             _other => PointType::OnCurve { smooth: false },
@@ -221,12 +224,12 @@ impl PathPoint {
     ///
     /// The `anchor` argument is a point that should be treated as the origin
     /// when applying the transform, which is used for things like scaling from
-    /// a fixed point.
-    pub fn transform(&mut self, affine: Affine, anchor: DVec2) {
+    /// a fixed point. The new position is rounded to `precision`.
+    pub fn transform(&mut self, affine: Affine, anchor: DVec2, precision: Precision) {
         let anchor = anchor.to_raw();
         let current = self.point.to_raw() - anchor;
         let new = affine * current + anchor;
-        self.point = DPoint::from_raw(new);
+        self.point = DPoint::from_raw(new, precision);
     }
 
     pub fn to_kurbo(self) -> Point {
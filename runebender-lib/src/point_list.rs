@@ -1,13 +1,13 @@
 /// Raw storage for the points that make up a glyph contour
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::ops::Range;
 use std::sync::Arc;
 
-use super::design_space::{DPoint, DVec2};
-use super::point::{EntityId, PathPoint};
+use super::design_space::{DPoint, DVec2, Precision};
+use super::point::{EntityId, PathPoint, PointType};
 use super::selection::Selection;
 
-use druid::kurbo::{Affine, CubicBez, Line, ParamCurve, PathSeg};
+use druid::kurbo::{Affine, CubicBez, Line, ParamCurve, PathSeg, Point};
 use druid::Data;
 
 #[derive(Clone, Data)]
@@ -57,6 +57,9 @@ mod protected {
     #[derive(Clone, Data)]
     pub(super) struct RawPoints {
         points: Arc<Vec<PathPoint>>,
+        /// Changes every time the points are mutated. This catches changes
+        /// made in place, which comparing the `Arc` alone would miss.
+        generation: u64,
         // these two use interior mutability so that we can rebuild the indices
         // in things like getters
         #[data(ignore)]
@@ -69,6 +72,7 @@ mod protected {
         pub(super) fn new(points: Vec<PathPoint>) -> Self {
             RawPoints {
                 points: Arc::new(points),
+                generation: next_generation(),
                 indices: RefCell::new(Arc::new(HashMap::new())),
                 needs_to_rebuild_indicies: Cell::new(true),
             }
@@ -85,10 +89,15 @@ mod protected {
             &self.points
         }
 
+        pub(super) fn generation(&self) -> u64 {
+            self.generation
+        }
+
         /// All mutable access invalidates the index map. It should be
         /// avoided unless actual mutation is going to occur.
         pub(super) fn as_mut(&mut self) -> &mut Vec<PathPoint> {
             self.set_needs_rebuild();
+            self.generation = next_generation();
             Arc::make_mut(&mut self.points)
         }
 
@@ -131,6 +140,7 @@ mod protected {
         pub(super) fn with_mut(&mut self, item: EntityId, f: impl FnOnce(&mut PathPoint)) {
             self.rebuild_if_needed();
             if let Some(idx) = self.index_for_point(item) {
+                self.generation = next_generation();
                 if let Some(val) = Arc::make_mut(&mut self.points).get_mut(idx) {
                     f(val);
                     val.id = item;
@@ -143,6 +153,14 @@ mod protected {
         }
     }
 
+    /// Generations are unique across all paths, so that a path's generation
+    /// identifies its points, even between copies of the path.
+    fn next_generation() -> u64 {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        static NEXT_GENERATION: AtomicU64 = AtomicU64::new(0);
+        NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+    }
+
     impl std::fmt::Debug for RawPoints {
         fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
             for pt in self.points.as_ref() {
@@ -174,6 +192,11 @@ impl PathPoints {
         PathPoints::from_raw_parts(new_parent, points, None, None, closed)
     }
 
+    /// A copy of these points, as a new path with new ids.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self::from_points_ignoring_parent(self.iter_points().collect(), self.closed)
+    }
+
     pub fn from_raw_parts(
         path_id: EntityId,
         points: Vec<PathPoint>,
@@ -238,6 +261,14 @@ impl PathPoints {
         self.points.as_ref()
     }
 
+    /// A number that changes whenever these points change.
+    ///
+    /// Two paths with the same generation have the same points, so this can
+    /// be used to cheaply check whether cached data is stale.
+    pub(crate) fn generation(&self) -> u64 {
+        self.points.generation()
+    }
+
     pub(crate) fn points_mut(&mut self) -> &mut Vec<PathPoint> {
         self.points.as_mut()
     }
@@ -310,6 +341,121 @@ impl PathPoints {
         }
     }
 
+    /// Append the points of another open path, so that this path's last
+    /// point is joined to the other's first point.
+    ///
+    /// If `merge` is true, the other path's first point is dropped, and this
+    /// path's last point takes its place.
+    ///
+    /// Returns the id of the point where the paths meet, if they are merged,
+    /// or else the new id of the other path's first point.
+    pub(crate) fn append(&mut self, other: &PathPoints, merge: bool) -> EntityId {
+        assert!(!self.closed && !other.closed);
+        let path_id = self.path_id;
+        let points = other
+            .as_slice()
+            .iter()
+            .skip(if merge { 1 } else { 0 })
+            .map(|pt| PathPoint {
+                id: EntityId::new_with_parent(path_id),
+                ..*pt
+            })
+            .collect::<Vec<_>>();
+        let first = match points.first() {
+            Some(pt) if !merge => pt.id,
+            _ => self.as_slice().last().unwrap().id,
+        };
+        self.points_mut().extend(points);
+        first
+    }
+
+    /// Close this path by merging its last point into its first.
+    ///
+    /// The two points should be at the same position.
+    pub(crate) fn close_merging_ends(&mut self) -> EntityId {
+        assert!(!self.closed && self.len() > 2);
+        self.points_mut().pop();
+        self.close()
+    }
+
+    /// Open this closed path at an on-curve point, so that the path starts
+    /// at that point and ends at a new copy of it.
+    ///
+    /// Returns the id of the new last point, or `None` if the path is
+    /// already open or `point` isn't one of its on-curve points.
+    pub(crate) fn break_at(&mut self, point: EntityId) -> Option<EntityId> {
+        if !self.closed {
+            return None;
+        }
+        let idx = self.points.index_for_point(point)?;
+        if !self.points.as_ref()[idx].is_on_curve() {
+            return None;
+        }
+        let path_id = self.path_id;
+        let points = self.points_mut();
+        points.rotate_left(idx);
+        let start = &mut points[0];
+        start.typ = PointType::OnCurve { smooth: false };
+        let end = PathPoint::on_curve(path_id, start.point);
+        points.push(end);
+        self.closed = false;
+        Some(end.id)
+    }
+
+    /// Make an on-curve point the start of this path.
+    ///
+    /// A closed path is rotated so that it starts at `point`; an open path
+    /// can only start at one of its ends, so it is reversed if `point` is its
+    /// last point. Returns `false` if nothing changed.
+    pub(crate) fn set_start_point(&mut self, point: EntityId) -> bool {
+        let idx = match self.points.index_for_point(point) {
+            Some(idx) if self.points.as_ref()[idx].is_on_curve() => idx,
+            _ => return false,
+        };
+        if self.closed {
+            if idx == self.len() - 1 {
+                return false;
+            }
+            self.points_mut().rotate_left(idx + 1);
+        } else {
+            if idx == 0 || idx != self.len() - 1 {
+                return false;
+            }
+            self.reverse_contour();
+        }
+        true
+    }
+
+    /// Make the curve starting at `start` a line, by removing its off-curve
+    /// points.
+    ///
+    /// Its ends become corners if they no longer have any handles.
+    pub(crate) fn downgrade_curve_seg(&mut self, start: EntityId) {
+        let start_idx = bail!(self.points.index_for_point(start));
+        let mut handles = Vec::new();
+        let mut idx = start_idx;
+        let end = loop {
+            idx = bail!(self.next_idx(idx));
+            let pt = self.points.as_ref()[idx];
+            if pt.is_on_curve() {
+                break pt.id;
+            }
+            handles.push(pt.id);
+        };
+        if handles.is_empty() {
+            return;
+        }
+        self.points_mut().retain(|pt| !handles.contains(&pt.id));
+        for id in [start, end].iter().copied() {
+            let has_handle = [self.prev_point(id), self.next_point(id)]
+                .iter()
+                .any(|pt| pt.map(|pt| pt.is_off_curve()).unwrap_or(false));
+            if !has_handle {
+                self.with_point_mut(id, |pt| pt.typ = PointType::OnCurve { smooth: false });
+            }
+        }
+    }
+
     /// Push a new on-curve point onto the end of the point list.
     ///
     /// The points must not be closed.
@@ -413,16 +559,16 @@ impl PathPoints {
         self.points.as_ref()[idx]
     }
 
-    pub fn transform_all(&mut self, affine: Affine, anchor: DPoint) {
+    pub fn transform_all(&mut self, affine: Affine, anchor: DPoint, precision: Precision) {
         let anchor = anchor.to_dvec2();
         self.points_mut()
             .iter_mut()
-            .for_each(|pt| pt.transform(affine, anchor));
+            .for_each(|pt| pt.transform(affine, anchor, precision));
 
         if let Some(trailing) = self.trailing() {
             //FIXME: what about the anchor?
             let new_trailing = affine * trailing.to_raw();
-            self.trailing = Some(DPoint::from_raw(new_trailing));
+            self.trailing = Some(DPoint::from_raw(new_trailing, precision));
         }
     }
 
@@ -437,21 +583,208 @@ impl PathPoints {
         points: &[EntityId],
         affine: Affine,
         anchor: DPoint,
+        precision: Precision,
     ) -> HashSet<EntityId> {
         let to_xform = self.points_for_points(points);
         let anchor = anchor.to_dvec2();
         for point in &to_xform {
             self.points
-                .with_mut(*point, |pt| pt.transform(affine, anchor));
+                .with_mut(*point, |pt| pt.transform(affine, anchor, precision));
             if let Some((on_curve, handle)) = self.tangent_handle(*point) {
                 if !to_xform.contains(&handle) {
-                    self.adjust_handle_angle(*point, on_curve, handle);
+                    self.adjust_handle_angle(*point, on_curve, handle, precision);
                 }
             }
         }
         to_xform
     }
 
+    /// Nudge the provided points, scaling the off-curve points of adjacent
+    /// curve segments so that the shape of those curves is preserved.
+    ///
+    /// For each cubic segment where only one of the two on-curve points is
+    /// moved, the off-curve points are scaled (independently on each axis)
+    /// between the fixed on-curve point and the moved one. If the segment
+    /// has no extent on an axis, the handles are moved as in a normal nudge.
+    pub fn nudge_points_interpolated(
+        &mut self,
+        points: &[EntityId],
+        delta: DVec2,
+        precision: Precision,
+    ) -> HashSet<EntityId> {
+        let segments = self.iter_segments().collect::<Vec<_>>();
+        let affine = Affine::translate(delta.to_raw());
+        let transformed = self.transform_points(points, affine, DPoint::ZERO, precision);
+
+        for seg in segments {
+            if let RawSegment::Cubic(p0, p1, p2, p3) = seg {
+                let (fixed, moved) = match (points.contains(&p0.id), points.contains(&p3.id)) {
+                    (true, false) => (p3.point, p0.point),
+                    (false, true) => (p0.point, p3.point),
+                    _ => continue,
+                };
+                let span = moved - fixed;
+                let new_span = span + delta;
+                for handle in [p1, p2].iter().filter(|p| !points.contains(&p.id)) {
+                    let current = match self.points.get(handle.id) {
+                        Some(pt) => pt.point,
+                        None => continue,
+                    };
+                    let scale_axis = |old: f64, current: f64, fixed: f64, span: f64, new: f64| {
+                        if span == 0.0 {
+                            current
+                        } else {
+                            fixed + (old - fixed) * new / span
+                        }
+                    };
+                    let x = scale_axis(handle.point.x, current.x, fixed.x, span.x, new_span.x);
+                    let y = scale_axis(handle.point.y, current.y, fixed.y, span.y, new_span.y);
+                    let new_pos = DPoint::from_raw((x, y), precision);
+                    self.points.with_mut(handle.id, |pt| pt.point = new_pos);
+                }
+            }
+        }
+        transformed
+    }
+
+    /// Move a smooth on-curve point along the line between its handles so that
+    /// the curvature is continuous across the point.
+    ///
+    /// The handles are not moved; the point is placed where it divides them
+    /// in the ratio that gives G2 continuity with the outer handles of the
+    /// adjacent segments.
+    pub(crate) fn harmonize_point(&mut self, point: EntityId, precision: Precision) {
+        let on_curve = bail!(self.path_point_for_id(point));
+        if !on_curve.is_smooth() {
+            return;
+        }
+        let a1 = bail!(self.prev_point(point).filter(PathPoint::is_off_curve));
+        let a0 = bail!(self.prev_point(a1.id).filter(PathPoint::is_off_curve));
+        let b1 = bail!(self.next_point(point).filter(PathPoint::is_off_curve));
+        let b2 = bail!(self.next_point(b1.id).filter(PathPoint::is_off_curve));
+
+        let (a0, a1, b1, b2) = (
+            a0.point.to_raw(),
+            a1.point.to_raw(),
+            b1.point.to_raw(),
+            b2.point.to_raw(),
+        );
+        let d = match line_intersection(a0, a1, b1, b2) {
+            Some(d) => d,
+            None => return,
+        };
+        let r0 = a0.distance(a1) / a1.distance(d);
+        let r1 = d.distance(b1) / b1.distance(b2);
+        let ratio = (r0 * r1).sqrt();
+        let t = ratio / (ratio + 1.0);
+        if !t.is_finite() {
+            return;
+        }
+
+        let new_pos = a1.lerp(b1, t);
+        self.points
+            .with_mut(point, |pt| pt.point = DPoint::from_raw(new_pos, precision));
+    }
+
+    /// Equalize the tension of the two handles in a cubic segment.
+    ///
+    /// The tension of a handle is its length relative to the distance from
+    /// its on-curve point to the intersection of the two handles; both handles
+    /// are set to the average tension, preserving their angles.
+    pub(crate) fn balance_segment(&mut self, seg: &RawSegment, precision: Precision) {
+        let (p0, p1, p2, p3) = match seg {
+            RawSegment::Cubic(p0, p1, p2, p3) => (*p0, *p1, *p2, *p3),
+            RawSegment::Line(..) => return,
+        };
+        let (start, end) = (p0.point.to_raw(), p3.point.to_raw());
+        let (c1, c2) = (p1.point.to_raw(), p2.point.to_raw());
+        let x = match line_intersection(start, c1, end, c2) {
+            Some(x) => x,
+            None => return,
+        };
+        let t1 = start.distance(c1) / start.distance(x);
+        let t2 = end.distance(c2) / end.distance(x);
+        let tension = (t1 + t2) / 2.0;
+        if !tension.is_finite() {
+            return;
+        }
+        let new_c1 = start.lerp(x, tension);
+        let new_c2 = end.lerp(x, tension);
+        self.points
+            .with_mut(p1.id, |pt| pt.point = DPoint::from_raw(new_c1, precision));
+        self.points
+            .with_mut(p2.id, |pt| pt.point = DPoint::from_raw(new_c2, precision));
+    }
+
+    /// Remove points wherever the outline can be refit with fewer segments,
+    /// staying within `tolerance` of the original.
+    ///
+    /// Only smooth points and points between two lines are removed, so
+    /// corners are preserved. Points in `locked`, and the segments they are
+    /// part of, are left as they are.
+    pub(crate) fn simplify(
+        &mut self,
+        tolerance: f64,
+        locked: &BTreeSet<EntityId>,
+        precision: Precision,
+    ) {
+        let segments = self.iter_segments().collect::<Vec<_>>();
+        if segments.is_empty() {
+            return;
+        }
+        let is_locked = |seg: &RawSegment| seg.iter_ids().any(|id| locked.contains(&id));
+        let removable = segments
+            .iter()
+            .enumerate()
+            .map(|(i, seg)| {
+                let prev = &segments[(i + segments.len() - 1) % segments.len()];
+                let both_lines =
+                    matches!((prev, seg), (RawSegment::Line(..), RawSegment::Line(..)));
+                (self.closed || i > 0)
+                    && (seg.start().is_smooth() || both_lines)
+                    && !is_locked(prev)
+                    && !is_locked(seg)
+            })
+            .collect::<Vec<_>>();
+        let kurbo_segments = segments
+            .iter()
+            .map(|seg| seg.to_kurbo())
+            .collect::<Vec<_>>();
+        let simplified =
+            crate::simplify::simplify(&kurbo_segments, &removable, self.closed, tolerance);
+        if simplified.len() == segments.len() {
+            return;
+        }
+
+        let mut points = Vec::new();
+        for (i, seg) in simplified {
+            points.push(segments[i].start());
+            if seg == kurbo_segments[i] {
+                // segments that weren't refit keep their handles
+                if let RawSegment::Cubic(_, p1, p2, _) = segments[i] {
+                    points.push(p1);
+                    points.push(p2);
+                }
+            } else if let PathSeg::Cubic(cubic) = seg {
+                points.push(PathPoint::off_curve(
+                    self.path_id,
+                    DPoint::from_raw(cubic.p1, precision),
+                ));
+                points.push(PathPoint::off_curve(
+                    self.path_id,
+                    DPoint::from_raw(cubic.p2, precision),
+                ));
+            }
+        }
+        if self.closed {
+            // the start point goes last
+            points.rotate_left(1);
+        } else {
+            points.push(segments[segments.len() - 1].end());
+        }
+        *self.points_mut() = points;
+    }
+
     /// For a list of points, returns a set including those points and any
     /// adjacent off-curve points.
     fn points_for_points(&mut self, points: &[EntityId]) -> HashSet<EntityId> {
@@ -474,14 +807,20 @@ impl PathPoints {
         to_xform
     }
 
-    pub fn update_handle(&mut self, bcp1: EntityId, mut dpt: DPoint, is_locked: bool) {
+    pub fn update_handle(
+        &mut self,
+        bcp1: EntityId,
+        mut dpt: DPoint,
+        is_locked: bool,
+        precision: Precision,
+    ) {
         if let Some((on_curve, bcp2)) = self.tangent_handle_opt(bcp1) {
             if is_locked {
                 dpt = dpt.axis_locked_to(bail!(self.points.get(on_curve)).point);
             }
             self.points.with_mut(bcp1, |p| p.point = dpt);
             if let Some(bcp2) = bcp2 {
-                self.adjust_handle_angle(bcp1, on_curve, bcp2);
+                self.adjust_handle_angle(bcp1, on_curve, bcp2, precision);
             }
         }
     }
@@ -489,7 +828,13 @@ impl PathPoints {
     /// Update a tangent handle in response to the movement of the partner handle.
     /// `bcp1` is the handle that has moved, and `bcp2` is the handle that needs
     /// to be adjusted.
-    fn adjust_handle_angle(&mut self, bcp1: EntityId, on_curve: EntityId, bcp2: EntityId) {
+    fn adjust_handle_angle(
+        &mut self,
+        bcp1: EntityId,
+        on_curve: EntityId,
+        bcp2: EntityId,
+        precision: Precision,
+    ) {
         let p1 = bail!(self.points.get(bcp1));
         let p2 = bail!(self.points.get(on_curve));
         let p3 = bail!(self.points.get(bcp2));
@@ -502,7 +847,7 @@ impl PathPoints {
         let norm_angle = raw_angle.normalize() * -1.0;
         let handle_len = (p3.point - p2.point).hypot();
 
-        let new_handle_offset = DVec2::from_raw(norm_angle * handle_len);
+        let new_handle_offset = DVec2::from_raw(norm_angle * handle_len, precision);
         let new_pos = p2.point + new_handle_offset;
         self.points.with_mut(bcp2, |pt| pt.point = new_pos)
     }
@@ -892,19 +1237,21 @@ impl RawSegment {
         }
     }
 
-    pub(crate) fn subsegment(self, range: Range<f64>) -> Self {
+    /// The part of this segment in `range`, with its new points rounded to
+    /// `precision`.
+    pub(crate) fn subsegment(self, range: Range<f64>, precision: Precision) -> Self {
         let subseg = self.to_kurbo().subsegment(range);
         let path_id = self.start_id().parent();
         match subseg {
             PathSeg::Line(Line { p0, p1 }) => RawSegment::Line(
-                PathPoint::on_curve(path_id, DPoint::from_raw(p0)),
-                PathPoint::on_curve(path_id, DPoint::from_raw(p1)),
+                PathPoint::on_curve(path_id, DPoint::from_raw(p0, precision)),
+                PathPoint::on_curve(path_id, DPoint::from_raw(p1, precision)),
             ),
             PathSeg::Cubic(CubicBez { p0, p1, p2, p3 }) => {
-                let p0 = PathPoint::on_curve(path_id, DPoint::from_raw(p0));
-                let p1 = PathPoint::off_curve(path_id, DPoint::from_raw(p1));
-                let p2 = PathPoint::off_curve(path_id, DPoint::from_raw(p2));
-                let p3 = PathPoint::on_curve(path_id, DPoint::from_raw(p3));
+                let p0 = PathPoint::on_curve(path_id, DPoint::from_raw(p0, precision));
+                let p1 = PathPoint::off_curve(path_id, DPoint::from_raw(p1, precision));
+                let p2 = PathPoint::off_curve(path_id, DPoint::from_raw(p2, precision));
+                let p3 = PathPoint::on_curve(path_id, DPoint::from_raw(p3, precision));
                 RawSegment::Cubic(p0, p1, p2, p3)
             }
             PathSeg::Quad(_) => panic!("quads are not supported"),
@@ -1039,6 +1386,19 @@ impl std::fmt::Debug for RawSegment {
     }
 }
 
+/// The intersection of the (infinite) lines through `a0, a1` and `b0, b1`,
+/// if they are not parallel.
+fn line_intersection(a0: Point, a1: Point, b0: Point, b1: Point) -> Option<Point> {
+    let da = a1 - a0;
+    let db = b1 - b0;
+    let denom = da.cross(db);
+    if denom.abs() < 1e-9 {
+        return None;
+    }
+    let t = (b0 - a0).cross(db) / denom;
+    Some(a0 + da * t)
+}
+
 impl std::fmt::Debug for PathPoints {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let closed = if self.closed { "Closed" } else { "Open" };
@@ -1082,6 +1442,102 @@ mod tests {
         assert!(points.debug_validate(), "{:?}", points);
     }
 
+    #[test]
+    fn downgrade_curve_seg() {
+        let mut points = PathPoints::new(DPoint::new(10., 10.));
+        let p1 = points.push_on_curve(DPoint::new(10., 20.));
+        points.push_on_curve(DPoint::new(20., 20.));
+        let p0 = points.close();
+
+        let path_id = points.id();
+        points.upgrade_line_seg(
+            p0,
+            PathPoint::off_curve(path_id, DPoint::new(15., 15.)),
+            PathPoint::off_curve(path_id, DPoint::new(5., 5.)),
+        );
+        points.with_point_mut(p1, |pt| pt.typ = PointType::OnCurve { smooth: true });
+        assert_eq!(points.len(), 5);
+
+        points.downgrade_curve_seg(p0);
+        assert!(points.debug_validate(), "{:?}", points);
+        assert_eq!(points.len(), 3);
+        assert!(points.as_slice().iter().all(|pt| pt.is_on_curve()));
+        assert!(!points.path_point_for_id(p1).unwrap().is_smooth());
+    }
+
+    #[test]
+    fn set_start_point() {
+        let mut points = PathPoints::new(DPoint::new(0., 0.));
+        let p1 = points.push_on_curve(DPoint::new(10., 0.));
+        let p2 = points.push_on_curve(DPoint::new(10., 10.));
+
+        assert!(!points.set_start_point(p1));
+        assert!(points.set_start_point(p2));
+        assert_eq!(points.start_point().id, p2);
+
+        let p0 = points.close();
+        assert_eq!(points.start_point().id, p0);
+        assert!(points.set_start_point(p1));
+        assert!(points.debug_validate(), "{:?}", points);
+        assert!(points.closed());
+        assert_eq!(points.start_point().id, p1);
+        assert!(!points.set_start_point(p1));
+    }
+
+    #[test]
+    fn append() {
+        let mut points = PathPoints::new(DPoint::new(0., 0.));
+        points.push_on_curve(DPoint::new(10., 0.));
+        let mut other = PathPoints::new(DPoint::new(20., 0.));
+        other.push_on_curve(DPoint::new(30., 0.));
+
+        let first = points.append(&other, false);
+        assert!(points.debug_validate(), "{:?}", points);
+        assert_eq!(points.len(), 4);
+        assert!(first.is_child_of(points.id()));
+        assert_eq!(
+            points.path_point_for_id(first).map(|pt| pt.point),
+            Some(DPoint::new(20., 0.))
+        );
+    }
+
+    #[test]
+    fn generation_changes_with_points() {
+        let mut points = PathPoints::new(DPoint::new(0., 0.));
+        let p1 = points.push_on_curve(DPoint::new(10., 0.));
+        let copy = points.clone();
+        assert_eq!(points.generation(), copy.generation());
+        assert!(points.same(&copy));
+
+        points.with_point_mut(p1, |pt| pt.point = DPoint::new(20., 0.));
+        assert_ne!(points.generation(), copy.generation());
+        assert!(!points.same(&copy));
+
+        // the points are no longer shared, so this mutates them in place
+        let generation = points.generation();
+        points.with_point_mut(p1, |pt| pt.point = DPoint::new(30., 0.));
+        assert_ne!(points.generation(), generation);
+    }
+
+    #[test]
+    fn break_at() {
+        let mut points = PathPoints::new(DPoint::new(0., 0.));
+        let p1 = points.push_on_curve(DPoint::new(10., 0.));
+        points.push_on_curve(DPoint::new(10., 10.));
+        points.close();
+
+        let end = points.break_at(p1).unwrap();
+        assert!(points.debug_validate(), "{:?}", points);
+        assert!(!points.closed());
+        assert_eq!(points.len(), 4);
+        assert_eq!(points.start_point().id, p1);
+        assert_eq!(points.as_slice().last().unwrap().id, end);
+        assert_eq!(
+            points.path_point_for_id(end).map(|pt| pt.point),
+            Some(DPoint::new(10., 0.))
+        );
+    }
+
     #[test]
     fn delete_points() {
         let path_id = EntityId::next();
@@ -1107,4 +1563,107 @@ mod tests {
         assert_eq!(points.clone().delete_points(&[p2.id]), Some(p1.id));
         assert_eq!(points.clone().delete_points(&[p0.id]), Some(p2.id));
     }
+
+    #[test]
+    fn balance_segment() {
+        let path_id = EntityId::next();
+        let p0 = PathPoint::on_curve(path_id, DPoint::new(0., 0.));
+        let p1 = PathPoint::off_curve(path_id, DPoint::new(0., 80.));
+        let p2 = PathPoint::off_curve(path_id, DPoint::new(60., 100.));
+        let p3 = PathPoint::on_curve(path_id, DPoint::new(100., 100.));
+        let mut points =
+            PathPoints::from_raw_parts(path_id, vec![p0, p1, p2, p3], None, None, false);
+
+        let seg = points.iter_segments().next().unwrap();
+        points.balance_segment(&seg, Precision::Integer);
+        let get = |id| points.path_point_for_id(id).unwrap().point;
+        assert_eq!(get(p1.id), DPoint::new(0., 60.));
+        assert_eq!(get(p2.id), DPoint::new(40., 100.));
+    }
+
+    #[test]
+    fn harmonize_point() {
+        let path_id = EntityId::next();
+        let pt = |x, y| DPoint::new(x, y);
+        let p0 = PathPoint::on_curve(path_id, pt(0., 0.));
+        let a0 = PathPoint::off_curve(path_id, pt(0., 50.));
+        let a1 = PathPoint::off_curve(path_id, pt(60., 100.));
+        let smooth = PathPoint::on_curve_smooth(path_id, pt(100., 100.));
+        let b1 = PathPoint::off_curve(path_id, pt(120., 100.));
+        let b2 = PathPoint::off_curve(path_id, pt(200., 50.));
+        let p3 = PathPoint::on_curve(path_id, pt(200., 0.));
+        let mut points = PathPoints::from_raw_parts(
+            path_id,
+            vec![p0, a0, a1, smooth, b1, b2, p3],
+            None,
+            None,
+            false,
+        );
+
+        points.harmonize_point(smooth.id, Precision::Integer);
+        let get = |id| points.path_point_for_id(id).unwrap().point;
+        // for these outer handles, the curvature matches when the smooth
+        // point is halfway between its handles; only the smooth point moves
+        assert_eq!(get(smooth.id), pt(90., 100.));
+        assert_eq!(get(a1.id), pt(60., 100.));
+        assert_eq!(get(b1.id), pt(120., 100.));
+        assert_eq!(get(a0.id), pt(0., 50.));
+        assert_eq!(get(b2.id), pt(200., 50.));
+    }
+
+    #[test]
+    fn harmonize_point_asymmetric() {
+        use druid::kurbo::ParamCurveCurvature;
+
+        let path_id = EntityId::next();
+        let pt = |x, y| DPoint::new(x, y);
+        let p0 = PathPoint::on_curve(path_id, pt(0., 0.));
+        let a0 = PathPoint::off_curve(path_id, pt(0., 40.));
+        let a1 = PathPoint::off_curve(path_id, pt(40., 100.));
+        let smooth = PathPoint::on_curve_smooth(path_id, pt(100., 100.));
+        let b1 = PathPoint::off_curve(path_id, pt(160., 100.));
+        let b2 = PathPoint::off_curve(path_id, pt(200., 70.));
+        let p3 = PathPoint::on_curve(path_id, pt(200., 0.));
+        let mut points = PathPoints::from_raw_parts(
+            path_id,
+            vec![p0, a0, a1, smooth, b1, b2, p3],
+            None,
+            None,
+            false,
+        );
+
+        points.harmonize_point(smooth.id, Precision::Hundredths);
+        let get = |id| points.path_point_for_id(id).unwrap().point.to_raw();
+        let before = CubicBez::new(get(p0.id), get(a0.id), get(a1.id), get(smooth.id));
+        let after = CubicBez::new(get(smooth.id), get(b1.id), get(b2.id), get(p3.id));
+        let (k0, k1) = (before.curvature(1.0), after.curvature(0.0));
+        assert!(
+            (k0 - k1).abs() < k0.abs() * 1e-3,
+            "curvature {} before the point, {} after",
+            k0,
+            k1
+        );
+        assert!(get(smooth.id).x > 110.0);
+        assert_eq!(get(a1.id), Point::new(40., 100.));
+        assert_eq!(get(b1.id), Point::new(160., 100.));
+    }
+
+    #[test]
+    fn nudge_points_interpolated() {
+        let path_id = EntityId::next();
+        let p0 = PathPoint::on_curve(path_id, DPoint::new(0., 0.));
+        let p1 = PathPoint::off_curve(path_id, DPoint::new(0., 50.));
+        let p2 = PathPoint::off_curve(path_id, DPoint::new(50., 100.));
+        let p3 = PathPoint::on_curve(path_id, DPoint::new(100., 100.));
+        let mut points =
+            PathPoints::from_raw_parts(path_id, vec![p0, p1, p2, p3], None, None, false);
+
+        let delta = DVec2::from_raw((100., 0.), Precision::Integer);
+        points.nudge_points_interpolated(&[p3.id], delta, Precision::Integer);
+        let get = |id| points.path_point_for_id(id).unwrap().point;
+        assert_eq!(get(p3.id), DPoint::new(200., 100.));
+        assert_eq!(get(p2.id), DPoint::new(100., 100.));
+        assert_eq!(get(p1.id), DPoint::new(0., 50.));
+        assert_eq!(get(p0.id), DPoint::new(0., 0.));
+    }
 }
@@ -0,0 +1,409 @@
+//! Converting quadratic (TrueType) contours to and from cubics.
+//!
+//! We only edit cubic paths, so glyphs with quadratic contours are converted
+//! to cubics when they are opened, and back to quadratics when they are
+//! saved. A quadratic curve can be represented exactly as a cubic, and the
+//! conversion back recognizes these curves, so glyphs that are opened and
+//! saved without being edited are unchanged; edited curves are approximated
+//! with as many quadratics as needed.
+
+use druid::kurbo::{CubicBez, ParamCurve, Point, Vec2};
+use norad::glyph::{Contour, ContourPoint, PointType};
+
+use crate::design_space::{self, Precision};
+
+/// The maximum distance, in design units, between an edited cubic curve
+/// and the quadratics that replace it.
+const APPROXIMATION_TOLERANCE: f64 = 1.0;
+/// The most quadratics a single cubic is split into.
+const MAX_QUADS_PER_CUBIC: usize = 16;
+/// How far a control point can be from the exact degree elevation of a
+/// quadratic, for the curve to be treated as that quadratic, in multiples of
+/// the precision's step.
+///
+/// Points are rounded to the precision when loaded, and implied on-curve
+/// points may be half a step from where they were.
+const ELEVATION_TOLERANCE: f64 = 0.75;
+
+/// `true` if any of the contour's segments are quadratic.
+pub(crate) fn is_quadratic(contour: &Contour) -> bool {
+    contour.points.iter().any(|pt| pt.typ == PointType::QCurve)
+}
+
+/// Convert the quadratic segments of a contour to cubics.
+///
+/// On-curve points implied by consecutive off-curve points are made
+/// explicit, as smooth points.
+pub(crate) fn to_cubic(contour: &Contour) -> Contour {
+    if !is_quadratic(contour) || contour.points.is_empty() {
+        return contour.clone();
+    }
+    convert_segments(contour, |from, off_curve, end, out| {
+        if end.typ != PointType::QCurve {
+            out.extend(off_curve.iter().cloned());
+            out.push(end.clone());
+            return;
+        }
+        if off_curve.is_empty() {
+            out.push(with_type(end, PointType::Line));
+            return;
+        }
+        let controls: Vec<Point> = off_curve.iter().map(position).collect();
+        for (i, control) in controls.iter().enumerate() {
+            let start = if i == 0 {
+                from
+            } else {
+                controls[i - 1].midpoint(*control)
+            };
+            let is_last = i == controls.len() - 1;
+            let end_pos = if is_last {
+                position(end)
+            } else {
+                control.midpoint(controls[i + 1])
+            };
+            out.push(off_curve_point(start + (*control - start) * (2.0 / 3.0)));
+            out.push(off_curve_point(
+                end_pos + (*control - end_pos) * (2.0 / 3.0),
+            ));
+            if is_last {
+                out.push(with_type(end, PointType::Curve));
+            } else {
+                out.push(ContourPoint::new(
+                    end_pos.x as f32,
+                    end_pos.y as f32,
+                    PointType::Curve,
+                    true,
+                    None,
+                    None,
+                    None,
+                ));
+            }
+        }
+    })
+}
+
+/// Convert the cubic segments of a contour to quadratics.
+///
+/// Smooth on-curve points that lie midway between two off-curve points are
+/// left implied. New points are rounded to `precision`.
+pub(crate) fn to_quadratic(contour: &Contour, precision: Precision) -> Contour {
+    if contour.points.is_empty() {
+        return contour.clone();
+    }
+    let converted = convert_segments(contour, |from, off_curve, end, out| {
+        if end.typ != PointType::Curve || off_curve.len() != 2 {
+            out.extend(off_curve.iter().cloned());
+            out.push(end.clone());
+            return;
+        }
+        let cubic = CubicBez::new(
+            from,
+            position(&off_curve[0]),
+            position(&off_curve[1]),
+            position(end),
+        );
+        let quads = cubic_to_quads(cubic, precision);
+        let last = quads.len() - 1;
+        for (i, (control, join)) in quads.into_iter().enumerate() {
+            out.push(off_curve_point(control));
+            if i == last {
+                out.push(with_type(end, PointType::QCurve));
+            } else {
+                out.push(ContourPoint::new(
+                    join.x as f32,
+                    join.y as f32,
+                    PointType::QCurve,
+                    true,
+                    None,
+                    None,
+                    None,
+                ));
+            }
+        }
+    });
+    remove_implied_points(converted, precision)
+}
+
+/// Call `convert` with each segment of the contour: the position it starts
+/// at, its off-curve points, and its end point. `convert` pushes the points
+/// that replace the segment.
+fn convert_segments(
+    contour: &Contour,
+    mut convert: impl FnMut(Point, &[ContourPoint], &ContourPoint, &mut Vec<ContourPoint>),
+) -> Contour {
+    let closed = contour.points[0].typ != PointType::Move;
+    let mut points = contour.points.clone();
+    let start = match points.iter().position(|pt| pt.typ != PointType::OffCurve) {
+        Some(idx) => {
+            points.rotate_left(idx);
+            points.remove(0)
+        }
+        // a closed quadratic contour can have no on-curve points, in which
+        // case there is one implied between the last and first points.
+        None => {
+            let implied = position(&points[points.len() - 1]).midpoint(position(&points[0]));
+            ContourPoint::new(
+                implied.x as f32,
+                implied.y as f32,
+                PointType::QCurve,
+                true,
+                None,
+                None,
+                None,
+            )
+        }
+    };
+
+    let mut out = Vec::with_capacity(points.len() + 1);
+    let mut current = position(&start);
+    let mut off_curve = Vec::new();
+    for pt in points {
+        if pt.typ == PointType::OffCurve {
+            off_curve.push(pt);
+        } else {
+            convert(current, &off_curve, &pt, &mut out);
+            current = position(&pt);
+            off_curve.clear();
+        }
+    }
+
+    if closed {
+        convert(current, &off_curve, &start, &mut out);
+        // the closing segment ends at the start; it goes first.
+        let start = out.pop().unwrap();
+        out.insert(0, start);
+    } else {
+        // trailing off-curve points in an open contour aren't part of any
+        // segment, but we keep them as they were.
+        out.extend(off_curve);
+        out.insert(0, start);
+    }
+    Contour::new(out, contour.identifier().cloned(), contour.lib().cloned())
+}
+
+/// Approximate a cubic curve with quadratics, returning the control point
+/// and end point of each.
+fn cubic_to_quads(cubic: CubicBez, precision: Precision) -> Vec<(Point, Point)> {
+    if let Some(control) = elevated_quad_control(cubic, precision) {
+        return vec![(control, cubic.p3)];
+    }
+    for n in 2..=MAX_QUADS_PER_CUBIC {
+        let pieces: Vec<CubicBez> = (0..n)
+            .map(|i| cubic.subsegment(i as f64 / n as f64..(i + 1) as f64 / n as f64))
+            .collect();
+        if pieces
+            .iter()
+            .all(|c| quad_error(*c) <= APPROXIMATION_TOLERANCE)
+            || n == MAX_QUADS_PER_CUBIC
+        {
+            let last = pieces.len() - 1;
+            return pieces
+                .iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let end = if i == last {
+                        cubic.p3
+                    } else {
+                        round(c.p3, precision)
+                    };
+                    (round(quad_control(*c), precision), end)
+                })
+                .collect();
+        }
+    }
+    unreachable!()
+}
+
+/// If this cubic is (up to rounding) a quadratic with a control point at a
+/// coordinate rounded to `precision`, return that control point.
+fn elevated_quad_control(cubic: CubicBez, precision: Precision) -> Option<Point> {
+    let estimate = quad_control(cubic);
+    let step = precision.step();
+    let tolerance = ELEVATION_TOLERANCE * step;
+    let axis = |p0: f64, c1: f64, c2: f64, p3: f64, estimate: f64| {
+        let base = design_space::round_coord(estimate, precision);
+        [base - step, base, base + step]
+            .iter()
+            .map(|c| {
+                let err1 = (p0 + (c - p0) * 2.0 / 3.0 - c1).abs();
+                let err2 = (p3 + (c - p3) * 2.0 / 3.0 - c2).abs();
+                (*c, err1.max(err2))
+            })
+            .filter(|(_, err)| *err <= tolerance)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(c, _)| c)
+    };
+    let CubicBez { p0, p1, p2, p3 } = cubic;
+    let x = axis(p0.x, p1.x, p2.x, p3.x, estimate.x)?;
+    let y = axis(p0.y, p1.y, p2.y, p3.y, estimate.y)?;
+    Some(Point::new(x, y))
+}
+
+/// The control point of the quadratic closest to this cubic.
+fn quad_control(cubic: CubicBez) -> Point {
+    let CubicBez { p0, p1, p2, p3 } = cubic;
+    (((p1.to_vec2() + p2.to_vec2()) * 3.0 - p0.to_vec2() - p3.to_vec2()) * 0.25).to_point()
+}
+
+/// The greatest distance between a cubic and the quadratic closest to it.
+fn quad_error(cubic: CubicBez) -> f64 {
+    let CubicBez { p0, p1, p2, p3 } = cubic;
+    let v: Vec2 = p3.to_vec2() - p2.to_vec2() * 3.0 + p1.to_vec2() * 3.0 - p0.to_vec2();
+    v.hypot() * 3f64.sqrt() / 36.0
+}
+
+/// Remove smooth, unnamed on-curve points that are implied by the off-curve
+/// points on either side of them, to within half a step of `precision`.
+fn remove_implied_points(contour: Contour, precision: Precision) -> Contour {
+    let closed = contour.points[0].typ != PointType::Move;
+    let len = contour.points.len();
+    let is_implied = |idx: usize| {
+        let pt = &contour.points[idx];
+        // we keep the first point, so closed contours start on-curve
+        if idx == 0 || !pt.smooth || pt.typ != PointType::QCurve {
+            return false;
+        }
+        if pt.name.is_some() || pt.identifier().is_some() || pt.lib().is_some() {
+            return false;
+        }
+        if !closed && idx == len - 1 {
+            return false;
+        }
+        let prev = &contour.points[idx - 1];
+        let next = &contour.points[(idx + 1) % len];
+        if prev.typ != PointType::OffCurve || next.typ != PointType::OffCurve {
+            return false;
+        }
+        let mid = position(prev).midpoint(position(next));
+        let tolerance = precision.step() / 2.0;
+        (mid.x - pt.x as f64).abs() <= tolerance && (mid.y - pt.y as f64).abs() <= tolerance
+    };
+    let points = (0..len)
+        .filter(|idx| !is_implied(*idx))
+        .map(|idx| contour.points[idx].clone())
+        .collect();
+    Contour::new(
+        points,
+        contour.identifier().cloned(),
+        contour.lib().cloned(),
+    )
+}
+
+fn position(pt: &ContourPoint) -> Point {
+    Point::new(pt.x as f64, pt.y as f64)
+}
+
+fn round(pt: Point, precision: Precision) -> Point {
+    Point::new(
+        design_space::round_coord(pt.x, precision),
+        design_space::round_coord(pt.y, precision),
+    )
+}
+
+fn off_curve_point(pt: Point) -> ContourPoint {
+    ContourPoint::new(
+        pt.x as f32,
+        pt.y as f32,
+        PointType::OffCurve,
+        false,
+        None,
+        None,
+        None,
+    )
+}
+
+fn with_type(pt: &ContourPoint, typ: PointType) -> ContourPoint {
+    let mut pt = pt.clone();
+    pt.typ = typ;
+    pt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contour(points: Vec<(f32, f32, PointType)>) -> Contour {
+        let points = points
+            .into_iter()
+            .map(|(x, y, typ)| ContourPoint::new(x, y, typ, false, None, None, None))
+            .collect();
+        Contour::new(points, None, None)
+    }
+
+    fn summary(contour: &Contour) -> Vec<String> {
+        contour
+            .points
+            .iter()
+            .map(|pt| format!("{} {} {:?}", pt.x, pt.y, pt.typ))
+            .collect()
+    }
+
+    /// Round every point, as happens when a contour is loaded into the editor.
+    fn rounded(contour: &Contour) -> Contour {
+        let points = contour
+            .points
+            .iter()
+            .map(|pt| {
+                let mut pt = pt.clone();
+                pt.x = pt.x.round();
+                pt.y = pt.y.round();
+                pt
+            })
+            .collect();
+        Contour::new(points, None, None)
+    }
+
+    #[test]
+    fn round_trip() {
+        use PointType::*;
+        // an 'o'-like shape, with an implied on-curve point on the top
+        let quadratic = contour(vec![
+            (0., 0., Line),
+            (0., 300., OffCurve),
+            (150., 300., OffCurve),
+            (300., 300., OffCurve),
+            (300., 0., QCurve),
+        ]);
+        let cubic = to_cubic(&quadratic);
+        assert!(!is_quadratic(&cubic));
+        // the two implied points become explicit
+        assert_eq!(cubic.points.iter().filter(|pt| pt.typ == Curve).count(), 3);
+        let back = to_quadratic(&rounded(&cubic), Precision::Integer);
+        assert_eq!(summary(&back), summary(&quadratic));
+    }
+
+    #[test]
+    fn fractional_round_trip() {
+        use PointType::*;
+        let quadratic = contour(vec![
+            (0., 0., Line),
+            (150.75, 300.75, OffCurve),
+            (300., 0., QCurve),
+        ]);
+        let cubic = to_cubic(&quadratic);
+        let back = to_quadratic(&cubic, Precision::Hundredths);
+        assert_eq!(summary(&back), summary(&quadratic));
+    }
+
+    #[test]
+    fn all_off_curve() {
+        use PointType::*;
+        let quadratic = contour(vec![
+            (0., 100., OffCurve),
+            (100., 100., OffCurve),
+            (100., 0., OffCurve),
+            (0., 0., OffCurve),
+        ]);
+        let cubic = to_cubic(&quadratic);
+        assert_eq!(cubic.points.iter().filter(|pt| pt.typ == Curve).count(), 4);
+        assert_eq!(cubic.points.len(), 12);
+    }
+
+    #[test]
+    fn approximate_cubic() {
+        let cubic = CubicBez::new((0., 0.), (0., 200.), (300., 200.), (300., 0.));
+        let quads = cubic_to_quads(cubic, Precision::Integer);
+        assert!(quads.len() > 1);
+        assert_eq!(quads.last().unwrap().1, Point::new(300., 0.));
+    }
+}
@@ -0,0 +1,145 @@
+//! Rasterizing glyphs, to preview how they look at small sizes.
+//!
+//! This is a simple supersampling rasterizer; it does no hinting, so what it
+//! shows is closest to an unhinted renderer, like the one used on macOS.
+
+use druid::kurbo::{BezPath, Point, Rect, Shape};
+
+/// The number of samples taken along each axis of a pixel.
+const SAMPLES_PER_AXIS: usize = 4;
+
+/// The coverage of each pixel touched by an outline.
+#[derive(Debug, Clone)]
+pub(crate) struct Bitmap {
+    /// The size of a pixel, in design units.
+    pub pixel_size: f64,
+    /// The pixel column and row of the bottom left pixel. Pixel (0, 0) has
+    /// its bottom left corner at the origin of design space.
+    pub origin: (i64, i64),
+    pub width: usize,
+    pub height: usize,
+    /// Coverage from 0.0 to 1.0, row by row, starting at the bottom.
+    coverage: Vec<f64>,
+}
+
+impl Bitmap {
+    /// The coverage of each pixel, along with its rectangle in design space.
+    pub fn pixels(&self) -> impl Iterator<Item = (Rect, f64)> + '_ {
+        self.coverage
+            .iter()
+            .enumerate()
+            .filter(|(_, coverage)| **coverage > 0.0)
+            .map(move |(i, coverage)| {
+                let col = self.origin.0 + (i % self.width) as i64;
+                let row = self.origin.1 + (i / self.width) as i64;
+                (self.pixel_rect(col, row), *coverage)
+            })
+    }
+
+    /// The bounds of the bitmap, in design space.
+    pub fn bounds(&self) -> Rect {
+        let (x, y) = self.origin;
+        self.pixel_rect(x, y)
+            .union(self.pixel_rect(x + self.width as i64 - 1, y + self.height as i64 - 1))
+    }
+
+    fn pixel_rect(&self, col: i64, row: i64) -> Rect {
+        let size = self.pixel_size;
+        Rect::new(
+            col as f64 * size,
+            row as f64 * size,
+            (col + 1) as f64 * size,
+            (row + 1) as f64 * size,
+        )
+    }
+}
+
+/// Rasterize `path` with the given pixel size, using the nonzero fill rule.
+///
+/// Returns `None` if the path is empty.
+pub(crate) fn rasterize(path: &BezPath, pixel_size: f64) -> Option<Bitmap> {
+    assert!(pixel_size > 0.0);
+    let bbox = path.bounding_box();
+    if path.elements().is_empty() || bbox.area() == 0.0 {
+        return None;
+    }
+    let x0 = (bbox.x0 / pixel_size).floor() as i64;
+    let y0 = (bbox.y0 / pixel_size).floor() as i64;
+    let width = ((bbox.x1 / pixel_size).ceil() as i64 - x0).max(1) as usize;
+    let height = ((bbox.y1 / pixel_size).ceil() as i64 - y0).max(1) as usize;
+
+    let step = pixel_size / SAMPLES_PER_AXIS as f64;
+    let total = (SAMPLES_PER_AXIS * SAMPLES_PER_AXIS) as f64;
+    let mut coverage = Vec::with_capacity(width * height);
+    for row in 0..height {
+        for col in 0..width {
+            let left = (x0 + col as i64) as f64 * pixel_size;
+            let bottom = (y0 + row as i64) as f64 * pixel_size;
+            let mut hits = 0;
+            for sy in 0..SAMPLES_PER_AXIS {
+                for sx in 0..SAMPLES_PER_AXIS {
+                    // sample at the center of each sub-pixel
+                    let pt = Point::new(
+                        left + (sx as f64 + 0.5) * step,
+                        bottom + (sy as f64 + 0.5) * step,
+                    );
+                    if path.winding(pt) != 0 {
+                        hits += 1;
+                    }
+                }
+            }
+            coverage.push(hits as f64 / total);
+        }
+    }
+
+    Some(Bitmap {
+        pixel_size,
+        origin: (x0, y0),
+        width,
+        height,
+        coverage,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect_path(rect: Rect) -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to((rect.x0, rect.y0));
+        path.line_to((rect.x1, rect.y0));
+        path.line_to((rect.x1, rect.y1));
+        path.line_to((rect.x0, rect.y1));
+        path.close_path();
+        path
+    }
+
+    #[test]
+    fn coverage() {
+        // two by two pixels, exactly on the grid
+        let bitmap = rasterize(&rect_path(Rect::new(0., 0., 200., 200.)), 100.).unwrap();
+        assert_eq!((bitmap.width, bitmap.height), (2, 2));
+        assert!(bitmap.pixels().all(|(_, c)| (c - 1.0).abs() < 1e-9));
+
+        // half a pixel off the grid on each side
+        let bitmap = rasterize(&rect_path(Rect::new(50., 0., 150., 100.)), 100.).unwrap();
+        assert_eq!(bitmap.origin, (0, 0));
+        assert_eq!((bitmap.width, bitmap.height), (2, 1));
+        let pixels = bitmap.pixels().collect::<Vec<_>>();
+        assert_eq!(pixels.len(), 2);
+        assert!(pixels.iter().all(|(_, c)| (c - 0.5).abs() < 1e-9));
+        assert_eq!(bitmap.bounds(), Rect::new(0., 0., 200., 100.));
+    }
+
+    #[test]
+    fn negative_coordinates() {
+        let bitmap = rasterize(&rect_path(Rect::new(-100., -150., 0., 0.)), 100.).unwrap();
+        assert_eq!(bitmap.origin, (-1, -2));
+        assert_eq!(bitmap.height, 2);
+        let pixels = bitmap.pixels().collect::<Vec<_>>();
+        assert_eq!(pixels[0].0, Rect::new(-100., -200., 0., -100.));
+        assert!((pixels[0].1 - 0.5).abs() < 1e-9);
+        assert!((pixels[1].1 - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,146 @@
+//! A second font, whose outlines are drawn behind the glyph being edited.
+//!
+//! This can be another UFO, or a compiled TrueType or OpenType font. Glyphs
+//! are matched by name, falling back to the glyph's first codepoint, and are
+//! scaled to the units per em of the font being edited.
+
+use std::path::Path;
+
+use druid::kurbo::{Affine, BezPath};
+use norad::{Glyph, GlyphName, Ufo};
+
+use crate::data::append_contour;
+
+/// How deeply nested components are followed in a reference UFO.
+const MAX_COMPONENT_DEPTH: usize = 8;
+
+/// A font loaded for comparison.
+pub struct ReferenceFont {
+    units_per_em: f64,
+    source: Source,
+}
+
+enum Source {
+    Ufo(Ufo),
+    /// The data of a compiled font, which is parsed as needed.
+    Compiled(Vec<u8>),
+}
+
+impl ReferenceFont {
+    /// Load a UFO, or a compiled font if the path is a file with a `.ttf`
+    /// or `.otf` extension.
+    pub fn load(path: &Path) -> Result<ReferenceFont, String> {
+        let is_compiled = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("ttf") || ext.eq_ignore_ascii_case("otf"))
+            .unwrap_or(false);
+        let (source, units_per_em) = if is_compiled {
+            let data = std::fs::read(path).map_err(|e| e.to_string())?;
+            let face = ttf_parser::Face::from_slice(&data, 0).map_err(|e| e.to_string())?;
+            let upm = face.units_per_em().unwrap_or(1000) as f64;
+            (Source::Compiled(data), upm)
+        } else {
+            let ufo = Ufo::load(path).map_err(|e| format!("{:?}", e))?;
+            let upm = ufo
+                .font_info
+                .as_ref()
+                .and_then(|info| info.units_per_em.map(|v| v.get()))
+                .unwrap_or(1000.);
+            (Source::Ufo(ufo), upm)
+        };
+        Ok(ReferenceFont {
+            units_per_em,
+            source,
+        })
+    }
+
+    /// The outline of the glyph matching `glyph`, scaled to `units_per_em`.
+    pub fn outline_for(&self, glyph: &Glyph, units_per_em: f64) -> Option<BezPath> {
+        let codepoint = glyph
+            .codepoints
+            .as_ref()
+            .and_then(|cps| cps.first().copied());
+        let mut outline = match &self.source {
+            Source::Ufo(ufo) => {
+                let name = Some(glyph.name.clone())
+                    .filter(|name| ufo.get_glyph(name).is_some())
+                    .or_else(|| codepoint.and_then(|cp| ufo_glyph_for_char(ufo, cp)))?;
+                let mut path = BezPath::new();
+                append_ufo_glyph(ufo, &name, Affine::default(), 0, &mut path);
+                path
+            }
+            Source::Compiled(data) => compiled_outline(data, &glyph.name, codepoint)?,
+        };
+        if self.units_per_em > 0.0 {
+            outline.apply_affine(Affine::scale(units_per_em / self.units_per_em));
+        }
+        Some(outline)
+    }
+}
+
+fn ufo_glyph_for_char(ufo: &Ufo, chr: char) -> Option<GlyphName> {
+    ufo.iter_names().find(|name| {
+        ufo.get_glyph(name)
+            .and_then(|glyph| glyph.codepoints.as_ref())
+            .map(|cps| cps.contains(&chr))
+            .unwrap_or(false)
+    })
+}
+
+fn append_ufo_glyph(ufo: &Ufo, name: &str, transform: Affine, depth: usize, path: &mut BezPath) {
+    let outline = match ufo.get_glyph(name).and_then(|g| g.outline.as_ref()) {
+        Some(outline) => outline,
+        None => return,
+    };
+    for contour in &outline.contours {
+        let mut contour_path = BezPath::new();
+        append_contour(&mut contour_path, contour);
+        contour_path.apply_affine(transform);
+        path.extend(contour_path.elements().iter().copied());
+    }
+    if depth < MAX_COMPONENT_DEPTH {
+        for component in &outline.components {
+            let affine: Affine = component.transform.into();
+            append_ufo_glyph(ufo, &component.base, transform * affine, depth + 1, path);
+        }
+    }
+}
+
+fn compiled_outline(data: &[u8], name: &str, codepoint: Option<char>) -> Option<BezPath> {
+    let face = ttf_parser::Face::from_slice(data, 0).ok()?;
+    let id = face
+        .glyph_index_by_name(name)
+        .or_else(|| codepoint.and_then(|cp| face.glyph_index(cp)))?;
+    let mut builder = OutlineBuilder(BezPath::new());
+    face.outline_glyph(id, &mut builder);
+    Some(builder.0)
+}
+
+struct OutlineBuilder(BezPath);
+
+impl ttf_parser::OutlineBuilder for OutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.move_to((x as f64, y as f64));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.line_to((x as f64, y as f64));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.quad_to((x1 as f64, y1 as f64), (x as f64, y as f64));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.curve_to(
+            (x1 as f64, y1 as f64),
+            (x2 as f64, y2 as f64),
+            (x as f64, y as f64),
+        );
+    }
+
+    fn close(&mut self) {
+        self.0.close_path();
+    }
+}
@@ -0,0 +1,327 @@
+//! Running scripts that edit the font.
+//!
+//! Scripts are written in [Rhai](https://rhai.rs), and see the font as the
+//! variable `font`:
+//!
+//! ```text
+//! for name in font.glyph_names {
+//!     let glyph = font.glyph(name);
+//!     glyph.move_by(10.0, 0.0);
+//!     glyph.width += 20.0;
+//! }
+//! font.x_height = 500.0;
+//! ```
+//!
+//! Coordinates and metrics are floats, and contour and point indices are
+//! integers. If a script fails, none of its changes are kept; changes to
+//! locked glyphs are never kept.
+
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+use norad::{Glyph, GlyphName, Ufo};
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Scope, FLOAT, INT};
+
+use crate::data::{FontMetrics, Workspace};
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// The number of operations a script may perform before it is stopped.
+///
+/// Scripts run on the UI thread, so this stops one that never finishes
+/// from hanging the app.
+const MAX_OPERATIONS: u64 = 10_000_000;
+
+/// The font being edited by a script.
+struct ScriptData {
+    ufo: Ufo,
+    metrics: FontMetrics,
+    /// All glyph names, in the order shown in the glyph grid.
+    names: Vec<GlyphName>,
+    /// The glyphs the script has modified.
+    changed: BTreeSet<GlyphName>,
+}
+
+/// The `font` variable.
+#[derive(Clone)]
+struct ScriptFont(Rc<RefCell<ScriptData>>);
+
+/// A glyph, as returned by `font.glyph(name)`.
+#[derive(Clone)]
+struct ScriptGlyph {
+    font: Rc<RefCell<ScriptData>>,
+    name: GlyphName,
+}
+
+impl ScriptFont {
+    fn glyph_names(&mut self) -> Array {
+        let data = self.0.borrow();
+        data.names
+            .iter()
+            .map(|name| Dynamic::from(name.to_string()))
+            .collect()
+    }
+
+    fn glyph(&mut self, name: &str) -> ScriptResult<ScriptGlyph> {
+        match self.0.borrow().ufo.get_glyph(name) {
+            Some(glyph) => Ok(ScriptGlyph {
+                font: self.0.clone(),
+                name: glyph.name.clone(),
+            }),
+            None => Err(format!("no glyph named '{}'", name).into()),
+        }
+    }
+
+    fn metric(&self, f: impl Fn(&FontMetrics) -> Option<f64>) -> Dynamic {
+        f(&self.0.borrow().metrics)
+            .map(Dynamic::from)
+            .unwrap_or(Dynamic::UNIT)
+    }
+
+    fn set_metric(&mut self, f: impl FnOnce(&mut FontMetrics)) {
+        f(&mut self.0.borrow_mut().metrics)
+    }
+}
+
+impl ScriptGlyph {
+    fn read<R>(&self, f: impl FnOnce(&Glyph) -> R) -> R {
+        let data = self.font.borrow();
+        f(data.ufo.get_glyph(&self.name).unwrap())
+    }
+
+    fn edit<R>(&self, f: impl FnOnce(&mut Glyph) -> R) -> R {
+        let mut data = self.font.borrow_mut();
+        data.changed.insert(self.name.clone());
+        f(data.ufo.get_glyph_mut(&self.name).unwrap())
+    }
+
+    fn name(&mut self) -> String {
+        self.name.to_string()
+    }
+
+    fn width(&mut self) -> FLOAT {
+        self.read(|glyph| glyph.advance.as_ref().map(|a| a.width).unwrap_or(0.) as FLOAT)
+    }
+
+    fn set_width(&mut self, width: FLOAT) {
+        self.edit(|glyph| {
+            glyph.advance.get_or_insert_with(Default::default).width = width as f32;
+        })
+    }
+
+    fn contour_count(&mut self) -> INT {
+        self.read(|glyph| {
+            glyph
+                .outline
+                .as_ref()
+                .map(|outline| outline.contours.len())
+                .unwrap_or(0) as INT
+        })
+    }
+
+    fn point_count(&mut self, contour: INT) -> ScriptResult<INT> {
+        self.read(|glyph| {
+            glyph
+                .outline
+                .as_ref()
+                .and_then(|outline| outline.contours.get(contour as usize))
+                .map(|contour| contour.points.len() as INT)
+                .ok_or_else(|| format!("no contour {}", contour).into())
+        })
+    }
+
+    fn point(&mut self, contour: INT, point: INT) -> ScriptResult<(FLOAT, FLOAT)> {
+        self.read(|glyph| {
+            glyph
+                .outline
+                .as_ref()
+                .and_then(|outline| outline.contours.get(contour as usize))
+                .and_then(|contour| contour.points.get(point as usize))
+                .map(|pt| (pt.x as FLOAT, pt.y as FLOAT))
+                .ok_or_else(|| format!("no point {} in contour {}", point, contour).into())
+        })
+    }
+
+    fn set_point(&mut self, contour: INT, point: INT, x: FLOAT, y: FLOAT) -> ScriptResult<()> {
+        // check first, so that the glyph isn't marked as changed
+        self.point(contour, point)?;
+        self.edit(|glyph| {
+            let outline = glyph.outline.as_mut().unwrap();
+            let pt = &mut outline.contours[contour as usize].points[point as usize];
+            pt.x = x as f32;
+            pt.y = y as f32;
+        });
+        Ok(())
+    }
+
+    /// Apply `fx` and `fy` to the coordinates of every point in the glyph's
+    /// contours.
+    fn map_points(&mut self, fx: impl Fn(f32) -> f32, fy: impl Fn(f32) -> f32) {
+        self.edit(|glyph| {
+            let contours = glyph.outline.iter_mut().flat_map(|o| o.contours.iter_mut());
+            for pt in contours.flat_map(|c| c.points.iter_mut()) {
+                pt.x = fx(pt.x);
+                pt.y = fy(pt.y);
+            }
+        })
+    }
+}
+
+fn make_engine(output: Rc<RefCell<Vec<String>>>) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(move |s| output.borrow_mut().push(s.to_string()));
+
+    engine
+        .register_type_with_name::<ScriptFont>("Font")
+        .register_get("glyph_names", ScriptFont::glyph_names)
+        .register_fn("glyph", ScriptFont::glyph)
+        .register_get("units_per_em", |f: &mut ScriptFont| {
+            f.metric(|m| Some(m.units_per_em))
+        })
+        .register_set("units_per_em", |f: &mut ScriptFont, v: FLOAT| {
+            if v <= 0.0 {
+                return Err(format!("units_per_em must be positive, not {}", v).into());
+            }
+            f.set_metric(|m| m.units_per_em = v);
+            Ok(())
+        })
+        .register_get("ascender", |f: &mut ScriptFont| f.metric(|m| m.ascender))
+        .register_set("ascender", |f: &mut ScriptFont, v: FLOAT| {
+            f.set_metric(|m| m.ascender = Some(v))
+        })
+        .register_get("descender", |f: &mut ScriptFont| f.metric(|m| m.descender))
+        .register_set("descender", |f: &mut ScriptFont, v: FLOAT| {
+            f.set_metric(|m| m.descender = Some(v))
+        })
+        .register_get("x_height", |f: &mut ScriptFont| f.metric(|m| m.x_height))
+        .register_set("x_height", |f: &mut ScriptFont, v: FLOAT| {
+            f.set_metric(|m| m.x_height = Some(v))
+        })
+        .register_get("cap_height", |f: &mut ScriptFont| {
+            f.metric(|m| m.cap_height)
+        })
+        .register_set("cap_height", |f: &mut ScriptFont, v: FLOAT| {
+            f.set_metric(|m| m.cap_height = Some(v))
+        });
+
+    engine
+        .register_type_with_name::<ScriptGlyph>("Glyph")
+        .register_get("name", ScriptGlyph::name)
+        .register_get_set("width", ScriptGlyph::width, ScriptGlyph::set_width)
+        .register_get("contour_count", ScriptGlyph::contour_count)
+        .register_fn("point_count", ScriptGlyph::point_count)
+        .register_fn("point_x", |g: &mut ScriptGlyph, c: INT, p: INT| {
+            g.point(c, p).map(|pt| pt.0)
+        })
+        .register_fn("point_y", |g: &mut ScriptGlyph, c: INT, p: INT| {
+            g.point(c, p).map(|pt| pt.1)
+        })
+        .register_fn("set_point", ScriptGlyph::set_point)
+        .register_fn("move_by", |g: &mut ScriptGlyph, dx: FLOAT, dy: FLOAT| {
+            g.map_points(|x| x + dx as f32, |y| y + dy as f32)
+        })
+        .register_fn("round", |g: &mut ScriptGlyph| {
+            g.map_points(f32::round, f32::round)
+        });
+    engine
+}
+
+/// Run a script against the workspace's font, returning anything it printed.
+///
+/// The script's changes are only applied if it runs without error.
+pub fn run_script(workspace: &mut Workspace, source: &str) -> String {
+    workspace.flush_sessions();
+    let data = Rc::new(RefCell::new(ScriptData {
+        ufo: workspace.font.ufo.clone(),
+        metrics: workspace.metrics().clone(),
+        names: workspace.sorted_glyph_names(),
+        changed: BTreeSet::new(),
+    }));
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let engine = make_engine(output.clone());
+    let mut scope = Scope::new();
+    scope.push("font", ScriptFont(data.clone()));
+    let result = engine.run_with_scope(&mut scope, source);
+
+    let mut output = output.take();
+    match result {
+        Ok(()) => {
+            let data = data.borrow();
+            let locked = workspace.apply_script_changes(
+                data.ufo.clone(),
+                data.metrics.clone(),
+                &data.changed,
+            );
+            output.push(format!(
+                "Changed {} glyphs.",
+                data.changed.len() - locked.len()
+            ));
+            if !locked.is_empty() {
+                let names = locked.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                output.push(format!("Skipped locked glyphs: {}", names.join(", ")));
+            }
+        }
+        Err(e) if matches!(*e, EvalAltResult::ErrorTooManyOperations(_)) => output.push(
+            "Error: the script was stopped after too many operations; is it stuck in a loop?"
+                .into(),
+        ),
+        Err(e) => output.push(format!("Error: {}", e)),
+    }
+    output.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::font_builder::FontBuilder;
+
+    #[test]
+    fn edit_font() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a", "b"]).workspace();
+        let output = run_script(
+            &mut workspace,
+            r#"
+            print(font.glyph_names.len());
+            let a = font.glyph("a");
+            a.width = 300.0;
+            font.x_height = 480.0;
+            "#,
+        );
+        assert_eq!(output, "2\nChanged 1 glyphs.");
+        let glyph = workspace.font.ufo.get_glyph("a").unwrap();
+        assert!((glyph.advance.as_ref().unwrap().width - 300.0).abs() < f32::EPSILON);
+        assert!((workspace.metrics().x_height.unwrap() - 480.0).abs() < f64::EPSILON);
+
+        // a failing script changes nothing
+        let output = run_script(
+            &mut workspace,
+            r#"let a = font.glyph("a"); a.width = 1.0; oops();"#,
+        );
+        assert!(output.starts_with("Error"));
+        let glyph = workspace.font.ufo.get_glyph("a").unwrap();
+        assert!((glyph.advance.as_ref().unwrap().width - 300.0).abs() < f32::EPSILON);
+        assert!(run_script(&mut workspace, r#"font.glyph("z")"#).starts_with("Error"));
+        assert!(run_script(&mut workspace, "font.units_per_em = 0.0;").starts_with("Error"));
+    }
+
+    #[test]
+    fn locked_glyphs() {
+        let mut workspace = FontBuilder::new().with_glyphs(vec!["a", "b"]).workspace();
+        workspace.set_glyph_locked(&"b".into(), true);
+        let before = workspace.font.ufo.get_glyph("b").unwrap().clone();
+        let output = run_script(
+            &mut workspace,
+            r#"
+            for name in font.glyph_names {
+                let glyph = font.glyph(name);
+                glyph.width = 300.0;
+            }
+            "#,
+        );
+        assert_eq!(output, "Changed 1 glyphs.\nSkipped locked glyphs: b");
+        assert_eq!(workspace.font.ufo.get_glyph("b"), Some(&before));
+    }
+}
@@ -0,0 +1,220 @@
+//! Writing an edit session as JSON, and reading it back.
+//!
+//! This is meant for bug reports, tests, and other tools. Paths are written
+//! in the same JSON format we use on the clipboard. Point ids are not kept,
+//! so selected points are written as their index in their path.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use druid::kurbo::Affine;
+use norad::glyph::{Advance, Anchor};
+use norad::GlyphName;
+
+use crate::component::{Component, ComponentParams};
+use crate::edit_session::EditSession;
+use crate::guides::{Guide, GuideLine};
+use crate::path::Path;
+use crate::point::EntityId;
+use crate::selection::Selection;
+
+/// The version of the format; files from a newer version are not read.
+const FORMAT_VERSION: u32 = 1;
+
+#[derive(Serialize, Deserialize)]
+struct SessionJson {
+    version: u32,
+    name: String,
+    #[serde(default)]
+    width: f64,
+    #[serde(default)]
+    paths: Vec<Path>,
+    #[serde(default)]
+    components: Vec<ComponentJson>,
+    #[serde(default)]
+    guides: Vec<GuideLine>,
+    #[serde(default)]
+    anchors: Vec<AnchorJson>,
+    /// Each selected point, as the index of its path and its index in that
+    /// path's points.
+    #[serde(default)]
+    selection: Vec<(usize, usize)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ComponentJson {
+    base: String,
+    transform: [f64; 6],
+    #[serde(default)]
+    params: Option<ComponentParams>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AnchorJson {
+    name: Option<String>,
+    x: f64,
+    y: f64,
+}
+
+/// Write the session's outline, guides, anchors and selection as JSON.
+pub(crate) fn to_json(session: &EditSession) -> Result<String, String> {
+    let point_indices: HashMap<EntityId, (usize, usize)> = session
+        .paths
+        .iter()
+        .enumerate()
+        .flat_map(|(path_idx, path)| {
+            path.points()
+                .iter()
+                .enumerate()
+                .map(move |(pt_idx, pt)| (pt.id, (path_idx, pt_idx)))
+        })
+        .collect();
+    let mut selection: Vec<_> = session
+        .selection
+        .iter()
+        .filter_map(|id| point_indices.get(id).copied())
+        .collect();
+    selection.sort_unstable();
+
+    let json = SessionJson {
+        version: FORMAT_VERSION,
+        name: session.name.to_string(),
+        width: session
+            .glyph
+            .advance
+            .as_ref()
+            .map(|advance| advance.width as f64)
+            .unwrap_or_default(),
+        paths: session.paths.as_ref().clone(),
+        components: session
+            .components
+            .iter()
+            .map(|component| ComponentJson {
+                base: component.base.to_string(),
+                transform: component.transform.as_coeffs(),
+                params: component.params,
+            })
+            .collect(),
+        guides: session
+            .guides
+            .iter()
+            .map(|guide| guide.guide.clone())
+            .collect(),
+        anchors: session
+            .glyph
+            .anchors
+            .iter()
+            .flatten()
+            .map(|anchor| AnchorJson {
+                name: anchor.name.clone(),
+                x: anchor.x as f64,
+                y: anchor.y as f64,
+            })
+            .collect(),
+        selection,
+    };
+    serde_json::to_string_pretty(&json).map_err(|e| e.to_string())
+}
+
+/// Replace the session's outline, guides, anchors and selection with those
+/// in `json`.
+///
+/// The glyph keeps its name, even if the JSON came from another glyph.
+pub(crate) fn load_json(session: &mut EditSession, json: &str) -> Result<(), String> {
+    let json: SessionJson = serde_json::from_str(json).map_err(|e| e.to_string())?;
+    if json.version > FORMAT_VERSION {
+        return Err(format!("unsupported session format {}", json.version));
+    }
+    if let Some((path_idx, pt_idx)) = json.selection.iter().find(|(path, pt)| {
+        json.paths
+            .get(*path)
+            .and_then(|p| p.points().get(*pt))
+            .is_none()
+    }) {
+        return Err(format!("no point {} in path {}", pt_idx, path_idx));
+    }
+
+    let selection: Selection = json
+        .selection
+        .iter()
+        .map(|(path, pt)| json.paths[*path].points()[*pt].id)
+        .collect();
+    let components = json
+        .components
+        .into_iter()
+        .map(|component| Component {
+            base: GlyphName::from(component.base),
+            transform: Affine::new(component.transform),
+            id: EntityId::next(),
+            params: component.params,
+        })
+        .collect();
+    let guides = json.guides.into_iter().map(Guide::new).collect();
+    let anchors: Vec<_> = json
+        .anchors
+        .into_iter()
+        .map(|anchor| Anchor {
+            name: anchor.name,
+            x: anchor.x as f32,
+            y: anchor.y as f32,
+            color: None,
+            identifier: None,
+        })
+        .collect();
+
+    session.paths = Arc::new(json.paths);
+    session.components = Arc::new(components);
+    session.outline_changed();
+    session.guides = Arc::new(guides);
+    session.selection = selection;
+    let glyph = Arc::make_mut(&mut session.glyph);
+    glyph.advance = Some(Advance {
+        width: json.width as f32,
+        height: glyph.advance.as_ref().map(|a| a.height).unwrap_or_default(),
+    });
+    glyph.anchors = if anchors.is_empty() {
+        None
+    } else {
+        Some(anchors)
+    };
+    session.rebuild_glyph();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::design_space::DPoint;
+    use crate::font_builder::FontBuilder;
+
+    #[test]
+    fn round_trip() {
+        let workspace = FontBuilder::new().with_glyphs(vec!["a", "b"]).workspace();
+        let mut session = EditSession::new(&"a".into(), &workspace);
+        let mut path = Path::new(DPoint::new(0., 0.));
+        path.line_to(DPoint::new(100., 0.), false);
+        let last = path.line_to(DPoint::new(100., 100.), false);
+        session.add_path(path);
+        session.selection.select_one(last);
+        session.guides = Arc::new(vec![Guide::horiz(DPoint::new(0., 500.))]);
+
+        let json = to_json(&session).unwrap();
+        let mut other = EditSession::new(&"b".into(), &workspace);
+        load_json(&mut other, &json).unwrap();
+        assert_eq!(other.name.as_ref(), "b");
+        assert_eq!(other.paths.len(), 1);
+        assert_eq!(other.paths[0].points().len(), 3);
+        assert_eq!(other.guides.len(), 1);
+        let selected = other.selection.iter().next().copied().unwrap();
+        assert_eq!(
+            other.path_point_for_id(selected).map(|pt| pt.point),
+            Some(DPoint::new(100., 100.))
+        );
+
+        assert!(load_json(
+            &mut other,
+            "{\"version\": 1, \"name\": \"x\", \"selection\": [[0, 0]]}"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Settings that persist across launches, such as the recently opened fonts
+//! and the options of the editor's tools.
+//!
+//! These are stored as JSON in the platform's configuration directory.
+
+use std::path::{Path, PathBuf};
+
+use druid::{Data, Lens};
+
+/// The most fonts listed in the 'Open Recent' menu.
+const MAX_RECENT_FILES: usize = 10;
+
+const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Application settings, shared by all windows.
+#[derive(Debug, Clone, Default, PartialEq, Data, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Fonts that have been opened or saved, most recent first.
+    #[data(same_fn = "PartialEq::eq")]
+    pub recent_files: Vec<PathBuf>,
+    /// Whether the most recent font is opened when runebender is launched
+    /// without a font.
+    pub reopen_last_font: bool,
+    /// The options of the editor's tools.
+    pub tool_options: ToolOptions,
+    /// Whether a glyph's edits are written into the font as soon as its
+    /// editor is closed, instead of only when the font is saved.
+    pub flush_on_close: bool,
+}
+
+/// Options that change how some of the editor's tools behave, shown in the
+/// strip below the toolbar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Data, Lens, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ToolOptions {
+    /// Whether the pen adds points at the extrema of a path when it is closed.
+    pub add_extrema_on_close: bool,
+    /// Whether the knife splits open paths into open pieces, instead of
+    /// closing the pieces along the cut.
+    pub knife_keeps_open_paths: bool,
+    /// The radius of the corners of rectangles drawn with the rectangle tool.
+    pub corner_radius: f64,
+    /// Which of the points inside a selection rectangle or lasso are selected.
+    pub marquee_mode: MarqueeMode,
+}
+
+/// Which of the points inside a selection rectangle or lasso are selected,
+/// for when overlapping outlines make it pick up too much.
+#[derive(Debug, Clone, Copy, PartialEq, Data, Serialize, Deserialize)]
+pub enum MarqueeMode {
+    /// Every visible point.
+    AllPoints,
+    /// Only points whose contour passes through the selected area, leaving
+    /// out the handles of contours that only reach into it.
+    TouchedContours,
+    /// Only points that are not covered by the fill of another contour.
+    UncoveredPoints,
+}
+
+impl Default for MarqueeMode {
+    fn default() -> Self {
+        MarqueeMode::AllPoints
+    }
+}
+
+impl Settings {
+    /// Load the settings, or the defaults if they can't be read.
+    pub fn load() -> Settings {
+        let path = match settings_path() {
+            Some(path) if path.exists() => path,
+            _ => return Settings::default(),
+        };
+        let result = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()));
+        match result {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::warn!("failed to read settings from {:?}: '{}'", path, e);
+                Settings::default()
+            }
+        }
+    }
+
+    /// Write the settings, logging any failure.
+    pub fn save(&self) {
+        let path = match settings_path() {
+            Some(path) => path,
+            None => {
+                log::warn!("no configuration directory, settings not saved");
+                return;
+            }
+        };
+        let result = path
+            .parent()
+            .map(std::fs::create_dir_all)
+            .unwrap_or(Ok(()))
+            .map_err(|e| e.to_string())
+            .and_then(|_| serde_json::to_string_pretty(self).map_err(|e| e.to_string()))
+            .and_then(|json| std::fs::write(&path, json).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            log::warn!("failed to write settings to {:?}: '{}'", path, e);
+        }
+    }
+
+    /// Move `path` to the front of the recent files, adding it if needed.
+    pub fn add_recent_file(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+        self.recent_files.retain(|existing| existing != &path);
+        self.recent_files.insert(0, path);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// The font to open at launch, if `reopen_last_font` is set.
+    pub fn font_to_reopen(&self) -> Option<&Path> {
+        if self.reopen_last_font {
+            self.recent_files.first().map(PathBuf::as_path)
+        } else {
+            None
+        }
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("runebender").join(SETTINGS_FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recent_files() {
+        let mut settings = Settings::default();
+        for i in 0..MAX_RECENT_FILES + 2 {
+            settings.add_recent_file(Path::new(&format!("/fonts/{}.ufo", i)));
+        }
+        assert_eq!(settings.recent_files.len(), MAX_RECENT_FILES);
+        assert_eq!(settings.recent_files[0], Path::new("/fonts/11.ufo"));
+
+        settings.add_recent_file(Path::new("/fonts/5.ufo"));
+        assert_eq!(settings.recent_files.len(), MAX_RECENT_FILES);
+        assert_eq!(settings.recent_files[0], Path::new("/fonts/5.ufo"));
+        assert_eq!(settings.recent_files[1], Path::new("/fonts/11.ufo"));
+
+        assert!(settings.font_to_reopen().is_none());
+        settings.reopen_last_font = true;
+        assert_eq!(settings.font_to_reopen(), Some(Path::new("/fonts/5.ufo")));
+    }
+}
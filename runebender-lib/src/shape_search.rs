@@ -0,0 +1,157 @@
+//! Finding contours that have the same shape as a given contour.
+
+use std::sync::Arc;
+
+use druid::kurbo::Vec2;
+use druid::{Data, Lens};
+use norad::glyph::{Contour, ContourPoint, Glyph, GlyphName, PointType};
+
+/// The tolerance a search starts with, in design units.
+pub const DEFAULT_TOLERANCE: f64 = 0.0;
+
+/// The contour being searched for.
+#[derive(Debug, Clone, Data, Lens)]
+pub struct ShapeQuery {
+    /// The glyph the contour is in.
+    pub glyph: GlyphName,
+    /// The index of the contour in its glyph; this contour is not counted
+    /// as a match.
+    pub index: usize,
+    pub contour: Arc<Contour>,
+    /// How far each point may be from where it is in the contour searched
+    /// for, in design units.
+    pub tolerance: f64,
+}
+
+/// A contour with the same shape as the one searched for.
+#[derive(Debug, Clone, Data)]
+pub struct ShapeMatch {
+    pub glyph: GlyphName,
+    /// The index of the contour in its glyph.
+    pub index: usize,
+    /// How far the contour is from the one searched for.
+    pub offset: Vec2,
+}
+
+impl ShapeQuery {
+    /// The contours of `glyph` that match this query.
+    pub fn find_in(&self, glyph: &Glyph) -> Vec<ShapeMatch> {
+        let contours = match glyph.outline.as_ref() {
+            Some(outline) => &outline.contours,
+            None => return Vec::new(),
+        };
+        contours
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| glyph.name != self.glyph || *i != self.index)
+            .filter_map(|(i, contour)| {
+                match_contour(&self.contour, contour, self.tolerance).map(|offset| ShapeMatch {
+                    glyph: glyph.name.clone(),
+                    index: i,
+                    offset,
+                })
+            })
+            .collect()
+    }
+}
+
+/// If `candidate` has the same shape as `target`, the offset that moves
+/// `target` onto it.
+///
+/// The contours must have the same kinds of points in the same order,
+/// although a closed contour may start at any point. Once the first points
+/// are aligned, every other point may be up to `tolerance` units from its
+/// counterpart.
+pub fn match_contour(target: &Contour, candidate: &Contour, tolerance: f64) -> Option<Vec2> {
+    let (target, candidate) = (&target.points, &candidate.points);
+    if target.is_empty() || target.len() != candidate.len() {
+        return None;
+    }
+    let closed = target[0].typ != PointType::Move;
+    if closed != (candidate[0].typ != PointType::Move) {
+        return None;
+    }
+    let starts = if closed { candidate.len() } else { 1 };
+    (0..starts).find_map(|start| match_from(target, candidate, start, tolerance))
+}
+
+/// Match `target` against `candidate`, with the first point of `target`
+/// aligned with the `start`th point of `candidate`.
+fn match_from(
+    target: &[ContourPoint],
+    candidate: &[ContourPoint],
+    start: usize,
+    tolerance: f64,
+) -> Option<Vec2> {
+    let offset = position(&candidate[start]) - position(&target[0]);
+    let len = candidate.len();
+    let matches = target.iter().enumerate().all(|(i, pt)| {
+        let other = &candidate[(start + i) % len];
+        other.typ == pt.typ && (position(other) - position(pt) - offset).hypot() <= tolerance
+    });
+    matches.then(|| offset)
+}
+
+fn position(pt: &ContourPoint) -> Vec2 {
+    Vec2::new(pt.x as f64, pt.y as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, size: f32) -> Contour {
+        let points = vec![(x, y), (x + size, y), (x + size, y + size), (x, y + size)]
+            .into_iter()
+            .map(|(x, y)| ContourPoint::new(x, y, PointType::Line, false, None, None, None))
+            .collect();
+        Contour::new(points, None, None)
+    }
+
+    #[test]
+    fn match_contours() {
+        let target = square(0., 0., 100.);
+        assert_eq!(
+            match_contour(&target, &square(50., 20., 100.), 0.0),
+            Some(Vec2::new(50., 20.))
+        );
+        assert_eq!(match_contour(&target, &square(0., 0., 101.), 0.0), None);
+        assert_eq!(
+            match_contour(&target, &square(0., 0., 101.), 2.0),
+            Some(Vec2::ZERO)
+        );
+
+        // the same square, starting at its second point
+        let mut rotated = square(10., 10., 100.);
+        rotated.points.rotate_left(1);
+        assert_eq!(
+            match_contour(&target, &rotated, 0.0),
+            Some(Vec2::new(10., 10.))
+        );
+
+        // an open contour must start at the same point
+        let mut open = square(0., 0., 100.);
+        open.points[0].typ = PointType::Move;
+        assert_eq!(match_contour(&target, &open, 0.0), None);
+        assert_eq!(match_contour(&open, &open, 0.0), Some(Vec2::ZERO));
+    }
+
+    #[test]
+    fn find_in_glyph() {
+        let mut glyph = Glyph::new_named("a");
+        glyph.outline = Some(norad::glyph::Outline {
+            contours: vec![square(0., 0., 100.), square(200., 0., 100.)],
+            components: Vec::new(),
+        });
+        let query = ShapeQuery {
+            glyph: "a".into(),
+            index: 0,
+            contour: Arc::new(square(0., 0., 100.)),
+            tolerance: DEFAULT_TOLERANCE,
+        };
+        let found = query.find_in(&glyph);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].index, 1);
+        assert_eq!(found[0].offset, Vec2::new(200., 0.));
+    }
+}
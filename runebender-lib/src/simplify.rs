@@ -0,0 +1,274 @@
+//! Reducing the number of points in a path.
+//!
+//! Points are removed one at a time, refitting the two segments on either
+//! side of the point with a single segment, for as long as the refit
+//! segments stay within some tolerance of the original outline. The tangents
+//! at the points that remain are never changed, so smooth points stay smooth
+//! and corners stay corners.
+
+use druid::kurbo::{CubicBez, Line, ParamCurve, ParamCurveNearest, PathSeg, Point, Vec2};
+
+/// The number of points sampled from each original segment, when measuring
+/// how far a refit segment strays from the original outline.
+const SAMPLES_PER_SEGMENT: usize = 8;
+/// The accuracy used when finding the nearest point on a refit segment.
+const NEAREST_ACCURACY: f64 = 1e-3;
+
+/// A run of the original segments, replaced by a single segment.
+struct Piece {
+    /// The index of the first original segment in the run.
+    start: usize,
+    seg: PathSeg,
+    /// Points along the original segments, excluding the end points.
+    samples: Vec<Point>,
+}
+
+impl Piece {
+    fn new(start: usize, seg: PathSeg) -> Self {
+        let samples = (1..SAMPLES_PER_SEGMENT)
+            .map(|i| seg.eval(i as f64 / SAMPLES_PER_SEGMENT as f64))
+            .collect();
+        Piece {
+            start,
+            seg,
+            samples,
+        }
+    }
+}
+
+/// Simplify a path made up of `segments`.
+///
+/// `removable[i]` is `true` if the point at the start of `segments[i]` may be
+/// removed. In an open path, the first point is never removed.
+///
+/// Returns the remaining segments, in order, each paired with the index of
+/// the original segment that it starts at.
+pub(crate) fn simplify(
+    segments: &[PathSeg],
+    removable: &[bool],
+    closed: bool,
+    tolerance: f64,
+) -> Vec<(usize, PathSeg)> {
+    assert_eq!(segments.len(), removable.len());
+    let mut pieces = segments
+        .iter()
+        .enumerate()
+        .map(|(i, seg)| Piece::new(i, *seg))
+        .collect::<Vec<_>>();
+    // a closed path needs at least two segments to enclose anything
+    let min_len = if closed { 2 } else { 1 };
+
+    loop {
+        if pieces.len() <= min_len {
+            break;
+        }
+        // merge the pair of pieces with the smallest error first
+        let first = if closed { 0 } else { 1 };
+        let best = (first..pieces.len())
+            .filter(|i| removable[pieces[*i].start])
+            .filter_map(|i| {
+                let prev = if i == 0 { pieces.len() - 1 } else { i - 1 };
+                merge(&pieces[prev], &pieces[i]).map(|(seg, err)| (prev, i, seg, err))
+            })
+            .filter(|(.., err)| *err <= tolerance)
+            .min_by(|a, b| a.3.partial_cmp(&b.3).unwrap());
+
+        let (prev, i, seg) = match best {
+            Some((prev, i, seg, _)) => (prev, i, seg),
+            None => break,
+        };
+        let removed = pieces.remove(i);
+        let prev = if prev > i { prev - 1 } else { prev };
+        let prev = &mut pieces[prev];
+        prev.seg = seg;
+        prev.samples.push(removed.seg.start());
+        prev.samples.extend(removed.samples);
+    }
+
+    pieces.into_iter().map(|p| (p.start, p.seg)).collect()
+}
+
+/// Fit a single segment to the two pieces, returning it along with the
+/// greatest distance from the original outline to the new segment.
+fn merge(a: &Piece, b: &Piece) -> Option<(PathSeg, f64)> {
+    let start = a.seg.start();
+    let end = b.seg.end();
+    let samples = a
+        .samples
+        .iter()
+        .chain(Some(b.seg.start()).iter())
+        .chain(b.samples.iter())
+        .copied()
+        .collect::<Vec<_>>();
+
+    let seg = match (a.seg, b.seg) {
+        (PathSeg::Line(_), PathSeg::Line(_)) => PathSeg::Line(Line::new(start, end)),
+        _ => PathSeg::Cubic(fit_cubic(
+            start,
+            start_tangent(a.seg)?,
+            end,
+            end_tangent(b.seg)?,
+            &samples,
+        )?),
+    };
+
+    let error = samples
+        .iter()
+        .map(|pt| seg.nearest(*pt, NEAREST_ACCURACY).1.sqrt())
+        .fold(0.0, f64::max);
+    Some((seg, error))
+}
+
+/// Fit a cubic from `start` to `end` through `samples`, with handles in the
+/// direction of `start_tan` and `end_tan`.
+///
+/// This is the least squares fit from Philip J. Schneider's "An Algorithm for
+/// Automatically Fitting Digitized Curves", with the samples parametrized by
+/// chord length.
+fn fit_cubic(
+    start: Point,
+    start_tan: Vec2,
+    end: Point,
+    end_tan: Vec2,
+    samples: &[Point],
+) -> Option<CubicBez> {
+    let chord = start.distance(end);
+    if chord == 0.0 {
+        return None;
+    }
+
+    let mut lengths = Vec::with_capacity(samples.len());
+    let mut total = 0.0;
+    let mut prev = start;
+    for pt in samples.iter().chain(Some(end).iter()) {
+        total += prev.distance(*pt);
+        lengths.push(total);
+        prev = *pt;
+    }
+
+    // the end handle points backwards along the end tangent
+    let end_dir = -end_tan;
+    let (mut c00, mut c01, mut c11, mut x0, mut x1) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for (pt, len) in samples.iter().zip(lengths.iter()) {
+        let t = len / total;
+        let mt = 1.0 - t;
+        let b0 = mt * mt * mt;
+        let b1 = 3.0 * mt * mt * t;
+        let b2 = 3.0 * mt * t * t;
+        let b3 = t * t * t;
+        let a0 = start_tan * b1;
+        let a1 = end_dir * b2;
+        let rest = *pt - (start.to_vec2() * (b0 + b1) + end.to_vec2() * (b2 + b3));
+        c00 += a0.dot(a0);
+        c01 += a0.dot(a1);
+        c11 += a1.dot(a1);
+        x0 += a0.dot(rest.to_vec2());
+        x1 += a1.dot(rest.to_vec2());
+    }
+
+    let det = c00 * c11 - c01 * c01;
+    let (alpha, beta) = if det.abs() > f64::EPSILON {
+        ((x0 * c11 - x1 * c01) / det, (c00 * x1 - c01 * x0) / det)
+    } else {
+        (chord / 3.0, chord / 3.0)
+    };
+    // handles pointing the wrong way would add a loop or a kink
+    if !alpha.is_finite() || !beta.is_finite() || alpha <= 0.0 || beta <= 0.0 {
+        return None;
+    }
+    Some(CubicBez::new(
+        start,
+        start + start_tan * alpha,
+        end + end_dir * beta,
+        end,
+    ))
+}
+
+/// The unit tangent at the start of a segment.
+fn start_tangent(seg: PathSeg) -> Option<Vec2> {
+    let points = control_points(seg);
+    tangent(points[0], &points[1..])
+}
+
+/// The unit tangent at the end of a segment, in the direction of travel.
+fn end_tangent(seg: PathSeg) -> Option<Vec2> {
+    let mut points = control_points(seg);
+    points.reverse();
+    tangent(points[0], &points[1..]).map(|tan| -tan)
+}
+
+/// The direction from `start` to the first of `rest` that isn't at `start`.
+fn tangent(start: Point, rest: &[Point]) -> Option<Vec2> {
+    rest.iter()
+        .find(|pt| **pt != start)
+        .map(|pt| (*pt - start).normalize())
+}
+
+fn control_points(seg: PathSeg) -> Vec<Point> {
+    match seg {
+        PathSeg::Line(line) => vec![line.p0, line.p1],
+        PathSeg::Quad(quad) => vec![quad.p0, quad.p1, quad.p2],
+        PathSeg::Cubic(cubic) => vec![cubic.p0, cubic.p1, cubic.p2, cubic.p3],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collinear_lines() {
+        let segs = vec![
+            PathSeg::Line(Line::new((0., 0.), (50., 0.))),
+            PathSeg::Line(Line::new((50., 0.), (100., 0.))),
+            PathSeg::Line(Line::new((100., 0.), (100., 100.))),
+        ];
+        let result = simplify(&segs, &[false, true, true], false, 0.5);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].0, 0);
+        assert_eq!(result[0].1.end(), Point::new(100., 0.));
+        assert_eq!(result[1].0, 2);
+    }
+
+    #[test]
+    fn split_curve() {
+        // a curve split in two can be refit, keeping its tangents
+        let curve = CubicBez::new((0., 0.), (0., 100.), (100., 200.), (200., 200.));
+        let (a, b) = (curve.subsegment(0.0..0.4), curve.subsegment(0.4..1.0));
+        let segs = vec![PathSeg::Cubic(a), PathSeg::Cubic(b)];
+        let result = simplify(&segs, &[false, true], false, 0.5);
+        assert_eq!(result.len(), 1);
+        let fit = match result[0].1 {
+            PathSeg::Cubic(fit) => fit,
+            _ => panic!("expected a cubic"),
+        };
+        assert!((fit.p1 - fit.p0).normalize().y > 0.999);
+        assert!((fit.p3 - fit.p2).normalize().x > 0.999);
+
+        // but not if the point is a corner
+        assert_eq!(simplify(&segs, &[false, false], false, 0.5).len(), 2);
+    }
+
+    #[test]
+    fn tolerance() {
+        // a circle drawn with four arcs, each split in two
+        const K: f64 = 0.5523 * 100.;
+        let arcs = [
+            CubicBez::new((100., 0.), (100., K), (K, 100.), (0., 100.)),
+            CubicBez::new((0., 100.), (-K, 100.), (-100., K), (-100., 0.)),
+            CubicBez::new((-100., 0.), (-100., -K), (-K, -100.), (0., -100.)),
+            CubicBez::new((0., -100.), (K, -100.), (100., -K), (100., 0.)),
+        ];
+        let segs = arcs
+            .iter()
+            .flat_map(|arc| vec![arc.subsegment(0.0..0.5), arc.subsegment(0.5..1.0)])
+            .map(PathSeg::Cubic)
+            .collect::<Vec<_>>();
+        let removable = vec![true; segs.len()];
+        // the split points can go, but a half circle is too far from two arcs
+        let result = simplify(&segs, &removable, true, 0.5);
+        assert_eq!(result.len(), 4);
+        assert!(result.iter().all(|(i, _)| i % 2 == 0));
+        assert_eq!(simplify(&segs, &removable, true, 20.0).len(), 2);
+    }
+}
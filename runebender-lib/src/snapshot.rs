@@ -0,0 +1,224 @@
+//! Named snapshots of a glyph's outline, stored in the glyph lib.
+//!
+//! Each snapshot keeps the glyph's contours, in the same JSON format we use
+//! on the clipboard, along with its components. Because they live in the
+//! lib, snapshots are saved with the UFO and travel with the glyph.
+
+use std::sync::Arc;
+
+use druid::kurbo::{Affine, BezPath};
+use druid::Data;
+use norad::{Glyph, GlyphName};
+
+use crate::component::Component;
+use crate::data::Workspace;
+use crate::path::Path;
+use crate::point::EntityId;
+
+/// The key in the glyph lib for the glyph's snapshots.
+const SNAPSHOTS_LIB_KEY: &str = "org.linebender.runebender.snapshots";
+
+const NAME_KEY: &str = "name";
+const DATE_KEY: &str = "date";
+const PATHS_KEY: &str = "paths";
+const COMPONENTS_KEY: &str = "components";
+const BASE_KEY: &str = "base";
+const TRANSFORM_KEY: &str = "transform";
+
+/// The outline of a glyph at some earlier point.
+#[derive(Debug, Clone, Data)]
+pub struct Snapshot {
+    pub name: String,
+    /// When the snapshot was taken, as an RFC 3339 timestamp.
+    pub date: String,
+    pub paths: Arc<Vec<Path>>,
+    pub components: Arc<Vec<Component>>,
+}
+
+impl Snapshot {
+    pub fn new(
+        name: impl Into<String>,
+        paths: Arc<Vec<Path>>,
+        components: Arc<Vec<Component>>,
+    ) -> Self {
+        Snapshot {
+            name: name.into(),
+            date: chrono::Local::now().to_rfc3339(),
+            paths,
+            components,
+        }
+    }
+
+    /// A name for a snapshot taken now, for when the user doesn't give one.
+    pub fn default_name() -> String {
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+
+    /// The snapshots stored in this glyph's lib, oldest first.
+    ///
+    /// Snapshots that can't be read are skipped.
+    pub fn load_all(glyph: &Glyph) -> Vec<Snapshot> {
+        glyph
+            .lib
+            .as_ref()
+            .and_then(|lib| lib.get(SNAPSHOTS_LIB_KEY))
+            .and_then(|value| value.as_array())
+            .map(|items| items.iter().filter_map(Snapshot::from_plist).collect())
+            .unwrap_or_default()
+    }
+
+    /// Replace the snapshots stored in this glyph's lib.
+    pub fn save_all(snapshots: &[Snapshot], glyph: &mut Glyph) {
+        if snapshots.is_empty() {
+            if let Some(lib) = glyph.lib.as_mut() {
+                lib.remove(SNAPSHOTS_LIB_KEY);
+            }
+            return;
+        }
+        let items = snapshots.iter().filter_map(Snapshot::to_plist).collect();
+        glyph
+            .lib
+            .get_or_insert_with(Default::default)
+            .insert(SNAPSHOTS_LIB_KEY.into(), plist::Value::Array(items));
+    }
+
+    /// The outline of this snapshot, with components resolved against the
+    /// current state of the font.
+    pub fn to_bezier(&self, workspace: &Workspace) -> BezPath {
+        let mut bez = BezPath::new();
+        for path in self.paths.iter() {
+            path.append_to_bezier(&mut bez);
+        }
+        for component in self.components.iter() {
+            if let Some(base) = workspace.get_bezier(&component.base) {
+                let mut base = (*base).clone();
+                base.apply_affine(component.transform);
+                bez.extend(base.elements().iter().copied());
+            }
+        }
+        bez
+    }
+
+    fn to_plist(&self) -> Option<plist::Value> {
+        let paths = match serde_json::to_string(&*self.paths) {
+            Ok(paths) => paths,
+            Err(e) => {
+                log::error!("failed to serialize snapshot '{}': '{}'", self.name, e);
+                return None;
+            }
+        };
+        let components = self
+            .components
+            .iter()
+            .map(|component| {
+                let transform = component
+                    .transform
+                    .as_coeffs()
+                    .iter()
+                    .map(|coeff| plist::Value::Real(*coeff))
+                    .collect();
+                let mut dict = plist::Dictionary::new();
+                dict.insert(BASE_KEY.into(), component.base.to_string().into());
+                dict.insert(TRANSFORM_KEY.into(), plist::Value::Array(transform));
+                plist::Value::Dictionary(dict)
+            })
+            .collect();
+
+        let mut dict = plist::Dictionary::new();
+        dict.insert(NAME_KEY.into(), self.name.clone().into());
+        dict.insert(DATE_KEY.into(), self.date.clone().into());
+        dict.insert(PATHS_KEY.into(), paths.into());
+        dict.insert(COMPONENTS_KEY.into(), plist::Value::Array(components));
+        Some(plist::Value::Dictionary(dict))
+    }
+
+    fn from_plist(value: &plist::Value) -> Option<Snapshot> {
+        let dict = value.as_dictionary()?;
+        let name = dict.get(NAME_KEY)?.as_string()?.to_owned();
+        let date = dict
+            .get(DATE_KEY)
+            .and_then(|date| date.as_string())
+            .unwrap_or_default()
+            .to_owned();
+        let paths: Vec<Path> = match serde_json::from_str(dict.get(PATHS_KEY)?.as_string()?) {
+            Ok(paths) => paths,
+            Err(e) => {
+                log::warn!("skipping unreadable snapshot '{}': '{}'", name, e);
+                return None;
+            }
+        };
+        let components = dict
+            .get(COMPONENTS_KEY)
+            .and_then(|components| components.as_array())
+            .map(|items| items.iter().filter_map(component_from_plist).collect())
+            .unwrap_or_default();
+        Some(Snapshot {
+            name,
+            date,
+            paths: Arc::new(paths),
+            components: Arc::new(components),
+        })
+    }
+}
+
+fn component_from_plist(value: &plist::Value) -> Option<Component> {
+    let dict = value.as_dictionary()?;
+    let base: GlyphName = dict.get(BASE_KEY)?.as_string()?.into();
+    let coeffs = dict
+        .get(TRANSFORM_KEY)?
+        .as_array()?
+        .iter()
+        .map(|coeff| coeff.as_real())
+        .collect::<Option<Vec<_>>>()?;
+    let transform = match coeffs.as_slice() {
+        [a, b, c, d, e, f] => Affine::new([*a, *b, *c, *d, *e, *f]),
+        _ => return None,
+    };
+    Some(Component {
+        base,
+        transform,
+        id: EntityId::next(),
+        params: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cubic_path::CubicPath;
+    use crate::design_space::DPoint;
+    use crate::point::PathPoint;
+
+    #[test]
+    fn round_trip() {
+        let path_id = EntityId::next();
+        let points = vec![
+            PathPoint::on_curve(path_id, DPoint::new(100., 0.)),
+            PathPoint::on_curve(path_id, DPoint::new(100., 100.)),
+            PathPoint::on_curve(path_id, DPoint::new(0., 0.)),
+        ];
+        let path: Path = CubicPath::from_raw_parts(path_id, points, None, true).into();
+        let component = Component {
+            base: "acutecomb".into(),
+            transform: Affine::translate((250., 0.)),
+            id: EntityId::next(),
+            params: None,
+        };
+        let snapshot = Snapshot::new("first", Arc::new(vec![path]), Arc::new(vec![component]));
+
+        let mut glyph = Glyph::new_named("a");
+        Snapshot::save_all(&[snapshot], &mut glyph);
+        let loaded = Snapshot::load_all(&glyph);
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "first");
+        assert_eq!(loaded[0].paths[0].points().len(), 3);
+        assert_eq!(loaded[0].components[0].base.as_ref(), "acutecomb");
+        assert_eq!(
+            loaded[0].components[0].transform.as_coeffs(),
+            Affine::translate((250., 0.)).as_coeffs()
+        );
+
+        Snapshot::save_all(&[], &mut glyph);
+        assert!(Snapshot::load_all(&glyph).is_empty());
+    }
+}
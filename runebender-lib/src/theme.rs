@@ -30,6 +30,8 @@ pub const PLACEHOLDER_GLYPH_COLOR: Key<Color> = Key::new("runebender.placeholder
 pub const PRIMARY_TEXT_COLOR: Key<Color> = Key::new("runebender.primary-text-color");
 /// The color for secondary text like less important labels
 pub const SECONDARY_TEXT_COLOR: Key<Color> = Key::new("runebender.secondary-text-color");
+/// The color for errors and problems, such as an invalid name in a dialog
+pub const PROBLEM_TEXT_COLOR: Key<Color> = Key::new("runebender.problem-text-color");
 
 /// The fill color of the rectangle when dragging a selection
 pub const SELECTION_RECT_FILL_COLOR: Key<Color> = Key::new("runebender.selection-rect-fill-color");
@@ -44,6 +46,7 @@ pub const UI_DETAIL_FONT: Key<FontDescriptor> = Key::new("runebender.detail-font
 pub const PATH_STROKE_COLOR: Key<Color> = Key::new("runebender.path-stroke-color");
 pub const PATH_FILL_COLOR: Key<Color> = Key::new("runebender.path-fill-color");
 pub const METRICS_COLOR: Key<Color> = Key::new("runebender.metrics-color");
+pub const BLUE_ZONE_COLOR: Key<Color> = Key::new("runebender.blue-zone-color");
 pub const GUIDE_COLOR: Key<Color> = Key::new("runebender.guide-color");
 pub const SELECTED_GUIDE_COLOR: Key<Color> = Key::new("runebender.selected-guide-color");
 pub const SELECTED_LINE_SEGMENT_COLOR: Key<Color> =
@@ -64,10 +67,22 @@ pub const OFF_CURVE_HANDLE_COLOR: Key<Color> = Key::new("runebender.off-curve-ha
 pub const DIRECTION_ARROW_COLOR: Key<Color> = Key::new("runebender.direction-arrow-color");
 pub const COMPONENT_FILL_COLOR: Key<Color> = Key::new("runebender.component-fill-color");
 
+// Colors used to highlight feature code
+pub const FEA_KEYWORD_COLOR: Key<Color> = Key::new("runebender.fea-keyword-color");
+pub const FEA_COMMENT_COLOR: Key<Color> = Key::new("runebender.fea-comment-color");
+pub const FEA_STRING_COLOR: Key<Color> = Key::new("runebender.fea-string-color");
+pub const FEA_CLASS_COLOR: Key<Color> = Key::new("runebender.fea-class-color");
+pub const FEA_NUMBER_COLOR: Key<Color> = Key::new("runebender.fea-number-color");
+
 // Colors used by tools in the tool menu
 pub const KNIFE_GUIDE: Key<Color> = Key::new("runebender.knife-guide");
 pub const KNIFE_GUIDE_INTERSECTION: Key<Color> = Key::new("runebender.knife-guide-intersection");
 
+/// The colors of the box showing coordinates and distances while dragging
+pub const DRAG_INFO_BACKGROUND_COLOR: Key<Color> =
+    Key::new("runebender.drag-info-background-color");
+pub const DRAG_INFO_TEXT_COLOR: Key<Color> = Key::new("runebender.drag-info-text-color");
+
 pub const SMOOTH_RADIUS: Key<f64> = Key::new("runebender.smooth-point-radius");
 pub const SMOOTH_SELECTED_RADIUS: Key<f64> = Key::new("runebender.smooth-point-selected-radius");
 pub const CORNER_RADIUS: Key<f64> = Key::new("runebender.corner-point-radius");
@@ -75,6 +90,11 @@ pub const CORNER_SELECTED_RADIUS: Key<f64> = Key::new("runebender.corner-point-s
 pub const OFF_CURVE_RADIUS: Key<f64> = Key::new("runebender.off-curve-point-radius");
 pub const OFF_CURVE_SELECTED_RADIUS: Key<f64> =
     Key::new("runebender.off-curve-point-selected-radius");
+/// How near a click must be to a point to hit it.
+///
+/// Like the radii above this is in display points, so it keeps the same
+/// physical size on high DPI screens; it shrinks when zoomed far in.
+pub const CLICK_DISTANCE: Key<f64> = Key::new("runebender.click-distance");
 
 pub fn configure_env(env: &mut Env) {
     env.set(UI_DETAIL_FONT, FontDescriptor::default().with_size(12.0));
@@ -92,6 +112,7 @@ druid_theme_loader::loadable_theme!(pub MyTheme {
     FOCUS_OUTLINE_COLOR,
     PRIMARY_TEXT_COLOR,
     SECONDARY_TEXT_COLOR,
+    PROBLEM_TEXT_COLOR,
     SELECTION_RECT_STROKE_COLOR,
     SELECTION_RECT_FILL_COLOR,
     SELECTED_TEXT_BACKGROUND_COLOR,
@@ -104,6 +125,7 @@ druid_theme_loader::loadable_theme!(pub MyTheme {
     PATH_STROKE_COLOR,
     PATH_FILL_COLOR,
     METRICS_COLOR,
+    BLUE_ZONE_COLOR,
     GUIDE_COLOR,
     SELECTED_GUIDE_COLOR,
     SELECTED_LINE_SEGMENT_COLOR,
@@ -118,14 +140,22 @@ druid_theme_loader::loadable_theme!(pub MyTheme {
     OFF_CURVE_HANDLE_COLOR,
     DIRECTION_ARROW_COLOR,
     COMPONENT_FILL_COLOR,
+    FEA_KEYWORD_COLOR,
+    FEA_COMMENT_COLOR,
+    FEA_STRING_COLOR,
+    FEA_CLASS_COLOR,
+    FEA_NUMBER_COLOR,
     KNIFE_GUIDE,
     KNIFE_GUIDE_INTERSECTION,
+    DRAG_INFO_BACKGROUND_COLOR,
+    DRAG_INFO_TEXT_COLOR,
     SMOOTH_RADIUS,
     SMOOTH_SELECTED_RADIUS,
     CORNER_RADIUS,
     CORNER_SELECTED_RADIUS,
     OFF_CURVE_RADIUS,
     OFF_CURVE_SELECTED_RADIUS,
+    CLICK_DISTANCE,
 
 });
 
@@ -9,13 +9,15 @@ use crate::cubic_path::CubicPath;
 use crate::design_space::DPoint;
 use crate::edit_session::EditSession;
 use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
-use crate::tools::{EditType, Tool};
+use crate::tools::{shape_corners, EditType, Tool};
 
 /// The state of the ellipse tool.
 #[derive(Debug, Default, Clone)]
 pub struct Ellipse {
     gesture: GestureState,
     shift_locked: bool,
+    /// If alt is held, the shape is drawn out from its center.
+    alt_down: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -28,17 +30,12 @@ enum GestureState {
 impl Ellipse {
     fn pts_for_rect(&self) -> Option<(DPoint, DPoint)> {
         if let GestureState::Begun { start, current } = self.gesture {
-            let mut current = current;
-            if self.shift_locked {
-                let mut vec2 = current - start;
-                vec2.y = if vec2.y.signum() > 0.0 {
-                    vec2.x.abs()
-                } else {
-                    vec2.x.abs() * -1.0
-                };
-                current = start + vec2;
-            }
-            Some((start, current))
+            Some(shape_corners(
+                start,
+                current,
+                self.shift_locked,
+                self.alt_down,
+            ))
         } else {
             None
         }
@@ -58,6 +55,14 @@ impl Tool for Ellipse {
         "Ellipse"
     }
 
+    fn hint(&self) -> &'static str {
+        "Drag to draw an ellipse; hold shift for a circle, alt to draw from the center"
+    }
+
+    fn edit_description(&self) -> &'static str {
+        "Draw Ellipse"
+    }
+
     fn cancel(
         &mut self,
         mouse: &mut Mouse,
@@ -75,10 +80,12 @@ impl Tool for Ellipse {
         _: &mut EditSession,
         _: &Env,
     ) -> Option<EditType> {
-        if key.key == KbKey::Shift {
-            self.shift_locked = true;
-            ctx.request_paint();
+        match key.key {
+            KbKey::Shift => self.shift_locked = true,
+            KbKey::Alt => self.alt_down = true,
+            _ => return None,
         }
+        ctx.request_paint();
         None
     }
 
@@ -89,10 +96,12 @@ impl Tool for Ellipse {
         _: &mut EditSession,
         _: &Env,
     ) -> Option<EditType> {
-        if key.key == KbKey::Shift {
-            self.shift_locked = false;
-            ctx.request_paint();
+        match key.key {
+            KbKey::Shift => self.shift_locked = false,
+            KbKey::Alt => self.alt_down = false,
+            _ => return None,
         }
+        ctx.request_paint();
         None
     }
 
@@ -139,6 +148,7 @@ impl MouseDelegate<EditSession> for Ellipse {
                 ellipse
                     .path_elements(1.0)
                     .chain(std::iter::once(PathEl::ClosePath)),
+                data.precision,
             ) {
                 data.paste_paths(vec![path.into()]);
             }
@@ -147,15 +157,16 @@ impl MouseDelegate<EditSession> for Ellipse {
     }
 
     fn left_drag_began(&mut self, event: Drag, data: &mut EditSession) {
-        let start = data.viewport.from_screen(event.start.pos);
-        let current = data.viewport.from_screen(event.current.pos);
+        let start = data.viewport.from_screen(event.start.pos, data.precision);
+        let current = data.viewport.from_screen(event.current.pos, data.precision);
         self.gesture = GestureState::Begun { start, current };
         self.shift_locked = event.current.mods.shift();
+        self.alt_down = event.current.mods.alt();
     }
 
     fn left_drag_changed(&mut self, drag: Drag, data: &mut EditSession) {
         if let GestureState::Begun { current, .. } = &mut self.gesture {
-            *current = data.viewport.from_screen(drag.current.pos);
+            *current = data.viewport.from_screen(drag.current.pos, data.precision);
         }
     }
 }
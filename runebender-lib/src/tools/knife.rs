@@ -2,10 +2,11 @@
 
 use druid::kurbo::{Line, LineIntersection, ParamCurve, ParamCurveArclen};
 use druid::piet::StrokeStyle;
-use druid::{Env, EventCtx, KbKey, KeyEvent, MouseEvent, PaintCtx, Point, RenderContext};
+use druid::{Cursor, Env, EventCtx, KbKey, KeyEvent, MouseEvent, PaintCtx, Point, RenderContext};
 
 use crate::cubic_path::CubicPath;
-use crate::design_space::DPoint;
+use crate::design_space::{DPoint, Precision};
+use crate::draw;
 use crate::edit_session::EditSession;
 use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
 use crate::path::Path;
@@ -100,6 +101,7 @@ impl Knife {
 
         self.intersections.clear();
 
+        let precision = data.precision;
         let iter = data
             .paths
             .iter()
@@ -107,7 +109,7 @@ impl Knife {
             .flat_map(|seg| {
                 seg.intersect_line(line)
                     .into_iter()
-                    .map(move |hit| DPoint::from_raw(line.eval(hit.line_t)))
+                    .map(move |hit| DPoint::from_raw(line.eval(hit.line_t), precision))
             });
         self.intersections.extend(iter);
     }
@@ -118,6 +120,18 @@ impl Tool for Knife {
         "Knife"
     }
 
+    fn hint(&self) -> &'static str {
+        "Drag across paths to cut them; hold shift to lock to an axis"
+    }
+
+    fn edit_description(&self) -> &'static str {
+        "Cut"
+    }
+
+    fn default_cursor(&self) -> Cursor {
+        Cursor::Crosshair
+    }
+
     fn cancel(
         &mut self,
         mouse: &mut Mouse,
@@ -206,6 +220,11 @@ impl Tool for Knife {
                 //ctx.stroke(Line::new(cms2, cme2), &Color::BLACK, 1.0);
             }
         }
+        if data.show_loupe {
+            if let Some((_, end)) = self.current_points() {
+                draw::draw_loupe(ctx, _env, data, end);
+            }
+        }
     }
 }
 
@@ -236,8 +255,10 @@ impl MouseDelegate<EditSession> for Knife {
 
         if let Some(line) = self.current_line_in_dspace() {
             if !self.intersections.is_empty() {
-                let new_paths = slice_paths(&data.paths, line);
+                let keep_open = data.tool_options.knife_keeps_open_paths;
+                let new_paths = slice_paths(&data.paths, line, keep_open, data.precision);
                 data.paths = new_paths.into();
+                data.outline_changed();
             }
         }
 
@@ -283,11 +304,18 @@ impl Hit {
 ///     - for each new path, insert a new line segment between the two cut points
 /// - modify the line so that it now starts at the last of those hit points
 /// - recursively try to cut each new path with the modified line
-fn slice_paths(paths: &[Path], line: Line) -> Vec<Path> {
+///
+/// If `keep_open` is true, open paths are instead split at each hit into
+/// open pieces, with nothing added along the cut.
+///
+/// The new points are rounded to `precision`.
+fn slice_paths(paths: &[Path], line: Line, keep_open: bool, precision: Precision) -> Vec<Path> {
     let mut out = Vec::new();
     for path in paths {
-        if !path.is_hyper() {
-            slice_path(path, line, &mut out);
+        if keep_open && !path.is_closed() {
+            split_open_path(path, line, &mut out, precision);
+        } else if !path.is_hyper() {
+            slice_path(path, line, &mut out, precision);
         } else {
             log::info!("slice not implemented for hyperbezier paths");
             out.push(path.clone());
@@ -301,12 +329,75 @@ fn slice_paths(paths: &[Path], line: Line) -> Vec<Path> {
 /// Resulting paths are pushed to the `acc` vec.
 ///
 /// If no modifications are made, the source `path` should still be pushed to `acc`.
-fn slice_path(path: &Path, line: Line, acc: &mut Vec<Path>) {
+fn slice_path(path: &Path, line: Line, acc: &mut Vec<Path>, precision: Precision) {
     let mut hits = Vec::new();
     // we clone here; the impl is recursive and if this path isn't sliced the
     // clone will be returned in `acc`.
     if let Path::Cubic(path) = path {
-        slice_path_impl(path.clone(), line, acc, &mut hits, 0)
+        slice_path_impl(path.clone(), line, acc, &mut hits, 0, precision)
+    }
+}
+
+/// Split an open path at each place it crosses a line.
+///
+/// The pieces are pushed to the `acc` vec; the first keeps the path's id.
+/// If the line doesn't cross the path, the path itself is pushed.
+fn split_open_path(path: &Path, line: Line, acc: &mut Vec<Path>, precision: Precision) {
+    let cubic = match path {
+        Path::Cubic(cubic) => cubic,
+        Path::Hyper(_) => {
+            log::info!("slice not implemented for hyperbezier paths");
+            acc.push(path.clone());
+            return;
+        }
+    };
+
+    let segments: Vec<(RawSegment, Vec<f64>)> = cubic
+        .iter_segments()
+        .map(|seg| {
+            let mut hits: Vec<f64> = seg
+                .to_kurbo()
+                .intersect_line(line)
+                .into_iter()
+                .map(|hit| hit.segment_t)
+                .collect();
+            hits.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            (seg, hits)
+        })
+        .collect();
+    if segments.iter().all(|(_, hits)| hits.is_empty()) {
+        acc.push(path.clone());
+        return;
+    }
+
+    let mut id = cubic.path_points().id();
+    let mut points = Vec::new();
+    for (seg, hits) in segments {
+        if hits.is_empty() {
+            append_all_points(&mut points, seg);
+            continue;
+        }
+        let mut prev_t = 0.0;
+        for t in hits {
+            if t > prev_t {
+                append_all_points(&mut points, seg.subsegment(prev_t..t, precision));
+            }
+            // a line through a point hits both of its segments; we only cut once
+            if points.len() > 1 {
+                let next_id = EntityId::next();
+                let cut = PathPoint::on_curve(next_id, points.last().unwrap().point);
+                let piece = std::mem::replace(&mut points, vec![cut]);
+                acc.push(finalize_path(piece, id, false).into());
+                id = next_id;
+            }
+            prev_t = t;
+        }
+        if prev_t < 1.0 {
+            append_all_points(&mut points, seg.subsegment(prev_t..1.0, precision));
+        }
+    }
+    if points.len() > 1 {
+        acc.push(finalize_path(points, id, false).into());
     }
 }
 
@@ -319,6 +410,7 @@ fn slice_path_impl(
     acc: &mut Vec<Path>,
     hit_buf: &mut Vec<Hit>,
     recurse: usize,
+    precision: Precision,
 ) {
     hit_buf.clear();
     hit_buf.extend(path.iter_segments().flat_map(|seg| {
@@ -330,7 +422,7 @@ fn slice_path_impl(
 
     if hit_buf.len() <= 1 || recurse == MAX_RECURSE {
         if let Some(hit) = hit_buf.first() {
-            path.split_segment_at_point(hit.seg, hit.intersection.segment_t);
+            path.split_segment_at_point(hit.seg, hit.intersection.segment_t, precision);
         }
         if recurse == MAX_RECURSE {
             log::info!("slice_path hit recurse limit");
@@ -365,20 +457,25 @@ fn slice_path_impl(
     let (start, end) = order_points(&path, start, end);
 
     // generate the path on either side of the cut
-    let (path_one, path_two) = split_path_at_intersections(&path, start, end);
+    let (path_one, path_two) = split_path_at_intersections(&path, start, end, precision);
 
     // calculate the cut line that remains to be processed
     let line = line.subsegment(next_line_start_t..1.0);
     // recurse on each of the new paths
-    slice_path_impl(path_one, line, acc, hit_buf, recurse + 1);
-    slice_path_impl(path_two, line, acc, hit_buf, recurse + 1);
+    slice_path_impl(path_one, line, acc, hit_buf, recurse + 1, precision);
+    slice_path_impl(path_two, line, acc, hit_buf, recurse + 1, precision);
 }
 
 /// Given a path and two points on that path, divide it in two.
 ///
 /// The 'first' path includes the paths original start point, and may be open.
 /// The 'second' path is the part that is 'sliced off', and it always closed.
-fn split_path_at_intersections(path: &CubicPath, start: Hit, end: Hit) -> (CubicPath, CubicPath) {
+fn split_path_at_intersections(
+    path: &CubicPath,
+    start: Hit,
+    end: Hit,
+    precision: Precision,
+) -> (CubicPath, CubicPath) {
     let one_id = path.path_points().id();
     let two_id = EntityId::next();
     let mut one_points = Vec::new();
@@ -393,20 +490,23 @@ fn split_path_at_intersections(path: &CubicPath, start: Hit, end: Hit) -> (Cubic
             append_all_points(&mut one_points, seg);
         } else {
             let cut_t = start.seg_t();
-            append_all_points(&mut one_points, seg.subsegment(0.0..cut_t));
+            append_all_points(&mut one_points, seg.subsegment(0.0..cut_t, precision));
 
             if seg.start_id() == end.seg.start_id() {
                 // the special case where the cut starts and ends in the same segment:
-                append_all_points(&mut one_points, seg.subsegment(end.seg_t()..1.));
-                append_all_points(&mut two_points, seg.subsegment(cut_t..end.seg_t()));
+                let after = seg.subsegment(end.seg_t()..1., precision);
+                append_all_points(&mut one_points, after);
+                let between = seg.subsegment(cut_t..end.seg_t(), precision);
+                append_all_points(&mut two_points, between);
                 two_is_done = true;
             } else {
-                append_all_points(&mut two_points, seg.subsegment(cut_t..1.));
+                append_all_points(&mut two_points, seg.subsegment(cut_t..1., precision));
             }
 
             if !path.is_closed() {
                 // add the cut line
-                two_points.push(PathPoint::on_curve(two_id, DPoint::from_raw(start.point)));
+                let cut = DPoint::from_raw(start.point, precision);
+                two_points.push(PathPoint::on_curve(two_id, cut));
             }
             break;
         }
@@ -416,9 +516,9 @@ fn split_path_at_intersections(path: &CubicPath, start: Hit, end: Hit) -> (Cubic
     for seg in &mut iter {
         if seg.start_id() == end.seg.start_id() {
             let cut_t = end.seg_t();
-            append_all_points(&mut one_points, seg.subsegment(cut_t..1.0));
+            append_all_points(&mut one_points, seg.subsegment(cut_t..1.0, precision));
             if !two_is_done {
-                append_all_points(&mut two_points, seg.subsegment(0.0..cut_t));
+                append_all_points(&mut two_points, seg.subsegment(0.0..cut_t, precision));
             }
             break;
         } else if !two_is_done {
@@ -545,7 +645,7 @@ mod tests {
 
         let line = Line::new((3., 6.), (8., -2.));
         let mut out = Vec::new();
-        slice_path(&path, line, &mut out);
+        slice_path(&path, line, &mut out, Precision::Integer);
 
         assert_eq!(out.len(), 2);
         let one = &out[0];
@@ -587,17 +687,19 @@ mod tests {
         bez.curve_to((9.0, 5.0), (15.0, 0.0), (0.0, 0.0));
         bez.close_path();
 
-        let path: Path = CubicPath::from_bezpath(bez).unwrap().into();
+        let path: Path = CubicPath::from_bezpath(bez, Precision::Integer)
+            .unwrap()
+            .into();
 
         // first try slicing a non-first segment
         let slice_line1 = Line::new((10., 20.), (25., 10.));
         let slice_line2 = Line::new((25., 10.), (10., 20.));
 
         let mut out = Vec::new();
-        slice_path(&path, slice_line1, &mut out);
+        slice_path(&path, slice_line1, &mut out, Precision::Integer);
         let first = out.clone();
         out.clear();
-        slice_path(&path, slice_line2, &mut out);
+        slice_path(&path, slice_line2, &mut out, Precision::Integer);
         let second = out;
         assert_eq!(first.len(), 2);
         assert_eq!(second.len(), 2);
@@ -610,10 +712,10 @@ mod tests {
         let slice_line2 = Line::new((10., 0.), (0., 10.));
 
         let mut out = Vec::new();
-        slice_path(&path, slice_line1, &mut out);
+        slice_path(&path, slice_line1, &mut out, Precision::Integer);
         let first = out.clone();
         out.clear();
-        slice_path(&path, slice_line2, &mut out);
+        slice_path(&path, slice_line2, &mut out, Precision::Integer);
         let second = out;
         assert_eq!(first.len(), 2);
         assert_eq!(second.len(), 2);
@@ -628,9 +730,11 @@ mod tests {
         bez.move_to((0.0, 0.0));
         bez.curve_to((0.0, 15.0), (10.0, 15.0), (10.0, 0.0));
 
-        let path: Path = CubicPath::from_bezpath(bez).unwrap().into();
+        let path: Path = CubicPath::from_bezpath(bez, Precision::Integer)
+            .unwrap()
+            .into();
         let slice_line = Line::new((0., 8.), (10., 8.));
-        let paths = slice_paths(&[path], slice_line);
+        let paths = slice_paths(&[path], slice_line, false, Precision::Integer);
         assert_eq!(paths.len(), 2);
 
         let path1 = paths.get(0).unwrap();
@@ -642,4 +746,20 @@ mod tests {
         assert!(path2.is_closed());
         assert_eq!(path2.points().len(), 5);
     }
+
+    #[test]
+    fn keep_open_paths_open() {
+        let mut path = Path::new(DPoint::new(0., 0.));
+        path.line_to(DPoint::new(20., 0.), false);
+        path.line_to(DPoint::new(20., 20.), false);
+
+        let slice_line = Line::new((10., -5.), (25., 10.));
+        let paths = slice_paths(&[path], slice_line, true, Precision::Integer);
+        assert_eq!(paths.len(), 3);
+        assert!(paths.iter().all(|path| !path.is_closed()));
+        assert_eq!(paths[0].points().len(), 2);
+        assert_eq!(paths[0].points()[1].point, DPoint::new(15., 0.));
+        assert_eq!(paths[1].points()[0].point, DPoint::new(15., 0.));
+        assert_eq!(paths[2].points()[1].point, DPoint::new(20., 20.));
+    }
 }
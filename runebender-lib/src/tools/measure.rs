@@ -6,10 +6,21 @@ use crate::design_space::DPoint;
 use crate::edit_session::EditSession;
 use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
 use crate::tools::{EditType, Tool};
+use crate::tunni::Tunni;
+use crate::util::format_num;
 
 #[derive(Default)]
 pub struct Measure {
     line: Option<Line>,
+    /// The measurement along `line`, updated when the line changes.
+    measurement: Option<Measurement>,
+}
+
+/// The measurement along the line, in design space.
+struct Measurement {
+    line: Line,
+    /// Where the line crosses the outline, as fractions of its length.
+    intersections: Vec<f64>,
 }
 
 const MEASURE_LINE_STROKE_COLOR: Color = Color::rgb8(0x73, 0x9B, 0xCB);
@@ -18,13 +29,22 @@ const MEASURE_INFO_FG_COLOR: Color = Color::rgb8(0xf8, 0xf8, 0xf8);
 const MEASURE_INFO_ONCURVE_COLOR: Color = Color::rgb8(0x80, 0x80, 0xe0);
 const MEASURE_INFO_OFFCURVE_COLOR: Color = Color::rgb8(0x60, 0xc0, 0x60);
 const MEASURE_INFO_DELTA_COLOR: Color = Color::rgb8(0xa0, 0x20, 0x20);
+const MEASURE_INFO_TENSION_COLOR: Color = Color::rgb8(0x40, 0x90, 0xd0);
 const MEASURE_INFO_FONT_SIZE: f64 = 9.0;
 const MEASURE_INTERSECTION_RADIUS: f64 = 3.0;
+/// With shift held, the measurement line snaps to multiples of this angle.
+const MEASURE_SNAP_ANGLE: f64 = std::f64::consts::FRAC_PI_4;
 
 // Don't report segments smaller than this.
 const MEASURE_FUZZY_TOLERANCE: f64 = 0.1;
 
 fn draw_info_bubble(ctx: &mut PaintCtx, pos: Point, label: impl Into<String>) {
+    draw_info_bubble_aligned(ctx, pos, label, 0.5)
+}
+
+/// Draw a bubble with its vertical center at `pos`; `align` is the fraction
+/// of the bubble's width that is to the left of `pos`.
+fn draw_info_bubble_aligned(ctx: &mut PaintCtx, pos: Point, label: impl Into<String>, align: f64) {
     let text = ctx.text();
     let layout = text
         .new_text_layout(label.into())
@@ -33,6 +53,7 @@ fn draw_info_bubble(ctx: &mut PaintCtx, pos: Point, label: impl Into<String>) {
         .build()
         .unwrap();
     let width = layout.size().width;
+    let pos = pos + Vec2::new((0.5 - align) * width, 0.0);
     let bubble = Rect::from_center_size(pos, Size::new(width + 6.0, 12.0)).to_rounded_rect(6.0);
     let origin = pos - Vec2::new(0.5 * width, 6.5);
     ctx.fill(bubble, &MEASURE_INFO_BG_COLOR);
@@ -61,13 +82,30 @@ fn draw_label(ctx: &mut PaintCtx, label: String, pos: Point, color: Color) {
     ctx.draw_text(&layout, pos);
 }
 
+/// Constrain `point` to the nearest multiple of `MEASURE_SNAP_ANGLE` from
+/// `start`, keeping its distance along that direction.
+fn snap_to_angle(point: Point, start: Point) -> Point {
+    let delta = point - start;
+    let angle = (delta.atan2() / MEASURE_SNAP_ANGLE).round() * MEASURE_SNAP_ANGLE;
+    let dir = Vec2::from_angle(angle);
+    start + dir * delta.dot(dir)
+}
+
 fn format_pt(pt: DPoint) -> String {
-    let x = format!("{:.1}", pt.x);
-    let y = format!("{:.1}", pt.y);
-    format!("{}, {}", x.trim_end_matches(".0"), y.trim_end_matches(".0"))
+    format!("{}, {}", format_num(pt.x, 1), format_num(pt.y, 1))
 }
 
 impl Measure {
+    fn measure(&self, data: &EditSession, line: Line) -> Measurement {
+        let p0 = data.viewport.from_screen(line.p0, data.precision);
+        let p1 = data.viewport.from_screen(line.p1, data.precision);
+        let line = Line::new(p0.to_raw(), p1.to_raw());
+        Measurement {
+            line,
+            intersections: self.compute_measurement(data, line),
+        }
+    }
+
     #[allow(clippy::float_cmp)]
     fn compute_measurement(&self, data: &EditSession, design_line: Line) -> Vec<f64> {
         // We scale the intersections to fixed point to make them easier to sort.
@@ -123,11 +161,17 @@ impl Measure {
             }
             for seg in path.iter_segments() {
                 let mid_pt = seg.eval(0.5);
-                let scr_pt = data.viewport.to_screen(DPoint::from_raw(mid_pt));
+                let scr_pt = data.viewport.affine() * mid_pt;
                 let delta = seg.raw_segment().start().point - seg.raw_segment().end().point;
                 let label = format_pt(DPoint::new(delta.x, delta.y));
                 // TODO: nudge placement of label to reduce crowding
                 draw_label(ctx, label, scr_pt, MEASURE_INFO_DELTA_COLOR);
+                if let Some(tunni) = Tunni::for_segment(&seg) {
+                    let (t1, t2) = tunni.tensions();
+                    let label = format!("tension {:.0}%, {:.0}%", t1 * 100.0, t2 * 100.0);
+                    let pos = scr_pt + Vec2::new(0.0, MEASURE_INFO_FONT_SIZE + 2.0);
+                    draw_label(ctx, label, pos, MEASURE_INFO_TENSION_COLOR);
+                }
             }
         }
     }
@@ -138,6 +182,10 @@ impl Tool for Measure {
         "Measure"
     }
 
+    fn hint(&self) -> &'static str {
+        "Drag to measure distance and angle; hold shift to snap to 45°"
+    }
+
     fn cancel(
         &mut self,
         mouse: &mut Mouse,
@@ -150,7 +198,10 @@ impl Tool for Measure {
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &EditSession, _env: &Env) {
         self.paint_coords(ctx, data);
-        if let Some(line) = self.line {
+        if let (Some(line), Some(measurement)) = (self.line, &self.measurement) {
+            let design_line = measurement.line;
+            let design_len = (design_line.p1 - design_line.p0).hypot();
+
             let angle = atan_to_angle((line.p1 - line.p0).atan2());
             let angle_offset = if angle < 90.0 {
                 Vec2::new(14.0, -6.0)
@@ -160,30 +211,32 @@ impl Tool for Measure {
                 Vec2::new(-14.0, 8.0)
             };
             ctx.stroke(line, &MEASURE_LINE_STROKE_COLOR, 1.0);
-            let label = format!("{:.1}°", angle);
-            draw_info_bubble(ctx, line.p1 + angle_offset, label);
-            // TODO: compute earlier than paint
-            if let Some(line) = self.line {
-                let p0 = data.viewport.from_screen(line.p0);
-                let p1 = data.viewport.from_screen(line.p1);
-                let design_line = Line::new(p0.to_raw(), p1.to_raw());
-                let design_len = (design_line.p1 - design_line.p0).hypot();
-                let intersections = self.compute_measurement(data, design_line);
-                for t in &intersections {
-                    let pt = line.p0.lerp(line.p1, *t);
-                    let circle = Circle::new(pt, MEASURE_INTERSECTION_RADIUS);
-                    ctx.fill(circle, &MEASURE_LINE_STROKE_COLOR);
-                }
-                for i in 0..intersections.len() - 1 {
-                    let t0 = intersections[i];
-                    let t1 = intersections[i + 1];
-                    let tmid = 0.5 * (t0 + t1);
-                    let seg_len = design_len * (t1 - t0);
-                    let center = design_line.p0.lerp(design_line.p1, tmid);
-                    let center_screen = data.viewport.to_screen(DPoint::from_raw(center));
-                    let len_label = format!("{:.1}", seg_len);
-                    draw_info_bubble(ctx, center_screen, len_label);
-                }
+            let per_mille = design_len * 1000.0 / data.units_per_em;
+            let label = format!(
+                "{} units   {}‰ em   {:.1}°",
+                format_num(design_len),
+                format_num(per_mille),
+                angle
+            );
+            // the bubble extends away from the end of the line
+            let align = if angle_offset.x < 0.0 { 1.0 } else { 0.0 };
+            draw_info_bubble_aligned(ctx, line.p1 + angle_offset, label, align);
+
+            let intersections = &measurement.intersections;
+            for t in intersections {
+                let pt = line.p0.lerp(line.p1, *t);
+                let circle = Circle::new(pt, MEASURE_INTERSECTION_RADIUS);
+                ctx.fill(circle, &MEASURE_LINE_STROKE_COLOR);
+            }
+            for i in 0..intersections.len() - 1 {
+                let t0 = intersections[i];
+                let t1 = intersections[i + 1];
+                let tmid = 0.5 * (t0 + t1);
+                let seg_len = design_len * (t1 - t0);
+                let center = design_line.p0.lerp(design_line.p1, tmid);
+                let center_screen = data.viewport.affine() * center;
+                let len_label = format!("{:.1}", seg_len);
+                draw_info_bubble(ctx, center_screen, len_label);
             }
         }
     }
@@ -199,6 +252,7 @@ impl Tool for Measure {
         let pre_line = self.line;
         mouse.mouse_event(event, data, self);
         if !pre_line.same(&self.line) {
+            self.measurement = self.line.map(|line| self.measure(data, line));
             ctx.request_paint();
         }
         None
@@ -208,6 +262,7 @@ impl Tool for Measure {
 impl MouseDelegate<EditSession> for Measure {
     fn cancel(&mut self, _data: &mut EditSession) {
         self.line = None;
+        self.measurement = None;
     }
 
     fn left_drag_began(&mut self, drag: Drag, _data: &mut EditSession) {
@@ -218,7 +273,7 @@ impl MouseDelegate<EditSession> for Measure {
         if let Some(line) = &mut self.line {
             let mut pos = drag.current.pos;
             if drag.current.mods.shift() {
-                pos = super::axis_locked_point(pos, drag.start.pos);
+                pos = snap_to_angle(pos, drag.start.pos);
             }
             line.p1 = pos;
         }
@@ -3,22 +3,28 @@
 mod ellipse;
 mod knife;
 mod measure;
+mod nib;
 mod pen;
+mod polygon;
 mod preview;
 mod rectangle;
 mod select;
+mod transform;
 
 pub use ellipse::Ellipse;
 pub use knife::Knife;
 pub use measure::Measure;
+pub use nib::Nib;
 pub use pen::Pen;
+pub use polygon::Polygon;
 pub use preview::Preview;
 pub use rectangle::Rectangle;
 pub use select::Select;
+pub use transform::Transform;
 
+use crate::design_space::DPoint;
 use crate::edit_session::EditSession;
 use crate::mouse::{Mouse, TaggedEvent};
-use druid::kurbo::Point;
 use druid::{Cursor, Env, EventCtx, KeyEvent, PaintCtx};
 
 /// Something to pass around instead of a Box<dyn Tool>
@@ -132,6 +138,17 @@ pub trait Tool {
 
     fn name(&self) -> ToolId;
 
+    /// A short description of how to use this tool, shown in the status bar.
+    fn hint(&self) -> &'static str {
+        ""
+    }
+
+    /// What an edit made with this tool is called in the Undo and Redo
+    /// menu items, like "Draw" for "Undo Draw".
+    fn edit_description(&self) -> &'static str {
+        self.name()
+    }
+
     fn default_cursor(&self) -> Cursor {
         Cursor::Arrow
     }
@@ -146,8 +163,12 @@ pub fn tool_for_id(id: ToolId) -> Option<Box<dyn Tool>> {
         "Select" => Some(Box::new(Select::default())),
         "Rectangle" => Some(Box::new(Rectangle::default())),
         "Ellipse" => Some(Box::new(Ellipse::default())),
+        "Polygon" => Some(Box::new(Polygon::polygon())),
+        "Star" => Some(Box::new(Polygon::star())),
+        "Transform" => Some(Box::new(Transform::default())),
         "Knife" => Some(Box::new(Knife::default())),
         "Measure" => Some(Box::new(Measure::default())),
+        "Nib" => Some(Box::new(Nib::default())),
         _ => None,
     }
 }
@@ -165,15 +186,42 @@ impl EditType {
             _ => true,
         }
     }
+
+    /// A description of this edit for the undo menu items, if its type alone
+    /// says what it was.
+    pub fn description(self) -> Option<&'static str> {
+        match self {
+            EditType::NudgeLeft
+            | EditType::NudgeRight
+            | EditType::NudgeUp
+            | EditType::NudgeDown => Some("Nudge"),
+            _ => None,
+        }
+    }
 }
 
-/// Lock the smallest axis of `point` (from `prev`) to that axis on `prev`.
-/// (aka shift + click)
-fn axis_locked_point(point: Point, prev: Point) -> Point {
-    let dxy = prev - point;
-    if dxy.x.abs() > dxy.y.abs() {
-        Point::new(point.x, prev.y)
+/// The corners of a shape's bounding box, as it is dragged from `start` to
+/// `current`.
+///
+/// If `square` is true the box is made square, using the larger side; if
+/// `from_center` is true, `start` is the center of the box instead of one of
+/// its corners.
+fn shape_corners(
+    start: DPoint,
+    current: DPoint,
+    square: bool,
+    from_center: bool,
+) -> (DPoint, DPoint) {
+    let mut vec2 = current - start;
+    if square {
+        let side = vec2.x.abs().max(vec2.y.abs());
+        vec2.x = side.copysign(vec2.x);
+        vec2.y = side.copysign(vec2.y);
+    }
+    let current = start + vec2;
+    if from_center {
+        (start - vec2, current)
     } else {
-        Point::new(prev.x, point.y)
+        (start, current)
     }
 }
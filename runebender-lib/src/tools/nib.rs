@@ -0,0 +1,176 @@
+//! The nib tool, for drawing strokes with a calligraphic nib.
+
+use std::collections::BTreeSet;
+
+use druid::kurbo::{BezPath, Point};
+use druid::{Color, Env, EventCtx, PaintCtx, RenderContext};
+
+use crate::cubic_path::CubicPath;
+use crate::design_space::{DPoint, Precision};
+use crate::edit_session::EditSession;
+use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
+use crate::path::Path;
+use crate::point::{EntityId, PathPoint};
+use crate::tools::{EditType, Tool};
+
+/// The distance, in screen space, the mouse must move before another point
+/// is added to the skeleton.
+const MIN_POINT_DISTANCE: f64 = 4.0;
+
+/// How far, in design units, the fitted skeleton may stray from the mouse's
+/// track.
+const SKELETON_TOLERANCE: f64 = 2.0;
+
+const PREVIEW_COLOR: Color = Color::rgb8(0x80, 0x80, 0x80);
+
+/// The state of the nib tool.
+///
+/// Dragging draws a skeleton, which is previewed as it will be filled by
+/// the workspace's nib. When the drag ends, the skeleton is smoothed and
+/// expanded into closed outlines.
+#[derive(Debug, Clone, Default)]
+pub struct Nib {
+    /// The points the mouse has passed through, in design space.
+    skeleton: Vec<Point>,
+    finished: bool,
+}
+
+impl Nib {
+    fn add_point(&mut self, pos: Point, data: &EditSession) {
+        let pos = data.viewport.from_screen(pos, data.precision).to_raw();
+        let min_distance = MIN_POINT_DISTANCE / data.viewport.zoom;
+        match self.skeleton.last() {
+            Some(last) if last.distance(pos) < min_distance => (),
+            _ => self.skeleton.push(pos),
+        }
+    }
+
+    /// The skeleton as drawn, as a polyline.
+    fn skeleton_polyline(&self) -> BezPath {
+        let mut bez = BezPath::new();
+        for (i, pt) in self.skeleton.iter().enumerate() {
+            if i == 0 {
+                bez.move_to(*pt);
+            } else {
+                bez.line_to(*pt);
+            }
+        }
+        bez
+    }
+
+    /// The skeleton as a path, with its curves fitted to the mouse's track
+    /// and its points rounded to `precision`.
+    fn fitted_skeleton(&self, precision: Precision) -> Option<Path> {
+        if self.skeleton.len() < 2 {
+            return None;
+        }
+        let path_id = EntityId::next();
+        let mut points = self
+            .skeleton
+            .iter()
+            .map(|pt| PathPoint::on_curve(path_id, DPoint::from_raw(*pt, precision)))
+            .collect::<Vec<_>>();
+        points.dedup_by(|a, b| a.point == b.point);
+        if points.len() < 2 {
+            return None;
+        }
+        let mut path: Path = CubicPath::from_raw_parts(path_id, points, None, false).into();
+        path.simplify(SKELETON_TOLERANCE, &BTreeSet::new(), precision);
+        Some(path)
+    }
+}
+
+impl Tool for Nib {
+    fn name(&self) -> &'static str {
+        "Nib"
+    }
+
+    fn hint(&self) -> &'static str {
+        "Drag to draw a stroke with the nib; choose the nib with Paths ▸ Nib…"
+    }
+
+    fn edit_description(&self) -> &'static str {
+        "Draw"
+    }
+
+    fn cancel(
+        &mut self,
+        mouse: &mut Mouse,
+        _ctx: &mut EventCtx,
+        data: &mut EditSession,
+    ) -> Option<EditType> {
+        mouse.cancel(data, self);
+        None
+    }
+
+    fn mouse_event(
+        &mut self,
+        event: TaggedEvent,
+        mouse: &mut Mouse,
+        ctx: &mut EventCtx,
+        data: &mut EditSession,
+        _: &Env,
+    ) -> Option<EditType> {
+        let pre_len = self.skeleton.len();
+        mouse.mouse_event(event, data, self);
+        if pre_len != self.skeleton.len() {
+            ctx.request_paint();
+        }
+
+        if self.finished {
+            self.finished = false;
+            Some(EditType::Normal)
+        } else {
+            None
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &EditSession, _env: &Env) {
+        if self.skeleton.len() < 2 {
+            return;
+        }
+        let affine = data.viewport.affine();
+        for mut piece in data.nib.pieces(&self.skeleton_polyline()) {
+            piece.apply_affine(affine);
+            ctx.fill(piece, &PREVIEW_COLOR);
+        }
+        let mut skeleton = self.skeleton_polyline();
+        skeleton.apply_affine(affine);
+        ctx.stroke(skeleton, &Color::WHITE, 1.0);
+    }
+}
+
+impl MouseDelegate<EditSession> for Nib {
+    fn cancel(&mut self, _data: &mut EditSession) {
+        self.skeleton.clear();
+    }
+
+    fn left_drag_began(&mut self, drag: Drag, data: &mut EditSession) {
+        self.skeleton.clear();
+        self.add_point(drag.start.pos, data);
+        self.add_point(drag.current.pos, data);
+    }
+
+    fn left_drag_changed(&mut self, drag: Drag, data: &mut EditSession) {
+        self.add_point(drag.current.pos, data);
+    }
+
+    fn left_drag_ended(&mut self, drag: Drag, data: &mut EditSession) {
+        self.add_point(drag.current.pos, data);
+        let skeleton = self.fitted_skeleton(data.precision);
+        self.skeleton.clear();
+        let outline: Vec<Path> = match skeleton {
+            Some(skeleton) => data
+                .nib
+                .expand(&skeleton.bezier(), data.precision)
+                .iter()
+                .map(|contour| Path::from_norad(contour, data.precision))
+                .collect(),
+            None => return,
+        };
+        if !outline.is_empty() {
+            data.paste_paths(outline);
+            self.finished = true;
+        }
+    }
+}
@@ -1,8 +1,9 @@
 //! The bezier (and hyperbezier!) pen tool.
 
-use druid::{Env, EventCtx, KbKey, KeyEvent, MouseEvent};
+use druid::{Cursor, Env, EventCtx, KbKey, KeyEvent, MouseEvent, PaintCtx};
 
 use crate::design_space::DPoint;
+use crate::draw::{self, DragInfo};
 use crate::edit_session::EditSession;
 use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
 use crate::path::Path;
@@ -15,6 +16,10 @@ pub struct Pen {
     hyperbezier_mode: bool,
     this_edit_type: Option<EditType>,
     state: State,
+    /// The point inserted into a segment by the last click, if any.
+    inserted: Option<EntityId>,
+    /// Whether the last click closed the path being drawn.
+    closing: bool,
 }
 
 impl Pen {
@@ -49,12 +54,14 @@ impl MouseDelegate<EditSession> for Pen {
             _ => None,
         };
         self.state = State::Ready;
+        self.closing = false;
     }
 
     fn left_down(&mut self, event: &MouseEvent, data: &mut EditSession) {
         let vport = data.viewport;
         assert!(matches!(self.state, State::Ready));
         if event.count == 1 {
+            self.inserted = None;
             let hit = data.hit_test_filtered(event.pos, None, |_| true);
             if let Some(hit) = hit {
                 if let Some(path) = data.active_path() {
@@ -64,6 +71,7 @@ impl MouseDelegate<EditSession> for Pen {
                             data.selection.select_one(selection);
                             self.this_edit_type = Some(EditType::Normal);
                             self.state = State::AddPoint(selection);
+                            self.closing = true;
                             return;
                         }
                     } else if event.mods.alt() && path.is_hyper() {
@@ -73,20 +81,27 @@ impl MouseDelegate<EditSession> for Pen {
                     }
                 }
 
-                // TODO: hit-test *other* points? more stuff when clicking on
-                // If selection is empty, and point is endpoint of open path,
-                // select that point.
+                // clicking the end of another open path joins it to the path
+                // being drawn; otherwise we continue drawing from it.
+                if data.join_active_path(hit)
+                    || (!data.selection.contains(&hit) && data.continue_path(hit))
+                {
+                    self.this_edit_type = Some(EditType::Normal);
+                    return;
+                }
             }
 
-            // Handle clicking on segment (split).
+            // Clicking on a segment inserts a point there, which is selected
+            // so that it can be adjusted right away.
             if let Some((seg, t)) = data.hit_test_segments(event.pos, None) {
-                self.this_edit_type = Some(EditType::Normal);
-                let path = data.path_for_point_mut(seg.start_id()).unwrap();
-                path.split_segment_at_point(seg, t);
+                self.inserted = data.insert_point_on_segment(seg, t);
+                if self.inserted.is_some() {
+                    self.this_edit_type = Some(EditType::Normal);
+                }
                 return;
             }
 
-            let dpoint = vport.from_screen(event.pos);
+            let dpoint = vport.from_screen(event.pos, data.precision);
             let new_point =
                 if let Some(active) = data.active_path_mut().filter(|path| !path.is_closed()) {
                     let dpoint = if event.mods.shift() {
@@ -112,6 +127,11 @@ impl MouseDelegate<EditSession> for Pen {
             self.state = State::AddPoint(new_point);
             self.this_edit_type = Some(EditType::Normal);
         } else if event.count == 2 {
+            // the first click of a double-click on a segment has already
+            // inserted a point, which we keep selected.
+            if self.inserted.is_some() {
+                return;
+            }
             // This is not what Glyphs does; rather, it sets the currently active
             // point to non-smooth.
             data.selection.clear();
@@ -126,6 +146,15 @@ impl MouseDelegate<EditSession> for Pen {
                 path.clear_trailing();
             }
         }
+        // extrema are added once the closing curve is finished being dragged
+        if self.closing && data.tool_options.add_extrema_on_close {
+            let precision = data.precision;
+            if let Some(path) = data.active_path_mut() {
+                path.add_extrema(precision);
+                self.this_edit_type.get_or_insert(EditType::Normal);
+            }
+        }
+        self.closing = false;
         self.state = State::Ready;
     }
 
@@ -141,6 +170,7 @@ impl MouseDelegate<EditSession> for Pen {
     fn left_drag_began(&mut self, drag: Drag, data: &mut EditSession) {
         if let State::AddPoint(id) = self.state {
             let pos = current_drag_pos(&drag, data);
+            let precision = data.precision;
             let path = bail!(data.path_for_point_mut(id));
             let seg = path.iter_segments().find(|seg| seg.end_id() == id);
             if let Some(seg) = seg {
@@ -148,7 +178,7 @@ impl MouseDelegate<EditSession> for Pen {
                     if !seg.end().is_smooth() {
                         path.toggle_point_type(id);
                     }
-                    path.upgrade_line_seg(&seg, true);
+                    path.upgrade_line_seg(&seg, true, precision);
                 }
             }
             path.update_trailing(id, pos);
@@ -165,8 +195,8 @@ impl MouseDelegate<EditSession> for Pen {
 }
 
 fn current_drag_pos(drag: &Drag, data: &EditSession) -> DPoint {
-    let start = data.viewport.from_screen(drag.start.pos);
-    let current = data.viewport.from_screen(drag.current.pos);
+    let start = data.viewport.from_screen(drag.start.pos, data.precision);
+    let current = data.viewport.from_screen(drag.current.pos, data.precision);
     if drag.current.mods.shift() {
         current.axis_locked_to(start)
     } else {
@@ -185,6 +215,22 @@ impl Tool for Pen {
         self.this_edit_type.take()
     }
 
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &EditSession, env: &Env) {
+        if let State::DragHandle(id) = self.state {
+            let on_curve = bail!(data.path_point_for_id(id));
+            let handle = bail!(data.path_for_point(id).and_then(Path::trailing));
+            let info = DragInfo {
+                pos: Some(handle),
+                delta: handle - on_curve.point,
+                handle: Some(handle - on_curve.point),
+            };
+            info.paint(ctx, env, data.viewport.to_screen(handle));
+            if data.show_loupe {
+                draw::draw_loupe(ctx, env, data, handle.to_raw());
+            }
+        }
+    }
+
     fn mouse_event(
         &mut self,
         event: TaggedEvent,
@@ -221,6 +267,18 @@ impl Tool for Pen {
     fn name(&self) -> ToolId {
         "Pen"
     }
+
+    fn default_cursor(&self) -> Cursor {
+        Cursor::Crosshair
+    }
+
+    fn hint(&self) -> &'static str {
+        "Click to add a point, drag for a curve; click the first point to close the path, or an open end to join it"
+    }
+
+    fn edit_description(&self) -> &'static str {
+        "Draw"
+    }
 }
 
 impl Default for State {
@@ -0,0 +1,269 @@
+//! The polygon and star shape tools
+
+use std::f64::consts::PI;
+
+use druid::kurbo::{BezPath, Point, Vec2};
+use druid::{Color, Env, EventCtx, KbKey, KeyEvent, PaintCtx, RenderContext};
+
+use crate::cubic_path::CubicPath;
+use crate::design_space::{DPoint, Precision};
+use crate::edit_session::EditSession;
+use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
+use crate::point::{EntityId, PathPoint};
+use crate::tools::{EditType, Tool};
+
+const MIN_SIDES: usize = 3;
+const MAX_SIDES: usize = 64;
+const DEFAULT_POLYGON_SIDES: usize = 6;
+const DEFAULT_STAR_POINTS: usize = 5;
+/// The radius of a star's inner points, relative to its outer points.
+const DEFAULT_STAR_RATIO: f64 = 0.5;
+const STAR_RATIO_STEP: f64 = 0.05;
+
+/// The state of the polygon and star tools.
+///
+/// The shape is dragged out from its center, with the first point under the
+/// mouse. While dragging, the up and down arrow keys change the number of
+/// sides (or points, for a star), and for a star the left and right arrow
+/// keys change the depth of its points.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    star: bool,
+    sides: usize,
+    star_ratio: f64,
+    gesture: GestureState,
+    shift_locked: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GestureState {
+    Ready,
+    Begun { center: DPoint, current: DPoint },
+    Finished,
+}
+
+impl Polygon {
+    pub fn polygon() -> Self {
+        Polygon {
+            star: false,
+            sides: DEFAULT_POLYGON_SIDES,
+            star_ratio: DEFAULT_STAR_RATIO,
+            gesture: GestureState::Ready,
+            shift_locked: false,
+        }
+    }
+
+    pub fn star() -> Self {
+        Polygon {
+            star: true,
+            sides: DEFAULT_STAR_POINTS,
+            ..Polygon::polygon()
+        }
+    }
+
+    /// The corners of the shape being drawn, in design space, going
+    /// counter-clockwise.
+    fn vertices(&self) -> Option<Vec<Point>> {
+        let (center, current) = match self.gesture {
+            GestureState::Begun { center, current } => (center.to_raw(), current.to_raw()),
+            _ => return None,
+        };
+        let radius = center.distance(current);
+        if radius < 1.0 {
+            return None;
+        }
+        // with shift held, the first point is straight up
+        let angle = if self.shift_locked {
+            PI / 2.0
+        } else {
+            (current - center).atan2()
+        };
+
+        let count = if self.star {
+            self.sides * 2
+        } else {
+            self.sides
+        };
+        let step = 2.0 * PI / count as f64;
+        let vertices = (0..count)
+            .map(|i| {
+                let radius = if self.star && i % 2 == 1 {
+                    radius * self.star_ratio
+                } else {
+                    radius
+                };
+                center + Vec2::from_angle(angle + step * i as f64) * radius
+            })
+            .collect();
+        Some(vertices)
+    }
+
+    fn adjust_sides(&mut self, delta: isize) {
+        let sides = self.sides as isize + delta;
+        self.sides = sides.max(MIN_SIDES as isize).min(MAX_SIDES as isize) as usize;
+    }
+
+    fn adjust_star_ratio(&mut self, delta: f64) {
+        self.star_ratio = (self.star_ratio + delta).max(0.05).min(0.95);
+    }
+}
+
+impl Tool for Polygon {
+    fn name(&self) -> &'static str {
+        if self.star {
+            "Star"
+        } else {
+            "Polygon"
+        }
+    }
+
+    fn edit_description(&self) -> &'static str {
+        if self.star {
+            "Draw Star"
+        } else {
+            "Draw Polygon"
+        }
+    }
+
+    fn hint(&self) -> &'static str {
+        if self.star {
+            "Drag from the center to draw a star; ↑↓ change the points, ←→ their depth"
+        } else {
+            "Drag from the center to draw a polygon; ↑↓ change the sides, shift to keep upright"
+        }
+    }
+
+    fn cancel(
+        &mut self,
+        mouse: &mut Mouse,
+        _ctx: &mut EventCtx,
+        data: &mut EditSession,
+    ) -> Option<EditType> {
+        mouse.cancel(data, self);
+        None
+    }
+
+    fn key_down(
+        &mut self,
+        key: &KeyEvent,
+        ctx: &mut EventCtx,
+        _: &mut EditSession,
+        _: &Env,
+    ) -> Option<EditType> {
+        match &key.key {
+            KbKey::Shift => self.shift_locked = true,
+            KbKey::ArrowUp => self.adjust_sides(1),
+            KbKey::ArrowDown => self.adjust_sides(-1),
+            KbKey::ArrowRight if self.star => self.adjust_star_ratio(STAR_RATIO_STEP),
+            KbKey::ArrowLeft if self.star => self.adjust_star_ratio(-STAR_RATIO_STEP),
+            _ => return None,
+        }
+        ctx.request_paint();
+        None
+    }
+
+    fn key_up(
+        &mut self,
+        key: &KeyEvent,
+        ctx: &mut EventCtx,
+        _: &mut EditSession,
+        _: &Env,
+    ) -> Option<EditType> {
+        if key.key == KbKey::Shift {
+            self.shift_locked = false;
+            ctx.request_paint();
+        }
+        None
+    }
+
+    fn mouse_event(
+        &mut self,
+        event: TaggedEvent,
+        mouse: &mut Mouse,
+        ctx: &mut EventCtx,
+        data: &mut EditSession,
+        _: &Env,
+    ) -> Option<EditType> {
+        let pre_state = self.gesture;
+        mouse.mouse_event(event, data, self);
+        if pre_state != self.gesture {
+            ctx.request_paint();
+        }
+
+        if self.gesture == GestureState::Finished {
+            self.gesture = GestureState::Ready;
+            Some(EditType::Normal)
+        } else {
+            None
+        }
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &EditSession, _env: &Env) {
+        if let Some(vertices) = self.vertices() {
+            let mut path = BezPath::new();
+            for (i, pt) in vertices.into_iter().enumerate() {
+                let pt = data.viewport.affine() * pt;
+                if i == 0 {
+                    path.move_to(pt);
+                } else {
+                    path.line_to(pt);
+                }
+            }
+            path.close_path();
+            ctx.stroke(path, &Color::grey(0.7), 1.0);
+        }
+    }
+}
+
+impl MouseDelegate<EditSession> for Polygon {
+    fn cancel(&mut self, _data: &mut EditSession) {
+        self.gesture = GestureState::Ready;
+    }
+
+    fn left_drag_ended(&mut self, _drag: Drag, data: &mut EditSession) {
+        let path = self
+            .vertices()
+            .and_then(|v| make_polygon_path(&v, data.precision));
+        self.gesture = GestureState::Ready;
+        if let Some(path) = path {
+            data.paste_paths(vec![path.into()]);
+            self.gesture = GestureState::Finished;
+        }
+    }
+
+    fn left_drag_began(&mut self, event: Drag, data: &mut EditSession) {
+        let center = data.viewport.from_screen(event.start.pos, data.precision);
+        let current = data.viewport.from_screen(event.current.pos, data.precision);
+        self.gesture = GestureState::Begun { center, current };
+        self.shift_locked = event.current.mods.shift();
+    }
+
+    fn left_drag_changed(&mut self, drag: Drag, data: &mut EditSession) {
+        if let GestureState::Begun { current, .. } = &mut self.gesture {
+            *current = data.viewport.from_screen(drag.current.pos, data.precision);
+        }
+    }
+}
+
+/// Build a closed path of line segments through `vertices`, which are
+/// rounded to `precision`.
+///
+/// Returns `None` if the rounded shape is too small to enclose anything.
+fn make_polygon_path(vertices: &[Point], precision: Precision) -> Option<CubicPath> {
+    let path_id = EntityId::next();
+    let mut points = vertices
+        .iter()
+        .map(|pt| PathPoint::on_curve(path_id, DPoint::from_raw(*pt, precision)))
+        .collect::<Vec<_>>();
+    // small shapes can have several vertices round to the same point
+    points.dedup_by(|a, b| a.point == b.point);
+    if points.len() > 1 && points[0].point == points[points.len() - 1].point {
+        points.pop();
+    }
+    if points.len() < MIN_SIDES {
+        return None;
+    }
+    // first point goes last in closed paths
+    points.rotate_left(1);
+    Some(CubicPath::from_raw_parts(path_id, points, None, true))
+}
@@ -28,6 +28,10 @@ impl Tool for Preview {
         "Preview"
     }
 
+    fn hint(&self) -> &'static str {
+        "Drag to scroll"
+    }
+
     fn cancel(
         &mut self,
         mouse: &mut Mouse,
@@ -82,6 +86,19 @@ impl MouseDelegate<EditSession> for Preview {
         let delta = drag.current.pos - drag.prev.pos;
         data.viewport.set_offset(offset + delta);
     }
+
+    // the middle button, which is also a pen's barrel button, pans too
+    fn other_down(&mut self, event: &MouseEvent, data: &mut EditSession) {
+        self.left_down(event, data);
+    }
+
+    fn other_up(&mut self, event: &MouseEvent, data: &mut EditSession) {
+        self.left_up(event, data);
+    }
+
+    fn other_drag_changed(&mut self, drag: Drag, data: &mut EditSession) {
+        self.left_drag_changed(drag, data);
+    }
 }
 
 impl Default for State {
@@ -1,22 +1,28 @@
 //! The rectangle shape tool
 
+use druid::kurbo::Vec2;
 use druid::{
     Color, Env, EventCtx, KbKey, KeyEvent, MouseEvent, PaintCtx, Point, Rect, RenderContext,
     TextLayout,
 };
 
 use crate::cubic_path::CubicPath;
-use crate::design_space::DPoint;
+use crate::design_space::{DPoint, Precision};
 use crate::edit_session::EditSession;
 use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
 use crate::point::{EntityId, PathPoint};
-use crate::tools::{EditType, Tool};
+use crate::tools::{shape_corners, EditType, Tool};
+
+/// The length of the handles of a cubic quarter circle with unit radius.
+const QUARTER_CIRCLE_HANDLE: f64 = 0.552_284_749_8;
 
 /// The state of the rectangle tool.
 #[derive(Debug, Clone)]
 pub struct Rectangle {
     gesture: GestureState,
     shift_locked: bool,
+    /// If alt is held, the shape is drawn out from its center.
+    alt_down: bool,
     coord_text: TextLayout<String>,
 }
 
@@ -27,6 +33,7 @@ impl Default for Rectangle {
         Rectangle {
             gesture: Default::default(),
             shift_locked: false,
+            alt_down: false,
             coord_text: layout,
         }
     }
@@ -43,17 +50,12 @@ enum GestureState {
 impl Rectangle {
     fn pts_for_rect(&self) -> Option<(DPoint, DPoint)> {
         if let GestureState::Begun { start, current } = self.gesture {
-            let mut current = current;
-            if self.shift_locked {
-                let mut vec2 = current - start;
-                vec2.y = if vec2.y.signum() > 0.0 {
-                    vec2.x.abs()
-                } else {
-                    vec2.x.abs() * -1.0
-                };
-                current = start + vec2;
-            }
-            Some((start, current))
+            Some(shape_corners(
+                start,
+                current,
+                self.shift_locked,
+                self.alt_down,
+            ))
         } else {
             None
         }
@@ -73,6 +75,14 @@ impl Tool for Rectangle {
         "Rectangle"
     }
 
+    fn hint(&self) -> &'static str {
+        "Drag to draw a rectangle; hold shift for a square, alt to draw from the center"
+    }
+
+    fn edit_description(&self) -> &'static str {
+        "Draw Rectangle"
+    }
+
     fn cancel(
         &mut self,
         mouse: &mut Mouse,
@@ -90,10 +100,12 @@ impl Tool for Rectangle {
         _: &mut EditSession,
         _: &Env,
     ) -> Option<EditType> {
-        if key.key == KbKey::Shift {
-            self.shift_locked = true;
-            ctx.request_paint();
+        match key.key {
+            KbKey::Shift => self.shift_locked = true,
+            KbKey::Alt => self.alt_down = true,
+            _ => return None,
         }
+        ctx.request_paint();
         None
     }
 
@@ -104,10 +116,12 @@ impl Tool for Rectangle {
         _: &mut EditSession,
         _: &Env,
     ) -> Option<EditType> {
-        if key.key == KbKey::Shift {
-            self.shift_locked = false;
-            ctx.request_paint();
+        match key.key {
+            KbKey::Shift => self.shift_locked = false,
+            KbKey::Alt => self.alt_down = false,
+            _ => return None,
         }
+        ctx.request_paint();
         None
     }
 
@@ -164,15 +178,17 @@ impl MouseDelegate<EditSession> for Rectangle {
 
     fn left_down(&mut self, event: &MouseEvent, data: &mut EditSession) {
         if event.count == 1 {
-            let pt = data.viewport.from_screen(event.pos);
+            let pt = data.viewport.from_screen(event.pos, data.precision);
             self.gesture = GestureState::Down(pt);
             self.shift_locked = event.mods.shift();
+            self.alt_down = event.mods.alt();
         }
     }
 
     fn left_up(&mut self, _event: &MouseEvent, data: &mut EditSession) {
         if let Some((start, current)) = self.pts_for_rect() {
-            let path = make_rect_path(start, current);
+            let radius = data.tool_options.corner_radius;
+            let path = make_rect_path(start, current, radius, data.precision);
             data.paste_paths(vec![path.into()]);
             self.gesture = GestureState::Finished;
         }
@@ -180,14 +196,14 @@ impl MouseDelegate<EditSession> for Rectangle {
 
     fn left_drag_began(&mut self, event: Drag, data: &mut EditSession) {
         if let GestureState::Down(start) = self.gesture {
-            let current = data.viewport.from_screen(event.current.pos);
+            let current = data.viewport.from_screen(event.current.pos, data.precision);
             self.gesture = GestureState::Begun { start, current };
         }
     }
 
     fn left_drag_changed(&mut self, drag: Drag, data: &mut EditSession) {
         if let GestureState::Begun { current, .. } = &mut self.gesture {
-            *current = data.viewport.from_screen(drag.current.pos);
+            *current = data.viewport.from_screen(drag.current.pos, data.precision);
         }
     }
 }
@@ -198,16 +214,51 @@ impl Default for GestureState {
     }
 }
 
-fn make_rect_path(p1: DPoint, p3: DPoint) -> CubicPath {
+/// Make a rectangle with opposite corners at `p1` and `p3`, with its corners
+/// rounded by `radius`. New points are rounded to `precision`.
+fn make_rect_path(p1: DPoint, p3: DPoint, radius: f64, precision: Precision) -> CubicPath {
     let path_id = EntityId::next();
     let p2 = DPoint::new(p3.x, p1.y);
     let p4 = DPoint::new(p1.x, p3.y);
+    let size = p3 - p1;
+    let radius = radius.min(size.x.abs() / 2.0).min(size.y.abs() / 2.0);
+    if radius <= 0.0 {
+        // first point goes last in closed paths
+        let points = vec![
+            PathPoint::on_curve(path_id, p2),
+            PathPoint::on_curve(path_id, p3),
+            PathPoint::on_curve(path_id, p4),
+            PathPoint::on_curve(path_id, p1),
+        ];
+        return CubicPath::from_raw_parts(path_id, points, None, true);
+    }
+
+    let corners = [p1, p2, p3, p4];
+    let mut points: Vec<PathPoint> = Vec::with_capacity(16);
+    for (i, corner) in corners.iter().enumerate() {
+        let corner = corner.to_raw();
+        let to_prev = (corners[(i + 3) % 4].to_raw() - corner).normalize();
+        let to_next = (corners[(i + 1) % 4].to_raw() - corner).normalize();
+        let at = |dir: Vec2, dist: f64| DPoint::from_raw(corner + dir * dist, precision);
+        let start = at(to_prev, radius);
+        // if the radius is half a side, the previous corner ends here
+        if points.last().map(|p| p.point) != Some(start) {
+            points.push(PathPoint::on_curve_smooth(path_id, start));
+        }
+        points.push(PathPoint::off_curve(
+            path_id,
+            at(to_prev, radius * (1.0 - QUARTER_CIRCLE_HANDLE)),
+        ));
+        points.push(PathPoint::off_curve(
+            path_id,
+            at(to_next, radius * (1.0 - QUARTER_CIRCLE_HANDLE)),
+        ));
+        points.push(PathPoint::on_curve_smooth(path_id, at(to_next, radius)));
+    }
+    if points.first().map(|p| p.point) == points.last().map(|p| p.point) {
+        points.pop();
+    }
     // first point goes last in closed paths
-    let points = vec![
-        PathPoint::on_curve(path_id, p2),
-        PathPoint::on_curve(path_id, p3),
-        PathPoint::on_curve(path_id, p4),
-        PathPoint::on_curve(path_id, p1),
-    ];
+    points.rotate_left(1);
     CubicPath::from_raw_parts(path_id, points, None, true)
 }
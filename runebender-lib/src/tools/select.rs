@@ -1,14 +1,19 @@
-use druid::kurbo::{BezPath, Circle, Insets, Point, Rect, Vec2};
+use std::collections::HashMap;
+
+use druid::kurbo::{Affine, BezPath, Circle, Insets, Line, PathSeg, Point, Rect, Shape, Vec2};
 use druid::piet::{RenderContext, StrokeStyle};
-use druid::{Data, Env, EventCtx, HotKey, KbKey, KeyEvent, MouseEvent, PaintCtx, RawMods};
+use druid::{Color, Data, Env, EventCtx, HotKey, KbKey, KeyEvent, MouseEvent, PaintCtx, RawMods};
 
+use crate::draw::DragInfo;
 use crate::edit_session::EditSession;
 use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
-use crate::path::Segment;
-use crate::point::EntityId;
+use crate::path::{Path, Segment};
+use crate::point::{EntityId, PathPoint};
+use crate::settings::MarqueeMode;
 use crate::tools::{EditType, Tool, ToolId};
+use crate::tunni::Tunni;
 use crate::{
-    design_space::{DPoint, DVec2, ViewPort},
+    design_space::{DPoint, DVec2, Precision, ViewPort},
     quadrant::Quadrant,
     selection::Selection,
     theme,
@@ -17,6 +22,7 @@ use crate::{
 // distance from edges of the selection bbox to where we draw the handles
 const SELECTION_BBOX_HANDLE_PADDING: Insets = Insets::uniform(6.0);
 const SELECTION_HANDLE_RADIUS: f64 = 4.;
+const ALIGNMENT_HINT_COLOR: Color = Color::rgb8(0xe0, 0x40, 0xa0);
 
 /// An item that can be selected.
 #[derive(Debug, Clone)]
@@ -25,6 +31,8 @@ enum Item {
     Point(EntityId),
     Guide(EntityId),
     Segment(Box<Segment>),
+    /// The Tunni point of a curve.
+    Tunni(Box<Tunni>),
 }
 
 /// The internal state of the mouse.
@@ -56,8 +64,42 @@ enum DragState {
         rect: Rect,
         toggle: bool,
     },
+    /// State for a drag that is a freeform (lasso) selection.
+    ///
+    /// This is started by holding alt when beginning a drag.
+    Lasso {
+        previous: Selection,
+        /// The points of the lasso, in screen space.
+        points: Vec<Point>,
+        toggle: bool,
+    },
     /// State for a drag that is moving a selected object.
-    Move { previous: EditSession, delta: DVec2 },
+    Move {
+        previous: EditSession,
+        delta: DVec2,
+        /// The point under the cursor when the drag began, if any.
+        point: Option<EntityId>,
+        /// Lines, in design space, to the points and guides that the point
+        /// under the cursor is aligned with.
+        alignments: Vec<Line>,
+    },
+    /// State for a drag that bends a curve segment, by moving its handles.
+    ///
+    /// This is started by holding alt when beginning a drag on a segment.
+    Bend {
+        previous: EditSession,
+        /// The segment as it was when the drag began.
+        segment: Box<Segment>,
+        /// The position on the segment where the drag began.
+        t: f64,
+    },
+    /// State for a drag that moves a curve's Tunni point, adjusting the
+    /// tension of its handles.
+    Tunni {
+        previous: EditSession,
+        /// The curve as it was when the drag began.
+        tunni: Box<Tunni>,
+    },
     TransformSelection {
         quadrant: Quadrant,
         previous: EditSession,
@@ -113,9 +155,15 @@ impl Tool for Select {
                     }
                     Some(Item::Segment(seg)) => {
                         let seg_point = data.viewport.affine()
-                            * seg.nearest_point(data.viewport.from_screen(self.last_pos));
+                            * seg.nearest_point(
+                                data.viewport.from_screen(self.last_pos, data.precision),
+                            );
                         paint_hover_indicator(ctx, data, seg_point, env);
                     }
+                    Some(Item::Tunni(tunni)) => {
+                        let point = data.viewport.affine() * tunni.point();
+                        paint_hover_indicator(ctx, data, point, env);
+                    }
                     Some(Item::Guide(id)) => {
                         if let Some(point) =
                             data.guides.iter().find(|g| g.id == *id).map(|guide| {
@@ -133,6 +181,31 @@ impl Tool for Select {
                     ctx.fill(rect, &env.get(theme::SELECTION_RECT_FILL_COLOR));
                     ctx.stroke(rect, &selection_stroke, 1.0);
                 }
+                DragState::Lasso { points, .. } => {
+                    let lasso = lasso_path(points);
+                    ctx.fill(&lasso, &env.get(theme::SELECTION_RECT_FILL_COLOR));
+                    ctx.stroke(&lasso, &selection_stroke, 1.0);
+                }
+                DragState::Move {
+                    delta,
+                    point,
+                    alignments,
+                    ..
+                } => {
+                    for line in alignments {
+                        let line = data.viewport.affine() * *line;
+                        ctx.stroke(line, &ALIGNMENT_HINT_COLOR, 0.5);
+                    }
+                    let info = match point {
+                        Some(id) => DragInfo::for_point(data, *id, *delta),
+                        None => DragInfo {
+                            pos: None,
+                            delta: *delta,
+                            handle: None,
+                        },
+                    };
+                    info.paint(ctx, env, self.last_pos);
+                }
                 // draw the selection bounding box
                 DragState::TransformSelection { pre_paths, .. } => {
                     ctx.stroke(
@@ -168,6 +241,9 @@ impl Tool for Select {
         _: &Env,
     ) -> Option<EditType> {
         assert!(self.this_edit_type.is_none());
+        if data.keyboard_editing && self.keyboard_edit(data, event) {
+            return self.this_edit_type.take();
+        }
         match event {
             e if e.key == KbKey::ArrowLeft
                 || e.key == KbKey::ArrowDown
@@ -183,7 +259,13 @@ impl Tool for Select {
             e if HotKey::new(None, KbKey::Tab).matches(e) => data.select_next(),
             //TODO: add Shift to SysMods
             e if HotKey::new(RawMods::Shift, KbKey::Tab).matches(e) => data.select_prev(),
-            _ => return None,
+            e => match selection_slot(e) {
+                Some((slot, true)) => data.save_named_selection(slot),
+                Some((slot, false)) => {
+                    data.restore_named_selection(slot);
+                }
+                None => return None,
+            },
         }
         self.this_edit_type.take()
     }
@@ -199,7 +281,7 @@ impl Tool for Select {
         assert!(self.this_edit_type.is_none());
         let pre_rect = self.state.drag_rect();
         mouse.mouse_event(event, data, self);
-        if !pre_rect.same(&self.state.drag_rect()) {
+        if !pre_rect.same(&self.state.drag_rect()) || self.state.is_lasso() {
             ctx.request_paint();
         }
         self.this_edit_type.take()
@@ -208,9 +290,42 @@ impl Tool for Select {
     fn name(&self) -> ToolId {
         "Select"
     }
+
+    fn hint(&self) -> &'static str {
+        "Click to select, shift-click to add to the selection; alt-drag to lasso, \
+         or alt-drag a curve to bend it; ctrl-alt-1…9 saves the selection, 1…9 restores it"
+    }
+
+    fn edit_description(&self) -> &'static str {
+        "Move"
+    }
 }
 
 impl Select {
+    /// Handle the keys used in keyboard editing mode: alt-arrows move
+    /// between points and contours, and return adds a point.
+    ///
+    /// Returns `true` if the key was handled.
+    fn keyboard_edit(&mut self, data: &mut EditSession, event: &KeyEvent) -> bool {
+        if event.mods.alt() {
+            match event.key {
+                KbKey::ArrowRight => data.select_next(),
+                KbKey::ArrowLeft => data.select_prev(),
+                KbKey::ArrowDown => data.select_adjacent_path(true),
+                KbKey::ArrowUp => data.select_adjacent_path(false),
+                _ => return false,
+            }
+            true
+        } else if event.key == KbKey::Enter && event.mods.is_empty() {
+            if data.insert_point_after_selection(data.nudge_amounts.shift) {
+                self.this_edit_type = Some(EditType::Normal);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
     fn nudge(&mut self, data: &mut EditSession, event: &KeyEvent) {
         let (mut nudge, edit_type) = match event.key {
             KbKey::ArrowLeft => (Vec2::new(-1.0, 0.), EditType::NudgeLeft),
@@ -220,16 +335,25 @@ impl Select {
             _ => unreachable!(),
         };
 
-        if event.mods.meta() {
-            nudge *= 100.;
+        let amounts = data.nudge_amounts;
+        let amount = if event.mods.meta() {
+            amounts.cmd
         } else if event.mods.shift() {
-            nudge *= 10.;
-        }
+            amounts.shift
+        } else {
+            amounts.plain
+        };
+        nudge *= amount;
 
-        data.nudge_selection(DVec2::from_raw(nudge));
+        // with ctrl held, we adjust the neighbouring handles proportionally
+        if event.mods.ctrl() {
+            data.nudge_selection_interpolated(DVec2::from_raw(nudge, data.precision));
+        } else {
+            data.nudge_selection(DVec2::from_raw(nudge, data.precision));
+        }
 
-        // for the purposes of undo, we only combine single-unit nudges
-        if nudge.hypot().abs() > 1.0 {
+        // for the purposes of undo, we only combine unmodified nudges
+        if event.mods.meta() || event.mods.shift() {
             self.this_edit_type = Some(EditType::Normal);
         } else {
             self.this_edit_type = Some(edit_type);
@@ -272,6 +396,8 @@ impl Select {
             } else {
                 Some(Item::Point(id))
             }
+        } else if let Some(tunni) = data.hit_test_tunni(pos) {
+            Some(Item::Tunni(Box::new(tunni)))
         } else if let Some((seg, _t)) =
             data.hit_test_segments(pos, Some(crate::edit_session::SEGMENT_CLICK_DISTANCE))
         {
@@ -306,7 +432,7 @@ impl MouseDelegate<EditSession> for Select {
                 _ => None,
             };
             self.state = match item {
-                Some(Item::SelectionHandle(_)) => MouseState::Down(item),
+                Some(Item::SelectionHandle(_)) | Some(Item::Tunni(_)) => MouseState::Down(item),
                 Some(Item::Point(id)) | Some(Item::Guide(id)) => {
                     if !append_mode {
                         if !data.selection.contains(&id) {
@@ -317,15 +443,16 @@ impl MouseDelegate<EditSession> for Select {
                     }
                     MouseState::Down(item)
                 }
-                // toggle segment type
+                // alt-click turns a line into a curve, and alt-drag bends it
                 Some(Item::Segment(seg)) if event.mods.alt() => {
                     if seg.is_line() {
+                        let precision = data.precision;
                         if let Some(path) = data.path_for_point_mut(seg.start_id()) {
-                            path.upgrade_line_seg(&seg, false);
+                            path.upgrade_line_seg(&seg, false, precision);
                             self.this_edit_type = Some(EditType::Normal);
                         }
                     }
-                    MouseState::SuppressDrag
+                    MouseState::Down(Some(Item::Segment(seg)))
                 }
                 Some(Item::Segment(seg)) => {
                     let all_selected = seg
@@ -363,7 +490,7 @@ impl MouseDelegate<EditSession> for Select {
                         Item::Segment(seg) => {
                             data.select_path(seg.start_id().parent(), append_mode);
                         }
-                        Item::SelectionHandle(_) => (),
+                        Item::SelectionHandle(_) | Item::Tunni(_) => (),
                     };
                     MouseState::WaitDoubleClick(item)
                 }
@@ -391,6 +518,12 @@ impl MouseDelegate<EditSession> for Select {
 
     fn left_drag_began(&mut self, drag: Drag, data: &mut EditSession) {
         self.state = match self.state.transition() {
+            // starting a freeform selection
+            MouseState::Down(None) if drag.start.mods.alt() => MouseState::Drag(DragState::Lasso {
+                previous: data.selection.clone(),
+                points: vec![drag.start.pos, drag.current.pos],
+                toggle: drag.current.mods.shift(),
+            }),
             // starting a rectangular selection
             MouseState::Down(None) => MouseState::Drag(DragState::Select {
                 previous: data.selection.clone(),
@@ -405,9 +538,27 @@ impl MouseDelegate<EditSession> for Select {
                     pre_paths: data.to_bezier(),
                 })
             }
-            MouseState::Down(Some(_)) => MouseState::Drag(DragState::Move {
+            MouseState::Down(Some(Item::Tunni(tunni))) if !data.tunni_is_locked(&tunni) => {
+                MouseState::Drag(DragState::Tunni {
+                    previous: data.clone(),
+                    tunni,
+                })
+            }
+            MouseState::Down(Some(Item::Tunni(_))) => MouseState::SuppressDrag,
+            MouseState::Down(Some(Item::Segment(seg))) if drag.start.mods.alt() => {
+                match bend_state(data, &seg, drag.start.pos) {
+                    Some(state) => MouseState::Drag(state),
+                    None => MouseState::SuppressDrag,
+                }
+            }
+            MouseState::Down(Some(item)) => MouseState::Drag(DragState::Move {
                 previous: data.clone(),
                 delta: DVec2::ZERO,
+                point: match item {
+                    Item::Point(id) => Some(id),
+                    _ => None,
+                },
+                alignments: Vec::new(),
             }),
             MouseState::SuppressDrag => MouseState::SuppressDrag,
             other => {
@@ -429,24 +580,60 @@ impl MouseDelegate<EditSession> for Select {
                     *rect = Rect::from_points(drag.current.pos, drag.start.pos);
                     update_selection_for_drag(data, previous, *rect, *toggle);
                 }
-                DragState::Move { delta, .. } => {
-                    let mut new_delta = delta_for_drag_change(&drag, data.viewport);
+                DragState::Lasso {
+                    previous,
+                    points,
+                    toggle,
+                } => {
+                    points.push(drag.current.pos);
+                    update_selection_for_lasso(data, previous, points, *toggle);
+                }
+                DragState::Move {
+                    previous,
+                    delta,
+                    point,
+                    alignments,
+                } => {
+                    let mut new_delta = delta_for_drag_change(&drag, data.viewport, data.precision);
                     if drag.current.mods.shift() {
                         new_delta = new_delta.axis_locked();
                     }
+                    // the point under the cursor snaps, and the rest of the
+                    // selection moves with it; cmd turns snapping off.
+                    alignments.clear();
+                    if let Some(start) = point.and_then(|id| previous.path_point_for_id(id)) {
+                        if !drag.current.mods.meta() {
+                            let (snapped, lines) = previous
+                                .snap_to_alignment(start.point + new_delta, &previous.selection);
+                            new_delta = snapped - start.point;
+                            *alignments = lines;
+                        }
+                    }
                     let drag_delta = new_delta - *delta;
                     if drag_delta.hypot() > 0. {
                         data.nudge_selection(drag_delta);
                         *delta = new_delta;
                     }
                 }
+                DragState::Tunni { tunni, .. } => {
+                    // with alt held, each handle's tension changes separately
+                    let target = data.viewport.inverse_affine() * drag.current.pos;
+                    data.drag_tunni_point(tunni, target, !drag.current.mods.alt());
+                }
+                DragState::Bend { segment, t, .. } => {
+                    let delta = delta_for_drag_change(&drag, data.viewport, data.precision);
+                    let precision = data.precision;
+                    if let Some(path) = data.path_for_point_mut(segment.start_id()) {
+                        path.bend_segment(segment, *t, delta, precision);
+                    }
+                }
                 DragState::TransformSelection {
                     quadrant,
                     previous,
                     delta,
                     ..
                 } => {
-                    let new_delta = delta_for_drag_change(&drag, data.viewport);
+                    let new_delta = delta_for_drag_change(&drag, data.viewport, data.precision);
                     let new_delta = quadrant.lock_delta(new_delta);
                     if new_delta.hypot() > 0.0 && new_delta != *delta {
                         *delta = new_delta;
@@ -454,14 +641,17 @@ impl MouseDelegate<EditSession> for Select {
                         let sel_rect = previous.selection_dpoint_bbox();
                         let scale = quadrant.scale_dspace_rect(sel_rect, new_delta);
                         let anchor = quadrant.inverse().point_in_dspace_rect(sel_rect);
-                        new_data.scale_selection(scale, DPoint::from_raw(anchor));
+                        let anchor = DPoint::from_raw(anchor, previous.precision);
+                        new_data.scale_selection(scale, anchor);
                         *data = new_data;
                     }
                 }
             }
             if matches!(
                 state,
-                DragState::Move { .. } | DragState::TransformSelection { .. }
+                DragState::Move { .. }
+                    | DragState::Bend { .. }
+                    | DragState::TransformSelection { .. }
             ) {
                 self.this_edit_type = Some(EditType::Drag);
             }
@@ -470,11 +660,17 @@ impl MouseDelegate<EditSession> for Select {
         }
     }
 
-    fn left_drag_ended(&mut self, _drag: Drag, _data: &mut EditSession) {
+    fn left_drag_ended(&mut self, _drag: Drag, data: &mut EditSession) {
         if let MouseState::Drag(state) = &self.state {
+            // a move is applied in steps; 'transform again' repeats all of it
+            if let DragState::Move { delta, .. } = state {
+                data.last_transform = Some(Affine::translate(delta.to_raw()));
+            }
             if matches!(
                 state,
-                DragState::Move { .. } | DragState::TransformSelection { .. }
+                DragState::Move { .. }
+                    | DragState::Bend { .. }
+                    | DragState::TransformSelection { .. }
             ) {
                 self.this_edit_type = Some(EditType::DragUp);
             }
@@ -486,8 +682,12 @@ impl MouseDelegate<EditSession> for Select {
         let old_state = std::mem::replace(&mut self.state, MouseState::Idle(None));
         if let MouseState::Drag(state) = old_state {
             match state {
-                DragState::Select { previous, .. } => data.selection = previous,
+                DragState::Select { previous, .. } | DragState::Lasso { previous, .. } => {
+                    data.selection = previous
+                }
                 DragState::Move { previous, .. }
+                | DragState::Bend { previous, .. }
+                | DragState::Tunni { previous, .. }
                 | DragState::TransformSelection { previous, .. } => {
                     *data = previous;
                     // we use 'Drag' and not 'DragUp' because we want this all to combine
@@ -499,11 +699,62 @@ impl MouseDelegate<EditSession> for Select {
     }
 }
 
+/// The named selection slot for a key press, and whether the selection
+/// should be saved to it (with ctrl and alt held) rather than restored.
+///
+/// Cmd and a digit switch editor tabs, so saving uses a combination nothing
+/// else binds. The digit is read from the physical key, because alt changes
+/// the character typed on some platforms.
+fn selection_slot(event: &KeyEvent) -> Option<(u8, bool)> {
+    let code = event.code.to_string();
+    let digit = code
+        .strip_prefix("Digit")
+        .or_else(|| code.strip_prefix("Numpad"))?
+        .parse::<u8>()
+        .ok()
+        .filter(|n| (1..=9).contains(n))?;
+    let mods = event.mods;
+    if mods.shift() || mods.meta() {
+        return None;
+    }
+    match (mods.ctrl(), mods.alt()) {
+        (true, true) => Some((digit, true)),
+        (false, false) => Some((digit, false)),
+        _ => None,
+    }
+}
+
+/// The state for bending the curve `seg`, from a drag starting at `pos`.
+///
+/// `seg` may have just been turned from a line into a curve, so we look
+/// up its current version. There is no state if either handle is locked.
+fn bend_state(data: &EditSession, seg: &Segment, pos: Point) -> Option<DragState> {
+    let segment = data
+        .path_for_point(seg.start_id())?
+        .iter_segments()
+        .find(|s| s.start_id() == seg.start_id())
+        .filter(|s| matches!(s, Segment::Cubic(_)) && !s.is_line())?;
+    // bending moves both handles, so a locked one leaves the curve as is
+    if segment
+        .raw_segment()
+        .points()
+        .any(|pt| !pt.is_on_curve() && data.locked.contains(&pt.id))
+    {
+        return None;
+    }
+    let (t, _) = segment.nearest(data.viewport.from_screen(pos, data.precision));
+    Some(DragState::Bend {
+        previous: data.clone(),
+        segment: Box::new(segment),
+        t,
+    })
+}
+
 /// When dragging, we only update positions when they change in design-space,
 /// so we keep track of the current total design-space delta.
-fn delta_for_drag_change(drag: &Drag, viewport: ViewPort) -> DVec2 {
-    let drag_start = viewport.from_screen(drag.start.pos);
-    let drag_pos = viewport.from_screen(drag.current.pos);
+fn delta_for_drag_change(drag: &Drag, viewport: ViewPort, precision: Precision) -> DVec2 {
+    let drag_start = viewport.from_screen(drag.start.pos, precision);
+    let drag_pos = viewport.from_screen(drag.current.pos, precision);
     drag_pos - drag_start
 }
 
@@ -534,11 +785,7 @@ fn update_selection_for_drag(
     //corresponds to shift being held
     toggle: bool,
 ) {
-    let in_select_rect = data
-        .iter_points()
-        .filter(|p| rect.contains(p.to_screen(data.viewport)))
-        .map(|p| p.id)
-        .collect();
+    let in_select_rect = marquee_points(data, &rect.to_path(0.1), |pt| rect.contains(pt));
     data.selection = if toggle {
         prev_sel.symmetric_difference(&in_select_rect)
     } else {
@@ -546,6 +793,105 @@ fn update_selection_for_drag(
     };
 }
 
+/// Like `update_selection_for_drag`, but for a freeform selection.
+fn update_selection_for_lasso(
+    data: &mut EditSession,
+    prev_sel: &Selection,
+    points: &[Point],
+    toggle: bool,
+) {
+    let lasso = lasso_path(points);
+    let bbox = lasso.bounding_box();
+    let in_lasso = marquee_points(data, &lasso, |pt| {
+        bbox.contains(pt) && lasso.winding(pt) != 0
+    });
+    data.selection = if toggle {
+        prev_sel.symmetric_difference(&in_lasso)
+    } else {
+        in_lasso
+    };
+}
+
+/// The visible points inside `area`, a selection rectangle or lasso in
+/// screen space, that the marquee option allows to be selected.
+///
+/// `contains` tests whether a point, in screen space, is inside the area.
+fn marquee_points(
+    data: &EditSession,
+    area: &BezPath,
+    contains: impl Fn(Point) -> bool,
+) -> Selection {
+    let mode = data.tool_options.marquee_mode;
+    let design_area = data.viewport.inverse_affine() * area.clone();
+    // whether each contour passes through the area, as we find out
+    let mut touched: HashMap<EntityId, bool> = HashMap::new();
+    let fills: Vec<(EntityId, BezPath)> = match mode {
+        MarqueeMode::UncoveredPoints => data
+            .paths
+            .iter()
+            .filter(|path| path.is_closed() && !data.hidden_paths.contains(&path.id()))
+            .map(|path| (path.id(), path.bezier()))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    data.iter_visible_points()
+        .filter(|p| contains(p.to_screen(data.viewport)))
+        .filter(|p| match mode {
+            MarqueeMode::AllPoints => true,
+            MarqueeMode::TouchedContours => *touched.entry(p.id.parent()).or_insert_with(|| {
+                data.path_for_point(p.id)
+                    .map(|path| path_touches_area(path, &design_area))
+                    .unwrap_or(true)
+            }),
+            MarqueeMode::UncoveredPoints => !is_covered(p, &fills),
+        })
+        .map(|p| p.id)
+        .collect()
+}
+
+/// Whether the outline of `path` passes through `area`, in design space.
+fn path_touches_area(path: &Path, area: &BezPath) -> bool {
+    let edges: Vec<Line> = area
+        .segments()
+        .filter_map(|seg| match seg {
+            PathSeg::Line(line) => Some(line),
+            _ => None,
+        })
+        .collect();
+    path.iter_segments().any(|seg| {
+        area.winding(seg.start().point.to_raw()) != 0
+            || edges
+                .iter()
+                .any(|edge| !seg.intersect_line(*edge).is_empty())
+    })
+}
+
+/// Whether `point` is inside the fill of any of the contours in `fills`,
+/// other than its own.
+fn is_covered(point: &PathPoint, fills: &[(EntityId, BezPath)]) -> bool {
+    let pt = point.point.to_raw();
+    let winding: i32 = fills
+        .iter()
+        .filter(|(id, _)| *id != point.id.parent())
+        .map(|(_, fill)| fill.winding(pt))
+        .sum();
+    winding != 0
+}
+
+/// The closed path described by the points of a lasso selection.
+fn lasso_path(points: &[Point]) -> BezPath {
+    let mut path = BezPath::new();
+    if let Some((first, rest)) = points.split_first() {
+        path.move_to(*first);
+        for pt in rest {
+            path.line_to(*pt);
+        }
+        path.close_path();
+    }
+    path
+}
+
 impl MouseState {
     /// Move to the Transition state, returning the previous state.
     fn transition(&mut self) -> Self {
@@ -568,6 +914,10 @@ impl MouseState {
             None
         }
     }
+
+    fn is_lasso(&self) -> bool {
+        matches!(self, MouseState::Drag(DragState::Lasso { .. }))
+    }
 }
 
 impl Default for MouseState {
@@ -614,3 +964,49 @@ fn paint_hover_indicator(ctx: &mut PaintCtx, _: &EditSession, point: Point, _env
     let circ = Circle::new(point, 3.0);
     ctx.fill(circ, &HOVER_ACCENT_COLOR);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widgets::EditorTabs;
+    use druid::{Code, Modifiers};
+
+    fn key_down(mods: Modifiers, key: &str, code: Code) -> KeyEvent {
+        KeyEvent {
+            key: KbKey::Character(key.into()),
+            code,
+            mods,
+            ..Default::default()
+        }
+    }
+
+    /// The editor tabs see every key before the editor, and claim cmd and a
+    /// digit; the selection slot keys have to get past them.
+    #[test]
+    fn selection_slot_keys() {
+        let cmd = if cfg!(target_os = "macos") {
+            Modifiers::META
+        } else {
+            Modifiers::CONTROL
+        };
+
+        let save = key_down(Modifiers::CONTROL | Modifiers::ALT, "3", Code::Digit3);
+        assert_eq!(EditorTabs::tab_number(&save), None);
+        assert_eq!(selection_slot(&save), Some((3, true)));
+
+        // alt can change the character typed, but not the key
+        let save = key_down(Modifiers::CONTROL | Modifiers::ALT, "£", Code::Digit3);
+        assert_eq!(selection_slot(&save), Some((3, true)));
+
+        let restore = key_down(Modifiers::empty(), "3", Code::Digit3);
+        assert_eq!(EditorTabs::tab_number(&restore), None);
+        assert_eq!(selection_slot(&restore), Some((3, false)));
+
+        let switch_tab = key_down(cmd, "3", Code::Digit3);
+        assert_eq!(EditorTabs::tab_number(&switch_tab), Some(3));
+        assert_eq!(selection_slot(&switch_tab), None);
+
+        let zero = key_down(Modifiers::empty(), "0", Code::Digit0);
+        assert_eq!(selection_slot(&zero), None);
+    }
+}
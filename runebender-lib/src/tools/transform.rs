@@ -0,0 +1,435 @@
+//! The free transform tool
+
+use std::f64::consts::PI;
+
+use druid::kurbo::{Affine, BezPath, Circle, Insets, Line, Point, Rect, Vec2};
+use druid::piet::{RenderContext, StrokeStyle};
+use druid::{Data, Env, EventCtx, MouseEvent, PaintCtx};
+
+use crate::design_space::{DPoint, DVec2};
+use crate::edit_session::EditSession;
+use crate::mouse::{Drag, Mouse, MouseDelegate, TaggedEvent};
+use crate::quadrant::Quadrant;
+use crate::selection::Selection;
+use crate::theme;
+use crate::tools::{EditType, Tool, ToolId};
+
+// distance from edges of the selection bbox to where we draw the scale handles
+const HANDLE_PADDING: Insets = Insets::uniform(6.0);
+/// The distance past the scale handles to the rotate and skew handles.
+const OUTER_HANDLE_OFFSET: f64 = 14.0;
+const HANDLE_RADIUS: f64 = 4.0;
+const ORIGIN_RADIUS: f64 = 5.0;
+/// With shift held, rotation snaps to multiples of this angle.
+const ROTATION_SNAP: f64 = PI / 12.0;
+
+/// Something in the transform box that can be dragged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Handle {
+    /// Scale from the opposite handle, or from the origin with alt held.
+    Scale(Quadrant),
+    /// Rotate around the origin; these sit outside the corners of the box.
+    Rotate(Quadrant),
+    /// Skew relative to the origin; these sit outside the edges of the box.
+    Skew(Quadrant),
+    /// The transform origin.
+    Origin,
+    /// The inside of the box; dragging here moves the selection.
+    Move,
+}
+
+#[derive(Debug, Clone)]
+enum MouseState {
+    /// The mouse is idle; it may be hovering on a handle.
+    Idle(Option<Handle>),
+    /// The mouse is down, and may be on a handle.
+    Down(Option<Handle>),
+    /// A handle is being dragged.
+    Drag {
+        handle: Handle,
+        previous: EditSession,
+        /// The bounding box of the selection when the drag began.
+        bbox: Rect,
+        /// The transform origin when the drag began.
+        origin: DPoint,
+        /// The paths before this transform; we draw these faintly until the
+        /// gesture completes.
+        pre_paths: BezPath,
+    },
+}
+
+/// The state of the transform tool.
+///
+/// This draws a box around the selection, with handles to scale, rotate,
+/// and skew it around a transform origin that can be moved.
+#[derive(Debug, Clone)]
+pub struct Transform {
+    state: MouseState,
+    /// The transform origin, if it has been moved, along with the selection
+    /// it was moved for. It goes back to the center of the selection if the
+    /// selection changes.
+    origin: Option<(Selection, DPoint)>,
+    /// The edit type produced by the current event, if any.
+    ///
+    /// This is stashed here because we can't return anything from the methods in
+    /// `MouseDelegate`.
+    this_edit_type: Option<EditType>,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform {
+            state: MouseState::Idle(None),
+            origin: None,
+            this_edit_type: None,
+        }
+    }
+}
+
+impl Transform {
+    /// The transform origin, in design space.
+    fn origin(&self, data: &EditSession) -> Option<DPoint> {
+        let bbox = selection_bbox(data)?;
+        match &self.origin {
+            Some((selection, origin)) if selection.same(&data.selection) => Some(*origin),
+            _ => Some(DPoint::from_raw(bbox.center(), data.precision)),
+        }
+    }
+
+    fn set_origin(&mut self, data: &EditSession, origin: DPoint) {
+        self.origin = Some((data.selection.clone(), origin));
+    }
+
+    /// The handles of the transform box, with their positions in screen space.
+    fn handles(&self, data: &EditSession) -> Vec<(Handle, Point)> {
+        let (bbox, origin) = match (selection_bbox(data), self.origin(data)) {
+            (Some(bbox), Some(origin)) => (bbox, origin),
+            _ => return Vec::new(),
+        };
+        let bbox = data.viewport.rect_to_screen(bbox);
+        let frame = bbox + HANDLE_PADDING;
+        let mut handles = vec![(Handle::Origin, data.viewport.to_screen(origin))];
+        #[allow(clippy::nonminimal_bool)]
+        for quad in Quadrant::all().iter().filter(|q| {
+            !(bbox.width() == 0. && q.modifies_x_axis())
+                && !(bbox.height() == 0. && q.modifies_y_axis())
+                && !matches!(q, Quadrant::Center)
+        }) {
+            let pos = quad.point_in_rect(frame);
+            let outer = pos + outward(*quad) * OUTER_HANDLE_OFFSET;
+            handles.push((Handle::Scale(*quad), pos));
+            if quad.modifies_x_axis() && quad.modifies_y_axis() {
+                handles.push((Handle::Rotate(*quad), outer));
+            } else {
+                handles.push((Handle::Skew(*quad), outer));
+            }
+        }
+        handles
+    }
+
+    fn handle_for_pos(&self, data: &EditSession, pos: Point) -> Option<Handle> {
+        let max_dist = HANDLE_RADIUS + 2.0;
+        let hit = self
+            .handles(data)
+            .into_iter()
+            .map(|(handle, pt)| (handle, pt.distance(pos)))
+            .filter(|(_, dist)| *dist <= max_dist)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(handle, _)| handle);
+        hit.or_else(|| {
+            let bbox = data.viewport.rect_to_screen(selection_bbox(data)?);
+            if (bbox + HANDLE_PADDING).contains(pos) {
+                Some(Handle::Move)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn hover_handle(&self) -> Option<Handle> {
+        match self.state {
+            MouseState::Idle(handle) | MouseState::Down(handle) => handle,
+            MouseState::Drag { handle, .. } => Some(handle),
+        }
+    }
+}
+
+impl Tool for Transform {
+    fn cancel(
+        &mut self,
+        mouse: &mut Mouse,
+        _ctx: &mut EventCtx,
+        data: &mut EditSession,
+    ) -> Option<EditType> {
+        mouse.cancel(data, self);
+        self.this_edit_type.take()
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &EditSession, env: &Env) {
+        let bbox = match selection_bbox(data) {
+            Some(bbox) => data.viewport.rect_to_screen(bbox),
+            None => return,
+        };
+        let stroke = env.get(theme::SELECTION_RECT_STROKE_COLOR);
+        if let MouseState::Drag { pre_paths, .. } = &self.state {
+            ctx.stroke(
+                data.viewport.affine() * pre_paths,
+                &env.get(theme::PLACEHOLDER_GLYPH_COLOR),
+                1.0,
+            );
+        }
+        let style = StrokeStyle::new().dash(vec![2.0, 4.0], 0.0);
+        ctx.stroke_styled(&bbox, &stroke, 0.5, &style);
+
+        let hot = self.hover_handle();
+        for (handle, pos) in self.handles(data) {
+            let is_hot = Some(handle) == hot;
+            match handle {
+                Handle::Scale(_) => {
+                    let rect =
+                        Rect::from_center_size(pos, (HANDLE_RADIUS * 2.0, HANDLE_RADIUS * 2.0));
+                    if is_hot {
+                        ctx.fill(rect, &stroke);
+                    }
+                    ctx.stroke(rect, &stroke, 0.5);
+                }
+                Handle::Rotate(_) | Handle::Skew(_) => {
+                    let circle = Circle::new(pos, HANDLE_RADIUS);
+                    if is_hot {
+                        ctx.fill(circle, &stroke);
+                    }
+                    ctx.stroke(circle, &stroke, 0.5);
+                }
+                Handle::Origin => {
+                    let circle = Circle::new(pos, ORIGIN_RADIUS);
+                    if is_hot {
+                        ctx.fill(circle, &stroke);
+                    }
+                    ctx.stroke(circle, &stroke, 1.0);
+                    let arm = ORIGIN_RADIUS * 2.0;
+                    let horiz = Line::new(pos - Vec2::new(arm, 0.0), pos + Vec2::new(arm, 0.0));
+                    let vert = Line::new(pos - Vec2::new(0.0, arm), pos + Vec2::new(0.0, arm));
+                    ctx.stroke(horiz, &stroke, 1.0);
+                    ctx.stroke(vert, &stroke, 1.0);
+                }
+                Handle::Move => (),
+            }
+        }
+    }
+
+    fn mouse_event(
+        &mut self,
+        event: TaggedEvent,
+        mouse: &mut Mouse,
+        ctx: &mut EventCtx,
+        data: &mut EditSession,
+        _: &Env,
+    ) -> Option<EditType> {
+        assert!(self.this_edit_type.is_none());
+        let pre_hover = self.hover_handle();
+        mouse.mouse_event(event, data, self);
+        if pre_hover != self.hover_handle() || matches!(self.state, MouseState::Drag { .. }) {
+            ctx.request_paint();
+        }
+        self.this_edit_type.take()
+    }
+
+    fn name(&self) -> ToolId {
+        "Transform"
+    }
+
+    fn hint(&self) -> &'static str {
+        "Drag the handles to scale, rotate, or skew the selection; drag the center to move the origin"
+    }
+}
+
+impl MouseDelegate<EditSession> for Transform {
+    fn mouse_moved(&mut self, event: &MouseEvent, data: &mut EditSession) {
+        self.state = MouseState::Idle(self.handle_for_pos(data, event.pos));
+    }
+
+    fn left_down(&mut self, event: &MouseEvent, data: &mut EditSession) {
+        if event.count == 1 {
+            self.state = MouseState::Down(self.handle_for_pos(data, event.pos));
+        }
+    }
+
+    fn left_up(&mut self, event: &MouseEvent, data: &mut EditSession) {
+        self.state = MouseState::Idle(self.handle_for_pos(data, event.pos));
+    }
+
+    fn left_drag_began(&mut self, _drag: Drag, data: &mut EditSession) {
+        let handle = match self.state {
+            MouseState::Down(Some(handle)) => handle,
+            _ => return,
+        };
+        let (bbox, origin) = match (selection_bbox(data), self.origin(data)) {
+            (Some(bbox), Some(origin)) => (bbox, origin),
+            _ => return,
+        };
+        self.state = MouseState::Drag {
+            handle,
+            previous: data.clone(),
+            bbox,
+            origin,
+            pre_paths: data.to_bezier(),
+        };
+    }
+
+    fn left_drag_changed(&mut self, drag: Drag, data: &mut EditSession) {
+        let (handle, previous, bbox, origin) = match &self.state {
+            MouseState::Drag {
+                handle,
+                previous,
+                bbox,
+                origin,
+                ..
+            } => (*handle, previous, *bbox, *origin),
+            _ => return,
+        };
+        // we work with unrounded points, so that small rotations and skews
+        // are possible
+        let start = data.viewport.inverse_affine() * drag.start.pos;
+        let current = data.viewport.inverse_affine() * drag.current.pos;
+        let mods = drag.current.mods;
+
+        let (affine, anchor) = match handle {
+            Handle::Origin => {
+                let new_origin = origin + DVec2::from_raw(current - start, data.precision);
+                self.set_origin(data, new_origin);
+                return;
+            }
+            Handle::Move => {
+                let mut delta = DVec2::from_raw(current - start, data.precision);
+                if mods.shift() {
+                    delta = delta.axis_locked();
+                }
+                let mut new_data = previous.clone();
+                new_data.nudge_selection(delta);
+                *data = new_data;
+                if self.origin.is_some() {
+                    self.set_origin(data, origin + delta);
+                }
+                self.this_edit_type = Some(EditType::Drag);
+                return;
+            }
+            Handle::Scale(quad) => {
+                let anchor = if mods.alt() {
+                    origin.to_raw()
+                } else {
+                    quad.inverse().point_in_dspace_rect(bbox)
+                };
+                let handle_pos = quad.point_in_dspace_rect(bbox);
+                let pos = handle_pos + (current - start);
+                let mut scale = Vec2::new(
+                    axis_scale(anchor.x, handle_pos.x, pos.x, quad.modifies_x_axis()),
+                    axis_scale(anchor.y, handle_pos.y, pos.y, quad.modifies_y_axis()),
+                );
+                // corner handles scale proportionally with shift held
+                if mods.shift() && quad.modifies_x_axis() && quad.modifies_y_axis() {
+                    let uniform = if scale.x.abs() > scale.y.abs() {
+                        scale.x
+                    } else {
+                        scale.y
+                    };
+                    scale = Vec2::new(uniform, uniform);
+                }
+                (Affine::scale_non_uniform(scale.x, scale.y), anchor)
+            }
+            Handle::Rotate(_) => {
+                let origin = origin.to_raw();
+                let mut angle = (current - origin).atan2() - (start - origin).atan2();
+                if mods.shift() {
+                    angle = (angle / ROTATION_SNAP).round() * ROTATION_SNAP;
+                }
+                (Affine::rotate(angle), origin)
+            }
+            Handle::Skew(quad) => {
+                let origin = origin.to_raw();
+                let handle_pos = quad.point_in_dspace_rect(bbox);
+                let affine = if quad.modifies_y_axis() {
+                    // top and bottom edges slide horizontally
+                    let dist = handle_pos.y - origin.y;
+                    if dist == 0.0 {
+                        return;
+                    }
+                    let skew = (current.x - start.x) / dist;
+                    Affine::new([1.0, 0.0, skew, 1.0, 0.0, 0.0])
+                } else {
+                    let dist = handle_pos.x - origin.x;
+                    if dist == 0.0 {
+                        return;
+                    }
+                    let skew = (current.y - start.y) / dist;
+                    Affine::new([1.0, skew, 0.0, 1.0, 0.0, 0.0])
+                };
+                (affine, origin)
+            }
+        };
+
+        // the anchor needn't be on the grid, so we apply it ourselves
+        let affine =
+            Affine::translate(anchor.to_vec2()) * affine * Affine::translate(-anchor.to_vec2());
+        let mut new_data = previous.clone();
+        new_data.transform_selection(affine, DPoint::ZERO);
+        *data = new_data;
+        self.this_edit_type = Some(EditType::Drag);
+    }
+
+    fn left_drag_ended(&mut self, _drag: Drag, _data: &mut EditSession) {
+        if let MouseState::Drag { handle, .. } = self.state {
+            if handle != Handle::Origin {
+                self.this_edit_type = Some(EditType::DragUp);
+            }
+        }
+    }
+
+    fn cancel(&mut self, data: &mut EditSession) {
+        let old_state = std::mem::replace(&mut self.state, MouseState::Idle(None));
+        if let MouseState::Drag {
+            handle, previous, ..
+        } = old_state
+        {
+            if handle != Handle::Origin {
+                *data = previous;
+                self.this_edit_type = Some(EditType::Drag);
+            }
+        }
+    }
+}
+
+/// The bounding box of the selected points, if any points are selected.
+fn selection_bbox(data: &EditSession) -> Option<Rect> {
+    if data.selection.iter().any(|id| !id.is_guide()) {
+        Some(data.selection_dpoint_bbox())
+    } else {
+        None
+    }
+}
+
+/// The direction, in screen space, in which the handle at `quad` points away
+/// from the box.
+fn outward(quad: Quadrant) -> Vec2 {
+    let x = match quad {
+        Quadrant::TopLeft | Quadrant::Left | Quadrant::BottomLeft => -1.0,
+        Quadrant::TopRight | Quadrant::Right | Quadrant::BottomRight => 1.0,
+        _ => 0.0,
+    };
+    let y = match quad {
+        Quadrant::TopLeft | Quadrant::Top | Quadrant::TopRight => -1.0,
+        Quadrant::BottomLeft | Quadrant::Bottom | Quadrant::BottomRight => 1.0,
+        _ => 0.0,
+    };
+    Vec2::new(x, y).normalize()
+}
+
+/// The scale along one axis that moves a handle at `from` to `to`, keeping
+/// `anchor` fixed.
+fn axis_scale(anchor: f64, from: f64, to: f64, enabled: bool) -> f64 {
+    let dist = from - anchor;
+    if !enabled || dist == 0.0 {
+        1.0
+    } else {
+        (to - anchor) / dist
+    }
+}
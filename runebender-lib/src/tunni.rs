@@ -0,0 +1,160 @@
+//! Tunni lines, for adjusting the tension of a curve.
+//!
+//! The handles of a cubic segment, extended, meet at a corner; each handle's
+//! tension is its length as a fraction of the distance to that corner. The
+//! Tunni line joins the ends of the two handles, and the Tunni point sits
+//! beyond its middle, as far from it as the middle of the segment's chord:
+//! dragging it lengthens or shortens both handles at once.
+
+use druid::kurbo::{CubicBez, Point, Vec2};
+
+use crate::path::Segment;
+use crate::point::EntityId;
+use crate::point_list::RawSegment;
+
+/// Handles shorter than this have no direction to extend along.
+const MIN_HANDLE_LENGTH: f64 = 1e-6;
+
+/// The Tunni construction for a cubic segment, in design space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Tunni {
+    pub(crate) curve: CubicBez,
+    /// The point where the extended handles meet.
+    pub(crate) corner: Point,
+    /// The ids of the segment's two off-curve points.
+    pub(crate) handles: (EntityId, EntityId),
+}
+
+impl Tunni {
+    /// The construction for `seg`, if it is a curve whose handles point
+    /// towards each other.
+    pub(crate) fn for_segment(seg: &Segment) -> Option<Tunni> {
+        let (p0, p1, p2, p3) = match seg {
+            Segment::Cubic(RawSegment::Cubic(p0, p1, p2, p3)) => (p0, p1, p2, p3),
+            _ => return None,
+        };
+        let curve = CubicBez::new(
+            p0.point.to_raw(),
+            p1.point.to_raw(),
+            p2.point.to_raw(),
+            p3.point.to_raw(),
+        );
+        let corner = handle_corner(&curve)?;
+        Some(Tunni {
+            curve,
+            corner,
+            handles: (p1.id, p2.id),
+        })
+    }
+
+    /// The point that is dragged to adjust both handles.
+    pub(crate) fn point(&self) -> Point {
+        let CubicBez { p0, p1, p2, p3 } = self.curve;
+        (p1.to_vec2() + p2.to_vec2() - (p0.to_vec2() + p3.to_vec2()) * 0.5).to_point()
+    }
+
+    /// The tension of each handle: its length relative to the distance from
+    /// its on-curve point to the corner.
+    pub(crate) fn tensions(&self) -> (f64, f64) {
+        let CubicBez { p0, p1, p2, p3 } = self.curve;
+        (
+            (p1 - p0).hypot() / (self.corner - p0).hypot(),
+            (p2 - p3).hypot() / (self.corner - p3).hypot(),
+        )
+    }
+
+    /// The handle positions that put the Tunni point as near as possible to
+    /// `target`, keeping the handles' directions.
+    ///
+    /// If `symmetric` is true both handles get the same tension; otherwise
+    /// each is free to change.
+    pub(crate) fn handles_for_point(&self, target: Point, symmetric: bool) -> (Point, Point) {
+        let CubicBez { p0, p3, .. } = self.curve;
+        let to_corner0 = self.corner - p0;
+        let to_corner3 = self.corner - p3;
+        // the Tunni point is `t0 * to_corner0 + t3 * to_corner3` from here
+        let offset = target - p0.midpoint(p3);
+        let (t0, t3) = if symmetric {
+            let dir = to_corner0 + to_corner3;
+            let t = offset.dot(dir) / dir.hypot2();
+            (t, t)
+        } else {
+            solve(to_corner0, to_corner3, offset).unwrap_or_else(|| self.tensions())
+        };
+        // handles that reach past the corner or turn around make loops
+        let (t0, t3) = (t0.max(0.0), t3.max(0.0));
+        (p0 + to_corner0 * t0, p3 + to_corner3 * t3)
+    }
+}
+
+/// The point where the curve's extended handles meet, if they meet ahead
+/// of both of them.
+fn handle_corner(curve: &CubicBez) -> Option<Point> {
+    let d0 = curve.p1 - curve.p0;
+    let d3 = curve.p2 - curve.p3;
+    if d0.hypot() < MIN_HANDLE_LENGTH || d3.hypot() < MIN_HANDLE_LENGTH {
+        return None;
+    }
+    // solve p0 + a * d0 == p3 + b * d3
+    let (a, b) = solve(d0, -d3, curve.p3 - curve.p0)?;
+    if a > 0.0 && b > 0.0 {
+        Some(curve.p0 + d0 * a)
+    } else {
+        None
+    }
+}
+
+/// Solve `a * u + b * v == target` for `a` and `b`.
+fn solve(u: Vec2, v: Vec2, target: Vec2) -> Option<(f64, f64)> {
+    let det = u.cross(v);
+    if det.abs() < 1e-9 {
+        return None;
+    }
+    Some((target.cross(v) / det, u.cross(target) / det))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::design_space::DPoint;
+    use crate::point::PathPoint;
+
+    fn segment(points: [(f64, f64); 4]) -> Segment {
+        let path = EntityId::next();
+        let pt = |i: usize| DPoint::new(points[i].0, points[i].1);
+        Segment::Cubic(RawSegment::Cubic(
+            PathPoint::on_curve(path, pt(0)),
+            PathPoint::off_curve(path, pt(1)),
+            PathPoint::off_curve(path, pt(2)),
+            PathPoint::on_curve(path, pt(3)),
+        ))
+    }
+
+    #[test]
+    fn quarter_circle() {
+        let tunni =
+            Tunni::for_segment(&segment([(0., 0.), (0., 55.), (45., 100.), (100., 100.)])).unwrap();
+        assert!((tunni.corner - Point::new(0., 100.)).hypot() < 1e-9);
+        let (t1, t2) = tunni.tensions();
+        assert!((t1 - 0.55).abs() < 1e-9 && (t2 - 0.55).abs() < 1e-9);
+
+        // dragging the point out along the diagonal increases both tensions
+        let (p1, p2) = tunni.handles_for_point(Point::new(-10., 110.), true);
+        assert!(p1.x.abs() < 1e-9 && p1.y > 55.);
+        assert!((p2.y - 100.).abs() < 1e-9);
+        assert!((p1.y - (100. - p2.x)).abs() < 1e-9);
+
+        // and back again is where we started
+        let (p1, p2) = tunni.handles_for_point(tunni.point(), false);
+        assert!((p1 - Point::new(0., 55.)).hypot() < 1e-9);
+        assert!((p2 - Point::new(45., 100.)).hypot() < 1e-9);
+    }
+
+    #[test]
+    fn handles_that_never_meet() {
+        let parallel = segment([(0., 0.), (0., 50.), (100., 50.), (100., 0.)]);
+        assert!(Tunni::for_segment(&parallel).is_none());
+        let diverging = segment([(0., 0.), (-10., 50.), (110., 50.), (100., 0.)]);
+        assert!(Tunni::for_segment(&diverging).is_none());
+    }
+}
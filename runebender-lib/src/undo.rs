@@ -9,11 +9,20 @@ const DEFAULT_UNDO_STACK_SIZE: usize = 128;
 #[derive(Debug)]
 pub(crate) struct UndoState<T> {
     max_undo_count: usize,
-    stack: VecDeque<T>,
+    stack: VecDeque<UndoGroup<T>>,
     /// The index in `stack` of the current document.
     live_index: usize,
 }
 
+/// A state, and a description of the edit that produced it.
+#[derive(Debug)]
+struct UndoGroup<T> {
+    state: T,
+    /// Shown in the undo and redo menu items, like "Nudge"; the initial
+    /// state has none.
+    description: Option<String>,
+}
+
 impl<T> UndoState<T> {
     pub(crate) fn new(init_state: T) -> Self {
         Self::new_sized(DEFAULT_UNDO_STACK_SIZE, init_state)
@@ -21,7 +30,10 @@ impl<T> UndoState<T> {
 
     fn new_sized(max_undo_count: usize, init_state: T) -> Self {
         let mut stack = VecDeque::new();
-        stack.push_back(init_state);
+        stack.push_back(UndoGroup {
+            state: init_state,
+            description: None,
+        });
         UndoState {
             max_undo_count,
             stack,
@@ -34,7 +46,7 @@ impl<T> UndoState<T> {
             return None;
         }
         self.live_index -= 1;
-        self.stack.get(self.live_index)
+        self.stack.get(self.live_index).map(|group| &group.state)
     }
 
     pub(crate) fn redo(&mut self) -> Option<&T> {
@@ -42,16 +54,49 @@ impl<T> UndoState<T> {
             return None;
         }
         self.live_index += 1;
-        self.stack.get(self.live_index)
+        self.stack.get(self.live_index).map(|group| &group.state)
     }
 
-    pub(crate) fn add_undo_group(&mut self, item: T) {
+    /// The current state.
+    pub(crate) fn current(&self) -> &T {
+        &self.stack[self.live_index].state
+    }
+
+    pub(crate) fn can_undo(&self) -> bool {
+        self.live_index > 0
+    }
+
+    pub(crate) fn can_redo(&self) -> bool {
+        self.live_index < self.stack.len() - 1
+    }
+
+    /// The description of the edit that `undo` would reverse, if any.
+    pub(crate) fn undo_description(&self) -> Option<&str> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.stack[self.live_index].description.as_deref()
+    }
+
+    /// The description of the edit that `redo` would reapply, if any.
+    pub(crate) fn redo_description(&self) -> Option<&str> {
+        self.stack
+            .get(self.live_index + 1)
+            .and_then(|group| group.description.as_deref())
+    }
+
+    /// Add a new state, described (for the undo and redo menu items) by
+    /// `description`, discarding any states that had been undone.
+    pub(crate) fn add_undo_group(&mut self, item: T, description: impl Into<String>) {
         if self.live_index < self.stack.len() - 1 {
             self.stack.truncate(self.live_index + 1);
         }
 
         self.live_index += 1;
-        self.stack.push_back(item);
+        self.stack.push_back(UndoGroup {
+            state: item,
+            description: Some(description.into()),
+        });
 
         if self.stack.len() > self.max_undo_count {
             self.stack.pop_front();
@@ -63,6 +108,35 @@ impl<T> UndoState<T> {
     /// This might be done if an edit occurs that combines with the previous undo,
     /// or if we want to save selection state.
     pub(crate) fn update_current_undo(&mut self, mut f: impl FnMut(&mut T)) {
-        f(self.stack.get_mut(self.live_index).unwrap())
+        f(&mut self.stack.get_mut(self.live_index).unwrap().state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptions() {
+        let mut undo = UndoState::new(0);
+        assert!(!undo.can_undo());
+        assert_eq!(undo.undo_description(), None);
+        undo.add_undo_group(1, "Nudge");
+        undo.add_undo_group(2, "Delete");
+        assert_eq!(undo.undo_description(), Some("Delete"));
+        assert!(!undo.can_redo());
+
+        assert_eq!(undo.undo(), Some(&1));
+        assert_eq!(undo.current(), &1);
+        assert_eq!(undo.undo_description(), Some("Nudge"));
+        assert_eq!(undo.redo_description(), Some("Delete"));
+        assert_eq!(undo.undo(), Some(&0));
+        assert!(!undo.can_undo());
+        assert_eq!(undo.redo_description(), Some("Nudge"));
+
+        // a new edit discards what was undone
+        undo.add_undo_group(3, "Paste");
+        assert_eq!(undo.undo_description(), Some("Paste"));
+        assert_eq!(undo.redo_description(), None);
     }
 }
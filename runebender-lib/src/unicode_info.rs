@@ -0,0 +1,227 @@
+//! Unicode character properties.
+//!
+//! This file relies on code that is generated in our build.rs script, from
+//! the subsets of UnicodeData.txt and Blocks.txt in `resources/`. Characters
+//! outside those subsets have a block, at best, but no other data.
+
+use std::cmp::Ordering;
+
+include!(concat!(env!("OUT_DIR"), "/unicode_data_codegen.rs"));
+
+/// The properties of a single character.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharInfo {
+    pub chr: char,
+    pub name: &'static str,
+    /// The two-letter general category, like `Lu`.
+    pub category: &'static str,
+    /// The bidi class, like `L` or `AL`.
+    pub bidi_class: &'static str,
+    /// The raw decomposition mapping, like `<compat> 0020 0301`.
+    decomposition: &'static str,
+}
+
+impl CharInfo {
+    /// The block this character belongs to.
+    pub fn block(&self) -> Option<&'static str> {
+        block_for_char(self.chr)
+    }
+
+    /// The long name of the general category, like 'Uppercase Letter'.
+    pub fn category_name(&self) -> &'static str {
+        match self.category {
+            "Lu" => "Uppercase Letter",
+            "Ll" => "Lowercase Letter",
+            "Lt" => "Titlecase Letter",
+            "Lm" => "Modifier Letter",
+            "Lo" => "Other Letter",
+            "Mn" => "Nonspacing Mark",
+            "Mc" => "Spacing Mark",
+            "Me" => "Enclosing Mark",
+            "Nd" => "Decimal Number",
+            "Nl" => "Letter Number",
+            "No" => "Other Number",
+            "Pc" => "Connector Punctuation",
+            "Pd" => "Dash Punctuation",
+            "Ps" => "Open Punctuation",
+            "Pe" => "Close Punctuation",
+            "Pi" => "Initial Punctuation",
+            "Pf" => "Final Punctuation",
+            "Po" => "Other Punctuation",
+            "Sm" => "Math Symbol",
+            "Sc" => "Currency Symbol",
+            "Sk" => "Modifier Symbol",
+            "So" => "Other Symbol",
+            "Zs" => "Space Separator",
+            "Zl" => "Line Separator",
+            "Zp" => "Paragraph Separator",
+            "Cc" => "Control",
+            "Cf" => "Format",
+            "Co" => "Private Use",
+            _ => "Unknown",
+        }
+    }
+
+    /// The long name of the bidi class, like 'Left To Right'.
+    pub fn bidi_class_name(&self) -> &'static str {
+        match self.bidi_class {
+            "L" => "Left To Right",
+            "R" => "Right To Left",
+            "AL" => "Arabic Letter",
+            "EN" => "European Number",
+            "ES" => "European Separator",
+            "ET" => "European Terminator",
+            "AN" => "Arabic Number",
+            "CS" => "Common Separator",
+            "NSM" => "Nonspacing Mark",
+            "BN" => "Boundary Neutral",
+            "B" => "Paragraph Separator",
+            "S" => "Segment Separator",
+            "WS" => "White Space",
+            "ON" => "Other Neutral",
+            "LRE" => "Left To Right Embedding",
+            "LRO" => "Left To Right Override",
+            "RLE" => "Right To Left Embedding",
+            "RLO" => "Right To Left Override",
+            "PDF" => "Pop Directional Format",
+            "LRI" => "Left To Right Isolate",
+            "RLI" => "Right To Left Isolate",
+            "FSI" => "First Strong Isolate",
+            "PDI" => "Pop Directional Isolate",
+            _ => "Unknown",
+        }
+    }
+
+    /// The tag of a compatibility decomposition, like `<compat>`.
+    ///
+    /// Canonical decompositions have no tag.
+    pub fn decomposition_tag(&self) -> Option<&'static str> {
+        self.decomposition
+            .split_whitespace()
+            .next()
+            .filter(|item| item.starts_with('<'))
+    }
+
+    /// The characters this character decomposes to, if any.
+    pub fn decomposition(&self) -> Vec<char> {
+        self.decomposition
+            .split_whitespace()
+            .filter(|item| !item.starts_with('<'))
+            .filter_map(|item| u32::from_str_radix(item, 16).ok())
+            .filter_map(std::char::from_u32)
+            .collect()
+    }
+}
+
+/// Look up the properties of this `char`, if we have them.
+pub fn char_info(chr: char) -> Option<CharInfo> {
+    UNICODE_DATA
+        .binary_search_by(|probe| probe.0.cmp(&chr))
+        .ok()
+        .map(|idx| {
+            let (chr, name, category, bidi_class, decomposition) = UNICODE_DATA[idx];
+            CharInfo {
+                chr,
+                name,
+                category,
+                bidi_class,
+                decomposition,
+            }
+        })
+}
+
+/// The name of the block containing this `char`, if we know it.
+pub fn block_for_char(chr: char) -> Option<&'static str> {
+    let chr = chr as u32;
+    UNICODE_BLOCKS
+        .iter()
+        .find(|(start, end, _)| (*start..=*end).contains(&chr))
+        .map(|(_, _, name)| *name)
+}
+
+/// Whether `chr` is assigned a meaning by the standard, if we know.
+///
+/// Our data only includes some blocks; for characters in other blocks
+/// this returns `None`.
+pub fn is_assigned(chr: char) -> Option<bool> {
+    if char_info(chr).is_some() {
+        return Some(true);
+    }
+    let chr = chr as u32;
+    let (start, end, _) = UNICODE_BLOCKS
+        .iter()
+        .find(|(start, end, _)| (*start..=*end).contains(&chr))?;
+    // if we have any of the block, we have all of it
+    let have_block = UNICODE_DATA
+        .binary_search_by(|probe| {
+            let probe = probe.0 as u32;
+            if probe < *start {
+                Ordering::Less
+            } else if probe > *end {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        })
+        .is_ok();
+    if have_block {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Characters with a canonical decomposition that includes `chr`.
+///
+/// For a base letter these are its accented forms, and for a combining
+/// mark they are the letters that use it.
+pub fn composed_forms(chr: char) -> Vec<char> {
+    UNICODE_DATA
+        .iter()
+        .filter(|(_, _, _, _, decomposition)| {
+            !decomposition.starts_with('<')
+                && decomposition
+                    .split_whitespace()
+                    .filter_map(|item| u32::from_str_radix(item, 16).ok())
+                    .any(|item| item == chr as u32)
+        })
+        .map(|(composed, ..)| *composed)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lookup() {
+        let info = char_info('Á').unwrap();
+        assert_eq!(info.name, "LATIN CAPITAL LETTER A WITH ACUTE");
+        assert_eq!(info.category_name(), "Uppercase Letter");
+        assert_eq!(info.bidi_class_name(), "Left To Right");
+        assert_eq!(info.block(), Some("Latin-1 Supplement"));
+        assert_eq!(info.decomposition(), vec!['A', '\u{301}']);
+        assert_eq!(info.decomposition_tag(), None);
+
+        let half = char_info('½').unwrap();
+        assert_eq!(half.decomposition_tag(), Some("<fraction>"));
+        assert_eq!(half.decomposition(), vec!['1', '\u{2044}', '2']);
+
+        assert_eq!(char_info('中'), None);
+        assert_eq!(block_for_char('中'), Some("CJK Unified Ideographs"));
+
+        assert_eq!(is_assigned('Á'), Some(true));
+        assert_eq!(is_assigned('\u{378}'), Some(false));
+        assert_eq!(is_assigned('中'), None);
+    }
+
+    #[test]
+    fn composed() {
+        let forms = composed_forms('A');
+        assert!(forms.contains(&'Á'));
+        assert!(forms.contains(&'Ä'));
+        assert!(!forms.contains(&'Ǻ'));
+        assert!(composed_forms('\u{301}').contains(&'é'));
+        assert!(composed_forms('q').is_empty());
+    }
+}
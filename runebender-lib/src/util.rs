@@ -1,7 +1,5 @@
 //! Shared helpers.
 
-use std::convert::TryFrom;
-
 use druid::kurbo::{Size, Vec2};
 
 /// Unwrap an optional, printing a message and returning if it is missing.
@@ -38,30 +36,26 @@ pub(crate) fn compute_scale(pre: Size, post: Size) -> Vec2 {
     Vec2::new(x, y)
 }
 
-/// temporary; creates a new blank  font with some placeholder glyphs.
-pub fn create_blank_font() -> norad::Ufo {
-    let mut ufo = norad::Ufo::new();
-    ufo.font_info = norad::FontInfo {
-        family_name: Some("Untitled".into()),
-        style_name: Some("Regular".into()),
-        units_per_em: Some(TryFrom::try_from(1000.0f64).unwrap()),
-        descender: Some(From::from(-200.0)),
-        ascender: Some(800.0.into()),
-        cap_height: Some(700.0.into()),
-        x_height: Some(500.0.into()),
-        ..Default::default()
+/// Format `num` with at most `decimals` decimal places, omitting trailing
+/// zeros and never showing `-0`.
+pub(crate) fn format_num(num: f64, decimals: usize) -> String {
+    let s = format!("{:.*}", decimals, num);
+    let s = if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &s
+    };
+    match s {
+        "-0" => "0".into(),
+        other => other.into(),
     }
-    .into();
+}
 
-    let layer = ufo.get_default_layer_mut().unwrap();
-    ('a'..='z')
-        .into_iter()
-        .chain('A'..='Z')
-        .map(|chr| {
-            let mut glyph = norad::Glyph::new_named(chr.to_string());
-            glyph.codepoints = Some(vec![chr]);
-            glyph
-        })
-        .for_each(|glyph| layer.insert_glyph(glyph));
-    ufo
+/// Creates a new blank font with some empty glyphs.
+///
+/// See [`FontBuilder`] for making other kinds of new fonts.
+///
+/// [`FontBuilder`]: crate::font_builder::FontBuilder
+pub fn create_blank_font() -> norad::Ufo {
+    crate::font_builder::FontBuilder::new().build()
 }
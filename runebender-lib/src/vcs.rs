@@ -0,0 +1,163 @@
+//! Basic awareness of a git repository containing the font.
+//!
+//! This runs the `git` command line tool, so it only works if git is
+//! installed; if it isn't, the font is treated as if it weren't in a
+//! repository.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use norad::GlyphName;
+
+/// The directory of the default layer, which the UFO spec fixes as `glyphs`.
+const DEFAULT_LAYER_DIR: &str = "glyphs";
+const CONTENTS_FILE: &str = "contents.plist";
+
+/// A git repository containing a UFO, and which of the UFO's glyphs differ
+/// from the last commit.
+#[derive(Debug, Clone)]
+pub struct GitStatus {
+    /// The root of the repository's work tree.
+    root: PathBuf,
+    /// The UFO's path, with symlinks resolved.
+    ufo_path: PathBuf,
+    /// Glyphs in the default layer that are changed or new.
+    pub modified_glyphs: HashSet<GlyphName>,
+}
+
+impl GitStatus {
+    /// Find the repository containing the UFO at `ufo_path`, and its status.
+    ///
+    /// Returns `None` if the UFO is not in a repository.
+    pub fn for_ufo(ufo_path: &Path) -> Option<GitStatus> {
+        let ufo_path = ufo_path.canonicalize().ok()?;
+        let output = run_git(&ufo_path, &["rev-parse", "--show-toplevel"]).ok()?;
+        let root = PathBuf::from(String::from_utf8_lossy(&output).trim());
+        let mut status = GitStatus {
+            root,
+            ufo_path,
+            modified_glyphs: HashSet::new(),
+        };
+        if let Err(e) = status.refresh() {
+            log::warn!("failed to get git status: '{}'", e);
+        }
+        Some(status)
+    }
+
+    /// Check which files have changed since the last commit.
+    pub fn refresh(&mut self) -> Result<(), String> {
+        let ufo_path = self.ufo_path.to_string_lossy().into_owned();
+        let output = run_git(
+            &self.root,
+            &[
+                "status",
+                "--porcelain",
+                "-z",
+                "--untracked-files=all",
+                "--",
+                &ufo_path,
+            ],
+        )?;
+        let changed = parse_status(&output);
+        let glyph_dir = self.ufo_path.join(DEFAULT_LAYER_DIR);
+        let file_names = glyph_file_names(&glyph_dir);
+        self.modified_glyphs = changed
+            .iter()
+            .map(|path| self.root.join(path))
+            .filter(|path| path.parent() == Some(glyph_dir.as_path()))
+            .filter_map(|path| {
+                let file_name = path.file_name()?.to_str()?;
+                file_names.get(file_name).cloned()
+            })
+            .collect();
+        Ok(())
+    }
+
+    /// Commit every change to the UFO, and only the UFO, with this message.
+    pub fn commit(&mut self, message: &str) -> Result<(), String> {
+        let ufo_path = self.ufo_path.to_string_lossy().into_owned();
+        run_git(&self.root, &["add", "--all", "--", &ufo_path])?;
+        run_git(&self.root, &["commit", "-m", message, "--", &ufo_path])?;
+        self.refresh()
+    }
+}
+
+/// Run git in `dir`, returning its output, or what it printed to stderr if
+/// it fails.
+fn run_git(dir: &Path, args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_owned())
+    }
+}
+
+/// Map the names of the glif files in a layer to the names of their glyphs.
+fn glyph_file_names(layer_dir: &Path) -> HashMap<String, GlyphName> {
+    let contents = match plist::Value::from_file(layer_dir.join(CONTENTS_FILE)) {
+        Ok(contents) => contents,
+        Err(e) => {
+            log::warn!("failed to read layer contents: '{}'", e);
+            return HashMap::new();
+        }
+    };
+    contents
+        .as_dictionary()
+        .map(|dict| {
+            dict.iter()
+                .filter_map(|(name, file)| {
+                    Some((file.as_string()?.to_owned(), name.as_str().into()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The paths in the output of `git status --porcelain -z`.
+///
+/// For renamed files, this is the new path.
+fn parse_status(output: &[u8]) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    let mut entries = output.split(|b| *b == 0).filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        // each entry is two status characters and a space, then the path
+        if entry.len() < 4 {
+            continue;
+        }
+        let (status, path) = entry.split_at(3);
+        paths.push(PathBuf::from(String::from_utf8_lossy(path).into_owned()));
+        // renames and copies are followed by the original path
+        if status[0] == b'R' || status[0] == b'C' {
+            entries.next();
+        }
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn porcelain_status() {
+        let output = b" M font.ufo/glyphs/a.glif\0?? font.ufo/glyphs/b.glif\0\
+            R  font.ufo/glyphs/c_.glif\0font.ufo/glyphs/C_.glif\0D  font.ufo/glyphs/d.glif\0";
+        let paths = parse_status(output);
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from("font.ufo/glyphs/a.glif"),
+                PathBuf::from("font.ufo/glyphs/b.glif"),
+                PathBuf::from("font.ufo/glyphs/c_.glif"),
+                PathBuf::from("font.ufo/glyphs/d.glif"),
+            ]
+        );
+    }
+}
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+use std::convert::TryInto;
+
 use druid::kurbo::Shape;
 use norad::{GlyphName, Ufo};
 
 use crate::data::Workspace;
-
-use std::convert::TryInto;
+use crate::opentype;
 
 pub type GlyphId = u16;
 
@@ -13,14 +15,18 @@ pub type GlyphId = u16;
 /// font file.
 #[derive(Debug, Clone, Default)]
 pub struct VirtualFont {
-    glyph_ids: Vec<(char, GlyphName)>,
+    glyph_ids: Vec<(Option<char>, GlyphName)>,
     cmap: Vec<u8>,
     hhea: Vec<u8>,
     hmtx: Vec<u8>,
+    maxp: Vec<u8>,
+    gsub: Vec<u8>,
+    gpos: Vec<u8>,
+    gdef: Vec<u8>,
 }
 
 /// Given a ufo, generate a vector of (codepoint, glyph name) pairs,
-/// sorted by codepoint.
+/// sorted by codepoint, followed by the unencoded glyphs.
 ///
 /// This is used as our 'glyphid' table.
 ///
@@ -28,20 +34,31 @@ pub struct VirtualFont {
 ///
 /// Although multiple codepoints can map to the same glyph, we do
 /// not actually handle this well in practice.
-fn glyph_ids(font: &Ufo) -> Vec<(char, GlyphName)> {
+fn glyph_ids(font: &Ufo) -> Vec<(Option<char>, GlyphName)> {
     let mut chars_and_names = Vec::with_capacity(font.glyph_count() + 1);
-    chars_and_names.push(('\0', GlyphName::from(".notdef")));
+    let mut unencoded = Vec::new();
     for glyph in font
         .get_default_layer()
         .iter()
         .flat_map(|layer| layer.iter_contents())
     {
-        for codepoint in glyph.codepoints.as_ref().iter().flat_map(|cps| cps.iter()) {
-            chars_and_names.push((*codepoint, glyph.name.clone()));
+        match glyph.codepoints.as_ref().filter(|cps| !cps.is_empty()) {
+            Some(codepoints) => {
+                for codepoint in codepoints {
+                    chars_and_names.push((Some(*codepoint), glyph.name.clone()));
+                }
+            }
+            None if &*glyph.name != ".notdef" => unencoded.push((None, glyph.name.clone())),
+            None => (),
         }
     }
     chars_and_names.sort();
-    chars_and_names
+    unencoded.sort();
+    // unencoded glyphs are only reachable through substitutions
+    std::iter::once((None, GlyphName::from(".notdef")))
+        .chain(chars_and_names)
+        .chain(unencoded)
+        .collect()
 }
 
 impl VirtualFont {
@@ -51,11 +68,26 @@ impl VirtualFont {
         let glyph_ids = glyph_ids(&workspace.font.ufo);
         let cmap = make_cmap_table(&glyph_ids);
         let (hhea, hmtx) = make_horiz_tables(workspace, &glyph_ids);
+        let maxp = make_maxp_table(glyph_ids.len());
+
+        // a glyph with multiple codepoints is referred to by its first id
+        let mut ids_by_name = HashMap::new();
+        for (id, (_, name)) in glyph_ids.iter().enumerate() {
+            ids_by_name.entry(name.clone()).or_insert(id as GlyphId);
+        }
+        let glyph_id = |name: &str| ids_by_name.get(name).copied();
+        let compiled = opentype::compile_features(&workspace.features, &glyph_id);
+        let positioning = opentype::compile_positioning(&workspace.font.ufo, &glyph_id);
+
         VirtualFont {
             glyph_ids,
             cmap,
             hhea,
             hmtx,
+            maxp,
+            gsub: compiled.gsub.unwrap_or_default(),
+            gpos: positioning.gpos.unwrap_or_default(),
+            gdef: positioning.gdef.unwrap_or_default(),
         }
     }
 
@@ -86,16 +118,40 @@ impl VirtualFont {
     pub fn hmtx(&self) -> &[u8] {
         &self.hmtx
     }
+
+    pub fn maxp(&self) -> &[u8] {
+        &self.maxp
+    }
+
+    /// The compiled substitutions; empty if the font has none.
+    pub fn gsub(&self) -> &[u8] {
+        &self.gsub
+    }
+
+    /// The compiled kerning and mark attachment; empty if the font has none.
+    pub fn gpos(&self) -> &[u8] {
+        &self.gpos
+    }
+
+    /// Glyph classes for the marks and bases in `gpos`; empty if there are
+    /// no marks.
+    pub fn gdef(&self) -> &[u8] {
+        &self.gdef
+    }
 }
 
-fn make_cmap_table(glyphs: &[(char, GlyphName)]) -> Vec<u8> {
+fn make_cmap_table(glyphs: &[(Option<char>, GlyphName)]) -> Vec<u8> {
     let mut start_codes = Vec::new();
     let mut end_codes: Vec<u16> = Vec::new();
     let mut offsets = Vec::new();
     let mut deltas = Vec::new();
 
-    for (i, (chr, _glyph_name)) in glyphs.iter().enumerate().skip(1) {
-        let chr: u16 = (*chr as u32).try_into().unwrap();
+    let encoded = glyphs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, (chr, _))| chr.map(|chr| (i, chr)));
+    for (i, chr) in encoded {
+        let chr: u16 = (chr as u32).try_into().unwrap();
         if end_codes.last().map(|c| c + 1) == Some(chr) {
             *end_codes.last_mut().unwrap() += 1;
         } else {
@@ -150,9 +206,17 @@ fn make_cmap_table(glyphs: &[(char, GlyphName)]) -> Vec<u8> {
     result
 }
 
+/// A version 0.5 'maxp' table, which only has the number of glyphs.
+fn make_maxp_table(num_glyphs: usize) -> Vec<u8> {
+    let mut result = Vec::with_capacity(6);
+    result.extend_from_slice(&0x0000_5000_u32.to_be_bytes());
+    result.extend_from_slice(&(num_glyphs as u16).to_be_bytes());
+    result
+}
+
 fn make_horiz_tables(
     workspace: &Workspace,
-    glyphs: &[(char, GlyphName)],
+    glyphs: &[(Option<char>, GlyphName)],
     //paths: &BezCache,
 ) -> (Vec<u8>, Vec<u8>) {
     let records = glyphs
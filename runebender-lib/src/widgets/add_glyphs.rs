@@ -0,0 +1,115 @@
+//! A panel for adding many glyphs at once.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, Scope, Scroll,
+    TextBox,
+};
+use druid::{Color, Lens, WidgetExt};
+
+use crate::charset::{self, TEMPLATES};
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The state of the panel: the workspace, plus the text being edited.
+#[derive(Clone, Data, Lens)]
+struct AddGlyphsState {
+    workspace: Workspace,
+    text: String,
+}
+
+impl AddGlyphsState {
+    /// The names in `text` that are not already in the font.
+    fn missing_glyphs(&self) -> Result<Vec<String>, String> {
+        let font = &self.workspace.font.ufo;
+        charset::parse_glyph_list(&self.text).map(|names| {
+            names
+                .into_iter()
+                .filter(|name| font.get_glyph(name.as_str()).is_none())
+                .collect()
+        })
+    }
+
+    fn summary(&self) -> String {
+        match self.missing_glyphs() {
+            Err(item) => format!("Not a glyph name or codepoint: '{}'", item),
+            Ok(names) if names.is_empty() => "No new glyphs.".into(),
+            Ok(names) if names.len() == 1 => "1 new glyph.".into(),
+            Ok(names) => format!("{} new glyphs.", names.len()),
+        }
+    }
+}
+
+pub fn add_glyphs() -> impl Widget<Workspace> {
+    let mut templates = Flex::row();
+    for template in TEMPLATES {
+        templates.add_child(Button::new(template.name).on_click(
+            move |_, data: &mut AddGlyphsState, _| {
+                if !data.text.trim().is_empty() {
+                    data.text.push(' ');
+                }
+                data.text.push_str(&template.to_spec());
+            },
+        ));
+        templates.add_spacer(4.0);
+    }
+
+    let text_box = TextBox::multiline()
+        .with_placeholder("Glyph names, U+XXXX, or U+XXXX-YYYY")
+        .expand()
+        .lens(AddGlyphsState::text);
+
+    let summary = Label::dynamic(|data: &AddGlyphsState, _| data.summary())
+        .with_text_color(theme::PROBLEM_TEXT_COLOR)
+        .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_flex_child(summary.expand_width(), 1.0)
+        .with_default_spacer()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Add").on_click(|ctx, data: &mut AddGlyphsState, _| {
+                if let Ok(names) = data.missing_glyphs() {
+                    let count = data.workspace.add_glyphs(&names);
+                    log::info!("added {} glyphs", count);
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End);
+
+    let panel = Flex::column()
+        .with_child(Label::new("Add Glyphs").with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(
+            Label::new("Templates:")
+                .with_text_color(theme::SECONDARY_TEXT_COLOR)
+                .with_text_size(12.0),
+        )
+        .with_spacer(4.0)
+        .with_child(Scroll::new(templates).horizontal())
+        .with_default_spacer()
+        .with_flex_child(text_box, 1.0)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(520., 360.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        |workspace: Workspace| AddGlyphsState {
+            workspace,
+            text: String::new(),
+        },
+        AddGlyphsState::workspace,
+        panel,
+    )
+}
@@ -0,0 +1,157 @@
+//! The floating panel with buttons for aligning and distributing the
+//! selected points.
+
+use druid::kurbo::{BezPath, Rect, Shape};
+use druid::widget::{prelude::*, Controller, Either, Flex, Painter, SizedBox};
+use druid::{Color, WidgetExt};
+
+use crate::consts::{self, cmd::Alignment};
+use crate::data::EditorState;
+
+const BUTTON_SIZE: f64 = 28.0;
+const BUTTON_PRESSED_COLOR: Color = Color::grey8(0xC4);
+const ICON_COLOR: Color = Color::grey8(0x30);
+
+/// A panel for aligning the selected points.
+pub struct AlignPane;
+
+impl AlignPane {
+    // this is not a blessed pattern
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> impl Widget<EditorState> {
+        build_widget().controller(AlignPane)
+    }
+}
+
+impl<W: Widget<EditorState>> Controller<EditorState, W> for AlignPane {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut EditorState,
+        env: &Env,
+    ) {
+        child.event(ctx, event, data, env);
+        // suppress clicks so that the editor doesn't handle them.
+        if matches!(event, Event::MouseUp(_) | Event::MouseDown(_)) {
+            ctx.set_handled();
+        }
+    }
+}
+
+fn build_widget() -> impl Widget<EditorState> {
+    let mut row = Flex::row();
+    for (i, alignment) in [
+        Alignment::Left,
+        Alignment::CenterX,
+        Alignment::Right,
+        Alignment::Top,
+        Alignment::CenterY,
+        Alignment::Bottom,
+        Alignment::DistributeX,
+        Alignment::DistributeY,
+    ]
+    .iter()
+    .enumerate()
+    {
+        // a gap between the horizontal, vertical, and distribute buttons
+        if i == 3 || i == 6 {
+            row.add_spacer(6.0);
+        }
+        row.add_child(button(*alignment));
+    }
+
+    // only show the panel if there is something to align
+    Either::new(
+        |data: &EditorState, _| {
+            data.session
+                .selection
+                .iter()
+                .filter(|id| !id.is_guide())
+                .count()
+                > 1
+        },
+        row.padding(4.0),
+        SizedBox::empty(),
+    )
+}
+
+fn button(alignment: Alignment) -> impl Widget<EditorState> {
+    let icon = icon(alignment);
+    Painter::new(move |ctx, _: &EditorState, _| {
+        if ctx.is_active() {
+            let frame = ctx.size().to_rect().inset(-2.0).to_rounded_rect(3.0);
+            ctx.fill(frame, &BUTTON_PRESSED_COLOR);
+        }
+        ctx.fill(&icon, &ICON_COLOR);
+    })
+    .fix_size(BUTTON_SIZE, BUTTON_SIZE)
+    .on_click(move |ctx, _, _| ctx.submit_command(consts::cmd::ALIGN_POINTS.with(alignment)))
+}
+
+/// An icon for each kind of alignment, drawn in a `BUTTON_SIZE` square.
+fn icon(alignment: Alignment) -> BezPath {
+    // (x0, y0, x1, y1) for icons that work along the x axis; the others
+    // are the same, transposed.
+    let (rects, transpose): (&[(f64, f64, f64, f64)], bool) = match alignment {
+        Alignment::Left => (
+            &[(6., 4., 7., 24.), (7., 8., 22., 12.), (7., 16., 16., 20.)],
+            false,
+        ),
+        Alignment::CenterX => (
+            &[
+                (13.5, 4., 14.5, 24.),
+                (6., 8., 22., 12.),
+                (9., 16., 19., 20.),
+            ],
+            false,
+        ),
+        Alignment::Right => (
+            &[
+                (21., 4., 22., 24.),
+                (6., 8., 21., 12.),
+                (12., 16., 21., 20.),
+            ],
+            false,
+        ),
+        Alignment::Top => (
+            &[(6., 4., 7., 24.), (7., 8., 22., 12.), (7., 16., 16., 20.)],
+            true,
+        ),
+        Alignment::CenterY => (
+            &[
+                (13.5, 4., 14.5, 24.),
+                (6., 8., 22., 12.),
+                (9., 16., 19., 20.),
+            ],
+            true,
+        ),
+        Alignment::Bottom => (
+            &[
+                (21., 4., 22., 24.),
+                (6., 8., 21., 12.),
+                (12., 16., 21., 20.),
+            ],
+            true,
+        ),
+        Alignment::DistributeX => (
+            &[(4., 9., 8., 19.), (12., 9., 16., 19.), (20., 9., 24., 19.)],
+            false,
+        ),
+        Alignment::DistributeY => (
+            &[(4., 9., 8., 19.), (12., 9., 16., 19.), (20., 9., 24., 19.)],
+            true,
+        ),
+    };
+    let mut path = BezPath::new();
+    for &(x0, y0, x1, y1) in rects {
+        let rect = if transpose {
+            Rect::new(y0, x0, y1, x1)
+        } else {
+            Rect::new(x0, y0, x1, y1)
+        };
+        path.extend(rect.path_elements(0.1));
+    }
+    path
+}
@@ -0,0 +1,124 @@
+//! A panel for choosing a glyph to show attached to the active glyph by
+//! their anchors.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, Scope, TextBox,
+};
+use druid::{Color, Lens, WidgetExt};
+use norad::GlyphName;
+
+use crate::anchor_preview;
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The most glyphs to suggest; the rest are summarized.
+const MAX_SUGGESTIONS: usize = 40;
+
+#[derive(Clone, Data, Lens)]
+struct AnchorPreviewState {
+    workspace: Workspace,
+    name: String,
+}
+
+impl AnchorPreviewState {
+    /// The entered name, if it is a glyph that attaches to the active glyph.
+    fn attachable_name(&self) -> Option<GlyphName> {
+        let ufo = &self.workspace.font.ufo;
+        let glyph = ufo.get_glyph(self.workspace.active_glyph()?)?;
+        let other = ufo.get_glyph(self.name.trim())?;
+        anchor_preview::attachment(glyph, other).map(|_| other.name.clone())
+    }
+
+    fn suggestions(&self) -> String {
+        let ufo = &self.workspace.font.ufo;
+        let glyph = match self.workspace.active_glyph().and_then(|n| ufo.get_glyph(n)) {
+            Some(glyph) => glyph,
+            None => return "No glyph is being edited.".into(),
+        };
+        let names = anchor_preview::attachable_glyphs(ufo, glyph);
+        if names.is_empty() {
+            return format!("No glyphs share anchors with '{}'.", glyph.name);
+        }
+        let mut text = names
+            .iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if names.len() > MAX_SUGGESTIONS {
+            text.push_str(&format!(" and {} more", names.len() - MAX_SUGGESTIONS));
+        }
+        text
+    }
+}
+
+pub fn anchor_preview_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Anchor Preview").with_text_size(16.0);
+    let explanation = Label::new(
+        "Show a mark on this glyph, or a base under it, positioned by matching \
+         anchors like 'top' and '_top'.",
+    )
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let name = TextBox::new()
+        .with_placeholder("Glyph name")
+        .fix_width(200.0)
+        .lens(AnchorPreviewState::name);
+
+    let suggestions = Label::dynamic(|data: &AnchorPreviewState, _| data.suggestions())
+        .with_text_color(theme::SECONDARY_TEXT_COLOR)
+        .with_text_size(12.0)
+        .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_child(Button::new("Off").on_click(|ctx, _, _| {
+            ctx.submit_command(consts::cmd::SET_ANCHOR_PREVIEW.with(None));
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+        }))
+        .with_flex_spacer(1.0)
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Show").on_click(|ctx, data: &mut AnchorPreviewState, _| {
+                if let Some(name) = data.attachable_name() {
+                    ctx.submit_command(consts::cmd::SET_ANCHOR_PREVIEW.with(Some(name)));
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(name)
+        .with_default_spacer()
+        .with_child(suggestions)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        |workspace: Workspace| AnchorPreviewState {
+            workspace,
+            name: String::new(),
+        },
+        AnchorPreviewState::workspace,
+        panel,
+    )
+}
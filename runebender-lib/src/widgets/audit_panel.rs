@@ -0,0 +1,45 @@
+//! A window listing problems found in the font's glyphs.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, List, Scroll};
+use druid::{Color, WidgetExt};
+
+use crate::app_delegate::EDIT_GLYPH;
+use crate::audit::Issue;
+use crate::data::Workspace;
+use crate::theme;
+
+pub fn audit_panel() -> impl Widget<Workspace> {
+    let summary = Label::dynamic(|data: &Workspace, _| match data.audit_issues.len() {
+        0 => "No problems found".to_string(),
+        1 => "1 problem".to_string(),
+        n => format!("{} problems", n),
+    })
+    .with_text_color(theme::PRIMARY_TEXT_COLOR);
+
+    let header = Flex::row()
+        .with_child(summary)
+        .with_flex_spacer(1.0)
+        .with_child(
+            Button::new("Check Again").on_click(|_, data: &mut Workspace, _| data.run_audit()),
+        )
+        .padding(8.0);
+
+    // clicking an issue opens its glyph
+    let issues = List::new(|| {
+        Label::dynamic(|issue: &Issue, _| format!("{}: {}", issue.glyph, issue.kind.description()))
+            .with_text_color(theme::PRIMARY_TEXT_COLOR)
+            .padding((8.0, 4.0))
+            .expand_width()
+            .on_click(|ctx, issue: &mut Issue, _| {
+                ctx.submit_command(EDIT_GLYPH.with(issue.glyph.clone()))
+            })
+    })
+    .lens(Workspace::audit_issues);
+
+    Flex::column()
+        .with_child(header)
+        .with_flex_child(Scroll::new(issues).vertical().expand(), 1.0)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .background(Color::WHITE)
+}
@@ -0,0 +1,208 @@
+//! A panel for renaming many glyphs at once.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Either, Flex, Label, LineBreaking, MainAxisAlignment, RadioGroup,
+    Scope, TextBox,
+};
+use druid::{Color, Lens, WidgetExt};
+use norad::GlyphName;
+
+use crate::charset;
+use crate::data::Workspace;
+use crate::glyph_rename::{self, RenameOp};
+use crate::theme;
+use crate::widgets::ModalHost;
+
+const FIELD_WIDTH: f64 = 140.0;
+/// The most renames we list in the summary.
+const MAX_LISTED_RENAMES: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Data)]
+enum RenameKind {
+    Replace,
+    AddSuffix,
+    RemoveSuffix,
+}
+
+/// The state of the panel: the workspace, plus the fields being edited.
+#[derive(Clone, Data, Lens)]
+struct RenameState {
+    workspace: Workspace,
+    /// The glyphs to rename; all glyphs if this is empty.
+    glyphs: String,
+    kind: RenameKind,
+    find: String,
+    replace: String,
+    suffix: String,
+}
+
+impl RenameState {
+    fn new(workspace: Workspace) -> Self {
+        RenameState {
+            workspace,
+            glyphs: String::new(),
+            kind: RenameKind::Replace,
+            find: String::new(),
+            replace: String::new(),
+            suffix: String::new(),
+        }
+    }
+
+    fn op(&self) -> RenameOp {
+        match self.kind {
+            RenameKind::Replace => RenameOp::Replace {
+                find: &self.find,
+                replace: &self.replace,
+            },
+            RenameKind::AddSuffix => RenameOp::AddSuffix(&self.suffix),
+            RenameKind::RemoveSuffix => RenameOp::RemoveSuffix(&self.suffix),
+        }
+    }
+
+    /// The renames to make, and the locked glyphs that are left out of them.
+    fn renames(&self) -> Result<(Vec<(GlyphName, GlyphName)>, Vec<GlyphName>), String> {
+        let font = &self.workspace.font.ufo;
+        let names: Vec<GlyphName> = if self.glyphs.trim().is_empty() {
+            self.workspace.sorted_glyph_names()
+        } else {
+            charset::parse_glyph_list(&self.glyphs)
+                .map_err(|item| format!("Not a glyph name or codepoint: '{}'", item))?
+                .into_iter()
+                .filter(|name| font.get_glyph(name.as_str()).is_some())
+                .map(Into::into)
+                .collect()
+        };
+        let (locked, renames): (Vec<_>, Vec<_>) =
+            glyph_rename::plan_renames(&names, &self.op(), |name| font.get_glyph(name).is_some())?
+                .into_iter()
+                .partition(|(old, _)| self.workspace.is_glyph_locked(old));
+        Ok((renames, locked.into_iter().map(|(old, _)| old).collect()))
+    }
+
+    fn summary(&self) -> String {
+        let (renames, locked) = match self.renames() {
+            Err(problem) => return problem,
+            Ok(planned) => planned,
+        };
+        let mut summary = if renames.is_empty() {
+            "No glyphs to rename.".to_string()
+        } else {
+            let mut listed = renames
+                .iter()
+                .take(MAX_LISTED_RENAMES)
+                .map(|(old, new)| format!("{} → {}", old, new))
+                .collect::<Vec<_>>();
+            if renames.len() > MAX_LISTED_RENAMES {
+                listed.push(format!("and {} more", renames.len() - MAX_LISTED_RENAMES));
+            }
+            listed.join(", ")
+        };
+        if !locked.is_empty() {
+            let names = locked.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+            summary.push_str(&format!("\nSkipped locked glyphs: {}", names.join(", ")));
+        }
+        summary
+    }
+}
+
+pub fn batch_rename() -> impl Widget<Workspace> {
+    let glyphs = TextBox::new()
+        .with_placeholder("All glyphs")
+        .expand_width()
+        .lens(RenameState::glyphs);
+
+    let kind = RadioGroup::new(vec![
+        ("Find and replace", RenameKind::Replace),
+        ("Add suffix", RenameKind::AddSuffix),
+        ("Remove suffix", RenameKind::RemoveSuffix),
+    ])
+    .lens(RenameState::kind);
+
+    let replace_fields = Flex::row()
+        .with_child(
+            TextBox::new()
+                .with_placeholder("Find")
+                .fix_width(FIELD_WIDTH)
+                .lens(RenameState::find),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            TextBox::new()
+                .with_placeholder("Replace with")
+                .fix_width(FIELD_WIDTH)
+                .lens(RenameState::replace),
+        );
+    let suffix_field = TextBox::new()
+        .with_placeholder(".sc")
+        .fix_width(FIELD_WIDTH)
+        .lens(RenameState::suffix);
+    let fields = Either::new(
+        |data: &RenameState, _| data.kind == RenameKind::Replace,
+        replace_fields,
+        suffix_field,
+    );
+
+    let summary = Either::new(
+        |data: &RenameState, _| data.renames().is_ok(),
+        summary_label().with_text_color(theme::SECONDARY_TEXT_COLOR),
+        summary_label().with_text_color(theme::PROBLEM_TEXT_COLOR),
+    );
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Rename").on_click(|ctx, data: &mut RenameState, _| {
+                if let Ok((renames, _)) = data.renames() {
+                    let count = data.workspace.rename_glyphs(&renames);
+                    log::info!("renamed {} glyphs", count);
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(Label::new("Rename Glyphs").with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(
+            Label::new("Glyphs (names, U+XXXX, or U+XXXX-YYYY):")
+                .with_text_color(theme::SECONDARY_TEXT_COLOR)
+                .with_text_size(12.0),
+        )
+        .with_spacer(4.0)
+        .with_child(glyphs)
+        .with_default_spacer()
+        .with_child(kind)
+        .with_spacer(4.0)
+        .with_child(fields)
+        .with_default_spacer()
+        .with_child(
+            Label::new("Components, kerning groups and kerning are updated to use the new names.")
+                .with_line_break_mode(LineBreaking::WordWrap)
+                .with_font(theme::UI_DETAIL_FONT)
+                .with_text_color(theme::SECONDARY_TEXT_COLOR),
+        )
+        .with_flex_spacer(1.0)
+        .with_child(summary.expand_width())
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(420., 360.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(RenameState::new, RenameState::workspace, panel)
+}
+
+fn summary_label() -> Label<RenameState> {
+    Label::dynamic(|data: &RenameState, _| data.summary())
+        .with_line_break_mode(LineBreaking::WordWrap)
+}
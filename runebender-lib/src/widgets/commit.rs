@@ -0,0 +1,91 @@
+//! A panel for writing a commit message, when the font is in a git
+//! repository.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Scope, TextBox};
+use druid::{Color, Lens, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+#[derive(Clone, Data, Lens)]
+struct CommitState {
+    workspace: Workspace,
+    message: String,
+}
+
+impl CommitState {
+    fn new(workspace: Workspace) -> Self {
+        CommitState {
+            workspace,
+            message: String::new(),
+        }
+    }
+}
+
+pub fn commit_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Commit").with_text_size(16.0);
+    let summary = Label::dynamic(|data: &CommitState, _| {
+        let modified = data
+            .workspace
+            .vcs
+            .as_ref()
+            .map(|vcs| vcs.modified_glyphs.len())
+            .unwrap_or(0);
+        match modified {
+            0 => "The font will be saved, and all its changes committed.".into(),
+            1 => "The font will be saved, and its changes committed, \
+                  including 1 modified glyph."
+                .into(),
+            n => format!(
+                "The font will be saved, and its changes committed, \
+                 including {} modified glyphs.",
+                n
+            ),
+        }
+    })
+    .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let message = TextBox::multiline()
+        .with_placeholder("Commit message")
+        .expand()
+        .lens(CommitState::message);
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Commit").on_click(|ctx, data: &mut CommitState, _| {
+                let message = data.message.trim();
+                if message.is_empty() {
+                    return;
+                }
+                ctx.submit_command(consts::cmd::COMMIT_CHANGES.with(message.to_owned()));
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(summary)
+        .with_default_spacer()
+        .with_flex_child(message, 1.0)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(420., 260.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(CommitState::new, CommitState::workspace, panel)
+}
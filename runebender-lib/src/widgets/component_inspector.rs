@@ -0,0 +1,183 @@
+//! A panel for editing the parameters of the components in the glyph in
+//! the active editor tab.
+//!
+//! This is intended to be shown as a modal panel.
+
+use std::sync::Arc;
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, Controller, CrossAxisAlignment, Either, Flex, Label, LineBreaking, List,
+    MainAxisAlignment, Scope, Scroll,
+};
+use druid::{lens, Color, Lens, WidgetExt};
+
+use crate::component::ComponentParams;
+use crate::consts::{self, cmd::SetComponentParamsArgs};
+use crate::data::Workspace;
+use crate::edit_session::EditSession;
+use crate::point::EntityId;
+use crate::theme;
+use crate::util::format_num;
+use crate::widgets::{EditableLabel, ModalHost};
+
+const FIELD_WIDTH: f64 = 56.0;
+
+#[derive(Clone, Data, Lens)]
+struct InspectorState {
+    workspace: Workspace,
+}
+
+/// A component, as shown in the list.
+#[derive(Clone, Data, Lens)]
+struct ComponentRow {
+    id: EntityId,
+    base: String,
+    smart: bool,
+    params: ComponentParams,
+}
+
+impl InspectorState {
+    fn new(workspace: Workspace) -> Self {
+        InspectorState { workspace }
+    }
+
+    fn session(&self) -> Option<&Arc<EditSession>> {
+        self.workspace
+            .active_tab
+            .and_then(|id| self.workspace.sessions.get(&id))
+    }
+
+    fn rows(&self) -> Arc<Vec<ComponentRow>> {
+        let rows = self
+            .session()
+            .map(|session| {
+                session
+                    .components
+                    .iter()
+                    .map(|comp| ComponentRow {
+                        id: comp.id,
+                        base: comp.base.to_string(),
+                        smart: comp.params.is_some(),
+                        params: comp.params(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Arc::new(rows)
+    }
+}
+
+/// Turns edits to a row's parameters into a command, so that the editor
+/// can update undo state.
+struct ParamsEditor;
+
+impl<W: Widget<ComponentRow>> Controller<ComponentRow, W> for ParamsEditor {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut ComponentRow,
+        env: &Env,
+    ) {
+        let mut child_data = data.clone();
+        child.event(ctx, event, &mut child_data, env);
+        if child_data.params != data.params {
+            ctx.submit_command(
+                consts::cmd::SET_COMPONENT_PARAMS.with(SetComponentParamsArgs {
+                    id: data.id,
+                    params: child_data.params,
+                }),
+            );
+        }
+    }
+}
+
+pub fn component_inspector() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &InspectorState, _| match data.session() {
+        Some(session) => format!("Components of '{}'", session.name),
+        None => "No glyph open".into(),
+    })
+    .with_text_size(16.0);
+
+    let explanation = Label::new(
+        "Editing a component's parameters makes it a smart component; \
+         its parameters are saved with each use of it.",
+    )
+    .with_line_break_mode(LineBreaking::WordWrap)
+    .with_font(theme::UI_DETAIL_FONT)
+    .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let rows = lens::Map::new(|data: &InspectorState| data.rows(), |_, _| ());
+    let list = Either::new(
+        |data: &InspectorState, _| data.rows().is_empty(),
+        Label::new("This glyph has no components.").with_text_color(theme::SECONDARY_TEXT_COLOR),
+        Scroll::new(List::new(component_row).lens(rows)).vertical(),
+    );
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Close").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_flex_child(list, 1.0)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(460., 440.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(InspectorState::new, InspectorState::workspace, panel)
+}
+
+fn component_row() -> impl Widget<ComponentRow> {
+    let name = Label::dynamic(|data: &ComponentRow, _| match data.smart {
+        true => format!("{} (smart)", data.base),
+        false => data.base.clone(),
+    });
+
+    let fields = Flex::row()
+        .with_child(field("x", ComponentParams::offset_x))
+        .with_child(field("y", ComponentParams::offset_y))
+        .with_child(field("scale x", ComponentParams::scale_x))
+        .with_child(field("scale y", ComponentParams::scale_y))
+        .with_child(field("rotate", ComponentParams::rotation))
+        .with_child(field("skew", ComponentParams::skew))
+        .lens(ComponentRow::params);
+
+    Flex::column()
+        .with_child(name)
+        .with_spacer(4.0)
+        .with_child(fields)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .padding((0.0, 6.0))
+        .controller(ParamsEditor)
+}
+
+/// An editable value with a caption below it.
+fn field(
+    caption: &str,
+    lens: impl Lens<ComponentParams, f64> + 'static,
+) -> impl Widget<ComponentParams> {
+    let value = EditableLabel::new(|v: &f64, _: &_| format_num(*v, 3), |s| s.parse().ok())
+        .with_font(theme::UI_DETAIL_FONT)
+        .lens(lens)
+        .fix_width(FIELD_WIDTH);
+    Flex::column()
+        .with_child(value)
+        .with_child(
+            Label::new(caption)
+                .with_font(theme::UI_DETAIL_FONT)
+                .with_text_color(theme::SECONDARY_TEXT_COLOR),
+        )
+        .padding((0.0, 0.0, 8.0, 0.0))
+}
@@ -0,0 +1,172 @@
+//! The floating panel that lists the contours of the glyph being edited.
+
+use std::sync::Arc;
+
+use druid::widget::{
+    prelude::*, Controller, CrossAxisAlignment, Either, Flex, Label, List, Painter, Scroll,
+    SizedBox,
+};
+use druid::{LensExt, WidgetExt};
+
+use crate::consts::{self, cmd::RenameContourArgs, cmd::SelectContourArgs};
+use crate::data::EditorState;
+use crate::edit_session::{ContourInfo, EditSession};
+use crate::theme;
+use crate::widgets::EditableLabel;
+
+const NAME_WIDTH: f64 = 96.0;
+const DETAIL_WIDTH: f64 = 56.0;
+const ICON_WIDTH: f64 = 18.0;
+
+/// A panel listing each contour, where contours can be selected, named,
+/// reordered, hidden, and locked.
+pub struct ContourList;
+
+impl ContourList {
+    // this is not a blessed pattern
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new() -> impl Widget<EditorState> {
+        build_widget().controller(ContourList)
+    }
+}
+
+impl<W: Widget<EditorState>> Controller<EditorState, W> for ContourList {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut EditorState,
+        env: &Env,
+    ) {
+        child.event(ctx, event, data, env);
+        // suppress clicks so that the editor doesn't handle them.
+        if matches!(event, Event::MouseUp(_) | Event::MouseDown(_)) {
+            ctx.set_handled();
+        }
+    }
+}
+
+/// Turns clicks on a row into selection, and edits to its name into a
+/// command, so that the editor can update undo state.
+struct ContourRow;
+
+impl<W: Widget<ContourInfo>> Controller<ContourInfo, W> for ContourRow {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut ContourInfo,
+        env: &Env,
+    ) {
+        let mut child_data = data.clone();
+        child.event(ctx, event, &mut child_data, env);
+        if child_data.name != data.name {
+            ctx.submit_command(consts::cmd::RENAME_CONTOUR.with(RenameContourArgs {
+                id: data.id,
+                name: child_data.name,
+            }));
+        }
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() && !ctx.is_handled() => {
+                ctx.submit_command(consts::cmd::SELECT_CONTOUR.with(SelectContourArgs {
+                    id: data.id,
+                    toggle: mouse.mods.shift(),
+                }));
+            }
+            _ => (),
+        }
+    }
+}
+
+fn build_widget() -> impl Widget<EditorState> {
+    let contours = EditorState::session.map(
+        |session: &Arc<EditSession>| session.contour_list(),
+        |_, _| (),
+    );
+    let list = Scroll::new(List::new(contour_row))
+        .vertical()
+        .lens(contours)
+        .padding(4.0);
+
+    Either::new(
+        |data: &EditorState, _| data.font.show_contour_list && !data.session.paths.is_empty(),
+        list,
+        SizedBox::empty(),
+    )
+}
+
+fn contour_row() -> impl Widget<ContourInfo> {
+    let visibility = icon_button(
+        |c| if c.hidden { "\u{25CB}" } else { "\u{25CF}" },
+        |c| consts::cmd::TOGGLE_CONTOUR_HIDDEN.with(c.id),
+    );
+
+    let name = EditableLabel::new(
+        |name: &String, _: &_| name.clone(),
+        |s| Some(s.trim().to_owned()),
+    )
+    .with_placeholder("Unnamed")
+    .with_font(theme::UI_DETAIL_FONT)
+    .lens(ContourInfo::name)
+    .fix_width(NAME_WIDTH);
+
+    let details = Label::dynamic(|c: &ContourInfo, _| {
+        let direction = match c.clockwise {
+            _ if !c.closed => "open",
+            Some(true) => "cw",
+            Some(false) => "ccw",
+            None => "closed",
+        };
+        format!("{} pt {}", c.point_count, direction)
+    })
+    .with_font(theme::UI_DETAIL_FONT)
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .fix_width(DETAIL_WIDTH);
+
+    let lock = icon_button(
+        |c| if c.locked { "\u{1F512}" } else { "\u{1F513}" },
+        |c| consts::cmd::TOGGLE_CONTOUR_LOCKED.with(c.id),
+    );
+    let up = icon_button(
+        |_| "\u{25B4}",
+        |c| consts::cmd::MOVE_CONTOUR.with((c.id, false)),
+    );
+    let down = icon_button(
+        |_| "\u{25BE}",
+        |c| consts::cmd::MOVE_CONTOUR.with((c.id, true)),
+    );
+
+    let selected = Painter::new(|ctx, c: &ContourInfo, env| {
+        if c.selected {
+            let rect = ctx.size().to_rect().to_rounded_rect(3.0);
+            ctx.fill(rect, &env.get(theme::FOCUS_BACKGROUND_COLOR));
+        }
+    });
+
+    Flex::row()
+        .with_child(visibility)
+        .with_child(name)
+        .with_spacer(4.0)
+        .with_child(details)
+        .with_child(lock)
+        .with_child(up)
+        .with_child(down)
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .padding((2.0, 1.0))
+        .background(selected)
+        .controller(ContourRow)
+}
+
+/// A small clickable label, which submits the command made by `cmd`.
+fn icon_button(
+    text: impl Fn(&ContourInfo) -> &'static str + 'static,
+    cmd: impl Fn(&ContourInfo) -> druid::Command + 'static,
+) -> impl Widget<ContourInfo> {
+    Label::dynamic(move |c: &ContourInfo, _| text(c).to_string())
+        .with_font(theme::UI_DETAIL_FONT)
+        .center()
+        .fix_width(ICON_WIDTH)
+        .on_click(move |ctx, c: &mut ContourInfo, _| ctx.submit_command(cmd(c)))
+}
@@ -1,23 +1,41 @@
 //! Controller widgets
 
+use std::sync::Arc;
+
 use druid::widget::prelude::*;
+use druid::widget::{CrossAxisAlignment, Flex};
 use druid::{InternalLifeCycle, LensExt, Rect, WidgetExt, WidgetPod};
 
 use crate::consts;
 use crate::data::EditorState;
 use crate::edit_session::EditSession;
-use crate::widgets::{CoordPane, FloatingPanel, GlyphPane, Toolbar};
+use crate::widgets::status_bar::STATUS_BAR_HEIGHT;
+use crate::widgets::{
+    component_cycle_banner, locked_banner, selected_points_list, AlignPane, ContourList, CoordPane,
+    FloatingPanel, GlyphPane, StatusBar, ToolOptionsBar, Toolbar,
+};
 
 /// the distance from the edge of a floating panel to the edge of the window.
 const FLOATING_PANEL_PADDING: f64 = 24.0;
 
+/// The space between the toolbar and the tool options below it.
+const TOOL_OPTIONS_SPACING: f64 = 8.0;
+
+/// The tallest the contour list grows before it scrolls.
+const CONTOUR_LIST_MAX_HEIGHT: f64 = 320.0;
+
 /// More like this is 'Editor' and 'Editor' is 'Canvas'?
 //TODO: we could combine this with controller above if we wanted?
 pub struct EditorController<W> {
     inner: W,
     toolbar: WidgetPod<(), FloatingPanel<Toolbar>>,
+    tool_options: WidgetPod<EditorState, FloatingPanel<Box<dyn Widget<EditorState>>>>,
     coord_panel: WidgetPod<EditorState, FloatingPanel<Box<dyn Widget<EditorState>>>>,
     glyph_panel: WidgetPod<EditorState, FloatingPanel<Box<dyn Widget<EditorState>>>>,
+    align_panel: WidgetPod<EditorState, FloatingPanel<Box<dyn Widget<EditorState>>>>,
+    contour_panel: WidgetPod<EditorState, FloatingPanel<Box<dyn Widget<EditorState>>>>,
+    locked_banner: WidgetPod<EditorState, FloatingPanel<Box<dyn Widget<EditorState>>>>,
+    status_bar: WidgetPod<EditorState, StatusBar>,
 }
 
 impl<W> EditorController<W> {
@@ -25,12 +43,30 @@ impl<W> EditorController<W> {
         EditorController {
             inner,
             toolbar: WidgetPod::new(FloatingPanel::new(Toolbar::default())),
+            tool_options: WidgetPod::new(FloatingPanel::new(ToolOptionsBar::new().boxed())),
             coord_panel: WidgetPod::new(FloatingPanel::new(
-                CoordPane::new()
-                    .lens(EditorState::session.then(EditSession::selected_coord.in_arc()))
+                Flex::column()
+                    .with_child(selected_points_list().lens(EditorState::session.map(
+                        |session: &Arc<EditSession>| session.selected_path_points(),
+                        |_, _| (),
+                    )))
+                    .with_child(
+                        CoordPane::new()
+                            .lens(EditorState::session.then(EditSession::selected_coord.in_arc())),
+                    )
+                    .cross_axis_alignment(CrossAxisAlignment::End)
                     .boxed(),
             )),
             glyph_panel: WidgetPod::new(FloatingPanel::new(GlyphPane::new().boxed())),
+            align_panel: WidgetPod::new(FloatingPanel::new(AlignPane::new().boxed())),
+            contour_panel: WidgetPod::new(FloatingPanel::new(ContourList::new().boxed())),
+            locked_banner: WidgetPod::new(FloatingPanel::new(
+                Flex::column()
+                    .with_child(locked_banner())
+                    .with_child(component_cycle_banner())
+                    .boxed(),
+            )),
+            status_bar: WidgetPod::new(StatusBar::default()),
         }
     }
 }
@@ -48,8 +84,13 @@ impl<W: Widget<EditorState>> Widget<EditorState> for EditorController<W> {
             }
         }
         self.toolbar.event(ctx, event, &mut (), env);
+        self.tool_options.event(ctx, event, data, env);
         self.coord_panel.event(ctx, event, data, env);
         self.glyph_panel.event(ctx, event, data, env);
+        self.align_panel.event(ctx, event, data, env);
+        self.contour_panel.event(ctx, event, data, env);
+        self.locked_banner.event(ctx, event, data, env);
+        self.status_bar.event(ctx, event, data, env);
         if !ctx.is_handled() {
             self.inner.event(ctx, event, data, env);
         }
@@ -70,8 +111,13 @@ impl<W: Widget<EditorState>> Widget<EditorState> for EditorController<W> {
             ctx.submit_command(crate::consts::cmd::TAKE_FOCUS);
         }
         self.toolbar.lifecycle(ctx, event, &(), env);
+        self.tool_options.lifecycle(ctx, event, data, env);
         self.coord_panel.lifecycle(ctx, event, data, env);
         self.glyph_panel.lifecycle(ctx, event, data, env);
+        self.align_panel.lifecycle(ctx, event, data, env);
+        self.contour_panel.lifecycle(ctx, event, data, env);
+        self.locked_banner.lifecycle(ctx, event, data, env);
+        self.status_bar.lifecycle(ctx, event, data, env);
         self.inner.lifecycle(ctx, event, data, env);
     }
 
@@ -82,8 +128,13 @@ impl<W: Widget<EditorState>> Widget<EditorState> for EditorController<W> {
         data: &EditorState,
         env: &Env,
     ) {
+        self.tool_options.update(ctx, data, env);
         self.coord_panel.update(ctx, data, env);
         self.glyph_panel.update(ctx, data, env);
+        self.align_panel.update(ctx, data, env);
+        self.contour_panel.update(ctx, data, env);
+        self.locked_banner.update(ctx, data, env);
+        self.status_bar.update(ctx, data, env);
         self.inner.update(ctx, old_data, data, env);
     }
 
@@ -97,9 +148,31 @@ impl<W: Widget<EditorState>> Widget<EditorState> for EditorController<W> {
         let child_bc = bc.loosen();
         let size = self.toolbar.layout(ctx, &child_bc, &(), env);
         let orig = (FLOATING_PANEL_PADDING, FLOATING_PANEL_PADDING);
-        self.toolbar
-            .set_layout_rect(ctx, &(), env, Rect::from_origin_size(orig, size));
-        let our_size = self.inner.layout(ctx, bc, data, env);
+        let toolbar_frame = Rect::from_origin_size(orig, size);
+        self.toolbar.set_layout_rect(ctx, &(), env, toolbar_frame);
+
+        // the options of the selected tool go just below the toolbar
+        let size = self.tool_options.layout(ctx, &child_bc, data, env);
+        let orig = (
+            FLOATING_PANEL_PADDING,
+            toolbar_frame.max_y() + TOOL_OPTIONS_SPACING,
+        );
+        let frame = Rect::from_origin_size(orig, size);
+        self.tool_options.set_layout_rect(ctx, data, env, frame);
+        // the status bar goes below the canvas, and the floating panels
+        // are placed relative to the canvas
+        let max = bc.max();
+        let inner_bc = BoxConstraints::tight(Size::new(
+            max.width,
+            (max.height - STATUS_BAR_HEIGHT).max(0.0),
+        ));
+        let our_size = self.inner.layout(ctx, &inner_bc, data, env);
+        let status_bc = BoxConstraints::tight(Size::new(our_size.width, STATUS_BAR_HEIGHT));
+        let status_size = self.status_bar.layout(ctx, &status_bc, data, env);
+        let status_frame = Rect::from_origin_size((0.0, our_size.height), status_size);
+        self.status_bar
+            .set_layout_rect(ctx, data, env, status_frame);
+
         let coords_size = self.coord_panel.layout(ctx, &child_bc, data, env);
         let coords_origin = (
             (our_size.width) - coords_size.width - FLOATING_PANEL_PADDING,
@@ -116,13 +189,46 @@ impl<W: Widget<EditorState>> Widget<EditorState> for EditorController<W> {
         );
         let frame = Rect::from_origin_size(orig, size);
         self.glyph_panel.set_layout_rect(ctx, data, env, frame);
-        our_size
+
+        let size = self.align_panel.layout(ctx, &child_bc, data, env);
+        let orig = (
+            our_size.width - size.width - FLOATING_PANEL_PADDING,
+            FLOATING_PANEL_PADDING,
+        );
+        let frame = Rect::from_origin_size(orig, size);
+        self.align_panel.set_layout_rect(ctx, data, env, frame);
+
+        // the contour list goes below the align panel, which is often empty
+        let top = match size.height > 0.0 {
+            true => frame.max_y() + FLOATING_PANEL_PADDING,
+            false => frame.max_y(),
+        };
+        let max_height = (our_size.height - top - FLOATING_PANEL_PADDING).max(0.0);
+        let contour_bc = BoxConstraints::new(
+            Size::ZERO,
+            Size::new(max.width, max_height.min(CONTOUR_LIST_MAX_HEIGHT)),
+        );
+        let size = self.contour_panel.layout(ctx, &contour_bc, data, env);
+        let orig = (our_size.width - size.width - FLOATING_PANEL_PADDING, top);
+        let frame = Rect::from_origin_size(orig, size);
+        self.contour_panel.set_layout_rect(ctx, data, env, frame);
+
+        let size = self.locked_banner.layout(ctx, &child_bc, data, env);
+        let orig = ((our_size.width - size.width) / 2.0, FLOATING_PANEL_PADDING);
+        let frame = Rect::from_origin_size(orig, size);
+        self.locked_banner.set_layout_rect(ctx, data, env, frame);
+        Size::new(our_size.width, our_size.height + status_size.height)
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &EditorState, env: &Env) {
         self.inner.paint(ctx, data, env);
         self.coord_panel.paint(ctx, data, env);
         self.glyph_panel.paint(ctx, data, env);
+        self.align_panel.paint(ctx, data, env);
+        self.contour_panel.paint(ctx, data, env);
+        self.locked_banner.paint(ctx, data, env);
+        self.status_bar.paint(ctx, data, env);
         self.toolbar.paint(ctx, &(), env);
+        self.tool_options.paint(ctx, data, env);
     }
 }
@@ -0,0 +1,49 @@
+//! A panel asking before a glyph with quadratic contours is opened.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment};
+use druid::{Color, WidgetExt};
+use norad::GlyphName;
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// A panel asking whether to open the glyph `name`, whose quadratic
+/// (TrueType) contours must be converted to cubics to be edited.
+pub fn convert_quadratic_panel(name: GlyphName) -> impl Widget<Workspace> {
+    let explanation = Label::new(
+        "Its quadratic (TrueType) contours will be converted to cubic curves for \
+         editing, and back to quadratics when saved. Points may move slightly.",
+    )
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let open_name = name.clone();
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_flex_spacer(1.0)
+        .with_child(Button::new("Convert and Open").on_click(move |ctx, _, _| {
+            ctx.submit_command(consts::cmd::CONVERT_AND_EDIT_GLYPH.with(open_name.clone()));
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+        }))
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(Label::new(format!("Convert '{}' to cubic curves?", name)).with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(400.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
@@ -1,15 +1,23 @@
 //! The floating panel that displays the coordinate of the currently
 //! selected point.
 
-use druid::kurbo::Circle;
-use druid::widget::{prelude::*, Controller, CrossAxisAlignment, Either, Flex, Label, SizedBox};
-use druid::{Color, FontDescriptor, FontFamily, Point, WidgetExt};
+use std::sync::Arc;
+
+use druid::kurbo::{Circle, Vec2};
+use druid::widget::{
+    prelude::*, Controller, CrossAxisAlignment, Either, Flex, Label, List, SizedBox,
+};
+use druid::{lens, Color, FontDescriptor, FontFamily, Point, WidgetExt};
 
 use crate::design_space::{DPoint, DVec2};
 use crate::edit_session::CoordinateSelection;
+use crate::point::PathPoint;
 use crate::quadrant::Quadrant;
 use crate::widgets::EditableLabel;
-use crate::{consts, theme, util};
+use crate::{consts, menus, theme, util};
+
+/// The most selected points we list individually.
+const MAX_LISTED_POINTS: usize = 12;
 
 /// A panel for editing the selected coordinate
 pub struct CoordPane;
@@ -39,13 +47,14 @@ impl<W: Widget<CoordinateSelection>> Controller<CoordinateSelection, W> for Coor
         // a command so that the Editor can update undo state:
         if child_data.frame.origin() != data.frame.origin() {
             let delta = child_data.frame.origin() - data.frame.origin();
-            ctx.submit_command(consts::cmd::NUDGE_SELECTION.with(DVec2::from_raw(delta)));
+            let delta = DVec2::from_raw(delta, data.precision);
+            ctx.submit_command(consts::cmd::NUDGE_SELECTION.with(delta));
         } else if child_data.frame.size() != data.frame.size() {
             let scale = util::compute_scale(data.frame.size(), child_data.frame.size());
             let scale_origin = child_data.quadrant.point_in_dspace_rect(data.frame);
             let args = consts::cmd::ScaleSelectionArgs {
                 scale,
-                origin: DPoint::from_raw(scale_origin),
+                origin: DPoint::from_raw(scale_origin, data.precision),
             };
             ctx.submit_command(consts::cmd::SCALE_SELECTION.with(args));
         }
@@ -57,6 +66,30 @@ impl<W: Widget<CoordinateSelection>> Controller<CoordinateSelection, W> for Coor
     }
 }
 
+/// Shows the point type dropdown when an on-curve point's type is clicked.
+struct PointTypeDropdown;
+
+impl<W: Widget<PathPoint>> Controller<PathPoint, W> for PointTypeDropdown {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut PathPoint,
+        env: &Env,
+    ) {
+        match event {
+            Event::MouseDown(mouse) if mouse.button.is_left() && data.is_on_curve() => {
+                let menu = menus::make_point_type_menu(data.id, data.is_smooth());
+                let below = ctx.window_origin() + Vec2::new(0.0, ctx.size().height);
+                ctx.show_context_menu(menu, below);
+                ctx.set_handled();
+            }
+            _ => child.event(ctx, event, data, env),
+        }
+    }
+}
+
 /// A widget for picking how to represent a multi-point selection.
 struct CoordRepresentationPicker;
 
@@ -220,3 +253,65 @@ fn build_widget() -> impl Widget<CoordinateSelection> {
     // if we have any points selected, show the numerical adjust widget, else an empty widget
     Either::new(|d, _| d.count != 0, picker_and_editor, SizedBox::empty())
 }
+
+/// A list of the selected points, with the type and position of each.
+///
+/// This is only shown when more than one point is selected.
+pub fn selected_points_list() -> impl Widget<Arc<Vec<PathPoint>>> {
+    let listed = lens::Map::new(
+        |points: &Arc<Vec<PathPoint>>| {
+            Arc::new(
+                points
+                    .iter()
+                    .take(MAX_LISTED_POINTS)
+                    .copied()
+                    .collect::<Vec<_>>(),
+            )
+        },
+        |_, _| (),
+    );
+    let overflow = Label::dynamic(|points: &Arc<Vec<PathPoint>>, _| {
+        match points.len().saturating_sub(MAX_LISTED_POINTS) {
+            0 => String::new(),
+            n => format!("and {} more", n),
+        }
+    })
+    .with_font(theme::UI_DETAIL_FONT)
+    .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let list = Flex::column()
+        .with_child(List::new(point_row).lens(listed))
+        .with_child(overflow)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .padding((8.0, 8.0, 8.0, 0.0));
+
+    Either::new(
+        |d: &Arc<Vec<PathPoint>>, _| d.len() > 1,
+        list,
+        SizedBox::empty(),
+    )
+}
+
+fn point_row() -> impl Widget<PathPoint> {
+    let point_type = Label::dynamic(|pt: &PathPoint, _| {
+        if pt.is_off_curve() {
+            "off-curve".to_string()
+        } else if pt.is_smooth() {
+            "smooth \u{25BE}".to_string()
+        } else {
+            "corner \u{25BE}".to_string()
+        }
+    })
+    .with_font(theme::UI_DETAIL_FONT)
+    .controller(PointTypeDropdown)
+    .fix_width(72.0);
+
+    let coords = Label::dynamic(|pt: &PathPoint, _| format!("{}, {}", pt.point.x, pt.point.y))
+        .with_font(FontDescriptor::new(FontFamily::MONOSPACE))
+        .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    Flex::row()
+        .with_child(point_type)
+        .with_child(coords)
+        .cross_axis_alignment(CrossAxisAlignment::Baseline)
+}
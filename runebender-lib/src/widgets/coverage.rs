@@ -0,0 +1,145 @@
+//! A window reporting which of the characters needed by a set of languages,
+//! or listed in a character set file, the font supports.
+
+use std::sync::Arc;
+
+use druid::lens;
+use druid::widget::prelude::*;
+use druid::widget::{Button, Checkbox, CrossAxisAlignment, Flex, Label, List, Scroll};
+use druid::{commands, Color, FileDialogOptions, FileSpec, WidgetExt};
+
+use crate::app_delegate::EDIT_GLYPH;
+use crate::charset;
+use crate::consts;
+use crate::coverage::{CoverageEntry, CoverageStatus, LANGUAGES};
+use crate::data::Workspace;
+use crate::theme;
+
+const CHARSET_FILE_TYPE: FileSpec = FileSpec::new("Character Set", &["txt"]);
+
+/// The number of language checkboxes in each row.
+const LANGUAGES_PER_ROW: usize = 4;
+
+pub fn coverage_panel() -> impl Widget<Workspace> {
+    let mut languages = Flex::column().cross_axis_alignment(CrossAxisAlignment::Start);
+    for row in LANGUAGES.chunks(LANGUAGES_PER_ROW) {
+        let mut checkboxes = Flex::row();
+        for language in row {
+            checkboxes.add_child(language_checkbox(language.name).fix_width(100.0));
+        }
+        languages.add_child(checkboxes);
+    }
+
+    let check_buttons = Flex::row()
+        .with_child(
+            Button::new("Check Languages").on_click(|_, data: &mut Workspace, _| {
+                if !data.coverage.languages.is_empty() {
+                    data.check_language_coverage();
+                }
+            }),
+        )
+        .with_spacer(4.0)
+        .with_child(Button::new("Check File…").on_click(|ctx, _, _| {
+            ctx.submit_command(
+                commands::SHOW_OPEN_PANEL.with(
+                    FileDialogOptions::new()
+                        .allowed_types(vec![CHARSET_FILE_TYPE])
+                        .accept_command(consts::cmd::CHECK_COVERAGE_FILE),
+                ),
+            )
+        }));
+
+    let summary = Label::dynamic(|data: &Workspace, _| {
+        let report = match data.coverage.report.as_ref() {
+            Some(report) => report,
+            None => return "Choose languages or a character set file to check.".into(),
+        };
+        format!(
+            "{}: {} missing, {} empty, {} complete",
+            report.target,
+            report.count(CoverageStatus::Missing),
+            report.count(CoverageStatus::Empty),
+            report.count(CoverageStatus::Complete)
+        )
+    })
+    .with_text_color(theme::PRIMARY_TEXT_COLOR);
+
+    let report_buttons = Flex::row()
+        .with_child(
+            Button::new("Add Missing Glyphs").on_click(|_, data: &mut Workspace, _| {
+                data.add_missing_glyphs();
+            }),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Check Again")
+                .on_click(|_, data: &mut Workspace, _| data.refresh_coverage()),
+        );
+
+    let header = Flex::column()
+        .with_child(languages)
+        .with_default_spacer()
+        .with_child(check_buttons)
+        .with_default_spacer()
+        .with_child(summary)
+        .with_spacer(4.0)
+        .with_child(report_buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .padding(8.0);
+
+    // clicking a character opens its glyph, if it has one
+    let entries = List::new(|| {
+        Label::dynamic(|entry: &CoverageEntry, _| {
+            format!(
+                "{}  U+{:04X}  {}: {}",
+                charset::display_char(entry.chr),
+                entry.chr as u32,
+                entry.glyph.as_deref().unwrap_or("no glyph"),
+                entry.status.description()
+            )
+        })
+        .with_text_color(theme::PRIMARY_TEXT_COLOR)
+        .padding((8.0, 4.0))
+        .expand_width()
+        .on_click(|ctx, entry: &mut CoverageEntry, _| {
+            if let Some(name) = entry.glyph.clone() {
+                ctx.submit_command(EDIT_GLYPH.with(name))
+            }
+        })
+    })
+    .lens(lens::Map::new(
+        |data: &Workspace| {
+            data.coverage
+                .report
+                .as_ref()
+                .map(|report| report.entries.clone())
+                .unwrap_or_default()
+        },
+        |_, _| (),
+    ));
+
+    Flex::column()
+        .with_child(header)
+        .with_flex_child(Scroll::new(entries).vertical().expand(), 1.0)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .background(Color::WHITE)
+}
+
+/// A checkbox that adds the language `name` to, or removes it from, the
+/// languages to check.
+fn language_checkbox(name: &'static str) -> impl Widget<Workspace> {
+    Checkbox::new(name).lens(lens::Map::new(
+        move |data: &Workspace| data.coverage.languages.contains(name),
+        move |data: &mut Workspace, checked| {
+            if checked == data.coverage.languages.contains(name) {
+                return;
+            }
+            let languages = Arc::make_mut(&mut data.coverage.languages);
+            if checked {
+                languages.insert(name);
+            } else {
+                languages.remove(name);
+            }
+        },
+    ))
+}
@@ -0,0 +1,108 @@
+//! A panel asking what to do with the components based on a glyph that is
+//! about to be deleted.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment};
+use druid::{Color, WidgetExt};
+
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The most glyphs we list by name.
+const MAX_LISTED_USERS: usize = 6;
+
+fn describe_users(data: &Workspace) -> String {
+    let users = match data.selected.as_ref() {
+        Some(name) => data.component_users(name),
+        None => return String::new(),
+    };
+    let mut listed = users
+        .iter()
+        .take(MAX_LISTED_USERS)
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    if users.len() > MAX_LISTED_USERS {
+        listed.push(format!("and {} more", users.len() - MAX_LISTED_USERS));
+    }
+    let glyphs = match users.len() {
+        1 => "1 glyph".to_string(),
+        n => format!("{} glyphs", n),
+    };
+    let mut description = format!(
+        "It is a component of {}: {}. Deleting it leaves them with missing components, \
+         unless they are decomposed first.",
+        glyphs,
+        listed.join(", ")
+    );
+    let locked = users
+        .iter()
+        .filter(|name| data.is_glyph_locked(name))
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    if !locked.is_empty() {
+        description.push_str(&format!(
+            " Locked glyphs can't be decomposed: {}.",
+            locked.join(", ")
+        ));
+    }
+    description
+}
+
+pub fn delete_glyph_panel() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &Workspace, _| match data.selected.as_ref() {
+        Some(name) => format!("Delete '{}'?", name),
+        None => "No glyph selected".into(),
+    })
+    .with_text_size(16.0);
+    let explanation = Label::dynamic(|data: &Workspace, _| describe_users(data))
+        .with_text_color(theme::SECONDARY_TEXT_COLOR)
+        .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_flex_spacer(1.0)
+        .with_child(
+            Button::new("Delete Anyway").on_click(|ctx, data: &mut Workspace, _| {
+                data.delete_selected_glyph();
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Decompose and Delete").on_click(|ctx, data: &mut Workspace, _| {
+                if let Some(name) = data.selected.clone() {
+                    let locked = data.decompose_component_uses(&name);
+                    if locked.is_empty() {
+                        data.delete_selected_glyph();
+                    } else {
+                        let names = locked.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+                        log::warn!(
+                            "not deleting '{}'; skipped locked glyphs: {}",
+                            name,
+                            names.join(", ")
+                        );
+                    }
+                }
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(400.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
@@ -0,0 +1,128 @@
+//! A panel for choosing the name of a copy of a glyph.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Scope, TextBox};
+use druid::{Color, Lens, WidgetExt};
+use norad::GlyphName;
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// Suffixes often given to alternate forms of a glyph.
+const SUFFIXES: &[&str] = &[".alt", ".sc", ".ss01", ".case"];
+
+/// The state of the panel: the workspace, plus the name of the copy.
+#[derive(Clone, Data, Lens)]
+struct DuplicateState {
+    workspace: Workspace,
+    new_name: String,
+}
+
+impl DuplicateState {
+    /// The reason the copy can't have the chosen name, if there is one.
+    fn problem(&self) -> Option<&'static str> {
+        let name = self.new_name.trim();
+        if name.is_empty() {
+            Some("The copy needs a name.")
+        } else if self.workspace.font.ufo.get_glyph(name).is_some() {
+            Some("A glyph with this name already exists.")
+        } else {
+            None
+        }
+    }
+
+    /// Name the copy `name` plus `suffix`, numbered if that name is taken.
+    fn use_suffix(&mut self, name: &str, suffix: &str) {
+        let new_name = format!("{}{}", name, suffix);
+        self.new_name = if self.workspace.font.ufo.get_glyph(&new_name).is_some() {
+            self.workspace.unused_glyph_name(&new_name).to_string()
+        } else {
+            new_name
+        };
+    }
+}
+
+/// A panel for copying the glyph `name` to a new name; the first unused
+/// numbered name, like `a.001`, is suggested.
+pub fn duplicate_glyph_panel(name: GlyphName) -> impl Widget<Workspace> {
+    let mut suffixes = Flex::row().with_child(suffix_button(name.clone(), "Numbered", None));
+    for suffix in SUFFIXES {
+        suffixes.add_spacer(4.0);
+        suffixes.add_child(suffix_button(name.clone(), suffix, Some(*suffix)));
+    }
+
+    let field = TextBox::new().expand_width().lens(DuplicateState::new_name);
+
+    let problem =
+        Label::dynamic(|data: &DuplicateState, _| data.problem().unwrap_or_default().to_string())
+            .with_text_color(theme::PROBLEM_TEXT_COLOR);
+
+    let duplicate_name = name.clone();
+    let buttons = Flex::row()
+        .with_flex_child(problem.expand_width(), 1.0)
+        .with_default_spacer()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Duplicate").on_click(move |ctx, data: &mut DuplicateState, _| {
+                if data.problem().is_none() {
+                    let args = consts::cmd::DuplicateGlyphArgs {
+                        name: duplicate_name.clone(),
+                        new_name: data.new_name.trim().into(),
+                    };
+                    ctx.submit_command(consts::cmd::DUPLICATE_GLYPH.with(args));
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(Label::new(format!("Duplicate '{}'", name)).with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(field)
+        .with_spacer(4.0)
+        .with_child(
+            Label::new("Suffix:")
+                .with_text_color(theme::SECONDARY_TEXT_COLOR)
+                .with_text_size(12.0),
+        )
+        .with_spacer(4.0)
+        .with_child(suffixes)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(400.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        move |workspace: Workspace| DuplicateState {
+            new_name: workspace.unused_glyph_name(&name).to_string(),
+            workspace,
+        },
+        DuplicateState::workspace,
+        panel,
+    )
+}
+
+/// A button that names the copy of `name` with `suffix`, or with the next
+/// number if `suffix` is `None`.
+fn suffix_button(
+    name: GlyphName,
+    title: &'static str,
+    suffix: Option<&'static str>,
+) -> impl Widget<DuplicateState> {
+    Button::new(title).on_click(move |_, data: &mut DuplicateState, _| match suffix {
+        Some(suffix) => data.use_suffix(&name, suffix),
+        None => data.new_name = data.workspace.unused_glyph_name(&name).to_string(),
+    })
+}
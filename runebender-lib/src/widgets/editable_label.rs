@@ -16,9 +16,18 @@
 //!
 //! This is a bit hacky, and depends on implementation details of other widgets.
 
+use druid::piet::{FontFamily, Text, TextLayoutBuilder};
 use druid::widget::prelude::*;
 use druid::widget::{LabelText, TextBox};
-use druid::{Color, Data, FontDescriptor, HotKey, KbKey, KeyOrValue, Selector, TextAlignment};
+use druid::{
+    Color, Data, FontDescriptor, HotKey, Insets, KbKey, KeyOrValue, Point, Selector, TextAlignment,
+};
+
+use crate::theme;
+
+const HINT_FONT_SIZE: f64 = 11.0;
+/// The space reserved below the label for the hint text.
+const HINT_HEIGHT: f64 = 16.0;
 
 // we send this to ourselves if another widget takes focus, in order
 // to validate and move out of editing mode
@@ -40,6 +49,10 @@ pub struct EditableLabel<T> {
     editing: bool,
     text_box: TextBox<String>,
     on_completion: Box<dyn Fn(&str) -> Option<T>>,
+    /// Suggests a replacement for the buffer when tab is pressed.
+    autocomplete: Option<Box<dyn Fn(&str) -> Option<String>>>,
+    /// Describes the contents of the buffer while editing.
+    hint: Option<Box<dyn Fn(&str) -> String>>,
 }
 
 impl<T: Data + std::fmt::Display + std::str::FromStr> EditableLabel<T> {
@@ -72,9 +85,27 @@ impl<T: Data> EditableLabel<T> {
             text_box: TextBox::new(),
             editing: false,
             on_completion: Box::new(on_completion),
+            autocomplete: None,
+            hint: None,
         }
     }
 
+    /// Builder-style method to set a function used to complete the text
+    /// when the user presses <tab> while editing.
+    ///
+    /// If the function returns `None`, the text is unchanged.
+    pub fn with_autocomplete(mut self, f: impl Fn(&str) -> Option<String> + 'static) -> Self {
+        self.autocomplete = Some(Box::new(f));
+        self
+    }
+
+    /// Builder-style method to set a function that describes the current
+    /// text; this is drawn below the label while editing.
+    pub fn with_hint(mut self, f: impl Fn(&str) -> String + 'static) -> Self {
+        self.hint = Some(Box::new(f));
+        self
+    }
+
     /// Builder-style method to set the placeholder text.
     pub fn with_placeholder(mut self, text: impl Into<String>) -> Self {
         self.text_box = self.text_box.with_placeholder(text);
@@ -155,6 +186,17 @@ impl<T: Data> Widget<T> for EditableLabel<T> {
                     ctx.set_handled();
                     self.cancel(ctx);
                 }
+                Event::KeyDown(k_e)
+                    if self.autocomplete.is_some()
+                        && HotKey::new(None, KbKey::Tab).matches(k_e) =>
+                {
+                    ctx.set_handled();
+                    let autocomplete = self.autocomplete.as_ref().unwrap();
+                    if let Some(completed) = autocomplete(&self.buffer) {
+                        self.old_buffer = std::mem::replace(&mut self.buffer, completed);
+                    }
+                    ctx.request_paint();
+                }
                 event => {
                     self.text_box.event(ctx, event, &mut self.buffer, env);
                     ctx.request_paint();
@@ -216,7 +258,11 @@ impl<T: Data> Widget<T> for EditableLabel<T> {
     }
 
     fn layout(&mut self, ctx: &mut LayoutCtx, bc: &BoxConstraints, _data: &T, env: &Env) -> Size {
-        self.text_box.layout(ctx, bc, &self.buffer, env)
+        let size = self.text_box.layout(ctx, bc, &self.buffer, env);
+        if self.hint.is_some() {
+            ctx.set_paint_insets(Insets::new(0.0, 0.0, 0.0, HINT_HEIGHT));
+        }
+        size
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, _data: &T, env: &Env) {
@@ -226,5 +272,18 @@ impl<T: Data> Widget<T> for EditableLabel<T> {
         //} else {
         self.text_box.paint(ctx, &self.buffer, env);
         //}
+
+        if let (true, Some(hint)) = (self.editing, self.hint.as_ref()) {
+            let text = hint(&self.buffer);
+            let origin = Point::new(0.0, ctx.size().height + 2.0);
+            let layout = ctx
+                .text()
+                .new_text_layout(text)
+                .font(FontFamily::SYSTEM_UI, HINT_FONT_SIZE)
+                .text_color(env.get(theme::SECONDARY_TEXT_COLOR))
+                .build()
+                .unwrap();
+            ctx.draw_text(&layout, origin);
+        }
     }
 }
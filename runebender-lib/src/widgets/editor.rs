@@ -2,14 +2,23 @@
 
 use std::sync::Arc;
 
+use druid::kurbo::{BezPath, Rect, Shape};
 use druid::widget::prelude::*;
-use druid::{Application, Clipboard, ClipboardFormat, Command, Data, KbKey};
+use druid::{
+    Application, Clipboard, ClipboardFormat, Command, Cursor, Data, KbKey, Modifiers, MouseButton,
+};
+use norad::GlyphName;
 
+use crate::anchor_preview;
 use crate::consts::{self, CANVAS_SIZE};
-use crate::data::EditorState;
-use crate::draw;
+use crate::data::{EditorState, FontMetrics, MetricLine, PasteOffset, UndoLabels};
+use crate::design_space::{self, DPoint, DVec2, Precision};
+use crate::draw::{self, PaintCache, ViewMode};
 use crate::edit_session::EditSession;
 use crate::mouse::{Mouse, TaggedEvent};
+use crate::path::Path;
+use crate::raster::{self, Bitmap};
+use crate::reference_font::ReferenceFont;
 use crate::theme;
 use crate::tools::{EditType, Preview, Select, Tool};
 use crate::undo::UndoState;
@@ -20,11 +29,58 @@ pub struct Editor {
     tool: Box<dyn Tool>,
     /// Set only if we're temporarily in preview mode. (when spacebar is held)
     temp_preview: Option<Box<dyn Tool>>,
-    undo: UndoState<Arc<EditSession>>,
+    /// If true, the temporary preview is because a pen's barrel button is
+    /// held, and ends when it is released.
+    barrel_preview: bool,
+    undo: UndoState<UndoEntry>,
     last_edit: EditType,
     /// If true, this session should be drawn with all glyphs filled and
     /// with no non-glyph items visible.
     draw_filled_outlines: bool,
+    /// How the session is drawn when not previewing; this is kept for as
+    /// long as the window is open, whichever tool is active.
+    view_mode: ViewMode,
+    /// The metric line currently being dragged, if any.
+    metric_drag: Option<MetricLine>,
+    /// If true, dragged points snap to the font's alignment zones.
+    snap_to_zones: bool,
+    /// The last position of the mouse over the canvas, in design space.
+    last_mouse: Option<DPoint>,
+    /// The clipboard data that was last pasted, and the number of times
+    /// in a row it has been pasted; each paste is offset from the last.
+    last_paste: Option<(Vec<u8>, usize)>,
+    /// If set, the glyph is also drawn rasterized at this size, in pixels
+    /// per em.
+    pixel_preview: Option<f64>,
+    /// The outline and pixel size that were last rasterized, and the result.
+    pixel_cache: Option<(BezPath, f64, Option<Bitmap>)>,
+    /// The reference font and glyph whose outline was last drawn, and that
+    /// outline.
+    reference_cache: Option<(Arc<ReferenceFont>, GlyphName, Option<BezPath>)>,
+    /// A glyph to draw attached to this one by their anchors.
+    anchor_preview: Option<GlyphName>,
+    /// Outlines and labels from the last paint that may be reused.
+    paint_cache: PaintCache,
+}
+
+/// A state of the editor that undo and redo return to.
+#[derive(Clone)]
+struct UndoEntry {
+    session: Arc<EditSession>,
+    /// The font's metrics, if a metric line was dragged just before or
+    /// after this state; other changes to the metrics aren't undone here.
+    metrics: Option<FontMetrics>,
+}
+
+/// Where pasted paths are placed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PasteMode {
+    /// Offset from the copied paths, further with each repeated paste.
+    Repeat,
+    /// Exactly where the paths were copied from.
+    InPlace,
+    /// Centered on the mouse.
+    AtMouse,
 }
 
 impl Editor {
@@ -33,9 +89,80 @@ impl Editor {
             mouse: Mouse::default(),
             tool: Box::new(Select::default()),
             temp_preview: None,
-            undo: UndoState::new(session),
+            barrel_preview: false,
+            undo: UndoState::new(UndoEntry {
+                session,
+                metrics: None,
+            }),
             last_edit: EditType::Normal,
             draw_filled_outlines: false,
+            view_mode: ViewMode::default(),
+            metric_drag: None,
+            snap_to_zones: false,
+            last_mouse: None,
+            last_paste: None,
+            pixel_preview: None,
+            pixel_cache: None,
+            reference_cache: None,
+            anchor_preview: None,
+            paint_cache: PaintCache::default(),
+        }
+    }
+
+    /// Handle dragging the vertical metric lines.
+    ///
+    /// This only starts if the metric drag modifier is held, so that lines
+    /// aren't moved by accident. Returns `true` if the event was handled.
+    fn metric_drag_event(
+        &mut self,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut EditorState,
+    ) -> bool {
+        match event {
+            Event::MouseDown(m) if m.button.is_left() && is_metric_drag_modifier(&m.mods) => {
+                let session = &data.session;
+                let hit = draw::hit_test_metric_lines(
+                    session.viewport,
+                    &session.glyph,
+                    &data.metrics,
+                    m.pos,
+                );
+                if hit.is_some() {
+                    // so that undoing the drag can return to these metrics
+                    let metrics = data.metrics.clone();
+                    self.undo
+                        .update_current_undo(|entry| entry.metrics = Some(metrics.clone()));
+                    self.metric_drag = hit;
+                    ctx.set_active(true);
+                    ctx.set_cursor(&Cursor::ResizeUpDown);
+                }
+                hit.is_some()
+            }
+            Event::MouseMove(m) if self.metric_drag.is_some() => {
+                let line = self.metric_drag.unwrap();
+                let session = &data.session;
+                let y = (session.viewport.inverse_affine() * m.pos).y;
+                let value = design_space::round_coord(y, session.precision);
+                data.metrics.set_value(line, value);
+                self.update_undo(
+                    Some(EditType::Drag),
+                    "Move Metric Line",
+                    &data.session,
+                    Some(&data.metrics),
+                );
+                data.set_undo_labels(self.undo_labels());
+                true
+            }
+            Event::MouseUp(_) if self.metric_drag.is_some() => {
+                // the next drag starts a new undo group
+                self.last_edit = EditType::DragUp;
+                self.metric_drag = None;
+                ctx.set_active(false);
+                ctx.set_cursor(&self.tool.default_cursor());
+                true
+            }
+            _ => false,
         }
     }
 
@@ -55,8 +182,48 @@ impl Editor {
                 _ => (),
             };
 
+            // a pen's barrel button is reported as the middle button, and
+            // pans while it is held, like the space bar
+            let mut edit = None;
+            if let TaggedEvent::Down(m) = &event {
+                if m.button == MouseButton::Middle
+                    && self.temp_preview.is_none()
+                    && self.tool.name() != "Preview"
+                {
+                    self.barrel_preview = true;
+                    edit = self.toggle_temporary_preview(ctx, data, true);
+                }
+            }
+            let barrel_released = self.barrel_preview
+                && matches!(&event, TaggedEvent::Up(m) if m.button == MouseButton::Middle);
+
+            let zones = if self.snap_to_zones {
+                Some(Arc::new(data.font.info.postscript.zones()))
+            } else {
+                None
+            };
+            let upm = data.upm();
+            let nib = data.font.nib;
+            let tool_options = data.font.tool_options;
+            let show_tunni_lines = data.font.show_tunni_lines;
+            let show_loupe = data.font.show_loupe;
+            let click_distance = env.get(theme::CLICK_DISTANCE);
+            let session = data.session_mut();
+            session.snap_zones = zones;
+            session.show_tunni_lines = show_tunni_lines;
+            session.show_loupe = show_loupe;
+            session.units_per_em = upm;
+            session.base_click_distance = click_distance;
+            session.nib = nib;
+            session.tool_options = tool_options;
+
             let tool = self.temp_preview.as_mut().unwrap_or(&mut self.tool);
-            return tool.mouse_event(event, &mut self.mouse, ctx, data.session_mut(), env);
+            let tool_edit = tool.mouse_event(event, &mut self.mouse, ctx, data.session_mut(), env);
+            if barrel_released {
+                self.barrel_preview = false;
+                self.toggle_temporary_preview(ctx, data, false);
+            }
+            return edit.or(tool_edit);
         } else if let TaggedEvent::Down(m) = event {
             let menu = crate::menus::make_context_menu(data, m.pos);
             ctx.show_context_menu(menu, m.pos);
@@ -64,12 +231,29 @@ impl Editor {
         None
     }
 
-    fn update_undo(&mut self, edit: Option<EditType>, data: &Arc<EditSession>) {
+    /// Record an edit for undo; `metrics` is set if the edit moved one of
+    /// the font's metric lines.
+    fn update_undo(
+        &mut self,
+        edit: Option<EditType>,
+        description: &str,
+        data: &Arc<EditSession>,
+        metrics: Option<&FontMetrics>,
+    ) {
         match edit {
             Some(edit) if self.last_edit.needs_new_undo_group(edit) => {
-                self.undo.add_undo_group(data.clone())
+                let entry = UndoEntry {
+                    session: data.clone(),
+                    metrics: metrics.cloned(),
+                };
+                self.undo.add_undo_group(entry, description)
             }
-            Some(_) => self.undo.update_current_undo(|state| *state = data.clone()),
+            Some(_) => self.undo.update_current_undo(|state| {
+                state.session = data.clone();
+                if let Some(metrics) = metrics {
+                    state.metrics = Some(metrics.clone());
+                }
+            }),
             // I'm not sure what to do here? I wanted to check if selections had
             // changed, and then update the current undo if necessary?
             // but that requires us to pass in the previous data. We can do that!
@@ -79,15 +263,37 @@ impl Editor {
         self.last_edit = edit.unwrap_or(self.last_edit);
     }
 
-    fn do_undo(&mut self) -> Option<&Arc<EditSession>> {
+    /// What an edit made in response to `event` is called in the Undo and
+    /// Redo menu items.
+    fn edit_description(&self, event: &Event, edit: Option<EditType>) -> &'static str {
+        match event {
+            Event::Command(cmd) => command_description(cmd),
+            Event::Paste(_) => "Paste",
+            Event::KeyDown(k) if k.key == KbKey::Backspace || k.key == KbKey::Delete => "Delete",
+            _ => edit
+                .and_then(EditType::description)
+                .unwrap_or_else(|| self.tool.edit_description()),
+        }
+    }
+
+    fn undo_labels(&self) -> UndoLabels {
+        UndoLabels {
+            undo: self.undo.undo_description().map(String::from),
+            redo: self.undo.redo_description().map(String::from),
+        }
+    }
+
+    fn do_undo(&mut self) -> Option<&UndoEntry> {
         self.undo.undo()
     }
 
-    fn do_redo(&mut self) -> Option<&Arc<EditSession>> {
+    fn do_redo(&mut self) -> Option<&UndoEntry> {
         self.undo.redo()
     }
 
-    fn do_copy(&self, data: &EditSession) {
+    fn do_copy(&mut self, data: &EditSession) {
+        // the next paste is the first of the new contents
+        self.last_paste = None;
         let mut formats = Vec::new();
         if let Some(data) = crate::clipboard::make_json(data) {
             formats.push(ClipboardFormat::new(
@@ -119,38 +325,44 @@ impl Editor {
         }
     }
 
-    fn do_paste(&self, session: &mut EditSession, clipboard: &Clipboard) -> Option<EditType> {
-        let paste_types = [
-            crate::consts::RUNEBENDER_PASTEBOARD_TYPE,
-            crate::consts::GLYPHS_APP_PASTEBOARD_TYPE,
-            ClipboardFormat::PDF,
-            ClipboardFormat::SVG,
-        ];
-        if let Some(match_) = clipboard.preferred_format(&paste_types) {
-            let paths = match (match_, clipboard.get_format(match_)) {
-                (_, None) => {
-                    log::warn!("no data returned for declared clipboard format {}", match_);
-                    return None;
-                }
-                (crate::consts::RUNEBENDER_PASTEBOARD_TYPE, Some(data)) => String::from_utf8(data)
-                    .ok()
-                    .and_then(|s| crate::clipboard::from_json(&s)),
-                (crate::consts::GLYPHS_APP_PASTEBOARD_TYPE, Some(data)) => {
-                    match String::from_utf8(data) {
-                        Ok(s) => crate::clipboard::from_glyphs_plist_string(s),
-                        Err(e) => crate::clipboard::from_glyphs_plist(e.into_bytes()),
-                    }
+    fn do_paste(
+        &mut self,
+        data: &mut EditorState,
+        clipboard: &Clipboard,
+        mode: PasteMode,
+    ) -> Option<EditType> {
+        let (raw, mut paths) = paths_from_clipboard(clipboard, data.session.precision)?;
+        let offset = match mode {
+            PasteMode::Repeat => {
+                let count = match self.last_paste.take() {
+                    Some((last, count)) if last == raw => count + 1,
+                    _ => 1,
+                };
+                self.last_paste = Some((raw, count));
+                let PasteOffset { x, y } = data.font.paste_offset;
+                DVec2::from_raw((x * count as f64, y * count as f64), data.session.precision)
+            }
+            PasteMode::InPlace => DVec2::ZERO,
+            PasteMode::AtMouse => match self.last_mouse {
+                Some(mouse) => {
+                    let bounds = paths.iter().map(|path| path.bezier().bounding_box()).fold(
+                        None,
+                        |acc: Option<Rect>, rect| {
+                            Some(acc.map(|acc| acc.union(rect)).unwrap_or(rect))
+                        },
+                    )?;
+                    mouse - DPoint::from_raw(bounds.center(), data.session.precision)
                 }
-                (ClipboardFormat::PDF, Some(data)) => crate::clipboard::from_pdf_data(data),
-                _ => None,
-            };
-            if let Some(paths) = paths {
-                session.paste_paths(paths);
-                return Some(EditType::Normal);
+                None => DVec2::ZERO,
+            },
+        };
+        if offset != DVec2::ZERO {
+            for path in paths.iter_mut() {
+                path.nudge_all_points(offset);
             }
         }
-
-        None
+        data.session_mut().paste_paths(paths);
+        Some(EditType::Normal)
     }
 
     /// handle a `Command`. Returns a bool indicating whether the command was
@@ -160,6 +372,10 @@ impl Editor {
         match cmd {
             c if c.is(consts::cmd::SELECT_ALL) => data.session_mut().select_all(),
             c if c.is(consts::cmd::DESELECT_ALL) => data.session_mut().selection.clear(),
+            c if c.is(consts::cmd::SELECT_ALIGNED_POINTS) => {
+                let vertical = *c.get_unchecked(consts::cmd::SELECT_ALIGNED_POINTS);
+                data.session_mut().select_aligned_points(vertical);
+            }
             c if c.is(consts::cmd::DELETE) => data.session_mut().delete_selection(),
             c if c.is(consts::cmd::ADD_GUIDE) => {
                 let point = cmd.get_unchecked(consts::cmd::ADD_GUIDE);
@@ -172,24 +388,79 @@ impl Editor {
                 data.session_mut().toggle_guide(*id, *pos);
                 return (true, Some(EditType::Normal));
             }
+            c if c.is(consts::cmd::EDIT_GUIDE) => {
+                let consts::cmd::EditGuideArgs { id, edit } =
+                    c.get_unchecked(consts::cmd::EDIT_GUIDE);
+                if !data.session.guides.iter().any(|g| g.id == *id) {
+                    return (false, None);
+                }
+                let changed = data.session_mut().edit_guide(*id, edit);
+                return (true, changed.then(|| EditType::Normal));
+            }
             c if c.is(druid::commands::COPY) => self.do_copy(&data.session),
+            c if c.is(consts::cmd::PASTE_IN_PLACE) => {
+                let clipboard = Application::global().clipboard();
+                let edit = self.do_paste(data, &clipboard, PasteMode::InPlace);
+                return (true, edit);
+            }
+            c if c.is(consts::cmd::PASTE_AT_MOUSE) => {
+                let clipboard = Application::global().clipboard();
+                let edit = self.do_paste(data, &clipboard, PasteMode::AtMouse);
+                return (true, edit);
+            }
+            c if c.is(consts::cmd::COPY_COORDINATES) => {
+                let format = *c.get_unchecked(consts::cmd::COPY_COORDINATES);
+                if let Some(text) = crate::clipboard::make_coordinate_text(&data.session, format) {
+                    Application::global().clipboard().put_string(text);
+                }
+            }
+            c if c.is(consts::cmd::PASTE_COORDINATES) => {
+                let text = Application::global().clipboard().get_string();
+                let precision = data.session.precision;
+                match text
+                    .as_deref()
+                    .and_then(|text| crate::clipboard::path_from_coordinate_text(text, precision))
+                {
+                    Some(path) => {
+                        data.session_mut().paste_paths(vec![path]);
+                        return (true, Some(EditType::Normal));
+                    }
+                    None => log::warn!("no coordinates on the clipboard"),
+                }
+            }
             c if c.is(druid::commands::UNDO) => {
+                let leaving = self.undo.current().metrics.is_some();
                 if let Some(prev) = self.do_undo() {
-                    //HACK: because zoom & offset is part of data, and we don't
-                    //want to jump around during undo/redo, we always manually
-                    //reuse the current viewport when handling these actions.
-                    let saved_viewport = data.session.viewport;
-                    data.session = prev.clone();
-                    data.session_mut().viewport = saved_viewport;
+                    restore_undo_entry(data, prev, leaving);
                 }
             }
             c if c.is(druid::commands::REDO) => {
+                let leaving = self.undo.current().metrics.is_some();
                 if let Some(next) = self.do_redo() {
-                    let saved_viewport = data.session.viewport;
-                    data.session = next.clone();
-                    data.session_mut().viewport = saved_viewport;
+                    restore_undo_entry(data, next, leaving);
                 }
             }
+            c if c.is(consts::cmd::FLIP_HORIZONTAL) => {
+                data.session_mut().flip_selection(true);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::FLIP_VERTICAL) => {
+                data.session_mut().flip_selection(false);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::TRANSFORM_AGAIN) => {
+                let transformed = data.session_mut().transform_again(false);
+                return (true, transformed.then(|| EditType::Normal));
+            }
+            c if c.is(consts::cmd::DUPLICATE_AND_TRANSFORM_AGAIN) => {
+                let transformed = data.session_mut().transform_again(true);
+                return (true, transformed.then(|| EditType::Normal));
+            }
+            c if c.is(consts::cmd::ALIGN_POINTS) => {
+                let alignment = c.get_unchecked(consts::cmd::ALIGN_POINTS);
+                data.session_mut().align_points(*alignment);
+                return (true, Some(EditType::Normal));
+            }
             c if c.is(consts::cmd::ALIGN_SELECTION) => {
                 data.session_mut().align_selection();
                 return (true, Some(EditType::Normal));
@@ -215,6 +486,154 @@ impl Editor {
                 data.session_mut().reverse_contours();
                 return (true, Some(EditType::Normal));
             }
+            c if c.is(consts::cmd::SET_POINT_TYPE) => {
+                let args = c.get_unchecked(consts::cmd::SET_POINT_TYPE);
+                if data.session_mut().set_point_smooth(args.id, args.smooth) {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::SET_START_POINT) => {
+                let id = *c.get_unchecked(consts::cmd::SET_START_POINT);
+                if data.session_mut().set_start_point(id) {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::DELETE_ITEM) => {
+                let id = *c.get_unchecked(consts::cmd::DELETE_ITEM);
+                data.session_mut().delete_item(id);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::ADD_POINT_ON_SEGMENT) => {
+                let args = c.get_unchecked(consts::cmd::ADD_POINT_ON_SEGMENT);
+                if let Some(seg) = data.session.segment_starting_at(args.start) {
+                    if data
+                        .session_mut()
+                        .insert_point_on_segment(seg, args.t)
+                        .is_some()
+                    {
+                        return (true, Some(EditType::Normal));
+                    }
+                }
+            }
+            c if c.is(consts::cmd::SET_SEGMENT_TYPE) => {
+                let args = c.get_unchecked(consts::cmd::SET_SEGMENT_TYPE);
+                if data
+                    .session_mut()
+                    .set_segment_is_line(args.start, args.line)
+                {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::REVERSE_CONTOUR) => {
+                let id = *c.get_unchecked(consts::cmd::REVERSE_CONTOUR);
+                data.session_mut().reverse_contour(id);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::SET_COMPONENT_PARAMS) => {
+                let args = c.get_unchecked(consts::cmd::SET_COMPONENT_PARAMS);
+                if data
+                    .session_mut()
+                    .set_component_params(args.id, args.params)
+                {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::SELECT_CONTOUR) => {
+                let args = c.get_unchecked(consts::cmd::SELECT_CONTOUR);
+                data.session_mut().select_contour(args.id, args.toggle);
+            }
+            c if c.is(consts::cmd::RENAME_CONTOUR) => {
+                let args = c.get_unchecked(consts::cmd::RENAME_CONTOUR);
+                data.session_mut().rename_contour(args.id, &args.name);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::MOVE_CONTOUR) => {
+                let (id, forward) = *c.get_unchecked(consts::cmd::MOVE_CONTOUR);
+                if data.session_mut().move_contour(id, forward) {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::TOGGLE_CONTOUR_HIDDEN) => {
+                let id = *c.get_unchecked(consts::cmd::TOGGLE_CONTOUR_HIDDEN);
+                data.session_mut().toggle_contour_hidden(id);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::TOGGLE_CONTOUR_LOCKED) => {
+                let id = *c.get_unchecked(consts::cmd::TOGGLE_CONTOUR_LOCKED);
+                data.session_mut().toggle_contour_locked(id);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::JOIN_CONTOURS) => {
+                if data.session_mut().join_selected_endpoints() {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::BREAK_CONTOURS) => {
+                if data.session_mut().break_selected_contours() {
+                    return (true, Some(EditType::Normal));
+                }
+            }
+            c if c.is(consts::cmd::TOGGLE_LOCK_SELECTION) => {
+                data.session_mut().toggle_lock_selection();
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::LOCK_CONTOURS) => {
+                data.session_mut().lock_selected_contours();
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::UNLOCK_ALL) => {
+                data.session_mut().unlock_all();
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::HARMONIZE_HANDLES) => {
+                data.session_mut().harmonize_selection();
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::BALANCE_HANDLES) => {
+                data.session_mut().balance_selection();
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::SIMPLIFY_SELECTION) => {
+                let tolerance = c.get_unchecked(consts::cmd::SIMPLIFY_SELECTION);
+                data.session_mut().simplify_selection(*tolerance);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::EXPAND_STROKE) => {
+                let nib = data.font.nib;
+                let expanded = data.session_mut().expand_stroke(&nib);
+                return (true, expanded.then(|| EditType::Normal));
+            }
+            c if c.is(consts::cmd::SAVE_SNAPSHOT) => {
+                let name = c.get_unchecked(consts::cmd::SAVE_SNAPSHOT);
+                data.session_mut().save_snapshot(name.clone());
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::RESTORE_SNAPSHOT) => {
+                let index = c.get_unchecked(consts::cmd::RESTORE_SNAPSHOT);
+                data.session_mut().restore_snapshot(*index);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::DELETE_SNAPSHOT) => {
+                let index = c.get_unchecked(consts::cmd::DELETE_SNAPSHOT);
+                data.session_mut().delete_snapshot(*index);
+                return (true, Some(EditType::Normal));
+            }
+            c if c.is(consts::cmd::TOGGLE_ZONE_SNAPPING) => {
+                self.snap_to_zones = !self.snap_to_zones;
+                return (true, None);
+            }
+            c if c.is(consts::cmd::SET_VIEW_MODE) => {
+                self.view_mode = *c.get_unchecked(consts::cmd::SET_VIEW_MODE);
+                return (true, None);
+            }
+            c if c.is(consts::cmd::SET_PIXEL_PREVIEW) => {
+                self.pixel_preview = *c.get_unchecked(consts::cmd::SET_PIXEL_PREVIEW);
+                return (true, None);
+            }
+            c if c.is(consts::cmd::SET_ANCHOR_PREVIEW) => {
+                self.anchor_preview = c.get_unchecked(consts::cmd::SET_ANCHOR_PREVIEW).clone();
+                return (true, None);
+            }
             // all unhandled commands:
             _ => return (false, None),
         }
@@ -223,6 +642,29 @@ impl Editor {
         (true, None)
     }
 
+    /// The outline of this glyph in the reference font, if there is one.
+    fn reference_outline(&mut self, data: &EditorState) -> Option<&BezPath> {
+        let font = match data.font.reference_font.as_ref() {
+            Some(font) => font,
+            None => {
+                self.reference_cache = None;
+                return None;
+            }
+        };
+        let name = &data.session.name;
+        let stale = match &self.reference_cache {
+            Some((prev_font, prev_name, _)) => !Arc::ptr_eq(prev_font, font) || prev_name != name,
+            None => true,
+        };
+        if stale {
+            let outline = font.outline_for(&data.session.glyph, data.upm());
+            self.reference_cache = Some((font.clone(), name.clone(), outline));
+        }
+        self.reference_cache
+            .as_ref()
+            .and_then(|(_, _, outline)| outline.as_ref())
+    }
+
     fn set_tool(&mut self, tool: Box<dyn Tool>) {
         self.draw_filled_outlines = tool.name() == "Preview";
         self.tool = tool;
@@ -257,6 +699,28 @@ impl Widget<EditorState> for Editor {
         let rect = (CANVAS_SIZE * data.session.viewport.zoom).to_rect();
         ctx.fill(rect, &env.get(theme::GLYPH_LIST_BACKGROUND));
 
+        if let Some(outline) = self.reference_outline(data) {
+            draw::draw_reference_outline(ctx, data.session.viewport, outline);
+        }
+
+        if let Some(name) = self.anchor_preview.as_ref() {
+            // both glyphs come from the font, so that moving an anchor in
+            // either is reflected immediately.
+            let ufo = &data.font.font.ufo;
+            let attachment = ufo
+                .get_glyph(&data.session.name)
+                .zip(ufo.get_glyph(name))
+                .and_then(|(glyph, other)| anchor_preview::attachment(glyph, other));
+            if let Some((attachment, outline)) = attachment.zip(data.font.get_bezier(name)) {
+                draw::draw_attached_outline(
+                    ctx,
+                    data.session.viewport,
+                    &outline,
+                    attachment.offset,
+                );
+            }
+        }
+
         draw::draw_session(
             ctx,
             env,
@@ -265,9 +729,45 @@ impl Widget<EditorState> for Editor {
             &data.metrics,
             &data.session,
             &data.font,
-            self.draw_filled_outlines,
+            match self.draw_filled_outlines {
+                true => ViewMode::Filled,
+                false => self.view_mode,
+            },
+            &mut self.paint_cache,
         );
 
+        if let Some(line) = self.metric_drag {
+            draw::draw_active_metric(
+                ctx,
+                env,
+                data.session.viewport,
+                &data.session.glyph,
+                &data.metrics,
+                line,
+            );
+        }
+
+        if let Some(ppem) = self.pixel_preview {
+            let pixel_size = data.upm() / ppem;
+            let outline = glyph_outline(data);
+            let stale = match &self.pixel_cache {
+                Some((prev, prev_size, _)) => *prev != outline || !prev_size.same(&pixel_size),
+                None => true,
+            };
+            if stale {
+                let bitmap = raster::rasterize(&outline, pixel_size);
+                self.pixel_cache = Some((outline, pixel_size, bitmap));
+            }
+            let bitmap = self.pixel_cache.as_ref().and_then(|(_, _, b)| b.as_ref());
+            draw::draw_pixel_preview(
+                ctx,
+                data.session.viewport,
+                bitmap,
+                pixel_size,
+                data.layout_bounds(),
+            );
+        }
+
         self.tool.paint(ctx, &data.session, env);
     }
 
@@ -286,6 +786,20 @@ impl Widget<EditorState> for Editor {
         let pre_selection = data.session.selection.clone();
         let pre_paths = data.session.paths.clone();
         let pre_components = data.session.components.clone();
+        let pre_guides = data.session.guides.clone();
+        let pre_glyph = data.session.glyph.clone();
+        let pre_generation = data.session.generation();
+        let read_only = data.session.is_read_only();
+        // the precision can be changed from the menu while the glyph is open
+        if data.session.precision != data.font.precision {
+            data.session_mut().precision = data.font.precision;
+        }
+
+        if !read_only && self.metric_drag_event(ctx, event, data) {
+            ctx.set_handled();
+            ctx.request_paint();
+            return;
+        }
 
         let edit = match event {
             Event::WindowConnected => {
@@ -297,10 +811,12 @@ impl Widget<EditorState> for Editor {
                     ctx.request_focus();
                     ctx.set_handled();
                     None
-                } else if let Some(tool) = cmd.get(consts::cmd::SET_TOOL) {
-                    let tool = crate::tools::tool_for_id(tool).unwrap();
+                } else if let Some(id) = cmd.get(consts::cmd::SET_TOOL) {
+                    let tool = crate::tools::tool_for_id(id).unwrap();
                     ctx.set_cursor(&tool.default_cursor());
                     self.set_tool(tool);
+                    // not the tool's name, which is the same for both pens
+                    data.remember_tool(*id);
                     None
                 } else if let Some(flag) = cmd.get(consts::cmd::TOGGLE_PREVIEW_TOOL) {
                     // we don't toggle preview if we're actually *in* preview
@@ -322,26 +838,79 @@ impl Widget<EditorState> for Editor {
                 data.session_mut().selection.clear();
                 None
             }
-            Event::KeyDown(k) => self.tool.key_down(k, ctx, data.session_mut(), env),
+            Event::KeyDown(k) => {
+                let nudge_amounts = data.font.nudge_amounts;
+                let keyboard_editing = data.font.keyboard_editing;
+                let session = data.session_mut();
+                session.nudge_amounts = nudge_amounts;
+                session.keyboard_editing = keyboard_editing;
+                self.tool.key_down(k, ctx, session, env)
+            }
             Event::KeyUp(k) => self.tool.key_up(k, ctx, data.session_mut(), env),
             Event::MouseUp(m) => self.send_mouse(ctx, TaggedEvent::Up(m.clone()), data, env),
-            Event::MouseMove(m) => self.send_mouse(ctx, TaggedEvent::Moved(m.clone()), data, env),
+            Event::MouseMove(m) => {
+                let session = &data.session;
+                let pos = session.viewport.from_screen(m.pos, session.precision);
+                self.last_mouse = Some(pos);
+                ctx.submit_command(consts::cmd::CURSOR_MOVED.with(Some(pos)));
+                self.send_mouse(ctx, TaggedEvent::Moved(m.clone()), data, env)
+            }
             Event::MouseDown(m) => self.send_mouse(ctx, TaggedEvent::Down(m.clone()), data, env),
-            Event::Paste(clipboard) => self.do_paste(data.session_mut(), clipboard),
+            Event::Paste(clipboard) => self.do_paste(data, clipboard, PasteMode::Repeat),
             _ => None,
         };
 
-        self.update_undo(edit, &data.session);
+        // a locked glyph can be inspected, and its points selected, but any
+        // edit is reverted straight away.
+        let edit = if read_only
+            && !(pre_generation == data.session.generation()
+                && pre_guides.same(&data.session.guides)
+                && pre_glyph.same(&data.session.glyph))
+        {
+            let session = data.session_mut();
+            session.paths = pre_paths;
+            session.components = pre_components;
+            session.outline_changed();
+            session.guides = pre_guides;
+            session.glyph = pre_glyph;
+            ctx.request_paint();
+            None
+        } else {
+            edit
+        };
+
+        let description = self.edit_description(event, edit);
+        self.update_undo(edit, description, &data.session, None);
+        data.set_undo_labels(self.undo_labels());
         if edit.is_some() || !pre_selection.same(&data.session.selection) {
             ctx.request_paint();
         }
 
-        if !pre_paths.same(&data.session.paths) || !pre_components.same(&data.session.components) {
+        if pre_generation != data.session.generation() {
             data.session_mut().rebuild_glyph();
         }
     }
 
-    fn lifecycle(&mut self, _: &mut LifeCycleCtx, _: &LifeCycle, _: &EditorState, _: &Env) {}
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &EditorState,
+        _: &Env,
+    ) {
+        if let LifeCycle::HotChanged(false) = event {
+            ctx.submit_command(consts::cmd::CURSOR_MOVED.with(None));
+        }
+        if let LifeCycle::WidgetAdded = event {
+            // go back to the tool this glyph was last edited with; this goes
+            // through a command so that the toolbar is updated too.
+            if let Some(memory) = data.remembered() {
+                if memory.tool != self.tool.name() {
+                    ctx.submit_command(consts::cmd::SET_TOOL.with(memory.tool));
+                }
+            }
+        }
+    }
 
     fn update(&mut self, ctx: &mut UpdateCtx, old: &EditorState, new: &EditorState, _env: &Env) {
         if !old.same(new) {
@@ -349,3 +918,134 @@ impl Widget<EditorState> for Editor {
         }
     }
 }
+
+/// Read paths from the clipboard, in the first format we understand,
+/// along with the raw clipboard data they were read from.
+/// The paths on the clipboard, and the data they were read from; points that
+/// aren't in our own format are rounded to `precision`.
+fn paths_from_clipboard(
+    clipboard: &Clipboard,
+    precision: Precision,
+) -> Option<(Vec<u8>, Vec<Path>)> {
+    let paste_types = [
+        crate::consts::RUNEBENDER_PASTEBOARD_TYPE,
+        crate::consts::GLYPHS_APP_PASTEBOARD_TYPE,
+        ClipboardFormat::PDF,
+        ClipboardFormat::SVG,
+    ];
+    let match_ = clipboard.preferred_format(&paste_types)?;
+    let data = match clipboard.get_format(match_) {
+        Some(data) => data,
+        None => {
+            log::warn!("no data returned for declared clipboard format {}", match_);
+            return None;
+        }
+    };
+    let paths = match match_ {
+        crate::consts::RUNEBENDER_PASTEBOARD_TYPE => std::str::from_utf8(&data)
+            .ok()
+            .and_then(crate::clipboard::from_json),
+        crate::consts::GLYPHS_APP_PASTEBOARD_TYPE => match String::from_utf8(data.clone()) {
+            Ok(s) => crate::clipboard::from_glyphs_plist_string(s, precision),
+            Err(e) => crate::clipboard::from_glyphs_plist(e.into_bytes(), precision),
+        },
+        ClipboardFormat::PDF => crate::clipboard::from_pdf_data(data.clone(), precision),
+        _ => None,
+    }?;
+    Some((data, paths))
+}
+
+/// What the edit made by a command is called in the Undo and Redo menu items.
+/// Return to `entry`, a state from the undo stack.
+///
+/// The font's metrics are only restored if a metric line was dragged
+/// between the current state and `entry`, which is the case when both
+/// states recorded them (`leaving` is whether the current state did).
+fn restore_undo_entry(data: &mut EditorState, entry: &UndoEntry, leaving: bool) {
+    //HACK: because zoom & offset is part of data, and we don't
+    //want to jump around during undo/redo, we always manually
+    //reuse the current viewport when handling these actions.
+    //Named selections aren't edits, so they are kept too.
+    let saved_viewport = data.session.viewport;
+    let named_selections = data.session.named_selections.clone();
+    data.session = entry.session.clone();
+    let session = data.session_mut();
+    session.viewport = saved_viewport;
+    session.named_selections = named_selections;
+    if let (true, Some(metrics)) = (leaving, entry.metrics.as_ref()) {
+        data.metrics = metrics.clone();
+    }
+}
+
+fn command_description(cmd: &Command) -> &'static str {
+    match cmd {
+        c if c.is(consts::cmd::DELETE) => "Delete",
+        c if c.is(consts::cmd::ADD_GUIDE) => "Add Guide",
+        c if c.is(consts::cmd::TOGGLE_GUIDE) || c.is(consts::cmd::EDIT_GUIDE) => "Change Guide",
+        c if c.is(consts::cmd::PASTE_IN_PLACE)
+            || c.is(consts::cmd::PASTE_AT_MOUSE)
+            || c.is(consts::cmd::PASTE_COORDINATES) =>
+        {
+            "Paste"
+        }
+        c if c.is(consts::cmd::FLIP_HORIZONTAL) || c.is(consts::cmd::FLIP_VERTICAL) => "Flip",
+        c if c.is(consts::cmd::TRANSFORM_AGAIN)
+            || c.is(consts::cmd::DUPLICATE_AND_TRANSFORM_AGAIN) =>
+        {
+            "Transform Again"
+        }
+        c if c.is(consts::cmd::ALIGN_POINTS) || c.is(consts::cmd::ALIGN_SELECTION) => "Align",
+        c if c.is(consts::cmd::NUDGE_SELECTION) => "Move Points",
+        c if c.is(consts::cmd::SCALE_SELECTION) => "Scale",
+        c if c.is(consts::cmd::ADJUST_SIDEBEARING) => "Adjust Sidebearing",
+        c if c.is(consts::cmd::BALANCE_HANDLES) => "Balance Handles",
+        c if c.is(consts::cmd::HARMONIZE_HANDLES) => "Harmonize",
+        c if c.is(consts::cmd::SET_POINT_TYPE) => "Change Point Type",
+        c if c.is(consts::cmd::SET_START_POINT) => "Set Start Point",
+        c if c.is(consts::cmd::DELETE_ITEM) => "Delete",
+        c if c.is(consts::cmd::ADD_POINT_ON_SEGMENT) => "Add Point",
+        c if c.is(consts::cmd::SET_SEGMENT_TYPE) => "Change Segment Type",
+        c if c.is(consts::cmd::REVERSE_CONTOUR) => "Reverse Contour",
+        c if c.is(consts::cmd::SIMPLIFY_SELECTION) => "Simplify",
+        c if c.is(consts::cmd::EXPAND_STROKE) => "Expand Stroke",
+        c if c.is(consts::cmd::BREAK_CONTOURS) => "Break Contours",
+        c if c.is(consts::cmd::JOIN_CONTOURS) => "Join Contours",
+        c if c.is(consts::cmd::REVERSE_CONTOURS) => "Reverse Contours",
+        c if c.is(consts::cmd::MOVE_CONTOUR) => "Reorder Contours",
+        c if c.is(consts::cmd::RENAME_CONTOUR) => "Rename Contour",
+        c if c.is(consts::cmd::TOGGLE_CONTOUR_HIDDEN) => "Hide Contour",
+        c if c.is(consts::cmd::TOGGLE_LOCK_SELECTION)
+            || c.is(consts::cmd::LOCK_CONTOURS)
+            || c.is(consts::cmd::TOGGLE_CONTOUR_LOCKED) =>
+        {
+            "Lock"
+        }
+        c if c.is(consts::cmd::UNLOCK_ALL) => "Unlock All",
+        c if c.is(consts::cmd::SET_COMPONENT_PARAMS) => "Change Component",
+        c if c.is(consts::cmd::RESTORE_SNAPSHOT) => "Restore Snapshot",
+        _ => "Edit",
+    }
+}
+
+/// The modifier that must be held to drag a metric line: cmd on mac and
+/// ctrl elsewhere.
+fn is_metric_drag_modifier(mods: &Modifiers) -> bool {
+    if cfg!(target_os = "macos") {
+        mods.meta()
+    } else {
+        mods.ctrl()
+    }
+}
+
+/// The glyph's outline, including its components.
+fn glyph_outline(data: &EditorState) -> BezPath {
+    let mut outline = data.session.to_bezier();
+    for comp in data.session.components.iter() {
+        if let Some(bez) = data.font.get_bezier(&comp.base) {
+            let mut bez = (*bez).clone();
+            bez.apply_affine(comp.transform);
+            outline.extend(bez.elements().iter().copied());
+        }
+    }
+    outline
+}
@@ -0,0 +1,363 @@
+//! A container that shows each open edit session as a tab.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use druid::kurbo::{Line, Rect};
+use druid::piet::{PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+use druid::{HotKey, KbKey, KeyEvent, MouseButton, Point, SysMods, WidgetExt, WidgetPod};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::edit_session::{EditSession, SessionId};
+use crate::theme;
+use crate::widgets::{Editor, EditorController, ScrollZoom};
+
+const TAB_BAR_HEIGHT: f64 = 28.0;
+const TAB_PADDING: f64 = 10.0;
+const MAX_TAB_WIDTH: f64 = 180.0;
+const CLOSE_BUTTON_SIZE: f64 = 14.0;
+const DRAG_THRESHOLD: f64 = 4.0;
+const DROP_MARKER_WIDTH: f64 = 2.0;
+
+/// The editor window's contents: a tab bar, and the editor for the
+/// active tab.
+///
+/// Editors for inactive tabs are kept around, so that switching tabs
+/// doesn't lose their undo history or current tool.
+#[derive(Default)]
+pub struct EditorTabs {
+    tabs: Vec<Tab>,
+    drag: Option<TabDrag>,
+}
+
+struct Tab {
+    id: SessionId,
+    editor: WidgetPod<Workspace, Box<dyn Widget<Workspace>>>,
+    label: Option<PietTextLayout>,
+    /// The tab's rect in the tab bar.
+    rect: Rect,
+}
+
+/// A tab being dragged to a new position.
+struct TabDrag {
+    index: usize,
+    start: Point,
+    /// The index the tab will be moved to, once the drag has started.
+    target: Option<usize>,
+}
+
+impl Tab {
+    fn new(session: Arc<EditSession>) -> Self {
+        let id = session.id;
+        let editor = EditorController::new(ScrollZoom::new(Editor::new(session)))
+            .lens(Workspace::editor_state(id))
+            .boxed();
+        Tab {
+            id,
+            editor: WidgetPod::new(editor),
+            label: None,
+            rect: Rect::ZERO,
+        }
+    }
+
+    fn close_button(&self) -> Rect {
+        let center = Point::new(
+            self.rect.x1 - TAB_PADDING / 2.0 - CLOSE_BUTTON_SIZE / 2.0,
+            self.rect.center().y,
+        );
+        Rect::from_center_size(center, (CLOSE_BUTTON_SIZE, CLOSE_BUTTON_SIZE))
+    }
+}
+
+impl EditorTabs {
+    /// Add and remove editors to match the workspace's tabs.
+    ///
+    /// Returns `true` if the tabs changed.
+    fn sync_tabs(&mut self, data: &Workspace) -> bool {
+        if self
+            .tabs
+            .iter()
+            .map(|tab| tab.id)
+            .eq(data.editor_tabs.iter().copied())
+        {
+            return false;
+        }
+        let mut existing = self
+            .tabs
+            .drain(..)
+            .map(|tab| (tab.id, tab))
+            .collect::<HashMap<_, _>>();
+        self.tabs = data
+            .editor_tabs
+            .iter()
+            .filter_map(|id| {
+                existing
+                    .remove(id)
+                    .or_else(|| data.sessions.get(id).cloned().map(Tab::new))
+            })
+            .collect();
+        true
+    }
+
+    fn active_index(&self, data: &Workspace) -> Option<usize> {
+        data.active_tab
+            .and_then(|id| self.tabs.iter().position(|tab| tab.id == id))
+    }
+
+    fn tab_at(&self, pos: Point) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.rect.contains(pos))
+    }
+
+    /// The index a dragged tab would move to if dropped at `x`.
+    fn drop_index(&self, x: f64) -> usize {
+        self.tabs
+            .iter()
+            .position(|tab| x < tab.rect.center().x)
+            .unwrap_or(self.tabs.len())
+    }
+
+    /// Handle clicks and drags in the tab bar; returns `true` if the event
+    /// was handled.
+    fn tab_bar_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace) -> bool {
+        match event {
+            Event::MouseDown(mouse) if mouse.pos.y < TAB_BAR_HEIGHT => {
+                if let Some(index) = self.tab_at(mouse.pos) {
+                    let id = self.tabs[index].id;
+                    if mouse.button == MouseButton::Middle
+                        || self.tabs[index].close_button().contains(mouse.pos)
+                    {
+                        data.close_tab(id);
+                    } else {
+                        data.active_tab = Some(id);
+                        self.drag = Some(TabDrag {
+                            index,
+                            start: mouse.pos,
+                            target: None,
+                        });
+                        ctx.set_active(true);
+                    }
+                }
+                ctx.request_paint();
+                true
+            }
+            Event::MouseMove(mouse) if ctx.is_active() => {
+                let drop_index = self.drop_index(mouse.pos.x);
+                if let Some(drag) = self.drag.as_mut() {
+                    if drag.target.is_some() || (mouse.pos - drag.start).hypot() > DRAG_THRESHOLD {
+                        drag.target = Some(drop_index);
+                        ctx.request_paint();
+                    }
+                }
+                true
+            }
+            Event::MouseUp(_) if ctx.is_active() => {
+                ctx.set_active(false);
+                if let Some(TabDrag {
+                    index,
+                    target: Some(target),
+                    ..
+                }) = self.drag.take()
+                {
+                    // the target is an insertion point, before the tab is removed
+                    let target = if target > index { target - 1 } else { target };
+                    data.move_tab(index, target);
+                }
+                ctx.request_paint();
+                true
+            }
+            Event::KeyDown(key) => {
+                match Self::tab_number(key) {
+                    // cmd+9 always selects the last tab
+                    Some(9) => data.active_tab = data.editor_tabs.last().copied(),
+                    Some(n) => match data.editor_tabs.get(n - 1) {
+                        Some(id) => data.active_tab = Some(*id),
+                        None => return false,
+                    },
+                    None => return false,
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The tab chosen by a key press, from 1 to 9, if it is cmd and a digit
+    /// with no other modifiers.
+    pub(crate) fn tab_number(key: &KeyEvent) -> Option<usize> {
+        if !HotKey::new(SysMods::Cmd, key.key.clone()).matches(key) {
+            return None;
+        }
+        match &key.key {
+            KbKey::Character(c) => c.parse::<usize>().ok().filter(|n| (1..=9).contains(n)),
+            _ => None,
+        }
+    }
+
+    fn paint_tab_bar(&mut self, ctx: &mut PaintCtx, data: &Workspace, env: &Env) {
+        let bar = Rect::new(0.0, 0.0, ctx.size().width, TAB_BAR_HEIGHT);
+        ctx.fill(bar, &env.get(theme::SIDEBAR_BACKGROUND));
+        let edge = Line::new((0.0, bar.y1 - 0.5), (bar.x1, bar.y1 - 0.5));
+        ctx.stroke(edge, &env.get(theme::SIDEBAR_EDGE_STROKE), 1.0);
+
+        let active = self.active_index(data);
+        for (i, tab) in self.tabs.iter().enumerate() {
+            if Some(i) == active {
+                ctx.fill(tab.rect, &env.get(theme::GLYPH_LIST_BACKGROUND));
+            }
+            let separator = Line::new((tab.rect.x1 - 0.5, 4.0), (tab.rect.x1 - 0.5, bar.y1 - 4.0));
+            ctx.stroke(separator, &env.get(theme::SIDEBAR_EDGE_STROKE), 1.0);
+
+            if let Some(label) = tab.label.as_ref() {
+                let height = label.size().height;
+                let origin = (tab.rect.x0 + TAB_PADDING, (TAB_BAR_HEIGHT - height) / 2.0);
+                ctx.with_save(|ctx| {
+                    let clip = tab
+                        .rect
+                        .with_size((tab.close_button().x0 - tab.rect.x0, tab.rect.height()));
+                    ctx.clip(clip);
+                    ctx.draw_text(label, origin);
+                });
+            }
+
+            let close = tab.close_button().inset(-4.0);
+            let color = env.get(theme::SECONDARY_TEXT_COLOR);
+            ctx.stroke(
+                Line::new((close.x0, close.y0), (close.x1, close.y1)),
+                &color,
+                1.0,
+            );
+            ctx.stroke(
+                Line::new((close.x0, close.y1), (close.x1, close.y0)),
+                &color,
+                1.0,
+            );
+        }
+
+        if let Some(target) = self.drag.as_ref().and_then(|drag| drag.target) {
+            let x = self
+                .tabs
+                .get(target)
+                .map(|tab| tab.rect.x0)
+                .or_else(|| self.tabs.last().map(|tab| tab.rect.x1))
+                .unwrap_or_default();
+            let marker = Rect::new(
+                x - DROP_MARKER_WIDTH / 2.0,
+                0.0,
+                x + DROP_MARKER_WIDTH / 2.0,
+                TAB_BAR_HEIGHT,
+            );
+            ctx.fill(marker, &env.get(theme::FOCUS_OUTLINE_COLOR));
+        }
+    }
+}
+
+impl Widget<Workspace> for EditorTabs {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace, env: &Env) {
+        if self.tab_bar_event(ctx, event, data) {
+            ctx.set_handled();
+            return;
+        }
+        if let Some(index) = self.active_index(data) {
+            self.tabs[index].editor.event(ctx, event, data, env);
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &Workspace,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            self.sync_tabs(data);
+        }
+        for tab in self.tabs.iter_mut() {
+            tab.editor.lifecycle(ctx, event, data, env);
+        }
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old_data: &Workspace, data: &Workspace, env: &Env) {
+        if self.sync_tabs(data) {
+            ctx.children_changed();
+            if self.tabs.is_empty() {
+                ctx.submit_command(druid::commands::CLOSE_WINDOW);
+            }
+        }
+        for tab in self.tabs.iter_mut() {
+            tab.editor.update(ctx, data, env);
+        }
+        if old_data.active_tab != data.active_tab {
+            // the newly visible editor should handle keyboard input
+            ctx.submit_command(consts::cmd::TAKE_FOCUS);
+            ctx.request_layout();
+        }
+        let dirty = |data: &Workspace, id| data.sessions.get(&id).map(|s| s.is_dirty());
+        if !old_data.editor_tabs.same(&data.editor_tabs)
+            || old_data.active_glyph() != data.active_glyph()
+            || self
+                .tabs
+                .iter()
+                .any(|tab| dirty(old_data, tab.id) != dirty(data, tab.id))
+        {
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &Workspace,
+        env: &Env,
+    ) -> Size {
+        let size = bc.max();
+        let text_color = env.get(theme::PRIMARY_TEXT_COLOR);
+        let font = env.get(theme::UI_DETAIL_FONT);
+        let mut x = 0.0;
+        for tab in self.tabs.iter_mut() {
+            let name = data
+                .sessions
+                .get(&tab.id)
+                .map(|session| {
+                    if session.is_dirty() {
+                        format!("• {}", session.name)
+                    } else {
+                        session.name.to_string()
+                    }
+                })
+                .unwrap_or_default();
+            let label = ctx
+                .text()
+                .new_text_layout(name)
+                .font(font.family.clone(), font.size)
+                .text_color(text_color.clone())
+                .build()
+                .ok();
+            let label_width = label.as_ref().map(|l| l.size().width).unwrap_or_default();
+            let width = (label_width + TAB_PADDING * 3.0 + CLOSE_BUTTON_SIZE).min(MAX_TAB_WIDTH);
+            tab.rect = Rect::new(x, 0.0, x + width, TAB_BAR_HEIGHT);
+            tab.label = label;
+            x += width;
+        }
+
+        if let Some(index) = self.active_index(data) {
+            let editor_size = Size::new(size.width, (size.height - TAB_BAR_HEIGHT).max(0.0));
+            let editor_bc = BoxConstraints::tight(editor_size);
+            let editor = &mut self.tabs[index].editor;
+            editor.layout(ctx, &editor_bc, data, env);
+            let origin = Point::new(0.0, TAB_BAR_HEIGHT);
+            editor.set_layout_rect(ctx, data, env, Rect::from_origin_size(origin, editor_size));
+        }
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &Workspace, env: &Env) {
+        if let Some(index) = self.active_index(data) {
+            self.tabs[index].editor.paint(ctx, data, env);
+        }
+        self.paint_tab_bar(ctx, data, env);
+    }
+}
@@ -0,0 +1,86 @@
+//! A panel for choosing how a glyph is exported as an image.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, Checkbox, CrossAxisAlignment, Flex, Label, MainAxisAlignment, RadioGroup, TextBox,
+};
+use druid::{commands, Color, FileDialogOptions, FileSpec, LensExt, WidgetExt};
+
+use crate::consts;
+use crate::data::{ImageExportOptions, ImageFormat, Workspace};
+use crate::theme;
+use crate::widgets::ModalHost;
+
+const SVG_FILE_TYPE: FileSpec = FileSpec::new("SVG Image", &["svg"]);
+const PNG_FILE_TYPE: FileSpec = FileSpec::new("PNG Image", &["png"]);
+
+pub fn export_glyph_image() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &Workspace, _| match data.image_export_glyph() {
+        Some(name) => format!("Export '{}'", name),
+        None => "Export Glyph".into(),
+    })
+    .with_text_size(16.0);
+
+    let format = RadioGroup::new(vec![("SVG", ImageFormat::Svg), ("PNG", ImageFormat::Png)])
+        .lens(Workspace::image_export.then(ImageExportOptions::format));
+
+    let size = Flex::row()
+        .with_child(Label::new("Height:"))
+        .with_default_spacer()
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .lens(Workspace::image_export.then(ImageExportOptions::size)),
+        )
+        .with_default_spacer()
+        .with_child(Label::new("px").with_text_color(theme::SECONDARY_TEXT_COLOR));
+
+    let metrics = Checkbox::new("Show metrics")
+        .lens(Workspace::image_export.then(ImageExportOptions::show_metrics));
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Export…").on_click(|ctx, data: &mut Workspace, _| {
+                let name = match data.image_export_glyph() {
+                    Some(name) => name,
+                    None => return,
+                };
+                let format = data.image_export.format;
+                let file_type = match format {
+                    ImageFormat::Svg => SVG_FILE_TYPE,
+                    ImageFormat::Png => PNG_FILE_TYPE,
+                };
+                let options = FileDialogOptions::new()
+                    .allowed_types(vec![file_type])
+                    .default_name(format!("{}.{}", name, format.extension()))
+                    .accept_command(consts::cmd::EXPORT_GLYPH_IMAGE);
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+                ctx.submit_command(commands::SHOW_SAVE_PANEL.with(options));
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(format)
+        .with_default_spacer()
+        .with_child(size)
+        .with_default_spacer()
+        .with_child(metrics)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(300.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
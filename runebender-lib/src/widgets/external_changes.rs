@@ -0,0 +1,73 @@
+//! A panel asking whether to reload glyphs that were changed on disk by
+//! another program.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment};
+use druid::{Color, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The most glyph names to list; the rest are summarized.
+const MAX_LISTED: usize = 40;
+
+pub fn external_changes_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Font Changed on Disk").with_text_size(16.0);
+    let explanation = Label::new(
+        "Another program changed these glyphs. Reloading replaces them with \
+         the versions on disk; glyphs with unsaved edits are kept as they are.",
+    )
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let glyphs = Label::dynamic(|data: &Workspace, _| {
+        let names = match data.external_changes.as_ref() {
+            Some(changes) => &changes.glyphs,
+            None => return String::new(),
+        };
+        let mut text = names
+            .iter()
+            .take(MAX_LISTED)
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if names.len() > MAX_LISTED {
+            text.push_str(&format!(" and {} more", names.len() - MAX_LISTED));
+        }
+        text
+    })
+    .with_text_size(12.0)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Ignore").on_click(|ctx, data: &mut Workspace, _| {
+                data.external_changes = None;
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .with_spacer(4.0)
+        .with_child(Button::new("Reload").on_click(|ctx, _, _| {
+            ctx.submit_command(consts::cmd::RELOAD_EXTERNAL_CHANGES);
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+        }))
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(glyphs)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
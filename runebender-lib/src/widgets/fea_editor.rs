@@ -0,0 +1,93 @@
+//! An editor for the font's OpenType feature code.
+
+use std::sync::Arc;
+
+use druid::text::{Attribute, RichText};
+use druid::widget::prelude::*;
+use druid::widget::{
+    CrossAxisAlignment, Flex, Label, LineBreaking, RawLabel, Scroll, Split, TextBox,
+};
+use druid::{Color, FontDescriptor, FontFamily, WidgetExt};
+
+use crate::data::Workspace;
+use crate::fea::{self, TokenKind};
+use crate::opentype;
+use crate::theme;
+
+const CODE_TEXT_SIZE: f64 = 13.0;
+
+pub fn features_editor() -> impl Widget<Workspace> {
+    let code_font = FontDescriptor::new(FontFamily::MONOSPACE).with_size(CODE_TEXT_SIZE);
+
+    let editor = TextBox::multiline()
+        .with_placeholder("# OpenType feature code")
+        .with_font(code_font.clone())
+        .expand()
+        .lens(Workspace::features);
+
+    let highlighted = Scroll::new(
+        RawLabel::new()
+            .with_font(code_font)
+            .with_line_break_mode(LineBreaking::Overflow)
+            .padding(8.0)
+            .lens(HighlightLens),
+    )
+    .expand();
+
+    let problems = Label::dynamic(|data: &Workspace, _| {
+        let mut problems = fea::check(&data.features)
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>();
+        if problems.is_empty() {
+            // only the names matter here, not the actual glyph ids
+            let glyph_id = |name: &str| data.font.ufo.get_glyph(name).map(|_| 0);
+            problems = opentype::compile_features(&data.features, &glyph_id).warnings;
+        }
+        problems.join("\n")
+    })
+    .with_text_color(theme::PROBLEM_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap)
+    .padding(8.0);
+
+    Flex::column()
+        .with_flex_child(
+            Split::columns(editor, highlighted)
+                .split_point(0.5)
+                .draggable(true),
+            1.0,
+        )
+        .with_child(problems.expand_width())
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .background(Color::WHITE)
+}
+
+/// Builds a highlighted copy of the feature code.
+struct HighlightLens;
+
+impl druid::Lens<Workspace, RichText> for HighlightLens {
+    fn with<V, F: FnOnce(&RichText) -> V>(&self, data: &Workspace, f: F) -> V {
+        f(&highlight(&data.features))
+    }
+
+    fn with_mut<V, F: FnOnce(&mut RichText) -> V>(&self, data: &mut Workspace, f: F) -> V {
+        // this is a read-only view
+        f(&mut highlight(&data.features))
+    }
+}
+
+fn highlight(text: &Arc<String>) -> RichText {
+    let mut rich = RichText::new(text.as_str().into());
+    for token in fea::tokenize(text) {
+        let color = match token.kind {
+            TokenKind::Keyword => theme::FEA_KEYWORD_COLOR,
+            TokenKind::Comment => theme::FEA_COMMENT_COLOR,
+            TokenKind::String => theme::FEA_STRING_COLOR,
+            TokenKind::GlyphClass => theme::FEA_CLASS_COLOR,
+            TokenKind::Number => theme::FEA_NUMBER_COLOR,
+            TokenKind::Punctuation | TokenKind::Name => continue,
+        };
+        rich.add_attribute(token.range, Attribute::text_color(color));
+    }
+    rich
+}
@@ -1,16 +1,28 @@
 //! a widget that uses harfbuzz to preview shaping.
 
-use druid::kurbo::Affine;
+use druid::kurbo::{Affine, Line, Point, Rect, Shape};
+use druid::piet::{FontFamily, Text, TextLayout, TextLayoutBuilder};
 use druid::widget::prelude::*;
-use harfbuzz_rs::{Blob, Face, Font, GlyphBuffer, UnicodeBuffer};
+use harfbuzz_rs::{Blob, Face, Feature, Font, GlyphBuffer, Tag, UnicodeBuffer};
 
-use crate::data::PreviewState;
+use crate::data::{PreviewState, Workspace};
 use crate::theme;
+use crate::util::format_num;
 use crate::virtual_font::{GlyphId, VirtualFont};
 
 const CMAP: [u8; 4] = [b'c', b'm', b'a', b'p'];
 const HHEA: [u8; 4] = [b'h', b'h', b'e', b'a'];
 const HMTX: [u8; 4] = [b'h', b'm', b't', b'x'];
+const MAXP: [u8; 4] = [b'm', b'a', b'x', b'p'];
+const GSUB: [u8; 4] = [b'G', b'S', b'U', b'B'];
+const GPOS: [u8; 4] = [b'G', b'P', b'O', b'S'];
+const GDEF: [u8; 4] = [b'G', b'D', b'E', b'F'];
+
+/// The space below each line for advance and sidebearing values.
+const METRICS_LABEL_HEIGHT: f64 = 32.0;
+const METRICS_LABEL_FONT_SIZE: f64 = 10.0;
+const METRICS_LABEL_PADDING: f64 = 2.0;
+const SIDEBEARING_ALPHA: f64 = 0.4;
 
 #[derive(Debug, Default)]
 pub struct Preview {
@@ -20,10 +32,11 @@ pub struct Preview {
 
 #[derive(Debug, Default)]
 struct Run {
-    // glyphs + advances
-    glyphs: Vec<(GlyphId, i32)>,
-    // the total width of the run in design points
-    width: i32,
+    // glyphs + positions
+    glyphs: Vec<(GlyphId, Point)>,
+    // the total advance of the run in design points; in vertical layout,
+    // this is its height.
+    advance: i32,
 }
 
 impl Run {
@@ -33,10 +46,42 @@ impl Run {
         let mut pos = 0;
         let mut glyphs = Vec::with_capacity(info.len());
         for (info, position) in info.iter().zip(positions.iter()) {
-            glyphs.push((info.codepoint as u16, pos + position.x_offset));
+            let x = pos + position.x_offset;
+            glyphs.push((
+                info.codepoint as u16,
+                Point::new(x as f64, position.y_offset as f64),
+            ));
             pos += position.x_advance;
         }
-        Run { glyphs, width: pos }
+        Run {
+            glyphs,
+            advance: pos,
+        }
+    }
+
+    /// Stack the shaped glyphs top to bottom, using the advance heights in
+    /// the font.
+    ///
+    /// The position of each glyph is the distance from the top of the run to
+    /// the top of the glyph's advance.
+    fn vertical(hb_output: &GlyphBuffer, virtual_font: &VirtualFont, font: &Workspace) -> Self {
+        let metrics = font.metrics();
+        let mut pos = 0.0;
+        let info = hb_output.get_glyph_infos();
+        let mut glyphs = Vec::with_capacity(info.len());
+        for info in info {
+            let id = info.codepoint as u16;
+            glyphs.push((id, Point::new(0.0, pos)));
+            pos += virtual_font
+                .glyph_for_id(id)
+                .and_then(|name| font.font.ufo.get_glyph(name))
+                .map(|glyph| metrics.advance_height(glyph))
+                .unwrap_or(metrics.units_per_em);
+        }
+        Run {
+            glyphs,
+            advance: pos as i32,
+        }
     }
 }
 
@@ -84,12 +129,27 @@ impl Widget<PreviewState> for Preview {
             CMAP => Some(Blob::with_bytes(virtual_font.cmap()).to_shared()),
             HHEA => Some(Blob::with_bytes(virtual_font.hhea()).to_shared()),
             HMTX => Some(Blob::with_bytes(virtual_font.hmtx()).to_shared()),
+            MAXP => Some(Blob::with_bytes(virtual_font.maxp()).to_shared()),
+            GSUB if !virtual_font.gsub().is_empty() => {
+                Some(Blob::with_bytes(virtual_font.gsub()).to_shared())
+            }
+            GPOS if !virtual_font.gpos().is_empty() => {
+                Some(Blob::with_bytes(virtual_font.gpos()).to_shared())
+            }
+            GDEF if !virtual_font.gdef().is_empty() => {
+                Some(Blob::with_bytes(virtual_font.gdef()).to_shared())
+            }
             _ => None,
         });
 
         let mut font = Font::new(face);
         let upm = data.font.units_per_em();
         font.set_ppem(upm as u32, upm as u32);
+        let mut features = parse_features(data.features());
+        if data.vertical() {
+            // use vertical alternates, as a vertical shaper would
+            features.push(Feature::new(Tag::new('v', 'e', 'r', 't'), 1, ..));
+        }
         let mut reuseable_buffer = None;
         self.layout.clear();
         for line in data.text().lines() {
@@ -97,37 +157,247 @@ impl Widget<PreviewState> for Preview {
                 .take()
                 .unwrap_or_else(UnicodeBuffer::new)
                 .add_str(line);
-            let output = harfbuzz_rs::shape(&font, buffer, &[]);
-            self.layout.push(Run::new(&output));
+            let output = harfbuzz_rs::shape(&font, buffer, &features);
+            let run = if data.vertical() {
+                Run::vertical(&output, virtual_font, &data.font)
+            } else {
+                Run::new(&output)
+            };
+            self.layout.push(run);
             reuseable_buffer = Some(output.clear());
         }
-        let width = self
+        let advance = self
             .layout
             .iter()
-            .map(|run| run.width)
+            .map(|run| run.advance)
             .max()
             .unwrap_or_default();
-        bc.constrain((width as f64, bc.max().height))
+        if data.vertical() {
+            let scale = data.font_size() / data.font.units_per_em();
+            let width = self.layout.len() as f64 * column_width(data);
+            bc.constrain((width, advance as f64 * scale))
+        } else {
+            bc.constrain((advance as f64, bc.max().height))
+        }
     }
 
     fn paint(&mut self, ctx: &mut PaintCtx, data: &PreviewState, env: &Env) {
+        if data.vertical() {
+            self.paint_vertical(ctx, data, env);
+            return;
+        }
         let glyph_color = env.get(theme::PRIMARY_TEXT_COLOR);
         let font_size = data.font_size();
         let scale = font_size / data.font.units_per_em();
+        let line_height = if data.show_metrics() {
+            font_size + METRICS_LABEL_HEIGHT
+        } else {
+            font_size
+        };
         for (line_n, run) in self.layout.iter().enumerate() {
-            let y_pos = (line_n + 1) as f64 * font_size;
+            let line_top = line_n as f64 * line_height;
+            let y_pos = line_top + font_size;
             for (glyph, pos) in &run.glyphs {
-                if let Some(bez) = self
-                    .virtual_font
-                    .glyph_for_id(*glyph)
-                    .and_then(|name| data.font.get_bezier(name))
-                {
+                let name = match self.virtual_font.glyph_for_id(*glyph) {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let bez = data.font.get_bezier(name);
+
+                let is_active = data.font.active_glyph() == Some(name);
+                if is_active || data.show_metrics() {
+                    let advance = data
+                        .font
+                        .font
+                        .ufo
+                        .get_glyph(name)
+                        .and_then(|glyph| glyph.advance_width())
+                        .unwrap_or_default() as f64;
+                    let x0 = pos.x * scale;
+                    let cell = Rect::new(x0, line_top, x0 + advance * scale, y_pos);
+                    if is_active {
+                        ctx.fill(cell, &env.get(theme::FOCUS_BACKGROUND_COLOR));
+                    }
+                    if data.show_metrics() {
+                        let bounds = bez.as_ref().map(|bez| bez.bounding_box());
+                        paint_metrics(ctx, cell, scale, advance, bounds, env);
+                    }
+                }
+
+                if let Some(bez) = bez {
                     //FIXME: actually calculate the baseline
                     let transform =
-                        Affine::new([scale, 0., 0., -scale, *pos as f64 * scale, y_pos]);
+                        Affine::new([scale, 0., 0., -scale, pos.x * scale, y_pos - pos.y * scale]);
                     ctx.fill(transform * &*bez, &glyph_color);
                 }
             }
         }
     }
 }
+
+impl Preview {
+    /// Paint each run as a column, with the first column on the right.
+    fn paint_vertical(&mut self, ctx: &mut PaintCtx, data: &PreviewState, env: &Env) {
+        let glyph_color = env.get(theme::PRIMARY_TEXT_COLOR);
+        let scale = data.font_size() / data.font.units_per_em();
+        let metrics = data.font.metrics();
+        let column_width = column_width(data);
+        let right = self.layout.len() as f64 * column_width;
+        for (column_n, run) in self.layout.iter().enumerate() {
+            let column_left = right - (column_n + 1) as f64 * column_width;
+            let column_center = column_left + data.font_size() / 2.0;
+            for (glyph, pos) in &run.glyphs {
+                let name = match self.virtual_font.glyph_for_id(*glyph) {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let ufo_glyph = match data.font.font.ufo.get_glyph(name) {
+                    Some(glyph) => glyph,
+                    None => continue,
+                };
+                let bez = data.font.get_bezier(name);
+                let top = pos.y * scale;
+                let height = metrics.advance_height(ufo_glyph);
+
+                let is_active = data.font.active_glyph() == Some(name);
+                if is_active || data.show_metrics() {
+                    let cell = Rect::new(
+                        column_left,
+                        top,
+                        column_left + data.font_size(),
+                        top + height * scale,
+                    );
+                    if is_active {
+                        ctx.fill(cell, &env.get(theme::FOCUS_BACKGROUND_COLOR));
+                    }
+                    if data.show_metrics() {
+                        paint_vertical_metrics(ctx, cell, height, env);
+                    }
+                }
+
+                if let Some(bez) = bez {
+                    let width = ufo_glyph.advance_width().unwrap_or_default() as f64;
+                    let origin = metrics.vertical_origin(ufo_glyph);
+                    let x = column_center - width * scale / 2.0;
+                    let y = top + origin * scale;
+                    let transform = Affine::new([scale, 0., 0., -scale, x, y]);
+                    ctx.fill(transform * &*bez, &glyph_color);
+                }
+            }
+        }
+    }
+}
+
+/// The width of each column in vertical layout.
+fn column_width(data: &PreviewState) -> f64 {
+    if data.show_metrics() {
+        data.font_size() + METRICS_LABEL_HEIGHT
+    } else {
+        data.font_size()
+    }
+}
+
+/// Draw the top and bottom of a glyph's vertical advance, with its value
+/// to the right.
+fn paint_vertical_metrics(ctx: &mut PaintCtx, cell: Rect, advance: f64, env: &Env) {
+    let color = env.get(theme::METRICS_COLOR);
+    ctx.stroke(
+        Line::new((cell.x0, cell.y0), (cell.x1, cell.y0)),
+        &color,
+        1.0,
+    );
+    ctx.stroke(
+        Line::new((cell.x0, cell.y1), (cell.x1, cell.y1)),
+        &color,
+        1.0,
+    );
+    let layout = ctx
+        .text()
+        .new_text_layout(format_num(advance, 0))
+        .font(FontFamily::SYSTEM_UI, METRICS_LABEL_FONT_SIZE)
+        .text_color(color)
+        .build()
+        .unwrap();
+    let y = cell.center().y - layout.size().height / 2.0;
+    ctx.draw_text(&layout, (cell.x1 + METRICS_LABEL_PADDING, y));
+}
+
+/// Draw the advance and sidebearings of a glyph occupying `cell`, with
+/// their values underneath.
+fn paint_metrics(
+    ctx: &mut PaintCtx,
+    cell: Rect,
+    scale: f64,
+    advance: f64,
+    bounds: Option<Rect>,
+    env: &Env,
+) {
+    let color = env.get(theme::METRICS_COLOR);
+    ctx.stroke(
+        Line::new((cell.x0, cell.y0), (cell.x0, cell.y1)),
+        &color,
+        1.0,
+    );
+    ctx.stroke(
+        Line::new((cell.x1, cell.y0), (cell.x1, cell.y1)),
+        &color,
+        1.0,
+    );
+
+    let mut labels = vec![(format_num(advance, 0), cell.center().x)];
+    if let Some(bounds) = bounds.filter(|b| b.width() > 0.0) {
+        let left = cell.x0 + bounds.x0 * scale;
+        let right = cell.x0 + bounds.x1 * scale;
+        let sidebearing_color = color.clone().with_alpha(SIDEBEARING_ALPHA);
+        ctx.stroke(
+            Line::new((left, cell.y0), (left, cell.y1)),
+            &sidebearing_color,
+            1.0,
+        );
+        ctx.stroke(
+            Line::new((right, cell.y0), (right, cell.y1)),
+            &sidebearing_color,
+            1.0,
+        );
+        labels.push((format_num(bounds.x0, 0), cell.x0));
+        labels.push((format_num(advance - bounds.x1, 0), cell.x1));
+    }
+
+    // the advance is on the first row, the sidebearings on the second
+    for (i, (label, x)) in labels.into_iter().enumerate() {
+        let layout = ctx
+            .text()
+            .new_text_layout(label)
+            .font(FontFamily::SYSTEM_UI, METRICS_LABEL_FONT_SIZE)
+            .text_color(color.clone())
+            .build()
+            .unwrap();
+        let size = layout.size();
+        let x = match i {
+            0 => x - size.width / 2.0,
+            1 => x + METRICS_LABEL_PADDING,
+            _ => x - size.width - METRICS_LABEL_PADDING,
+        };
+        let y = cell.y1 + METRICS_LABEL_PADDING + if i == 0 { 0.0 } else { size.height };
+        ctx.draw_text(&layout, (x, y));
+    }
+}
+
+/// Parse a list of feature tags to enable, like `smcp`, or to disable,
+/// like `-liga`.
+fn parse_features(text: &str) -> Vec<Feature> {
+    text.split(|c: char| c.is_whitespace() || c == ',')
+        .filter_map(|item| {
+            let (tag, value) = match item.strip_prefix('-') {
+                Some(tag) => (tag, 0),
+                None => (item.trim_start_matches('+'), 1),
+            };
+            if tag.is_empty() || tag.len() > 4 || !tag.is_ascii() {
+                return None;
+            }
+            let mut chars = tag.chars().chain(std::iter::repeat(' '));
+            let tag = Tag::new(chars.next()?, chars.next()?, chars.next()?, chars.next()?);
+            Some(Feature::new(tag, value, ..))
+        })
+        .collect()
+}
@@ -2,16 +2,22 @@
 //!
 //! This is intended to be shown as a modal panel.
 
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
 use druid::widget::prelude::*;
-use druid::widget::{Button, CrossAxisAlignment, Flex, Label};
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, Scroll, Tabs};
 use druid::{Color, LensExt, WidgetExt};
 
 use norad::GlyphName;
 
 use crate::data::{FontMetrics, SimpleFontInfo, Workspace};
+use crate::font_info::{LegalInfo, NameInfo, Os2Info, PostscriptInfo};
 use crate::theme;
 use crate::widgets::{EditableLabel, ModalHost};
 
+const LABEL_WIDTH: f64 = 160.0;
 fn glyphname_label() -> EditableLabel<GlyphName> {
     EditableLabel::new(
         |data: &GlyphName, _: &_| data.to_string(),
@@ -20,63 +26,213 @@ fn glyphname_label() -> EditableLabel<GlyphName> {
 }
 
 pub fn font_info() -> impl Widget<Workspace> {
-    Flex::column()
-        .with_child(
-            Flex::row()
-                .with_child(glyphname_label().lens(SimpleFontInfo::family_name))
-                .with_default_spacer()
-                .with_child(glyphname_label().lens(SimpleFontInfo::style_name)),
+    let tabs = Tabs::new()
+        .with_tab("Naming", naming_tab())
+        .with_tab("Dimensions", dimensions_tab())
+        .with_tab("OS/2", os2_tab().lens(SimpleFontInfo::os2))
+        .with_tab(
+            "PostScript",
+            postscript_tab().lens(SimpleFontInfo::postscript),
         )
+        .with_tab("Legal", legal_tab().lens(SimpleFontInfo::legal));
+
+    let problems = Label::dynamic(|info: &SimpleFontInfo, _| info.problems().join("\n"))
+        .with_text_color(theme::PROBLEM_TEXT_COLOR)
+        .with_line_break_mode(LineBreaking::WordWrap);
+
+    Flex::column()
+        .with_flex_child(tabs, 1.0)
         .with_default_spacer()
         .with_child(
             Flex::row()
-                .with_child(Label::new("Cap height:").with_text_color(theme::SECONDARY_TEXT_COLOR))
+                .with_flex_child(problems.expand_width(), 1.0)
                 .with_default_spacer()
                 .with_child(
-                    option_f64_editlabel()
-                        .lens(SimpleFontInfo::metrics.then(FontMetrics::cap_height)),
-                ),
+                    Button::new("Done")
+                        .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+                )
+                .cross_axis_alignment(CrossAxisAlignment::End),
         )
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(520., 440.)
+        .padding(16.0)
+        .background(Color::WHITE)
+        .lens(Workspace::info)
+}
+
+fn naming_tab() -> impl Widget<SimpleFontInfo> {
+    let names = Flex::column()
         .with_child(
-            Flex::row()
-                .with_child(Label::new("x-height:").with_text_color(theme::SECONDARY_TEXT_COLOR))
-                .with_default_spacer()
-                .with_child(
-                    option_f64_editlabel()
-                        .lens(SimpleFontInfo::metrics.then(FontMetrics::x_height)),
-                ),
+            field("Style map family name:", text_field()).lens(NameInfo::style_map_family_name),
         )
         .with_child(
-            Flex::row()
-                .with_child(Label::new("Ascender:").with_text_color(theme::SECONDARY_TEXT_COLOR))
-                .with_default_spacer()
-                .with_child(
-                    option_f64_editlabel()
-                        .lens(SimpleFontInfo::metrics.then(FontMetrics::ascender)),
-                ),
+            field("Preferred family name:", text_field()).lens(NameInfo::preferred_family_name),
         )
         .with_child(
-            Flex::row()
-                .with_child(
-                    Label::new("Descender:")
-                        .with_text_color(theme::SECONDARY_TEXT_COLOR)
-                        .center(),
-                )
-                .with_default_spacer()
-                .with_child(
-                    option_f64_editlabel()
-                        .lens(SimpleFontInfo::metrics.then(FontMetrics::descender)),
-                ),
+            field("Preferred subfamily name:", text_field())
+                .lens(NameInfo::preferred_subfamily_name),
         )
-        .with_flex_spacer(1.0)
+        .with_child(field("PostScript name:", text_field()).lens(NameInfo::postscript_font_name))
         .with_child(
-            Button::new("Done").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+            field("PostScript full name:", text_field()).lens(NameInfo::postscript_full_name),
         )
-        .cross_axis_alignment(CrossAxisAlignment::End)
-        .fix_height(300.)
-        .padding(16.0)
-        .background(Color::WHITE)
-        .lens(Workspace::info)
+        .with_child(field("Version major:", optional_field()).lens(NameInfo::version_major))
+        .with_child(field("Version minor:", optional_field()).lens(NameInfo::version_minor))
+        .lens(SimpleFontInfo::names);
+
+    tab(Flex::column()
+        .with_child(field("Family name:", glyphname_label()).lens(SimpleFontInfo::family_name))
+        .with_child(field("Style name:", glyphname_label()).lens(SimpleFontInfo::style_name))
+        .with_child(names))
+}
+
+fn dimensions_tab() -> impl Widget<SimpleFontInfo> {
+    tab(Flex::column()
+        .with_child(
+            field("Units per em:", units_per_em_field())
+                .lens(SimpleFontInfo::metrics.then(FontMetrics::units_per_em)),
+        )
+        .with_child(
+            field("Ascender:", option_f64_editlabel())
+                .lens(SimpleFontInfo::metrics.then(FontMetrics::ascender)),
+        )
+        .with_child(
+            field("Descender:", option_f64_editlabel())
+                .lens(SimpleFontInfo::metrics.then(FontMetrics::descender)),
+        )
+        .with_child(
+            field("x-height:", option_f64_editlabel())
+                .lens(SimpleFontInfo::metrics.then(FontMetrics::x_height)),
+        )
+        .with_child(
+            field("Cap height:", option_f64_editlabel())
+                .lens(SimpleFontInfo::metrics.then(FontMetrics::cap_height)),
+        )
+        .with_child(
+            field("Italic angle:", option_f64_editlabel())
+                .lens(SimpleFontInfo::metrics.then(FontMetrics::italic_angle)),
+        ))
+}
+
+fn os2_tab() -> impl Widget<Os2Info> {
+    tab(Flex::column()
+        .with_child(field("Weight class:", optional_field()).lens(Os2Info::weight_class))
+        .with_child(field("Vendor ID:", text_field()).lens(Os2Info::vendor_id))
+        .with_child(field("Typo ascender:", optional_field()).lens(Os2Info::typo_ascender))
+        .with_child(field("Typo descender:", optional_field()).lens(Os2Info::typo_descender))
+        .with_child(field("Typo line gap:", optional_field()).lens(Os2Info::typo_line_gap))
+        .with_child(field("Win ascent:", optional_field()).lens(Os2Info::win_ascent))
+        .with_child(field("Win descent:", optional_field()).lens(Os2Info::win_descent))
+        .with_child(field("hhea ascender:", optional_field()).lens(Os2Info::hhea_ascender))
+        .with_child(field("hhea descender:", optional_field()).lens(Os2Info::hhea_descender))
+        .with_child(field("hhea line gap:", optional_field()).lens(Os2Info::hhea_line_gap)))
+}
+
+fn postscript_tab() -> impl Widget<PostscriptInfo> {
+    tab(Flex::column()
+        .with_child(field("Blue values:", number_list_field()).lens(PostscriptInfo::blue_values))
+        .with_child(field("Other blues:", number_list_field()).lens(PostscriptInfo::other_blues))
+        .with_child(field("Family blues:", number_list_field()).lens(PostscriptInfo::family_blues))
+        .with_child(
+            field("Family other blues:", number_list_field())
+                .lens(PostscriptInfo::family_other_blues),
+        )
+        .with_child(field("Stem snap H:", number_list_field()).lens(PostscriptInfo::stem_snap_h))
+        .with_child(field("Stem snap V:", number_list_field()).lens(PostscriptInfo::stem_snap_v))
+        .with_child(field("Blue fuzz:", optional_field()).lens(PostscriptInfo::blue_fuzz))
+        .with_child(field("Blue shift:", optional_field()).lens(PostscriptInfo::blue_shift))
+        .with_child(field("Blue scale:", optional_field()).lens(PostscriptInfo::blue_scale))
+        .with_child(
+            field("Underline position:", optional_field()).lens(PostscriptInfo::underline_position),
+        )
+        .with_child(
+            field("Underline thickness:", optional_field())
+                .lens(PostscriptInfo::underline_thickness),
+        ))
+}
+
+fn legal_tab() -> impl Widget<LegalInfo> {
+    tab(Flex::column()
+        .with_child(field("Copyright:", text_field()).lens(LegalInfo::copyright))
+        .with_child(field("Trademark:", text_field()).lens(LegalInfo::trademark))
+        .with_child(field("Year:", optional_field()).lens(LegalInfo::year))
+        .with_child(field("Designer:", text_field()).lens(LegalInfo::designer))
+        .with_child(field("Designer URL:", text_field()).lens(LegalInfo::designer_url))
+        .with_child(field("Manufacturer:", text_field()).lens(LegalInfo::manufacturer))
+        .with_child(field("Manufacturer URL:", text_field()).lens(LegalInfo::manufacturer_url))
+        .with_child(field("License:", text_field()).lens(LegalInfo::license))
+        .with_child(field("License URL:", text_field()).lens(LegalInfo::license_url)))
+}
+
+/// Wrap the fields of a tab so that they can scroll if the panel is too small.
+fn tab<T: Data>(fields: Flex<T>) -> impl Widget<T> {
+    Scroll::new(
+        fields
+            .cross_axis_alignment(CrossAxisAlignment::Start)
+            .padding(8.0),
+    )
+    .vertical()
+}
+
+/// A row with a label followed by an editor for a single field.
+fn field<T: Data>(label: &str, editor: impl Widget<T> + 'static) -> impl Widget<T> {
+    Flex::row()
+        .with_child(
+            Label::new(label)
+                .with_text_color(theme::SECONDARY_TEXT_COLOR)
+                .fix_width(LABEL_WIDTH),
+        )
+        .with_default_spacer()
+        .with_flex_child(editor, 1.0)
+        .padding((0.0, 2.0))
+}
+
+fn text_field() -> EditableLabel<Arc<str>> {
+    EditableLabel::new(
+        |data: &Arc<str>, _: &_| data.to_string(),
+        |s| Some(s.trim().into()),
+    )
+}
+
+/// An editor for an optional number; clearing the text removes the value.
+fn optional_field<T: Data + Display + FromStr>() -> EditableLabel<Option<T>> {
+    EditableLabel::new(
+        |data: &Option<T>, _: &_| data.as_ref().map(T::to_string).unwrap_or_default(),
+        |s| {
+            let s = s.trim();
+            if s.is_empty() {
+                Some(None)
+            } else {
+                s.parse().ok().map(Some)
+            }
+        },
+    )
+}
+
+/// An editor for a list of numbers, separated by spaces or commas.
+fn number_list_field() -> EditableLabel<Arc<Vec<f64>>> {
+    EditableLabel::new(
+        |data: &Arc<Vec<f64>>, _: &_| {
+            data.iter()
+                .map(f64::to_string)
+                .collect::<Vec<_>>()
+                .join(" ")
+        },
+        |s| {
+            s.split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<f64>().ok())
+                .collect::<Option<Vec<_>>>()
+                .map(Arc::new)
+        },
+    )
+}
+
+fn units_per_em_field() -> EditableLabel<f64> {
+    EditableLabel::new(
+        |data: &f64, _: &_| data.to_string(),
+        |s| s.trim().parse::<f64>().ok().filter(|upm| *upm > 0.0),
+    )
 }
 
 fn option_f64_editlabel() -> EditableLabel<Option<f64>> {
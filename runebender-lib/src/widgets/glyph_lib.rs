@@ -0,0 +1,119 @@
+//! A raw editor for the selected glyph's lib.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, Scope, TextBox,
+};
+use druid::{Color, FontDescriptor, FontFamily, Lens, WidgetExt};
+use norad::GlyphName;
+
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+#[derive(Clone, Data, Lens)]
+struct GlyphLibState {
+    workspace: Workspace,
+    name: Option<GlyphName>,
+    text: String,
+}
+
+impl GlyphLibState {
+    fn new(workspace: Workspace) -> Self {
+        let name = workspace.selected.clone();
+        let lib = name
+            .as_ref()
+            .and_then(|name| workspace.font.ufo.get_glyph(name))
+            .and_then(|glyph| glyph.lib.clone());
+        GlyphLibState {
+            workspace,
+            name,
+            text: lib_to_xml(lib),
+        }
+    }
+}
+
+pub fn glyph_lib_editor() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &GlyphLibState, _| match data.name.as_ref() {
+        Some(name) => format!("Lib for '{}'", name),
+        None => "No glyph selected".into(),
+    })
+    .with_text_size(16.0);
+
+    let text_box = TextBox::multiline()
+        .with_font(FontDescriptor::new(FontFamily::MONOSPACE))
+        .expand()
+        .lens(GlyphLibState::text);
+
+    let problem = Label::dynamic(|data: &GlyphLibState, _| match lib_from_xml(&data.text) {
+        Ok(_) => String::new(),
+        Err(e) => e,
+    })
+    .with_text_color(theme::PROBLEM_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_flex_child(problem.expand_width(), 1.0)
+        .with_default_spacer()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Apply").on_click(|ctx, data: &mut GlyphLibState, _| {
+                let name = match data.name.clone() {
+                    Some(name) => name,
+                    None => return,
+                };
+                if let Ok(lib) = lib_from_xml(&data.text) {
+                    data.workspace.set_glyph_lib(&name, lib);
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End);
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_flex_child(text_box, 1.0)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(560., 440.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(GlyphLibState::new, GlyphLibState::workspace, panel)
+}
+
+/// Serialize a lib as an XML plist; an empty lib is an empty string.
+fn lib_to_xml(lib: Option<plist::Dictionary>) -> String {
+    let lib = match lib {
+        Some(lib) if !lib.is_empty() => lib,
+        _ => return String::new(),
+    };
+    let mut buf = Vec::new();
+    if let Err(e) = plist::Value::Dictionary(lib).to_writer_xml(&mut buf) {
+        log::error!("failed to serialize glyph lib: '{}'", e);
+    }
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+/// Parse the text of the editor; it must be empty or an XML plist
+/// containing a single dictionary.
+fn lib_from_xml(text: &str) -> Result<Option<plist::Dictionary>, String> {
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    let value = plist::Value::from_reader_xml(std::io::Cursor::new(text.as_bytes()))
+        .map_err(|e| format!("Invalid plist: {}", e))?;
+    match value.into_dictionary() {
+        Some(lib) if lib.is_empty() => Ok(None),
+        Some(lib) => Ok(Some(lib)),
+        None => Err("The lib must be a dictionary".into()),
+    }
+}
@@ -1,13 +1,17 @@
-//! The floating panel that displays the sidebearings, advance, and other
-//! glyph metrics
+//! The floating panel that displays the sidebearings, advance, bounding box,
+//! and other glyph metrics
 
-use druid::widget::{prelude::*, Controller, Flex};
+use druid::widget::{prelude::*, Controller, Flex, Label, TextBox};
 use druid::{FontDescriptor, FontFamily, LensExt, WidgetExt};
 
-use crate::data::{EditorState, GlyphDetail, Sidebearings};
-use crate::widgets::{EditableLabel, GlyphPainter};
+use crate::data::{EditorState, GlyphDetail, GlyphStats, Sidebearings};
+use crate::widgets::{codepoints_editor, EditableLabel, GlyphPainter};
 use crate::{consts, theme};
 
+const NOTES_WIDTH: f64 = 208.0;
+const NOTES_HEIGHT: f64 = 48.0;
+const VERTICAL_METRIC_WIDTH: f64 = 48.0;
+
 /// A panel for editing the selected coordinate
 pub struct GlyphPane;
 
@@ -98,5 +102,101 @@ fn build_widget() -> impl Widget<EditorState> {
                 .lens(EditorState::detail_glyph.then(GlyphDetail::advance))
                 .fix_width(64.0),
         )
+        .with_spacer(8.0)
+        .with_child(
+            codepoints_editor()
+                .fix_width(NOTES_WIDTH)
+                .lens(EditorState::detail_glyph),
+        )
+        .with_spacer(8.0)
+        .with_child(vertical_metrics().lens(EditorState::detail_glyph))
+        .with_spacer(8.0)
+        .with_child(glyph_stats())
+        .with_spacer(8.0)
+        .with_child(
+            TextBox::multiline()
+                .with_placeholder("Notes")
+                .with_font(theme::UI_DETAIL_FONT)
+                .fix_size(NOTES_WIDTH, NOTES_HEIGHT)
+                .lens(EditorState::detail_glyph.then(GlyphDetail::note)),
+        )
         .padding(8.0)
 }
+
+/// The advance height and vertical origin, for vertical layout.
+fn vertical_metrics() -> impl Widget<GlyphDetail> {
+    let label = |text: &str| {
+        Label::new(text)
+            .with_font(theme::UI_DETAIL_FONT)
+            .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    };
+    let value = || {
+        EditableLabel::parse()
+            .with_font(FontDescriptor::new(FontFamily::MONOSPACE))
+            .with_text_size(12.0)
+            .with_text_alignment(druid::TextAlignment::End)
+            .fix_width(VERTICAL_METRIC_WIDTH)
+    };
+    Flex::row()
+        .with_child(label("height"))
+        .with_child(value().lens(GlyphDetail::advance_height))
+        .with_spacer(8.0)
+        .with_child(label("v. origin"))
+        .with_child(value().lens(GlyphDetail::vertical_origin))
+}
+
+/// The glyph's bounding box and overshoots, which update as it is edited.
+fn glyph_stats() -> impl Widget<EditorState> {
+    Label::dynamic(|data: &EditorState, _| match data.glyph_stats() {
+        Some(stats) => format_stats(&stats),
+        None => "No outline".into(),
+    })
+    .with_font(theme::UI_DETAIL_FONT)
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .fix_width(NOTES_WIDTH)
+}
+
+fn format_stats(stats: &GlyphStats) -> String {
+    let GlyphStats {
+        bounds,
+        left_sidebearing,
+        right_sidebearing,
+        top,
+        bottom,
+    } = stats;
+    format!(
+        "bounds: {}, {} to {}, {} ({} × {})\n\
+         sidebearings: {} / {}\n\
+         top: {} {}\n\
+         bottom: {} {}",
+        num(bounds.x0),
+        num(bounds.y0),
+        num(bounds.x1),
+        num(bounds.y1),
+        num(bounds.width()),
+        num(bounds.height()),
+        num(*left_sidebearing),
+        num(*right_sidebearing),
+        format_overshoot(top.distance),
+        top.line,
+        format_overshoot(bottom.distance),
+        bottom.line,
+    )
+}
+
+/// Describe an overshoot in words, so it's clear which side of the line the
+/// glyph is on.
+fn format_overshoot(distance: f64) -> String {
+    if distance > 0.0 {
+        format!("{} past", num(distance))
+    } else if distance < 0.0 {
+        format!("{} short of", num(distance.abs()))
+    } else {
+        "on".into()
+    }
+}
+
+/// Format a number to at most two decimal places.
+fn num(value: f64) -> String {
+    ((value * 100.0).round() / 100.0).to_string()
+}
@@ -0,0 +1,141 @@
+//! A quick switcher for opening a glyph by name or character.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Controller, CrossAxisAlignment, Flex, Label, Scope, TextBox};
+use druid::{Color, KbKey, Lens, Selector, WidgetExt};
+use norad::GlyphName;
+
+use crate::app_delegate::EDIT_GLYPH;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The most matches shown at once.
+const MAX_MATCHES: usize = 10;
+
+/// Sent by the switcher to its text box, once it is added.
+const FOCUS_QUERY: Selector = Selector::new("runebender.glyph-switcher-focus-query");
+
+#[derive(Clone, Data, Lens)]
+struct SwitcherState {
+    workspace: Workspace,
+    query: String,
+    /// The index of the highlighted match.
+    selected: usize,
+}
+
+impl SwitcherState {
+    fn new(workspace: Workspace) -> Self {
+        SwitcherState {
+            workspace,
+            query: String::new(),
+            selected: 0,
+        }
+    }
+
+    fn matches(&self) -> Vec<GlyphName> {
+        self.workspace.find_glyphs(&self.query, MAX_MATCHES)
+    }
+}
+
+pub fn glyph_switcher() -> impl Widget<Workspace> {
+    let query = TextBox::new()
+        .with_placeholder("Glyph name or character")
+        .with_text_size(16.0)
+        .expand_width()
+        .lens(SwitcherState::query)
+        .controller(SwitcherController);
+
+    let matches = Label::dynamic(|data: &SwitcherState, _| {
+        let matches = data.matches();
+        if matches.is_empty() && !data.query.trim().is_empty() {
+            return "No matching glyphs".into();
+        }
+        matches
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let marker = if i == data.selected { "›" } else { " " };
+                format!("{} {}", marker, name)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    })
+    .with_text_color(theme::PRIMARY_TEXT_COLOR);
+
+    let panel = Flex::column()
+        .with_child(query)
+        .with_default_spacer()
+        .with_child(matches)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(320.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(SwitcherState::new, SwitcherState::workspace, panel)
+}
+
+/// Takes focus when the switcher is shown, and handles the keys for
+/// choosing a match.
+struct SwitcherController;
+
+impl<W: Widget<SwitcherState>> Controller<SwitcherState, W> for SwitcherController {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut SwitcherState,
+        env: &Env,
+    ) {
+        match event {
+            Event::Command(cmd) if cmd.is(FOCUS_QUERY) => {
+                ctx.request_focus();
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if key.key == KbKey::Escape => {
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if key.key == KbKey::Enter => {
+                if let Some(name) = data.matches().get(data.selected) {
+                    ctx.submit_command(EDIT_GLYPH.with(name.clone()));
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if key.key == KbKey::ArrowDown => {
+                let count = data.matches().len();
+                data.selected = (data.selected + 1).min(count.saturating_sub(1));
+                ctx.set_handled();
+            }
+            Event::KeyDown(key) if key.key == KbKey::ArrowUp => {
+                data.selected = data.selected.saturating_sub(1);
+                ctx.set_handled();
+            }
+            _ => {
+                let old_query = data.query.clone();
+                child.event(ctx, event, data, env);
+                if data.query != old_query {
+                    data.selected = 0;
+                }
+            }
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        child: &mut W,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &SwitcherState,
+        env: &Env,
+    ) {
+        if let LifeCycle::WidgetAdded = event {
+            ctx.submit_command(FOCUS_QUERY.to(ctx.widget_id()));
+        }
+        child.lifecycle(ctx, event, data, env)
+    }
+}
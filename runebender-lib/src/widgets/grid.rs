@@ -1,34 +1,264 @@
 //! The top-level widget for the main glyph list window.
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use druid::kurbo::{Affine, Rect, Shape, Size};
-//use druid::piet::{
-//FontBuilder, PietText, PietTextLayout, RenderContext, Text, TextLayout, TextLayoutBuilder,
-//};
+use druid::kurbo::{Affine, BezPath, Circle, Line, Point, Rect, Shape, Size};
 use druid::widget::prelude::*;
-use druid::{Data, Insets, TextLayout, WidgetExt, WidgetPod};
+use druid::{Color, Data, Insets, KbKey, LensExt, TextLayout};
+use norad::GlyphName;
 
 use crate::app_delegate::EDIT_GLYPH;
-use crate::data::{GridGlyph, Workspace};
+use crate::data::{GlyphSort, GridGlyph, Workspace};
 use crate::theme;
-use crate::widgets::Maybe;
 
 const GLYPH_SIZE: f64 = 128.;
+const SECTION_HEADER_HEIGHT: f64 = 28.;
+const SECTION_HEADER_PADDING: f64 = 8.;
+/// The distance the mouse must move before a click becomes a drag.
+const DRAG_THRESHOLD: f64 = 4.0;
+const DROP_MARKER_WIDTH: f64 = 3.0;
+/// The opacity of a glyph's mark color when tinting its cell.
+const MARK_COLOR_ALPHA: f64 = 0.3;
+/// The dot drawn on glyphs that have changed since the font was committed.
+const MODIFIED_BADGE_COLOR: Color = Color::rgb8(0xE0, 0x8A, 0x1E);
+const MODIFIED_BADGE_RADIUS: f64 = 4.0;
+const MODIFIED_BADGE_INSET: f64 = 12.0;
 
+const LOCKED_BADGE_COLOR: Color = Color::grey8(0x60);
+
+/// The grid of all glyphs in the font.
+///
+/// Fonts can have many thousands of glyphs, so the grid does not have a
+/// widget per glyph; it lays out cells arithmetically, and only builds and
+/// paints the cells that are visible.
 #[derive(Default)]
 pub struct GlyphGrid {
-    children: Vec<WidgetPod<Workspace, Box<dyn Widget<Workspace>>>>,
+    cells: Vec<GridCell>,
+    sections: Vec<SectionHeader>,
+    drag: Option<GlyphDrag>,
+    /// The index of the cell the mouse is down in.
+    active: Option<usize>,
+    /// Glyph outlines already scaled and positioned for a cell.
+    thumbnails: HashMap<GlyphName, Thumbnail>,
+}
+
+/// A single glyph in the grid.
+struct GridCell {
+    name: GlyphName,
+    rect: Rect,
+    /// The glyph's name, built the first time the cell is painted.
+    label: Option<TextLayout<Arc<str>>>,
+}
+
+/// A glyph's outline, transformed into the coordinate space of a cell.
+struct Thumbnail {
+    /// The outline this was made from; if the glyph's outline is a
+    /// different `Arc`, the glyph has changed.
+    outline: Arc<BezPath>,
+    upm: f64,
+    path: BezPath,
+}
+
+/// A glyph being dragged to a new position in the glyph order.
+struct GlyphDrag {
+    /// The index of the cell being dragged.
+    index: usize,
+    start: Point,
+    /// The index of the cell the glyph will be inserted before, once the
+    /// drag has started.
+    target: Option<usize>,
+}
+
+/// A title drawn above the first glyph of a section.
+struct SectionHeader {
+    /// The index of the first cell in this section.
+    start: usize,
+    text: TextLayout<String>,
+    origin: Point,
 }
 
 impl GlyphGrid {
-    fn update_children(&mut self, data: &Workspace) {
-        self.children.clear();
-        for key in data.font.ufo.iter_names() {
-            let widget = Maybe::or_empty(GridInner::new);
-            self.children.push(WidgetPod::new(
-                widget.lens(Workspace::glyph_grid(key)).boxed(),
-            ));
+    fn update_cells(&mut self, data: &Workspace) {
+        self.cells.clear();
+        self.sections.clear();
+        self.drag = None;
+        self.active = None;
+        for section in data.grid_sections() {
+            if let Some(title) = section.title {
+                let mut text = TextLayout::from_text(title);
+                text.set_font(theme::UI_DETAIL_FONT);
+                text.set_text_color(theme::SECONDARY_TEXT_COLOR);
+                self.sections.push(SectionHeader {
+                    start: self.cells.len(),
+                    text,
+                    origin: Point::ZERO,
+                });
+            }
+            for name in section.glyphs {
+                self.cells.push(GridCell {
+                    name,
+                    rect: Rect::ZERO,
+                    label: None,
+                });
+            }
+        }
+        // don't hold on to thumbnails of glyphs that were deleted or renamed
+        let names: HashSet<_> = self.cells.iter().map(|cell| &cell.name).collect();
+        self.thumbnails.retain(|name, _| names.contains(name));
+    }
+
+    fn layout_changed(old: &Workspace, new: &Workspace) -> bool {
+        !old.font.same(&new.font)
+            || old.glyph_sort != new.glyph_sort
+            || old.group_glyphs != new.group_glyphs
+            || old.mark_filter != new.mark_filter
+            || old.coverage.filter != new.coverage.filter
+            || (new.coverage.filter.is_some() && !old.coverage.report.same(&new.coverage.report))
+    }
+
+    /// Glyphs can only be reordered by dragging when the grid is showing
+    /// the font's glyph order.
+    fn can_reorder(data: &Workspace) -> bool {
+        data.glyph_sort == GlyphSort::GlyphOrder && !data.group_glyphs
+    }
+
+    fn cell_at(&self, pos: Point) -> Option<usize> {
+        self.cells.iter().position(|cell| cell.rect.contains(pos))
+    }
+
+    /// The index of the cell that a glyph dropped at `pos` should be
+    /// inserted before.
+    fn drop_index(&self, pos: Point) -> Option<usize> {
+        self.cell_at(pos).map(|idx| {
+            if pos.x > self.cells[idx].rect.center().x {
+                idx + 1
+            } else {
+                idx
+            }
+        })
+    }
+
+    fn drag_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace) {
+        match event {
+            Event::MouseDown(m) if m.button.is_left() && m.count == 1 => {
+                self.drag = self
+                    .cell_at(m.pos)
+                    .filter(|_| GlyphGrid::can_reorder(data))
+                    .map(|index| GlyphDrag {
+                        index,
+                        start: m.pos,
+                        target: None,
+                    });
+            }
+            Event::MouseMove(m) if self.drag.is_some() => {
+                let drop_index = self.drop_index(m.pos);
+                let drag = self.drag.as_mut().unwrap();
+                if drag.target.is_some() || m.pos.distance(drag.start) > DRAG_THRESHOLD {
+                    drag.target = drop_index.or(drag.target).or(Some(drag.index));
+                    ctx.request_paint();
+                }
+            }
+            Event::MouseUp(_) => {
+                if let Some(GlyphDrag {
+                    index,
+                    target: Some(target),
+                    ..
+                }) = self.drag.take()
+                {
+                    let name = self.cells[index].name.clone();
+                    let before = self.cells.get(target).map(|cell| cell.name.clone());
+                    data.move_glyph(&name, before.as_ref());
+                    ctx.request_paint();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Select or open the glyph under the mouse, or show its context menu.
+    fn click_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace) {
+        match event {
+            Event::MouseDown(m) if m.button.is_right() => {
+                if let Some(cell) = self.cell_at(m.pos).map(|idx| &self.cells[idx]) {
+                    data.selected = Some(cell.name.clone());
+                    let menu = crate::menus::make_grid_context_menu(&cell.name);
+                    ctx.show_context_menu(menu, m.pos);
+                }
+            }
+            Event::MouseDown(m) => {
+                self.active = self.cell_at(m.pos);
+                ctx.request_focus();
+                if let Some(cell) = self.active.map(|idx| &self.cells[idx]) {
+                    ctx.set_active(true);
+                    ctx.request_paint();
+                    if m.count == 1 {
+                        data.selected = Some(cell.name.clone());
+                    } else if m.count == 2 {
+                        ctx.submit_command(EDIT_GLYPH.with(cell.name.clone()));
+                    }
+                }
+            }
+            Event::MouseUp(_) => {
+                if ctx.is_active() {
+                    ctx.set_active(false);
+                    self.active = None;
+                    ctx.request_paint();
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Select the glyph mapped to a typed character, or open the selected
+    /// glyph when enter is pressed.
+    fn key_event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace) {
+        let key = match event {
+            Event::KeyDown(key) if !key.mods.ctrl() && !key.mods.meta() => key,
+            _ => return,
+        };
+        match &key.key {
+            KbKey::Enter => {
+                if let Some(name) = data.selected.clone() {
+                    ctx.submit_command(EDIT_GLYPH.with(name));
+                    ctx.set_handled();
+                }
+            }
+            KbKey::Character(text) => {
+                let mut chars = text.chars();
+                let chr = match (chars.next(), chars.next()) {
+                    (Some(chr), None) => chr,
+                    _ => return,
+                };
+                if let Some(name) = data.glyph_for_char(chr) {
+                    if let Some(cell) = self.cells.iter().find(|cell| cell.name == name) {
+                        ctx.scroll_area_to_view(cell.rect);
+                    }
+                    data.selected = Some(name);
+                    ctx.request_paint();
+                }
+                ctx.set_handled();
+            }
+            _ => (),
+        }
+    }
+
+    fn paint_drop_marker(&self, ctx: &mut PaintCtx, env: &Env) {
+        let target = match self.drag.as_ref().and_then(|drag| drag.target) {
+            Some(target) => target,
+            None => return,
+        };
+        let x_and_rect = match self.cells.get(target) {
+            Some(cell) => Some((cell.rect.x0, cell.rect)),
+            None => self.cells.last().map(|cell| (cell.rect.x1, cell.rect)),
+        };
+        if let Some((x, rect)) = x_and_rect {
+            let line = Line::new((x, rect.y0 + 4.0), (x, rect.y1 - 4.0));
+            ctx.stroke(
+                line,
+                &env.get(theme::FOCUS_OUTLINE_COLOR),
+                DROP_MARKER_WIDTH,
+            );
         }
     }
 }
@@ -36,48 +266,89 @@ impl GlyphGrid {
 impl Widget<Workspace> for GlyphGrid {
     fn paint(&mut self, ctx: &mut PaintCtx, data: &Workspace, env: &Env) {
         ctx.render_ctx.clear(env.get(theme::GLYPH_LIST_BACKGROUND));
-        let row_len = 1.0_f64.max(ctx.size().width / GLYPH_SIZE).floor() as usize;
-        let _row_count = if self.children.is_empty() {
-            0
-        } else {
-            self.children.len() / row_len + 1
-        };
+        let visible = ctx.region().bounding_box();
+        let GlyphGrid {
+            cells,
+            thumbnails,
+            active,
+            ..
+        } = self;
+
+        // cells are laid out in rows from the top, so the visible ones are
+        // all together.
+        let visible_cells = cells
+            .iter_mut()
+            .enumerate()
+            .skip_while(|(_, cell)| cell.rect.y1 <= visible.y0)
+            .take_while(|(_, cell)| cell.rect.y0 < visible.y1);
+        for (idx, cell) in visible_cells {
+            let rect = cell.rect;
+            let glyph = match Workspace::glyph_grid(cell.name.clone()).get(data) {
+                Some(glyph) => glyph,
+                None => continue,
+            };
+            let is_active = *active == Some(idx);
+            let thumbnail = cached_thumbnail(thumbnails, &glyph);
+            let label = cell.label.get_or_insert_with(|| {
+                let mut text = TextLayout::from_text(glyph.name.clone());
+                text.set_font(theme::UI_DETAIL_FONT);
+                text.set_text_color(theme::PRIMARY_TEXT_COLOR);
+                text
+            });
+            label.rebuild_if_needed(ctx.text(), env);
+            ctx.with_save(|ctx| {
+                ctx.transform(Affine::translate(rect.origin().to_vec2()));
+                paint_cell(ctx, &glyph, thumbnail, label, is_active, env);
+            });
+        }
 
-        for child in &mut self.children {
-            child.paint(ctx, data, env);
+        for header in &mut self.sections {
+            header.text.draw(ctx, header.origin);
         }
+
+        self.paint_drop_marker(ctx, env);
     }
 
     fn layout(
         &mut self,
         ctx: &mut LayoutCtx,
         bc: &BoxConstraints,
-        data: &Workspace,
+        _data: &Workspace,
         env: &Env,
     ) -> Size {
         let width = (bc.max().width / GLYPH_SIZE).floor() * GLYPH_SIZE;
         let mut x: f64 = 0.;
         let mut y: f64 = 0.;
 
-        let child_bc = BoxConstraints::tight(Size::new(GLYPH_SIZE, GLYPH_SIZE));
+        let mut sections = self.sections.iter_mut().peekable();
 
-        for child in &mut self.children {
-            if x > 0. && x + GLYPH_SIZE > width {
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            if let Some(header) = sections.next_if(|header| header.start == i) {
+                if i > 0 {
+                    y += GLYPH_SIZE;
+                }
+                x = 0.;
+                header.text.rebuild_if_needed(ctx.text(), env);
+                let text_height = header.text.size().height;
+                header.origin = Point::new(
+                    SECTION_HEADER_PADDING,
+                    y + SECTION_HEADER_HEIGHT - text_height - 4.0,
+                );
+                y += SECTION_HEADER_HEIGHT;
+            } else if x > 0. && x + GLYPH_SIZE > width {
                 y += GLYPH_SIZE;
                 x = 0.;
             }
-            child.layout(ctx, &child_bc, data, env);
-            let rect = Rect::from_origin_size((x, y), (GLYPH_SIZE, GLYPH_SIZE));
-            child.set_layout_rect(ctx, data, env, rect);
+            cell.rect = Rect::from_origin_size((x, y), (GLYPH_SIZE, GLYPH_SIZE));
             x += GLYPH_SIZE;
         }
         Size::new(width, y + GLYPH_SIZE)
     }
 
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace, env: &Env) {
-        for child in &mut self.children {
-            child.event(ctx, event, data, env);
-        }
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut Workspace, _env: &Env) {
+        self.click_event(ctx, event, data);
+        self.drag_event(ctx, event, data);
+        self.key_event(ctx, event, data);
     }
 
     fn lifecycle(
@@ -85,30 +356,33 @@ impl Widget<Workspace> for GlyphGrid {
         ctx: &mut LifeCycleCtx,
         event: &LifeCycle,
         data: &Workspace,
-        env: &Env,
+        _env: &Env,
     ) {
-        if let LifeCycle::WidgetAdded = event {
-            if self.children.is_empty() {
-                self.update_children(data);
-            }
-        }
-
-        for child in &mut self.children {
-            child.lifecycle(ctx, event, data, env);
+        match event {
+            LifeCycle::WidgetAdded if self.cells.is_empty() => self.update_cells(data),
+            LifeCycle::BuildFocusChain => ctx.register_for_focus(),
+            _ => (),
         }
     }
 
-    fn update(&mut self, ctx: &mut UpdateCtx, old: &Workspace, new: &Workspace, env: &Env) {
-        //eprintln!("grid update generation {}, {}", old.cache.generation.get(), new.cache.generation.get());
-        if !old.font.same(&new.font) {
-            //eprintln!("old font changed");
-            self.update_children(new);
-            ctx.children_changed();
+    fn update(&mut self, ctx: &mut UpdateCtx, old: &Workspace, new: &Workspace, _env: &Env) {
+        if GlyphGrid::layout_changed(old, new) {
+            self.update_cells(new);
+            ctx.request_layout();
+            ctx.request_paint();
+        } else if !old.same(new) {
+            // only the visible cells are repainted, and their outlines
+            // are cached, so this is cheap.
             ctx.request_paint();
-        } else {
-            //eprintln!("cache same {}", old.cache.same(&new.cache));
-            for child in &mut self.children {
-                child.update(ctx, new, env);
+        }
+        for header in &mut self.sections {
+            if header.text.needs_rebuild_after_update(ctx) {
+                ctx.request_layout();
+            }
+        }
+        for label in self.cells.iter_mut().filter_map(|cell| cell.label.as_mut()) {
+            if label.needs_rebuild_after_update(ctx) {
+                ctx.request_paint();
             }
         }
     }
@@ -120,125 +394,98 @@ impl GlyphGrid {
     }
 }
 
-#[derive(Debug, Clone)]
-struct GridInner {
-    text: TextLayout<Arc<str>>,
-}
-
-impl GridInner {
-    fn new() -> Self {
-        GridInner {
-            text: TextLayout::new(),
-        }
+/// The thumbnail for `glyph`, creating it if needed.
+#[allow(clippy::float_cmp)]
+fn cached_thumbnail<'a>(
+    cache: &'a mut HashMap<GlyphName, Thumbnail>,
+    glyph: &GridGlyph,
+) -> &'a BezPath {
+    let stale = match cache.get(&glyph.name) {
+        Some(thumb) => !Arc::ptr_eq(&thumb.outline, &glyph.outline) || thumb.upm != glyph.upm,
+        None => true,
+    };
+    if stale {
+        let thumb = Thumbnail {
+            outline: glyph.outline.clone(),
+            upm: glyph.upm,
+            path: thumbnail_transform(&glyph.outline, glyph.upm) * &*glyph.outline,
+        };
+        cache.insert(glyph.name.clone(), thumb);
     }
+    &cache[&glyph.name].path
 }
 
-impl Widget<GridGlyph> for GridInner {
-    fn paint(&mut self, ctx: &mut PaintCtx, data: &GridGlyph, env: &Env) {
-        let path = data.outline.clone();
-        let bb = path.bounding_box();
-        let geom = ctx.size().to_rect();
-        let scale = geom.height() as f64 / data.upm;
-        let scale = scale * 0.75; // some margins around glyphs
-        let scaled_width = bb.width() * scale as f64;
-        let l_pad = ((geom.width() as f64 - scaled_width) / 2.).round();
-        let baseline = (geom.height() * 0.29) as f64;
-        let affine = Affine::new([
-            scale as f64,
-            0.0,
-            0.0,
-            -scale as f64,
-            l_pad,
-            geom.height() - baseline,
-        ]);
-
-        let glyph_rect: Rect = geom - Insets::uniform(5.0);
-        let rounded = glyph_rect.to_rounded_rect(5.0);
-        ctx.fill(rounded, &env.get(theme::GLYPH_GRID_CELL_BACKGROUND_COLOR));
-        ctx.stroke(rounded, &env.get(theme::GLYPH_GRID_CELL_OUTLINE_COLOR), 2.0);
-        if ctx.is_active() || data.is_selected {
-            ctx.fill(rounded, &env.get(theme::FOCUS_BACKGROUND_COLOR));
-            ctx.stroke(rounded, &env.get(theme::FOCUS_OUTLINE_COLOR), 4.0);
-        }
-        let glyph_color = if data.is_placeholder {
-            env.get(theme::PLACEHOLDER_GLYPH_COLOR)
-        } else {
-            env.get(theme::PRIMARY_TEXT_COLOR)
-        };
-
-        ctx.render_ctx.fill(affine * &*path, &glyph_color);
-
-        let text_size = self.text.size();
+/// The transform from design space to the coordinate space of a cell.
+fn thumbnail_transform(outline: &BezPath, upm: f64) -> Affine {
+    let bb = outline.bounding_box();
+    let scale = GLYPH_SIZE / upm;
+    let scale = scale * 0.75; // some margins around glyphs
+    let scaled_width = bb.width() * scale;
+    let l_pad = ((GLYPH_SIZE - scaled_width) / 2.).round();
+    let baseline = GLYPH_SIZE * 0.29;
+    Affine::new([scale, 0.0, 0.0, -scale, l_pad, GLYPH_SIZE - baseline])
+}
 
-        let xpos = geom.x0 + (geom.width() - text_size.width) / 2.0;
-        let ypos = geom.max_y() - text_size.height;
+/// A small padlock, with the top left of its shackle at `origin`.
+fn paint_lock_badge(ctx: &mut PaintCtx, origin: Point) {
+    let body = Rect::from_origin_size((origin.x, origin.y + 4.0), (8.0, 6.0));
+    let mut shackle = BezPath::new();
+    shackle.move_to((body.x0 + 2.0, body.y0));
+    shackle.line_to((body.x0 + 2.0, origin.y));
+    shackle.line_to((body.x1 - 2.0, origin.y));
+    shackle.line_to((body.x1 - 2.0, body.y0));
+    ctx.fill(body, &LOCKED_BADGE_COLOR);
+    ctx.stroke(shackle, &LOCKED_BADGE_COLOR, 1.5);
+}
 
-        self.text.draw(ctx, (xpos, ypos - 8.0));
+/// Paint a single cell, with its origin at (0, 0).
+fn paint_cell(
+    ctx: &mut PaintCtx,
+    data: &GridGlyph,
+    thumbnail: &BezPath,
+    label: &mut TextLayout<Arc<str>>,
+    is_active: bool,
+    env: &Env,
+) {
+    let geom = Size::new(GLYPH_SIZE, GLYPH_SIZE).to_rect();
+    let glyph_rect: Rect = geom - Insets::uniform(5.0);
+    let rounded = glyph_rect.to_rounded_rect(5.0);
+    ctx.fill(rounded, &env.get(theme::GLYPH_GRID_CELL_BACKGROUND_COLOR));
+    ctx.stroke(rounded, &env.get(theme::GLYPH_GRID_CELL_OUTLINE_COLOR), 2.0);
+    if let Some(mark) = data.mark_color {
+        ctx.fill(rounded, &mark.to_color().with_alpha(MARK_COLOR_ALPHA));
     }
-
-    fn layout(
-        &mut self,
-        ctx: &mut LayoutCtx,
-        bc: &BoxConstraints,
-        _: &GridGlyph,
-        env: &Env,
-    ) -> Size {
-        self.text.rebuild_if_needed(ctx.text(), env);
-        bc.max()
+    if is_active || data.is_selected {
+        ctx.fill(rounded, &env.get(theme::FOCUS_BACKGROUND_COLOR));
+        ctx.stroke(rounded, &env.get(theme::FOCUS_OUTLINE_COLOR), 4.0);
     }
-
-    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut GridGlyph, _env: &Env) {
-        match event {
-            Event::MouseDown(m) => {
-                ctx.set_active(true);
-                ctx.request_paint();
-                if m.count == 1 {
-                    data.is_selected = true;
-                } else if m.count == 2 {
-                    ctx.submit_command(EDIT_GLYPH.with(data.name.clone()));
-                }
-            }
-            Event::MouseUp(_) => {
-                if ctx.is_active() {
-                    ctx.set_active(false);
-                    ctx.request_paint();
-                }
-            }
-            _ => (),
-        }
+    if data.is_modified {
+        let center = Point::new(
+            glyph_rect.max_x() - MODIFIED_BADGE_INSET,
+            glyph_rect.min_y() + MODIFIED_BADGE_INSET,
+        );
+        ctx.fill(
+            Circle::new(center, MODIFIED_BADGE_RADIUS),
+            &MODIFIED_BADGE_COLOR,
+        );
     }
-
-    fn lifecycle(
-        &mut self,
-        ctx: &mut LifeCycleCtx,
-        event: &LifeCycle,
-        data: &GridGlyph,
-        env: &Env,
-    ) {
-        match event {
-            LifeCycle::HotChanged(_) => ctx.request_paint(),
-            LifeCycle::WidgetAdded => {
-                self.text.set_text(data.name.clone());
-                self.text.set_font(theme::UI_DETAIL_FONT);
-                self.text.set_text_color(theme::PRIMARY_TEXT_COLOR);
-                self.text.rebuild_if_needed(ctx.text(), env);
-            }
-            _ => (),
-        }
+    if data.is_locked {
+        let origin = Point::new(
+            glyph_rect.min_x() + MODIFIED_BADGE_INSET - 4.0,
+            glyph_rect.min_y() + MODIFIED_BADGE_INSET - 2.0,
+        );
+        paint_lock_badge(ctx, origin);
     }
+    let glyph_color = if data.is_placeholder {
+        env.get(theme::PLACEHOLDER_GLYPH_COLOR)
+    } else {
+        env.get(theme::PRIMARY_TEXT_COLOR)
+    };
 
-    fn update(&mut self, ctx: &mut UpdateCtx, old: &GridGlyph, new: &GridGlyph, _env: &Env) {
-        if !old.same(new) {
-            ctx.request_paint();
-            // I don't know if our name can change? but if it can we need to rebuild our text
-            // object.
-            if !old.name.same(&new.name) {
-                self.text.set_text(new.name.clone());
-                ctx.request_layout();
-            }
-        }
-        if self.text.needs_rebuild_after_update(ctx) {
-            ctx.request_layout();
-        }
-    }
+    ctx.render_ctx.fill(thumbnail, &glyph_color);
+
+    let text_size = label.size();
+    let xpos = geom.x0 + (geom.width() - text_size.width) / 2.0;
+    let ypos = geom.max_y() - text_size.height;
+    label.draw(ctx, (xpos, ypos - 8.0));
 }
@@ -0,0 +1,72 @@
+//! A panel for naming a guide.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Scope, TextBox};
+use druid::{Color, Lens, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::guides::GuideEdit;
+use crate::point::EntityId;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The state of the panel: the workspace, plus the name being edited.
+#[derive(Clone, Data, Lens)]
+struct GuideNameState {
+    workspace: Workspace,
+    name: String,
+}
+
+/// A panel for naming the guide `id`, whose current name is `name`.
+pub fn guide_name_panel(id: EntityId, name: String) -> impl Widget<Workspace> {
+    let field = TextBox::new()
+        .with_placeholder("No name")
+        .expand_width()
+        .lens(GuideNameState::name);
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Set Name").on_click(move |ctx, data: &mut GuideNameState, _| {
+                ctx.submit_command(consts::cmd::EDIT_GUIDE.with(consts::cmd::EditGuideArgs {
+                    id,
+                    edit: GuideEdit::Rename(data.name.clone()),
+                }));
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(Label::new("Name Guide").with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(
+            Label::new("The name is drawn along the guide; leave it empty to remove it.")
+                .with_text_color(theme::SECONDARY_TEXT_COLOR),
+        )
+        .with_spacer(4.0)
+        .with_child(field)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        move |workspace| GuideNameState {
+            workspace,
+            name: name.clone(),
+        },
+        GuideNameState::workspace,
+        panel,
+    )
+}
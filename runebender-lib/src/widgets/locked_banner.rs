@@ -0,0 +1,74 @@
+//! The banners shown at the top of the editor: for a locked glyph, and for
+//! a glyph whose components lead back to itself.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, Controller, CrossAxisAlignment, Either, Flex, Label, SizedBox};
+use druid::WidgetExt;
+
+use crate::consts;
+use crate::data::EditorState;
+use crate::theme;
+
+/// Says that the glyph can't be edited, with a button to unlock it.
+pub fn locked_banner() -> impl Widget<EditorState> {
+    let banner = Flex::row()
+        .with_child(
+            Label::new("This glyph is locked; changes will not be kept.")
+                .with_text_color(theme::PRIMARY_TEXT_COLOR),
+        )
+        .with_default_spacer()
+        .with_child(
+            Button::new("Unlock").on_click(|ctx, data: &mut EditorState, _| {
+                let name = data.session.name.clone();
+                ctx.submit_command(consts::cmd::UNLOCK_GLYPH.with(name));
+            }),
+        )
+        .cross_axis_alignment(CrossAxisAlignment::Center)
+        .padding((12.0, 6.0));
+
+    Either::new(
+        |data: &EditorState, _| data.session.is_read_only(),
+        banner,
+        SizedBox::empty(),
+    )
+    .controller(SuppressClicks)
+}
+
+/// Says that the glyph's components form a cycle, which can't be drawn.
+pub fn component_cycle_banner() -> impl Widget<EditorState> {
+    let banner = Label::dynamic(|data: &EditorState, _| {
+        let cycle = data.font.component_cycle(&data.session.name);
+        format!(
+            "Components form a cycle ({}); the repeated component is not drawn.",
+            cycle.unwrap_or_default()
+        )
+    })
+    .with_text_color(theme::PRIMARY_TEXT_COLOR)
+    .padding((12.0, 6.0));
+
+    Either::new(
+        |data: &EditorState, _| data.font.component_cycle(&data.session.name).is_some(),
+        banner,
+        SizedBox::empty(),
+    )
+    .controller(SuppressClicks)
+}
+
+/// Keeps clicks on the banner from reaching the editor.
+struct SuppressClicks;
+
+impl<W: Widget<EditorState>> Controller<EditorState, W> for SuppressClicks {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut EditorState,
+        env: &Env,
+    ) {
+        child.event(ctx, event, data, env);
+        if matches!(event, Event::MouseUp(_) | Event::MouseDown(_)) {
+            ctx.set_handled();
+        }
+    }
+}
@@ -56,7 +56,6 @@ impl<T: Data> Maybe<T> {
     }
 
     /// Create a new `Maybe` widget where the `None` branch is an empty widget.
-    #[allow(dead_code)]
     pub fn or_empty<W1: Widget<T> + 'static>(some_maker: impl Fn() -> W1 + 'static) -> Maybe<T> {
         Self::new(some_maker, SizedBox::empty)
     }
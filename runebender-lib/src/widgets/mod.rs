@@ -1,31 +1,105 @@
 //! Druid `Widget`s.
 
+mod add_glyphs;
+mod align_pane;
+mod anchor_preview;
+mod audit_panel;
+mod batch_rename;
+mod commit;
+mod component_inspector;
+mod contour_list;
 mod controller;
+mod convert_quadratic;
 mod coord_pane;
+mod coverage;
+mod delete_glyph;
+mod duplicate_glyph;
 mod editable_label;
 mod editor;
+mod editor_tabs;
+mod export_image;
+mod external_changes;
+mod fea_editor;
 mod font_preview;
 mod fontinfo;
 mod glyph;
+mod glyph_lib;
 mod glyph_pane;
+mod glyph_switcher;
 mod grid;
+mod guide_name;
+mod locked_banner;
 mod maybe;
 mod modal_host;
+mod new_font;
+mod nib;
+mod nudge_amounts;
+mod paste_offset;
+mod rename_glyph;
+mod revert;
+mod save_status;
+mod script_console;
 mod scroll_zoom;
+mod shape_search;
+mod shape_to_component;
 mod sidebar;
+mod simplify;
+mod snapshots;
+mod status_bar;
+mod tool_options;
 mod toolbar;
+mod unicode_inspector;
+mod unsaved_changes;
+mod zoom_options;
 
+pub use add_glyphs::add_glyphs;
+pub use align_pane::AlignPane;
+pub use anchor_preview::anchor_preview_panel;
+pub use audit_panel::audit_panel;
+pub use batch_rename::batch_rename;
+pub use commit::commit_panel;
+pub use component_inspector::component_inspector;
+pub use contour_list::ContourList;
 pub use controller::EditorController;
-pub use coord_pane::CoordPane;
+pub use convert_quadratic::convert_quadratic_panel;
+pub use coord_pane::{selected_points_list, CoordPane};
+pub use coverage::coverage_panel;
+pub use delete_glyph::delete_glyph_panel;
+pub use duplicate_glyph::duplicate_glyph_panel;
 pub use editable_label::EditableLabel;
 pub use editor::Editor;
+pub use editor_tabs::EditorTabs;
+pub use export_image::export_glyph_image;
+pub use external_changes::external_changes_panel;
+pub use fea_editor::features_editor;
 pub use font_preview::Preview;
 pub use fontinfo::font_info;
 pub use glyph::GlyphPainter;
+pub use glyph_lib::glyph_lib_editor;
 pub use glyph_pane::GlyphPane;
+pub use glyph_switcher::glyph_switcher;
 pub use grid::GlyphGrid;
+pub use guide_name::guide_name_panel;
+pub use locked_banner::{component_cycle_banner, locked_banner};
 use maybe::Maybe;
 pub use modal_host::ModalHost;
+pub use new_font::new_font_panel;
+pub use nib::nib_panel;
+pub use nudge_amounts::nudge_amounts_panel;
+pub use paste_offset::paste_offset_panel;
+pub use rename_glyph::rename_glyph_panel;
+pub use revert::revert_panel;
+pub use save_status::save_status;
+pub use script_console::{run_script_dialog, script_console};
 pub use scroll_zoom::ScrollZoom;
-pub use sidebar::Sidebar;
+pub use shape_search::shape_search_panel;
+pub use shape_to_component::shape_to_component_panel;
+pub use sidebar::{codepoints_editor, Sidebar};
+pub use simplify::simplify_panel;
+pub use snapshots::snapshots_panel;
+pub use status_bar::StatusBar;
+pub use tool_options::ToolOptionsBar;
 pub use toolbar::{FloatingPanel, Toolbar};
+pub use unicode_inspector::unicode_inspector;
+pub use unsaved_changes::unsaved_changes_panel;
+pub use zoom_options::zoom_options_panel;
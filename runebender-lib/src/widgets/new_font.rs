@@ -0,0 +1,200 @@
+//! A panel for choosing the name, metrics and glyphs of a new font.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, Checkbox, CrossAxisAlignment, Either, Flex, Label, LineBreaking, MainAxisAlignment,
+    Scope, TextBox,
+};
+use druid::{commands, Color, FileDialogOptions, Lens, WidgetExt};
+
+use crate::charset::TEMPLATES;
+use crate::consts;
+use crate::data::Workspace;
+use crate::font_builder::FontBuilder;
+use crate::menus::UFO_FILE_TYPE;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+const LABEL_WIDTH: f64 = 100.0;
+const FIELD_WIDTH: f64 = 80.0;
+
+/// The state of the panel: the workspace, plus the fields being edited.
+#[derive(Clone, Data, Lens)]
+struct NewFontState {
+    workspace: Workspace,
+    family_name: String,
+    units_per_em: f64,
+    ascender: f64,
+    descender: f64,
+    x_height: f64,
+    glyphs: String,
+    placeholder_outlines: bool,
+}
+
+impl NewFontState {
+    fn new(workspace: Workspace) -> Self {
+        NewFontState {
+            workspace,
+            family_name: "Untitled".into(),
+            units_per_em: 1000.0,
+            ascender: 800.0,
+            descender: -200.0,
+            x_height: 500.0,
+            glyphs: TEMPLATES[0].to_spec(),
+            placeholder_outlines: false,
+        }
+    }
+
+    fn font(&self) -> Result<FontBuilder, String> {
+        let family_name = self.family_name.trim();
+        if family_name.is_empty() {
+            return Err("The font needs a family name.".into());
+        }
+        // the range allowed by the OpenType head table
+        if !(16.0..=16384.0).contains(&self.units_per_em) {
+            return Err("Units per em must be between 16 and 16384.".into());
+        }
+        if self.descender > 0.0 || self.ascender <= self.x_height || self.x_height <= 0.0 {
+            return Err(
+                "The descender, x-height and ascender should be in that order, \
+                with the descender at or below the baseline."
+                    .into(),
+            );
+        }
+        FontBuilder::new()
+            .with_family_name(family_name)
+            .with_units_per_em(self.units_per_em)
+            .with_ascender(self.ascender)
+            .with_descender(self.descender)
+            .with_x_height(self.x_height)
+            .with_placeholder_outlines(self.placeholder_outlines)
+            .with_glyph_list(&self.glyphs)
+            .map_err(|item| format!("Not a glyph name or codepoint: '{}'", item))
+    }
+
+    fn summary(&self) -> String {
+        match self.font() {
+            Err(problem) => problem,
+            Ok(font) if font.glyph_names().len() == 1 => "1 glyph.".into(),
+            Ok(font) => format!("{} glyphs.", font.glyph_names().len()),
+        }
+    }
+}
+
+pub fn new_font_panel() -> impl Widget<Workspace> {
+    let mut templates = Flex::row();
+    for template in TEMPLATES {
+        templates.add_child(
+            Button::new(template.name)
+                .on_click(move |_, data: &mut NewFontState, _| data.glyphs = template.to_spec()),
+        );
+        templates.add_spacer(4.0);
+    }
+
+    let glyphs = TextBox::multiline()
+        .with_placeholder("Glyph names, U+XXXX, or U+XXXX-YYYY")
+        .expand()
+        .lens(NewFontState::glyphs);
+
+    let summary = Either::new(
+        |data: &NewFontState, _| data.font().is_ok(),
+        summary_label().with_text_color(theme::SECONDARY_TEXT_COLOR),
+        summary_label().with_text_color(theme::PROBLEM_TEXT_COLOR),
+    );
+
+    let buttons = Flex::row()
+        .with_flex_child(summary.expand_width(), 1.0)
+        .with_default_spacer()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Create…").on_click(|ctx, data: &mut NewFontState, _| {
+                if let Ok(font) = data.font() {
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                    ctx.submit_command(consts::cmd::NEW_FONT.with(font));
+                    ctx.submit_command(
+                        commands::SHOW_SAVE_PANEL.with(
+                            FileDialogOptions::new()
+                                .allowed_types(vec![UFO_FILE_TYPE])
+                                .default_name(format!("{}.ufo", data.family_name.trim())),
+                        ),
+                    );
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End);
+
+    let panel = Flex::column()
+        .with_child(Label::new("New Font").with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(field_row(
+            "Family name:",
+            TextBox::new()
+                .fix_width(FIELD_WIDTH * 2.0)
+                .lens(NewFontState::family_name),
+        ))
+        .with_spacer(4.0)
+        .with_child(number_row("Units per em:", NewFontState::units_per_em))
+        .with_spacer(4.0)
+        .with_child(number_row("Ascender:", NewFontState::ascender))
+        .with_spacer(4.0)
+        .with_child(number_row("x-height:", NewFontState::x_height))
+        .with_spacer(4.0)
+        .with_child(number_row("Descender:", NewFontState::descender))
+        .with_default_spacer()
+        .with_child(
+            Label::new("Glyphs:")
+                .with_text_color(theme::SECONDARY_TEXT_COLOR)
+                .with_text_size(12.0),
+        )
+        .with_spacer(4.0)
+        .with_child(templates)
+        .with_spacer(4.0)
+        .with_flex_child(glyphs, 1.0)
+        .with_spacer(4.0)
+        .with_child(
+            Checkbox::new("Give glyphs placeholder outlines")
+                .lens(NewFontState::placeholder_outlines),
+        )
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(560., 480.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(NewFontState::new, NewFontState::workspace, panel)
+}
+
+fn summary_label() -> Label<NewFontState> {
+    Label::dynamic(|data: &NewFontState, _| data.summary())
+        .with_line_break_mode(LineBreaking::WordWrap)
+}
+
+fn field_row(
+    label: &'static str,
+    field: impl Widget<NewFontState> + 'static,
+) -> impl Widget<NewFontState> {
+    Flex::row()
+        .with_child(Label::new(label).fix_width(LABEL_WIDTH))
+        .with_child(field)
+}
+
+fn number_row(
+    label: &'static str,
+    lens: impl Lens<NewFontState, f64> + 'static,
+) -> impl Widget<NewFontState> {
+    field_row(
+        label,
+        TextBox::new()
+            .with_formatter(ParseFormatter::new())
+            .fix_width(FIELD_WIDTH)
+            .lens(lens),
+    )
+}
@@ -0,0 +1,93 @@
+//! A panel for choosing the nib used by the nib tool and 'Expand Stroke'.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, RadioGroup, TextBox,
+};
+use druid::{Color, Lens, LensExt, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::nib::{Nib, NibShape};
+use crate::theme;
+use crate::widgets::ModalHost;
+
+pub fn nib_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Nib").with_text_size(16.0);
+    let explanation = Label::new(
+        "Paths drawn with the nib tool, or expanded with 'Expand Stroke', \
+         are filled with the shape this nib makes as it moves along them.",
+    )
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let shape = RadioGroup::new(vec![("Flat", NibShape::Flat), ("Oval", NibShape::Oval)])
+        .lens(Workspace::nib.then(Nib::shape));
+
+    let sizes = Flex::column()
+        .with_child(value_row("width:", "units", Nib::width))
+        .with_spacer(4.0)
+        .with_child(value_row("height:", "units", Nib::height))
+        .with_spacer(4.0)
+        .with_child(value_row("angle:", "degrees", Nib::angle))
+        .cross_axis_alignment(CrossAxisAlignment::End);
+
+    let height_note = Label::new("The height is only used by oval nibs.")
+        .with_line_break_mode(LineBreaking::WordWrap)
+        .with_font(theme::UI_DETAIL_FONT)
+        .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let buttons = Flex::row()
+        .with_child(Button::new("Reset").on_click(|_, data: &mut Workspace, _| {
+            data.nib = Nib::default();
+        }))
+        .with_flex_spacer(1.0)
+        .with_child(Button::new("Expand Stroke").on_click(|ctx, _, _| {
+            ctx.submit_command(consts::cmd::EXPAND_STROKE);
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+        }))
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Done").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(shape)
+        .with_default_spacer()
+        .with_child(sizes)
+        .with_default_spacer()
+        .with_child(height_note)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
+
+fn value_row(
+    label: &str,
+    unit: &str,
+    lens: impl Lens<Nib, f64> + 'static,
+) -> impl Widget<Workspace> {
+    Flex::row()
+        .with_child(Label::new(label))
+        .with_spacer(4.0)
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .fix_width(60.0)
+                .lens(Workspace::nib.then(lens)),
+        )
+        .with_spacer(4.0)
+        .with_child(Label::new(unit).with_text_color(theme::SECONDARY_TEXT_COLOR))
+}
@@ -0,0 +1,73 @@
+//! A panel for setting how far the arrow keys move the selection.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, TextBox,
+};
+use druid::{Color, Lens, LensExt, WidgetExt};
+
+use crate::data::{NudgeAmounts, Workspace};
+use crate::theme;
+use crate::widgets::ModalHost;
+
+pub fn nudge_amounts_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Nudge Amounts").with_text_size(16.0);
+    let explanation = Label::new("Each press of an arrow key moves the selection by:")
+        .with_text_color(theme::SECONDARY_TEXT_COLOR);
+    let rounding = Label::new(
+        "Fractional amounts are rounded to whole units, \
+         unless fractional coordinates are enabled.",
+    )
+    .with_line_break_mode(LineBreaking::WordWrap)
+    .with_font(theme::UI_DETAIL_FONT)
+    .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let amounts = Flex::column()
+        .with_child(amount_row("no modifier:", NudgeAmounts::plain))
+        .with_spacer(4.0)
+        .with_child(amount_row("shift:", NudgeAmounts::shift))
+        .with_spacer(4.0)
+        .with_child(amount_row("cmd:", NudgeAmounts::cmd))
+        .cross_axis_alignment(CrossAxisAlignment::End);
+
+    let buttons = Flex::row()
+        .with_child(Button::new("Reset").on_click(|_, data: &mut Workspace, _| {
+            data.nudge_amounts = NudgeAmounts::default();
+        }))
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Done").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(amounts)
+        .with_default_spacer()
+        .with_child(rounding)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(300.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
+
+fn amount_row(label: &str, lens: impl Lens<NudgeAmounts, f64> + 'static) -> impl Widget<Workspace> {
+    Flex::row()
+        .with_child(Label::new(label))
+        .with_spacer(4.0)
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .fix_width(60.0)
+                .lens(Workspace::nudge_amounts.then(lens)),
+        )
+}
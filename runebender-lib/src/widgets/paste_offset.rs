@@ -0,0 +1,57 @@
+//! A panel for setting how far repeated pastes are offset.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, MainAxisAlignment, TextBox};
+use druid::{Color, LensExt, WidgetExt};
+
+use crate::data::{PasteOffset, Workspace};
+use crate::theme;
+use crate::widgets::ModalHost;
+
+pub fn paste_offset_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Paste Offset").with_text_size(16.0);
+    let explanation = Label::new("Each time the same paths are pasted, they move by:")
+        .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let offset = Flex::row()
+        .with_child(Label::new("x:"))
+        .with_spacer(4.0)
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .fix_width(60.0)
+                .lens(Workspace::paste_offset.then(PasteOffset::x)),
+        )
+        .with_default_spacer()
+        .with_child(Label::new("y:"))
+        .with_spacer(4.0)
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .fix_width(60.0)
+                .lens(Workspace::paste_offset.then(PasteOffset::y)),
+        );
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Done").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(offset)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(300.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
@@ -0,0 +1,96 @@
+//! A panel for renaming a single glyph.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Scope, TextBox};
+use druid::{Color, Lens, WidgetExt};
+use norad::GlyphName;
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The state of the panel: the workspace, plus the name being edited.
+#[derive(Clone, Data, Lens)]
+struct RenameGlyphState {
+    workspace: Workspace,
+    name: String,
+}
+
+impl RenameGlyphState {
+    /// The reason the glyph can't be given the new name, if there is one.
+    fn problem(&self, old: &str) -> Option<&'static str> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            Some("The glyph needs a name.")
+        } else if name != old && self.workspace.font.ufo.get_glyph(name).is_some() {
+            Some("A glyph with this name already exists.")
+        } else {
+            None
+        }
+    }
+}
+
+/// A panel for renaming the glyph `old`, with `new` as the suggested name.
+///
+/// If `new` can't be used, the reason is shown straight away.
+pub fn rename_glyph_panel(old: GlyphName, new: GlyphName) -> impl Widget<Workspace> {
+    let field = TextBox::new().expand_width().lens(RenameGlyphState::name);
+
+    let problem_old = old.clone();
+    let problem = Label::dynamic(move |data: &RenameGlyphState, _| {
+        data.problem(&problem_old).unwrap_or_default().to_string()
+    })
+    .with_text_color(theme::PROBLEM_TEXT_COLOR);
+
+    let rename_old = old.clone();
+    let buttons = Flex::row()
+        .with_flex_child(problem.expand_width(), 1.0)
+        .with_default_spacer()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Rename").on_click(move |ctx, data: &mut RenameGlyphState, _| {
+                if data.problem(&rename_old).is_some() {
+                    return;
+                }
+                let new: GlyphName = data.name.trim().into();
+                if new != rename_old {
+                    ctx.submit_command(consts::cmd::RENAME_GLYPH.with(
+                        consts::cmd::RenameGlyphArgs {
+                            old: rename_old.clone(),
+                            new,
+                        },
+                    ));
+                }
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(Label::new(format!("Rename '{}'", old)).with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(field)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        move |workspace| RenameGlyphState {
+            workspace,
+            name: new.to_string(),
+        },
+        RenameGlyphState::workspace,
+        panel,
+    )
+}
@@ -0,0 +1,46 @@
+//! A panel confirming that all unsaved changes should be discarded.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment};
+use druid::{Color, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+pub fn revert_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Revert to Saved").with_text_size(16.0);
+    let explanation = Label::new(
+        "All changes made since the font was last saved will be lost, \
+         including the undo history of open glyphs. This can't be undone.",
+    )
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(Button::new("Revert").on_click(|ctx, _, _| {
+            ctx.submit_command(consts::cmd::REVERT_TO_SAVED);
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+        }))
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
@@ -0,0 +1,33 @@
+//! An indicator shown while the font is saved in the background.
+
+use druid::widget::prelude::*;
+use druid::widget::{Flex, Label, SizedBox, Spinner, ViewSwitcher};
+use druid::WidgetExt;
+
+use crate::data::{SaveStatus, Workspace};
+use crate::theme;
+
+const SPINNER_SIZE: f64 = 12.0;
+
+/// A spinner while the font is being saved, or a message if saving failed.
+pub fn save_status() -> impl Widget<Workspace> {
+    ViewSwitcher::new(
+        |data: &Workspace, _| data.save_status.clone(),
+        |status, _, _| match status {
+            SaveStatus::Idle => SizedBox::empty().boxed(),
+            SaveStatus::Saving => Flex::row()
+                .with_child(Spinner::new().fix_size(SPINNER_SIZE, SPINNER_SIZE))
+                .with_spacer(4.0)
+                .with_child(
+                    Label::new("Saving…")
+                        .with_font(theme::UI_DETAIL_FONT)
+                        .with_text_color(theme::SECONDARY_TEXT_COLOR),
+                )
+                .boxed(),
+            SaveStatus::Failed(e) => Label::new(format!("Save failed: {}", e))
+                .with_font(theme::UI_DETAIL_FONT)
+                .with_text_color(theme::PROBLEM_TEXT_COLOR)
+                .boxed(),
+        },
+    )
+}
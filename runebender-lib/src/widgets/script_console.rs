@@ -0,0 +1,48 @@
+//! A window showing the output of scripts.
+
+use std::sync::Arc;
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, Scroll};
+use druid::{commands, Color, FileDialogOptions, FileSpec, FontDescriptor, FontFamily, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+
+pub const SCRIPT_FILE_TYPE: FileSpec = FileSpec::new("Rhai Script", &["rhai"]);
+
+const OUTPUT_TEXT_SIZE: f64 = 13.0;
+
+pub fn script_console() -> impl Widget<Workspace> {
+    let output = Label::dynamic(|data: &Workspace, _| data.script_output.to_string())
+        .with_font(FontDescriptor::new(FontFamily::MONOSPACE).with_size(OUTPUT_TEXT_SIZE))
+        .with_text_color(theme::PRIMARY_TEXT_COLOR)
+        .with_line_break_mode(LineBreaking::WordWrap)
+        .padding(8.0)
+        .expand_width();
+
+    let buttons =
+        Flex::row()
+            .with_child(Button::new("Run Script…").on_click(|ctx, _, _| {
+                ctx.submit_command(commands::SHOW_OPEN_PANEL.with(run_script_dialog()))
+            }))
+            .with_spacer(4.0)
+            .with_child(Button::new("Clear").on_click(|_, data: &mut Workspace, _| {
+                data.script_output = Arc::new(String::new())
+            }))
+            .padding(8.0);
+
+    Flex::column()
+        .with_flex_child(Scroll::new(output).vertical().expand(), 1.0)
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .background(Color::WHITE)
+}
+
+/// The options for the panel that chooses a script to run.
+pub fn run_script_dialog() -> FileDialogOptions {
+    FileDialogOptions::new()
+        .allowed_types(vec![SCRIPT_FILE_TYPE])
+        .accept_command(consts::cmd::RUN_SCRIPT)
+}
@@ -1,6 +1,6 @@
 use druid::widget::prelude::*;
 use druid::widget::Scroll;
-use druid::{Color, Command, KbKey, Point, Vec2};
+use druid::{Color, Command, HotKey, KbKey, Point, SysMods, Vec2};
 
 use crate::consts::CANVAS_SIZE;
 use crate::data::EditorState;
@@ -8,13 +8,36 @@ use crate::data::EditorState;
 const MIN_ZOOM: f64 = 0.02;
 const MAX_ZOOM: f64 = 50.;
 /// mouse wheel deltas are big, so we scale them down
-const ZOOM_SCALE: f64 = 0.001;
+const ZOOM_SCALE: f64 = 0.0007;
+/// How much each 'zoom in' or 'zoom out' command zooms.
+const KEY_ZOOM_FACTOR: f64 = 1.25;
+/// How long an animated zoom takes, in nanoseconds.
+const ZOOM_ANIMATION_DURATION: f64 = 120_000_000.0;
 
 /// A widget that wraps a scroll widget, adding zoom.
 pub struct ScrollZoom<T: Widget<EditorState>> {
     mouse: Point,
     child: Scroll<EditorState, T>,
     needs_center_after_layout: bool,
+    /// A remembered scroll position, to be restored after the first layout.
+    restore_offset: Option<Vec2>,
+    /// The scroll position that keeps the zoom's fixed point in place.
+    ///
+    /// This can only be set after the child has been laid out at the new
+    /// zoom; before that, the scroll position is clamped to the old size.
+    pending_offset: Option<Vec2>,
+    animation: Option<ZoomAnimation>,
+}
+
+/// A zoom in progress, from one zoom level to another.
+#[derive(Debug, Clone, Copy)]
+struct ZoomAnimation {
+    from: f64,
+    to: f64,
+    /// The point, in our coordinate space, that stays in place.
+    fixed_point: Vec2,
+    /// The time since the animation began, in nanoseconds.
+    elapsed: f64,
 }
 
 impl<T: Widget<EditorState>> ScrollZoom<T> {
@@ -23,46 +46,91 @@ impl<T: Widget<EditorState>> ScrollZoom<T> {
             child: Scroll::new(inner),
             mouse: Point::ZERO,
             needs_center_after_layout: true,
+            restore_offset: None,
+            pending_offset: None,
+            animation: None,
         }
     }
 
-    /// Updates zoom based on a delta from a scroll wheel
-    fn wheel_zoom(&mut self, data: &mut EditorState, delta: Vec2, fixed_point: Option<Vec2>) {
-        let last_zoom = data.session.viewport.zoom;
+    /// Updates zoom based on a delta from a scroll wheel, keeping
+    /// `fixed_point` in place.
+    fn wheel_zoom(&mut self, data: &mut EditorState, delta: Vec2, fixed_point: Vec2) {
         let delta = most_significant_axis(delta);
-        // we want to zoom in smaller units at smaller scales
-        let zoom_scale = (last_zoom + 1.0).ln() * ZOOM_SCALE;
+        let sensitivity = data.font.zoom_options.sensitivity.max(0.0);
+        // zooming is multiplicative, so that each step feels the same at
+        // any zoom level
+        let factor = (delta * ZOOM_SCALE * sensitivity).exp();
+        let next_zoom = data.session.viewport.zoom * factor;
+        self.set_zoom(data, next_zoom, fixed_point)
+    }
+
+    /// Updates zoom based on a magnification delta from a trackpad, keeping
+    /// the point under the mouse in place.
+    fn pinch_zoom(&mut self, data: &mut EditorState, delta: f64) {
+        let sensitivity = data.font.zoom_options.sensitivity.max(0.0);
+        let next_zoom = data.session.viewport.zoom * (1.0 + delta * sensitivity).max(0.1);
+        self.set_zoom(data, next_zoom, self.mouse.to_vec2())
+    }
 
-        let delta = delta.round() * zoom_scale;
-        if delta == 0. {
+    /// Zoom by `factor`, in response to a key or menu command; the zoom is
+    /// animated if that option is set.
+    fn step_zoom(
+        &mut self,
+        ctx: &mut EventCtx,
+        data: &mut EditorState,
+        factor: f64,
+        fixed_point: Vec2,
+    ) {
+        if !data.font.zoom_options.animate {
+            let next_zoom = data.session.viewport.zoom * factor;
+            self.set_zoom(data, next_zoom, fixed_point);
             return;
         }
-
-        let next_zoom = (last_zoom + delta).min(MAX_ZOOM).max(MIN_ZOOM);
-        self.set_zoom(data, next_zoom, fixed_point)
+        // repeated steps add up, even if the last hasn't finished
+        let from = data.session.viewport.zoom;
+        let to = self.animation.map(|anim| anim.to).unwrap_or(from) * factor;
+        self.animation = Some(ZoomAnimation {
+            from,
+            to: clamp_zoom(to),
+            fixed_point,
+            elapsed: 0.0,
+        });
+        ctx.request_anim_frame();
     }
 
-    fn pinch_zoom(&mut self, data: &mut EditorState, delta: f64) {
-        let next_zoom = (data.session.viewport.zoom + delta)
-            .min(MAX_ZOOM)
-            .max(MIN_ZOOM);
-        self.set_zoom(data, next_zoom, None)
+    /// Advance the zoom animation by `interval` nanoseconds.
+    fn animate(&mut self, ctx: &mut EventCtx, data: &mut EditorState, interval: u64) {
+        let mut anim = match self.animation.take() {
+            Some(anim) => anim,
+            None => return,
+        };
+        anim.elapsed += interval as f64;
+        let t = (anim.elapsed / ZOOM_ANIMATION_DURATION).min(1.0);
+        // ease out, and interpolate geometrically so the speed looks even
+        let t = 1.0 - (1.0 - t).powi(2);
+        let zoom = anim.from * (anim.to / anim.from).powf(t);
+        self.set_zoom(data, zoom, anim.fixed_point);
+        if t < 1.0 {
+            self.animation = Some(anim);
+            ctx.request_anim_frame();
+        }
     }
 
-    /// Set the zoom multiplier directly.
-    fn set_zoom(&mut self, data: &mut EditorState, new_zoom: f64, fixed_point: Option<Vec2>) {
-        let fixed_point = fixed_point.unwrap_or_else(|| self.mouse.to_vec2());
+    /// Set the zoom multiplier directly, keeping `fixed_point` (in our
+    /// coordinate space) over the same point on the canvas.
+    fn set_zoom(&mut self, data: &mut EditorState, new_zoom: f64, fixed_point: Vec2) {
+        let new_zoom = clamp_zoom(new_zoom);
         let delta_zoom = new_zoom / data.session.viewport.zoom;
         // prevents jitter when we're near our max or min zoom levels
-        if (delta_zoom).abs() < 0.001 {
+        if (delta_zoom - 1.0).abs() < 1e-6 {
             return;
         }
-        // we keep the mouse in the same relative position after zoom
-        // by adjusting the scroll offsets:
-        let scroll_off = self.child.offset() + fixed_point;
-        let next_off = scroll_off * delta_zoom;
-        let delta_off = next_off - scroll_off;
-        self.child.scroll_by(delta_off);
+        // we keep the fixed point in the same relative position after zoom
+        // by adjusting the scroll offsets; several zooms can happen before
+        // the next layout, so we start from any offset not yet applied.
+        let offset = self.pending_offset.unwrap_or_else(|| self.child.offset());
+        let scroll_off = offset + fixed_point;
+        self.pending_offset = Some(scroll_off * delta_zoom - fixed_point);
         data.session_mut().viewport.zoom = new_zoom;
     }
 
@@ -105,16 +173,17 @@ impl<T: Widget<EditorState>> ScrollZoom<T> {
         data.session_mut().viewport.zoom = new_zoom;
     }
 
-    fn handle_zoom_cmd(&mut self, cmd: &Command, view_size: Size, data: &mut EditorState) {
+    fn handle_zoom_cmd(&mut self, ctx: &mut EventCtx, cmd: &Command, data: &mut EditorState) {
         use crate::consts::cmd;
-        const ZOOM_DELTA: Vec2 = Vec2::new(50.0, 0.0);
-        let view_center = view_size.to_rect().center().to_vec2();
+        let view_center = ctx.size().to_rect().center().to_vec2();
         if cmd.is(cmd::ZOOM_IN) {
-            self.wheel_zoom(data, ZOOM_DELTA, Some(view_center))
+            self.step_zoom(ctx, data, KEY_ZOOM_FACTOR, view_center)
         } else if cmd.is(cmd::ZOOM_OUT) {
-            self.wheel_zoom(data, -ZOOM_DELTA, Some(view_center))
+            self.step_zoom(ctx, data, KEY_ZOOM_FACTOR.recip(), view_center)
         } else if cmd.is(cmd::ZOOM_DEFAULT) {
-            self.set_zoom(data, 1.0, None);
+            self.animation = None;
+            self.set_zoom(data, 1.0, view_center);
+            self.pending_offset = None;
             self.needs_center_after_layout = true;
         }
     }
@@ -127,6 +196,16 @@ impl<T: Widget<EditorState>> ScrollZoom<T> {
         ctx.request_layout();
         ctx.set_handled();
     }
+
+    /// Save the scroll position, so it can be restored if this glyph is
+    /// closed and reopened.
+    fn remember_offset(&self, data: &mut EditorState) {
+        // until the first layout, the offset isn't meaningful
+        if self.restore_offset.is_none() && !self.needs_center_after_layout {
+            let offset = self.pending_offset.unwrap_or_else(|| self.child.offset());
+            data.remember_scroll_offset(offset);
+        }
+    }
 }
 
 impl<T: Widget<EditorState>> Widget<EditorState> for ScrollZoom<T> {
@@ -144,9 +223,16 @@ impl<T: Widget<EditorState>> Widget<EditorState> for ScrollZoom<T> {
         env: &Env,
     ) -> Size {
         let size = self.child.layout(ctx, bc, data, env);
-        if self.needs_center_after_layout {
+        let pending_offset = self.pending_offset.take();
+        if let Some(offset) = self.restore_offset.take() {
+            let delta_off = offset - self.child.offset();
+            self.child.scroll_by(delta_off);
+        } else if self.needs_center_after_layout {
             self.set_initial_scroll(data, size);
             self.needs_center_after_layout = false;
+        } else if let Some(offset) = pending_offset {
+            let delta_off = offset - self.child.offset();
+            self.child.scroll_by(delta_off);
         }
         size
     }
@@ -157,11 +243,25 @@ impl<T: Widget<EditorState>> Widget<EditorState> for ScrollZoom<T> {
             Event::Command(c)
                 if c.is(cmd::ZOOM_IN) || c.is(cmd::ZOOM_OUT) || c.is(cmd::ZOOM_DEFAULT) =>
             {
-                self.handle_zoom_cmd(c, ctx.size(), data);
+                self.handle_zoom_cmd(ctx, c, data);
                 self.after_zoom_changed(ctx, env);
+                self.remember_offset(data);
                 return;
             }
-            Event::WindowSize(size) if self.needs_center_after_layout => {
+            Event::AnimFrame(interval) if self.animation.is_some() => {
+                // the child may be animating too, so it still gets the event
+                self.animate(ctx, data, *interval);
+                ctx.request_layout();
+            }
+            // cmd+= is also zoom in, without needing shift for '+'
+            Event::KeyDown(k) if HotKey::new(SysMods::Cmd, "=").matches(k) => {
+                ctx.submit_command(cmd::ZOOM_IN);
+                ctx.set_handled();
+                return;
+            }
+            Event::WindowSize(size)
+                if self.needs_center_after_layout && self.restore_offset.is_none() =>
+            {
                 self.set_initial_viewport(data, *size);
                 ctx.request_layout();
             }
@@ -179,18 +279,23 @@ impl<T: Widget<EditorState>> Widget<EditorState> for ScrollZoom<T> {
                 self.mouse = mouse.pos;
             }
             Event::Wheel(wheel) if wheel.mods.alt() => {
-                self.wheel_zoom(data, wheel.wheel_delta, None);
+                self.animation = None;
+                self.wheel_zoom(data, wheel.wheel_delta, wheel.pos.to_vec2());
                 self.after_zoom_changed(ctx, env);
+                self.remember_offset(data);
                 return;
             }
             Event::Zoom(delta) => {
+                self.animation = None;
                 self.pinch_zoom(data, *delta);
                 self.after_zoom_changed(ctx, env);
+                self.remember_offset(data);
                 return;
             }
             _ => (),
         }
         self.child.event(ctx, event, data, env);
+        self.remember_offset(data);
     }
 
     fn lifecycle(
@@ -200,6 +305,13 @@ impl<T: Widget<EditorState>> Widget<EditorState> for ScrollZoom<T> {
         data: &EditorState,
         env: &Env,
     ) {
+        if let LifeCycle::WidgetAdded = event {
+            // a glyph that is being reopened keeps its old viewport
+            if let Some(offset) = data.remembered().and_then(|m| m.scroll_offset) {
+                self.restore_offset = Some(offset);
+                self.needs_center_after_layout = false;
+            }
+        }
         self.child.lifecycle(ctx, event, data, env)
     }
 
@@ -208,6 +320,10 @@ impl<T: Widget<EditorState>> Widget<EditorState> for ScrollZoom<T> {
     }
 }
 
+fn clamp_zoom(zoom: f64) -> f64 {
+    zoom.min(MAX_ZOOM).max(MIN_ZOOM)
+}
+
 fn most_significant_axis(delta: Vec2) -> f64 {
     if delta.x.abs() > delta.y.abs() {
         delta.x
@@ -0,0 +1,92 @@
+//! A window listing contours with the same shape as a chosen contour.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, List, Scroll, TextBox};
+use druid::{Color, WidgetExt};
+
+use crate::app_delegate::EDIT_GLYPH;
+use crate::data::Workspace;
+use crate::shape_search::{ShapeMatch, ShapeQuery};
+use crate::theme;
+use crate::widgets::{Maybe, ModalHost};
+
+pub fn shape_search_panel() -> impl Widget<Workspace> {
+    let summary = Label::dynamic(|data: &Workspace, _| {
+        let glyph = match data.shape_query.as_ref() {
+            Some(query) => format!("contour {} of '{}'", query.index + 1, query.glyph),
+            None => return "No contour searched for".to_string(),
+        };
+        match data.shape_matches.len() {
+            0 => format!("No matches for {}", glyph),
+            1 => format!("1 match for {}", glyph),
+            n => format!("{} matches for {}", n, glyph),
+        }
+    })
+    .with_text_color(theme::PRIMARY_TEXT_COLOR);
+
+    let tolerance = Maybe::or_empty(|| {
+        Flex::row()
+            .with_child(Label::new("Tolerance:"))
+            .with_default_spacer()
+            .with_child(
+                TextBox::new()
+                    .with_formatter(ParseFormatter::new())
+                    .lens(ShapeQuery::tolerance),
+            )
+            .with_default_spacer()
+            .with_child(Label::new("units").with_text_color(theme::SECONDARY_TEXT_COLOR))
+    })
+    .lens(Workspace::shape_query);
+
+    let header = Flex::column()
+        .with_child(summary)
+        .with_default_spacer()
+        .with_child(
+            Flex::row()
+                .with_child(tolerance)
+                .with_flex_spacer(1.0)
+                .with_child(
+                    Button::new("Search Again")
+                        .on_click(|_, data: &mut Workspace, _| data.run_shape_search()),
+                )
+                .with_spacer(4.0)
+                .with_child(Button::new("Make Component…").on_click(
+                    |ctx, data: &mut Workspace, _| {
+                        if data.shape_query.is_some() {
+                            ctx.submit_command(ModalHost::make_modal_command(
+                                crate::widgets::shape_to_component_panel,
+                            ))
+                        }
+                    },
+                )),
+        )
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .padding(8.0);
+
+    // clicking a match opens its glyph
+    let matches = List::new(|| {
+        Label::dynamic(|found: &ShapeMatch, _| {
+            format!(
+                "{}: contour {}, moved by ({}, {})",
+                found.glyph,
+                found.index + 1,
+                found.offset.x,
+                found.offset.y
+            )
+        })
+        .with_text_color(theme::PRIMARY_TEXT_COLOR)
+        .padding((8.0, 4.0))
+        .expand_width()
+        .on_click(|ctx, found: &mut ShapeMatch, _| {
+            ctx.submit_command(EDIT_GLYPH.with(found.glyph.clone()))
+        })
+    })
+    .lens(Workspace::shape_matches);
+
+    Flex::column()
+        .with_child(header)
+        .with_flex_child(Scroll::new(matches).vertical().expand(), 1.0)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .background(Color::WHITE)
+}
@@ -0,0 +1,153 @@
+//! A panel for replacing the contours found by 'find shape' with components.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, MainAxisAlignment, Scope, Scroll, TextBox,
+};
+use druid::{Color, Lens, WidgetExt};
+use norad::GlyphName;
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The state of the panel: the workspace, plus the name of the base glyph.
+#[derive(Clone, Data, Lens)]
+struct ShapeToComponentState {
+    workspace: Workspace,
+    base: String,
+}
+
+impl ShapeToComponentState {
+    fn new(workspace: Workspace) -> Self {
+        let base = match workspace.shape_query.as_ref() {
+            // a glyph that is just the shape is the natural base
+            Some(query) if is_single_contour(&workspace, &query.glyph) => query.glyph.to_string(),
+            Some(query) => workspace.unused_glyph_name(&query.glyph).to_string(),
+            None => String::new(),
+        };
+        ShapeToComponentState { workspace, base }
+    }
+
+    fn base(&self) -> GlyphName {
+        self.base.trim().into()
+    }
+
+    /// The reason the contours can't be replaced, if there is one.
+    fn problem(&self) -> Option<String> {
+        let base = self.base();
+        if base.is_empty() {
+            return Some("The components need a base glyph.".into());
+        }
+        match self.workspace.shape_replacements(&base) {
+            None => Some(format!("'{}' has no contour of this shape.", base)),
+            Some(replacements) if replacements.is_empty() => {
+                Some("There are no contours to replace.".into())
+            }
+            Some(_) => None,
+        }
+    }
+
+    /// A description of each change that would be made.
+    fn preview(&self) -> String {
+        let base = self.base();
+        let replacements = match self.workspace.shape_replacements(&base) {
+            Some(replacements) if !base.is_empty() => replacements,
+            _ => return String::new(),
+        };
+        let mut lines = Vec::new();
+        if self.workspace.font.ufo.get_glyph(&base).is_none() {
+            lines.push(format!(
+                "'{}' will be added, with the contour searched for.",
+                base
+            ));
+        }
+        lines.extend(replacements.iter().map(|found| {
+            format!(
+                "{}: contour {} becomes '{}' at ({}, {})",
+                found.glyph,
+                found.index + 1,
+                base,
+                found.offset.x,
+                found.offset.y
+            )
+        }));
+        lines.join("\n")
+    }
+}
+
+/// `true` if the glyph `name` has one contour and no components.
+fn is_single_contour(workspace: &Workspace, name: &GlyphName) -> bool {
+    workspace
+        .font
+        .ufo
+        .get_glyph(name)
+        .and_then(|glyph| glyph.outline.as_ref())
+        .map(|outline| outline.contours.len() == 1 && outline.components.is_empty())
+        .unwrap_or(false)
+}
+
+/// A panel for choosing the base glyph of the components that replace the
+/// contours found by 'find shape', with a preview of the changes.
+pub fn shape_to_component_panel() -> impl Widget<Workspace> {
+    let field = Flex::row()
+        .with_child(Label::new("Base glyph:"))
+        .with_default_spacer()
+        .with_flex_child(
+            TextBox::new()
+                .expand_width()
+                .lens(ShapeToComponentState::base),
+            1.0,
+        );
+
+    let preview = Label::dynamic(|data: &ShapeToComponentState, _| data.preview())
+        .with_text_color(theme::PRIMARY_TEXT_COLOR)
+        .with_text_size(12.0)
+        .expand_width();
+
+    let problem =
+        Label::dynamic(|data: &ShapeToComponentState, _| data.problem().unwrap_or_default())
+            .with_text_color(theme::PROBLEM_TEXT_COLOR);
+
+    let buttons = Flex::row()
+        .with_flex_child(problem.expand_width(), 1.0)
+        .with_default_spacer()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Replace").on_click(|ctx, data: &mut ShapeToComponentState, _| {
+                if data.problem().is_none() {
+                    let base = data.base();
+                    ctx.submit_command(consts::cmd::REPLACE_SHAPE_WITH_COMPONENT.with(base));
+                    ctx.submit_command(ModalHost::DISMISS_MODAL);
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(Label::new("Replace Contours with Components").with_text_size(16.0))
+        .with_default_spacer()
+        .with_child(field)
+        .with_default_spacer()
+        .with_child(Scroll::new(preview).vertical().fix_height(200.))
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(400.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        ShapeToComponentState::new,
+        ShapeToComponentState::workspace,
+        panel,
+    )
+}
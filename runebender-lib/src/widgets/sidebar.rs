@@ -3,19 +3,20 @@
 use druid::kurbo::Line;
 use druid::{
     BoxConstraints, Color, Data, Env, Event, EventCtx, LayoutCtx, LifeCycle, LifeCycleCtx,
-    PaintCtx, Rect, RenderContext, Size, UpdateCtx, Widget, WidgetPod,
+    PaintCtx, Rect, RenderContext, Size, TextAlignment, UpdateCtx, Widget, WidgetPod,
 };
 
-use druid::widget::{Controller, Flex, Label, SizedBox, WidgetExt};
+use druid::widget::{Button, Controller, Flex, Label, SizedBox, TextBox, WidgetExt};
 
 use norad::GlyphName;
 
 use crate::data::{GlyphDetail, Workspace};
 use crate::theme;
-use crate::widgets::{EditableLabel, GlyphPainter, Maybe};
+use crate::widgets::{EditableLabel, GlyphPainter, Maybe, ModalHost};
 
 const SELECTED_GLYPH_BOTTOM_PADDING: f64 = 10.0;
 const SELECTED_GLYPH_HEIGHT: f64 = 100.0;
+const NOTES_HEIGHT: f64 = 60.0;
 
 // So that accents don't paint too much over other widgets
 const GLYPH_TOP_PADDING: f64 = SELECTED_GLYPH_HEIGHT * 0.2;
@@ -35,24 +36,12 @@ fn selected_glyph_widget() -> impl Widget<GlyphDetail> {
                         .map(Into::into)
                 },
             )
+            .with_autocomplete(crate::glyph_names::complete_name)
+            .with_hint(crate::glyph_names::describe_name)
             .controller(RenameController)
             .lens(GlyphDetail::glyph_name),
         )
-        .with_child(
-            Maybe::new(
-                || {
-                    Label::dynamic(|d: &char, _| format!("{} (U+{:04X})", d, *d as u32))
-                        .with_text_color(theme::SECONDARY_TEXT_COLOR)
-                        .with_font(theme::UI_DETAIL_FONT)
-                },
-                || {
-                    Label::new("____")
-                        .with_text_color(theme::SECONDARY_TEXT_COLOR)
-                        .with_font(theme::UI_DETAIL_FONT)
-                },
-            )
-            .lens(GlyphDetail::codepoint),
-        )
+        .with_child(codepoints_editor())
         .with_spacer(GLYPH_TOP_PADDING)
         .with_child(GlyphPainter::new().fix_height(SELECTED_GLYPH_HEIGHT))
         .with_child(
@@ -75,6 +64,46 @@ fn selected_glyph_widget() -> impl Widget<GlyphDetail> {
                 )
                 .padding((8.0, 0.0)),
         )
+        .with_default_spacer()
+        .with_child(
+            TextBox::multiline()
+                .with_placeholder("Notes")
+                .with_font(theme::UI_DETAIL_FONT)
+                .fix_height(NOTES_HEIGHT)
+                .expand_width()
+                .lens(GlyphDetail::note)
+                .padding((8.0, 0.0)),
+        )
+        .with_child(Button::new("Edit Lib…").on_click(|ctx, _, _| {
+            ctx.submit_command(ModalHost::make_modal_command(
+                crate::widgets::glyph_lib_editor,
+            ))
+        }))
+}
+
+/// The glyph's codepoints, editable as a list, with the characters below.
+pub fn codepoints_editor() -> impl Widget<GlyphDetail> {
+    Flex::column()
+        .with_child(
+            EditableLabel::new(
+                |s: &String, _: &_| s.clone(),
+                |s| {
+                    crate::charset::parse_codepoints(s)
+                        .ok()
+                        .map(|chars| crate::charset::format_codepoints(&chars))
+                },
+            )
+            .with_placeholder("No codepoints")
+            .with_hint(crate::charset::describe_codepoints)
+            .with_font(theme::UI_DETAIL_FONT)
+            .with_text_alignment(TextAlignment::Center)
+            .lens(GlyphDetail::codepoints),
+        )
+        .with_child(
+            Label::dynamic(|d: &GlyphDetail, _| d.codepoint_chars())
+                .with_text_color(theme::SECONDARY_TEXT_COLOR)
+                .with_font(theme::UI_DETAIL_FONT),
+        )
 }
 
 impl Sidebar {
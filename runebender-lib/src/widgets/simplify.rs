@@ -0,0 +1,84 @@
+//! A panel for choosing the tolerance used to simplify paths.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, Scope, TextBox,
+};
+use druid::{Color, Lens, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+/// The tolerance the panel starts with, in design units.
+const DEFAULT_TOLERANCE: f64 = 1.0;
+
+#[derive(Clone, Data, Lens)]
+struct SimplifyState {
+    workspace: Workspace,
+    tolerance: f64,
+}
+
+impl SimplifyState {
+    fn new(workspace: Workspace) -> Self {
+        SimplifyState {
+            workspace,
+            tolerance: DEFAULT_TOLERANCE,
+        }
+    }
+}
+
+pub fn simplify_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Simplify Paths").with_text_size(16.0);
+    let explanation = Label::new(
+        "Removes points from the selected paths, keeping the outline within the tolerance.",
+    )
+    .with_text_color(theme::SECONDARY_TEXT_COLOR)
+    .with_line_break_mode(LineBreaking::WordWrap);
+
+    let tolerance = Flex::row()
+        .with_child(Label::new("Tolerance:"))
+        .with_default_spacer()
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .lens(SimplifyState::tolerance),
+        )
+        .with_default_spacer()
+        .with_child(Label::new("units").with_text_color(theme::SECONDARY_TEXT_COLOR));
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Cancel")
+                .on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Simplify").on_click(|ctx, data: &mut SimplifyState, _| {
+                let tolerance = data.tolerance.max(0.0);
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+                ctx.submit_command(consts::cmd::SIMPLIFY_SELECTION.with(tolerance));
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(tolerance)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(300.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(SimplifyState::new, SimplifyState::workspace, panel)
+}
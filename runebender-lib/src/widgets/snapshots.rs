@@ -0,0 +1,189 @@
+//! A panel for saving, previewing, and restoring snapshots of the glyph in
+//! the active editor tab.
+//!
+//! This is intended to be shown as a modal panel.
+
+use std::sync::Arc;
+
+use druid::kurbo::{Affine, BezPath, Shape};
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Either, Flex, Label, List, MainAxisAlignment, Painter, Scope,
+    Scroll, TextBox,
+};
+use druid::{lens, Color, Lens, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::edit_session::EditSession;
+use crate::snapshot::Snapshot;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+const PREVIEW_SIZE: f64 = 48.0;
+const PREVIEW_PADDING: f64 = 4.0;
+
+#[derive(Clone, Data, Lens)]
+struct SnapshotsState {
+    workspace: Workspace,
+    /// The name for the next snapshot.
+    name: String,
+}
+
+/// A snapshot, as shown in the list.
+#[derive(Clone, Data)]
+struct SnapshotRow {
+    index: usize,
+    name: String,
+    date: String,
+    outline: Arc<BezPath>,
+}
+
+impl SnapshotsState {
+    fn new(workspace: Workspace) -> Self {
+        SnapshotsState {
+            workspace,
+            name: String::new(),
+        }
+    }
+
+    fn session(&self) -> Option<&Arc<EditSession>> {
+        self.workspace
+            .active_tab
+            .and_then(|id| self.workspace.sessions.get(&id))
+    }
+
+    fn rows(&self) -> Arc<Vec<SnapshotRow>> {
+        let snapshots = self
+            .session()
+            .map(|session| session.snapshots())
+            .unwrap_or_default();
+        let rows = snapshots
+            .iter()
+            .enumerate()
+            // newest first
+            .rev()
+            .map(|(index, snapshot)| SnapshotRow {
+                index,
+                name: snapshot.name.clone(),
+                date: display_date(&snapshot.date),
+                outline: Arc::new(snapshot.to_bezier(&self.workspace)),
+            })
+            .collect();
+        Arc::new(rows)
+    }
+}
+
+pub fn snapshots_panel() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &SnapshotsState, _| match data.session() {
+        Some(session) => format!("Snapshots of '{}'", session.name),
+        None => "No glyph open".into(),
+    })
+    .with_text_size(16.0);
+
+    let save = Flex::row()
+        .with_flex_child(
+            TextBox::new()
+                .with_placeholder("Snapshot name")
+                .expand_width()
+                .lens(SnapshotsState::name),
+            1.0,
+        )
+        .with_default_spacer()
+        .with_child(
+            Button::new("Save Snapshot").on_click(|ctx, data: &mut SnapshotsState, _| {
+                if data.session().is_none() {
+                    return;
+                }
+                let name = match data.name.trim() {
+                    "" => Snapshot::default_name(),
+                    name => name.to_owned(),
+                };
+                ctx.submit_command(consts::cmd::SAVE_SNAPSHOT.with(name));
+                data.name.clear();
+            }),
+        );
+
+    let rows = lens::Map::new(|data: &SnapshotsState| data.rows(), |_, _| ());
+    let list = Either::new(
+        |data: &SnapshotsState, _| data.rows().is_empty(),
+        Label::new("There are no snapshots of this glyph.")
+            .with_text_color(theme::SECONDARY_TEXT_COLOR),
+        Scroll::new(List::new(snapshot_row).lens(rows)).vertical(),
+    );
+
+    let buttons = Flex::row()
+        .with_child(
+            Button::new("Close").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(save)
+        .with_default_spacer()
+        .with_flex_child(list, 1.0)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(420., 440.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(SnapshotsState::new, SnapshotsState::workspace, panel)
+}
+
+fn snapshot_row() -> impl Widget<SnapshotRow> {
+    let preview = Painter::new(|ctx, data: &SnapshotRow, env| {
+        let rect = ctx.size().to_rect();
+        ctx.stroke(rect, &env.get(theme::GLYPH_LIST_STROKE), 1.0);
+        let bounds = data.outline.bounding_box();
+        if bounds.width() <= 0.0 || bounds.height() <= 0.0 {
+            return;
+        }
+        let available = rect.inset(-PREVIEW_PADDING);
+        let scale = (available.width() / bounds.width()).min(available.height() / bounds.height());
+        // flip y, and center the outline in the preview
+        let affine = Affine::translate(rect.center().to_vec2())
+            * Affine::new([scale, 0.0, 0.0, -scale, 0.0, 0.0])
+            * Affine::translate(-bounds.center().to_vec2());
+        ctx.fill(affine * &*data.outline, &env.get(theme::PRIMARY_TEXT_COLOR));
+    })
+    .fix_size(PREVIEW_SIZE, PREVIEW_SIZE);
+
+    let labels = Flex::column()
+        .with_child(Label::dynamic(|data: &SnapshotRow, _| data.name.clone()))
+        .with_child(
+            Label::dynamic(|data: &SnapshotRow, _| data.date.clone())
+                .with_font(theme::UI_DETAIL_FONT)
+                .with_text_color(theme::SECONDARY_TEXT_COLOR),
+        )
+        .cross_axis_alignment(CrossAxisAlignment::Start);
+
+    Flex::row()
+        .with_child(preview)
+        .with_default_spacer()
+        .with_flex_child(labels, 1.0)
+        .with_child(
+            Button::new("Restore").on_click(|ctx, data: &mut SnapshotRow, _| {
+                ctx.submit_command(consts::cmd::RESTORE_SNAPSHOT.with(data.index));
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+            }),
+        )
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Delete").on_click(|ctx, data: &mut SnapshotRow, _| {
+                ctx.submit_command(consts::cmd::DELETE_SNAPSHOT.with(data.index));
+            }),
+        )
+        .padding((0.0, 4.0))
+}
+
+/// Format a snapshot's RFC 3339 timestamp for display.
+fn display_date(date: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(date)
+        .map(|date| date.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| date.to_owned())
+}
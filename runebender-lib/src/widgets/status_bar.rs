@@ -0,0 +1,136 @@
+//! A bar along the bottom of the editor, with information about the cursor,
+//! zoom, selection, and current tool.
+
+use druid::kurbo::Line;
+use druid::piet::{PietTextLayout, Text, TextLayout, TextLayoutBuilder};
+use druid::widget::prelude::*;
+
+use crate::consts;
+use crate::data::{EditorState, SaveStatus};
+use crate::design_space::DPoint;
+use crate::theme;
+use crate::tools::{self, ToolId};
+
+pub const STATUS_BAR_HEIGHT: f64 = 22.0;
+const TEXT_PADDING: f64 = 8.0;
+const SEPARATOR: &str = "    ";
+
+pub struct StatusBar {
+    /// The position of the mouse in design space, if it is over the canvas.
+    cursor: Option<DPoint>,
+    tool: ToolId,
+    layout: Option<PietTextLayout>,
+}
+
+impl Default for StatusBar {
+    fn default() -> Self {
+        StatusBar {
+            cursor: None,
+            tool: "Select",
+            layout: None,
+        }
+    }
+}
+
+impl StatusBar {
+    fn text(&self, data: &EditorState) -> String {
+        let mut items = Vec::new();
+        if let Some(pt) = self.cursor {
+            items.push(format!("x {:.0}, y {:.0}", pt.x, pt.y));
+        }
+        items.push(format!("{:.0}%", data.session.viewport.zoom * 100.0));
+        // druid has no accessibility API for us to report this through yet,
+        // so in keyboard editing mode we spell the selection out here.
+        if data.font.keyboard_editing {
+            if let Some(description) = data.session.describe_selection() {
+                items.push(description);
+            }
+        } else {
+            self.push_selection_count(data, &mut items);
+        }
+        if let Some(hint) = tools::tool_for_id(self.tool).map(|tool| tool.hint()) {
+            if !hint.is_empty() {
+                items.push(hint.into());
+            }
+        }
+        match &data.font.save_status {
+            SaveStatus::Idle => (),
+            SaveStatus::Saving => items.push("Saving…".into()),
+            SaveStatus::Failed(e) => items.push(format!("Save failed: {}", e)),
+        }
+        items.join(SEPARATOR)
+    }
+
+    fn push_selection_count(&self, data: &EditorState, items: &mut Vec<String>) {
+        let selected = data
+            .session
+            .selection
+            .iter()
+            .filter(|id| !id.is_guide())
+            .count();
+        match selected {
+            0 => (),
+            1 => items.push("1 point selected".into()),
+            n => items.push(format!("{} points selected", n)),
+        }
+    }
+}
+
+impl Widget<EditorState> for StatusBar {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, _: &mut EditorState, _: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(pt) = cmd.get(consts::cmd::CURSOR_MOVED) {
+                self.cursor = *pt;
+                ctx.request_layout();
+            } else if let Some(tool) = cmd.get(consts::cmd::SET_TOOL) {
+                self.tool = *tool;
+                ctx.request_layout();
+            }
+        }
+    }
+
+    fn lifecycle(&mut self, _: &mut LifeCycleCtx, _: &LifeCycle, _: &EditorState, _: &Env) {}
+
+    fn update(&mut self, ctx: &mut UpdateCtx, old: &EditorState, data: &EditorState, _: &Env) {
+        if !old.session.viewport.same(&data.session.viewport)
+            || !old.session.selection.same(&data.session.selection)
+            || !old.font.save_status.same(&data.font.save_status)
+            || old.font.keyboard_editing != data.font.keyboard_editing
+            || (data.font.keyboard_editing && old.session.generation() != data.session.generation())
+        {
+            ctx.request_layout();
+        }
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &EditorState,
+        env: &Env,
+    ) -> Size {
+        let font = env.get(theme::UI_DETAIL_FONT);
+        self.layout = ctx
+            .text()
+            .new_text_layout(self.text(data))
+            .font(font.family.clone(), font.size)
+            .text_color(env.get(theme::SECONDARY_TEXT_COLOR))
+            .build()
+            .ok();
+        bc.constrain(Size::new(bc.max().width, STATUS_BAR_HEIGHT))
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, _: &EditorState, env: &Env) {
+        let size = ctx.size();
+        ctx.fill(size.to_rect(), &env.get(theme::SIDEBAR_BACKGROUND));
+        let edge = Line::new((0.0, 0.5), (size.width, 0.5));
+        ctx.stroke(edge, &env.get(theme::SIDEBAR_EDGE_STROKE), 1.0);
+        if let Some(layout) = self.layout.as_ref() {
+            let y = (size.height - layout.size().height) / 2.0;
+            ctx.with_save(|ctx| {
+                ctx.clip(size.to_rect());
+                ctx.draw_text(layout, (TEXT_PADDING, y));
+            });
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! The strip below the toolbar showing the options of the selected tool.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{Checkbox, Controller, Flex, Label, Radio, SizedBox, TextBox};
+use druid::{LensExt, WidgetExt, WidgetPod};
+
+use crate::consts;
+use crate::data::{EditorState, Workspace};
+use crate::settings::{MarqueeMode, ToolOptions};
+use crate::theme;
+use crate::tools::ToolId;
+
+const RADIUS_FIELD_WIDTH: f64 = 48.0;
+
+/// Shows the options of the selected tool, if it has any.
+///
+/// Like the toolbar, this follows the `SET_TOOL` command.
+pub struct ToolOptionsBar {
+    tool: ToolId,
+    inner: WidgetPod<EditorState, Box<dyn Widget<EditorState>>>,
+}
+
+impl ToolOptionsBar {
+    pub fn new() -> Self {
+        ToolOptionsBar {
+            tool: "Select",
+            inner: WidgetPod::new(options_for_tool("Select")),
+        }
+    }
+}
+
+impl Default for ToolOptionsBar {
+    fn default() -> Self {
+        ToolOptionsBar::new()
+    }
+}
+
+impl Widget<EditorState> for ToolOptionsBar {
+    fn event(&mut self, ctx: &mut EventCtx, event: &Event, data: &mut EditorState, env: &Env) {
+        if let Event::Command(cmd) = event {
+            if let Some(tool) = cmd.get(consts::cmd::SET_TOOL) {
+                if *tool != self.tool {
+                    self.tool = *tool;
+                    self.inner = WidgetPod::new(options_for_tool(*tool));
+                    ctx.children_changed();
+                }
+                return;
+            }
+        }
+        self.inner.event(ctx, event, data, env);
+        // suppress clicks so that the editor doesn't handle them.
+        if matches!(event, Event::MouseUp(_) | Event::MouseDown(_)) {
+            ctx.set_handled();
+        }
+    }
+
+    fn lifecycle(
+        &mut self,
+        ctx: &mut LifeCycleCtx,
+        event: &LifeCycle,
+        data: &EditorState,
+        env: &Env,
+    ) {
+        self.inner.lifecycle(ctx, event, data, env);
+    }
+
+    fn update(&mut self, ctx: &mut UpdateCtx, _: &EditorState, data: &EditorState, env: &Env) {
+        self.inner.update(ctx, data, env);
+    }
+
+    fn layout(
+        &mut self,
+        ctx: &mut LayoutCtx,
+        bc: &BoxConstraints,
+        data: &EditorState,
+        env: &Env,
+    ) -> Size {
+        let size = self.inner.layout(ctx, bc, data, env);
+        self.inner.set_layout_rect(ctx, data, env, size.to_rect());
+        size
+    }
+
+    fn paint(&mut self, ctx: &mut PaintCtx, data: &EditorState, env: &Env) {
+        self.inner.paint(ctx, data, env);
+    }
+}
+
+/// Turns changes to the options into a command, so that they are saved
+/// with the settings.
+struct OptionsEditor;
+
+impl<W: Widget<ToolOptions>> Controller<ToolOptions, W> for OptionsEditor {
+    fn event(
+        &mut self,
+        child: &mut W,
+        ctx: &mut EventCtx,
+        event: &Event,
+        data: &mut ToolOptions,
+        env: &Env,
+    ) {
+        let mut child_data = *data;
+        child.event(ctx, event, &mut child_data, env);
+        if child_data != *data {
+            ctx.submit_command(consts::cmd::SET_TOOL_OPTIONS.with(child_data));
+        }
+    }
+}
+
+fn options_for_tool(tool: ToolId) -> Box<dyn Widget<EditorState>> {
+    let options: Box<dyn Widget<ToolOptions>> = match tool {
+        "Select" => Flex::row()
+            .with_child(Label::new("Marquee selects:").with_font(theme::UI_DETAIL_FONT))
+            .with_spacer(4.0)
+            .with_child(Radio::new("All points", MarqueeMode::AllPoints))
+            .with_spacer(4.0)
+            .with_child(Radio::new(
+                "Points of contours it touches",
+                MarqueeMode::TouchedContours,
+            ))
+            .with_spacer(4.0)
+            .with_child(Radio::new(
+                "Points not under a fill",
+                MarqueeMode::UncoveredPoints,
+            ))
+            .lens(ToolOptions::marquee_mode)
+            .boxed(),
+        "Pen" => Checkbox::new("Add points at extrema when closing")
+            .lens(ToolOptions::add_extrema_on_close)
+            .boxed(),
+        "Knife" => Checkbox::new("Keep open paths open")
+            .lens(ToolOptions::knife_keeps_open_paths)
+            .boxed(),
+        "Rectangle" => Flex::row()
+            .with_child(Label::new("Corner radius:").with_font(theme::UI_DETAIL_FONT))
+            .with_spacer(4.0)
+            .with_child(
+                TextBox::new()
+                    .with_formatter(ParseFormatter::new())
+                    .fix_width(RADIUS_FIELD_WIDTH)
+                    .lens(ToolOptions::corner_radius),
+            )
+            .boxed(),
+        _ => return SizedBox::empty().boxed(),
+    };
+    let tool_options = EditorState::font.map(|data: &Workspace| data.tool_options, |_, _| ());
+    options
+        .controller(OptionsEditor)
+        .padding(8.0)
+        .lens(tool_options)
+        .boxed()
+}
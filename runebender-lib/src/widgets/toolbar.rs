@@ -243,6 +243,24 @@ impl Default for Toolbar {
             hotkey: HotKey::new(SysMods::Shift, "U"),
         };
 
+        let polygon = ToolbarItem {
+            name: "Polygon",
+            icon: constrain_path(polygon_path()),
+            hotkey: HotKey::new(None, "y"),
+        };
+
+        let star = ToolbarItem {
+            name: "Star",
+            icon: constrain_path(star_path()),
+            hotkey: HotKey::new(SysMods::Shift, "Y"),
+        };
+
+        let transform = ToolbarItem {
+            name: "Transform",
+            icon: constrain_path(transform_path()),
+            hotkey: HotKey::new(None, "t"),
+        };
+
         let knife = ToolbarItem {
             name: "Knife",
             icon: constrain_path(knife_path()),
@@ -255,8 +273,15 @@ impl Default for Toolbar {
             hotkey: HotKey::new(None, "m"),
         };
 
+        let nib = ToolbarItem {
+            name: "Nib",
+            icon: constrain_path(nib_path()),
+            hotkey: HotKey::new(None, "n"),
+        };
+
         Toolbar::new(vec![
-            select, pen, hyperpen, knife, preview, measure, rectangle, ellipse,
+            select, transform, pen, hyperpen, nib, knife, preview, measure, rectangle, ellipse,
+            polygon, star,
         ])
     }
 }
@@ -355,6 +380,53 @@ fn hyperpen_path() -> BezPath {
     bez
 }
 
+fn transform_path() -> BezPath {
+    let mut bez = BezPath::new();
+
+    bez.move_to((80.0, 20.0));
+    bez.line_to((320.0, 20.0));
+    bez.line_to((260.0, 260.0));
+    bez.line_to((20.0, 260.0));
+    bez.close_path();
+
+    for (x, y) in &[(80.0, 20.0), (320.0, 20.0), (260.0, 260.0), (20.0, 260.0)] {
+        bez.move_to((x - 20.0, y - 20.0));
+        bez.line_to((x + 20.0, y - 20.0));
+        bez.line_to((x + 20.0, y + 20.0));
+        bez.line_to((x - 20.0, y + 20.0));
+        bez.close_path();
+    }
+    bez
+}
+
+fn nib_path() -> BezPath {
+    let mut bez = BezPath::new();
+
+    bez.move_to((40.0, 0.0));
+    bez.line_to((240.0, 0.0));
+    bez.line_to((240.0, 110.0));
+    bez.line_to((40.0, 110.0));
+    bez.line_to((40.0, 0.0));
+    bez.close_path();
+
+    bez.move_to((40.0, 110.0));
+    bez.line_to((240.0, 110.0));
+    bez.curve_to((270.0, 250.0), (230.0, 330.0), (140.0, 500.0));
+    bez.curve_to((50.0, 330.0), (10.0, 250.0), (40.0, 110.0));
+    bez.close_path();
+
+    bez.move_to((140.0, 500.0));
+    bez.line_to((140.0, 300.0));
+
+    bez.move_to((140.0, 220.0));
+    bez.curve_to((162.0, 220.0), (180.0, 238.0), (180.0, 260.0));
+    bez.curve_to((180.0, 282.0), (162.0, 300.0), (140.0, 300.0));
+    bez.curve_to((118.0, 300.0), (100.0, 282.0), (100.0, 260.0));
+    bez.curve_to((100.0, 238.0), (118.0, 220.0), (140.0, 220.0));
+    bez.close_path();
+    bez
+}
+
 fn knife_path() -> BezPath {
     let mut bez = BezPath::new();
 
@@ -508,3 +580,32 @@ fn ellipse_path() -> BezPath {
     bez.close_path();
     bez
 }
+
+fn polygon_path() -> BezPath {
+    let mut bez = BezPath::new();
+
+    bez.move_to((110.0, 0.0));
+    bez.line_to((220.0, 80.0));
+    bez.line_to((178.0, 210.0));
+    bez.line_to((42.0, 210.0));
+    bez.line_to((0.0, 80.0));
+    bez.close_path();
+    bez
+}
+
+fn star_path() -> BezPath {
+    let mut bez = BezPath::new();
+
+    bez.move_to((110.0, 0.0));
+    bez.line_to((136.0, 76.0));
+    bez.line_to((220.0, 76.0));
+    bez.line_to((152.0, 124.0));
+    bez.line_to((178.0, 210.0));
+    bez.line_to((110.0, 158.0));
+    bez.line_to((42.0, 210.0));
+    bez.line_to((68.0, 124.0));
+    bez.line_to((0.0, 76.0));
+    bez.line_to((84.0, 76.0));
+    bez.close_path();
+    bez
+}
@@ -0,0 +1,174 @@
+//! A panel showing the unicode properties of the selected glyph's
+//! codepoints, and suggesting related glyphs to add.
+//!
+//! This is intended to be shown as a modal panel.
+
+use std::collections::HashSet;
+
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, Scope, Scroll,
+};
+use druid::{Color, Lens, WidgetExt};
+
+use crate::charset;
+use crate::data::Workspace;
+use crate::theme;
+use crate::unicode_info::{self, CharInfo};
+use crate::widgets::ModalHost;
+
+#[derive(Clone, Data, Lens)]
+struct InspectorState {
+    workspace: Workspace,
+}
+
+impl InspectorState {
+    fn codepoints(&self) -> Vec<char> {
+        self.workspace
+            .selected
+            .as_ref()
+            .and_then(|name| self.workspace.font.ufo.get_glyph(name))
+            .and_then(|glyph| glyph.codepoints.clone())
+            .unwrap_or_default()
+    }
+
+    fn description(&self) -> String {
+        let codepoints = self.codepoints();
+        if codepoints.is_empty() {
+            return "The selected glyph has no codepoints.".into();
+        }
+        codepoints
+            .iter()
+            .map(|chr| describe_char(*chr))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Composed forms of the glyph's codepoints that are not in the font.
+    fn missing_related(&self) -> Vec<char> {
+        let in_font = self
+            .workspace
+            .font
+            .ufo
+            .get_default_layer()
+            .map(|layer| {
+                layer
+                    .iter_contents()
+                    .flat_map(|glyph| glyph.codepoints.clone().unwrap_or_default())
+                    .collect::<HashSet<_>>()
+            })
+            .unwrap_or_default();
+        let mut missing = Vec::new();
+        for chr in self.codepoints() {
+            for related in unicode_info::composed_forms(chr) {
+                if !in_font.contains(&related) && !missing.contains(&related) {
+                    missing.push(related);
+                }
+            }
+        }
+        missing
+    }
+
+    fn suggestion(&self) -> String {
+        let missing = self.missing_related();
+        if missing.is_empty() {
+            return "No related glyphs are missing.".into();
+        }
+        let chars = missing
+            .iter()
+            .map(|chr| charset::display_char(*chr))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("Related glyphs not in the font: {}", chars)
+    }
+}
+
+fn describe_char(chr: char) -> String {
+    let heading = format!("U+{:04X} {}", chr as u32, charset::display_char(chr));
+    let block = unicode_info::block_for_char(chr).unwrap_or("Unknown");
+    let info = match unicode_info::char_info(chr) {
+        Some(info) => info,
+        None => return format!("{}\nBlock: {}\nNo other data.", heading, block),
+    };
+    format!(
+        "{} {}\nBlock: {}\nCategory: {} ({})\nBidi class: {} ({})\nDecomposition: {}",
+        heading,
+        info.name,
+        block,
+        info.category_name(),
+        info.category,
+        info.bidi_class_name(),
+        info.bidi_class,
+        describe_decomposition(&info),
+    )
+}
+
+fn describe_decomposition(info: &CharInfo) -> String {
+    let chars = info.decomposition();
+    if chars.is_empty() {
+        return "none".into();
+    }
+    let chars = chars
+        .iter()
+        .map(|chr| format!("U+{:04X} {}", *chr as u32, charset::display_char(*chr)))
+        .collect::<Vec<_>>()
+        .join(" + ");
+    match info.decomposition_tag() {
+        Some(tag) => format!("{} {}", tag, chars),
+        None => chars,
+    }
+}
+
+pub fn unicode_inspector() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &InspectorState, _| match &data.workspace.selected {
+        Some(name) => format!("Unicode Info for '{}'", name),
+        None => "No glyph selected".into(),
+    })
+    .with_text_size(16.0);
+
+    let description = Label::dynamic(|data: &InspectorState, _| data.description())
+        .with_line_break_mode(LineBreaking::WordWrap);
+
+    let suggestion = Label::dynamic(|data: &InspectorState, _| data.suggestion())
+        .with_line_break_mode(LineBreaking::WordWrap)
+        .with_font(theme::UI_DETAIL_FONT)
+        .with_text_color(theme::SECONDARY_TEXT_COLOR);
+
+    let buttons = Flex::row()
+        .with_child(Button::new("Add Related Glyphs").on_click(
+            |_, data: &mut InspectorState, _| {
+                let names = data
+                    .missing_related()
+                    .into_iter()
+                    .map(charset::name_for_char)
+                    .collect::<Vec<_>>();
+                let count = data.workspace.add_glyphs(&names);
+                log::info!("added {} glyphs", count);
+            },
+        ))
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Close").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    let panel = Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_flex_child(Scroll::new(description).vertical(), 1.0)
+        .with_default_spacer()
+        .with_child(suggestion)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_size(460., 400.)
+        .padding(16.0)
+        .background(Color::WHITE);
+
+    Scope::from_lens(
+        |workspace: Workspace| InspectorState { workspace },
+        InspectorState::workspace,
+        panel,
+    )
+}
@@ -0,0 +1,66 @@
+//! A panel asking whether to save the font before closing it.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::widget::prelude::*;
+use druid::widget::{Button, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment};
+use druid::{commands, Color, FileDialogOptions, WidgetExt};
+
+use crate::consts;
+use crate::data::Workspace;
+use crate::menus::UFO_FILE_TYPE;
+use crate::theme;
+use crate::widgets::ModalHost;
+
+pub fn unsaved_changes_panel() -> impl Widget<Workspace> {
+    let title = Label::dynamic(|data: &Workspace, _| {
+        format!(
+            "Save changes to '{} {}' before closing?",
+            data.info.family_name, data.info.style_name
+        )
+    })
+    .with_text_size(16.0)
+    .with_line_break_mode(LineBreaking::WordWrap);
+    let explanation = Label::new("Your changes will be lost if you don't save them.")
+        .with_text_color(theme::SECONDARY_TEXT_COLOR)
+        .with_line_break_mode(LineBreaking::WordWrap);
+
+    let buttons = Flex::row()
+        .with_child(Button::new("Don't Save").on_click(|ctx, _, _| {
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+            ctx.submit_command(consts::cmd::CLOSE_WITHOUT_SAVING);
+        }))
+        .with_flex_spacer(1.0)
+        .with_child(Button::new("Cancel").on_click(|ctx, _, _| {
+            ctx.submit_command(ModalHost::DISMISS_MODAL);
+            ctx.submit_command(consts::cmd::CANCEL_CLOSE);
+        }))
+        .with_spacer(4.0)
+        .with_child(
+            Button::new("Save").on_click(|ctx, data: &mut Workspace, _| {
+                ctx.submit_command(ModalHost::DISMISS_MODAL);
+                ctx.submit_command(consts::cmd::CLOSE_AFTER_SAVING);
+                if data.font.path.is_some() {
+                    ctx.submit_command(commands::SAVE_FILE);
+                } else {
+                    ctx.submit_command(
+                        commands::SHOW_SAVE_PANEL
+                            .with(FileDialogOptions::new().allowed_types(vec![UFO_FILE_TYPE])),
+                    );
+                }
+            }),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
@@ -0,0 +1,61 @@
+//! A panel for adjusting how the editor zooms.
+//!
+//! This is intended to be shown as a modal panel.
+
+use druid::text::format::ParseFormatter;
+use druid::widget::prelude::*;
+use druid::widget::{
+    Button, Checkbox, CrossAxisAlignment, Flex, Label, LineBreaking, MainAxisAlignment, TextBox,
+};
+use druid::{Color, LensExt, WidgetExt};
+
+use crate::data::{Workspace, ZoomOptions};
+use crate::theme;
+use crate::widgets::ModalHost;
+
+pub fn zoom_options_panel() -> impl Widget<Workspace> {
+    let title = Label::new("Zoom").with_text_size(16.0);
+    let explanation =
+        Label::new("Sensitivity scales how far each scroll or pinch zooms; 1.0 is the default.")
+            .with_text_color(theme::SECONDARY_TEXT_COLOR)
+            .with_line_break_mode(LineBreaking::WordWrap);
+
+    let sensitivity = Flex::row()
+        .with_child(Label::new("sensitivity:"))
+        .with_spacer(4.0)
+        .with_child(
+            TextBox::new()
+                .with_formatter(ParseFormatter::new())
+                .fix_width(60.0)
+                .lens(Workspace::zoom_options.then(ZoomOptions::sensitivity)),
+        );
+
+    let animate = Checkbox::new("Animate zooming in and out with the keyboard")
+        .lens(Workspace::zoom_options.then(ZoomOptions::animate));
+
+    let buttons = Flex::row()
+        .with_child(Button::new("Reset").on_click(|_, data: &mut Workspace, _| {
+            data.zoom_options = ZoomOptions::default();
+        }))
+        .with_flex_spacer(1.0)
+        .with_child(
+            Button::new("Done").on_click(|ctx, _, _| ctx.submit_command(ModalHost::DISMISS_MODAL)),
+        )
+        .main_axis_alignment(MainAxisAlignment::End)
+        .expand_width();
+
+    Flex::column()
+        .with_child(title)
+        .with_default_spacer()
+        .with_child(explanation)
+        .with_default_spacer()
+        .with_child(sensitivity)
+        .with_default_spacer()
+        .with_child(animate)
+        .with_default_spacer()
+        .with_child(buttons)
+        .cross_axis_alignment(CrossAxisAlignment::Start)
+        .fix_width(360.)
+        .padding(16.0)
+        .background(Color::WHITE)
+}
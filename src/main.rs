@@ -1,10 +1,13 @@
 //! A font editor.
 
+use std::path::{Path, PathBuf};
+
 use druid::kurbo::Line;
 use druid::widget::{Button, Flex, Label, Painter, Scroll, WidgetExt};
-use druid::{AppLauncher, Env, LocalizedString, RenderContext, Size, Widget, WindowDesc};
+use druid::{AppLauncher, Env, RenderContext, Size, Widget, WindowDesc};
 
 use runebender_lib::data::{AppState, Workspace};
+use runebender_lib::settings::Settings;
 use runebender_lib::widgets::{self, GlyphGrid, ModalHost, Sidebar};
 use runebender_lib::{menus, theme, Delegate};
 
@@ -12,7 +15,13 @@ fn main() {
     let state = get_initial_state();
 
     let main_window = WindowDesc::new(make_ui())
-        .title(LocalizedString::new("Runebender"))
+        .title(|data: &AppState, _: &Env| {
+            if data.workspace.is_dirty() {
+                "• Runebender".to_string()
+            } else {
+                "Runebender".to_string()
+            }
+        })
         .menu(menus::make_menu)
         .window_size(Size::new(900.0, 800.0));
 
@@ -50,6 +59,8 @@ fn make_ui() -> impl Widget<AppState> {
                 .with_child(label)
                 .with_spacer(8.0)
                 .with_child(button)
+                .with_spacer(8.0)
+                .with_child(widgets::save_status())
                 .padding(5.0)
                 .center()
                 .fix_height(40.)
@@ -67,11 +78,16 @@ fn make_ui() -> impl Widget<AppState> {
 }
 
 /// If there was an argument passed at the command line, try to open it as a .ufo
-/// file, otherwise return blank state.
+/// file or .designspace, otherwise reopen the last font if the settings say to,
+/// or else return blank state.
 fn get_initial_state() -> AppState {
+    let mut settings = Settings::load();
     let (font_file, path) = if let Some(arg) = std::env::args().nth(1) {
-        match norad::Ufo::load(&arg) {
-            Ok(ufo) => (ufo, Some(std::path::PathBuf::from(arg))),
+        // a .designspace opens its default source
+        let path = runebender_lib::font_path_for_file(Path::new(&arg))
+            .unwrap_or_else(|| PathBuf::from(&arg));
+        match norad::Ufo::load(&path) {
+            Ok(ufo) => (ufo, Some(path)),
             Err(e) => {
                 eprintln!(
                     "Failed to load first arg '{}' as ufo file.\nError:'{}'",
@@ -80,11 +96,28 @@ fn get_initial_state() -> AppState {
                 std::process::exit(1);
             }
         }
+    } else if let Some(last) = settings.font_to_reopen() {
+        match norad::Ufo::load(last) {
+            Ok(ufo) => (ufo, Some(last.to_owned())),
+            Err(e) => {
+                eprintln!("Failed to reopen '{}'.\nError:'{}'", last.display(), e);
+                (runebender_lib::create_blank_font(), None)
+            }
+        }
     } else {
         (runebender_lib::create_blank_font(), None)
     };
 
+    if let Some(path) = &path {
+        settings.add_recent_file(path);
+        settings.save();
+    }
     let mut workspace = Workspace::default();
     workspace.set_file(font_file, path);
-    AppState { workspace }
+    workspace.tool_options = settings.tool_options;
+    workspace.flush_on_close = settings.flush_on_close;
+    AppState {
+        workspace,
+        settings,
+    }
 }